@@ -1,6 +1,8 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use anyhow::{Result, anyhow};
+use super::{answered, for_each_line, TokenSender};
+use crate::error::{EscriturasError, Result};
+use crate::provider::Provider;
 
 #[derive(Serialize)]
 struct ClaudeMessage {
@@ -13,6 +15,7 @@ struct ClaudeRequest {
     model: String,
     max_tokens: u32,
     messages: Vec<ClaudeMessage>,
+    stream: bool,
 }
 
 #[derive(Deserialize)]
@@ -25,10 +28,30 @@ struct ClaudeResponse {
     content: Vec<ClaudeContent>,
 }
 
+/// A server-sent event from the streaming Messages API
+#[derive(Deserialize)]
+struct ClaudeStreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    delta: Option<ClaudeStreamDelta>,
+    error: Option<ClaudeStreamError>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeStreamDelta {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeStreamError {
+    message: String,
+}
+
 #[derive(Clone)]
 pub struct ClaudeClient {
     client: Client,
     api_key: String,
+    base_url: String,
 }
 
 impl ClaudeClient {
@@ -36,10 +59,59 @@ impl ClaudeClient {
         Self {
             client: Client::new(),
             api_key: api_key.to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
         }
     }
 
+    /// Send requests to another API root, such as a proxy
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
     pub async fn query(&self, model: &str, prompt: &str) -> Result<String> {
+        let response = self.send(model, prompt, false).await?;
+        let claude_response: ClaudeResponse = response.json().await?;
+        Ok(claude_response.content.first()
+            .map(|c| c.text.clone())
+            .unwrap_or_default())
+    }
+
+    /// Query with streaming, sending text deltas to `tokens` as they arrive.
+    /// Returns the full response once the stream completes.
+    pub async fn query_stream(&self, model: &str, prompt: &str, tokens: TokenSender) -> Result<String> {
+        let response = self.send(model, prompt, true).await?;
+        let mut full = String::new();
+
+        for_each_line(response, |line| {
+            let Some(data) = line.strip_prefix("data:") else {
+                return Ok(()); // "event:" lines carry nothing we need
+            };
+            let event: ClaudeStreamEvent = serde_json::from_str(data.trim())?;
+            match event.event_type.as_str() {
+                "content_block_delta" => {
+                    if let Some(text) = event.delta.and_then(|d| d.text) {
+                        full.push_str(&text);
+                        let _ = tokens.send(text);
+                    }
+                }
+                "error" => {
+                    let message = event.error.map(|e| e.message).unwrap_or_default();
+                    return Err(EscriturasError::ProviderRejected {
+                        provider: Provider::Claude,
+                        message: format!("Claude API error: {}", message),
+                    });
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+        .await?;
+
+        Ok(answered(Provider::Claude, model, full))
+    }
+
+    async fn send(&self, model: &str, prompt: &str, stream: bool) -> Result<reqwest::Response> {
         let request = ClaudeRequest {
             model: model.to_string(),
             max_tokens: 4096,
@@ -47,27 +119,45 @@ impl ClaudeClient {
                 role: "user".to_string(),
                 content: prompt.to_string(),
             }],
+            stream,
         };
+        tracing::debug!(model, stream, prompt_chars = prompt.len(), "Claude request");
 
         let response = self.client
-            .post("https://api.anthropic.com/v1/messages")
+            .post(format!("{}/messages", self.base_url))
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", "2023-06-01")
             .header("content-type", "application/json")
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::Claude, e))?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Claude API error {}: {}", status, text));
+            tracing::warn!("Claude API error {}: {}", status, text);
+            return Err(EscriturasError::from_status(Provider::Claude, status, format!("Claude API error {}: {}", status, text)));
         }
 
-        let claude_response: ClaudeResponse = response.json().await?;
-        Ok(claude_response.content.first()
-            .map(|c| c.text.clone())
-            .unwrap_or_default())
+        Ok(response)
+    }
+
+    /// Whether the API accepts the key, by listing models (which costs nothing)
+    pub async fn check_key(&self) -> Result<()> {
+        let response = self.client
+            .get(format!("{}/models", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .send()
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::Claude, e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(EscriturasError::from_status(Provider::Claude, status, format!("Claude API error {}: {}", status, text)));
+        }
+        Ok(())
     }
 
     pub fn list_models() -> Vec<String> {
@@ -1,7 +1,87 @@
+//! AI providers
+//!
+//! Prompts and token estimates are always here; the clients for Claude,
+//! OpenAI, and Ollama need the `ai` feature.
+
+#[cfg(feature = "ai")]
+use crate::config::Config;
+#[cfg(feature = "ai")]
+use crate::error::{EscriturasError, Result};
+#[cfg(feature = "ai")]
+use crate::provider::Provider;
+
+#[cfg(feature = "ai")]
 pub mod claude;
+#[cfg(feature = "ai")]
 pub mod ollama;
+#[cfg(feature = "ai")]
 pub mod openai;
+pub mod prompt;
+pub mod usage;
 
+#[cfg(feature = "ai")]
 pub use claude::ClaudeClient;
+#[cfg(feature = "ai")]
 pub use ollama::OllamaClient;
+#[cfg(feature = "ai")]
 pub use openai::OpenAIClient;
+pub use usage::{context_window, estimate_tokens};
+
+/// Receives response text as it is generated by a streaming query
+#[cfg(feature = "ai")]
+pub type TokenSender = tokio::sync::mpsc::UnboundedSender<String>;
+
+/// Ask `provider` with the client and key from `config`, sending the answer
+/// to `tokens` as it's generated and returning the whole of it
+#[cfg(feature = "ai")]
+pub async fn ask(config: &Config, provider: Provider, model: &str, prompt: &str, tokens: TokenSender) -> Result<String> {
+    let key = || {
+        config.api_key(provider).ok_or_else(|| EscriturasError::ProviderAuth {
+            provider,
+            message: format!("No {} API key", provider.display_name()),
+        })
+    };
+    match provider {
+        Provider::Ollama => config.ollama_client().query_stream(model, prompt, tokens).await,
+        Provider::Claude => config.claude_client(&key()?).query_stream(model, prompt, tokens).await,
+        Provider::OpenAI => config.openai_client(&key()?).query_stream(model, prompt, tokens).await,
+    }
+}
+
+/// Announce a streamed answer as `Event::AiQueryCompleted`, and pass it on
+#[cfg(feature = "ai")]
+pub(crate) fn answered(provider: Provider, model: &str, answer: String) -> String {
+    crate::events::emit(crate::events::Event::AiQueryCompleted {
+        provider: provider.as_str().to_string(),
+        model: model.to_string(),
+        answer_chars: answer.chars().count(),
+    });
+    answer
+}
+
+/// Read a streaming HTTP body line by line (NDJSON or server-sent events).
+/// Buffers raw bytes so multi-byte characters split across chunks stay intact.
+#[cfg(feature = "ai")]
+pub(crate) async fn for_each_line(
+    mut response: reqwest::Response,
+    mut on_line: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut buffer: Vec<u8> = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        buffer.extend_from_slice(&chunk);
+        while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if !line.is_empty() {
+                on_line(line)?;
+            }
+        }
+    }
+
+    let rest = String::from_utf8_lossy(&buffer);
+    if !rest.trim().is_empty() {
+        on_line(rest.trim())?;
+    }
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use anyhow::{Result, anyhow};
+use super::{answered, for_each_line, TokenSender};
+use crate::error::{EscriturasError, Result};
+use crate::provider::Provider;
 
 
 #[derive(Serialize)]
@@ -19,6 +21,14 @@ struct OllamaResponse {
     done: bool,
 }
 
+/// One line of a streaming /api/generate response
+#[derive(Deserialize)]
+struct OllamaStreamChunk {
+    #[serde(default)]
+    response: String,
+    error: Option<String>,
+}
+
 #[derive(Deserialize)]
 #[allow(dead_code)]
 struct OllamaModel {
@@ -54,24 +64,65 @@ impl OllamaClient {
             stream: false,
             format: None,
         };
+        tracing::debug!(model, stream = false, prompt_chars = prompt.len(), url = %url, "Ollama request");
         
         let response = self
             .client
             .post(&url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::Ollama, e))?;
         
         if !response.status().is_success() {
-            return Err(anyhow!(
-                "Ollama request failed with status: {}. Make sure Ollama is running with: ollama serve", 
-                response.status()
-            ));
+            return Err(failed(response, "Ollama request failed").await);
         }
         
         let ollama_response: OllamaResponse = response.json().await?;
         Ok(ollama_response.response)
     }
+
+    /// Query with streaming, sending each generated chunk to `tokens` as it arrives.
+    /// Returns the full response once generation is done.
+    pub async fn query_stream(&self, model: &str, prompt: &str, tokens: TokenSender) -> Result<String> {
+        let url = format!("{}/api/generate", self.base_url);
+
+        let request = OllamaRequest {
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            stream: true,
+            format: None,
+        };
+        tracing::debug!(model, stream = true, prompt_chars = prompt.len(), url = %url, "Ollama request");
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::Ollama, e))?;
+
+        if !response.status().is_success() {
+            return Err(failed(response, "Ollama request failed").await);
+        }
+
+        let mut full = String::new();
+        for_each_line(response, |line| {
+            let chunk: OllamaStreamChunk = serde_json::from_str(line)?;
+            if let Some(error) = chunk.error {
+                return Err(EscriturasError::ProviderRejected { provider: Provider::Ollama, message: format!("Ollama error: {}", error) });
+            }
+            if !chunk.response.is_empty() {
+                full.push_str(&chunk.response);
+                let _ = tokens.send(chunk.response);
+            }
+            Ok(())
+        })
+        .await?;
+
+        Ok(answered(Provider::Ollama, model, full))
+    }
     
     #[allow(dead_code)]
     pub async fn query_json(&self, model: &str, prompt: &str) -> Result<String> {
@@ -89,13 +140,11 @@ impl OllamaClient {
             .post(&url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::Ollama, e))?;
         
         if !response.status().is_success() {
-            return Err(anyhow!(
-                "Ollama JSON request failed with status: {}. Make sure Ollama is running with: ollama serve", 
-                response.status()
-            ));
+            return Err(failed(response, "Ollama JSON request failed").await);
         }
         
         let ollama_response: OllamaResponse = response.json().await?;
@@ -105,10 +154,10 @@ impl OllamaClient {
     pub async fn list_models(&self) -> Result<Vec<String>> {
         let url = format!("{}/api/tags", self.base_url);
         
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send().await.map_err(|e| EscriturasError::unreachable(Provider::Ollama, e))?;
         
         if !response.status().is_success() {
-            return Err(anyhow!("Failed to list models: {}", response.status()));
+            return Err(failed(response, "Failed to list models").await);
         }
         
         let models_response: OllamaModelsResponse = response.json().await?;
@@ -126,4 +175,11 @@ impl OllamaClient {
         let models = self.list_models().await?;
         Ok(models.iter().any(|m| m == name))
     }
-}
\ No newline at end of file
+}
+
+/// The error for a response Ollama didn't answer with success, with what it said
+async fn failed(response: reqwest::Response, what: &str) -> EscriturasError {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    EscriturasError::from_status(Provider::Ollama, status, format!("{} with status {}: {}", what, status, text.trim()))
+}
@@ -1,6 +1,8 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use anyhow::{Result, anyhow};
+use super::{answered, for_each_line, TokenSender};
+use crate::error::{EscriturasError, Result};
+use crate::provider::Provider;
 
 #[derive(Serialize)]
 struct OpenAIMessage {
@@ -12,6 +14,7 @@ struct OpenAIMessage {
 struct OpenAIRequest {
     model: String,
     messages: Vec<OpenAIMessage>,
+    stream: bool,
 }
 
 #[derive(Deserialize)]
@@ -29,10 +32,27 @@ struct OpenAIResponse {
     choices: Vec<OpenAIChoice>,
 }
 
+/// A chunk from the streaming chat completions API
+#[derive(Deserialize)]
+struct OpenAIStreamChunk {
+    choices: Vec<OpenAIStreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIStreamChoice {
+    delta: OpenAIStreamDelta,
+}
+
+#[derive(Deserialize)]
+struct OpenAIStreamDelta {
+    content: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct OpenAIClient {
     client: Client,
     api_key: String,
+    base_url: String,
 }
 
 impl OpenAIClient {
@@ -40,36 +60,93 @@ impl OpenAIClient {
         Self {
             client: Client::new(),
             api_key: api_key.to_string(),
+            base_url: "https://api.openai.com/v1".to_string(),
         }
     }
 
+    /// Send requests to another OpenAI-compatible API instead
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
     pub async fn query(&self, model: &str, prompt: &str) -> Result<String> {
+        let response = self.send(model, prompt, false).await?;
+        let openai_response: OpenAIResponse = response.json().await?;
+        Ok(openai_response.choices.first()
+            .map(|c| c.message.content.clone())
+            .unwrap_or_default())
+    }
+
+    /// Query with streaming, sending content deltas to `tokens` as they arrive.
+    /// Returns the full response once the stream completes.
+    pub async fn query_stream(&self, model: &str, prompt: &str, tokens: TokenSender) -> Result<String> {
+        let response = self.send(model, prompt, true).await?;
+        let mut full = String::new();
+
+        for_each_line(response, |line| {
+            let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                return Ok(());
+            };
+            if data == "[DONE]" {
+                return Ok(());
+            }
+            let chunk: OpenAIStreamChunk = serde_json::from_str(data)?;
+            if let Some(text) = chunk.choices.into_iter().next().and_then(|c| c.delta.content) {
+                full.push_str(&text);
+                let _ = tokens.send(text);
+            }
+            Ok(())
+        })
+        .await?;
+
+        Ok(answered(Provider::OpenAI, model, full))
+    }
+
+    async fn send(&self, model: &str, prompt: &str, stream: bool) -> Result<reqwest::Response> {
         let request = OpenAIRequest {
             model: model.to_string(),
             messages: vec![OpenAIMessage {
                 role: "user".to_string(),
                 content: prompt.to_string(),
             }],
+            stream,
         };
+        tracing::debug!(model, stream, prompt_chars = prompt.len(), base_url = %self.base_url, "OpenAI request");
 
         let response = self.client
-            .post("https://api.openai.com/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::OpenAI, e))?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("OpenAI API error {}: {}", status, text));
+            tracing::warn!("OpenAI API error {}: {}", status, text);
+            return Err(EscriturasError::from_status(Provider::OpenAI, status, format!("OpenAI API error {}: {}", status, text)));
         }
 
-        let openai_response: OpenAIResponse = response.json().await?;
-        Ok(openai_response.choices.first()
-            .map(|c| c.message.content.clone())
-            .unwrap_or_default())
+        Ok(response)
+    }
+
+    /// Whether the API accepts the key, by listing models (which costs nothing)
+    pub async fn check_key(&self) -> Result<()> {
+        let response = self.client
+            .get(format!("{}/models", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .map_err(|e| EscriturasError::unreachable(Provider::OpenAI, e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(EscriturasError::from_status(Provider::OpenAI, status, format!("OpenAI API error {}: {}", status, text)));
+        }
+        Ok(())
     }
 
     pub fn list_models() -> Vec<String> {
@@ -0,0 +1,65 @@
+//! The parts of a study prompt, shared by every place that asks the AI
+//! (the TUI chat and the `query` command) so they word things the same way
+
+use crate::scripture::Scripture;
+use crate::state::{ChatMessage, ChatRole};
+
+/// Opening instructions of every prompt
+pub const STUDY_INSTRUCTIONS: &str = "You are helping with LDS (Latter-day Saint) scripture study. \
+When answering, prioritize the Book of Mormon, Doctrine and Covenants, \
+and Pearl of Great Price alongside the Bible. Include specific verse citations.\n\n";
+
+/// Saved verses sent with each question; the rest are left out of the prompt
+pub const PROMPT_CONTEXT_VERSES: usize = 20;
+
+/// Verses to answer from, one per line; empty when there are none
+pub fn context_block(verses: &[Scripture]) -> String {
+    if verses.is_empty() {
+        return String::new();
+    }
+    let mut block = String::from("Scripture Context:\n");
+    for verse in verses {
+        block.push_str(&format!("{}: {}\n", verse.verse_title, verse.scripture_text));
+    }
+    block.push('\n');
+    block
+}
+
+/// The earlier turns of a conversation; empty when there are none
+pub fn history_block(history: &[ChatMessage]) -> String {
+    if history.is_empty() {
+        return String::new();
+    }
+    let mut block = String::from("Conversation so far:\n");
+    for msg in history {
+        match msg.role {
+            ChatRole::User => block.push_str(&format!("User: {}\n", msg.content)),
+            ChatRole::Assistant => block.push_str(&format!("Assistant: {}\n", msg.content)),
+        }
+    }
+    block.push('\n');
+    block
+}
+
+/// The question being asked, last in the prompt
+pub fn question_block(question: &str) -> String {
+    format!(
+        "Current question: {}\n\nPlease provide specific scripture references in your answer.",
+        question
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_are_empty_without_content() {
+        assert_eq!(context_block(&[]), "");
+        assert_eq!(history_block(&[]), "");
+
+        let history = [ChatMessage { role: ChatRole::User, content: "What is faith?".to_string() }];
+        assert_eq!(history_block(&history), "Conversation so far:\nUser: What is faith?\n\n");
+        assert!(question_block("Why?").starts_with("Current question: Why?\n\n"));
+    }
+}
@@ -0,0 +1,41 @@
+//! Prompt size estimates
+//!
+//! The providers don't share a tokenizer, and the prompt needs sizing before
+//! anything is sent, so counts are a character-based estimate: close enough to
+//! warn before a prompt outgrows the model's context window.
+
+use crate::provider::Provider;
+
+/// Tokens Ollama keeps by default (`num_ctx`); longer prompts are cut from the
+/// front whatever the model itself supports
+pub const OLLAMA_CONTEXT: usize = 4096;
+
+/// Rough token count, at about four characters per token of English
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Context window of a model in tokens
+pub fn context_window(provider: Provider, model: &str) -> usize {
+    match provider {
+        Provider::Ollama => OLLAMA_CONTEXT,
+        Provider::Claude => 200_000,
+        Provider::OpenAI if model.starts_with("gpt-3.5") => 16_385,
+        Provider::OpenAI if model == "gpt-4" => 8_192,
+        Provider::OpenAI => 128_000,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimates() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("And it came to pass"), 5);
+        assert_eq!(context_window(Provider::OpenAI, "gpt-3.5-turbo"), 16_385);
+        assert_eq!(context_window(Provider::OpenAI, "gpt-4o"), 128_000);
+        assert_eq!(context_window(Provider::Ollama, "llama3.2"), OLLAMA_CONTEXT);
+    }
+}
@@ -0,0 +1,307 @@
+//! Backups of everything made while studying
+//!
+//! A backup is one JSON file holding the saved scriptures and their tags,
+//! highlights, conversations, memorization attempts, Come, Follow Me progress,
+//! and journal entries (by file name), so it can be read or diffed as text.
+//!
+//! Importing merges a backup into what's here rather than replacing it, so
+//! moving between two machines that were both used loses nothing: verses,
+//! tags, attempts, readings, and conversations missing here are added, and
+//! what's already here is kept where the two differ (a highlight's color).
+//! A journal entry whose file name is taken by a different entry is written
+//! under the next free time instead.
+
+use chrono::{Duration, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::come_follow_me::CfmProgress;
+use crate::error::{EscriturasError, Result};
+use crate::journal::{Journal, FILE_DATE_FORMAT};
+use crate::memorize::MemorizeHistory;
+use crate::state::{ConversationHistory, SavedScriptures};
+use crate::store::Store;
+
+/// Bumped when a change to the format would confuse older versions
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    /// When it was made, as RFC 3339
+    pub created: String,
+    #[serde(default)]
+    pub saved: SavedScriptures,
+    /// Highlight colors by verse title
+    #[serde(default)]
+    pub highlights: BTreeMap<String, String>,
+    #[serde(default)]
+    pub conversations: ConversationHistory,
+    #[serde(default)]
+    pub memorize: MemorizeHistory,
+    #[serde(default)]
+    pub progress: CfmProgress,
+    /// Journal entries' contents by file name (`2026-10-15-143205.md`)
+    #[serde(default)]
+    pub journal: BTreeMap<String, String>,
+}
+
+/// What an import added
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MergeSummary {
+    pub verses: usize,
+    pub tags: usize,
+    pub highlights: usize,
+    pub conversations: usize,
+    pub attempts: usize,
+    pub readings: usize,
+    pub notes: usize,
+}
+
+impl MergeSummary {
+    /// "3 saved verses, 1 note", or "nothing new"
+    pub fn describe(&self) -> String {
+        let counts = [
+            (self.verses, "saved verse", "saved verses"),
+            (self.tags, "tag", "tags"),
+            (self.highlights, "highlight", "highlights"),
+            (self.conversations, "conversation", "conversations"),
+            (self.attempts, "memorization attempt", "memorization attempts"),
+            (self.readings, "finished reading", "finished readings"),
+            (self.notes, "journal entry", "journal entries"),
+        ];
+        let parts: Vec<String> = counts
+            .iter()
+            .filter(|(n, ..)| *n > 0)
+            .map(|(n, one, many)| format!("{} {}", n, if *n == 1 { one } else { many }))
+            .collect();
+        if parts.is_empty() {
+            "nothing new".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+impl Backup {
+    /// Everything in the store and the journal
+    pub fn collect(store: &Store, journal: &Journal) -> Result<Self> {
+        let mut notes = BTreeMap::new();
+        for entry in &journal.entries {
+            if let Some(name) = entry.path.file_name().and_then(|n| n.to_str()) {
+                notes.insert(name.to_string(), entry.content.clone());
+            }
+        }
+        Ok(Self {
+            version: FORMAT_VERSION,
+            created: Local::now().to_rfc3339(),
+            saved: store.saved()?,
+            highlights: store.highlights()?,
+            conversations: store.conversations()?,
+            memorize: store.memorize_history()?,
+            progress: store.cfm_progress()?,
+            journal: notes,
+        })
+    }
+
+    /// `escrituras-backup-2026-10-15.json`, for today
+    pub fn file_name() -> String {
+        format!("escrituras-backup-{}.json", Local::now().format("%Y-%m-%d"))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| EscriturasError::io(format!("Could not read {}", path.display()), e))?;
+        let backup: Self =
+            serde_json::from_str(&content).map_err(|e| EscriturasError::ParseError(format!("{} isn't an escrituras backup: {}", path.display(), e)))?;
+        if backup.version > FORMAT_VERSION {
+            return Err(EscriturasError::NewerVersion(format!("{} is from a newer version of escrituras", path.display())));
+        }
+        Ok(backup)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Merge this backup into the store and journal
+    pub fn import(&self, store: &mut Store, journal: &Journal) -> Result<MergeSummary> {
+        let loaded = Self::collect(store, journal)?;
+        let mut here = loaded.clone();
+        let highlighted: HashSet<String> = here.highlights.keys().cloned().collect();
+        let summary = self.merge_into(&mut here);
+
+        store.save_saved(&loaded.saved, &here.saved)?;
+        store.save_conversations(&loaded.conversations, &here.conversations)?;
+        store.save_memorize_history(&loaded.memorize, &here.memorize)?;
+        store.save_cfm_progress(&loaded.progress, &here.progress)?;
+        for (verse, color) in here.highlights.iter().filter(|(verse, _)| !highlighted.contains(*verse)) {
+            store.set_highlight(verse, Some(color))?;
+        }
+        if summary.notes > 0 {
+            fs::create_dir_all(&journal.dir)?;
+        }
+        for (name, content) in &here.journal {
+            let path = journal.dir.join(name);
+            if !path.exists() {
+                fs::write(&path, content)?;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Add what's in this backup and not in `here` to `here`
+    fn merge_into(&self, here: &mut Self) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+
+        let verses = self.saved.verses.iter().cloned();
+        summary.verses = here.saved.add(verses);
+        for (verse, tags) in &self.saved.tags {
+            let merged = here.saved.tags.entry(verse.clone()).or_default();
+            for tag in tags {
+                if !merged.contains(tag) {
+                    merged.push(tag.clone());
+                    summary.tags += 1;
+                }
+            }
+        }
+
+        for (verse, color) in &self.highlights {
+            if !here.highlights.contains_key(verse) {
+                here.highlights.insert(verse.clone(), color.clone());
+                summary.highlights += 1;
+            }
+        }
+
+        let known: HashSet<(u64, String)> =
+            here.conversations.conversations.iter().map(|c| (c.timestamp, c.title().to_string())).collect();
+        for conversation in &self.conversations.conversations {
+            if !known.contains(&(conversation.timestamp, conversation.title().to_string())) {
+                here.conversations.conversations.push(conversation.clone());
+                summary.conversations += 1;
+            }
+        }
+        here.conversations.conversations.sort_by_key(|c| c.timestamp);
+        let excess = here.conversations.conversations.len().saturating_sub(ConversationHistory::MAX_CONVERSATIONS);
+        here.conversations.conversations.drain(..excess);
+
+        for (verse, attempts) in &self.memorize.verses {
+            let merged = here.memorize.verses.entry(verse.clone()).or_default();
+            for attempt in attempts {
+                if !merged.contains(attempt) {
+                    merged.push(*attempt);
+                    summary.attempts += 1;
+                }
+            }
+            merged.sort_by_key(|a| a.timestamp);
+        }
+
+        for (week, labels) in &self.progress.weeks {
+            let merged = here.progress.weeks.entry(*week).or_default();
+            for label in labels {
+                summary.readings += usize::from(merged.insert(label.clone()));
+            }
+        }
+
+        for (name, content) in &self.journal {
+            if here.journal.values().any(|c| c == content) {
+                continue;
+            }
+            let name = free_name(name, &here.journal);
+            here.journal.insert(name, content.clone());
+            summary.notes += 1;
+        }
+
+        summary
+    }
+}
+
+/// `name`, or when it's taken, the same entry started a second later (and so
+/// on), keeping the name one the journal can read its date from
+pub(crate) fn free_name(name: &str, taken: &BTreeMap<String, String>) -> String {
+    let Some(mut time) = name.strip_suffix(".md").and_then(|s| NaiveDateTime::parse_from_str(s, FILE_DATE_FORMAT).ok())
+    else {
+        return name.to_string();
+    };
+    let mut name = name.to_string();
+    while taken.contains_key(&name) {
+        time += Duration::seconds(1);
+        name = format!("{}.md", time.format(FILE_DATE_FORMAT));
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripture::Scripture;
+    use chrono::NaiveDate;
+
+    fn verse(number: i32) -> Scripture {
+        Scripture::test("Moroni", 10, number).short("Moro.").text("And when ye shall receive these things")
+    }
+
+    #[test]
+    fn test_merge_adds_what_is_missing_and_keeps_what_is_here() {
+        let mut theirs = Backup::default();
+        theirs.saved.add([verse(4), verse(5)]);
+        theirs.saved.set_tags("Moroni 10:4", vec!["prayer".to_string(), "faith".to_string()]);
+        theirs.highlights.insert("Moroni 10:4".to_string(), "blue".to_string());
+        theirs.highlights.insert("Moroni 10:5".to_string(), "pink".to_string());
+        theirs.memorize.record("Moroni 10:4", 0.5);
+        let week = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        theirs.progress.weeks.entry(week).or_default().insert("Genesis 1".to_string());
+        theirs.journal.insert("2026-01-05-070000.md".to_string(), "theirs".to_string());
+
+        let mut here = Backup::default();
+        here.saved.add([verse(4)]);
+        here.saved.set_tags("Moroni 10:4", vec!["faith".to_string()]);
+        here.highlights.insert("Moroni 10:4".to_string(), "yellow".to_string());
+        here.journal.insert("2026-01-05-070000.md".to_string(), "mine".to_string());
+
+        let summary = theirs.merge_into(&mut here);
+        assert_eq!(
+            summary,
+            MergeSummary { verses: 1, tags: 1, highlights: 1, conversations: 0, attempts: 1, readings: 1, notes: 1 }
+        );
+        assert_eq!(here.saved.tags["Moroni 10:4"], ["faith", "prayer"]);
+        assert_eq!(here.highlights["Moroni 10:4"], "yellow");
+        assert_eq!(here.journal["2026-01-05-070000.md"], "mine");
+        assert_eq!(here.journal["2026-01-05-070001.md"], "theirs");
+        assert_eq!(summary.describe(), "1 saved verse, 1 tag, 1 highlight, 1 memorization attempt, 1 finished reading, 1 journal entry");
+
+        // Importing the same backup again adds nothing
+        assert_eq!(theirs.merge_into(&mut here).describe(), "nothing new");
+    }
+
+    #[test]
+    fn test_export_then_import_into_another_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = Store::open_at(&dir.path().join("a/study.db")).unwrap();
+        let mut saved = SavedScriptures::default();
+        saved.add([verse(4)]);
+        store.save_saved(&SavedScriptures::default(), &saved).unwrap();
+        store.set_highlight("Moroni 10:4", Some("green")).unwrap();
+        let journal = Journal::load_from(&dir.path().join("a/journal")).unwrap();
+        journal.add_note(&[verse(4)], "A promise").unwrap();
+
+        let path = dir.path().join("backup.json");
+        Backup::collect(&store, &Journal::load_from(&journal.dir).unwrap()).unwrap().write(&path).unwrap();
+
+        let mut other = Store::open_at(&dir.path().join("b/study.db")).unwrap();
+        let other_journal = Journal::load_from(&dir.path().join("b/journal")).unwrap();
+        let summary = Backup::read(&path).unwrap().import(&mut other, &other_journal).unwrap();
+        assert_eq!((summary.verses, summary.highlights, summary.notes), (1, 1, 1));
+        assert_eq!(other.highlight("Moroni 10:4").unwrap().as_deref(), Some("green"));
+        let notes = Journal::load_from(&other_journal.dir).unwrap();
+        assert!(notes.entries[0].content.contains("A promise"));
+        let missing = Backup::read(&dir.path().join("missing.json")).unwrap_err();
+        assert!(matches!(&missing, EscriturasError::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
+        assert!(missing.to_string().starts_with("Could not read "));
+    }
+}
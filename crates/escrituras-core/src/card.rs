@@ -0,0 +1,590 @@
+//! Quote cards
+//!
+//! A verse or passage laid out on a card to share: its text centered and
+//! wrapped to fill the card, with its reference above or below. Cards are
+//! SVG, which names a font for whatever opens it to draw the text in, or
+//! PNG, drawn here with a built-in pixel font so there's nothing to install.
+//! The background, the SVG font, and where the reference goes come from the
+//! `card_background`, `card_font`, and `card_reference` settings.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::copy_format::passage_reference;
+use crate::error::{EscriturasError, Result};
+use crate::scripture::Scripture;
+
+/// The size of a link preview, which most sites crop other sizes to
+pub const DEFAULT_WIDTH: u32 = 1200;
+pub const DEFAULT_HEIGHT: u32 = 630;
+pub const DEFAULT_BACKGROUND: &str = "#1f2a44";
+pub const DEFAULT_FONT: &str = "Georgia, 'Times New Roman', serif";
+
+/// Cards smaller or larger than this aren't drawn
+const SIZE_RANGE: std::ops::RangeInclusive<u32> = 200..=4000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardFormat {
+    Png,
+    Svg,
+}
+
+impl CardFormat {
+    /// By a file's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// Where the reference goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReferencePlacement {
+    Above,
+    #[default]
+    Below,
+}
+
+impl ReferencePlacement {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "above" | "top" => Some(Self::Above),
+            "below" | "bottom" => Some(Self::Below),
+            _ => None,
+        }
+    }
+}
+
+/// A color as "#rrggbb"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// "#1f2a44", "1f2a44", or "#fff"
+    pub fn parse(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.is_ascii() {
+            return None;
+        }
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        match hex.len() {
+            6 => Some(Self(channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+            3 => {
+                let short = |i: usize| channel(&hex[i..i + 1]).map(|c| c * 17);
+                Some(Self(short(0)?, short(1)?, short(2)?))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+
+    /// Near-black text on light backgrounds, near-white on dark ones
+    fn text_on(&self) -> Self {
+        let luminance = 0.299 * self.0 as f32 + 0.587 * self.1 as f32 + 0.114 * self.2 as f32;
+        if luminance > 140.0 {
+            Self(0x22, 0x22, 0x22)
+        } else {
+            Self(0xf5, 0xf2, 0xea)
+        }
+    }
+
+    /// `self` a `weight` of the way toward `other`
+    fn mix(&self, other: Self, weight: f32) -> Self {
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * weight).round() as u8;
+        Self(mix(self.0, other.0), mix(self.1, other.1), mix(self.2, other.2))
+    }
+}
+
+/// How a card looks
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardStyle {
+    pub background: Rgb,
+    /// Font family of SVG cards, as CSS names it
+    pub font: String,
+    pub reference: ReferencePlacement,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for CardStyle {
+    fn default() -> Self {
+        Self {
+            background: Rgb::parse(DEFAULT_BACKGROUND).unwrap_or(Rgb(0, 0, 0)),
+            font: DEFAULT_FONT.to_string(),
+            reference: ReferencePlacement::default(),
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+        }
+    }
+}
+
+impl CardStyle {
+    /// The default style with the card settings applied
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut style = Self::default();
+        if let Some(background) = &config.card_background {
+            style.background = parse_background(background)?;
+        }
+        if let Some(font) = &config.card_font {
+            style.font = font.clone();
+        }
+        if let Some(reference) = &config.card_reference {
+            style.reference = parse_reference_placement(reference)?;
+        }
+        Ok(style)
+    }
+
+    /// "1080x1080"
+    pub fn with_size(mut self, size: &str) -> Result<Self> {
+        let invalid = || {
+            EscriturasError::InvalidSetting(format!(
+                "Card size must be WIDTHxHEIGHT, each from {} to {}, like 1080x1080",
+                SIZE_RANGE.start(),
+                SIZE_RANGE.end()
+            ))
+        };
+        let (width, height) = size.trim().to_ascii_lowercase().split_once('x').ok_or_else(invalid).and_then(|(w, h)| {
+            Ok((w.trim().parse::<u32>().map_err(|_| invalid())?, h.trim().parse::<u32>().map_err(|_| invalid())?))
+        })?;
+        if !SIZE_RANGE.contains(&width) || !SIZE_RANGE.contains(&height) {
+            return Err(invalid());
+        }
+        self.width = width;
+        self.height = height;
+        Ok(self)
+    }
+
+    fn foreground(&self) -> Rgb {
+        self.background.text_on()
+    }
+
+    /// The reference, quieter than the text
+    fn accent(&self) -> Rgb {
+        self.foreground().mix(self.background, 0.35)
+    }
+
+    fn margin(&self) -> u32 {
+        self.width.min(self.height) / 10
+    }
+}
+
+/// A `card_background` value
+pub(crate) fn parse_background(value: &str) -> Result<Rgb> {
+    Rgb::parse(value)
+        .ok_or_else(|| EscriturasError::InvalidSetting("card_background must be a color like #1f2a44".to_string()))
+}
+
+/// A `card_reference` value
+pub(crate) fn parse_reference_placement(value: &str) -> Result<ReferencePlacement> {
+    ReferencePlacement::from_name(value)
+        .ok_or_else(|| EscriturasError::InvalidSetting("card_reference must be above or below".to_string()))
+}
+
+/// A passage and its reference, ready to draw
+#[derive(Debug, Clone, PartialEq)]
+pub struct Card {
+    pub text: String,
+    pub reference: String,
+}
+
+impl Card {
+    /// One verse or a passage from a single chapter, its verses run together
+    pub fn of_verses(verses: &[Scripture]) -> Option<Self> {
+        if verses.is_empty() {
+            return None;
+        }
+        let text = verses.iter().map(|v| v.scripture_text.trim()).collect::<Vec<_>>().join(" ");
+        Some(Self { text, reference: passage_reference(verses) })
+    }
+
+    /// "Moroni 10-4.png": the reference, safe as a file name
+    pub fn file_name(&self, format: CardFormat) -> String {
+        let name: String = self.reference.chars().map(|c| if matches!(c, ':' | '/' | '\\') { '-' } else { c }).collect();
+        format!("{}.{}", name, format.extension())
+    }
+
+    pub fn render(&self, format: CardFormat, style: &CardStyle) -> Result<Vec<u8>> {
+        match format {
+            CardFormat::Png => self.png(style),
+            CardFormat::Svg => Ok(self.svg(style).into_bytes()),
+        }
+    }
+
+    /// Write the card to `path`, in the format its extension names
+    pub fn save(&self, path: &Path, style: &CardStyle) -> Result<()> {
+        let format = CardFormat::from_path(path).ok_or_else(|| {
+            EscriturasError::InvalidSetting(format!("Can't tell the format of {}: name it .png or .svg", path.display()))
+        })?;
+        std::fs::write(path, self.render(format, style)?)?;
+        Ok(())
+    }
+
+    pub fn svg(&self, style: &CardStyle) -> String {
+        // Proportional fonts average about half an em per character
+        let text = fit(&self.text, style, &SVG_SIZES, |size| size / 2, |size| size * 7 / 5);
+        let reference_size = (style.width / 40).max(14);
+        let (text_top, reference_y) = stack(style, &text, reference_size * 3 / 2);
+        let center = style.width / 2;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"{bg}\"/>\n\
+             <g font-family=\"{font}\" text-anchor=\"middle\">\n",
+            w = style.width,
+            h = style.height,
+            bg = style.background.hex(),
+            font = escape_xml(&style.font),
+        );
+        svg.push_str(&format!("<text font-size=\"{}\" fill=\"{}\">\n", text.size, style.foreground().hex()));
+        for (i, line) in text.lines.iter().enumerate() {
+            // Text is placed by its baseline, about four fifths of an em down
+            let y = text_top + i as u32 * text.line_height + text.size * 4 / 5;
+            svg.push_str(&format!("<tspan x=\"{}\" y=\"{}\">{}</tspan>\n", center, y, escape_xml(line)));
+        }
+        svg.push_str("</text>\n");
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" letter-spacing=\"1\" fill=\"{}\">{}</text>\n</g>\n</svg>\n",
+            center,
+            reference_y + reference_size * 4 / 5,
+            reference_size,
+            style.accent().hex(),
+            escape_xml(&self.reference)
+        ));
+        svg
+    }
+
+    pub fn png(&self, style: &CardStyle) -> Result<Vec<u8>> {
+        let text = fit(&self.text, style, &PNG_SCALES, |scale| GLYPH_SIZE * scale, |scale| GLYPH_SIZE * scale * 3 / 2);
+        let reference_scale = (style.width / 400).max(2);
+        let (text_top, reference_y) = stack(style, &text, GLYPH_SIZE * reference_scale);
+
+        let mut canvas = Canvas::new(style.width, style.height, style.background);
+        for (i, line) in text.lines.iter().enumerate() {
+            canvas.centered_line(line, text_top + i as u32 * text.line_height, text.size, style.foreground());
+        }
+        canvas.centered_line(&self.reference, reference_y, reference_scale, style.accent());
+        canvas.encode()
+    }
+}
+
+/// Font sizes of SVG text to try, largest first
+const SVG_SIZES: [u32; 14] = [72, 66, 60, 56, 52, 48, 44, 40, 36, 32, 28, 24, 20, 16];
+/// How many times to enlarge the pixel font, largest first
+const PNG_SCALES: [u32; 7] = [8, 7, 6, 5, 4, 3, 2];
+
+/// Text wrapped at the largest size that fits the card
+struct Fitted {
+    size: u32,
+    lines: Vec<String>,
+    line_height: u32,
+}
+
+/// Wrap `text` at each size in turn until it fits beside the reference,
+/// cutting it short at the smallest if nothing does
+fn fit(text: &str, style: &CardStyle, sizes: &[u32], advance: impl Fn(u32) -> u32, line_height: impl Fn(u32) -> u32) -> Fitted {
+    let margin = style.margin();
+    let width = style.width.saturating_sub(2 * margin);
+    // Room left by the reference and the gap before it
+    let height = style.height.saturating_sub(2 * margin).saturating_sub(style.height / 8);
+    for &size in sizes {
+        let lines = wrap(text, (width / advance(size).max(1)) as usize);
+        if lines.len() as u32 * line_height(size) <= height {
+            return Fitted { size, lines, line_height: line_height(size) };
+        }
+    }
+    let size = sizes.last().copied().unwrap_or(16);
+    let mut lines = wrap(text, (width / advance(size).max(1)) as usize);
+    let rows = (height / line_height(size).max(1)).max(1) as usize;
+    if lines.len() > rows {
+        lines.truncate(rows);
+        if let Some(last) = lines.last_mut() {
+            last.push_str(" ...");
+        }
+    }
+    Fitted { size, lines, line_height: line_height(size) }
+}
+
+/// Tops of the text block and the reference line, centered on the card
+/// together with a gap between them
+fn stack(style: &CardStyle, text: &Fitted, reference_height: u32) -> (u32, u32) {
+    let block = text.lines.len() as u32 * text.line_height;
+    let gap = reference_height * 2;
+    let top = style.height.saturating_sub(block + gap + reference_height) / 2;
+    match style.reference {
+        ReferencePlacement::Above => (top + reference_height + gap, top),
+        ReferencePlacement::Below => (top, top + block + gap),
+    }
+}
+
+/// Greedy word wrap at `width` characters; longer words are broken
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while word.chars().count() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let rest = word.chars().skip(width).collect();
+            lines.push(word.chars().take(width).collect());
+            word = rest;
+        }
+        if line.is_empty() {
+            line = word;
+        } else if line.chars().count() + 1 + word.chars().count() <= width {
+            line.push(' ');
+            line.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut line, word));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Where a card is saved from the TUI: the pictures folder, or else home
+pub fn default_dir() -> Option<PathBuf> {
+    dirs::picture_dir().or_else(dirs::home_dir)
+}
+
+/// RGB pixels
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: Rgb) -> Self {
+        let pixels = [background.0, background.1, background.2].repeat((width * height) as usize);
+        Self { width, height, pixels }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, size: u32, color: Rgb) {
+        for py in y..(y + size).min(self.height) {
+            for px in x..(x + size).min(self.width) {
+                let i = ((py * self.width + px) * 3) as usize;
+                self.pixels[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+
+    /// A line of the pixel font, `scale` pixels to a dot, centered across
+    fn centered_line(&mut self, line: &str, top: u32, scale: u32, color: Rgb) {
+        let advance = GLYPH_SIZE * scale;
+        let mut x = self.width.saturating_sub(line.chars().count() as u32 * advance) / 2;
+        for c in line.chars() {
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for column in 0..GLYPH_SIZE {
+                    if bits & (1 << column) != 0 {
+                        self.fill(x + column * scale, top + row as u32 * scale, scale, color);
+                    }
+                }
+            }
+            x += advance;
+        }
+    }
+
+    fn encode(self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let encoded = encoder.write_header().and_then(|mut writer| writer.write_image_data(&self.pixels));
+        encoded.map_err(|e| EscriturasError::Other(format!("Couldn't encode the card: {}", e)))?;
+        Ok(out)
+    }
+}
+
+/// Glyphs are eight dots square
+const GLYPH_SIZE: u32 = 8;
+
+/// The dots of a character, a row to a byte, leftmost dot the lowest bit.
+/// Typographic punctuation is drawn as its ASCII look-alike, and what the
+/// font doesn't have as "?".
+fn glyph(c: char) -> &'static [u8; 8] {
+    let c = match c {
+        '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        '–' | '—' => '-',
+        '…' => '.',
+        c => c,
+    };
+    let index = match c {
+        ' '..='~' => c as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &FONT[index]
+}
+
+/// ASCII 0x20-0x7e of the public-domain font8x8 (Daniel Hepper, after the IBM PC font)
+#[rustfmt::skip]
+const FONT: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // !
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // "
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // #
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // $
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // %
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // &
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // (
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // )
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // *
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ,
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // .
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // /
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // 0
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // 1
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // 2
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // 3
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // 4
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // 5
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // 6
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // 7
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // 8
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // :
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ;
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // <
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // =
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // >
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // ?
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // @
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // A
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // B
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // C
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // D
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // E
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // F
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // G
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // H
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // I
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // J
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // K
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // L
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // M
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // N
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // O
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // P
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // Q
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // R
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // S
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // T
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // U
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // V
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // W
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // X
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // Y
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // Z
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // [
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // backslash
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ]
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // _
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // a
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // b
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // c
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // d
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // e
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // f
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // g
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // h
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // i
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // j
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // k
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // l
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // m
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // n
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // o
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // p
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // q
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // r
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // s
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // t
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // u
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // v
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // w
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // x
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // y
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // z
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // {
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // |
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // }
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ~
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card() -> Card {
+        Card {
+            text: "And when ye shall receive these things, I would exhort you that ye would ask God, the Eternal Father, \
+                   in the name of Christ, if these things are not true"
+                .to_string(),
+            reference: "Moroni 10:4".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_svg_card() {
+        let style = CardStyle { reference: ReferencePlacement::Above, font: "Palatino & co".to_string(), ..CardStyle::default() };
+        let svg = card().svg(&style);
+        assert!(svg.starts_with("<svg") && svg.contains("width=\"1200\" height=\"630\""));
+        assert!(svg.contains("fill=\"#1f2a44\""));
+        assert!(svg.contains("font-family=\"Palatino &amp; co\""));
+        assert!(svg.contains(">Moroni 10:4</text>"));
+        // Placed above the text's first line, which wraps onto more
+        let y = |tag: &str| -> u32 {
+            let rest = &svg[svg.find(tag).unwrap()..];
+            let rest = &rest[rest.find(" y=\"").unwrap() + 4..];
+            rest[..rest.find('"').unwrap()].parse().unwrap()
+        };
+        assert!(y("<text x=") < y("<tspan"));
+        assert!(svg.matches("<tspan").count() > 1);
+        assert_eq!(card().file_name(CardFormat::Svg), "Moroni 10-4.svg");
+    }
+
+    #[test]
+    fn test_png_card() {
+        let style = CardStyle::default().with_size("400x300").unwrap();
+        let png = card().png(&style).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (400, 300));
+        // Background in the corner, text in the middle
+        assert_eq!(&pixels[0..3], &[0x1f, 0x2a, 0x44]);
+        assert!(pixels.chunks(3).any(|p| p == [0xf5, 0xf2, 0xea]));
+
+        assert!(CardStyle::default().with_size("10x10").is_err());
+        assert_eq!(Rgb::parse("#fff"), Some(Rgb(255, 255, 255)));
+        assert_eq!(wrap("a bb ccc dddddd", 4), ["a bb", "ccc", "dddd", "dd"]);
+    }
+}
@@ -0,0 +1,343 @@
+//! Come, Follow Me reading schedule
+//!
+//! The weekly schedule changes every year, so it's read from an optional
+//! `come_follow_me.json` in the data directory:
+//!
+//! ```json
+//! [{ "start": "2026-01-05", "title": "The First Book of Moses", "reading": ["Moses 1", "Genesis 1-2"] }]
+//! ```
+//!
+//! Chapter ranges ("Genesis 1-2") are read as one entry per chapter. Which
+//! readings have been finished is kept per week in the study data store
+//! (see `store`).
+
+use chrono::{Days, Local, NaiveDate};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{EscriturasError, Result};
+use crate::scripture::{ScriptureDb, ScriptureRange};
+use crate::title::Title;
+
+/// One week of the schedule
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CfmWeek {
+    /// Monday the week begins
+    pub start: NaiveDate,
+    pub title: String,
+    /// References as printed in the manual ("Genesis 1-2", "Moses 2:1-10")
+    pub reading: Vec<String>,
+}
+
+/// A chapter (or part of one) assigned for a week
+#[derive(Debug, Clone)]
+pub struct CfmReading {
+    /// "Genesis 1", or the verse range for a partial chapter
+    pub label: String,
+    pub range: ScriptureRange,
+}
+
+impl CfmWeek {
+    /// Sunday the week ends
+    pub fn end(&self) -> NaiveDate {
+        self.start + Days::new(6)
+    }
+
+    /// The week's reading, one entry per chapter; references that don't
+    /// resolve in the database are skipped
+    pub fn readings(&self, db: &ScriptureDb) -> Vec<CfmReading> {
+        let chapter_range = Regex::new(r"^([^:]*?[A-Za-z&][^:]*?)\s*(\d+)\s*[-–—]\s*(\d+)$").unwrap();
+        let chapter = |book: &str, number: i32| {
+            db.parse_reference(&format!("{} {}", book, number))
+                .map(|range| CfmReading { label: format!("{} {}", range.book_title, number), range })
+        };
+
+        let mut readings = Vec::new();
+        for reference in &self.reading {
+            let reference = reference.trim();
+            if let Some(caps) = chapter_range.captures(reference) {
+                let (from, to) = (caps[2].parse().unwrap_or(0), caps[3].parse().unwrap_or(0));
+                readings.extend((from..=to).filter_map(|n| chapter(&caps[1], n)));
+            } else if let Some(range) = db.parse_reference(reference) {
+                let whole_chapter = !reference.contains(':');
+                let label = if whole_chapter {
+                    format!("{} {}", range.book_title, range.chapter_number)
+                } else {
+                    range.display_title()
+                };
+                readings.push(CfmReading { label, range });
+            }
+        }
+        readings
+    }
+}
+
+/// Readings as one line, runs of whole chapters joined: "Alma 5–7, Alma 8:1-10"
+pub fn reading_summary(readings: &[CfmReading]) -> String {
+    // (book, first chapter, last chapter) of each run, or a partial chapter's label
+    let mut parts: Vec<Result<(&str, i32, i32), &str>> = Vec::new();
+    for reading in readings {
+        let range = &reading.range;
+        let whole = reading.label == format!("{} {}", range.book_title, range.chapter_number);
+        match parts.last_mut() {
+            Some(Ok((book, _, last))) if whole && *book == range.book_title && *last + 1 == range.chapter_number => {
+                *last = range.chapter_number;
+            }
+            _ if whole => parts.push(Ok((&range.book_title, range.chapter_number, range.chapter_number))),
+            _ => parts.push(Err(&reading.label)),
+        }
+    }
+    parts
+        .into_iter()
+        .map(|part| match part {
+            Ok((book, first, last)) if first == last => format!("{} {}", book, first),
+            Ok((book, first, last)) => format!("{} {}–{}", book, first, last),
+            Err(label) => label.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CfmSchedule {
+    weeks: Vec<CfmWeek>,
+}
+
+impl CfmSchedule {
+    pub const FILE_NAME: &'static str = "come_follow_me.json";
+
+    /// Load `come_follow_me.json` from a data directory
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open schedule {:?}: {}", path, e)))?;
+        Ok(Self::from_weeks(serde_json::from_str(&content)?))
+    }
+
+    pub fn from_weeks(mut weeks: Vec<CfmWeek>) -> Self {
+        weeks.sort_by_key(|w| w.start);
+        Self { weeks }
+    }
+
+    /// Weeks in date order
+    pub fn weeks(&self) -> &[CfmWeek] {
+        &self.weeks
+    }
+
+    /// Index of the week containing `date`: the last one starting on or before
+    /// it, if that's within seven days
+    pub fn week_for(&self, date: NaiveDate) -> Option<usize> {
+        let i = self.weeks.iter().rposition(|w| w.start <= date)?;
+        ((date - self.weeks[i].start).num_days() < 7).then_some(i)
+    }
+
+    /// This week, or the nearest week when today falls outside the schedule
+    pub fn current_week(&self) -> Option<usize> {
+        if self.weeks.is_empty() {
+            return None;
+        }
+        let today = Local::now().date_naive();
+        self.week_for(today).or_else(|| {
+            let before = self.weeks.iter().rposition(|w| w.start <= today);
+            Some(before.unwrap_or(0))
+        })
+    }
+}
+
+/// Readings finished in one volume over a schedule
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct VolumeProgress {
+    pub volume: Title,
+    pub read: usize,
+    pub assigned: usize,
+}
+
+/// Finished readings, keyed by week start date
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CfmProgress {
+    pub weeks: BTreeMap<NaiveDate, BTreeSet<String>>,
+}
+
+impl CfmProgress {
+    /// From the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Result<Self> {
+        crate::store::Store::open()?.cfm_progress()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write what changed since `loaded` to the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, loaded: &Self) -> Result<()> {
+        crate::store::Store::open()?.save_cfm_progress(loaded, self)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn is_done(&self, week: &CfmWeek, label: &str) -> bool {
+        self.weeks.get(&week.start).is_some_and(|done| done.contains(label))
+    }
+
+    /// Mark a reading finished, or unfinished if it already was; returns the new state
+    pub fn toggle(&mut self, week: &CfmWeek, label: &str) -> bool {
+        let done = self.weeks.entry(week.start).or_default();
+        let now_done = done.insert(label.to_string()) || !done.remove(label);
+        if done.is_empty() {
+            self.weeks.remove(&week.start);
+        }
+        now_done
+    }
+
+    /// Mark a reading finished, leaving it finished if it already was
+    pub fn mark_done(&mut self, week: &CfmWeek, label: &str) {
+        self.weeks.entry(week.start).or_default().insert(label.to_string());
+    }
+
+    /// Whether every reading of a week is finished (never for a week with none)
+    pub fn is_finished(&self, week: &CfmWeek, db: &ScriptureDb) -> bool {
+        let readings = week.readings(db);
+        !readings.is_empty() && readings.iter().all(|r| self.is_done(week, &r.label))
+    }
+
+    /// Weeks finished in a row, counting back from week `current`. The
+    /// current week only adds to the streak; it can't break it until it's over.
+    pub fn streak(&self, schedule: &CfmSchedule, db: &ScriptureDb, current: usize) -> usize {
+        let weeks = &schedule.weeks()[..=current.min(schedule.weeks().len().saturating_sub(1))];
+        let (this_week, earlier) = match weeks.split_last() {
+            Some(split) => split,
+            None => return 0,
+        };
+        let earlier = earlier.iter().rev().take_while(|w| self.is_finished(w, db)).count();
+        earlier + usize::from(self.is_finished(this_week, db))
+    }
+
+    /// Readings finished and assigned in each volume over the whole schedule,
+    /// in the order the volumes come up
+    pub fn volume_progress(&self, schedule: &CfmSchedule, db: &ScriptureDb) -> Vec<VolumeProgress> {
+        let mut volumes: Vec<VolumeProgress> = Vec::new();
+        for week in schedule.weeks() {
+            for reading in week.readings(db) {
+                let Some(verse) = db.get_verses_in_range(&reading.range).into_iter().next() else {
+                    continue;
+                };
+                let i = match volumes.iter().position(|v| v.volume == verse.volume_title) {
+                    Some(i) => i,
+                    None => {
+                        volumes.push(VolumeProgress { volume: verse.volume_title.clone(), read: 0, assigned: 0 });
+                        volumes.len() - 1
+                    }
+                };
+                volumes[i].assigned += 1;
+                volumes[i].read += usize::from(self.is_done(week, &reading.label));
+            }
+        }
+        volumes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripture::Scripture;
+
+    fn verse(book: &str, chapter: i32, number: i32) -> Scripture {
+        Scripture::test(book, chapter, number).volume("Old Testament")
+    }
+
+    fn week(start: &str, reading: &[&str]) -> CfmWeek {
+        CfmWeek {
+            start: start.parse().unwrap(),
+            title: "Title".to_string(),
+            reading: reading.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_week_for_date() {
+        let schedule = CfmSchedule::from_weeks(vec![week("2026-01-12", &[]), week("2026-01-05", &[])]);
+        let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+        assert_eq!(schedule.week_for(date("2026-01-05")), Some(0));
+        assert_eq!(schedule.week_for(date("2026-01-11")), Some(0));
+        assert_eq!(schedule.week_for(date("2026-01-14")), Some(1));
+        assert_eq!(schedule.week_for(date("2026-01-19")), None);
+        assert_eq!(schedule.week_for(date("2026-01-01")), None);
+    }
+
+    #[test]
+    fn test_readings_expand_chapter_ranges() {
+        let db = ScriptureDb::from_verses(vec![
+            verse("Genesis", 1, 1),
+            verse("Genesis", 1, 2),
+            verse("Genesis", 2, 1),
+            verse("Genesis", 3, 1),
+            verse("Genesis", 3, 2),
+        ]);
+        let readings = week("2026-01-05", &["Genesis 1-2", "Genesis 3:2", "Moses 1"]).readings(&db);
+        let labels: Vec<&str> = readings.iter().map(|r| r.label.as_str()).collect();
+
+        assert_eq!(labels, ["Genesis 1", "Genesis 2", "Genesis 3:2"]);
+        assert_eq!(readings[0].range.end_verse, 2);
+        assert_eq!(reading_summary(&readings), "Genesis 1–2, Genesis 3:2");
+    }
+
+    #[test]
+    fn test_progress_toggle() {
+        let week = week("2026-01-05", &["Genesis 1"]);
+        let mut progress = CfmProgress::default();
+
+        assert!(progress.toggle(&week, "Genesis 1"));
+        assert!(progress.is_done(&week, "Genesis 1"));
+        assert!(!progress.toggle(&week, "Genesis 1"));
+        assert!(progress.weeks.is_empty());
+
+        progress.mark_done(&week, "Genesis 1");
+        progress.mark_done(&week, "Genesis 1");
+        assert!(progress.is_done(&week, "Genesis 1"));
+    }
+
+    #[test]
+    fn test_streak_and_volume_progress() {
+        let db = ScriptureDb::from_verses(vec![verse("Genesis", 1, 1), verse("Genesis", 2, 1), verse("Genesis", 3, 1)]);
+        let schedule = CfmSchedule::from_weeks(vec![
+            week("2026-01-05", &["Genesis 1"]),
+            week("2026-01-12", &["Genesis 2"]),
+            week("2026-01-19", &["Genesis 3"]),
+        ]);
+        let weeks = schedule.weeks();
+        let mut progress = CfmProgress::default();
+        progress.mark_done(&weeks[0], "Genesis 1");
+        progress.mark_done(&weeks[1], "Genesis 2");
+
+        // This week isn't read yet, which doesn't end the streak
+        assert_eq!(progress.streak(&schedule, &db, 2), 2);
+        progress.mark_done(&weeks[2], "Genesis 3");
+        assert_eq!(progress.streak(&schedule, &db, 2), 3);
+        progress.toggle(&weeks[1], "Genesis 2");
+        assert_eq!(progress.streak(&schedule, &db, 2), 1);
+
+        assert_eq!(
+            progress.volume_progress(&schedule, &db),
+            [VolumeProgress { volume: "Old Testament".into(), read: 2, assigned: 3 }]
+        );
+    }
+}
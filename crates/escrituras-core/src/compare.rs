@@ -0,0 +1,91 @@
+//! Word-level comparison of parallel passages
+//!
+//! For passages quoted in another book (Isaiah in the Book of Mormon, the
+//! Sermon on the Mount in 3 Nephi): verses are paired in order and each pair
+//! is diffed word by word. Words match ignoring case and punctuation, so a
+//! changed comma doesn't hide that the wording is the same.
+
+use crate::scripture::Scripture;
+
+/// One word of a verse pair
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordChange {
+    /// In both verses (as written in the second)
+    Same(String),
+    /// Only in the first verse
+    Removed(String),
+    /// Only in the second verse
+    Added(String),
+}
+
+/// Verses of two passages side by side, in order; the shorter passage runs
+/// out with None
+pub fn pair_verses<'a>(first: &[&'a Scripture], second: &[&'a Scripture]) -> Vec<(Option<&'a Scripture>, Option<&'a Scripture>)> {
+    (0..first.len().max(second.len()))
+        .map(|i| (first.get(i).copied(), second.get(i).copied()))
+        .collect()
+}
+
+fn normalize(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// The words of `first` turned into those of `second`: the longest run of
+/// shared words kept, removals before additions where they differ
+pub fn diff_words(first: &str, second: &str) -> Vec<WordChange> {
+    let a: Vec<&str> = first.split_whitespace().collect();
+    let b: Vec<&str> = second.split_whitespace().collect();
+    let (na, nb): (Vec<String>, Vec<String>) =
+        (a.iter().map(|w| normalize(w)).collect(), b.iter().map(|w| normalize(w)).collect());
+
+    // lcs[i][j]: shared words in a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if na[i] == nb[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && na[i] == nb[j] {
+            changes.push(WordChange::Same(b[j].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(WordChange::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            changes.push(WordChange::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_words_ignores_case_and_punctuation() {
+        let changes = diff_words("And it shall come to pass in the last days,", "And it shall come to pass in the last days, when");
+        assert_eq!(changes.last(), Some(&WordChange::Added("when".to_string())));
+        assert!(changes[..changes.len() - 1].iter().all(|c| matches!(c, WordChange::Same(_))));
+
+        assert_eq!(
+            diff_words("the Lord's house shall be", "the house of the Lord shall be"),
+            [
+                WordChange::Same("the".into()),
+                WordChange::Removed("Lord's".into()),
+                WordChange::Same("house".into()),
+                WordChange::Added("of".into()),
+                WordChange::Added("the".into()),
+                WordChange::Added("Lord".into()),
+                WordChange::Same("shall".into()),
+                WordChange::Same("be".into()),
+            ]
+        );
+    }
+}
@@ -1,61 +1,918 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
-use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::error::{EscriturasError, Result};
+use chrono::NaiveTime;
+use crate::ai::prompt::STUDY_INSTRUCTIONS;
+#[cfg(feature = "ai")]
+use crate::ai::{ClaudeClient, OllamaClient, OpenAIClient};
+use crate::copy_format::CopyFormat;
+use crate::links::DEFAULT_LANGUAGE;
+use crate::provider::Provider;
+use crate::search::{SearchFilter, SearchMode};
+
+/// Pane sizes chosen by the user
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PaneLayout {
+    /// Width of the Browse navigation pane in columns
+    pub nav_width: u16,
+    /// Percentage of the AI screen given to the chat pane
+    pub query_split: u16,
+    /// Hide the navigation pane for a wide reading view
+    pub nav_hidden: bool,
+    /// Collapse the footnote pane under the chapter text
+    #[serde(default)]
+    pub footnotes_hidden: bool,
+    /// Longest line in reading mode, in columns
+    #[serde(default = "default_reading_width")]
+    pub reading_width: u16,
+    /// Fit more on small windows: no blank line between verses, only top borders on panes
+    #[serde(default)]
+    pub compact: bool,
+    /// Show the related-verses panel beside the chapter text
+    #[serde(default)]
+    pub related_visible: bool,
+    /// Show the chapter outline beside the chapter text
+    #[serde(default)]
+    pub outline_visible: bool,
+}
+
+fn default_reading_width() -> u16 {
+    72
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            nav_width: 30,
+            query_split: 50,
+            nav_hidden: false,
+            footnotes_hidden: false,
+            reading_width: default_reading_width(),
+            compact: false,
+            related_visible: false,
+            outline_visible: false,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     pub provider: Option<String>,
     pub default_model: Option<String>,
+    /// Model to use with each provider, remembered when switching between them
+    pub claude_model: Option<String>,
+    pub openai_model: Option<String>,
+    pub ollama_model: Option<String>,
     pub claude_api_key: Option<String>,
     pub openai_api_key: Option<String>,
+    /// Clipboard format name (see `CopyFormat`)
+    pub copy_format: Option<String>,
+    pub layout: Option<PaneLayout>,
+    /// Show the verse of the day when the TUI starts (default true)
+    pub verse_of_the_day: Option<bool>,
+    /// Ollama server (default http://localhost:11434)
+    pub ollama_url: Option<String>,
+    /// OpenAI-compatible API root (default https://api.openai.com/v1)
+    pub openai_base_url: Option<String>,
+    /// Anthropic API root, for a proxy (default https://api.anthropic.com/v1)
+    pub claude_base_url: Option<String>,
+    /// Folder holding `lds-scriptures-2020.12.08/` and `data/`, searched
+    /// before the current directory and the data directory
+    pub data_dir: Option<String>,
+    /// Folder the study data is synced through (see `sync`)
+    pub sync_dir: Option<String>,
+    /// Replaces the opening study instructions of every AI prompt
+    pub prompt_template: Option<String>,
+    /// Time of day ("07:30") the desktop app reminds you of the day's reading
+    pub study_reminder: Option<String>,
+    /// How much goes in the log file: a level, or filter directives (default info)
+    pub log_level: Option<String>,
+    /// Keep API keys in this file even when a keychain is available (default false)
+    pub plaintext_keys: Option<bool>,
+    /// Color theme by name, over the one chosen in theme.toml
+    pub theme: Option<String>,
+    /// Key bindings file (default keybindings.toml next to this file)
+    pub keybindings: Option<String>,
+    /// Results per search (default 50)
+    pub search_limit: Option<usize>,
+    /// Search by meaning as well as by keyword when embeddings are installed (default true)
+    pub semantic_search: Option<bool>,
+    /// Language of Gospel Library links, as its three-letter code ("spa"; default eng)
+    pub gospel_library_language: Option<String>,
+    /// Text-to-speech program and its options ("espeak-ng -s 140"), given the
+    /// text after them (default: the system's own; see `read_aloud`)
+    pub speech_command: Option<String>,
+    /// Quote card background color ("#1f2a44"; see `card`)
+    pub card_background: Option<String>,
+    /// Font family of SVG quote cards, as CSS names it
+    pub card_font: Option<String>,
+    /// Whether a quote card's reference goes above or below the text (default below)
+    pub card_reference: Option<String>,
+    /// Named sets of AI settings, one of which can be used in place of those above
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// The profile applied on load, with the settings it replaced
+    #[serde(skip)]
+    active: Option<(String, Box<Config>)>,
+    /// Settings taken from `ESCRITURAS_*` variables on load, in order
+    #[serde(skip)]
+    overrides: Vec<Override>,
+}
+
+/// A setting replaced by an environment variable, which isn't saved
+#[derive(Debug, Clone)]
+struct Override {
+    variable: String,
+    key: &'static str,
+    before: Option<String>,
+    applied: Option<String>,
 }
 
+/// AI settings that differ in some setting: "work" using Ollama on a LAN
+/// host, "home" using Claude. Unset fields keep the top-level value.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub ollama_url: Option<String>,
+    pub openai_base_url: Option<String>,
+    pub claude_base_url: Option<String>,
+    pub prompt_template: Option<String>,
+}
+
+/// The profile chosen with `--profile`, over `ESCRITURAS_PROFILE`
+static SELECTED_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Environment variable naming the profile to use
+pub const PROFILE_VARIABLE: &str = "ESCRITURAS_PROFILE";
+
+/// Environment variable naming a file to read `prompt_template` from
+pub const PROMPT_FILE_VARIABLE: &str = "ESCRITURAS_PROMPT_FILE";
+
+/// Where an API key came from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    Environment,
+    Keychain,
+    ConfigFile,
+}
+
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
 impl Config {
     pub fn new() -> Self {
         Self {
             provider: Some("ollama".to_string()),
             default_model: None,
+            claude_model: None,
+            openai_model: None,
+            ollama_model: None,
             claude_api_key: None,
             openai_api_key: None,
+            copy_format: None,
+            layout: None,
+            verse_of_the_day: None,
+            ollama_url: None,
+            openai_base_url: None,
+            claude_base_url: None,
+            data_dir: None,
+            sync_dir: None,
+            prompt_template: None,
+            study_reminder: None,
+            log_level: None,
+            plaintext_keys: None,
+            theme: None,
+            keybindings: None,
+            search_limit: None,
+            semantic_search: None,
+            gospel_library_language: None,
+            speech_command: None,
+            card_background: None,
+            card_font: None,
+            card_reference: None,
+            profiles: BTreeMap::new(),
+            active: None,
+            overrides: Vec::new(),
         }
     }
 
+    /// The config file, then the active profile (see `active_profile`), then
+    /// `ESCRITURAS_*` environment variables, each over the one before
     pub fn load() -> Result<Self> {
-        let config_path = Self::get_config_path()?;
-        
-        if !config_path.exists() {
-            return Ok(Self::new());
+        let mut config = Self::read(&Self::get_config_path()?)?;
+        if let Some(name) = Self::active_profile() {
+            config.apply_profile(&name);
         }
-        
-        let config_content = fs::read_to_string(&config_path)?;
-        let config: Config = serde_json::from_str(&config_content)?;
+        config.apply_env(|name| std::env::var(name).ok())?;
         Ok(config)
     }
 
+    /// The variable that overrides a setting: `ESCRITURAS_` and its name in
+    /// capitals, as `ESCRITURAS_OLLAMA_URL`. API keys have their own (see
+    /// `Provider::key_variable`).
+    pub fn variable(key: &str) -> String {
+        format!("ESCRITURAS_{}", key.to_uppercase())
+    }
+
+    /// The variable a setting was taken from on load, if any
+    pub fn overridden_by(&self, key: &str) -> Option<&str> {
+        self.overrides.iter().rev().find(|o| o.key == key).map(|o| o.variable.as_str())
+    }
+
+    /// Replace settings with those given in the environment, checked as
+    /// `set` checks them
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<()> {
+        if let Some(path) = var(PROMPT_FILE_VARIABLE).filter(|p| !p.trim().is_empty()) {
+            let template = fs::read_to_string(&path).map_err(|e| EscriturasError::io(format!("{}: could not read {}", PROMPT_FILE_VARIABLE, path), e))?;
+            self.apply_override(PROMPT_FILE_VARIABLE, "prompt_template", &template)?;
+        }
+        for &key in Self::KEYS.iter().filter(|k| !Self::is_secret(k)) {
+            let variable = Self::variable(key);
+            if let Some(value) = var(&variable).filter(|v| !v.trim().is_empty()) {
+                self.apply_override(&variable, key, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_override(&mut self, variable: &str, key: &'static str, value: &str) -> Result<()> {
+        // A model is for the provider in use, so it wins over one saved for that provider
+        let keys = match key {
+            "model" => vec![key, model_key(self.current_provider())],
+            _ => vec![key],
+        };
+        for key in keys {
+            let before = self.get(key)?;
+            self.set(key, Some(value)).map_err(|e| EscriturasError::InvalidSetting(format!("{}: {}", variable, e)))?;
+            let applied = self.get(key)?;
+            self.overrides.push(Override { variable: variable.to_string(), key, before, applied });
+        }
+        Ok(())
+    }
+
+    /// Use a profile for the rest of the run, over `ESCRITURAS_PROFILE`
+    pub fn select_profile(name: &str) {
+        let _ = SELECTED_PROFILE.set(name.to_string());
+    }
+
+    /// The profile chosen with `select_profile` or `ESCRITURAS_PROFILE`
+    pub fn active_profile() -> Option<String> {
+        SELECTED_PROFILE
+            .get()
+            .cloned()
+            .or_else(|| std::env::var(PROFILE_VARIABLE).ok())
+            .filter(|name| !name.trim().is_empty())
+    }
+
+    /// The name of the profile applied to this config, if any
+    pub fn profile_name(&self) -> Option<&str> {
+        self.active.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Replace settings with a profile's. A profile that doesn't exist yet
+    /// changes nothing, but settings saved while it's active create it.
+    fn apply_profile(&mut self, name: &str) {
+        let base = Box::new(self.clone());
+        let profile = self.profiles.get(name).cloned().unwrap_or_default();
+        if profile.provider.is_some() {
+            self.provider = profile.provider;
+        }
+        for (setting, value) in [
+            (&mut self.ollama_url, profile.ollama_url),
+            (&mut self.openai_base_url, profile.openai_base_url),
+            (&mut self.claude_base_url, profile.claude_base_url),
+            (&mut self.prompt_template, profile.prompt_template),
+        ] {
+            if value.is_some() {
+                *setting = value;
+            }
+        }
+        if let Some(model) = profile.model {
+            self.set_model(self.current_provider(), &model);
+        }
+        self.active = Some((name.to_string(), base));
+    }
+
+    /// This config as it's written. Settings from the environment are left
+    /// out unless they've been changed since. With a profile active, profile
+    /// settings that were changed or set by the profile go back into it, and
+    /// the top-level ones are left as they were.
+    fn to_file(&self) -> Config {
+        let mut config = self.clone();
+        for o in self.overrides.iter().rev() {
+            if config.get(o.key).ok().flatten() == o.applied {
+                let _ = config.set(o.key, o.before.as_deref());
+            }
+        }
+        config.overrides.clear();
+
+        let Some((name, base)) = config.active.take() else {
+            return config;
+        };
+        let old = config.profiles.get(&name).cloned().unwrap_or_default();
+        let keep = |set: &Option<String>, now: &Option<String>, before: &Option<String>| {
+            if set.is_some() || now != before { now.clone() } else { None }
+        };
+        let provider = config.current_provider();
+        let profile = Profile {
+            provider: keep(&old.provider, &config.provider, &base.provider),
+            model: keep(&old.model, &config.saved_model(provider), &base.saved_model(provider)),
+            ollama_url: keep(&old.ollama_url, &config.ollama_url, &base.ollama_url),
+            openai_base_url: keep(&old.openai_base_url, &config.openai_base_url, &base.openai_base_url),
+            claude_base_url: keep(&old.claude_base_url, &config.claude_base_url, &base.claude_base_url),
+            prompt_template: keep(&old.prompt_template, &config.prompt_template, &base.prompt_template),
+        };
+
+        config.provider = base.provider;
+        config.default_model = base.default_model;
+        config.claude_model = base.claude_model;
+        config.openai_model = base.openai_model;
+        config.ollama_model = base.ollama_model;
+        config.ollama_url = base.ollama_url;
+        config.openai_base_url = base.openai_base_url;
+        config.claude_base_url = base.claude_base_url;
+        config.prompt_template = base.prompt_template;
+        config.profiles.insert(name, profile);
+        config
+    }
+
+    /// The configured provider (Ollama when there's none)
+    pub fn current_provider(&self) -> Provider {
+        self.provider.as_deref().and_then(Provider::from_str).unwrap_or(Provider::Ollama)
+    }
+
+    /// API key for a provider: the environment variable first, then the
+    /// keychain, then the config file
+    pub fn api_key(&self, provider: Provider) -> Option<String> {
+        self.find_api_key(provider).map(|(key, _)| key)
+    }
+
+    /// An API key and where it was found
+    pub fn find_api_key(&self, provider: Provider) -> Option<(String, KeySource)> {
+        let saved = match provider {
+            Provider::Claude => &self.claude_api_key,
+            Provider::OpenAI => &self.openai_api_key,
+            Provider::Ollama => return None,
+        };
+        std::env::var(provider.key_variable()?)
+            .ok()
+            .map(|key| (key, KeySource::Environment))
+            .or_else(|| crate::keychain::get(provider).map(|key| (key, KeySource::Keychain)))
+            .or_else(|| saved.clone().map(|key| (key, KeySource::ConfigFile)))
+    }
+
+    fn saved_key(&mut self, provider: Provider) -> Option<&mut Option<String>> {
+        match provider {
+            Provider::Claude => Some(&mut self.claude_api_key),
+            Provider::OpenAI => Some(&mut self.openai_api_key),
+            Provider::Ollama => None,
+        }
+    }
+
+    /// Store a provider's API key: in the keychain when there is one (taking
+    /// it out of this config), or in this config when `plaintext_keys` allows.
+    /// The caller saves the config.
+    pub fn store_api_key(&mut self, provider: Provider, key: &str) -> Result<KeySource> {
+        let plaintext = self.plaintext_keys.unwrap_or(false);
+        let saved = self.saved_key(provider).ok_or_else(|| EscriturasError::InvalidSetting("Ollama doesn't use an API key".to_string()))?;
+        if !plaintext && crate::keychain::available() {
+            crate::keychain::set(provider, key)?;
+            *saved = None;
+            return Ok(KeySource::Keychain);
+        }
+        if !plaintext {
+            return Err(EscriturasError::Keychain(format!(
+                "No keychain is available to store the key. Set plaintext_keys to true to keep it in config.toml, or set {}",
+                provider.key_variable().unwrap_or_default()
+            )));
+        }
+        *saved = Some(key.trim().to_string());
+        Ok(KeySource::ConfigFile)
+    }
+
+    /// Forget a provider's stored API key, in the keychain and in this config
+    pub fn remove_api_key(&mut self, provider: Provider) -> Result<()> {
+        if let Some(saved) = self.saved_key(provider) {
+            *saved = None;
+        }
+        if crate::keychain::available() {
+            crate::keychain::remove(provider)?;
+        }
+        Ok(())
+    }
+
+    /// Move API keys saved in the config file into the keychain, unless
+    /// `plaintext_keys` keeps them there. Returns the providers whose keys moved.
+    pub fn migrate_keys(&mut self) -> Result<Vec<Provider>> {
+        if self.plaintext_keys.unwrap_or(false) || !crate::keychain::available() {
+            return Ok(Vec::new());
+        }
+        let mut moved = Vec::new();
+        for provider in [Provider::Claude, Provider::OpenAI] {
+            if let Some(key) = self.saved_key(provider).and_then(|saved| saved.clone()) {
+                self.store_api_key(provider, &key)?;
+                moved.push(provider);
+            }
+        }
+        Ok(moved)
+    }
+
+    /// Migrate the saved config's keys (see `migrate_keys`), saving it if any moved
+    pub fn migrate_saved_keys() -> Result<Vec<Provider>> {
+        if !Self::exists() {
+            return Ok(Vec::new());
+        }
+        let mut config = Self::load()?;
+        let moved = config.migrate_keys()?;
+        if !moved.is_empty() {
+            config.save()?;
+        }
+        Ok(moved)
+    }
+
+    pub fn ollama_url(&self) -> &str {
+        self.ollama_url.as_deref().unwrap_or(DEFAULT_OLLAMA_URL)
+    }
+
+    /// Language code of Gospel Library links (see `links::gospel_library_url`)
+    pub fn gospel_library_language(&self) -> &str {
+        self.gospel_library_language.as_deref().unwrap_or(DEFAULT_LANGUAGE)
+    }
+
+    #[cfg(feature = "ai")]
+    pub fn ollama_client(&self) -> OllamaClient {
+        OllamaClient::new(self.ollama_url())
+    }
+
+    /// OpenAI client, pointed at `openai_base_url` when it's set
+    #[cfg(feature = "ai")]
+    pub fn openai_client(&self, api_key: &str) -> OpenAIClient {
+        match &self.openai_base_url {
+            Some(url) => OpenAIClient::new(api_key).with_base_url(url),
+            None => OpenAIClient::new(api_key),
+        }
+    }
+
+    /// Claude client, pointed at `claude_base_url` when it's set
+    #[cfg(feature = "ai")]
+    pub fn claude_client(&self, api_key: &str) -> ClaudeClient {
+        match &self.claude_base_url {
+            Some(url) => ClaudeClient::new(api_key).with_base_url(url),
+            None => ClaudeClient::new(api_key),
+        }
+    }
+
+    /// Make `model` the one used with `provider`, and `default_model` too
+    /// when that's the configured provider
+    pub fn set_model(&mut self, provider: Provider, model: &str) {
+        if self.current_provider() == provider {
+            self.default_model = Some(model.to_string());
+        }
+        let saved = match provider {
+            Provider::Claude => &mut self.claude_model,
+            Provider::OpenAI => &mut self.openai_model,
+            Provider::Ollama => &mut self.ollama_model,
+        };
+        *saved = Some(model.to_string());
+    }
+
+    /// The model to use with a provider: the one last used with it, or else
+    /// the provider's own default
+    pub fn model_for(&self, provider: Provider) -> String {
+        self.saved_model(provider).unwrap_or_else(|| provider.default_model().to_string())
+    }
+
+    /// The model last used with a provider; `default_model` stands in for
+    /// the configured provider until one is recorded
+    pub fn saved_model(&self, provider: Provider) -> Option<String> {
+        let saved = match provider {
+            Provider::Claude => &self.claude_model,
+            Provider::OpenAI => &self.openai_model,
+            Provider::Ollama => &self.ollama_model,
+        };
+        match saved {
+            Some(model) => Some(model.clone()),
+            None if provider == self.current_provider() => self.default_model.clone(),
+            None => None,
+        }
+    }
+
+    /// Search filter with the configured limit and mode
+    pub fn search_defaults(&self) -> SearchFilter {
+        let mut filter = SearchFilter::default();
+        if let Some(limit) = self.search_limit {
+            filter.limit = limit;
+        }
+        if self.semantic_search == Some(false) {
+            filter.mode = SearchMode::Keyword;
+        }
+        filter
+    }
+
+    /// Opening instructions of every AI prompt: `prompt_template` when it's
+    /// set, otherwise the built-in study instructions
+    pub fn study_instructions(&self) -> String {
+        match self.prompt_template.as_deref().map(str::trim) {
+            Some(template) if !template.is_empty() => format!("{}\n\n", template),
+            _ => STUDY_INSTRUCTIONS.to_string(),
+        }
+    }
+
+    /// Settings `scriptures config` reads and changes by name
+    pub const KEYS: &'static [&'static str] = &[
+        "provider",
+        "model",
+        "claude_model",
+        "openai_model",
+        "ollama_model",
+        "claude_api_key",
+        "openai_api_key",
+        "ollama_url",
+        "openai_base_url",
+        "claude_base_url",
+        "data_dir",
+        "sync_dir",
+        "prompt_template",
+        "copy_format",
+        "verse_of_the_day",
+        "study_reminder",
+        "log_level",
+        "plaintext_keys",
+        "theme",
+        "keybindings",
+        "search_limit",
+        "semantic_search",
+        "gospel_library_language",
+        "speech_command",
+        "card_background",
+        "card_font",
+        "card_reference",
+    ];
+
+    /// Whether a setting holds a credential that shouldn't be printed in full
+    pub fn is_secret(key: &str) -> bool {
+        key.ends_with("_api_key")
+    }
+
+    fn field(&mut self, key: &str) -> Result<&mut Option<String>> {
+        Ok(match key {
+            "provider" => &mut self.provider,
+            "model" => &mut self.default_model,
+            "claude_model" => &mut self.claude_model,
+            "openai_model" => &mut self.openai_model,
+            "ollama_model" => &mut self.ollama_model,
+            "claude_api_key" => &mut self.claude_api_key,
+            "openai_api_key" => &mut self.openai_api_key,
+            "ollama_url" => &mut self.ollama_url,
+            "openai_base_url" => &mut self.openai_base_url,
+            "claude_base_url" => &mut self.claude_base_url,
+            "data_dir" => &mut self.data_dir,
+            "sync_dir" => &mut self.sync_dir,
+            "prompt_template" => &mut self.prompt_template,
+            "copy_format" => &mut self.copy_format,
+            "study_reminder" => &mut self.study_reminder,
+            "log_level" => &mut self.log_level,
+            "theme" => &mut self.theme,
+            "keybindings" => &mut self.keybindings,
+            "gospel_library_language" => &mut self.gospel_library_language,
+            "speech_command" => &mut self.speech_command,
+            "card_background" => &mut self.card_background,
+            "card_font" => &mut self.card_font,
+            "card_reference" => &mut self.card_reference,
+            _ => return Err(EscriturasError::InvalidSetting(format!("Unknown setting '{}'. Settings: {}", key, Self::KEYS.join(", ")))),
+        })
+    }
+
+    /// The on/off settings
+    fn flag(&mut self, key: &str) -> Option<&mut Option<bool>> {
+        match key {
+            "verse_of_the_day" => Some(&mut self.verse_of_the_day),
+            "plaintext_keys" => Some(&mut self.plaintext_keys),
+            "semantic_search" => Some(&mut self.semantic_search),
+            _ => None,
+        }
+    }
+
+    /// A setting by name, or None when it isn't set
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let mut config = self.clone();
+        if let Some(flag) = config.flag(key) {
+            return Ok(flag.map(|on| on.to_string()));
+        }
+        if key == "search_limit" {
+            return Ok(self.search_limit.map(|n| n.to_string()));
+        }
+        Ok(config.field(key)?.clone())
+    }
+
+    /// Change a setting by name, checking the value first; None clears it
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        let value = value.map(str::trim).filter(|v| !v.is_empty());
+        if let Some(flag) = self.flag(key) {
+            *flag = value
+                .map(|v| v.parse().map_err(|_| EscriturasError::InvalidSetting(format!("{} must be true or false", key))))
+                .transpose()?;
+            return Ok(());
+        }
+        match (key, value) {
+            ("search_limit", value) => {
+                self.search_limit = value
+                    .map(|v| match v.parse() {
+                        Ok(n @ 1..=200) => Ok(n),
+                        _ => Err(EscriturasError::InvalidSetting("search_limit must be a number from 1 to 200".to_string())),
+                    })
+                    .transpose()?;
+                return Ok(());
+            }
+            ("provider", Some(v)) if Provider::from_str(v).is_none() => {
+                return Err(EscriturasError::InvalidSetting(format!("Unknown provider '{}'. Use ollama, claude, or openai", v)));
+            }
+            ("copy_format", Some(v)) if CopyFormat::from_name(v).is_none() => {
+                let names: Vec<&str> = CopyFormat::all().iter().map(|f| f.name()).collect();
+                return Err(EscriturasError::InvalidSetting(format!("Unknown format '{}'. Use {}", v, names.join(", "))));
+            }
+            ("ollama_url" | "openai_base_url" | "claude_base_url", Some(v)) if !(v.starts_with("http://") || v.starts_with("https://")) => {
+                return Err(EscriturasError::InvalidSetting(format!("{} must start with http:// or https://", key)));
+            }
+            ("study_reminder", Some(v)) if NaiveTime::parse_from_str(v, "%H:%M").is_err() => {
+                return Err(EscriturasError::InvalidSetting("study_reminder must be a time like 07:30".to_string()));
+            }
+            ("log_level", Some(v)) => crate::logging::check_level(v)?,
+            ("gospel_library_language", Some(v)) if !(v.len() == 3 && v.bytes().all(|b| b.is_ascii_alphabetic())) => {
+                return Err(EscriturasError::InvalidSetting("gospel_library_language must be a three-letter code like eng or spa".to_string()));
+            }
+            ("card_background", Some(v)) => {
+                crate::card::parse_background(v)?;
+            }
+            ("card_reference", Some(v)) => {
+                crate::card::parse_reference_placement(v)?;
+            }
+            _ => {}
+        }
+        let value = match (key, value) {
+            ("provider", Some(v)) => Provider::from_str(v).map(|p| p.as_str().to_string()),
+            ("ollama_url" | "openai_base_url" | "claude_base_url", Some(v)) => Some(v.trim_end_matches('/').to_string()),
+            ("gospel_library_language", Some(v)) => Some(v.to_ascii_lowercase()),
+            ("card_background", Some(v)) => crate::card::parse_background(v).ok().map(|c| c.hex()),
+            (_, v) => v.map(str::to_string),
+        };
+        *self.field(key)? = value;
+        Ok(())
+    }
+
+    /// `~/.config/escrituras/config.toml`, whether or not it exists yet
+    pub fn path() -> Result<PathBuf> {
+        Self::get_config_path()
+    }
+
+    /// Whether a config file has been written yet (false on first run)
+    pub fn exists() -> bool {
+        Self::get_config_path().is_ok_and(|p| p.exists() || p.with_file_name(OLD_CONFIG_FILE).exists())
+    }
+
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::get_config_path()?;
-        
+        self.to_file().write(&Self::get_config_path()?)
+    }
+
+    /// The config file at `path`, after taking over a `config.json` beside
+    /// it (see `migrate_json`); a new config when there's neither
+    fn read(path: &Path) -> Result<Self> {
+        Self::migrate_json(path)?;
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let config_content = fs::read_to_string(path)?;
+        Ok(toml::from_str(&config_content)?)
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
         // Create config directory if it doesn't exist
-        if let Some(parent) = config_path.parent() {
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
-        
-        let config_content = serde_json::to_string_pretty(self)?;
-        fs::write(&config_path, config_content)?;
+        fs::write(path, toml::to_string_pretty(self)?)?;
         Ok(())
     }
 
-    pub fn save_default_model(model: &str) -> Result<()> {
+    /// Rewrite the `config.json` older versions kept as TOML at `path`,
+    /// leaving the JSON as `config.json.old`. Once `path` exists this does nothing.
+    fn migrate_json(path: &Path) -> Result<()> {
+        let old = path.with_file_name(OLD_CONFIG_FILE);
+        if path.exists() || !old.exists() {
+            return Ok(());
+        }
+        let config: Config = serde_json::from_str(&fs::read_to_string(&old)?)?;
+        config.write(path)?;
+        let kept = old.with_extension("json.old");
+        fs::rename(&old, &kept).map_err(|e| EscriturasError::io(format!("Could not move {} to {}", old.display(), kept.display()), e))?;
+        tracing::info!("moved settings from {} to {}", old.display(), path.display());
+        Ok(())
+    }
+
+    /// Record the model chosen for a provider, which becomes the default
+    pub fn save_model(provider: Provider, model: &str) -> Result<()> {
+        let mut config = Self::load().unwrap_or_else(|_| Self::new());
+        config.set_model(provider, model);
+        config.save()
+    }
+
+    pub fn save_copy_format(format: &str) -> Result<()> {
+        let mut config = Self::load().unwrap_or_else(|_| Self::new());
+        config.copy_format = Some(format.to_string());
+        config.save()
+    }
+
+    pub fn save_layout(layout: PaneLayout) -> Result<()> {
         let mut config = Self::load().unwrap_or_else(|_| Self::new());
-        config.default_model = Some(model.to_string());
+        config.layout = Some(layout);
         config.save()
     }
 
     fn get_config_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow!("Could not determine config directory"))?;
-        
-        Ok(config_dir.join("escrituras").join("config.json"))
+        Ok(crate::paths::config_dir()?.join("config.toml"))
+    }
+}
+
+/// Settings file of older versions, which `load` rewrites as config.toml
+const OLD_CONFIG_FILE: &str = "config.json";
+
+/// The setting holding the model saved for a provider
+fn model_key(provider: Provider) -> &'static str {
+    match provider {
+        Provider::Claude => "claude_model",
+        Provider::OpenAI => "openai_model",
+        Provider::Ollama => "ollama_model",
+    }
+}
+
+/// An API key as it can be shown: only its first and last few characters
+pub fn mask_secret(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len());
+    }
+    let (head, tail): (String, String) = (chars[..4].iter().collect(), chars[chars.len() - 4..].iter().collect());
+    format!("{}…{}", head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_and_set_by_name() {
+        let mut config = Config::new();
+        config.set("model", Some("llama3")).unwrap();
+        config.set("provider", Some("Claude")).unwrap();
+        config.set("ollama_url", Some("http://gpu-box:11434/")).unwrap();
+        config.set("verse_of_the_day", Some("false")).unwrap();
+        assert_eq!(config.get("model").unwrap().as_deref(), Some("llama3"));
+        assert_eq!(config.get("provider").unwrap().as_deref(), Some("claude"));
+        assert_eq!(config.ollama_url(), "http://gpu-box:11434");
+        assert_eq!(config.get("verse_of_the_day").unwrap().as_deref(), Some("false"));
+
+        config.set("model", None).unwrap();
+        assert_eq!(config.get("model").unwrap(), None);
+        assert!(config.set("provider", Some("gemini")).is_err());
+        assert!(config.set("copy_format", Some("html")).is_err());
+        assert!(config.set("ollama_url", Some("localhost")).is_err());
+        assert!(config.set("study_reminder", Some("7am")).is_err());
+        config.set("study_reminder", Some("07:30")).unwrap();
+        assert_eq!(config.gospel_library_language(), "eng");
+        assert!(config.set("gospel_library_language", Some("spanish")).is_err());
+        config.set("gospel_library_language", Some("SPA")).unwrap();
+        assert_eq!(config.gospel_library_language(), "spa");
+        assert!(config.get("colour").is_err());
+    }
+
+    #[test]
+    fn test_models_and_search_defaults() {
+        let mut config = Config::new();
+        config.set("provider", Some("claude")).unwrap();
+        config.set("model", Some("claude-sonnet-4-5")).unwrap();
+        assert_eq!(config.model_for(Provider::Claude), "claude-sonnet-4-5");
+        assert_eq!(config.saved_model(Provider::Ollama), None);
+        assert_eq!(config.model_for(Provider::Ollama), Provider::Ollama.default_model());
+        config.set_model(Provider::Ollama, "llama3");
+        assert_eq!(config.model_for(Provider::Ollama), "llama3");
+        assert_eq!(config.model_for(Provider::Claude), "claude-sonnet-4-5");
+
+        config.set("search_limit", Some("20")).unwrap();
+        config.set("semantic_search", Some("false")).unwrap();
+        let filter = config.search_defaults();
+        assert_eq!((filter.limit, filter.mode), (20, SearchMode::Keyword));
+        assert!(config.set("search_limit", Some("0")).is_err());
+        assert!(config.set("claude_base_url", Some("api.example.com")).is_err());
+    }
+
+    #[test]
+    fn test_profile_settings_stay_in_the_profile() {
+        let mut config = Config::new();
+        config.set("provider", Some("claude")).unwrap();
+        config.set("model", Some("claude-sonnet-4-5")).unwrap();
+        config.profiles.insert(
+            "work".to_string(),
+            Profile {
+                provider: Some("ollama".to_string()),
+                model: Some("llama3".to_string()),
+                ollama_url: Some("http://gpu-box:11434".to_string()),
+                ..Profile::default()
+            },
+        );
+
+        config.apply_profile("work");
+        assert_eq!(config.profile_name(), Some("work"));
+        assert_eq!(config.current_provider(), Provider::Ollama);
+        assert_eq!(config.model_for(Provider::Ollama), "llama3");
+        assert_eq!(config.ollama_url(), "http://gpu-box:11434");
+
+        // Changes while the profile is active are saved to it
+        config.set_model(Provider::Ollama, "gemma3");
+        config.set("prompt_template", Some("Answer briefly.")).unwrap();
+        let file = config.to_file();
+        assert_eq!(file.current_provider(), Provider::Claude);
+        assert_eq!(file.model_for(Provider::Claude), "claude-sonnet-4-5");
+        assert_eq!((file.ollama_url.as_deref(), file.prompt_template.as_deref()), (None, None));
+        let work = &file.profiles["work"];
+        assert_eq!(work.model.as_deref(), Some("gemma3"));
+        assert_eq!(work.prompt_template.as_deref(), Some("Answer briefly."));
+        assert_eq!(work.openai_base_url, None);
+
+        // A new profile is empty until something is set in it
+        let mut config = file;
+        config.apply_profile("home");
+        assert_eq!(config.current_provider(), Provider::Claude);
+        assert_eq!(config.to_file().profiles["home"], Profile::default());
+    }
+
+    #[test]
+    fn test_environment_overrides_are_not_saved() {
+        let mut config = Config::new();
+        config.set("ollama_url", Some("http://localhost:11434")).unwrap();
+        config.set_model(Provider::Ollama, "llama3");
+        let env = |name: &str| match name {
+            "ESCRITURAS_OLLAMA_URL" => Some("http://gpu-box:11434/".to_string()),
+            "ESCRITURAS_MODEL" => Some("gemma3".to_string()),
+            "ESCRITURAS_SEARCH_LIMIT" => Some("10".to_string()),
+            _ => None,
+        };
+        config.apply_env(env).unwrap();
+        assert_eq!(config.ollama_url(), "http://gpu-box:11434");
+        assert_eq!(config.model_for(Provider::Ollama), "gemma3");
+        assert_eq!(config.search_defaults().limit, 10);
+        assert_eq!(config.overridden_by("ollama_url"), Some("ESCRITURAS_OLLAMA_URL"));
+
+        // Only what was changed since loading is written
+        config.set("search_limit", Some("30")).unwrap();
+        let file = config.to_file();
+        assert_eq!(file.ollama_url.as_deref(), Some("http://localhost:11434"));
+        assert_eq!(file.model_for(Provider::Ollama), "llama3");
+        assert_eq!(file.search_limit, Some(30));
+
+        let bad = |name: &str| (name == "ESCRITURAS_PROVIDER").then(|| "gemini".to_string());
+        let error = Config::new().apply_env(bad).unwrap_err().to_string();
+        assert!(error.starts_with("ESCRITURAS_PROVIDER: Unknown provider"), "{}", error);
+    }
+
+    #[test]
+    fn test_plaintext_keys_stay_in_the_config() {
+        let mut config = Config::new();
+        config.set("plaintext_keys", Some("true")).unwrap();
+        assert_eq!(config.store_api_key(Provider::Claude, " sk-ant-abcdefghijkl ").unwrap(), KeySource::ConfigFile);
+        assert_eq!(config.claude_api_key.as_deref(), Some("sk-ant-abcdefghijkl"));
+        assert!(config.migrate_keys().unwrap().is_empty());
+        assert!(config.store_api_key(Provider::Ollama, "key").is_err());
+
+        assert_eq!(mask_secret("sk-ant-abcdefghijkl"), "sk-a…ijkl");
+        assert_eq!(mask_secret("short"), "*****");
+    }
+
+    #[test]
+    fn test_config_json_becomes_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let json = r#"{"provider": "ollama", "ollama_model": "llama3", "search_limit": 30,
+            "layout": {"nav_width": 40, "query_split": 60, "nav_hidden": false},
+            "profiles": {"work": {"provider": "claude", "model": "claude-sonnet-4-5"}}}"#;
+        fs::write(dir.path().join("config.json"), json).unwrap();
+
+        let config = Config::read(&path).unwrap();
+        assert!(path.exists() && !dir.path().join("config.json").exists());
+        assert!(dir.path().join("config.json.old").exists());
+        assert_eq!(config.current_provider(), Provider::Ollama);
+        assert_eq!(config.model_for(Provider::Ollama), "llama3");
+        assert_eq!(config.search_limit, Some(30));
+        assert_eq!(config.layout.unwrap().nav_width, 40);
+        assert_eq!(config.profiles["work"].model.as_deref(), Some("claude-sonnet-4-5"));
+
+        // What's written reads back the same, and the old file is left alone
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("provider = \"ollama\"\n") && text.contains("[layout]\n"), "{}", text);
+        fs::write(dir.path().join("config.json"), "{}").unwrap();
+        assert_eq!(Config::read(&path).unwrap().search_limit, Some(30));
     }
 }
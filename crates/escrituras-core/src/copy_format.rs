@@ -0,0 +1,174 @@
+//! Formats for copying verses to the clipboard
+//!
+//! Every UI copies through `CopyFormat::format` so the same passage produces
+//! the same text everywhere. The format is chosen by `copy_format` in the config,
+//! and the command line prints with it too (`--format`).
+
+use crate::links::{study_url, DEFAULT_LANGUAGE};
+use crate::scripture::Scripture;
+
+/// How copied verses are laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyFormat {
+    /// Reference on the first line, then the text
+    #[default]
+    Reference,
+    /// Just the verse text
+    Plain,
+    /// "text — Reference"
+    Attributed,
+    /// Markdown blockquote ending in a linked reference
+    Markdown,
+    /// Text with a numbered footnote giving the reference and link
+    Footnote,
+    /// Just the reference, for scripts
+    ReferenceOnly,
+}
+
+impl CopyFormat {
+    pub fn all() -> &'static [CopyFormat] {
+        &[
+            CopyFormat::Reference,
+            CopyFormat::Plain,
+            CopyFormat::Attributed,
+            CopyFormat::Markdown,
+            CopyFormat::Footnote,
+            CopyFormat::ReferenceOnly,
+        ]
+    }
+
+    /// Name used in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            CopyFormat::Reference => "reference",
+            CopyFormat::Plain => "plain",
+            CopyFormat::Attributed => "attributed",
+            CopyFormat::Markdown => "markdown",
+            CopyFormat::Footnote => "footnote",
+            CopyFormat::ReferenceOnly => "ref-only",
+        }
+    }
+
+    /// A format by its name, or "md" for markdown
+    pub fn from_name(name: &str) -> Option<CopyFormat> {
+        if name == "md" {
+            return Some(CopyFormat::Markdown);
+        }
+        Self::all().iter().copied().find(|f| f.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyFormat::Reference => "Reference, then text",
+            CopyFormat::Plain => "Plain text",
+            CopyFormat::Attributed => "Text — Reference",
+            CopyFormat::Markdown => "Markdown quote with link",
+            CopyFormat::Footnote => "Text with footnote",
+            CopyFormat::ReferenceOnly => "Reference only",
+        }
+    }
+
+    /// Format one verse or a consecutive passage from a single chapter
+    pub fn format(&self, verses: &[Scripture]) -> String {
+        self.format_in(verses, DEFAULT_LANGUAGE)
+    }
+
+    /// Format a passage with its links to the Gospel Library in a language
+    /// ("spa"; see `links::gospel_library_url`)
+    pub fn format_in(&self, verses: &[Scripture], language: &str) -> String {
+        let (Some(first), Some(last)) = (verses.first(), verses.last()) else {
+            return String::new();
+        };
+        let reference = passage_reference(verses);
+        let link = study_url(first, last.verse_number, language);
+        let numbered = |prefix: &str| -> String {
+            if let [verse] = verses {
+                format!("{}{}", prefix, verse.scripture_text)
+            } else {
+                verses
+                    .iter()
+                    .map(|v| format!("{}{} {}", prefix, v.verse_number, v.scripture_text))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        };
+
+        match self {
+            CopyFormat::Reference => format!("{}\n{}", reference, numbered("")),
+            CopyFormat::Plain => numbered(""),
+            CopyFormat::Attributed => {
+                let text = verses.iter().map(|v| v.scripture_text.as_str()).collect::<Vec<_>>().join(" ");
+                format!("{} — {}", text, reference)
+            }
+            CopyFormat::Markdown => match link {
+                Some(link) => format!("{}\n>\n> — [{}]({})", numbered("> "), reference, link),
+                None => format!("{}\n>\n> — {}", numbered("> "), reference),
+            },
+            CopyFormat::Footnote => match link {
+                Some(link) => format!("{}[^1]\n\n[^1]: {}, {}", numbered(""), reference, link),
+                None => format!("{}[^1]\n\n[^1]: {}", numbered(""), reference),
+            },
+            CopyFormat::ReferenceOnly => reference,
+        }
+    }
+}
+
+/// "Alma 32:21" for one verse, "Alma 32:21-23" for a passage
+pub(crate) fn passage_reference(verses: &[Scripture]) -> String {
+    match verses {
+        [] => String::new(),
+        [verse] => verse.verse_title.clone(),
+        [first, .., last] => format!(
+            "{} {}:{}-{}",
+            first.book_title, first.chapter_number, first.verse_number, last.verse_number
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(number: i32, text: &str) -> Scripture {
+        Scripture::test("1 Nephi", 3, number).short("1 Ne.").text(text)
+    }
+
+    #[test]
+    fn test_single_verse_formats() {
+        let verses = [verse(7, "I will go and do.")];
+
+        assert_eq!(CopyFormat::Reference.format(&verses), "1 Nephi 3:7\nI will go and do.");
+        assert_eq!(CopyFormat::Plain.format(&verses), "I will go and do.");
+        assert_eq!(CopyFormat::Attributed.format(&verses), "I will go and do. — 1 Nephi 3:7");
+        assert_eq!(
+            CopyFormat::Markdown.format(&verses),
+            "> I will go and do.\n>\n> — [1 Nephi 3:7](https://www.churchofjesuschrist.org/study/scriptures/bofm/1-ne/3?lang=eng&id=p7#p7)"
+        );
+        assert!(CopyFormat::Footnote.format(&verses).starts_with("I will go and do.[^1]\n\n[^1]: 1 Nephi 3:7, https://"));
+        assert_eq!(CopyFormat::ReferenceOnly.format(&verses), "1 Nephi 3:7");
+    }
+
+    #[test]
+    fn test_passage_formats() {
+        let verses = [verse(7, "I will go."), verse(8, "And it came to pass.")];
+
+        assert_eq!(
+            CopyFormat::Reference.format(&verses),
+            "1 Nephi 3:7-8\n7 I will go.\n8 And it came to pass."
+        );
+        assert_eq!(
+            CopyFormat::Attributed.format(&verses),
+            "I will go. And it came to pass. — 1 Nephi 3:7-8"
+        );
+        assert!(CopyFormat::Markdown.format(&verses).contains("&id=p7-p8#p7"));
+    }
+
+    #[test]
+    fn test_format_names_roundtrip() {
+        for format in CopyFormat::all() {
+            assert_eq!(CopyFormat::from_name(format.name()), Some(*format));
+        }
+        assert_eq!(CopyFormat::from_name("md"), Some(CopyFormat::Markdown));
+        assert_eq!(CopyFormat::from_name("nope"), None);
+    }
+}
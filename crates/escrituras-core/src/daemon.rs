@@ -0,0 +1,330 @@
+//! A background process that keeps the scriptures, embeddings, and embedding
+//! model loaded, so command-line lookups and searches don't reload them
+//!
+//! Clients connect to a Unix socket in the runtime directory and send a JSON
+//! request per line; each gets a JSON response line. When no daemon is
+//! running, `request` returns None and the caller loads the data itself and
+//! answers with the same functions the daemon uses.
+
+use crate::embeddings::EmbeddingsDb;
+use crate::error::{EscriturasError, Result};
+use crate::scripture::{Scripture, ScriptureDb, ScriptureRange};
+use crate::search::{SearchFilter, SearchHit, SearchMode};
+use crate::word_index::Concordance;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A search as the command line asks for it, before the volume and book are resolved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchRequest {
+    pub query: String,
+    pub volume: Option<String>,
+    pub book: Option<String>,
+    pub mode: SearchMode,
+    pub limit: usize,
+    /// Verses of the chapter to include before and after each hit
+    pub context: i32,
+}
+
+/// A search hit with the verses around it (just the hit without context)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextHit {
+    pub hit: SearchHit,
+    pub passage: Vec<Scripture>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    Status,
+    Lookup { references: Vec<String> },
+    Search(SearchRequest),
+    Concordance { word: String, volume: Option<String>, book: Option<String> },
+    Stop,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    /// Unix timestamp (seconds) when it started
+    pub started: u64,
+    pub verses: usize,
+    pub embeddings: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    Status(DaemonStatus),
+    Passages { passages: Vec<Vec<Scripture>> },
+    Hits { hits: Vec<ContextHit> },
+    Concordance(Concordance),
+    Stopping,
+    Error { message: String },
+}
+
+impl DaemonResponse {
+    /// The response, or its error message as an error
+    pub fn ok(self) -> Result<Self> {
+        match self {
+            DaemonResponse::Error { message } => Err(EscriturasError::Other(message)),
+            response => Ok(response),
+        }
+    }
+}
+
+/// Where the daemon listens: `daemon.sock` in the runtime directory
+pub fn socket_path() -> Result<PathBuf> {
+    Ok(crate::paths::runtime_dir()?.join("daemon.sock"))
+}
+
+/// The verses of each reference, failing on the first that doesn't parse
+pub fn lookup_passages(db: &ScriptureDb, references: &[String]) -> Result<Vec<Vec<Scripture>>> {
+    references
+        .iter()
+        .map(|r| {
+            let range = db.parse_reference(r).ok_or_else(|| EscriturasError::DataNotFound(format!("Not a reference: {}", r)))?;
+            Ok(db.get_verses_in_range(&range).into_iter().cloned().collect())
+        })
+        .collect()
+}
+
+/// The volume and book titles named on the command line, naming the one that doesn't exist
+fn resolve_scope(db: &ScriptureDb, volume: Option<&str>, book: Option<&str>) -> Result<(Option<String>, Option<String>)> {
+    let volume = volume
+        .map(|v| {
+            db.find_volume(v)
+                .map(str::to_string)
+                .ok_or_else(|| EscriturasError::DataNotFound(format!("No such volume: {} (expected one of: {})", v, db.get_volumes().join(", "))))
+        })
+        .transpose()?;
+    let book = book
+        .map(|b| db.parse_reference(b).map(|r| r.book_title).ok_or_else(|| EscriturasError::DataNotFound(format!("No such book: {}", b))))
+        .transpose()?;
+    Ok((volume, book))
+}
+
+/// Run a search, naming the volume or book that doesn't exist
+pub fn search_with_context(db: &ScriptureDb, embeddings: Option<&mut EmbeddingsDb>, request: &SearchRequest) -> Result<Vec<ContextHit>> {
+    let (volume, book) = resolve_scope(db, request.volume.as_deref(), request.book.as_deref())?;
+    let filter = SearchFilter { volume, book, mode: request.mode, limit: request.limit };
+
+    let hits = crate::search::search(db, embeddings, &request.query, &filter);
+    Ok(hits
+        .into_iter()
+        .map(|hit| {
+            let around = ScriptureRange::of_verse(&hit.scripture);
+            let passage = db.verses_around(&around, request.context, request.context).into_iter().cloned().collect();
+            ContextHit { hit, passage }
+        })
+        .collect())
+}
+
+/// Every occurrence of a single word in the volume or book
+pub fn concordance(db: &ScriptureDb, word: &str, volume: Option<&str>, book: Option<&str>) -> Result<Concordance> {
+    let (volume, book) = resolve_scope(db, volume, book)?;
+    if crate::word_index::words(word).count() != 1 {
+        return Err(EscriturasError::Other(format!("A concordance is of one word, not {:?}", word)));
+    }
+    Ok(crate::word_index::concordance(db.word_index(), db.all_verses(), word, |verse| {
+        volume.as_ref().is_none_or(|v| &verse.volume_title == v) && book.as_ref().is_none_or(|b| &verse.book_title == b)
+    }))
+}
+
+/// What the daemon holds
+pub struct DaemonState {
+    pub db: ScriptureDb,
+    pub embeddings: Option<EmbeddingsDb>,
+    pub started: u64,
+}
+
+impl DaemonState {
+    /// The response to a request (Stop is answered by the server, which then exits)
+    pub fn handle(&mut self, request: &DaemonRequest) -> DaemonResponse {
+        let result = match request {
+            DaemonRequest::Status => Ok(DaemonResponse::Status(DaemonStatus {
+                pid: std::process::id(),
+                started: self.started,
+                verses: self.db.all_verses().len(),
+                embeddings: self.embeddings.is_some(),
+            })),
+            DaemonRequest::Lookup { references } => {
+                lookup_passages(&self.db, references).map(|passages| DaemonResponse::Passages { passages })
+            }
+            DaemonRequest::Search(search) if search.mode != SearchMode::Keyword && self.embeddings.is_none() => Err(EscriturasError::EmbeddingsMissing(
+                "The daemon was started without embeddings, so it can only search by keyword. Restart it once they're installed"
+                    .to_string(),
+            )),
+            DaemonRequest::Search(search) => {
+                search_with_context(&self.db, self.embeddings.as_mut(), search).map(|hits| DaemonResponse::Hits { hits })
+            }
+            DaemonRequest::Concordance { word, volume, book } => {
+                concordance(&self.db, word, volume.as_deref(), book.as_deref()).map(DaemonResponse::Concordance)
+            }
+            DaemonRequest::Stop => Ok(DaemonResponse::Stopping),
+        };
+        result.unwrap_or_else(|e| DaemonResponse::Error { message: e.to_string() })
+    }
+}
+
+/// The running daemon's response, or None when there's no daemon to ask
+pub async fn request(request: &DaemonRequest) -> Result<Option<DaemonResponse>> {
+    match socket_path() {
+        Ok(path) => request_at(&path, request).await,
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(unix)]
+pub async fn request_at(path: &Path, request: &DaemonRequest) -> Result<Option<DaemonResponse>> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    // No socket, or one left behind by a daemon that's gone
+    let Ok(stream) = tokio::net::UnixStream::connect(path).await else {
+        return Ok(None);
+    };
+    let mut stream = BufReader::new(stream);
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.get_mut().write_all(line.as_bytes()).await?;
+
+    let mut reply = String::new();
+    if stream.read_line(&mut reply).await? == 0 {
+        return Err(EscriturasError::Other("The daemon closed the connection without answering".to_string()));
+    }
+    Ok(Some(serde_json::from_str(&reply)?))
+}
+
+#[cfg(not(unix))]
+pub async fn request_at(_path: &Path, _request: &DaemonRequest) -> Result<Option<DaemonResponse>> {
+    Ok(None)
+}
+
+/// Answer requests on the socket at `path` until a Stop request or Ctrl-C,
+/// then remove it
+#[cfg(unix)]
+pub async fn serve(state: DaemonState, path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Arc;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixListener;
+    use tokio::sync::{Mutex, Notify};
+
+    if path.exists() {
+        if tokio::net::UnixStream::connect(path).await.is_ok() {
+            return Err(EscriturasError::Other(format!("A daemon is already running ({})", path.display())));
+        }
+        std::fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(path).map_err(|e| EscriturasError::io(format!("Couldn't listen on {}", path.display()), e))?;
+    // Only this user's commands talk to it
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    let state = Arc::new(Mutex::new(state));
+    let stop = Arc::new(Notify::new());
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let (state, stop) = (Arc::clone(&state), Arc::clone(&stop));
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    let mut line = String::new();
+                    while stream.read_line(&mut line).await.unwrap_or(0) > 0 {
+                        let response = match serde_json::from_str::<DaemonRequest>(&line) {
+                            Ok(request) => state.lock().await.handle(&request),
+                            Err(e) => DaemonResponse::Error { message: format!("Bad request: {}", e) },
+                        };
+                        let mut reply = serde_json::to_string(&response).unwrap_or_default();
+                        reply.push('\n');
+                        if stream.get_mut().write_all(reply.as_bytes()).await.is_err() {
+                            break;
+                        }
+                        if matches!(response, DaemonResponse::Stopping) {
+                            stop.notify_one();
+                        }
+                        line.clear();
+                    }
+                });
+            }
+            _ = stop.notified() => break,
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    let _ = std::fs::remove_file(path);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub async fn serve(_state: DaemonState, _path: &Path) -> Result<()> {
+    Err(EscriturasError::Other("The daemon needs Unix sockets, which this system doesn't have".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(number: i32) -> Scripture {
+        let text = if number == 21 { "faith is not to have a perfect knowledge".to_string() } else { format!("verse {}", number) };
+        Scripture::test("Alma", 32, number).text(&text)
+    }
+
+    fn state() -> DaemonState {
+        DaemonState { db: ScriptureDb::from_verses((1..=30).map(verse).collect()), embeddings: None, started: 0 }
+    }
+
+    #[test]
+    fn test_handle_lookup_and_search() {
+        let mut state = state();
+        let lookup = DaemonRequest::Lookup { references: vec!["Alma 32:21-22".into(), "Alma 99".into()] };
+        assert!(matches!(state.handle(&lookup), DaemonResponse::Error { message } if message == "Not a reference: Alma 99"));
+
+        let search = SearchRequest {
+            query: "perfect knowledge".into(),
+            volume: Some("book of mormon".into()),
+            book: None,
+            mode: SearchMode::Keyword,
+            limit: 5,
+            context: 1,
+        };
+        let DaemonResponse::Hits { hits } = state.handle(&DaemonRequest::Search(search.clone())) else {
+            panic!("expected hits");
+        };
+        let passage: Vec<i32> = hits[0].passage.iter().map(|v| v.verse_number).collect();
+        assert_eq!(passage, [20, 21, 22]);
+
+        // Without embeddings the daemon can't search by meaning
+        let semantic = DaemonRequest::Search(SearchRequest { mode: SearchMode::Semantic, ..search });
+        assert!(state.handle(&semantic).ok().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_serve_answers_until_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.sock");
+        assert!(request_at(&path, &DaemonRequest::Status).await.unwrap().is_none());
+
+        let server = tokio::spawn({
+            let path = path.clone();
+            async move { serve(state(), &path).await }
+        });
+        while !path.exists() {
+            tokio::task::yield_now().await;
+        }
+        let status = request_at(&path, &DaemonRequest::Status).await.unwrap();
+        assert!(matches!(status, Some(DaemonResponse::Status(DaemonStatus { verses: 30, embeddings: false, .. }))));
+        let lookup = DaemonRequest::Lookup { references: vec!["Alma 32:21".into()] };
+        let Some(DaemonResponse::Passages { passages }) = request_at(&path, &lookup).await.unwrap() else {
+            panic!("expected passages");
+        };
+        assert_eq!(passages[0][0].verse_title, "Alma 32:21");
+
+        assert!(matches!(request_at(&path, &DaemonRequest::Stop).await.unwrap(), Some(DaemonResponse::Stopping)));
+        server.await.unwrap().unwrap();
+        assert!(!path.exists());
+    }
+}
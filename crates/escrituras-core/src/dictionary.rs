@@ -0,0 +1,135 @@
+//! Webster's 1828 dictionary lookup
+//!
+//! Noah Webster's *American Dictionary of the English Language* (1828) is
+//! contemporaneous with the Book of Mormon translation, so its definitions show
+//! what scripture words meant to readers of the time. The public-domain text is
+//! read from `webster1828.json` in the data directory: an object mapping each
+//! headword to its definition, or to a list of definitions.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{EscriturasError, Result};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Definitions {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Headword -> definitions, with headwords lowercased
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    entries: HashMap<String, Vec<String>>,
+}
+
+impl Dictionary {
+    pub const FILE_NAME: &'static str = "webster1828.json";
+
+    /// Load `webster1828.json` from a data directory
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open dictionary {:?}: {}", path, e)))?;
+        let raw: HashMap<String, Definitions> = serde_json::from_str(&content)
+            .map_err(|e| EscriturasError::ParseError(format!("Failed to parse dictionary {:?}: {}", path, e)))?;
+
+        Ok(Self::from_entries(raw.into_iter().map(|(word, defs)| {
+            let defs = match defs {
+                Definitions::One(def) => vec![def],
+                Definitions::Many(defs) => defs,
+            };
+            (word, defs)
+        })))
+    }
+
+    pub fn from_entries(entries: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for (word, defs) in entries {
+            map.entry(word.trim().to_lowercase()).or_default().extend(defs);
+        }
+        Self { entries: map }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Definitions for a word as it appears in a verse, trying its base form
+    /// when the inflected one isn't a headword ("believeth" -> "believe").
+    /// Returns the matching headword and its definitions.
+    pub fn lookup(&self, word: &str) -> Option<(&str, &[String])> {
+        Self::base_forms(&word.to_lowercase())
+            .into_iter()
+            .find_map(|form| self.entries.get_key_value(&form))
+            .map(|(headword, defs)| (headword.as_str(), defs.as_slice()))
+    }
+
+    /// The word itself, then guesses at its base form with common (and archaic) endings removed
+    fn base_forms(word: &str) -> Vec<String> {
+        let word = word.trim_matches(|c: char| !c.is_alphabetic());
+        let word = word.strip_suffix("'s").unwrap_or(word);
+        let mut forms = vec![word.to_string()];
+
+        for suffix in ["eth", "est", "ed", "ing", "es", "s", "ly"] {
+            let Some(stem) = word.strip_suffix(suffix) else {
+                continue;
+            };
+            if stem.len() < 2 {
+                continue;
+            }
+            // carrieth / carried / carries -> carry
+            if let Some(root) = stem.strip_suffix('i') {
+                forms.push(format!("{}y", root));
+            }
+            forms.push(stem.to_string());
+            forms.push(format!("{}e", stem));
+            // sinneth / stopped -> sin / stop
+            let mut chars = stem.chars().rev();
+            if let (Some(a), Some(b)) = (chars.next(), chars.next()) {
+                if a == b && !"aeiou".contains(a) {
+                    forms.push(stem[..stem.len() - a.len_utf8()].to_string());
+                }
+            }
+        }
+        forms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::from_entries(
+            ["BELIEVE", "CARRY", "SIN", "KNOW", "CHARITY", "WAX"]
+                .into_iter()
+                .map(|w| (w.to_string(), vec![format!("Definition of {}", w.to_lowercase())])),
+        )
+    }
+
+    #[test]
+    fn test_lookup_exact_and_case_insensitive() {
+        let dict = dictionary();
+        assert_eq!(dict.lookup("Charity").unwrap().0, "charity");
+        assert_eq!(dict.lookup("charity,").unwrap().1, ["Definition of charity"]);
+        assert!(dict.lookup("adieu").is_none());
+    }
+
+    #[test]
+    fn test_lookup_archaic_inflections() {
+        let dict = dictionary();
+        assert_eq!(dict.lookup("believeth").unwrap().0, "believe");
+        assert_eq!(dict.lookup("knowest").unwrap().0, "know");
+        assert_eq!(dict.lookup("carrieth").unwrap().0, "carry");
+        assert_eq!(dict.lookup("sinneth").unwrap().0, "sin");
+        assert_eq!(dict.lookup("waxed").unwrap().0, "wax");
+    }
+}
@@ -1,106 +1,323 @@
-use anyhow::{anyhow, Result};
+//! Semantic search
+//!
+//! Every verse's embedding is in `scripture_embeddings.npy`, so related verses
+//! need only that file. Searching embeds the query with the same local model
+//! (fastembed), which needs the `embeddings` feature; without it, anything
+//! that embeds text fails with `EmbeddingsMissing`.
+
+#[cfg(feature = "embeddings")]
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
-use ndarray::Array2;
+use ndarray::{Array2, Axis};
 use ndarray_npy::ReadNpyExt;
-use serde::Deserialize;
+#[cfg(feature = "embeddings")]
+use ndarray_npy::WriteNpyExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
+#[cfg(feature = "embeddings")]
+use std::{fs, io::BufWriter, path::PathBuf};
 use std::path::Path;
+use std::sync::Arc;
+#[cfg(feature = "embeddings")]
+use std::sync::{Mutex, PoisonError};
+
+use crate::error::{EscriturasError, Result};
+#[cfg(feature = "embeddings")]
+use crate::paths;
+use crate::scripture::Scripture;
+use crate::timings;
+
+/// Verses embedded per model call when generating
+#[cfg(feature = "embeddings")]
+const GENERATE_BATCH: usize = 256;
 
 /// Download the embedding model for semantic search (called during installation)
+#[cfg(feature = "embeddings")]
 pub fn download_embedding_model() -> Result<()> {
-    println!("Downloading embedding model for semantic search...");
+    // Progress goes to stderr: stdout may be an MCP client's JSON-RPC stream
+    eprintln!("Downloading embedding model for semantic search...");
 
     // Show progress since we're not in TUI mode
     let options = InitOptions::new(EmbeddingModel::BGESmallENV15)
+        .with_cache_dir(paths::model_cache_dir())
         .with_show_download_progress(true);
 
     TextEmbedding::try_new(options)
-        .map_err(|e| anyhow!("Failed to download model: {}", e))?;
+        .map_err(|e| EscriturasError::Other(format!("Failed to download model: {}", e)))?;
+
+    eprintln!("✓ Embedding model cached successfully");
+    Ok(())
+}
 
-    println!("✓ Embedding model cached successfully");
+/// Fetch the embedding model into the cache without printing (for UIs that own the terminal)
+#[cfg(feature = "embeddings")]
+pub fn cache_embedding_model() -> Result<()> {
+    TextEmbedding::try_new(InitOptions::new(EmbeddingModel::BGESmallENV15).with_cache_dir(paths::model_cache_dir()))
+        .map_err(|e| EscriturasError::Other(format!("Failed to download model: {}", e)))?;
     Ok(())
 }
 
-#[derive(Deserialize)]
+/// Where fastembed keeps the model: under `FASTEMBED_CACHE_DIR`, else in the
+/// cache directory (see `paths::model_cache_dir`)
+#[cfg(feature = "embeddings")]
+pub fn embedding_model_dir() -> PathBuf {
+    let code = TextEmbedding::get_model_info(&EmbeddingModel::BGESmallENV15)
+        .map(|info| info.model_code.clone())
+        .unwrap_or_default();
+    paths::model_cache_dir().join(format!("models--{}", code.replace('/', "--")))
+}
+
+/// Whether the model has been downloaded, so loading it won't go to the network
+#[cfg(feature = "embeddings")]
+pub fn embedding_model_cached() -> bool {
+    let Ok(snapshots) = fs::read_dir(embedding_model_dir().join("snapshots")) else {
+        return false;
+    };
+    snapshots.flatten().any(|snapshot| snapshot.path().join("onnx").join("model.onnx").exists())
+}
+
+/// The text embedded for a verse (as scripts/generate_embeddings.py does)
+pub(crate) fn embedding_text(verse: &Scripture) -> String {
+    format!("{}: {}", verse.verse_title, verse.scripture_text)
+}
+
+/// Embed every verse with the local model and write the .npy and metadata
+/// files into `data_dir`, replacing any there. `progress` is called with
+/// (verses done, total) after each batch.
+#[cfg(feature = "embeddings")]
+pub fn generate_embeddings(verses: &[Scripture], data_dir: &Path, mut progress: impl FnMut(usize, usize)) -> Result<()> {
+    if verses.is_empty() {
+        return Err(EscriturasError::Other("No verses to embed".to_string()));
+    }
+    let texts: Vec<String> = verses.iter().map(embedding_text).collect();
+    let embeddings = embed_texts(&texts, &mut progress)?;
+    fs::create_dir_all(data_dir)?;
+    write_npy(&data_dir.join(EmbeddingsDb::FILE_NAME), &embeddings)?;
+    let metadata: Vec<Metadata> = verses.iter().map(|v| Metadata { verse_title: v.verse_title.clone() }).collect();
+    serde_json::to_writer(BufWriter::new(File::create(data_dir.join(EmbeddingsDb::METADATA_FILE))?), &metadata)?;
+    Ok(())
+}
+
+/// Embed texts with the local model, a batch at a time, as one row each.
+/// `progress` is called with (texts done, total) after each batch.
+#[cfg(feature = "embeddings")]
+pub(crate) fn embed_texts(texts: &[String], mut progress: impl FnMut(usize, usize)) -> Result<Array2<f32>> {
+    let options = InitOptions::new(EmbeddingModel::BGESmallENV15)
+        .with_cache_dir(paths::model_cache_dir())
+        .with_show_download_progress(true);
+    let mut model = TextEmbedding::try_new(options).map_err(|e| EscriturasError::EmbeddingsMissing(format!("Failed to load embedding model: {}", e)))?;
+
+    let mut values = Vec::new();
+    let mut done = 0;
+    for batch in texts.chunks(GENERATE_BATCH) {
+        let embeddings = model
+            .embed(batch, None)
+            .map_err(|e| EscriturasError::Embedding(format!("Failed to embed text: {}", e)))?;
+        values.extend(embeddings.into_iter().flatten());
+        done += batch.len();
+        progress(done, texts.len());
+    }
+    Array2::from_shape_vec((texts.len(), values.len() / texts.len().max(1)), values)
+        .map_err(|e| EscriturasError::Embedding(format!("Embeddings of uneven length: {}", e)))
+}
+
+#[cfg(feature = "embeddings")]
+pub(crate) fn write_npy(path: &Path, embeddings: &Array2<f32>) -> Result<()> {
+    embeddings
+        .write_npy(BufWriter::new(File::create(path)?))
+        .map_err(|e| EscriturasError::Other(format!("Failed to write .npy file: {}", e)))
+}
+
+#[derive(Serialize, Deserialize)]
 struct Metadata {
     verse_title: String,
 }
 
-/// Embeddings database for semantic search using local ONNX model
+/// Embeddings database for semantic search using local ONNX model. Clones
+/// share the vectors and the loaded model, so a clone can search on another
+/// thread without loading either again.
+#[derive(Clone)]
 pub struct EmbeddingsDb {
-    embeddings: Array2<f32>,
-    verse_titles: Vec<String>,
-    model: Option<TextEmbedding>,
+    embeddings: Arc<Array2<f32>>,
+    verse_titles: Arc<Vec<String>>,
+    #[cfg(feature = "embeddings")]
+    model: Arc<Mutex<Option<TextEmbedding>>>,
 }
 
 impl EmbeddingsDb {
+    pub const FILE_NAME: &'static str = "scripture_embeddings.npy";
+    pub const METADATA_FILE: &'static str = "scripture_metadata.json";
+
     /// Load embeddings from .npy file and metadata from JSON
     pub fn load(data_dir: &Path) -> Result<Self> {
-        let embeddings_path = data_dir.join("scripture_embeddings.npy");
-        let metadata_path = data_dir.join("scripture_metadata.json");
+        let _stage = timings::stage("load embeddings");
+        let embeddings_path = data_dir.join(Self::FILE_NAME);
+        let metadata_path = data_dir.join(Self::METADATA_FILE);
 
         // Load embeddings from .npy file
         let embeddings_file = File::open(&embeddings_path)
-            .map_err(|e| anyhow!("Failed to open embeddings file {:?}: {}", embeddings_path, e))?;
+            .map_err(|e| EscriturasError::EmbeddingsMissing(format!("Failed to open embeddings file {:?}: {}", embeddings_path, e)))?;
         let embeddings: Array2<f32> = Array2::read_npy(embeddings_file)
-            .map_err(|e| anyhow!("Failed to read .npy file: {}", e))?;
+            .map_err(|e| EscriturasError::ParseError(format!("Failed to read .npy file: {}", e)))?;
 
         // Load metadata from JSON
         let metadata_file = File::open(&metadata_path)
-            .map_err(|e| anyhow!("Failed to open metadata file {:?}: {}", metadata_path, e))?;
+            .map_err(|e| EscriturasError::EmbeddingsMissing(format!("Failed to open metadata file {:?}: {}", metadata_path, e)))?;
         let metadata: Vec<Metadata> = serde_json::from_reader(BufReader::new(metadata_file))?;
 
         let verse_titles: Vec<String> = metadata.into_iter().map(|m| m.verse_title).collect();
 
         if embeddings.nrows() != verse_titles.len() {
-            return Err(anyhow!(
+            return Err(EscriturasError::ParseError(format!(
                 "Embeddings count ({}) doesn't match metadata count ({})",
                 embeddings.nrows(),
                 verse_titles.len()
-            ));
+            )));
         }
 
         Ok(Self {
-            embeddings,
-            verse_titles,
-            model: None,
+            embeddings: Arc::new(embeddings),
+            verse_titles: Arc::new(verse_titles),
+            #[cfg(feature = "embeddings")]
+            model: Arc::default(),
         })
     }
 
-    /// Initialize the embedding model (lazy-loaded on first query)
-    fn ensure_model(&mut self) -> Result<()> {
-        if self.model.is_none() {
-            // Model will be downloaded to ~/.cache/fastembed/ on first use (~33MB)
-            // Disable download progress to avoid corrupting TUI display
-            let options = InitOptions::new(EmbeddingModel::BGESmallENV15)
-                .with_show_download_progress(false);
-            self.model = Some(
-                TextEmbedding::try_new(options)
-                    .map_err(|e| anyhow!("Failed to load embedding model: {}", e))?,
-            );
+    /// Add embeddings for more verses, such as the library's paragraphs, one
+    /// row per title
+    pub fn append(&mut self, titles: Vec<String>, embeddings: &Array2<f32>) -> Result<()> {
+        if embeddings.nrows() != titles.len() || embeddings.ncols() != self.dimension() {
+            return Err(EscriturasError::ParseError(format!(
+                "Embeddings of {} rows by {} don't fit {} titles by {}",
+                embeddings.nrows(),
+                embeddings.ncols(),
+                titles.len(),
+                self.dimension()
+            )));
         }
+        self.embeddings = Arc::new(
+            ndarray::concatenate(Axis(0), &[self.embeddings.view(), embeddings.view()])
+                .map_err(|e| EscriturasError::ParseError(e.to_string()))?,
+        );
+        Arc::make_mut(&mut self.verse_titles).extend(titles);
         Ok(())
     }
 
-    /// Embed query text using local ONNX model
-    pub fn embed_query(&mut self, text: &str) -> Result<Vec<f32>> {
-        self.ensure_model()?;
+    /// Number of verses embedded
+    pub fn verse_count(&self) -> usize {
+        self.verse_titles.len()
+    }
 
-        let model = self.model.as_mut().unwrap();
-        let embeddings = model
-            .embed(vec![text], None)
-            .map_err(|e| anyhow!("Failed to embed query: {}", e))?;
+    /// Length of each embedding vector
+    pub fn dimension(&self) -> usize {
+        self.embeddings.ncols()
+    }
+
+    /// Mismatches between the embedded verses and the scripture data: verses
+    /// with no embedding and embeddings for verses that don't exist
+    pub fn check_index(&self, verses: &[Scripture]) -> Vec<String> {
+        let embedded: HashSet<&str> = self.verse_titles.iter().map(String::as_str).collect();
+        let known: HashSet<&str> = verses.iter().map(|v| v.verse_title.as_str()).collect();
+        let missing: Vec<&str> = verses.iter().map(|v| v.verse_title.as_str()).filter(|t| !embedded.contains(t)).collect();
+        let unknown: Vec<&str> = self.verse_titles.iter().map(String::as_str).filter(|t| !known.contains(t)).collect();
+
+        let mut problems = Vec::new();
+        if !missing.is_empty() {
+            problems.push(format!("{} verses have no embedding (first: {})", missing.len(), missing[0]));
+        }
+        if !unknown.is_empty() {
+            problems.push(format!("{} embeddings are for unknown verses (first: {})", unknown.len(), unknown[0]));
+        }
+        if embedded.len() < self.verse_titles.len() {
+            problems.push(format!("{} verses are embedded more than once", self.verse_titles.len() - embedded.len()));
+        }
+        problems
+    }
+
+    /// Re-embed `samples` verses spread through the data and report any whose
+    /// stored vector no longer matches its text (a different model or edited text)
+    pub fn check_sample(&mut self, verses: &[Scripture], samples: usize) -> Result<Vec<String>> {
+        let rows: HashMap<&str, usize> = self.verse_titles.iter().enumerate().map(|(i, t)| (t.as_str(), i)).collect();
+        let step = (verses.len() / samples.max(1)).max(1);
+        let picked: Vec<(&Scripture, usize)> = verses
+            .iter()
+            .step_by(step)
+            .filter_map(|v| rows.get(v.verse_title.as_str()).map(|&row| (v, row)))
+            .collect();
+
+        let texts: Vec<String> = picked.iter().map(|(v, _)| embedding_text(v)).collect();
+        let fresh = self.embed(texts, "verses")?;
+
+        Ok(picked
+            .iter()
+            .zip(fresh)
+            .filter_map(|((verse, row), vector)| {
+                let similarity = cosine_similarity(self.embeddings.row(*row).as_slice().unwrap(), &vector);
+                (similarity < 0.99).then(|| {
+                    format!("{} doesn't match its text (similarity {:.2})", verse.verse_title, similarity)
+                })
+            })
+            .collect())
+    }
+
+    /// Initialize the embedding model (lazy-loaded on first query, or up front
+    /// to report a missing model before searching)
+    #[cfg(feature = "embeddings")]
+    pub fn ensure_model(&mut self) -> Result<()> {
+        let mut model = self.model.lock().unwrap_or_else(PoisonError::into_inner);
+        if model.is_none() {
+            *model = Some(load_model()?);
+        }
+        Ok(())
+    }
+
+    /// Without the `embeddings` feature there's no model to load
+    #[cfg(not(feature = "embeddings"))]
+    pub fn ensure_model(&mut self) -> Result<()> {
+        Err(EscriturasError::EmbeddingsMissing(
+            "This build of escrituras has no embedding model (the `embeddings` feature)".to_string(),
+        ))
+    }
+
+    /// Embed `texts` with the model, loading it first
+    #[cfg(feature = "embeddings")]
+    fn embed(&mut self, texts: Vec<String>, what: &str) -> Result<Vec<Vec<f32>>> {
+        // Held while embedding: clones searching at once take turns with the model
+        let mut model = self.model.lock().unwrap_or_else(PoisonError::into_inner);
+        let model = match &mut *model {
+            Some(model) => model,
+            None => model.insert(load_model()?),
+        };
+        model.embed(texts, None).map_err(|e| EscriturasError::Embedding(format!("Failed to embed {}: {}", what, e)))
+    }
 
-        embeddings
+    #[cfg(not(feature = "embeddings"))]
+    fn embed(&mut self, _texts: Vec<String>, _what: &str) -> Result<Vec<Vec<f32>>> {
+        self.ensure_model().map(|()| Vec::new())
+    }
+
+    /// Embed query text using local ONNX model
+    pub fn embed_query(&mut self, text: &str) -> Result<Vec<f32>> {
+        self.embed(vec![text.to_string()], "query")?
             .into_iter()
             .next()
-            .ok_or_else(|| anyhow!("No embedding returned"))
+            .ok_or_else(|| EscriturasError::Embedding("No embedding returned".to_string()))
     }
 
     /// Find verses semantically similar to query
     /// Returns (verse_title, similarity_score) pairs sorted by similarity (highest first)
     pub fn search(&mut self, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+        self.search_where(query, limit, |_| true)
+    }
+
+    /// Semantic search over only the verse titles `keep` accepts
+    pub fn search_where(
+        &mut self,
+        query: &str,
+        limit: usize,
+        keep: impl Fn(&str) -> bool,
+    ) -> Result<Vec<(String, f32)>> {
         let query_emb = self.embed_query(query)?;
 
         // Compute cosine similarity against all embeddings
@@ -109,6 +326,7 @@ impl EmbeddingsDb {
             .rows()
             .into_iter()
             .enumerate()
+            .filter(|(i, _)| keep(&self.verse_titles[*i]))
             .map(|(i, row)| {
                 let score = cosine_similarity(row.as_slice().unwrap(), &query_emb);
                 (i, score)
@@ -124,10 +342,48 @@ impl EmbeddingsDb {
             .map(|(i, score)| (self.verse_titles[i].clone(), score))
             .collect())
     }
+
+    /// Verses most similar to a verse in the database, using its stored embedding
+    /// (no model needed). The verse itself is left out; empty if it isn't embedded.
+    pub fn similar_to(&self, verse_title: &str, limit: usize) -> Vec<(String, f32)> {
+        let Some(index) = self.verse_titles.iter().position(|t| t == verse_title) else {
+            return Vec::new();
+        };
+        let target = self.embeddings.row(index);
+        let target = target.as_slice().unwrap();
+
+        let mut scores: Vec<(usize, f32)> = self
+            .embeddings
+            .rows()
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(i, row)| (i, cosine_similarity(row.as_slice().unwrap(), target)))
+            .collect();
+        scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scores.truncate(limit);
+
+        scores
+            .into_iter()
+            .map(|(i, score)| (self.verse_titles[i].clone(), score))
+            .collect()
+    }
+}
+
+/// Load the embedding model, downloading it to the cache directory on first
+/// use (~33MB)
+#[cfg(feature = "embeddings")]
+fn load_model() -> Result<TextEmbedding> {
+    // Disable download progress to avoid corrupting TUI display
+    let options = InitOptions::new(EmbeddingModel::BGESmallENV15)
+        .with_cache_dir(paths::model_cache_dir())
+        .with_show_download_progress(false);
+    timings::time("load embedding model", || TextEmbedding::try_new(options))
+        .map_err(|e| EscriturasError::EmbeddingsMissing(format!("Failed to load embedding model: {}", e)))
 }
 
 /// Compute cosine similarity between two vectors
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
     let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
     let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
@@ -174,4 +430,41 @@ mod tests {
         let sim = cosine_similarity(&a, &b);
         assert_eq!(sim, 0.0);
     }
+
+    #[test]
+    fn test_similar_to_excludes_the_verse() {
+        let db = EmbeddingsDb {
+            embeddings: Arc::new(ndarray::arr2(&[[1.0, 0.0], [0.9, 0.1], [0.0, 1.0]])),
+            verse_titles: Arc::new(vec!["Alma 32:21".into(), "Ether 12:6".into(), "Genesis 1:1".into()]),
+            #[cfg(feature = "embeddings")]
+            model: Arc::default(),
+        };
+        let similar = db.similar_to("Alma 32:21", 5);
+        assert_eq!(similar.iter().map(|(t, _)| t.as_str()).collect::<Vec<_>>(), ["Ether 12:6", "Genesis 1:1"]);
+        assert_eq!(db.similar_to("Alma 32:21", 1).len(), 1);
+        assert!(db.similar_to("Moroni 10:4", 5).is_empty());
+    }
+
+    fn verse(number: i32) -> Scripture {
+        Scripture::test("Alma", 32, number)
+    }
+
+    #[test]
+    fn test_check_index_reports_missing_and_unknown_verses() {
+        let db = EmbeddingsDb {
+            embeddings: Arc::new(ndarray::arr2(&[[1.0, 0.0], [0.0, 1.0]])),
+            verse_titles: Arc::new(vec!["Alma 32:21".into(), "Alma 32:99".into()]),
+            #[cfg(feature = "embeddings")]
+            model: Arc::default(),
+        };
+        assert_eq!(db.dimension(), 2);
+        assert!(db.check_index(&[verse(21), verse(99)]).is_empty());
+        assert_eq!(
+            db.check_index(&[verse(21), verse(22)]),
+            [
+                "1 verses have no embedding (first: Alma 32:22)",
+                "1 embeddings are for unknown verses (first: Alma 32:99)"
+            ]
+        );
+    }
 }
@@ -0,0 +1,138 @@
+//! Errors
+//!
+//! What can go wrong in escrituras-core, by kind, so the TUI, the desktop app,
+//! and the MCP server can tell a missing API key from Ollama not running or
+//! embeddings not installed, and say what to do about it (see `hint`). The
+//! messages are what's shown; the kinds are for deciding what else to show.
+
+#[cfg(feature = "ai")]
+use reqwest::StatusCode;
+
+use crate::provider::Provider;
+
+pub type Result<T, E = EscriturasError> = std::result::Result<T, E>;
+
+/// New kinds may be added in any release, so matches need a `_` arm
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum EscriturasError {
+    /// Something asked for isn't there: scripture data, a verse, a journal entry
+    #[error("{0}")]
+    DataNotFound(String),
+    /// The provider has no API key, or turned down the one it was sent
+    #[error("{message}")]
+    ProviderAuth { provider: Provider, message: String },
+    /// The provider couldn't be reached, or is down or too busy to answer
+    #[error("{message}")]
+    ProviderUnavailable { provider: Provider, message: String },
+    /// The provider answered with an error, such as for a model it doesn't have
+    #[error("{message}")]
+    ProviderRejected { provider: Provider, message: String },
+    /// Semantic search without the embeddings or the embedding model
+    #[error("{0}")]
+    EmbeddingsMissing(String),
+    /// The embedding model failed to embed
+    #[error("{0}")]
+    Embedding(String),
+    /// A file or response that isn't what it should be
+    #[error("{0}")]
+    ParseError(String),
+    /// A setting given a value it can't have
+    #[error("{0}")]
+    InvalidSetting(String),
+    /// A file written by a newer version of escrituras
+    #[error("{0}")]
+    NewerVersion(String),
+    /// There's no keychain, or it wouldn't store or give up a key
+    #[error("{0}")]
+    Keychain(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error(transparent)]
+    Database(#[from] rusqlite::Error),
+    #[cfg(any(feature = "ai", feature = "download"))]
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<serde_json::Error> for EscriturasError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::ParseError(e.to_string())
+    }
+}
+
+impl From<toml::de::Error> for EscriturasError {
+    fn from(e: toml::de::Error) -> Self {
+        Self::ParseError(e.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for EscriturasError {
+    fn from(e: toml::ser::Error) -> Self {
+        Self::ParseError(e.to_string())
+    }
+}
+
+impl EscriturasError {
+    /// An error response from `provider`, by its status: a bad key, the
+    /// service being down or rate limited, or the request itself
+    #[cfg(feature = "ai")]
+    pub(crate) fn from_status(provider: Provider, status: StatusCode, message: String) -> Self {
+        match status.as_u16() {
+            401 | 403 => Self::ProviderAuth { provider, message },
+            429 | 500..=599 => Self::ProviderUnavailable { provider, message },
+            _ => Self::ProviderRejected { provider, message },
+        }
+    }
+
+    /// A request to `provider` that got no response
+    #[cfg(feature = "ai")]
+    pub(crate) fn unreachable(provider: Provider, e: reqwest::Error) -> Self {
+        let name = provider.display_name().split(' ').next().unwrap_or_default();
+        Self::ProviderUnavailable { provider, message: format!("Couldn't reach {}: {}", name, e) }
+    }
+
+    /// An I/O error saying what was being done ("Could not read backup.json"),
+    /// keeping its kind
+    pub(crate) fn io(context: impl std::fmt::Display, e: std::io::Error) -> Self {
+        Self::Io(std::io::Error::new(e.kind(), format!("{}: {}", context, e)))
+    }
+
+    /// What to do about it, where there's something to suggest
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::ProviderAuth { provider, .. } => provider.key_variable().map(|variable| {
+                format!("Set {} or save a key with `scriptures config set {}_api_key <key>`", variable, provider.as_str())
+            }),
+            Self::ProviderUnavailable { provider: Provider::Ollama, .. } => {
+                Some("Start Ollama with `ollama serve`, or set ollama_url to where it runs".to_string())
+            }
+            Self::ProviderUnavailable { .. } => Some("Check your connection, or try again in a minute".to_string()),
+            Self::ProviderRejected { provider: Provider::Ollama, .. } => {
+                Some("Check the model name; `ollama list` shows the models installed".to_string())
+            }
+            Self::EmbeddingsMissing(_) => Some("Install them with `scriptures embeddings download`".to_string()),
+            Self::NewerVersion(_) => Some("Update escrituras to read it".to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ai"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_errors_by_status() {
+        let error = |status| EscriturasError::from_status(Provider::Claude, status, "Claude API error".to_string());
+        assert!(matches!(error(StatusCode::UNAUTHORIZED), EscriturasError::ProviderAuth { .. }));
+        assert!(matches!(error(StatusCode::TOO_MANY_REQUESTS), EscriturasError::ProviderUnavailable { .. }));
+        assert!(matches!(error(StatusCode::NOT_FOUND), EscriturasError::ProviderRejected { .. }));
+        assert_eq!(error(StatusCode::UNAUTHORIZED).to_string(), "Claude API error");
+        assert!(error(StatusCode::UNAUTHORIZED).hint().unwrap().contains("ANTHROPIC_API_KEY"));
+        assert_eq!(error(StatusCode::NOT_FOUND).hint(), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! Study events
+//!
+//! What happens while someone studies, announced to whoever subscribed, so
+//! stats, sync, or an outside tool can follow along without every UI calling
+//! each of them. Core announces what it does itself: a search through
+//! `search::search` (but not the as-you-type `search::preview`), an answer
+//! streamed from an AI provider, a note written with `Journal::add_note`. The
+//! UIs announce what only they see, such as the verse being read or a journal
+//! entry written in an editor.
+//!
+//! Subscribers run on the thread that emits, in the order they subscribed, so
+//! they should be quick and hand anything slow to a thread or channel of
+//! their own.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::search::SearchMode;
+
+/// Something that happened. New kinds may be added in any release.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Event {
+    /// A verse came up to be read ("Alma 32:21")
+    VerseViewed { verse_title: String },
+    /// A search was run, and how many verses it found
+    SearchPerformed { query: String, mode: SearchMode, hits: usize },
+    /// An AI provider finished streaming an answer
+    AiQueryCompleted { provider: String, model: String, answer_chars: usize },
+    /// A journal entry was written, about these passages
+    NoteAdded { path: PathBuf, references: Vec<String> },
+}
+
+type Subscriber = Arc<dyn Fn(&Event) + Send + Sync>;
+
+static SUBSCRIBERS: RwLock<Vec<(SubscriptionId, Subscriber)>> = RwLock::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A subscription, to `unsubscribe` with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Call `subscriber` with every event from now on
+pub fn subscribe(subscriber: impl Fn(&Event) + Send + Sync + 'static) -> SubscriptionId {
+    let id = SubscriptionId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    SUBSCRIBERS.write().unwrap_or_else(PoisonError::into_inner).push((id, Arc::new(subscriber)));
+    id
+}
+
+/// Stop calling a subscriber. Returns whether it was still subscribed.
+pub fn unsubscribe(id: SubscriptionId) -> bool {
+    let mut subscribers = SUBSCRIBERS.write().unwrap_or_else(PoisonError::into_inner);
+    let before = subscribers.len();
+    subscribers.retain(|(s, _)| *s != id);
+    subscribers.len() < before
+}
+
+/// Announce an event to every subscriber
+pub fn emit(event: Event) {
+    // Called without the lock held, so a subscriber can subscribe or emit too
+    let subscribers: Vec<Subscriber> =
+        SUBSCRIBERS.read().unwrap_or_else(PoisonError::into_inner).iter().map(|(_, s)| s.clone()).collect();
+    if subscribers.is_empty() {
+        return;
+    }
+    tracing::trace!(?event, "Event");
+    for subscriber in subscribers {
+        subscriber(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_subscribers_hear_events_until_unsubscribed() {
+        // Other tests emit too, so only this test's verse counts
+        let heard = Arc::new(Mutex::new(Vec::new()));
+        let id = subscribe({
+            let heard = heard.clone();
+            move |event| {
+                if matches!(event, Event::VerseViewed { verse_title } if verse_title == "Events 1:1") {
+                    heard.lock().unwrap().push(event.clone());
+                }
+            }
+        });
+        let viewed = || emit(Event::VerseViewed { verse_title: "Events 1:1".to_string() });
+
+        viewed();
+        assert_eq!(heard.lock().unwrap().len(), 1);
+        assert!(unsubscribe(id));
+        viewed();
+        assert_eq!(heard.lock().unwrap().len(), 1);
+        assert!(!unsubscribe(id));
+
+        let json = serde_json::to_string(&heard.lock().unwrap()[0]).unwrap();
+        assert_eq!(json, r#"{"event":"verse_viewed","verse_title":"Events 1:1"}"#);
+    }
+}
@@ -0,0 +1,442 @@
+//! Exports of scripture for other study tools
+//!
+//! Anki imports plain text with a few `#` header lines naming the separator,
+//! note type, and tags column, so cards are written as tab-separated text
+//! rather than a packaged `.apkg` collection.
+//!
+//! The Obsidian vault is a Markdown note per chapter, named like the chapter
+//! ("Alma 32") so `[[Alma 32]]` links work from anywhere in the vault, with a
+//! block anchor on each verse (`[[Alma 32#^v21]]`).
+//!
+//! Handouts are single Markdown documents: a chapter, the saved scriptures, or
+//! a chat conversation, ready to print or convert. Chapters and saved
+//! scriptures link to the Gospel Library, as do vault notes (`source`). A
+//! handout can also be laid out as a PDF, written here in the standard Times
+//! fonts every PDF reader has, so there's no font to embed.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::card::wrap;
+use crate::copy_format::CopyFormat;
+use crate::footnotes::FootnoteDb;
+use crate::links::{chapter_url, DEFAULT_LANGUAGE};
+use crate::scripture::{chapter_label, Scripture, ScriptureDb};
+use crate::state::{ChatMessage, ChatRole, SavedScriptures};
+
+/// Most cloze deletions in one verse
+const MAX_CLOZES: usize = 3;
+
+/// Words too common to be worth a cloze deletion
+pub(crate) const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "against", "because", "before", "being", "shall", "should", "their", "there",
+    "these", "they", "thereof", "those", "through", "unto", "which", "while", "would", "yea",
+];
+
+/// Anki note type of exported cards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnkiNoteType {
+    /// Reference on the front, text on the back
+    Basic,
+    /// Text with key words as cloze deletions, reference as the extra
+    Cloze,
+}
+
+/// Cards for Anki's File → Import, one per verse, with the verse's tags
+pub fn anki_cards(verses: &[(Scripture, Vec<String>)], note_type: AnkiNoteType) -> String {
+    let name = match note_type {
+        AnkiNoteType::Basic => "Basic",
+        AnkiNoteType::Cloze => "Cloze",
+    };
+    let mut out = format!("#separator:tab\n#html:false\n#notetype:{}\n#tags column:3\n", name);
+    for (verse, tags) in verses {
+        let text = field(&verse.scripture_text);
+        let (first, second) = match note_type {
+            AnkiNoteType::Basic => (field(&verse.verse_title), text),
+            AnkiNoteType::Cloze => (cloze(&text), field(&verse.verse_title)),
+        };
+        // Anki tags can't contain spaces
+        let tags: Vec<String> = tags.iter().map(|t| t.replace(char::is_whitespace, "_")).collect();
+        out.push_str(&format!("{}\t{}\t{}\n", first, second, tags.join(" ")));
+    }
+    out
+}
+
+/// Text safe for one tab-separated field
+fn field(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The text with its longest uncommon words as cloze deletions (one card
+/// each), about one per eight words
+fn cloze(text: &str) -> String {
+    let words: Vec<&str> = text.split(' ').collect();
+    let bare = |word: &str| word.trim_matches(|c: char| !c.is_alphanumeric()).to_string();
+
+    let mut candidates: Vec<usize> = (0..words.len())
+        .filter(|&i| {
+            let word = bare(words[i]);
+            word.chars().count() >= 5 && !STOP_WORDS.contains(&word.to_lowercase().as_str())
+        })
+        .collect();
+    // Longest first; earlier words win ties so the choice is stable
+    candidates.sort_by_key(|&i| std::cmp::Reverse(bare(words[i]).chars().count()));
+    candidates.truncate((words.len() / 8).clamp(1, MAX_CLOZES));
+    candidates.sort();
+
+    let mut out: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+    for (n, &i) in candidates.iter().enumerate() {
+        let word = bare(words[i]);
+        out[i] = words[i].replacen(&word, &format!("{{{{c{}::{}}}}}", n + 1, word), 1);
+    }
+    out.join(" ")
+}
+
+/// A chapter as a handout: its title and link, then each verse numbered in
+/// bold. None when there's no such chapter.
+pub fn chapter_markdown(db: &ScriptureDb, book: &str, chapter: i32) -> Option<String> {
+    let verses = db.get_verses_for_chapter(book, chapter);
+    let first = verses.first()?;
+    let mut out = format!("# {} {}\n\n*{} · {}*", book, chapter, first.volume_title, chapter_label(book, chapter));
+    if let Some(url) = chapter_url(first, DEFAULT_LANGUAGE) {
+        out.push_str(&format!(" · [Gospel Library]({})", url));
+    }
+    out.push_str("\n\n");
+    for verse in &verses {
+        out.push_str(&format!("**{}** {}\n\n", verse.verse_number, verse.scripture_text));
+    }
+    Some(out)
+}
+
+/// The saved scriptures as quotes linked to the Gospel Library, each with its tags
+pub fn saved_markdown(saved: &SavedScriptures) -> String {
+    let mut out = String::from("# Saved Scriptures\n\n");
+    for verse in &saved.verses {
+        out.push_str(&CopyFormat::Markdown.format(std::slice::from_ref(verse)));
+        out.push_str("\n\n");
+        if let Some(tags) = saved.tags.get(&verse.verse_title).filter(|t| !t.is_empty()) {
+            let tags: Vec<String> = tags.iter().map(|t| format!("#{}", t)).collect();
+            out.push_str(&format!("{}\n\n", tags.join(" ")));
+        }
+    }
+    out
+}
+
+/// A conversation as Markdown, each question a heading over its answer
+pub fn chat_markdown(messages: &[ChatMessage]) -> String {
+    let mut out = String::new();
+    for message in messages {
+        match message.role {
+            ChatRole::User => out.push_str(&format!("### {}\n\n", message.content.trim())),
+            ChatRole::Assistant => out.push_str(&format!("{}\n\n", message.content.trim())),
+        }
+    }
+    out
+}
+
+/// Letter paper, in points
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const PAGE_MARGIN: f32 = 72.0;
+
+/// A handout as a PDF on Letter pages: headings in bold, quotes indented in
+/// italics, and everything else as paragraphs, with Markdown's emphasis
+/// dropped and links reduced to their text
+pub fn handout_pdf(markdown: &str) -> Vec<u8> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut y = PAGE_HEIGHT - PAGE_MARGIN;
+    for block in markdown.split("\n\n").map(str::trim).filter(|b| !b.is_empty()) {
+        let (font, size, indent) = pdf_style(block);
+        let line_height = size * 1.4;
+        // Times averages about half an em a character
+        let columns = ((PAGE_WIDTH - 2.0 * PAGE_MARGIN - indent) / (size * 0.5)) as usize;
+        for line in block.lines().map(pdf_plain).filter(|l| !l.is_empty()) {
+            for line in wrap(&line, columns) {
+                if y - line_height < PAGE_MARGIN {
+                    pages.push(std::mem::take(&mut page));
+                    y = PAGE_HEIGHT - PAGE_MARGIN;
+                }
+                y -= line_height;
+                page.push_str(&format!("BT /{} {} Tf {} {:.1} Td ({}) Tj ET\n", font, size, PAGE_MARGIN + indent, y, pdf_string(&line)));
+            }
+        }
+        y -= size * 0.6;
+    }
+    pages.push(page);
+
+    let font = |name: &str| format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", name);
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        // The page tree, once the pages are numbered
+        String::new(),
+        font("Times-Roman"),
+        font("Times-Bold"),
+        font("Times-Italic"),
+    ];
+    let mut kids = Vec::new();
+    for content in pages {
+        let number = objects.len() + 1;
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            number + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+        kids.push(format!("{} 0 R", number));
+    }
+    objects[1] = format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), kids.len());
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref));
+    pdf.into_bytes()
+}
+
+/// The font (F1 roman, F2 bold, F3 italic), size, and indent of a block
+fn pdf_style(block: &str) -> (&'static str, f32, f32) {
+    let level = block.chars().take_while(|&c| c == '#').count();
+    match level {
+        1 => ("F2", 20.0, 0.0),
+        2 => ("F2", 16.0, 0.0),
+        3..=6 => ("F2", 13.0, 0.0),
+        _ if block.starts_with('>') => ("F3", 11.0, 18.0),
+        _ => ("F1", 11.0, 0.0),
+    }
+}
+
+/// A line of Markdown without its heading or quote marks, emphasis, or link addresses
+fn pdf_plain(line: &str) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap());
+    let line = line.trim_start_matches('#').trim_start_matches('>').trim();
+    link.replace_all(line, "$1").replace(['*', '`'], "")
+}
+
+/// Text as a PDF string literal's contents in WinAnsiEncoding, with anything
+/// it can't encode as `?`
+fn pdf_string(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        let byte = match c {
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{2026}' => 0x85,
+            c if (' '..='~').contains(&c) || ('\u{A0}'..='\u{FF}').contains(&c) => c as u8,
+            _ => b'?',
+        };
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(byte as char);
+            }
+            b' '..=b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    out
+}
+
+/// A note of an exported vault
+#[derive(Debug, Clone)]
+pub struct VaultFile {
+    /// Relative to the vault folder: `Book of Mormon/Alma/Alma 32.md`
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// The scriptures as Obsidian notes: an index of the volumes and books, a note
+/// per book listing its chapters, and a note per chapter whose footnote
+/// cross-references link to the verses they cite
+pub fn vault_files(db: &ScriptureDb, footnotes: Option<&FootnoteDb>) -> Vec<VaultFile> {
+    let mut files = Vec::new();
+    let mut index = String::from("# Scriptures\n");
+    // Footnote references repeat a lot and parsing each is slow
+    let mut links: HashMap<String, Option<String>> = HashMap::new();
+
+    for volume in db.get_volumes() {
+        index.push_str(&format!("\n## {}\n\n", volume));
+        for book in db.get_books_for_volume(volume) {
+            index.push_str(&format!("- [[{}]]\n", book));
+            let dir = PathBuf::from(volume.as_str()).join(book.as_str());
+            let chapters = db.get_chapters_for_book(&book);
+
+            let mut book_note = format!("# {}\n\n*{}*\n\n", book, volume);
+            for chapter in &chapters {
+                book_note.push_str(&format!("- [[{} {}]]\n", book, chapter));
+            }
+            files.push(VaultFile { path: dir.join(format!("{}.md", book)), content: book_note });
+
+            for (i, &chapter) in chapters.iter().enumerate() {
+                let verses = db.get_verses_for_chapter(&book, chapter);
+                let mut note = format!("---\nvolume: {}\nbook: {}\nchapter: {}\n", volume, book, chapter);
+                if let Some(url) = verses.first().and_then(|v| chapter_url(v, DEFAULT_LANGUAGE)) {
+                    note.push_str(&format!("source: {}\n", url));
+                }
+                note.push_str("---\n\n");
+                note.push_str(&format!("# {} {}\n\n", book, chapter));
+
+                let mut nav = Vec::new();
+                if let Some(prev) = i.checked_sub(1).map(|p| chapters[p]) {
+                    nav.push(format!("[[{} {}|← {} {}]]", book, prev, book, prev));
+                }
+                nav.push(format!("[[{}]]", book));
+                if let Some(next) = chapters.get(i + 1) {
+                    nav.push(format!("[[{} {}|{} {} →]]", book, next, book, next));
+                }
+                note.push_str(&format!("{}\n\n", nav.join(" · ")));
+
+                let mut notes = String::new();
+                for verse in verses {
+                    note.push_str(&format!("**{}** {} ^v{}\n\n", verse.verse_number, verse.scripture_text, verse.verse_number));
+                    for footnote in footnotes.map_or(&[][..], |f| f.for_verse(&verse.verse_title)) {
+                        let references: Vec<String> = footnote
+                            .references
+                            .iter()
+                            .map(|r| {
+                                links
+                                    .entry(r.clone())
+                                    .or_insert_with(|| vault_link(db, r))
+                                    .clone()
+                                    .unwrap_or_else(|| r.clone())
+                            })
+                            .collect();
+                        let word = if footnote.word.is_empty() { String::new() } else { format!(" *{}*", footnote.word) };
+                        notes.push_str(&format!(
+                            "- [[#^v{}|{}{}]]{}: {}\n",
+                            verse.verse_number,
+                            verse.verse_number,
+                            footnote.marker,
+                            word,
+                            references.join("; ")
+                        ));
+                    }
+                }
+                if !notes.is_empty() {
+                    note.push_str(&format!("## Cross-references\n\n{}", notes));
+                }
+                files.push(VaultFile { path: dir.join(format!("{} {}.md", book, chapter)), content: note });
+            }
+        }
+    }
+    files.push(VaultFile { path: PathBuf::from("Scriptures.md"), content: index });
+    files
+}
+
+/// A wiki-link to the chapter or first verse a reference names, shown as the
+/// reference; None for study-aid entries ("TG Faith") and unknown books
+fn vault_link(db: &ScriptureDb, reference: &str) -> Option<String> {
+    let range = db.parse_reference(reference)?;
+    let chapter = format!("{} {}", range.book_title, range.chapter_number);
+    Some(if reference.contains(':') {
+        format!("[[{}#^v{}|{}]]", chapter, range.start_verse, reference)
+    } else {
+        format!("[[{}|{}]]", chapter, reference)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(number: i32, text: &str) -> Scripture {
+        Scripture::test("Alma", 32, number).text(text)
+    }
+
+    #[test]
+    fn test_anki_cards() {
+        let verses = [(verse(21, "Faith is not to have a perfect knowledge\tof things;"), vec!["seminary prep".to_string()])];
+        assert_eq!(
+            anki_cards(&verses, AnkiNoteType::Basic),
+            "#separator:tab\n#html:false\n#notetype:Basic\n#tags column:3\n\
+             Alma 32:21\tFaith is not to have a perfect knowledge of things;\tseminary_prep\n"
+        );
+        assert!(anki_cards(&verses, AnkiNoteType::Cloze)
+            .ends_with("Faith is not to have a perfect {{c1::knowledge}} of things;\tAlma 32:21\tseminary_prep\n"));
+    }
+
+    #[test]
+    fn test_vault_links_chapters_and_cross_references() {
+        let mut verses = vec![verse(21, "Faith is not"), verse(22, "And now")];
+        verses.push(Scripture { chapter_number: 33, verse_number: 1, verse_title: "Alma 33:1".into(), ..verse(1, "Now") });
+        let db = ScriptureDb::from_verses(verses);
+        let footnotes = FootnoteDb::from_json(
+            r#"{"Alma 32:21": [{"marker": "a", "word": "faith", "references": ["Alma 33:1", "TG Faith"]}]}"#,
+        )
+        .unwrap();
+
+        let files = vault_files(&db, Some(&footnotes));
+        let paths: Vec<String> = files.iter().map(|f| f.path.display().to_string()).collect();
+        assert_eq!(
+            paths,
+            [
+                "Book of Mormon/Alma/Alma.md",
+                "Book of Mormon/Alma/Alma 32.md",
+                "Book of Mormon/Alma/Alma 33.md",
+                "Scriptures.md"
+            ]
+        );
+        let chapter = &files[1].content;
+        assert!(chapter.contains("source: https://www.churchofjesuschrist.org/study/scriptures/bofm/alma/32?lang=eng\n"));
+        assert!(chapter.contains("[[Alma]] · [[Alma 33|Alma 33 →]]\n"));
+        assert!(chapter.contains("**21** Faith is not ^v21\n"));
+        assert!(chapter.contains("- [[#^v21|21a]] *faith*: [[Alma 33#^v1|Alma 33:1]]; TG Faith\n"));
+    }
+
+    #[test]
+    fn test_chapter_and_saved_handouts() {
+        let db = ScriptureDb::from_verses(vec![verse(21, "Faith is not"), verse(22, "And now")]);
+        assert_eq!(
+            chapter_markdown(&db, "Alma", 32).unwrap(),
+            "# Alma 32\n\n*Book of Mormon · Chapter 32* · \
+             [Gospel Library](https://www.churchofjesuschrist.org/study/scriptures/bofm/alma/32?lang=eng)\n\n\
+             **21** Faith is not\n\n**22** And now\n\n"
+        );
+        assert!(chapter_markdown(&db, "Alma", 40).is_none());
+
+        let mut saved = SavedScriptures::default();
+        saved.add([verse(21, "Faith is not")]);
+        saved.set_tags("Alma 32:21", vec!["faith".to_string()]);
+        let handout = saved_markdown(&saved);
+        assert!(handout.starts_with("# Saved Scriptures\n\n> Faith is not\n>\n> — [Alma 32:21]("));
+        assert!(handout.ends_with(")\n\n#faith\n\n"));
+    }
+
+    #[test]
+    fn test_handout_pdf() {
+        let pdf = String::from_utf8(handout_pdf("# Alma 32\n\n**21** Faith (is) not — [a link](https://example.com)")).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.contains("/F2 20 Tf 72 692.0 Td (Alma 32) Tj"));
+        assert!(pdf.contains("(21 Faith \\(is\\) not \\227 a link) Tj"));
+        assert!(pdf.contains("/Count 1"));
+        // Every object is where the cross-reference table says
+        let xref = pdf.rfind("startxref\n").unwrap() + "startxref\n".len();
+        let xref: usize = pdf[xref..].lines().next().unwrap().parse().unwrap();
+        for (i, entry) in pdf[xref..].lines().skip(3).take_while(|l| l.ends_with(" n ")).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+
+        let long = vec!["Verse text that goes on and on"; 80].join("\n\n");
+        assert!(String::from_utf8(handout_pdf(&long)).unwrap().contains("/Count 3"));
+    }
+}
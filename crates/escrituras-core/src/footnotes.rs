@@ -0,0 +1,92 @@
+//! Verse footnotes and cross-references
+//!
+//! The scripture text doesn't carry the study footnotes, so they're read from an
+//! optional `footnotes.json` in the data directory, keyed by verse title:
+//!
+//! ```json
+//! { "Alma 32:21": [{ "marker": "a", "word": "faith", "references": ["TG Faith", "Heb. 11:1"] }] }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{EscriturasError, Result};
+
+/// One lettered footnote on a word of a verse
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Footnote {
+    /// Superscript letter ("a", "b", ...)
+    pub marker: String,
+    /// The word or phrase the footnote is attached to
+    #[serde(default)]
+    pub word: String,
+    /// Cross-references ("Heb. 11:1") and study-aid entries ("TG Faith")
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FootnoteDb {
+    verses: HashMap<String, Vec<Footnote>>,
+}
+
+impl FootnoteDb {
+    pub const FILE_NAME: &'static str = "footnotes.json";
+
+    /// Load `footnotes.json` from a data directory
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open footnotes {:?}: {}", path, e)))?;
+        Self::from_json(&content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        let verses = serde_json::from_str(content)?;
+        Ok(Self { verses })
+    }
+
+    /// Footnotes of a verse by title ("Alma 32:21"), in marker order
+    pub fn for_verse(&self, verse_title: &str) -> &[Footnote] {
+        self.verses.get(verse_title).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Add footnotes from elsewhere (such as the hymns' cross-references)
+    /// after a verse's own
+    pub fn extend(&mut self, notes: impl IntoIterator<Item = (String, Footnote)>) {
+        for (verse_title, note) in notes {
+            self.verses.entry(verse_title).or_default().push(note);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.verses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verses.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footnotes_by_verse() {
+        let db = FootnoteDb::from_json(
+            r#"{"Alma 32:21": [
+                {"marker": "a", "word": "faith", "references": ["TG Faith", "Heb. 11:1"]},
+                {"marker": "b", "references": ["Ether 12:6"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let notes = db.for_verse("Alma 32:21");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].references, ["TG Faith", "Heb. 11:1"]);
+        assert_eq!(notes[1].word, "");
+        assert!(db.for_verse("Alma 32:22").is_empty());
+    }
+}
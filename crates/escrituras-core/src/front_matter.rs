@@ -0,0 +1,156 @@
+//! Book introductions and front matter
+//!
+//! The scripture JSON has only verses, so what's printed before them (title
+//! pages, introductions, the testimonies of the witnesses, book headings) is
+//! read from an optional `front_matter.json` in the data directory. It maps
+//! each book to the sections printed before its first chapter:
+//!
+//! ```json
+//! { "1 Nephi": [{ "title": "The Testimony of Three Witnesses", "paragraphs": ["Be it known unto all nations..."] }] }
+//! ```
+//!
+//! Sections become chapter 0 of their book ("1 Nephi 0"), which Browse lists as
+//! its introduction: each section's title is an entry of its own, followed by
+//! one per paragraph, numbered on through the chapter like verses.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{EscriturasError, Result};
+use crate::scripture::{Scripture, ScriptureDb};
+use crate::timings;
+
+/// Chapter number of front matter
+pub const FRONT_MATTER_CHAPTER: i32 = 0;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FrontMatterSection {
+    pub title: String,
+    pub paragraphs: Vec<String>,
+}
+
+/// Front matter by book title
+#[derive(Debug, Clone, Default)]
+pub struct FrontMatter {
+    books: HashMap<String, Vec<FrontMatterSection>>,
+}
+
+impl FrontMatter {
+    pub const FILE_NAME: &'static str = "front_matter.json";
+
+    /// Load `front_matter.json` from a data directory
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let _stage = timings::stage("load front matter");
+        let path = data_dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open front matter {:?}: {}", path, e)))?;
+        Self::from_json(&content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        Ok(Self { books: serde_json::from_str(content)? })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+
+    /// The sections printed before a book's first chapter
+    pub fn sections(&self, book: &str) -> &[FrontMatterSection] {
+        self.books.get(book).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Put each book's front matter before its first chapter, as chapter 0.
+    /// Books the scriptures don't have are left out.
+    pub fn insert_into(&self, db: &mut ScriptureDb) {
+        let mut verses: Vec<Scripture> = Vec::with_capacity(db.all_verses().len() + self.books.len() * 8);
+        for verse in db.all_verses() {
+            let starts_book = verses.last().is_none_or(|last| last.book_title != verse.book_title);
+            if starts_book && verse.chapter_number != FRONT_MATTER_CHAPTER {
+                verses.extend(self.as_scriptures(verse));
+            }
+            verses.push(verse.clone());
+        }
+        let unknown: Vec<&String> = self.books.keys().filter(|book| db.get_chapters_for_book(book).is_empty()).collect();
+        if !unknown.is_empty() {
+            tracing::warn!("Front matter for books that aren't in the scriptures: {:?}", unknown);
+        }
+        *db = ScriptureDb::from_verses(verses);
+    }
+
+    /// A book's front matter in the shape of verses, titled like the book's
+    /// first verse `first`
+    fn as_scriptures(&self, first: &Scripture) -> Vec<Scripture> {
+        let entries = self.sections(&first.book_title).iter().flat_map(|s| std::iter::once(&s.title).chain(&s.paragraphs));
+        entries
+            .enumerate()
+            .map(|(i, text)| {
+                let number = i as i32 + 1;
+                Scripture {
+                    volume_title: first.volume_title.clone(),
+                    book_title: first.book_title.clone(),
+                    book_short_title: first.book_short_title.clone(),
+                    chapter_number: FRONT_MATTER_CHAPTER,
+                    verse_number: number,
+                    verse_title: format!("{} {}:{}", first.book_title, FRONT_MATTER_CHAPTER, number),
+                    verse_short_title: format!("{} {}:{}", first.book_short_title, FRONT_MATTER_CHAPTER, number),
+                    scripture_text: text.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> ScriptureDb {
+        let verse = |book: &str, short: &str, chapter, number| Scripture {
+            volume_title: "Book of Mormon".into(),
+            book_title: book.into(),
+            book_short_title: short.into(),
+            chapter_number: chapter,
+            verse_number: number,
+            verse_title: format!("{} {}:{}", book, chapter, number),
+            verse_short_title: format!("{} {}:{}", short, chapter, number),
+            scripture_text: String::new(),
+        };
+        ScriptureDb::from_verses(vec![verse("1 Nephi", "1 Ne.", 1, 1), verse("1 Nephi", "1 Ne.", 1, 2), verse("Enos", "Enos", 1, 1)])
+    }
+
+    #[test]
+    fn test_front_matter_becomes_chapter_zero() {
+        let front_matter = FrontMatter::from_json(
+            r#"{
+                "1 Nephi": [
+                    { "title": "The Testimony of Three Witnesses", "paragraphs": ["Be it known unto all nations", "And the honor be to the Father"] },
+                    { "title": "The First Book of Nephi", "paragraphs": ["An account of Lehi and his wife Sariah"] }
+                ],
+                "Genesis": [{ "title": "Genesis", "paragraphs": [] }]
+            }"#,
+        )
+        .unwrap();
+        let mut db = db();
+        front_matter.insert_into(&mut db);
+
+        assert_eq!(db.get_chapters_for_book("1 Nephi"), [0, 1]);
+        let intro: Vec<&str> = db.get_verses_for_chapter("1 Nephi", 0).iter().map(|v| v.scripture_text.as_str()).collect();
+        assert_eq!(intro[0], "The Testimony of Three Witnesses");
+        assert_eq!(intro[3], "The First Book of Nephi");
+        assert_eq!(intro.len(), 5);
+        // In canonical order, before the book's first verse
+        let titles: Vec<&str> = db.all_verses().iter().map(|v| v.verse_title.as_str()).collect();
+        assert_eq!(&titles[4..7], ["1 Nephi 0:5", "1 Nephi 1:1", "1 Nephi 1:2"]);
+        assert_eq!(db.get_by_title("1 Nephi 0:2").unwrap().verse_short_title, "1 Ne. 0:2");
+        assert!(db.get_chapters_for_book("Genesis").is_empty());
+
+        // A book named alone still starts at chapter 1; "intro" asks for the front matter
+        assert_eq!(db.parse_reference("1 Nephi").unwrap().chapter_number, 1);
+        let range = db.parse_reference("1 ne intro").unwrap();
+        assert_eq!((range.chapter_number, range.start_verse, range.end_verse), (0, 1, 5));
+        assert_eq!(db.parse_reference("1 Nephi 0:2").unwrap().start_verse, 2);
+    }
+}
@@ -0,0 +1,282 @@
+//! Hymns
+//!
+//! An optional collection of the public-domain hymn texts in the data
+//! directory: `hymns.json` is an array of hymns (number, title, verses), each
+//! with the scriptures it's drawn from:
+//!
+//! ```json
+//! [{ "number": 2, "title": "The Spirit of God", "author": "William W. Phelps",
+//!    "verses": ["The Spirit of God like a fire is burning!"], "scriptures": ["D&C 109:79-80"] }]
+//! ```
+//!
+//! Stanzas take the shape of verses in a "Hymns" volume (hymn 2, stanza 1 is
+//! "Hymns 2:1"), so they browse, search, and copy like scripture.
+
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::error::{EscriturasError, Result};
+use crate::footnotes::Footnote;
+use crate::scripture::{stem_word, Scripture, ScriptureDb, ScriptureRange};
+
+/// `volume_title` and `book_title` of hymn stanzas
+pub const HYMN_VOLUME: &str = "Hymns";
+
+/// Marker of the footnotes linking hymns and scriptures
+const CROSS_REFERENCE_MARKER: &str = "♪";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Hymn {
+    pub number: i32,
+    pub title: String,
+    #[serde(default)]
+    pub author: String,
+    /// Stanzas, in order
+    pub verses: Vec<String>,
+    /// References to the scriptures the hymn draws on ("D&C 109:79-80")
+    #[serde(default)]
+    pub scriptures: Vec<String>,
+}
+
+impl Hymn {
+    /// "2 The Spirit of God"
+    pub fn label(&self) -> String {
+        format!("{} {}", self.number, self.title)
+    }
+
+    /// The stanzas in the shape of verses, numbered from 1
+    pub fn as_scriptures(&self) -> Vec<Scripture> {
+        self.verses
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let title = format!("{} {}:{}", HYMN_VOLUME, self.number, i + 1);
+                Scripture {
+                    volume_title: HYMN_VOLUME.into(),
+                    book_title: HYMN_VOLUME.into(),
+                    book_short_title: HYMN_VOLUME.into(),
+                    chapter_number: self.number,
+                    verse_number: i as i32 + 1,
+                    verse_title: title.clone(),
+                    verse_short_title: title,
+                    scripture_text: text.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+pub struct HymnDb {
+    hymns: Vec<Hymn>,
+    // Stemmed words of each stanza, built on first search
+    stems: OnceLock<Vec<Vec<HashSet<String>>>>,
+}
+
+impl HymnDb {
+    pub const FILE_NAME: &'static str = "hymns.json";
+
+    /// Load `hymns.json` from a data directory
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open hymns {:?}: {}", path, e)))?;
+        Ok(Self::from_hymns(serde_json::from_str(&content)?))
+    }
+
+    /// A collection in hymnbook order
+    pub fn from_hymns(mut hymns: Vec<Hymn>) -> Self {
+        hymns.sort_by_key(|h| h.number);
+        Self { hymns, stems: OnceLock::new() }
+    }
+
+    pub fn hymns(&self) -> &[Hymn] {
+        &self.hymns
+    }
+
+    pub fn get(&self, number: i32) -> Option<&Hymn> {
+        self.hymns.iter().find(|h| h.number == number)
+    }
+
+    /// A hymn by number ("2", "#2", "Hymn 2") or by its title in any case
+    pub fn find(&self, query: &str) -> Option<&Hymn> {
+        let query = query.trim().to_lowercase();
+        let number = query.trim_start_matches("hymns").trim_start_matches("hymn").trim().trim_start_matches('#');
+        if let Ok(number) = number.parse() {
+            return self.get(number);
+        }
+        self.hymns
+            .iter()
+            .find(|h| h.title.to_lowercase() == query)
+            .or_else(|| self.hymns.iter().find(|h| h.title.to_lowercase().contains(&query)))
+    }
+
+    /// Hymns whose title contains the query, or with a stanza containing
+    /// every (stemmed) word of it
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&Hymn> {
+        let query_lower = query.trim().to_lowercase();
+        let stemmer = Stemmer::create(Algorithm::English);
+        let terms: Vec<String> = query_lower
+            .split_whitespace()
+            .map(|word| stem_word(&stemmer, word))
+            .filter(|s| !s.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let stems = self.stems.get_or_init(|| {
+            self.hymns
+                .iter()
+                .map(|hymn| {
+                    hymn.verses
+                        .iter()
+                        .map(|v| v.to_lowercase().split_whitespace().map(|w| stem_word(&stemmer, w)).collect())
+                        .collect()
+                })
+                .collect()
+        });
+
+        self.hymns
+            .iter()
+            .zip(stems)
+            .filter(|(hymn, stanzas)| {
+                hymn.title.to_lowercase().contains(&query_lower)
+                    || stanzas.iter().any(|stanza: &HashSet<String>| terms.iter().all(|term| stanza.contains(term)))
+            })
+            .map(|(hymn, _)| hymn)
+            .take(limit)
+            .collect()
+    }
+
+    /// Every stanza of every hymn, in the shape of verses
+    pub fn as_scriptures(&self) -> Vec<Scripture> {
+        self.hymns.iter().flat_map(Hymn::as_scriptures).collect()
+    }
+
+    /// Hymns drawing on any verse of a passage
+    pub fn citing(&self, db: &ScriptureDb, range: &ScriptureRange) -> Vec<&Hymn> {
+        self.hymns
+            .iter()
+            .filter(|hymn| {
+                cited_ranges(db, hymn).iter().any(|cited| {
+                    cited.book_title == range.book_title
+                        && cited.chapter_number == range.chapter_number
+                        && cited.start_verse <= range.end_verse
+                        && range.start_verse <= cited.end_verse
+                })
+            })
+            .collect()
+    }
+
+    /// Cross-references both ways, as footnotes keyed by verse title: each
+    /// stanza lists its hymn's scriptures, and each cited verse lists the
+    /// hymns drawing on it ("Hymns 2")
+    pub fn footnotes(&self, db: &ScriptureDb) -> Vec<(String, Footnote)> {
+        let mut notes = Vec::new();
+        let mut cited_by: HashMap<String, Vec<String>> = HashMap::new();
+        for hymn in &self.hymns {
+            if hymn.scriptures.is_empty() {
+                continue;
+            }
+            for stanza in hymn.as_scriptures() {
+                notes.push((stanza.verse_title, footnote("Scriptures", hymn.scriptures.clone())));
+            }
+            let reference = format!("{} {}", HYMN_VOLUME, hymn.number);
+            for range in cited_ranges(db, hymn) {
+                for verse in db.get_verses_in_range(&range) {
+                    let hymns = cited_by.entry(verse.verse_title.clone()).or_default();
+                    if !hymns.contains(&reference) {
+                        hymns.push(reference.clone());
+                    }
+                }
+            }
+        }
+        notes.extend(cited_by.into_iter().map(|(title, hymns)| (title, footnote("Hymns", hymns))));
+        notes
+    }
+}
+
+fn footnote(word: &str, references: Vec<String>) -> Footnote {
+    Footnote { marker: CROSS_REFERENCE_MARKER.to_string(), word: word.to_string(), references }
+}
+
+/// A hymn's scripture references that name verses in `db`
+fn cited_ranges(db: &ScriptureDb, hymn: &Hymn) -> Vec<ScriptureRange> {
+    hymn.scriptures.iter().filter_map(|r| db.parse_reference(r)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hymn(number: i32, title: &str, verses: &[&str], scriptures: &[&str]) -> Hymn {
+        Hymn {
+            number,
+            title: title.to_string(),
+            author: String::new(),
+            verses: verses.iter().map(|v| v.to_string()).collect(),
+            scriptures: scriptures.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn hymns() -> HymnDb {
+        HymnDb::from_hymns(vec![
+            hymn(85, "How Firm a Foundation", &["How firm a foundation, ye Saints of the Lord"], &["Isaiah 41:10"]),
+            hymn(2, "The Spirit of God", &["The Spirit of God like a fire is burning!", "We'll sing and we'll shout"], &["D&C 109:79-80"]),
+        ])
+    }
+
+    fn scriptures() -> ScriptureDb {
+        let verse = |book: &str, short: &str, chapter, number| Scripture {
+            volume_title: "Volume".into(),
+            book_title: book.into(),
+            book_short_title: short.into(),
+            chapter_number: chapter,
+            verse_number: number,
+            verse_title: format!("{} {}:{}", book, chapter, number),
+            verse_short_title: format!("{} {}:{}", short, chapter, number),
+            scripture_text: String::new(),
+        };
+        ScriptureDb::from_verses(vec![
+            verse("Isaiah", "Isa.", 41, 10),
+            verse("Doctrine and Covenants", "D&C", 109, 79),
+            verse("Doctrine and Covenants", "D&C", 109, 80),
+        ])
+    }
+
+    #[test]
+    fn test_find_search_and_stanzas() {
+        let db = hymns();
+        assert_eq!(db.hymns()[0].number, 2);
+        assert_eq!(db.find("#85").unwrap().title, "How Firm a Foundation");
+        assert_eq!(db.find("hymn 2").unwrap().number, 2);
+        assert_eq!(db.find("spirit of god").unwrap().number, 2);
+        assert!(db.find("99").is_none());
+
+        let numbers = |query| db.search(query, 10).iter().map(|h| h.number).collect::<Vec<_>>();
+        assert_eq!(numbers("burns"), [2]);
+        assert_eq!(numbers("foundation"), [85]);
+        assert!(numbers("").is_empty());
+
+        let stanzas = db.get(2).unwrap().as_scriptures();
+        assert_eq!(stanzas[1].verse_title, "Hymns 2:2");
+        assert_eq!((stanzas[1].volume_title.as_str(), stanzas[1].chapter_number), (HYMN_VOLUME, 2));
+    }
+
+    #[test]
+    fn test_cross_references_both_ways() {
+        let (db, scriptures) = (hymns(), scriptures());
+        let range = scriptures.parse_reference("D&C 109:80").unwrap();
+        assert_eq!(db.citing(&scriptures, &range).iter().map(|h| h.number).collect::<Vec<_>>(), [2]);
+
+        let notes = db.footnotes(&scriptures);
+        let refs = |title: &str| notes.iter().find(|(t, _)| t == title).map(|(_, note)| note.references.clone());
+        assert_eq!(refs("Hymns 2:2").unwrap(), ["D&C 109:79-80"]);
+        assert_eq!(refs("Doctrine and Covenants 109:79").unwrap(), ["Hymns 2"]);
+        assert_eq!(refs("Isaiah 41:10").unwrap(), ["Hymns 85"]);
+    }
+}
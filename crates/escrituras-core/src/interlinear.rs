@@ -0,0 +1,170 @@
+//! Interlinear Hebrew and Greek for the Bible
+//!
+//! Two optional public-domain files in the data directory tie the words of
+//! the King James Bible to the Hebrew and Greek beneath them:
+//!
+//! - `kjv_strongs.json` maps each verse title to its English words, each
+//!   with the Strong's numbers of the original words it translates:
+//!   `{ "Genesis 1:1": [["In the beginning", "H7225"], ["God", "H430"], ...] }`.
+//!   A word may carry several numbers, separated by spaces ("H853 H8064").
+//! - `strongs.json` is Strong's lexicon, each number's original word and its
+//!   definition: `{ "H430": { "lemma": "אֱלֹהִים", "transliteration": "ʼĕlôhîym",
+//!   "definition": "gods in the ordinary sense; ..." } }`. The field names of
+//!   the Open Scriptures edition (`xlit`, `strongs_def`, `kjv_def`) work too.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{EscriturasError, Result};
+
+/// A lexicon entry
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StrongsEntry {
+    /// The Hebrew or Greek word
+    #[serde(default)]
+    pub lemma: String,
+    #[serde(default, alias = "xlit", alias = "translit")]
+    pub transliteration: String,
+    #[serde(default, alias = "pron")]
+    pub pronunciation: String,
+    #[serde(default, alias = "strongs_def")]
+    pub definition: String,
+    /// How the King James translators rendered it
+    #[serde(default, alias = "kjv_def")]
+    pub kjv_usage: String,
+}
+
+/// An English word or phrase of a verse and the Strong's numbers it translates
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "(String, String)")]
+pub struct TaggedWord {
+    pub text: String,
+    /// Normalized Strong's numbers ("H430"); empty for words the
+    /// translators supplied
+    pub strongs: Vec<String>,
+}
+
+impl From<(String, String)> for TaggedWord {
+    fn from((text, numbers): (String, String)) -> Self {
+        let strongs = numbers.split(|c: char| c.is_whitespace() || c == ',').filter_map(normalize).collect();
+        Self { text, strongs }
+    }
+}
+
+/// A Strong's number in its usual form: "h0430" and "H430" are both "H430".
+/// None unless it's H or G and a number.
+pub fn normalize(number: &str) -> Option<String> {
+    let number = number.trim();
+    let mut chars = number.chars();
+    let language = chars.next()?.to_ascii_uppercase();
+    let digits = chars.as_str().trim_start_matches('0');
+    if !matches!(language, 'H' | 'G') || digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}{}", language, digits))
+}
+
+/// Strong's lexicon and the tagged words of each Bible verse
+#[derive(Debug, Clone, Default)]
+pub struct Interlinear {
+    lexicon: HashMap<String, StrongsEntry>,
+    verses: HashMap<String, Vec<TaggedWord>>,
+}
+
+impl Interlinear {
+    pub const LEXICON_FILE_NAME: &'static str = "strongs.json";
+    pub const VERSES_FILE_NAME: &'static str = "kjv_strongs.json";
+
+    /// Load `strongs.json` and `kjv_strongs.json` from a data directory
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let read = |name: &str| {
+            let path = data_dir.join(name);
+            fs::read_to_string(&path)
+                .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open {:?}: {}", path, e)))
+                .map(|content| (path, content))
+        };
+        let (path, content) = read(Self::LEXICON_FILE_NAME)?;
+        let lexicon: HashMap<String, StrongsEntry> = serde_json::from_str(&content)
+            .map_err(|e| EscriturasError::ParseError(format!("Failed to parse {:?}: {}", path, e)))?;
+        let (path, content) = read(Self::VERSES_FILE_NAME)?;
+        let verses: HashMap<String, Vec<TaggedWord>> = serde_json::from_str(&content)
+            .map_err(|e| EscriturasError::ParseError(format!("Failed to parse {:?}: {}", path, e)))?;
+        Ok(Self::from_parts(lexicon, verses))
+    }
+
+    pub fn from_parts(
+        lexicon: impl IntoIterator<Item = (String, StrongsEntry)>,
+        verses: impl IntoIterator<Item = (String, Vec<TaggedWord>)>,
+    ) -> Self {
+        Self {
+            lexicon: lexicon.into_iter().filter_map(|(number, entry)| Some((normalize(&number)?, entry))).collect(),
+            verses: verses.into_iter().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verses.is_empty()
+    }
+
+    /// The tagged words of a verse, by its title ("Genesis 1:1")
+    pub fn words(&self, verse_title: &str) -> Option<&[TaggedWord]> {
+        self.verses.get(verse_title).map(Vec::as_slice)
+    }
+
+    /// A lexicon entry by Strong's number, in any of its forms ("h0430")
+    pub fn lookup(&self, number: &str) -> Option<&StrongsEntry> {
+        self.lexicon.get(&normalize(number)?)
+    }
+
+    /// How many verses use a Strong's number
+    pub fn occurrences(&self, number: &str) -> usize {
+        let Some(number) = normalize(number) else {
+            return 0;
+        };
+        self.verses.values().filter(|words| words.iter().any(|w| w.strongs.contains(&number))).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interlinear() -> Interlinear {
+        let lexicon = r#"{
+            "H430": { "lemma": "אֱלֹהִים", "xlit": "ʼĕlôhîym", "strongs_def": "gods; God", "kjv_def": "God, god" },
+            "H07225": { "lemma": "רֵאשִׁית", "transliteration": "rêʼshîyth", "definition": "the first" }
+        }"#;
+        let verses = r#"{
+            "Genesis 1:1": [["In the beginning", "H7225"], ["God", "H430"], ["created", "H1254 H853"], ["the", ""]],
+            "Genesis 1:2": [["Spirit", "H7307"], ["of God", "H430"]]
+        }"#;
+        let lexicon: HashMap<String, StrongsEntry> = serde_json::from_str(lexicon).unwrap();
+        let verses: HashMap<String, Vec<TaggedWord>> = serde_json::from_str(verses).unwrap();
+        Interlinear::from_parts(lexicon, verses)
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("h0430").as_deref(), Some("H430"));
+        assert_eq!(normalize(" G26 ").as_deref(), Some("G26"));
+        assert_eq!(normalize("X12"), None);
+        assert_eq!(normalize("H"), None);
+        assert_eq!(normalize("H0"), None);
+    }
+
+    #[test]
+    fn test_words_and_lookup() {
+        let interlinear = interlinear();
+        let words = interlinear.words("Genesis 1:1").unwrap();
+        assert_eq!(words[2].strongs, ["H1254", "H853"]);
+        assert!(words[3].strongs.is_empty());
+        assert!(interlinear.words("1 Nephi 1:1").is_none());
+
+        let god = interlinear.lookup("h0430").unwrap();
+        assert_eq!((god.transliteration.as_str(), god.definition.as_str()), ("ʼĕlôhîym", "gods; God"));
+        assert_eq!(interlinear.lookup("H7225").unwrap().definition, "the first");
+        assert_eq!(interlinear.occurrences("H430"), 2);
+    }
+}
@@ -0,0 +1,240 @@
+//! Study journal
+//!
+//! Each entry is a plain Markdown file in `~/.local/share/escrituras/journal/`,
+//! named by the time it was started (`2026-10-15-143205.md`) so entries sort
+//! chronologically and can be edited with any tool outside the app.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::copy_format::passage_reference;
+use crate::error::{EscriturasError, Result};
+use crate::events::{self, Event};
+use crate::scripture::{Scripture, ScriptureDb};
+
+pub(crate) const FILE_DATE_FORMAT: &str = "%Y-%m-%d-%H%M%S";
+
+/// One journal entry on disk
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub path: PathBuf,
+    /// When the entry was started (from the file name)
+    pub created: NaiveDateTime,
+    /// First `## ` heading, or the first line of text
+    pub title: String,
+    pub content: String,
+}
+
+impl JournalEntry {
+    fn read(path: &Path) -> Option<Self> {
+        let stem = path.file_stem()?.to_str()?;
+        let created = NaiveDateTime::parse_from_str(stem, FILE_DATE_FORMAT).ok()?;
+        let content = fs::read_to_string(path).ok()?;
+        let title = content
+            .lines()
+            .find_map(|l| l.strip_prefix("## "))
+            .or_else(|| content.lines().map(str::trim).find(|l| !l.is_empty() && !l.starts_with('#')))
+            .unwrap_or("Untitled")
+            .trim()
+            .to_string();
+
+        Some(Self { path: path.to_path_buf(), created, title, content })
+    }
+
+    /// References the entry is about: its `## ` headings and the attributions
+    /// under quoted verses
+    pub fn references(&self) -> Vec<&str> {
+        self.content
+            .lines()
+            .filter_map(|l| l.strip_prefix("## ").or_else(|| l.strip_prefix("> — ")))
+            .map(str::trim)
+            .collect()
+    }
+
+    /// Whether one of the entry's references is in `book` ("Alma")
+    pub fn is_about_book(&self, book: &str) -> bool {
+        self.references().iter().any(|r| {
+            r.strip_prefix(book).is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+    }
+
+    /// Whether one of the entry's references includes the verse
+    pub fn is_about(&self, db: &ScriptureDb, verse: &Scripture) -> bool {
+        self.references().iter().filter_map(|r| db.parse_reference(r)).any(|range| {
+            range.book_title == verse.book_title
+                && range.chapter_number == verse.chapter_number
+                && range.contains_verse(verse.verse_number)
+        })
+    }
+}
+
+/// All journal entries, newest first
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    pub dir: PathBuf,
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::get_journal_dir()?)
+    }
+
+    pub fn load_from(dir: &Path) -> Result<Self> {
+        let mut journal = Self { dir: dir.to_path_buf(), entries: Vec::new() };
+        journal.reload()?;
+        Ok(journal)
+    }
+
+    /// Re-read the journal directory (e.g. after an entry was edited)
+    pub fn reload(&mut self) -> Result<()> {
+        self.entries.clear();
+        if !self.dir.exists() {
+            return Ok(());
+        }
+        for item in fs::read_dir(&self.dir)? {
+            let path = item?.path();
+            if path.extension().is_some_and(|e| e == "md") {
+                self.entries.extend(JournalEntry::read(&path));
+            }
+        }
+        self.entries.sort_by_key(|e| Reverse(e.created));
+        Ok(())
+    }
+
+    /// Write a new entry from the template and return its path and initial content
+    pub fn create_entry(&self, chapter: Option<&str>, verses: &[Scripture]) -> Result<(PathBuf, String)> {
+        if self.dir.as_os_str().is_empty() {
+            return Err(EscriturasError::Other("Could not determine journal directory".to_string()));
+        }
+        let now = Local::now();
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self.dir.join(format!("{}.md", now.format(FILE_DATE_FORMAT)));
+        if path.exists() {
+            return Err(EscriturasError::Io(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("Journal entry {} already exists", path.display()),
+            )));
+        }
+        let content = Self::template(now, chapter, verses);
+        fs::write(&path, &content)?;
+        Ok((path, content))
+    }
+
+    /// Dated heading, the chapter being studied, and the selected verses as a quote
+    pub fn template(now: DateTime<Local>, chapter: Option<&str>, verses: &[Scripture]) -> String {
+        let mut out = format!("# {}\n\n", now.format("%A, %B %-d, %Y"));
+        if let Some(chapter) = chapter {
+            out.push_str(&format!("## {}\n\n", chapter));
+        }
+        if !verses.is_empty() {
+            for verse in verses {
+                out.push_str(&format!("> **{}** {}\n", verse.verse_number, verse.scripture_text));
+            }
+            out.push_str(&format!(">\n> — {}\n\n", passage_reference(verses)));
+        }
+        out.push('\n');
+        out
+    }
+
+    /// Write an entry with a note on some verses, without opening an editor.
+    /// Announced as `Event::NoteAdded`.
+    pub fn add_note(&self, verses: &[Scripture], note: &str) -> Result<PathBuf> {
+        let reference = passage_reference(verses);
+        let (path, template) = self.create_entry(Some(&reference), verses)?;
+        fs::write(&path, format!("{}\n\n{}\n", template.trim_end(), note.trim()))?;
+        let references = if verses.is_empty() { Vec::new() } else { vec![reference] };
+        events::emit(Event::NoteAdded { path: path.clone(), references });
+        Ok(path)
+    }
+
+    /// Remove a new entry the user closed without writing anything
+    pub fn discard_if_unchanged(path: &Path, template: &str) -> Result<bool> {
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() || content == template {
+            fs::remove_file(path)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    pub fn delete(&mut self, index: usize) -> Result<JournalEntry> {
+        let entry = self
+            .entries
+            .get(index)
+            .cloned()
+            .ok_or_else(|| EscriturasError::DataNotFound(format!("No journal entry {}", index)))?;
+        fs::remove_file(&entry.path)?;
+        self.entries.remove(index);
+        Ok(entry)
+    }
+
+    fn get_journal_dir() -> Result<PathBuf> {
+        Ok(crate::paths::data_dir()?.join("journal"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn verse(number: i32, text: &str) -> Scripture {
+        Scripture::test("Alma", 32, number).text(text)
+    }
+
+    #[test]
+    fn test_template_includes_chapter_and_verses() {
+        let now = Local.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap();
+        let template = Journal::template(now, Some("Alma 32"), &[verse(21, "Faith is not"), verse(22, "And now")]);
+
+        assert_eq!(
+            template,
+            "# Thursday, October 15, 2026\n\n## Alma 32\n\n> **21** Faith is not\n> **22** And now\n>\n> — Alma 32:21-22\n\n\n"
+        );
+    }
+
+    #[test]
+    fn test_entries_listed_newest_first_and_unchanged_discarded() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("2026-01-02-080000.md"), "# Friday\n\n## Moroni 10\n\nPrayed about it.\n").unwrap();
+        fs::write(dir.path().join("2026-03-04-080000.md"), "Just a thought\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let journal = Journal::load_from(dir.path()).unwrap();
+        let titles: Vec<&str> = journal.entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["Just a thought", "Moroni 10"]);
+
+        let (path, template) = journal.create_entry(Some("Alma 32"), &[]).unwrap();
+        assert!(Journal::discard_if_unchanged(&path, &template).unwrap());
+        assert!(!path.exists());
+
+        let (path, template) = journal.create_entry(None, &[]).unwrap();
+        fs::write(&path, format!("{}Learned something.\n", template)).unwrap();
+        assert!(!Journal::discard_if_unchanged(&path, &template).unwrap());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_notes_are_found_by_book() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::load_from(dir.path()).unwrap();
+        let path = journal.add_note(&[verse(21, "Faith is not")], "Hope comes before knowing.").unwrap();
+        assert!(fs::read_to_string(&path).unwrap().ends_with("> — Alma 32:21\n\nHope comes before knowing.\n"));
+
+        let journal = Journal::load_from(dir.path()).unwrap();
+        let entry = &journal.entries[0];
+        assert_eq!(entry.title, "Alma 32:21");
+        assert_eq!(entry.references(), ["Alma 32:21", "Alma 32:21"]);
+        assert!(entry.is_about_book("Alma"));
+        assert!(!entry.is_about_book("Al"));
+
+        let db = ScriptureDb::from_verses(vec![verse(20, "And now"), verse(21, "Faith is not")]);
+        assert!(entry.is_about(&db, &verse(21, "")));
+        assert!(!entry.is_about(&db, &verse(20, "")));
+    }
+}
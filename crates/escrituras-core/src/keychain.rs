@@ -0,0 +1,70 @@
+//! API keys in the operating system's keychain
+//!
+//! Keys are stored through the `keyring` crate under the service "escrituras"
+//! with the provider's name as the account: in the login keychain on macOS,
+//! in the Credential Manager on Windows (as `claude.escrituras`), and in the
+//! Secret Service on Linux. Where there's no keychain (or nothing answers for
+//! it), keys come only from the environment or the config file.
+
+use keyring::credential::CredentialPersistence;
+use keyring::Entry;
+
+use crate::error::{EscriturasError, Result};
+use crate::provider::Provider;
+
+const SERVICE: &str = "escrituras";
+
+fn entry(provider: Provider) -> Result<Entry> {
+    Entry::new(SERVICE, provider.as_str()).map_err(|e| refused("open the key", e))
+}
+
+fn refused(what: &str, e: keyring::Error) -> EscriturasError {
+    EscriturasError::Keychain(format!("The keychain refused to {}: {}", what, e))
+}
+
+/// Whether keys can be stored in the keychain here
+pub fn available() -> bool {
+    // Without a native store keyring falls back to keeping keys in memory
+    if !matches!(keyring::default::default_credential_builder().persistence(), CredentialPersistence::UntilDelete) {
+        return false;
+    }
+    matches!(entry(Provider::Claude).map(|entry| entry.get_password()), Ok(Ok(_) | Err(keyring::Error::NoEntry)))
+}
+
+/// A provider's key from the keychain
+pub fn get(provider: Provider) -> Option<String> {
+    entry(provider).ok()?.get_password().ok().filter(|key| !key.is_empty())
+}
+
+/// Store a provider's key, replacing any already there
+pub fn set(provider: Provider, key: &str) -> Result<()> {
+    let key = key.trim();
+    if key.is_empty() || key.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        return Err(EscriturasError::InvalidSetting("That doesn't look like an API key".to_string()));
+    }
+    if !available() {
+        return Err(EscriturasError::Keychain("No keychain is available on this system".to_string()));
+    }
+    entry(provider)?.set_password(key).map_err(|e| refused("store the key", e))
+}
+
+/// Remove a provider's key; false when there wasn't one
+pub fn remove(provider: Provider) -> Result<bool> {
+    match entry(provider)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(refused("remove the key", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_keys_are_refused() {
+        for key in ["", "  ", "sk-ant one", "sk\"x", "sk\\x"] {
+            assert!(set(Provider::Claude, key).is_err(), "{:?}", key);
+        }
+    }
+}
@@ -1,15 +1,101 @@
+//! Scripture study for the standard works: the text and its search, study
+//! data, and AI providers, shared by the TUI, the desktop app, and the
+//! browser and mobile bindings.
+//!
+//! The types re-exported here are the API other crates build on, and changes
+//! to them follow semver (checked on every pull request; see CONTRIBUTING).
+//! Start with [`ScriptureDb`], which loads the scriptures and looks up,
+//! parses, and searches them; [`search::search`] adds semantic matches and
+//! filters. Types a frontend hands across a boundary ([`Scripture`],
+//! [`ScriptureRange`], [`ChatMessage`], [`SearchHit`], ...) serialize with
+//! serde. Errors are an [`EscriturasError`], which can gain kinds in any
+//! release. The AI clients, the embedding model, downloads, the MCP server,
+//! and the daemon are behind features, all on by default.
+
 pub mod ai;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod backup;
+pub mod card;
+pub mod come_follow_me;
+pub mod compare;
 pub mod config;
+pub mod copy_format;
+#[cfg(feature = "daemon")]
+pub mod daemon;
+pub mod dictionary;
 pub mod embeddings;
+pub mod error;
+pub mod events;
+pub mod export;
+pub mod footnotes;
+pub mod front_matter;
+pub mod hymns;
+pub mod interlinear;
+pub mod journal;
+pub mod keychain;
+pub mod library;
+pub mod links;
+pub mod logging;
+pub mod mastery;
+#[cfg(feature = "mcp")]
 pub mod mcp;
+pub mod memorize;
+pub mod outline;
+pub mod parallels;
+pub mod paths;
 pub mod provider;
+pub mod quiz;
+pub mod read_aloud;
+pub mod reading;
 pub mod scripture;
+pub mod search;
+pub mod setup;
 pub mod state;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod store;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sync;
+pub mod talks;
+pub mod timings;
+pub mod title;
+pub mod topics;
+pub mod votd;
+pub mod word_index;
 
 // Re-export main types for convenience
-pub use ai::{ClaudeClient, OllamaClient, OpenAIClient};
-pub use config::Config;
-pub use embeddings::{download_embedding_model, EmbeddingsDb};
+pub use ai::{context_window, estimate_tokens};
+#[cfg(feature = "ai")]
+pub use ai::{ClaudeClient, OllamaClient, OpenAIClient, TokenSender};
+#[cfg(not(target_arch = "wasm32"))]
+pub use backup::{Backup, MergeSummary};
+pub use card::{Card, CardFormat, CardStyle};
+pub use come_follow_me::{reading_summary, CfmProgress, CfmReading, CfmSchedule, CfmWeek, VolumeProgress};
+pub use config::{Config, KeySource, PaneLayout, Profile};
+pub use copy_format::CopyFormat;
+pub use dictionary::Dictionary;
+pub use embeddings::EmbeddingsDb;
+#[cfg(feature = "embeddings")]
+pub use embeddings::{cache_embedding_model, download_embedding_model, embedding_model_cached, embedding_model_dir, generate_embeddings};
+pub use error::EscriturasError;
+pub use footnotes::{Footnote, FootnoteDb};
+pub use front_matter::FrontMatter;
+pub use hymns::{Hymn, HymnDb, HYMN_VOLUME};
+pub use interlinear::{Interlinear, StrongsEntry, TaggedWord};
+pub use journal::{Journal, JournalEntry};
+pub use library::{Library, LibraryBook, LibraryChapter, LIBRARY_VOLUME};
+pub use mastery::{Deck, DECKS};
+pub use memorize::{MemorizeAttempt, MemorizeHistory, VerseStats};
+pub use outline::{Outline, OutlineCache, OutlineSection};
+pub use parallels::{Alignment, PARALLELS};
 pub use provider::Provider;
+pub use read_aloud::{CommandVoice, ReadAloud, Voice};
 pub use scripture::{Scripture, ScriptureDb, ScriptureRange};
-pub use state::{ChatMessage, ChatRole};
+pub use search::{MatchSource, SearchFilter, SearchHit, SearchMode, SemanticQuery};
+pub use state::{ChatMessage, ChatRole, Conversation, ConversationHistory, SavedScriptures, SessionState};
+#[cfg(not(target_arch = "wasm32"))]
+pub use store::{Store, HIGHLIGHT_COLORS};
+pub use talks::{Talk, TalkDb, TalkHit, TALK_VOLUME};
+pub use title::Title;
+pub use topics::{Topic, TopicIndex, TopicPage, TopicTalk, TOPICS};
+pub use votd::{todays_verse, verse_of_the_day};
+pub use word_index::{Concordance, WordIndex};
@@ -0,0 +1,402 @@
+//! Supplemental library
+//!
+//! Books imported from EPUB files (Jesus the Christ, public-domain
+//! commentaries) to study beside the scriptures. Each is kept in the
+//! `library/` folder of the data directory as `<id>.json`, its title, author,
+//! and chapters of paragraphs, and once embedded, `<id>.npy` holds one row
+//! per paragraph in book order.
+//!
+//! Paragraphs take the shape of verses in a "Library" volume (chapter 3,
+//! paragraph 4 of Jesus the Christ is "Jesus the Christ 3:4"), so they browse,
+//! search, save, and go to the AI as context like scripture.
+
+use ndarray::Array2;
+use ndarray_npy::ReadNpyExt;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+use crate::embeddings::EmbeddingsDb;
+use crate::error::{EscriturasError, Result};
+use crate::links::decode;
+use crate::scripture::Scripture;
+
+/// `volume_title` of library paragraphs
+pub const LIBRARY_VOLUME: &str = "Library";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LibraryBook {
+    pub title: String,
+    #[serde(default)]
+    pub author: String,
+    pub chapters: Vec<LibraryChapter>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LibraryChapter {
+    pub title: String,
+    pub paragraphs: Vec<String>,
+}
+
+impl LibraryBook {
+    /// The file name stem it's kept under: its title, lowercased and hyphenated
+    pub fn id(&self) -> String {
+        let words: Vec<String> = self
+            .title
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect();
+        if words.is_empty() { "book".to_string() } else { words.join("-") }
+    }
+
+    pub fn paragraph_count(&self) -> usize {
+        self.chapters.iter().map(|c| c.paragraphs.len()).sum()
+    }
+
+    /// Every paragraph in the shape of a verse, chapters and paragraphs numbered from 1
+    pub fn as_scriptures(&self) -> Vec<Scripture> {
+        let mut verses = Vec::with_capacity(self.paragraph_count());
+        for (c, chapter) in self.chapters.iter().enumerate() {
+            for (p, text) in chapter.paragraphs.iter().enumerate() {
+                let title = format!("{} {}:{}", self.title, c + 1, p + 1);
+                verses.push(Scripture {
+                    volume_title: LIBRARY_VOLUME.into(),
+                    book_title: self.title.as_str().into(),
+                    book_short_title: self.title.as_str().into(),
+                    chapter_number: c as i32 + 1,
+                    verse_number: p as i32 + 1,
+                    verse_title: title.clone(),
+                    verse_short_title: title,
+                    scripture_text: text.clone(),
+                });
+            }
+        }
+        verses
+    }
+}
+
+/// The imported books, in order of title
+#[derive(Default)]
+pub struct Library {
+    books: Vec<LibraryBook>,
+    // Each book's paragraph embeddings, when it's been embedded
+    embeddings: Vec<Option<Array2<f32>>>,
+}
+
+impl Library {
+    pub const DIR_NAME: &'static str = "library";
+
+    /// The `library/` folder of a data directory
+    pub fn dir(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::DIR_NAME)
+    }
+
+    /// Every book in `library/` with its embeddings; empty when there's no folder
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let Ok(entries) = fs::read_dir(Self::dir(data_dir)) else {
+            return Ok(Self::default());
+        };
+        let mut books = Vec::new();
+        for path in entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|e| e == "json")) {
+            let content = fs::read_to_string(&path)?;
+            let book: LibraryBook = serde_json::from_str(&content)
+                .map_err(|e| EscriturasError::ParseError(format!("Failed to read {:?}: {}", path, e)))?;
+            let embeddings = match File::open(path.with_extension("npy")) {
+                Ok(file) => Some(
+                    Array2::<f32>::read_npy(file)
+                        .map_err(|e| EscriturasError::ParseError(format!("Failed to read .npy file: {}", e)))?,
+                ),
+                Err(_) => None,
+            };
+            let embeddings = embeddings.filter(|e| e.nrows() == book.paragraph_count());
+            books.push((book, embeddings));
+        }
+        books.sort_by(|a, b| a.0.title.cmp(&b.0.title));
+        let (books, embeddings) = books.into_iter().unzip();
+        Ok(Self { books, embeddings })
+    }
+
+    pub fn books(&self) -> &[LibraryBook] {
+        &self.books
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+
+    /// A book by title
+    pub fn get(&self, title: &str) -> Option<&LibraryBook> {
+        self.books.iter().find(|b| b.title == title)
+    }
+
+    /// Whether a book has been embedded for semantic search
+    pub fn is_embedded(&self, title: &str) -> bool {
+        self.books.iter().position(|b| b.title == title).is_some_and(|i| self.embeddings[i].is_some())
+    }
+
+    /// Every paragraph of every book, in the shape of verses
+    pub fn as_scriptures(&self) -> Vec<Scripture> {
+        self.books.iter().flat_map(LibraryBook::as_scriptures).collect()
+    }
+
+    /// Add the embedded books' paragraphs to the scriptures' embeddings, so
+    /// semantic search finds them too
+    pub fn add_embeddings(&self, db: &mut EmbeddingsDb) -> Result<()> {
+        for (book, embeddings) in self.books.iter().zip(&self.embeddings) {
+            if let Some(embeddings) = embeddings {
+                let titles = book.as_scriptures().into_iter().map(|v| v.verse_title).collect();
+                db.append(titles, embeddings)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read an EPUB file into the library, replacing a book of the same title
+pub fn import_epub(path: &Path, data_dir: &Path) -> Result<LibraryBook> {
+    let file = File::open(path)
+        .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open {:?}: {}", path, e)))?;
+    let book = read_epub(file)?;
+    save(&book, data_dir)?;
+    Ok(book)
+}
+
+/// Write a book into the library (its embeddings, now stale, are removed)
+pub fn save(book: &LibraryBook, data_dir: &Path) -> Result<PathBuf> {
+    let dir = Library::dir(data_dir);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.json", book.id()));
+    fs::write(&path, serde_json::to_string(book)?)?;
+    let _ = fs::remove_file(path.with_extension("npy"));
+    Ok(path)
+}
+
+/// Take a book out of the library by title or id. Returns whether it was there.
+pub fn remove(data_dir: &Path, title: &str) -> Result<bool> {
+    let library = Library::load(data_dir)?;
+    let wanted = title.to_lowercase();
+    let Some(book) = library.books.iter().find(|b| b.title.to_lowercase() == wanted || b.id() == wanted) else {
+        return Ok(false);
+    };
+    let path = Library::dir(data_dir).join(format!("{}.json", book.id()));
+    fs::remove_file(&path)?;
+    let _ = fs::remove_file(path.with_extension("npy"));
+    Ok(true)
+}
+
+/// Embed a book's paragraphs with the local model for semantic search.
+/// `progress` is called with (paragraphs done, total).
+#[cfg(feature = "embeddings")]
+pub fn embed(book: &LibraryBook, data_dir: &Path, progress: impl FnMut(usize, usize)) -> Result<()> {
+    let texts: Vec<String> = book.as_scriptures().iter().map(crate::embeddings::embedding_text).collect();
+    if texts.is_empty() {
+        return Err(EscriturasError::Other(format!("{} has no paragraphs to embed", book.title)));
+    }
+    let embeddings = crate::embeddings::embed_texts(&texts, progress)?;
+    crate::embeddings::write_npy(&Library::dir(data_dir).join(format!("{}.npy", book.id())), &embeddings)
+}
+
+/// A book from an EPUB: its title and author from the package metadata, and
+/// a chapter for each document in reading order that has paragraphs
+pub fn read_epub(reader: impl Read + Seek) -> Result<LibraryBook> {
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| EscriturasError::ParseError(format!("Not an EPUB: {}", e)))?;
+    let container = read_entry(&mut archive, "META-INF/container.xml")?;
+    let package_path = Regex::new(r#"<rootfile\b[^>]*\bfull-path\s*=\s*["']([^"']+)["']"#)
+        .unwrap()
+        .captures(&container)
+        .map(|c| c[1].to_string())
+        .ok_or_else(|| EscriturasError::ParseError("EPUB has no package file".to_string()))?;
+    let package = read_entry(&mut archive, &package_path)?;
+    let base = package_path.rsplit_once('/').map_or(String::new(), |(dir, _)| format!("{}/", dir));
+
+    let markup = Markup::new();
+    let element = |name: &str| {
+        Regex::new(&format!(r"(?is)<dc:{}\b[^>]*>(.*?)</dc:{}>", name, name))
+            .unwrap()
+            .captures(&package)
+            .map(|c| markup.text(&c[1]))
+            .unwrap_or_default()
+    };
+    let (title, author) = (element("title"), element("creator"));
+
+    let attribute = Regex::new(r#"([\w:-]+)\s*=\s*["']([^"']*)["']"#).unwrap();
+    let attributes = |tag: &str| -> HashMap<String, String> {
+        attribute.captures_iter(tag).map(|c| (c[1].to_string(), c[2].to_string())).collect()
+    };
+    let manifest: HashMap<String, String> = Regex::new(r"(?s)<item\b[^>]*>")
+        .unwrap()
+        .find_iter(&package)
+        .map(|tag| attributes(tag.as_str()))
+        .filter_map(|attrs| Some((attrs.get("id")?.clone(), attrs.get("href")?.clone())))
+        .collect();
+    let spine: Vec<String> = Regex::new(r"(?s)<itemref\b[^>]*>")
+        .unwrap()
+        .find_iter(&package)
+        .filter_map(|tag| attributes(tag.as_str()).remove("idref"))
+        .collect();
+
+    let mut chapters = Vec::new();
+    for idref in spine {
+        let Some(href) = manifest.get(&idref) else { continue };
+        let href = href.split('#').next().unwrap_or_default();
+        let Ok(document) = read_entry(&mut archive, &format!("{}{}", base, decode(href))) else {
+            continue;
+        };
+        let paragraphs = markup.paragraphs(&document);
+        if paragraphs.is_empty() {
+            continue;
+        }
+        let title = markup.heading(&document).unwrap_or_else(|| format!("Chapter {}", chapters.len() + 1));
+        chapters.push(LibraryChapter { title, paragraphs });
+    }
+    if chapters.is_empty() {
+        return Err(EscriturasError::ParseError("EPUB has no text".to_string()));
+    }
+    let title = if title.is_empty() { "Untitled".to_string() } else { title };
+    Ok(LibraryBook { title, author, chapters })
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut zip::ZipArchive<R>, name: &str) -> Result<String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| EscriturasError::ParseError(format!("EPUB is missing {}: {}", name, e)))?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Text out of XHTML
+struct Markup {
+    paragraph: Regex,
+    heading: Regex,
+    line_break: Regex,
+    tag: Regex,
+    entity: Regex,
+}
+
+impl Markup {
+    fn new() -> Self {
+        Self {
+            paragraph: Regex::new(r"(?is)<p\b[^>]*>(.*?)</p>").unwrap(),
+            heading: Regex::new(r"(?is)<h[1-3]\b[^>]*>(.*?)</h[1-3]>").unwrap(),
+            line_break: Regex::new(r"(?i)<br\b[^>]*>").unwrap(),
+            tag: Regex::new(r"(?s)<[^>]*>").unwrap(),
+            entity: Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap(),
+        }
+    }
+
+    /// The text of each non-empty paragraph
+    fn paragraphs(&self, document: &str) -> Vec<String> {
+        self.paragraph.captures_iter(document).map(|c| self.text(&c[1])).filter(|p| !p.is_empty()).collect()
+    }
+
+    /// The document's first heading
+    fn heading(&self, document: &str) -> Option<String> {
+        self.heading.captures_iter(document).map(|c| self.text(&c[1])).find(|h| !h.is_empty())
+    }
+
+    /// Markup without its tags and entities, on one line
+    fn text(&self, markup: &str) -> String {
+        let text = self.line_break.replace_all(markup, " ");
+        let text = self.tag.replace_all(&text, "");
+        let text = self.entity.replace_all(&text, |c: &regex::Captures| {
+            let name = &c[1];
+            let code = match name.strip_prefix('#') {
+                Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok(),
+                Some(decimal) => decimal.parse().ok(),
+                None => named_entity(name),
+            };
+            code.and_then(char::from_u32).map_or_else(|| c[0].to_string(), String::from)
+        });
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// The character of an HTML entity books commonly use
+fn named_entity(name: &str) -> Option<u32> {
+    Some(match name {
+        "amp" => 0x26,
+        "lt" => 0x3C,
+        "gt" => 0x3E,
+        "quot" => 0x22,
+        "apos" => 0x27,
+        "nbsp" => 0x20,
+        "ndash" => 0x2013,
+        "mdash" => 0x2014,
+        "lsquo" => 0x2018,
+        "rsquo" => 0x2019,
+        "ldquo" => 0x201C,
+        "rdquo" => 0x201D,
+        "hellip" => 0x2026,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    fn epub() -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let mut add = |name: &str, content: &str| {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        };
+        add("mimetype", "application/epub+zip");
+        add(
+            "META-INF/container.xml",
+            r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+        );
+        add(
+            "OEBPS/content.opf",
+            r#"<package><metadata><dc:title>Jesus the Christ</dc:title><dc:creator>James E. Talmage</dc:creator></metadata>
+               <manifest><item id="cover" href="cover.xhtml"/><item id="c1" href="text/chapter%201.xhtml"/>
+               <item id="c2" href="text/c2.xhtml"/></manifest>
+               <spine><itemref idref="cover"/><itemref idref="c2"/><itemref idref="c1"/></spine></package>"#,
+        );
+        add("OEBPS/cover.xhtml", "<html><body><img src='cover.jpg'/></body></html>");
+        add(
+            "OEBPS/text/chapter 1.xhtml",
+            "<html><body><h1>Chapter 1: <em>Introduction</em></h1><p>The &ldquo;Christ&rdquo;\n  was <b>known</b>.</p><p> </p></body></html>",
+        );
+        add("OEBPS/text/c2.xhtml", "<html><body><p class='x'>Pre&#8209;existence &amp; more</p></body></html>");
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_read_epub_in_spine_order() {
+        let book = read_epub(Cursor::new(epub())).unwrap();
+        assert_eq!((book.title.as_str(), book.author.as_str()), ("Jesus the Christ", "James E. Talmage"));
+        assert_eq!(book.id(), "jesus-the-christ");
+        let titles: Vec<&str> = book.chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["Chapter 1", "Chapter 1: Introduction"]);
+        assert_eq!(book.chapters[0].paragraphs, ["Pre\u{2011}existence & more"]);
+        assert_eq!(book.chapters[1].paragraphs, ["The \u{201C}Christ\u{201D} was known."]);
+        assert!(read_epub(Cursor::new(b"not a zip".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_saved_books_load_as_verses() {
+        let dir = tempfile::tempdir().unwrap();
+        let book = read_epub(Cursor::new(epub())).unwrap();
+        save(&book, dir.path()).unwrap();
+
+        let library = Library::load(dir.path()).unwrap();
+        assert_eq!(library.books(), [book]);
+        assert!(!library.is_embedded("Jesus the Christ"));
+        let verses = library.as_scriptures();
+        assert_eq!(verses[1].verse_title, "Jesus the Christ 2:1");
+        assert_eq!(verses[1].volume_title, LIBRARY_VOLUME);
+
+        assert!(remove(dir.path(), "jesus-the-christ").unwrap());
+        assert!(Library::load(dir.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+//! Links to scripture from other apps
+//!
+//! `escrituras://` links carry a reference, either as the path
+//! (`escrituras://Alma%2032:21`) or as a `ref` parameter
+//! (`escrituras://open?ref=Alma+32:21-23`). Gospel Library study links
+//! (`https://www.churchofjesuschrist.org/study/scriptures/bofm/alma/32?id=p21-p23`)
+//! are read too, and written by `gospel_library_url` for copies, exports, and
+//! opening a passage in a browser.
+
+use crate::scripture::{Scripture, ScriptureDb, ScriptureRange};
+
+/// The URI scheme the desktop app handles
+pub const SCHEME: &str = "escrituras";
+
+const STUDY_PREFIX: &str = "churchofjesuschrist.org/study/scriptures/";
+
+/// Gospel Library language of links when none is set (`gospel_library_language`)
+pub const DEFAULT_LANGUAGE: &str = "eng";
+
+/// The reference a link points to, or None when it isn't a scripture link
+pub fn reference_from_link(db: &ScriptureDb, link: &str) -> Option<ScriptureRange> {
+    let link = link.trim();
+    if let Some(rest) = link.strip_prefix(&format!("{}://", SCHEME)) {
+        let reference = match rest.split_once('?') {
+            Some((_, query)) => query_param(query, "ref")?,
+            None => rest.trim_end_matches('/').to_string(),
+        };
+        return db.parse_reference(&decode(&reference));
+    }
+    let (_, path) = link.split_once(STUDY_PREFIX)?;
+    study_reference(db, path)
+}
+
+/// An `escrituras://` link to a reference
+pub fn link_to(range: &ScriptureRange) -> String {
+    format!("{}://{}", SCHEME, range.display_title().replace(' ', "%20"))
+}
+
+/// A passage in the Gospel Library at churchofjesuschrist.org, in a language
+/// by its code ("eng", "spa", "por"), opened at and highlighting its verses;
+/// a whole chapter is linked as the chapter. None when the reference names no
+/// verses or isn't in the standard works (a hymn, say).
+pub fn gospel_library_url(db: &ScriptureDb, range: &ScriptureRange, language: &str) -> Option<String> {
+    let verses = db.get_verses_in_range(range);
+    let (first, last) = (verses.first()?, verses.last()?);
+    let chapter = db.get_verses_for_chapter(&range.book_title, range.chapter_number);
+    if chapter.len() == verses.len() {
+        return chapter_url(first, language);
+    }
+    study_url(first, last.verse_number, language)
+}
+
+/// Link to verses `first` to `last` of `first`'s chapter
+pub(crate) fn study_url(first: &Scripture, last: i32, language: &str) -> Option<String> {
+    let id = if last == first.verse_number {
+        format!("p{}", first.verse_number)
+    } else {
+        format!("p{}-p{}", first.verse_number, last)
+    };
+    Some(format!("{}&id={}#p{}", chapter_url(first, language)?, id, first.verse_number))
+}
+
+/// Link to the chapter of a verse
+///
+/// Book slugs are derived from the short title ("1 Ne." -> "1-ne", "D&C" -> "dc"),
+/// which matches the site's URLs for the standard works.
+pub(crate) fn chapter_url(verse: &Scripture, language: &str) -> Option<String> {
+    let volume = match verse.volume_title.as_str() {
+        "Old Testament" => "ot",
+        "New Testament" => "nt",
+        "Book of Mormon" => "bofm",
+        "Doctrine and Covenants" => "dc-testament",
+        "Pearl of Great Price" => "pgp",
+        _ => return None,
+    };
+    Some(format!(
+        "https://www.{}{}/{}/{}?lang={}",
+        STUDY_PREFIX,
+        volume,
+        book_slug(&verse.book_short_title),
+        verse.chapter_number,
+        language
+    ))
+}
+
+/// A book's part of a Gospel Library URL, from its short title
+fn book_slug(short_title: &str) -> String {
+    short_title.to_lowercase().replace("d&c", "dc").replace('.', "").replace([' ', '—'], "-")
+}
+
+/// The reference of a study link's path: `bofm/alma/32?id=p21-p23#p21`
+fn study_reference(db: &ScriptureDb, path: &str) -> Option<ScriptureRange> {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let path = path.split('#').next()?;
+    let mut parts = path.split('/');
+    let (_volume, slug, chapter) = (parts.next()?, parts.next()?, parts.next()?);
+    let chapter: i32 = chapter.parse().ok()?;
+
+    let book = db
+        .all_verses()
+        .iter()
+        .find(|v| book_slug(&v.book_short_title) == slug)
+        .map(|v| v.book_title.clone())?;
+    // `id=p21` or `id=p21-p23`; without one it's the whole chapter
+    let id = query_param(query.split('#').next()?, "id").unwrap_or_default();
+    let verses: Vec<&str> = id.split('-').filter_map(|p| p.strip_prefix('p')).collect();
+    let reference = match verses.as_slice() {
+        [start] => format!("{} {}:{}", book, chapter, start),
+        [start, end] => format!("{} {}:{}-{}", book, chapter, start, end),
+        _ => format!("{} {}", book, chapter),
+    };
+    db.parse_reference(&reference)
+}
+
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+/// Undo URL encoding: `%20` and `+` for spaces, `%3A` for colons, ...
+pub(crate) fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripture::Scripture;
+
+    fn verse(number: i32) -> Scripture {
+        Scripture::test("1 Nephi", 3, number).short("1 Ne.")
+    }
+
+    #[test]
+    fn test_links_resolve_to_references() {
+        let db = ScriptureDb::from_verses((1..=9).map(verse).collect());
+        let title = |link: &str| reference_from_link(&db, link).map(|r| r.display_title());
+
+        assert_eq!(title("escrituras://1%20Nephi%203:7").as_deref(), Some("1 Nephi 3:7"));
+        assert_eq!(title("escrituras://open?ref=1+Ne+3%3A7-8").as_deref(), Some("1 Nephi 3:7-8"));
+        assert_eq!(
+            title("https://www.churchofjesuschrist.org/study/scriptures/bofm/1-ne/3?id=p7-p8#p7").as_deref(),
+            Some("1 Nephi 3:7-8")
+        );
+        assert_eq!(title("https://www.churchofjesuschrist.org/study/scriptures/bofm/1-ne/3").as_deref(), Some("1 Nephi 3:1-9"));
+        assert_eq!(title("escrituras://Moroni%2010:4"), None);
+        assert_eq!(title("https://example.com/1-ne/3"), None);
+
+        let link = link_to(&db.parse_reference("1 Nephi 3:7").unwrap());
+        assert_eq!(title(&link).as_deref(), Some("1 Nephi 3:7"));
+    }
+
+    #[test]
+    fn test_gospel_library_urls() {
+        let db = ScriptureDb::from_verses((1..=9).map(verse).collect());
+        let url = |reference: &str, language| gospel_library_url(&db, &db.parse_reference(reference).unwrap(), language);
+
+        let passage = url("1 Ne 3:7-8", "spa").unwrap();
+        assert_eq!(passage, "https://www.churchofjesuschrist.org/study/scriptures/bofm/1-ne/3?lang=spa&id=p7-p8#p7");
+        assert_eq!(url("1 Ne 3:7", DEFAULT_LANGUAGE).unwrap(), "https://www.churchofjesuschrist.org/study/scriptures/bofm/1-ne/3?lang=eng&id=p7#p7");
+        assert_eq!(url("1 Ne 3", DEFAULT_LANGUAGE).unwrap(), "https://www.churchofjesuschrist.org/study/scriptures/bofm/1-ne/3?lang=eng");
+        // Read back to the same passage
+        assert_eq!(reference_from_link(&db, &passage).map(|r| r.display_title()).as_deref(), Some("1 Nephi 3:7-8"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Logging
+//!
+//! Diagnostics go through `tracing` to a log file in the data directory,
+//! `logs/escrituras.log.2026-10-15`: a new file each day, keeping the last
+//! week. Warnings and errors also go to stderr, except while the TUI has the
+//! terminal (see `set_terminal_in_use`), where they'd be drawn over the screen.
+//!
+//! How much is written is `log_level` in the config: a level (`info` by
+//! default) for escrituras's own messages, or filter directives like
+//! `escrituras_core::sync=trace,reqwest=debug`. `--verbose` raises it to
+//! `debug` and shows it on stderr too.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use tracing_appender::non_blocking::WorkerGuard;
+#[cfg(not(target_arch = "wasm32"))]
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+#[cfg(not(target_arch = "wasm32"))]
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::filter::EnvFilter;
+#[cfg(not(target_arch = "wasm32"))]
+use tracing_subscriber::{fmt, prelude::*};
+
+use crate::error::{EscriturasError, Result};
+
+/// Log files are named this, then the date
+pub const FILE_PREFIX: &str = "escrituras.log";
+
+/// Days of log files kept
+#[cfg(not(target_arch = "wasm32"))]
+const KEEP_FILES: usize = 7;
+
+/// `log_level` values that are plain levels
+pub const LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+
+/// Crates whose messages a plain level applies to; others only log warnings
+const OWN_TARGETS: &[&str] = &["escrituras_core", "escrituras_tauri", "scriptures"];
+
+static TERMINAL_IN_USE: AtomicBool = AtomicBool::new(false);
+
+/// `logs/` in the data directory
+pub fn log_dir() -> Result<PathBuf> {
+    Ok(crate::paths::data_dir()?.join("logs"))
+}
+
+/// The newest log file, if anything has been logged
+pub fn latest_log() -> Result<Option<PathBuf>> {
+    let dir = log_dir()?;
+    if !dir.exists() {
+        return Ok(None);
+    }
+    let mut logs: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(FILE_PREFIX)))
+        .collect();
+    // Dated names sort by date
+    logs.sort();
+    Ok(logs.pop())
+}
+
+/// Whether a `log_level` value can be used. Each directive has to name a
+/// level, since a bare word would be taken as a crate to log everything from.
+pub fn check_level(level: &str) -> Result<()> {
+    let is_level = |l: &str| l == "off" || LEVELS.contains(&l.to_lowercase().as_str());
+    let valid = level.split(',').all(|d| match d.rsplit_once('=') {
+        Some((_, l)) => is_level(l),
+        None => is_level(d),
+    });
+    if !valid || EnvFilter::builder().parse(directives(level)).is_err() {
+        return Err(EscriturasError::InvalidSetting(format!("log_level must be one of {}, or directives like escrituras_core=debug", LEVELS.join(", "))));
+    }
+    Ok(())
+}
+
+/// A plain level for escrituras's crates (and warnings from the rest), or the
+/// directives as given
+fn directives(level: &str) -> String {
+    if LEVELS.contains(&level) {
+        let own: Vec<String> = OWN_TARGETS.iter().map(|t| format!("{}={}", t, level)).collect();
+        format!("warn,{}", own.join(","))
+    } else {
+        level.to_string()
+    }
+}
+
+/// Keeps the log file's writer running; what's logged after it's dropped may be lost
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub struct LogGuard {
+    _writer: Option<WorkerGuard>,
+}
+
+/// Start logging at `level` (a `log_level` value, or None for `info`). With
+/// `verbose`, at least `debug`, shown on stderr as well. Logging goes on to
+/// stderr alone when the log file can't be opened.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init(level: Option<&str>, verbose: bool) -> LogGuard {
+    let level = match level {
+        _ if verbose => "debug",
+        Some(level) => level,
+        None => "info",
+    };
+    let file_filter = EnvFilter::builder().parse(directives(level)).unwrap_or_else(|_| EnvFilter::new(directives("info")));
+    let stderr_filter = EnvFilter::new(directives(if verbose { level } else { "warn" }));
+    let stderr = fmt::layer()
+        .with_writer(std::io::stderr)
+        .without_time()
+        .with_target(false)
+        .with_filter(stderr_filter)
+        .with_filter(filter_fn(|_| !TERMINAL_IN_USE.load(Ordering::Relaxed)));
+
+    let appender = log_dir().ok().filter(|dir| fs::create_dir_all(dir).is_ok()).and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(FILE_PREFIX)
+            .max_log_files(KEEP_FILES)
+            .build(dir)
+            .ok()
+    });
+    let (file, guard) = match appender {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let layer = fmt::layer().with_writer(writer).with_ansi(false).with_filter(file_filter);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+    // Already set (say by a test) is fine
+    let _ = tracing_subscriber::registry().with(stderr).with(file).try_init();
+    LogGuard { _writer: guard }
+}
+
+/// Say that the TUI has taken the terminal, or given it back. Nothing is
+/// written to stderr while it has it.
+pub fn set_terminal_in_use(in_use: bool) {
+    TERMINAL_IN_USE.store(in_use, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_apply_to_our_crates() {
+        assert_eq!(directives("debug"), "warn,escrituras_core=debug,escrituras_tauri=debug,scriptures=debug");
+        assert_eq!(directives("escrituras_core::sync=trace"), "escrituras_core::sync=trace");
+        assert!(check_level("trace").is_ok());
+        assert!(check_level("reqwest=debug,info").is_ok());
+        assert!(check_level("loud").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Doctrinal mastery decks
+//!
+//! The doctrinal mastery passages of seminary's four courses, built in as
+//! decks to memorize. A deck is a list of references; its passages are
+//! looked up in the scriptures when studied and reviewed in the order the
+//! memorization history says they're due (see `MemorizeHistory::next_review`).
+
+use crate::memorize::MemorizeHistory;
+use crate::scripture::{Scripture, ScriptureDb};
+
+/// A built-in set of passages to memorize
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deck {
+    pub name: &'static str,
+    /// One reference per passage, in the order they're taught
+    pub references: &'static [&'static str],
+}
+
+/// The built-in decks, in the order of the course rotation
+pub const DECKS: &[Deck] = &[
+    Deck {
+        name: "Old Testament",
+        references: &[
+            "Moses 1:39",
+            "Moses 7:18",
+            "Abraham 2:9-11",
+            "Abraham 3:22-23",
+            "Genesis 1:26-27",
+            "Genesis 2:24",
+            "Genesis 39:9",
+            "Exodus 20:3-17",
+            "Joshua 24:15",
+            "Psalm 24:3-4",
+            "Psalm 119:105",
+            "Psalm 127:3",
+            "Proverbs 3:5-6",
+            "Isaiah 1:18",
+            "Isaiah 5:20",
+            "Isaiah 29:13-14",
+            "Isaiah 53:3-5",
+            "Isaiah 58:6-7",
+            "Isaiah 58:13-14",
+            "Jeremiah 1:4-5",
+            "Ezekiel 3:16-17",
+            "Amos 3:7",
+            "Malachi 3:8-10",
+            "Malachi 4:5-6",
+        ],
+    },
+    Deck {
+        name: "New Testament",
+        references: &[
+            "Matthew 5:14-16",
+            "Matthew 11:28-30",
+            "Matthew 16:15-19",
+            "Matthew 22:36-39",
+            "Matthew 28:19-20",
+            "Luke 2:52",
+            "Luke 22:19-20",
+            "Luke 24:36-39",
+            "John 3:5",
+            "John 3:16",
+            "John 7:17",
+            "John 17:3",
+            "1 Corinthians 6:19-20",
+            "1 Corinthians 11:11",
+            "1 Corinthians 15:20-22",
+            "1 Corinthians 15:40-42",
+            "Ephesians 1:10",
+            "Ephesians 4:11-14",
+            "Philippians 4:13",
+            "2 Thessalonians 2:1-3",
+            "2 Timothy 3:15-17",
+            "Hebrews 12:9",
+            "James 1:5-6",
+            "James 2:17-18",
+            "1 Peter 4:6",
+            "Revelation 20:12",
+        ],
+    },
+    Deck {
+        name: "Book of Mormon",
+        references: &[
+            "1 Nephi 3:7",
+            "2 Nephi 2:25",
+            "2 Nephi 2:27",
+            "2 Nephi 26:33",
+            "2 Nephi 28:30",
+            "2 Nephi 32:3",
+            "2 Nephi 32:8-9",
+            "Mosiah 2:17",
+            "Mosiah 3:19",
+            "Mosiah 4:9",
+            "Mosiah 18:8-10",
+            "Alma 7:11-13",
+            "Alma 34:9-10",
+            "Alma 39:9",
+            "Alma 41:10",
+            "Helaman 5:12",
+            "3 Nephi 11:10-11",
+            "3 Nephi 12:48",
+            "3 Nephi 18:15",
+            "3 Nephi 18:20-21",
+            "Ether 12:6",
+            "Ether 12:27",
+            "Moroni 7:41",
+            "Moroni 7:45",
+            "Moroni 7:47-48",
+            "Moroni 10:4-5",
+        ],
+    },
+    Deck {
+        name: "Doctrine and Covenants and Church History",
+        references: &[
+            "Joseph Smith--History 1:15-20",
+            "D&C 1:30",
+            "D&C 1:37-38",
+            "D&C 6:36",
+            "D&C 8:2-3",
+            "D&C 13:1",
+            "D&C 18:10-11",
+            "D&C 18:15-16",
+            "D&C 19:16-19",
+            "D&C 21:4-6",
+            "D&C 29:10-11",
+            "D&C 49:15-17",
+            "D&C 58:42-43",
+            "D&C 64:9-11",
+            "D&C 76:22-24",
+            "D&C 82:10",
+            "D&C 84:20-22",
+            "D&C 88:118",
+            "D&C 89:18-21",
+            "D&C 107:8",
+            "D&C 121:36",
+            "D&C 121:41-42",
+            "D&C 130:22-23",
+            "D&C 131:1-4",
+            "D&C 135:3",
+        ],
+    },
+];
+
+impl Deck {
+    /// A deck by name in any case, or the first whose name starts with it ("book")
+    pub fn find(name: &str) -> Option<&'static Deck> {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+        DECKS
+            .iter()
+            .find(|d| d.name.to_lowercase() == name)
+            .or_else(|| DECKS.iter().find(|d| d.name.to_lowercase().starts_with(&name)))
+    }
+
+    /// Each passage's verses, in deck order, leaving out any the scriptures lack
+    pub fn passages(&self, db: &ScriptureDb) -> Vec<Vec<Scripture>> {
+        self.references
+            .iter()
+            .filter_map(|r| db.parse_reference(r))
+            .map(|range| db.get_verses_in_range(&range).into_iter().cloned().collect::<Vec<_>>())
+            .filter(|verses| !verses.is_empty())
+            .collect()
+    }
+
+    /// The passages in the order to review them: those never studied first,
+    /// then by when they come due. A passage is as due as its least-known verse.
+    pub fn review_order(&self, db: &ScriptureDb, history: &MemorizeHistory) -> Vec<Vec<Scripture>> {
+        let mut passages = self.passages(db);
+        passages.sort_by_key(|verses| next_review(history, verses));
+        passages
+    }
+
+    /// How many passages are due for review at `now` (Unix seconds)
+    pub fn due(&self, db: &ScriptureDb, history: &MemorizeHistory, now: u64) -> usize {
+        self.passages(db).iter().filter(|verses| next_review(history, verses) <= now).count()
+    }
+}
+
+/// When the passage's least-known verse is next due; 0 for a verse never studied
+fn next_review(history: &MemorizeHistory, verses: &[Scripture]) -> u64 {
+    verses.iter().map(|v| history.next_review(&v.verse_title).unwrap_or(0)).min().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memorize::MemorizeAttempt;
+
+    fn db() -> ScriptureDb {
+        let verse = |book: &str, chapter, number| Scripture {
+            volume_title: "Book of Mormon".into(),
+            book_title: book.into(),
+            book_short_title: book.into(),
+            chapter_number: chapter,
+            verse_number: number,
+            verse_title: format!("{} {}:{}", book, chapter, number),
+            verse_short_title: format!("{} {}:{}", book, chapter, number),
+            scripture_text: String::new(),
+        };
+        ScriptureDb::from_verses(vec![
+            verse("1 Nephi", 3, 7),
+            verse("Ether", 12, 6),
+            verse("Ether", 12, 27),
+            verse("Moroni", 10, 4),
+            verse("Moroni", 10, 5),
+        ])
+    }
+
+    #[test]
+    fn test_find_and_passages() {
+        assert_eq!(Deck::find("book").unwrap().name, "Book of Mormon");
+        assert_eq!(Deck::find("NEW TESTAMENT").unwrap().name, "New Testament");
+        assert!(Deck::find("").is_none());
+        assert!(DECKS.iter().all(|deck| deck.references.len() >= 24));
+
+        let passages = Deck::find("Book of Mormon").unwrap().passages(&db());
+        let lengths: Vec<usize> = passages.iter().map(Vec::len).collect();
+        assert_eq!(lengths, [1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_review_order_puts_due_passages_first() {
+        let (db, deck) = (db(), Deck::find("Book of Mormon").unwrap());
+        let day = 24 * 60 * 60;
+        let mut history = MemorizeHistory::new();
+        let recalled = |timestamp| vec![MemorizeAttempt { timestamp, accuracy: 1.0 }];
+        history.verses.insert("1 Nephi 3:7".to_string(), recalled(10 * day));
+        history.verses.insert("Ether 12:6".to_string(), recalled(day));
+        history.verses.insert("Moroni 10:4".to_string(), recalled(day));
+
+        // Ether 12:27 and Moroni 10:5 were never studied; Ether 12:6 came due first
+        let order: Vec<String> = deck.review_order(&db, &history).iter().map(|p| p[0].verse_title.clone()).collect();
+        assert_eq!(order, ["Ether 12:27", "Moroni 10:4", "Ether 12:6", "1 Nephi 3:7"]);
+        assert_eq!(deck.due(&db, &history, 5 * day), 3);
+    }
+}
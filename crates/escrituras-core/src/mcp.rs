@@ -1,8 +1,14 @@
 use crate::embeddings::EmbeddingsDb;
+use crate::error::{EscriturasError, Result};
+use crate::hymns::{Hymn, HymnDb};
+use crate::interlinear::{Interlinear, StrongsEntry};
 use crate::scripture::ScriptureDb;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
 
 #[derive(Debug, Deserialize)]
 struct McpRequest {
@@ -72,7 +78,7 @@ fn get_tools() -> Vec<ToolDefinition> {
                 "properties": {
                     "reference": {
                         "type": "string",
-                        "description": "Scripture reference (e.g., 'John 3:16', '1 Nephi 3:7', 'D&C 4:2')"
+                        "description": "Scripture reference (e.g., 'John 3:16', '1 Nephi 3:7', 'D&C 4:2', or '1 Nephi intro' for a book's introduction)"
                     }
                 },
                 "required": ["reference"]
@@ -90,7 +96,7 @@ fn get_tools() -> Vec<ToolDefinition> {
                     },
                     "chapter": {
                         "type": "integer",
-                        "description": "Chapter number"
+                        "description": "Chapter number (0 for the book's introduction, when installed)"
                     }
                 },
                 "required": ["book", "chapter"]
@@ -152,6 +158,70 @@ fn get_tools() -> Vec<ToolDefinition> {
                 }
             }),
         },
+        ToolDefinition {
+            name: "lookup_hymn".to_string(),
+            description: "Get the text of a hymn by number or title, with the scriptures it draws on".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "hymn": {
+                        "type": "string",
+                        "description": "Hymn number or title (e.g., '2', 'How Firm a Foundation')"
+                    }
+                },
+                "required": ["hymn"]
+            }),
+        },
+        ToolDefinition {
+            name: "search_hymns".to_string(),
+            description: "Search hymn titles and texts by keyword with stemming".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Search query"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of results (default: 10)",
+                        "default": 10
+                    }
+                },
+                "required": ["query"]
+            }),
+        },
+        ToolDefinition {
+            name: "hymns_for_scripture".to_string(),
+            description: "List the hymns that draw on a scripture passage".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "reference": {
+                        "type": "string",
+                        "description": "Scripture reference (e.g., 'D&C 109:79', 'Isaiah 41')"
+                    }
+                },
+                "required": ["reference"]
+            }),
+        },
+        ToolDefinition {
+            name: "strongs_lookup".to_string(),
+            description: "Look up the Hebrew or Greek behind the King James Bible: a Strong's number's definition, or a verse's words with their Strong's numbers and definitions".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "number": {
+                        "type": "string",
+                        "description": "Strong's number (e.g., 'H430', 'G26')"
+                    },
+                    "reference": {
+                        "type": "string",
+                        "description": "Bible verse reference (e.g., 'Genesis 1:1', 'John 3:16')"
+                    }
+                }
+            }),
+        },
     ]
 }
 
@@ -184,6 +254,8 @@ fn handle_tool_call(
     id: Option<serde_json::Value>,
     params: &serde_json::Value,
     db: &ScriptureDb,
+    hymns: Option<&HymnDb>,
+    interlinear: Option<&Interlinear>,
     embeddings: &mut Option<EmbeddingsDb>,
 ) -> McpResponse {
     let tool_name = params
@@ -198,6 +270,30 @@ fn handle_tool_call(
         "search_scriptures" => handle_search_scriptures(id, &arguments, db, embeddings),
         "get_context" => handle_get_context(id, &arguments, db),
         "list_books" => handle_list_books(id, &arguments, db),
+        "lookup_hymn" | "search_hymns" | "hymns_for_scripture" => {
+            let Some(hymns) = hymns else {
+                return McpResponse::error(id, -32603, &format!("No hymns: add {} to the data directory", HymnDb::FILE_NAME));
+            };
+            match tool_name {
+                "lookup_hymn" => handle_lookup_hymn(id, &arguments, hymns),
+                "search_hymns" => handle_search_hymns(id, &arguments, hymns),
+                _ => handle_hymns_for_scripture(id, &arguments, db, hymns),
+            }
+        }
+        "strongs_lookup" => {
+            let Some(interlinear) = interlinear else {
+                return McpResponse::error(
+                    id,
+                    -32603,
+                    &format!(
+                        "No Strong's data: add {} and {} to the data directory",
+                        Interlinear::LEXICON_FILE_NAME,
+                        Interlinear::VERSES_FILE_NAME
+                    ),
+                );
+            };
+            handle_strongs_lookup(id, &arguments, db, interlinear)
+        }
         _ => McpResponse::error(id, -32601, &format!("Unknown tool: {}", tool_name)),
     }
 }
@@ -304,15 +400,20 @@ fn handle_search_scriptures(
     let mut combined_results: Vec<String> = Vec::new();
     let mut seen_titles: HashSet<String> = HashSet::new();
 
-    // Try semantic search if embeddings are available (uses local ONNX model)
+    // Try semantic search if embeddings are available (uses local ONNX model),
+    // falling back to keywords alone when it fails
     if let Some(emb) = embeddings {
-        if let Ok(semantic_matches) = emb.search(query, semantic_limit) {
-            for (verse_title, _score) in semantic_matches {
-                if let Some(scripture) = db.get_by_title(&verse_title) {
-                    seen_titles.insert(verse_title);
-                    combined_results.push(format!("{} - {}", scripture.verse_title, scripture.scripture_text));
+        match emb.search(query, semantic_limit) {
+            Ok(semantic_matches) => {
+                for (verse_title, _score) in semantic_matches {
+                    if let Some(scripture) = db.get_by_title(&verse_title) {
+                        seen_titles.insert(verse_title);
+                        combined_results.push(format!("{} - {}", scripture.verse_title, scripture.scripture_text));
+                    }
                 }
             }
+            Err(e @ EscriturasError::EmbeddingsMissing(_)) => tracing::debug!("Searching by keyword only: {}", e),
+            Err(e) => tracing::warn!("Semantic search failed: {}", e),
         }
     }
 
@@ -373,15 +474,7 @@ fn handle_get_context(
     }
 
     let scripture_ref = &refs[0];
-    let verses = db.get_verses_for_chapter(&scripture_ref.book_title, scripture_ref.chapter_number);
-
-    let start_verse = (scripture_ref.start_verse - before).max(1);
-    let end_verse = scripture_ref.end_verse + after;
-
-    let context_verses: Vec<_> = verses
-        .into_iter()
-        .filter(|v| v.verse_number >= start_verse && v.verse_number <= end_verse)
-        .collect();
+    let context_verses = db.verses_around(scripture_ref, before, after);
 
     if context_verses.is_empty() {
         return McpResponse::error(id, -32602, &format!("Verse not found: {}", reference));
@@ -461,8 +554,173 @@ fn handle_list_books(
     )
 }
 
+fn handle_lookup_hymn(
+    id: Option<serde_json::Value>,
+    args: &serde_json::Value,
+    hymns: &HymnDb,
+) -> McpResponse {
+    let query = match args.get("hymn").and_then(|v| v.as_str()) {
+        Some(q) => q,
+        None => return McpResponse::error(id, -32602, "Missing 'hymn' parameter"),
+    };
+    let Some(hymn) = hymns.find(query) else {
+        return McpResponse::error(id, -32602, &format!("Hymn not found: {}", query));
+    };
+
+    let mut text = hymn.label();
+    if !hymn.author.is_empty() {
+        text.push_str(&format!("\nText: {}", hymn.author));
+    }
+    for (i, stanza) in hymn.verses.iter().enumerate() {
+        text.push_str(&format!("\n\n{}. {}", i + 1, stanza));
+    }
+    if !hymn.scriptures.is_empty() {
+        text.push_str(&format!("\n\nScriptures: {}", hymn.scriptures.join("; ")));
+    }
+    text_response(id, text)
+}
+
+fn handle_search_hymns(
+    id: Option<serde_json::Value>,
+    args: &serde_json::Value,
+    hymns: &HymnDb,
+) -> McpResponse {
+    let query = match args.get("query").and_then(|v| v.as_str()) {
+        Some(q) => q,
+        None => return McpResponse::error(id, -32602, "Missing 'query' parameter"),
+    };
+    let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+
+    let found = hymns.search(query, limit);
+    if found.is_empty() {
+        return text_response(id, format!("No hymns found for '{}'", query));
+    }
+    text_response(id, format!("Found {} hymns for '{}':\n\n{}", found.len(), query, hymn_list(&found)))
+}
+
+fn handle_hymns_for_scripture(
+    id: Option<serde_json::Value>,
+    args: &serde_json::Value,
+    db: &ScriptureDb,
+    hymns: &HymnDb,
+) -> McpResponse {
+    let reference = match args.get("reference").and_then(|v| v.as_str()) {
+        Some(r) => r,
+        None => return McpResponse::error(id, -32602, "Missing 'reference' parameter"),
+    };
+    let Some(range) = db.parse_reference(reference) else {
+        return McpResponse::error(id, -32602, &format!("Could not parse reference: {}", reference));
+    };
+
+    let found = hymns.citing(db, &range);
+    if found.is_empty() {
+        return text_response(id, format!("No hymns draw on {}", range.display_title()));
+    }
+    text_response(id, format!("Hymns drawing on {}:\n\n{}", range.display_title(), hymn_list(&found)))
+}
+
+fn handle_strongs_lookup(
+    id: Option<serde_json::Value>,
+    args: &serde_json::Value,
+    db: &ScriptureDb,
+    interlinear: &Interlinear,
+) -> McpResponse {
+    if let Some(number) = args.get("number").and_then(|v| v.as_str()) {
+        let Some(entry) = interlinear.lookup(number) else {
+            return McpResponse::error(id, -32602, &format!("Strong's number not found: {}", number));
+        };
+        let mut text = strongs_entry(number.trim(), entry);
+        text.push_str(&format!("\nUsed in {} verses", interlinear.occurrences(number)));
+        return text_response(id, text);
+    }
+
+    let reference = match args.get("reference").and_then(|v| v.as_str()) {
+        Some(r) => r,
+        None => return McpResponse::error(id, -32602, "Missing 'number' or 'reference' parameter"),
+    };
+    let Some(range) = db.parse_reference(reference) else {
+        return McpResponse::error(id, -32602, &format!("Could not parse reference: {}", reference));
+    };
+    let mut sections = Vec::new();
+    for verse in db.get_verses_in_range(&range) {
+        let Some(words) = interlinear.words(&verse.verse_title) else {
+            continue;
+        };
+        let mut section = format!("{} - {}\n", verse.verse_title, verse.scripture_text);
+        for word in words.iter().filter(|w| !w.strongs.is_empty()) {
+            section.push_str(&format!("\n\"{}\"", word.text));
+            for number in &word.strongs {
+                match interlinear.lookup(number) {
+                    Some(entry) => section.push_str(&format!("\n  {}", strongs_entry(number, entry).replace('\n', "\n  "))),
+                    None => section.push_str(&format!("\n  {}", number)),
+                }
+            }
+        }
+        sections.push(section);
+    }
+    if sections.is_empty() {
+        return text_response(id, format!("No Strong's data for {}", range.display_title()));
+    }
+    text_response(id, sections.join("\n\n"))
+}
+
+/// "H430 אֱלֹהִים (ʼĕlôhîym)" and the definition under it
+fn strongs_entry(number: &str, entry: &StrongsEntry) -> String {
+    let mut text = format!("{} {}", number, entry.lemma);
+    if !entry.transliteration.is_empty() {
+        text.push_str(&format!(" ({})", entry.transliteration));
+    }
+    if !entry.definition.is_empty() {
+        text.push_str(&format!("\n{}", entry.definition));
+    }
+    if !entry.kjv_usage.is_empty() {
+        text.push_str(&format!("\nKJV: {}", entry.kjv_usage));
+    }
+    text
+}
+
+fn hymn_list(hymns: &[&Hymn]) -> String {
+    hymns.iter().map(|h| format!("- {}", h.label())).collect::<Vec<_>>().join("\n")
+}
+
+fn text_response(id: Option<serde_json::Value>, text: String) -> McpResponse {
+    McpResponse::success(
+        id,
+        serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": text
+            }]
+        }),
+    )
+}
+
+/// Answer one JSON-RPC message, or None for a notification (which gets no reply)
+pub fn handle_message(
+    message: &str,
+    db: &ScriptureDb,
+    hymns: Option<&HymnDb>,
+    interlinear: Option<&Interlinear>,
+    embeddings: &mut Option<EmbeddingsDb>,
+) -> Option<String> {
+    let response = match serde_json::from_str::<McpRequest>(message) {
+        Ok(request) => match request.method.as_str() {
+            "initialize" => handle_initialize(request.id),
+            "notifications/initialized" => return None,
+            "tools/list" => handle_tools_list(request.id),
+            "tools/call" => handle_tool_call(request.id, &request.params, db, hymns, interlinear, embeddings),
+            _ => McpResponse::error(request.id, -32601, &format!("Method not found: {}", request.method)),
+        },
+        Err(e) => McpResponse::error(None, -32700, &format!("Parse error: {}", e)),
+    };
+    serde_json::to_string(&response).ok()
+}
+
+/// Serve MCP over stdin/stdout, one JSON-RPC message per line
 pub fn run_mcp_server(
     db: ScriptureDb,
+    hymns: Option<HymnDb>,
+    interlinear: Option<Interlinear>,
     mut embeddings: Option<EmbeddingsDb>,
 ) {
     let stdin = std::io::stdin();
@@ -478,29 +736,266 @@ pub fn run_mcp_server(
             continue;
         }
 
-        let request: McpRequest = match serde_json::from_str(&line) {
-            Ok(r) => r,
-            Err(e) => {
-                let response = McpResponse::error(None, -32700, &format!("Parse error: {}", e));
-                let mut stdout = stdout.lock();
-                let _ = serde_json::to_writer(&mut stdout, &response);
-                let _ = writeln!(stdout);
-                let _ = stdout.flush();
-                continue;
+        if let Some(response) = handle_message(&line, &db, hymns.as_ref(), interlinear.as_ref(), &mut embeddings) {
+            let mut stdout = stdout.lock();
+            let _ = writeln!(stdout, "{}", response);
+            let _ = stdout.flush();
+        }
+    }
+}
+
+/// Largest request body the HTTP server reads; JSON-RPC messages are far smaller
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Largest request line and headers together the HTTP server reads
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// Whether a request's `Origin` is this computer. Browsers send one with
+/// every cross-site request, so checking it keeps a web page (say, one whose
+/// name was rebound to 127.0.0.1) from calling the server.
+fn is_local_origin(origin: &str) -> bool {
+    let Some((_, rest)) = origin.split_once("://") else {
+        return false;
+    };
+    let host = match rest.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(""),
+        None => rest.split([':', '/']).next().unwrap_or(""),
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "::1")
+}
+
+/// What the HTTP server answers from, shared by its connections
+struct ServerState {
+    db: ScriptureDb,
+    hymns: Option<HymnDb>,
+    interlinear: Option<Interlinear>,
+    embeddings: Option<EmbeddingsDb>,
+}
+
+/// Serve MCP over HTTP: each JSON-RPC message is POSTed to `/mcp` and answered
+/// in the response body (the streamable HTTP transport, without server-sent events)
+pub async fn run_mcp_http_server(
+    db: ScriptureDb,
+    hymns: Option<HymnDb>,
+    interlinear: Option<Interlinear>,
+    embeddings: Option<EmbeddingsDb>,
+    addr: &str,
+) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| EscriturasError::io(format!("Couldn't listen on {}", addr), e))?;
+    let state = Arc::new(Mutex::new(ServerState { db, hymns, interlinear, embeddings }));
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            if let Err(e) = serve_http_connection(stream, state).await {
+                tracing::warn!("MCP connection failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Requests on one connection until the client closes it
+async fn serve_http_connection(stream: TcpStream, state: Arc<Mutex<ServerState>>) -> Result<()> {
+    let mut stream = BufReader::new(stream);
+    loop {
+        let mut head_left = MAX_HEAD_BYTES;
+        let mut request_line = String::new();
+        if read_head_line(&mut stream, &mut request_line, &mut head_left).await? == 0 {
+            return Ok(());
+        }
+        if !request_line.ends_with('\n') {
+            return write_response(stream.get_mut(), "431 Request Header Fields Too Large", "").await;
+        }
+        let mut content_length = 0;
+        let mut foreign_origin = false;
+        loop {
+            let mut header = String::new();
+            let read = read_head_line(&mut stream, &mut header, &mut head_left).await?;
+            if read == 0 && head_left > 0 {
+                return Ok(());
+            }
+            if !header.ends_with('\n') {
+                return write_response(stream.get_mut(), "431 Request Header Fields Too Large", "").await;
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().map_err(|_| EscriturasError::ParseError(format!("Bad Content-Length: {}", value.trim())))?;
+                } else if name.eq_ignore_ascii_case("origin") {
+                    foreign_origin = !is_local_origin(value.trim());
+                }
+            }
+        }
+        // The body isn't read, so the connection can't carry another request
+        if content_length > MAX_BODY_BYTES {
+            return write_response(stream.get_mut(), "413 Payload Too Large", "").await;
+        }
+        if foreign_origin {
+            return write_response(stream.get_mut(), "403 Forbidden", "").await;
+        }
+        let mut body = vec![0; content_length];
+        stream.read_exact(&mut body).await?;
+
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let (status, reply) = match (method, path.split('?').next().unwrap_or("")) {
+            ("POST", "/mcp") => {
+                let message = String::from_utf8_lossy(&body).into_owned();
+                let state = Arc::clone(&state);
+                // Embedding a search query is slow and synchronous, so it's
+                // kept off the threads the connections are served from
+                let response = tokio::task::spawn_blocking(move || {
+                    let mut state = state.lock().unwrap();
+                    let ServerState { db, hymns, interlinear, embeddings } = &mut *state;
+                    handle_message(&message, db, hymns.as_ref(), interlinear.as_ref(), embeddings)
+                })
+                .await
+                .map_err(|e| EscriturasError::Other(format!("Answering an MCP request failed: {}", e)))?;
+                match response {
+                    Some(response) => ("200 OK", Some(response)),
+                    None => ("202 Accepted", None),
+                }
             }
+            (_, "/mcp") => ("405 Method Not Allowed", None),
+            _ => ("404 Not Found", None),
         };
 
-        let response = match request.method.as_str() {
-            "initialize" => handle_initialize(request.id),
-            "notifications/initialized" => continue, // Notification, no response
-            "tools/list" => handle_tools_list(request.id),
-            "tools/call" => handle_tool_call(request.id, &request.params, &db, &mut embeddings),
-            _ => McpResponse::error(request.id, -32601, &format!("Method not found: {}", request.method)),
+        write_response(stream.get_mut(), status, &reply.unwrap_or_default()).await?;
+    }
+}
+
+/// Read a line of the request head into `line`, no further than the `left`
+/// bytes the head has left, so one endless header can't fill memory
+async fn read_head_line(stream: &mut BufReader<TcpStream>, line: &mut String, left: &mut u64) -> Result<usize> {
+    let read = (&mut *stream).take(*left).read_line(line).await?;
+    *left -= read as u64;
+    Ok(read)
+}
+
+async fn write_response(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+    let head = format!("HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n", status, body.len());
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interlinear::TaggedWord;
+    use crate::scripture::Scripture;
+
+    #[test]
+    fn test_handle_message() {
+        let db = ScriptureDb::new();
+        let mut embeddings = None;
+
+        let reply = handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#, &db, None, None, &mut embeddings).unwrap();
+        assert!(reply.contains("\"lookup_verse\""));
+        assert!(handle_message(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#, &db, None, None, &mut embeddings).is_none());
+        let reply = handle_message("not json", &db, None, None, &mut embeddings).unwrap();
+        assert!(reply.contains("-32700"));
+    }
+
+    #[test]
+    fn test_hymn_tools() {
+        let db = ScriptureDb::new();
+        let hymns = HymnDb::from_hymns(vec![Hymn {
+            number: 2,
+            title: "The Spirit of God".to_string(),
+            author: "William W. Phelps".to_string(),
+            verses: vec!["The Spirit of God like a fire is burning!".to_string()],
+            scriptures: vec!["D&C 109:79-80".to_string()],
+        }]);
+        let mut embeddings = None;
+        let call = |tool: &str, args: &str| {
+            format!(r#"{{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{{"name":"{}","arguments":{}}}}}"#, tool, args)
+        };
+
+        let reply = handle_message(&call("lookup_hymn", r#"{"hymn":"2"}"#), &db, Some(&hymns), None, &mut embeddings).unwrap();
+        assert!(reply.contains("2 The Spirit of God") && reply.contains("Scriptures: D&C 109:79-80"));
+        let reply = handle_message(&call("search_hymns", r#"{"query":"burn"}"#), &db, Some(&hymns), None, &mut embeddings).unwrap();
+        assert!(reply.contains("Found 1 hymns"));
+        let reply = handle_message(&call("lookup_hymn", r#"{"hymn":"2"}"#), &db, None, None, &mut embeddings).unwrap();
+        assert!(reply.contains("No hymns: add hymns.json"));
+    }
+
+    #[test]
+    fn test_strongs_lookup() {
+        let verse = Scripture {
+            volume_title: "Old Testament".into(),
+            book_title: "Genesis".into(),
+            book_short_title: "Gen.".into(),
+            chapter_number: 1,
+            verse_number: 1,
+            verse_title: "Genesis 1:1".to_string(),
+            verse_short_title: "Gen. 1:1".to_string(),
+            scripture_text: "In the beginning God created the heaven and the earth.".to_string(),
+        };
+        let db = ScriptureDb::from_verses(vec![verse]);
+        let god = StrongsEntry {
+            lemma: "אֱלֹהִים".to_string(),
+            transliteration: "ʼĕlôhîym".to_string(),
+            definition: "gods; God".to_string(),
+            ..Default::default()
+        };
+        let words = vec![TaggedWord::from(("God".to_string(), "H430".to_string())), TaggedWord::from(("the".to_string(), String::new()))];
+        let interlinear = Interlinear::from_parts([("H430".to_string(), god)], [("Genesis 1:1".to_string(), words)]);
+        let mut embeddings = None;
+        let mut call = |args: &str| {
+            let message = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{{"name":"strongs_lookup","arguments":{}}}}}"#, args);
+            handle_message(&message, &db, None, Some(&interlinear), &mut embeddings).unwrap()
+        };
+
+        let reply = call(r#"{"number":"h0430"}"#);
+        assert!(reply.contains("gods; God") && reply.contains("Used in 1 verses"));
+        let reply = call(r#"{"reference":"Gen. 1:1"}"#);
+        assert!(reply.contains("\\\"God\\\"") && reply.contains("H430 אֱלֹהִים (ʼĕlôhîym)"));
+        assert!(call(r#"{"number":"G26"}"#).contains("not found"));
+    }
+
+    #[test]
+    fn test_local_origins() {
+        assert!(is_local_origin("http://localhost:3000"));
+        assert!(is_local_origin("http://127.0.0.1"));
+        assert!(is_local_origin("http://[::1]:8080"));
+        assert!(!is_local_origin("https://example.com"));
+        assert!(!is_local_origin("http://localhost.example.com"));
+        assert!(!is_local_origin("null"));
+    }
+
+    #[tokio::test]
+    async fn test_http_refuses_large_bodies_and_foreign_origins() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(ServerState { db: ScriptureDb::new(), hymns: None, interlinear: None, embeddings: None }));
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_http_connection(stream, Arc::clone(&state)));
+            }
+        });
+        // A request's headers and body, and the status line of the reply
+        let status = |rest: String| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(format!("POST /mcp HTTP/1.1\r\n{}", rest).as_bytes()).await.unwrap();
+            let mut reply = String::new();
+            BufReader::new(stream).read_line(&mut reply).await.unwrap();
+            reply.trim_end().to_string()
         };
 
-        let mut stdout = stdout.lock();
-        let _ = serde_json::to_writer(&mut stdout, &response);
-        let _ = writeln!(stdout);
-        let _ = stdout.flush();
+        assert_eq!(status("Content-Length: 10000000000\r\n\r\n".to_string()).await, "HTTP/1.1 413 Payload Too Large");
+        // Exactly the head's limit, so nothing is left unread when the server hangs up
+        let endless = format!("X-Padding: {}", "a".repeat(MAX_HEAD_BYTES as usize - "POST /mcp HTTP/1.1\r\nX-Padding: ".len()));
+        assert_eq!(status(endless).await, "HTTP/1.1 431 Request Header Fields Too Large");
+        assert_eq!(status("Origin: https://example.com\r\nContent-Length: 0\r\n\r\n".to_string()).await, "HTTP/1.1 403 Forbidden");
+        let list = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+        let local = format!("Origin: http://localhost:6277\r\nContent-Length: {}\r\n\r\n{}", list.len(), list);
+        assert_eq!(status(local).await, "HTTP/1.1 200 OK");
     }
 }
@@ -0,0 +1,348 @@
+//! Memorization progress tracking
+//!
+//! Records the word-accuracy score of each typed memorization attempt per verse,
+//! kept in the study data store so any UI can show progress over time, and
+//! spaces reviews out from it: a verse recalled well comes back after a day,
+//! then two, four, and so on, and one missed comes back right away.
+//!
+//! An attempt is scored by lining its words up with the verse's (a longest
+//! common subsequence, ignoring case and punctuation): the score is the share
+//! of the verse's words it got.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::Result;
+use crate::scripture::Scripture;
+
+/// Accuracy that counts as recalling a verse
+pub const RECALLED: f32 = 0.9;
+
+/// Times the wait between reviews doubles, to at most 64 days
+const MAX_DOUBLINGS: u32 = 6;
+
+/// A single typed attempt at a verse
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct MemorizeAttempt {
+    /// Unix timestamp (seconds) of the attempt
+    pub timestamp: u64,
+    /// Fraction of words recalled correctly (0.0 - 1.0)
+    pub accuracy: f32,
+}
+
+/// Summary of all attempts at a verse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerseStats {
+    pub best: f32,
+    pub last: f32,
+    pub attempts: usize,
+}
+
+/// Typing attempt history, keyed by verse title (e.g., "John 3:16")
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MemorizeHistory {
+    pub verses: HashMap<String, Vec<MemorizeAttempt>>,
+}
+
+impl MemorizeHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// From the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Result<Self> {
+        crate::store::Store::open()?.memorize_history()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let history: MemorizeHistory = serde_json::from_str(&content)?;
+        Ok(history)
+    }
+
+    /// Write what changed since `loaded` to the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, loaded: &Self) -> Result<()> {
+        crate::store::Store::open()?.save_memorize_history(loaded, self)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Record an attempt at the current time
+    pub fn record(&mut self, verse_title: &str, accuracy: f32) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.verses
+            .entry(verse_title.to_string())
+            .or_default()
+            .push(MemorizeAttempt {
+                timestamp,
+                accuracy: accuracy.clamp(0.0, 1.0),
+            });
+    }
+
+    /// Best/last/count for a verse, or None if it has never been attempted
+    pub fn stats(&self, verse_title: &str) -> Option<VerseStats> {
+        let attempts = self.verses.get(verse_title)?;
+        let last = attempts.last()?.accuracy;
+        let best = attempts.iter().map(|a| a.accuracy).fold(0.0, f32::max);
+        Some(VerseStats {
+            best,
+            last,
+            attempts: attempts.len(),
+        })
+    }
+
+    /// When a verse is next due for review (Unix seconds): a day after its
+    /// last attempt for one recalled attempt in a row, doubling with each
+    /// more, or at the last attempt itself when it wasn't recalled. None if
+    /// it has never been attempted, so it's due now.
+    pub fn next_review(&self, verse_title: &str) -> Option<u64> {
+        let attempts = self.verses.get(verse_title)?;
+        let last = attempts.last()?;
+        let streak = attempts.iter().rev().take_while(|a| a.accuracy >= RECALLED).count() as u32;
+        let days = match streak {
+            0 => 0,
+            n => 1u64 << (n - 1).min(MAX_DOUBLINGS),
+        };
+        Some(last.timestamp + days * 24 * 60 * 60)
+    }
+
+    /// All attempts across verses in chronological order (for charting progress)
+    pub fn timeline(&self) -> Vec<(&str, MemorizeAttempt)> {
+        let mut all: Vec<(&str, MemorizeAttempt)> = self
+            .verses
+            .iter()
+            .flat_map(|(title, attempts)| attempts.iter().map(move |a| (title.as_str(), *a)))
+            .collect();
+        all.sort_by_key(|(_, a)| a.timestamp);
+        all
+    }
+}
+
+/// Represents the diff result between original and user text
+#[derive(Debug)]
+pub struct DiffResult {
+    /// Words from original with their status
+    pub original_words: Vec<(String, WordStatus)>,
+    /// Words from user with their status
+    pub user_words: Vec<(String, WordStatus)>,
+}
+
+/// Fraction of words marked correct (1.0 when there are no words)
+pub fn word_accuracy(words: &[(String, WordStatus)]) -> f32 {
+    if words.is_empty() {
+        return 1.0;
+    }
+    let correct = words.iter().filter(|(_, status)| *status == WordStatus::Correct).count();
+    correct as f32 / words.len() as f32
+}
+
+/// Split a passage diff back into per-verse accuracies
+pub fn passage_verse_accuracies(passage: &[Scripture], diff: &DiffResult) -> Vec<f32> {
+    let mut offset = 0;
+    passage
+        .iter()
+        .map(|verse| {
+            let count = verse.scripture_text.split_whitespace().count();
+            let end = (offset + count).min(diff.original_words.len());
+            let accuracy = word_accuracy(&diff.original_words[offset..end]);
+            offset = end;
+            accuracy
+        })
+        .collect()
+}
+
+/// Score a typed attempt against a passage, one accuracy per verse
+pub fn verse_accuracies(passage: &[Scripture], attempt: &str) -> Vec<f32> {
+    let text = passage
+        .iter()
+        .map(|v| v.scripture_text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    passage_verse_accuracies(passage, &compute_word_diff(&text, attempt))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordStatus {
+    Correct,  // Word matches
+    Missing,  // Word in original but not in user
+    Wrong,    // Word in user but not matching original
+}
+
+/// Normalize a word for comparison (lowercase, strip punctuation)
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphabetic())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Compute LCS (Longest Common Subsequence) for word alignment
+fn compute_lcs(original: &[String], user: &[String]) -> Vec<(usize, usize)> {
+    let m = original.len();
+    let n = user.len();
+
+    // Build LCS table
+    let mut dp = vec![vec![0usize; n + 1]; m + 1];
+    for i in 1..=m {
+        for j in 1..=n {
+            if original[i - 1] == user[j - 1] {
+                dp[i][j] = dp[i - 1][j - 1] + 1;
+            } else {
+                dp[i][j] = dp[i - 1][j].max(dp[i][j - 1]);
+            }
+        }
+    }
+
+    // Backtrack to find matching pairs
+    let mut matches = Vec::new();
+    let mut i = m;
+    let mut j = n;
+    while i > 0 && j > 0 {
+        if original[i - 1] == user[j - 1] {
+            matches.push((i - 1, j - 1));
+            i -= 1;
+            j -= 1;
+        } else if dp[i - 1][j] > dp[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    matches.reverse();
+    matches
+}
+
+/// Compute word-by-word diff using LCS
+pub fn compute_word_diff(original: &str, user: &str) -> DiffResult {
+    // Split into words, preserving original forms
+    let orig_words: Vec<&str> = original.split_whitespace().collect();
+    let user_words: Vec<&str> = user.split_whitespace().collect();
+
+    // Normalize for comparison
+    let orig_normalized: Vec<String> = orig_words.iter().map(|w| normalize_word(w)).collect();
+    let user_normalized: Vec<String> = user_words.iter().map(|w| normalize_word(w)).collect();
+
+    // Find LCS matches
+    let matches = compute_lcs(&orig_normalized, &user_normalized);
+    let match_set_orig: HashSet<usize> = matches.iter().map(|(o, _)| *o).collect();
+    let match_set_user: HashSet<usize> = matches.iter().map(|(_, u)| *u).collect();
+
+    // Build result for original words
+    let original_result: Vec<(String, WordStatus)> = orig_words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            if match_set_orig.contains(&i) {
+                (w.to_string(), WordStatus::Correct)
+            } else {
+                (w.to_string(), WordStatus::Missing)
+            }
+        })
+        .collect();
+
+    // Build result for user words
+    let user_result: Vec<(String, WordStatus)> = user_words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            if match_set_user.contains(&i) {
+                (w.to_string(), WordStatus::Correct)
+            } else {
+                (w.to_string(), WordStatus::Wrong)
+            }
+        })
+        .collect();
+
+    DiffResult {
+        original_words: original_result,
+        user_words: user_result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_best_last_count() {
+        let mut history = MemorizeHistory::new();
+        assert!(history.stats("John 3:16").is_none());
+
+        history.record("John 3:16", 0.5);
+        history.record("John 3:16", 0.92);
+        history.record("John 3:16", 0.85);
+
+        let stats = history.stats("John 3:16").unwrap();
+        assert_eq!(stats.attempts, 3);
+        assert!((stats.best - 0.92).abs() < f32::EPSILON);
+        assert!((stats.last - 0.85).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_reviews_spread_out_while_recalled() {
+        let day = 24 * 60 * 60;
+        let mut history = MemorizeHistory::new();
+        assert_eq!(history.next_review("Ether 12:27"), None);
+
+        let attempt = |timestamp, accuracy| MemorizeAttempt { timestamp, accuracy };
+        history.verses.insert("Ether 12:27".to_string(), vec![attempt(1000, 0.5)]);
+        assert_eq!(history.next_review("Ether 12:27"), Some(1000));
+        history.verses.get_mut("Ether 12:27").unwrap().extend([attempt(2000, 0.95), attempt(3000, 1.0), attempt(4000, 0.92)]);
+        assert_eq!(history.next_review("Ether 12:27"), Some(4000 + 4 * day));
+        history.verses.get_mut("Ether 12:27").unwrap().push(attempt(5000, 0.4));
+        assert_eq!(history.next_review("Ether 12:27"), Some(5000));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+
+        let mut history = MemorizeHistory::new();
+        history.record("Moroni 10:4", 1.0);
+        history.record("Alma 32:21", 0.75);
+        history.save_to(&path).unwrap();
+
+        let loaded = MemorizeHistory::load_from(&path).unwrap();
+        assert_eq!(loaded.stats("Moroni 10:4").unwrap().attempts, 1);
+        assert_eq!(loaded.timeline().len(), 2);
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = MemorizeHistory::load_from(&dir.path().join("missing.json")).unwrap();
+        assert!(history.verses.is_empty());
+    }
+
+    #[test]
+    fn test_word_diff_scores_attempt() {
+        let diff = compute_word_diff("And it came to pass, that I, Nephi", "and it came to pass I nephi said");
+        let statuses: Vec<WordStatus> = diff.original_words.iter().map(|(_, s)| *s).collect();
+        use WordStatus::*;
+        assert_eq!(statuses, [Correct, Correct, Correct, Correct, Correct, Missing, Correct, Correct]);
+        assert_eq!(diff.user_words.last().unwrap(), &("said".to_string(), Wrong));
+        assert_eq!(word_accuracy(&diff.original_words), 7.0 / 8.0);
+    }
+}
@@ -0,0 +1,423 @@
+//! Chapter outlines
+//!
+//! An outline divides a chapter into sections, each a run of verses with its
+//! speaker and whether it tells what happened (narrative) or teaches
+//! (doctrine), and long sections into the narrative and teaching within them.
+//! `Outline::heuristic` builds one at once from the wording of the verses:
+//! a section starts where someone new speaks ("I, Nephi", "thus saith the
+//! Lord", "Alma said") and where the narration picks up again ("And it came
+//! to pass"). The AI provider can do better; `prompt` asks it for an outline
+//! with the heuristic one as a starting point and `Outline::from_ai` reads its
+//! answer. AI outlines are kept in an `OutlineCache`, one file per chapter.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::error::{EscriturasError, Result};
+use crate::scripture::Scripture;
+
+/// Sections at least this long are divided into their narrative and teaching
+const SUBDIVIDE_VERSES: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    /// What happened
+    #[default]
+    Narrative,
+    /// Teaching, prophecy, and commandment
+    Doctrine,
+}
+
+impl SectionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Narrative => "Narrative",
+            Self::Doctrine => "Teaching",
+        }
+    }
+}
+
+/// A run of verses in an outline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutlineSection {
+    pub title: String,
+    #[serde(alias = "start")]
+    pub start_verse: i32,
+    #[serde(alias = "end")]
+    pub end_verse: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    #[serde(default)]
+    pub kind: SectionKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineSection>,
+}
+
+impl OutlineSection {
+    pub fn contains_verse(&self, verse: i32) -> bool {
+        (self.start_verse..=self.end_verse).contains(&verse)
+    }
+
+    /// "1-7", or "4" for a single verse
+    pub fn range_label(&self) -> String {
+        if self.start_verse == self.end_verse {
+            self.start_verse.to_string()
+        } else {
+            format!("{}–{}", self.start_verse, self.end_verse)
+        }
+    }
+}
+
+/// Who made an outline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "by")]
+pub enum OutlineSource {
+    Heuristic,
+    Ai { model: String },
+}
+
+/// The sections of a chapter, in order
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Outline {
+    pub book: String,
+    pub chapter: i32,
+    /// Number of the chapter's last verse, to tell a cached outline of other
+    /// scripture data from this one's
+    pub last_verse: i32,
+    pub source: OutlineSource,
+    pub sections: Vec<OutlineSection>,
+}
+
+impl Outline {
+    /// An outline of a chapter's verses from their wording alone
+    pub fn heuristic(verses: &[Scripture]) -> Self {
+        let (book, chapter) = verses.first().map_or((String::new(), 0), |v| (v.book_title.to_string(), v.chapter_number));
+        let last_verse = verses.last().map_or(0, |v| v.verse_number);
+
+        // The first person to name themselves narrates the chapter
+        let narrator = verses.iter().find_map(|v| narrator_in(&v.scripture_text));
+        let mut runs: Vec<(Option<String>, Vec<&Scripture>)> = Vec::new();
+        for verse in verses {
+            let current = runs.last().and_then(|(speaker, _)| speaker.clone()).or_else(|| narrator.clone());
+            let speaker = match speaker_in(&verse.scripture_text) {
+                Some(speaker) => Some(speaker),
+                None if resumes_narration(&verse.scripture_text) => narrator.clone(),
+                None => current.clone(),
+            };
+            match runs.last_mut() {
+                Some((run_speaker, run)) if *run_speaker == speaker || (speaker == current && run.is_empty()) => run.push(verse),
+                _ => runs.push((speaker, vec![verse])),
+            }
+        }
+
+        let sections = runs.into_iter().map(|(speaker, verses)| section(speaker, &verses, narrator.as_deref())).collect();
+        Self { book, chapter, last_verse, source: OutlineSource::Heuristic, sections }
+    }
+
+    /// An outline from the AI's answer to `prompt`: JSON with a "sections"
+    /// list, perhaps with text around it. Sections outside the chapter are
+    /// dropped, and ranges are kept in order.
+    pub fn from_ai(verses: &[Scripture], answer: &str, model: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Answer {
+            sections: Vec<OutlineSection>,
+        }
+        let invalid = |detail: String| EscriturasError::ParseError(format!("The AI's outline isn't usable: {}", detail));
+        let (start, end) = answer.find('{').zip(answer.rfind('}')).ok_or_else(|| invalid("no JSON in the answer".to_string()))?;
+        let parsed: Answer = serde_json::from_str(&answer[start..=end]).map_err(|e| invalid(e.to_string()))?;
+
+        let mut outline = Self::heuristic(verses);
+        outline.sections = clean_sections(parsed.sections, 1, outline.last_verse);
+        if outline.sections.is_empty() {
+            return Err(invalid("no sections in the chapter".to_string()));
+        }
+        outline.source = OutlineSource::Ai { model: model.to_string() };
+        Ok(outline)
+    }
+
+    /// The innermost section holding a verse: (section, child) indexes
+    pub fn section_of(&self, verse: i32) -> Option<(usize, Option<usize>)> {
+        let i = self.sections.iter().position(|s| s.contains_verse(verse))?;
+        Some((i, self.sections[i].children.iter().position(|c| c.contains_verse(verse))))
+    }
+}
+
+/// Prompt asking the AI to outline a chapter, answering in the JSON
+/// `Outline::from_ai` reads
+pub fn prompt(verses: &[Scripture], heuristic: &Outline) -> String {
+    let title = verses.first().map_or(String::new(), |v| format!("{} {}", v.book_title, v.chapter_number));
+    let mut prompt = format!(
+        "Outline {} for a reader who wants to get their bearings in it. Divide the chapter into its main sections, \
+         and sections longer than a few verses into their parts. For each, give a short descriptive title \
+         (\"Lehi's vision of the tree of life\", not \"Narrative\"), its first and last verse, who is speaking \
+         if anyone is, and whether it is narrative (what happened) or doctrine (teaching, prophecy, or commandment).\n\n\
+         Answer with JSON only, in this form:\n\
+         {{\"sections\": [{{\"title\": \"...\", \"start\": 1, \"end\": 7, \"speaker\": \"Nephi\", \"kind\": \"narrative\", \
+         \"children\": [{{\"title\": \"...\", \"start\": 1, \"end\": 3, \"kind\": \"narrative\"}}]}}]}}\n\n\
+         Sections must cover every verse in order without overlapping.\n\n",
+        title
+    );
+    prompt.push_str("A rough division by who is speaking, to improve on:\n");
+    for section in &heuristic.sections {
+        prompt.push_str(&format!("- verses {}: {}\n", section.range_label(), section.title));
+    }
+    prompt.push_str("\nThe chapter:\n");
+    for verse in verses {
+        prompt.push_str(&format!("{} {}\n", verse.verse_number, verse.scripture_text));
+    }
+    prompt
+}
+
+/// AI outlines, a JSON file per chapter
+#[derive(Debug, Clone)]
+pub struct OutlineCache {
+    dir: PathBuf,
+}
+
+impl OutlineCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `~/.cache/escrituras/outlines`
+    pub fn default_dir() -> Result<PathBuf> {
+        Ok(crate::paths::cache_dir()?.join("outlines"))
+    }
+
+    /// The cached outline of a chapter, if it was made from these verses
+    pub fn get(&self, book: &str, chapter: i32, last_verse: i32) -> Option<Outline> {
+        let content = fs::read_to_string(self.path(book, chapter)).ok()?;
+        let outline: Outline = serde_json::from_str(&content).ok()?;
+        (outline.book == book && outline.chapter == chapter && outline.last_verse == last_verse).then_some(outline)
+    }
+
+    pub fn put(&self, outline: &Outline) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(&outline.book, outline.chapter), serde_json::to_string_pretty(outline)?)?;
+        Ok(())
+    }
+
+    /// "Alma 32.json"
+    fn path(&self, book: &str, chapter: i32) -> PathBuf {
+        let name: String = book.chars().map(|c| if matches!(c, '/' | '\\' | ':') { '-' } else { c }).collect();
+        Path::new(&self.dir).join(format!("{} {}.json", name, chapter))
+    }
+}
+
+/// A section of the verses one speaker has, divided by kind when it's long
+fn section(speaker: Option<String>, verses: &[&Scripture], narrator: Option<&str>) -> OutlineSection {
+    let kinds = smooth(verses.iter().map(|v| kind_of(&v.scripture_text)).collect());
+    let doctrine = kinds.iter().filter(|k| **k == SectionKind::Doctrine).count();
+    let kind = if doctrine * 2 > kinds.len() { SectionKind::Doctrine } else { SectionKind::Narrative };
+
+    let mut children = Vec::new();
+    if verses.len() >= SUBDIVIDE_VERSES {
+        let mut start = 0;
+        for i in 1..=verses.len() {
+            if i == verses.len() || kinds[i] != kinds[start] {
+                children.push(OutlineSection {
+                    title: kinds[start].label().to_string(),
+                    start_verse: verses[start].verse_number,
+                    end_verse: verses[i - 1].verse_number,
+                    speaker: None,
+                    kind: kinds[start],
+                    children: Vec::new(),
+                });
+                start = i;
+            }
+        }
+        if children.len() < 2 {
+            children.clear();
+        }
+    }
+
+    let title = match (&speaker, kind) {
+        (Some(speaker), SectionKind::Narrative) if Some(speaker.as_str()) == narrator => format!("{}'s account", speaker),
+        (Some(speaker), SectionKind::Narrative) => format!("{} speaks", speaker),
+        (Some(speaker), SectionKind::Doctrine) => format!("{} teaches", speaker),
+        (None, kind) => kind.label().to_string(),
+    };
+    OutlineSection {
+        title,
+        start_verse: verses.first().map_or(0, |v| v.verse_number),
+        end_verse: verses.last().map_or(0, |v| v.verse_number),
+        speaker,
+        kind,
+        children,
+    }
+}
+
+/// Whether a verse tells or teaches, by its words
+fn kind_of(text: &str) -> SectionKind {
+    const NARRATIVE: &[&str] = &[
+        "came to pass", "went", "came", "did", "took", "departed", "journey", "returned", "pitched", "smote",
+        "slew", "battle", "armies", "army", "wilderness", "brought", "gathered", "fled", "began", "arose",
+    ];
+    const DOCTRINE: &[&str] = &[
+        "shall", "must", "commandment", "commandments", "repent", "repentance", "faith", "believe", "salvation",
+        "redemption", "ye", "you", "blessed", "righteous", "righteousness", "christ", "atonement", "spirit",
+        "covenant", "baptized", "baptism", "prophesy", "eternal", "mercy", "grace", "sin", "sins", "hearts",
+    ];
+    let text = text.to_lowercase();
+    let words: Vec<&str> = text.split(|c: char| !c.is_alphabetic()).filter(|w| !w.is_empty()).collect();
+    let count = |markers: &[&str]| {
+        markers
+            .iter()
+            .map(|m| if m.contains(' ') { text.matches(m).count() } else { words.iter().filter(|w| *w == m).count() })
+            .sum::<usize>()
+    };
+    if count(DOCTRINE) > count(NARRATIVE) + 1 {
+        SectionKind::Doctrine
+    } else {
+        SectionKind::Narrative
+    }
+}
+
+/// Fold single verses of one kind between verses of the other into them
+fn smooth(mut kinds: Vec<SectionKind>) -> Vec<SectionKind> {
+    for i in 1..kinds.len().saturating_sub(1) {
+        if kinds[i - 1] == kinds[i + 1] && kinds[i] != kinds[i - 1] {
+            kinds[i] = kinds[i - 1];
+        }
+    }
+    kinds
+}
+
+/// "I, Nephi, ..." names the one writing
+fn narrator_in(text: &str) -> Option<String> {
+    static NARRATOR: OnceLock<Regex> = OnceLock::new();
+    let re = NARRATOR.get_or_init(|| Regex::new(r"\bI,\s+([A-Z][a-z]+)\b").unwrap());
+    re.captures(text).map(|c| c[1].to_string())
+}
+
+/// Someone beginning to speak in a verse
+fn speaker_in(text: &str) -> Option<String> {
+    static SPEAKER: OnceLock<Regex> = OnceLock::new();
+    let re = SPEAKER.get_or_init(|| {
+        Regex::new(
+            r"\b(?:the\s+)?([A-Z][a-z]+(?:\s+[A-Z][a-z]+)?)\s+(?:said|spake|saith|answered|cried|prophesied|began to speak|commanded)\b",
+        )
+        .unwrap()
+    });
+    if let Some(narrator) = narrator_in(text) {
+        return Some(narrator);
+    }
+    let name = re.captures(text)?[1].to_string();
+    match name.as_str() {
+        "Lord" | "Lord God" | "God" => Some("The Lord".to_string()),
+        _ if NOT_NAMES.contains(&name.split_whitespace().next()?) => None,
+        _ => Some(name),
+    }
+}
+
+/// Capitalized words that start sentences rather than name speakers
+const NOT_NAMES: &[&str] = &[
+    "And", "Now", "Behold", "Then", "Yea", "But", "For", "Wherefore", "Therefore", "O", "I", "Thus", "It", "He",
+    "She", "They", "We", "Ye", "This", "These", "Verily", "Amen", "When", "After", "Lo", "Hear",
+];
+
+/// The narration picking up again after someone's words
+fn resumes_narration(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("And it came to pass") || text.starts_with("And now it came to pass")
+}
+
+/// Sections within `first..=last`, in order and without overlapping, their
+/// children cleaned the same way within them
+fn clean_sections(sections: Vec<OutlineSection>, first: i32, last: i32) -> Vec<OutlineSection> {
+    let mut sections: Vec<OutlineSection> = sections
+        .into_iter()
+        .filter(|s| !s.title.trim().is_empty() && s.start_verse <= s.end_verse && s.end_verse >= first && s.start_verse <= last)
+        .collect();
+    sections.sort_by_key(|s| s.start_verse);
+    let mut next = first;
+    let mut cleaned = Vec::new();
+    for mut section in sections {
+        section.start_verse = section.start_verse.max(next);
+        section.end_verse = section.end_verse.min(last);
+        if section.start_verse > section.end_verse {
+            continue;
+        }
+        next = section.end_verse + 1;
+        section.title = section.title.trim().to_string();
+        section.speaker = section.speaker.filter(|s| !s.trim().is_empty());
+        let (start, end) = (section.start_verse, section.end_verse);
+        section.children = clean_sections(std::mem::take(&mut section.children), start, end);
+        cleaned.push(section);
+    }
+    cleaned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(texts: &[&str]) -> Vec<Scripture> {
+        texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| Scripture {
+                volume_title: "Book of Mormon".into(),
+                book_title: "1 Nephi".into(),
+                book_short_title: "1 Ne.".into(),
+                chapter_number: 2,
+                verse_number: i as i32 + 1,
+                verse_title: format!("1 Nephi 2:{}", i + 1),
+                verse_short_title: format!("1 Ne. 2:{}", i + 1),
+                scripture_text: text.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_heuristic_outline_follows_speakers() {
+        let verses = chapter(&[
+            "For behold, it came to pass that I, Nephi, went down into the wilderness with my father.",
+            "And it came to pass that he departed into the wilderness, and took his family.",
+            "And the Lord said unto me: Blessed art thou, Nephi, because of thy faith.",
+            "And inasmuch as ye shall keep my commandments, ye shall prosper, and shall be led to a land of promise.",
+            "And inasmuch as thy brethren shall rebel against thee, they shall be cut off from the presence of the Lord.",
+            "And it came to pass that I returned to the tent of my father.",
+        ]);
+        let outline = Outline::heuristic(&verses);
+        let sections: Vec<(&str, i32, i32)> =
+            outline.sections.iter().map(|s| (s.title.as_str(), s.start_verse, s.end_verse)).collect();
+        assert_eq!(sections, [("Nephi's account", 1, 2), ("The Lord teaches", 3, 5), ("Nephi's account", 6, 6)]);
+        assert_eq!(outline.sections[1].kind, SectionKind::Doctrine);
+        assert_eq!(outline.section_of(4), Some((1, None)));
+        assert!(prompt(&verses, &outline).contains("- verses 3–5: The Lord teaches"));
+    }
+
+    #[test]
+    fn test_ai_outline_is_checked_and_cached() {
+        let verses = chapter(&["One.", "Two.", "Three.", "Four."]);
+        let answer = r#"Here it is:
+            ```json
+            {"sections": [
+                {"title": "Departure", "start": 1, "end": 3, "speaker": "Nephi", "kind": "narrative",
+                 "children": [{"title": "Leaving", "start": 1, "end": 2}, {"title": "Overlap", "start": 2, "end": 9}]},
+                {"title": "Promise", "start": 3, "end": 7, "kind": "doctrine"},
+                {"title": "", "start": 4, "end": 4}
+            ]}
+            ```"#;
+        let outline = Outline::from_ai(&verses, answer, "llama3").unwrap();
+        let ranges: Vec<(i32, i32)> = outline.sections.iter().map(|s| (s.start_verse, s.end_verse)).collect();
+        assert_eq!(ranges, [(1, 3), (4, 4)]);
+        let children: Vec<(i32, i32)> = outline.sections[0].children.iter().map(|s| (s.start_verse, s.end_verse)).collect();
+        assert_eq!(children, [(1, 2), (3, 3)]);
+        assert_eq!(outline.source, OutlineSource::Ai { model: "llama3".to_string() });
+        assert!(Outline::from_ai(&verses, "I can't outline that.", "llama3").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = OutlineCache::new(dir.path());
+        cache.put(&outline).unwrap();
+        assert_eq!(cache.get("1 Nephi", 2, 4), Some(outline));
+        // Not for a chapter with other verses
+        assert_eq!(cache.get("1 Nephi", 2, 5), None);
+        assert!(dir.path().join("1 Nephi 2.json").exists());
+    }
+}
@@ -0,0 +1,161 @@
+//! Parallel passages of the Book of Mormon and the Bible
+//!
+//! Book of Mormon prophets quote whole chapters of Isaiah, and the Savior's
+//! sermon in 3 Nephi repeats the Sermon on the Mount. The passages are built
+//! in as a table pairing each quotation with its source verse for verse, so a
+//! chapter can be read beside its parallel with the verses lined up (see
+//! `alignments`).
+
+/// A passage of the Book of Mormon and the Bible passage it quotes, the same
+/// number of verses long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parallel {
+    pub passage: &'static str,
+    pub source: &'static str,
+}
+
+const fn parallel(passage: &'static str, source: &'static str) -> Parallel {
+    Parallel { passage, source }
+}
+
+/// The built-in parallels, in Book of Mormon order
+pub const PARALLELS: &[Parallel] = &[
+    parallel("1 Nephi 20:1-22", "Isaiah 48:1-22"),
+    parallel("1 Nephi 21:1-26", "Isaiah 49:1-26"),
+    parallel("2 Nephi 6:6-7", "Isaiah 49:22-23"),
+    parallel("2 Nephi 6:16-18", "Isaiah 49:24-26"),
+    parallel("2 Nephi 7:1-11", "Isaiah 50:1-11"),
+    parallel("2 Nephi 8:1-23", "Isaiah 51:1-23"),
+    parallel("2 Nephi 8:24-25", "Isaiah 52:1-2"),
+    parallel("2 Nephi 12:1-22", "Isaiah 2:1-22"),
+    parallel("2 Nephi 13:1-26", "Isaiah 3:1-26"),
+    parallel("2 Nephi 14:1-6", "Isaiah 4:1-6"),
+    parallel("2 Nephi 15:1-30", "Isaiah 5:1-30"),
+    parallel("2 Nephi 16:1-13", "Isaiah 6:1-13"),
+    parallel("2 Nephi 17:1-25", "Isaiah 7:1-25"),
+    parallel("2 Nephi 18:1-22", "Isaiah 8:1-22"),
+    parallel("2 Nephi 19:1-21", "Isaiah 9:1-21"),
+    parallel("2 Nephi 20:1-34", "Isaiah 10:1-34"),
+    parallel("2 Nephi 21:1-16", "Isaiah 11:1-16"),
+    parallel("2 Nephi 22:1-6", "Isaiah 12:1-6"),
+    parallel("2 Nephi 23:1-22", "Isaiah 13:1-22"),
+    parallel("2 Nephi 24:1-32", "Isaiah 14:1-32"),
+    parallel("Mosiah 12:21-24", "Isaiah 52:7-10"),
+    parallel("Mosiah 14:1-12", "Isaiah 53:1-12"),
+    parallel("Mosiah 15:29-31", "Isaiah 52:8-10"),
+    parallel("3 Nephi 12:3-48", "Matthew 5:3-48"),
+    parallel("3 Nephi 13:1-34", "Matthew 6:1-34"),
+    parallel("3 Nephi 14:1-27", "Matthew 7:1-27"),
+    parallel("3 Nephi 16:18-20", "Isaiah 52:8-10"),
+    parallel("3 Nephi 20:36-38", "Isaiah 52:1-3"),
+    parallel("3 Nephi 20:41-45", "Isaiah 52:11-15"),
+    parallel("3 Nephi 22:1-17", "Isaiah 54:1-17"),
+    parallel("3 Nephi 24:1-18", "Malachi 3:1-18"),
+    parallel("3 Nephi 25:1-6", "Malachi 4:1-6"),
+];
+
+/// A chapter's verses lined up with those of a parallel chapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    pub book: String,
+    pub chapter: i32,
+    pub other_book: String,
+    pub other_chapter: i32,
+    /// (verse, other verse) pairs, in order
+    pub pairs: Vec<(i32, i32)>,
+}
+
+impl Alignment {
+    /// The verse of the other chapter paralleling one of this chapter
+    pub fn counterpart(&self, verse: i32) -> Option<i32> {
+        self.pairs.iter().find(|(v, _)| *v == verse).map(|(_, other)| *other)
+    }
+
+    /// The verse of this chapter paralleling one of the other chapter
+    pub fn counterpart_of_other(&self, other: i32) -> Option<i32> {
+        self.pairs.iter().find(|(_, o)| *o == other).map(|(v, _)| *v)
+    }
+
+    /// "Isaiah 2", the chapter to read beside this one
+    pub fn other_title(&self) -> String {
+        format!("{} {}", self.other_book, self.other_chapter)
+    }
+}
+
+/// The chapters paralleling a chapter of either the Book of Mormon or the
+/// Bible, in table order, each with its verses lined up with this chapter's
+pub fn alignments(book: &str, chapter: i32) -> Vec<Alignment> {
+    let mut alignments: Vec<Alignment> = Vec::new();
+    for parallel in PARALLELS {
+        let (Some(passage), Some(source)) = (parse(parallel.passage), parse(parallel.source)) else {
+            continue;
+        };
+        let (this, other) = if (passage.0, passage.1) == (book, chapter) {
+            (passage, source)
+        } else if (source.0, source.1) == (book, chapter) {
+            (source, passage)
+        } else {
+            continue;
+        };
+        let pairs = (this.2..=this.3).zip(other.2..=other.3);
+        match alignments.iter_mut().find(|a| a.other_book == other.0 && a.other_chapter == other.1) {
+            Some(alignment) => alignment.pairs.extend(pairs),
+            None => alignments.push(Alignment {
+                book: book.to_string(),
+                chapter,
+                other_book: other.0.to_string(),
+                other_chapter: other.1,
+                pairs: pairs.collect(),
+            }),
+        }
+    }
+    for alignment in &mut alignments {
+        alignment.pairs.sort();
+    }
+    alignments
+}
+
+/// "2 Nephi 8:1-23" as (book, chapter, first verse, last verse)
+fn parse(passage: &str) -> Option<(&str, i32, i32, i32)> {
+    let (book, location) = passage.rsplit_once(' ')?;
+    let (chapter, verses) = location.split_once(':')?;
+    let (start, end) = verses.split_once('-').unwrap_or((verses, verses));
+    Some((book, chapter.parse().ok()?, start.parse().ok()?, end.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallels_are_verse_for_verse() {
+        for parallel in PARALLELS {
+            let passage = parse(parallel.passage).unwrap();
+            let source = parse(parallel.source).unwrap();
+            assert_eq!(passage.3 - passage.2, source.3 - source.2, "{:?}", parallel);
+        }
+    }
+
+    #[test]
+    fn test_alignments_both_ways() {
+        let nephi = alignments("2 Nephi", 8);
+        let chapters: Vec<String> = nephi.iter().map(Alignment::other_title).collect();
+        assert_eq!(chapters, ["Isaiah 51", "Isaiah 52"]);
+        assert_eq!(nephi[0].counterpart(23), Some(23));
+        assert_eq!(nephi[1].counterpart(24), Some(1));
+        assert_eq!(nephi[1].counterpart(3), None);
+
+        // Isaiah 52 is quoted in four places
+        let isaiah = alignments("Isaiah", 52);
+        let chapters: Vec<String> = isaiah.iter().map(Alignment::other_title).collect();
+        assert_eq!(chapters, ["2 Nephi 8", "Mosiah 12", "Mosiah 15", "3 Nephi 16", "3 Nephi 20"]);
+        let third_nephi = &isaiah[4];
+        assert_eq!(third_nephi.counterpart(2), Some(37));
+        assert_eq!(third_nephi.counterpart(12), Some(42));
+        assert_eq!(third_nephi.counterpart_of_other(45), Some(15));
+
+        let sermon = &alignments("Matthew", 5)[0];
+        assert_eq!((sermon.counterpart(1), sermon.counterpart(3)), (None, Some(3)));
+        assert!(alignments("Alma", 32).is_empty());
+    }
+}
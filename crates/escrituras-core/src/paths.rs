@@ -0,0 +1,192 @@
+//! Where files are kept
+//!
+//! Settings (`config.toml`, `theme.toml`, `keybindings.toml`) are in the
+//! config directory, `~/.config/escrituras`. Scripture data, embeddings, and
+//! what you make while studying (saved verses, the journal, progress,
+//! conversations) are in the data directory, `~/.local/share/escrituras`. The
+//! last session is in the state directory, `~/.local/state/escrituras`, and
+//! the embedding model in the cache directory, `~/.cache/escrituras`. Each
+//! follows its `XDG_*_HOME` variable; macOS and Windows use their own
+//! folders, where config, data, and state are all one.
+//!
+//! Older versions kept everything in the config directory and could only find
+//! scripture data in the current directory or there. `migrate` moves those
+//! files to where they go now.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{EscriturasError, Result};
+use crate::setup::SCRIPTURE_JSON;
+
+/// Kept in the config directory by older versions; now in the data directory
+const DATA_ENTRIES: &[&str] = &[
+    "lds-scriptures-2020.12.08",
+    "data",
+    "saved_scriptures.json",
+    "journal",
+    "memorize_history.json",
+    "come_follow_me_progress.json",
+    "conversations.json",
+];
+
+/// Kept in the config directory by older versions; now in the state directory
+const STATE_ENTRIES: &[&str] = &["session.json"];
+
+/// Where fastembed put the model when run without `FASTEMBED_CACHE_DIR`
+const OLD_MODEL_CACHE: &str = ".fastembed_cache";
+
+fn platform_dir(dir: Option<PathBuf>, kind: &str) -> Result<PathBuf> {
+    dir.map(|d| d.join("escrituras")).ok_or_else(|| EscriturasError::Other(format!("Could not determine {} directory", kind)))
+}
+
+/// `~/.config/escrituras`
+pub fn config_dir() -> Result<PathBuf> {
+    platform_dir(dirs::config_dir(), "config")
+}
+
+/// `~/.local/share/escrituras`
+pub fn data_dir() -> Result<PathBuf> {
+    platform_dir(dirs::data_dir(), "data")
+}
+
+/// `~/.local/state/escrituras`, or the data directory where there's no such thing
+pub fn state_dir() -> Result<PathBuf> {
+    platform_dir(dirs::state_dir().or_else(dirs::data_dir), "state")
+}
+
+/// `~/.cache/escrituras`
+pub fn cache_dir() -> Result<PathBuf> {
+    platform_dir(dirs::cache_dir(), "cache")
+}
+
+/// Sockets and other files that last as long as a login: `$XDG_RUNTIME_DIR/escrituras`,
+/// or the state directory
+pub fn runtime_dir() -> Result<PathBuf> {
+    match dirs::runtime_dir() {
+        Some(dir) => Ok(dir.join("escrituras")),
+        None => state_dir(),
+    }
+}
+
+/// Where the embedding model is downloaded: `FASTEMBED_CACHE_DIR` when it's
+/// set, else `models/` in the cache directory
+pub fn model_cache_dir() -> PathBuf {
+    match std::env::var_os("FASTEMBED_CACHE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => cache_dir().map(|d| d.join("models")).unwrap_or_else(|_| PathBuf::from(OLD_MODEL_CACHE)),
+    }
+}
+
+/// The directories files go in, as one value so migration can be tested
+struct Layout {
+    config: PathBuf,
+    data: PathBuf,
+    state: PathBuf,
+    models: PathBuf,
+}
+
+/// Move files from where older versions kept them, returning each move as
+/// (from, to). Something already at the new location is left alone, so this
+/// does nothing once it has run.
+pub fn migrate() -> Result<Vec<(PathBuf, PathBuf)>> {
+    let layout = Layout { config: config_dir()?, data: data_dir()?, state: state_dir()?, models: model_cache_dir() };
+    migrate_from(&layout, &std::env::current_dir()?)
+}
+
+fn migrate_from(layout: &Layout, cwd: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut moves = Vec::new();
+    let entries = DATA_ENTRIES
+        .iter()
+        .map(|name| (name, &layout.data))
+        .chain(STATE_ENTRIES.iter().map(|name| (name, &layout.state)));
+    for (name, dir) in entries {
+        moves.push((layout.config.join(name), dir.join(name)));
+    }
+    moves.push((cwd.join(OLD_MODEL_CACHE), layout.models.clone()));
+
+    let mut done = Vec::new();
+    for (from, to) in moves {
+        if from != to && from.exists() && !to.exists() {
+            move_path(&from, &to).map_err(|e| EscriturasError::io(format!("Could not move {} to {}", from.display(), to.display()), e))?;
+            done.push((from, to));
+        }
+    }
+
+    // Scripture data next to the binary (as a release or checkout unpacks
+    // it) is copied, not moved, so the folder it came from still works
+    if cwd.join(SCRIPTURE_JSON).exists() && !layout.data.join(SCRIPTURE_JSON).exists() {
+        for name in ["lds-scriptures-2020.12.08", "data"] {
+            let (from, to) = (cwd.join(name), layout.data.join(name));
+            if from.is_dir() && !to.exists() {
+                copy_dir(&from, &to).map_err(|e| EscriturasError::io(format!("Could not copy {} to {}", from.display(), to.display()), e))?;
+                done.push((from, to));
+            }
+        }
+    }
+    Ok(done)
+}
+
+/// Rename, or copy and delete when that crosses filesystems
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        copy_dir(from, to)?;
+        fs::remove_dir_all(from)?;
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_migrate_moves_old_files_once() {
+        let root = tempdir().unwrap();
+        let dir = |name: &str| root.path().join(name);
+        let layout = Layout { config: dir("config"), data: dir("data"), state: dir("state"), models: dir("cache/models") };
+        let cwd = dir("checkout");
+        fs::create_dir_all(layout.config.join("journal")).unwrap();
+        fs::write(layout.config.join("journal/2026-01-05.md"), "notes").unwrap();
+        fs::write(layout.config.join("session.json"), "{}").unwrap();
+        fs::write(layout.config.join("config.toml"), "{}").unwrap();
+        fs::create_dir_all(cwd.join(SCRIPTURE_JSON).parent().unwrap()).unwrap();
+        fs::write(cwd.join(SCRIPTURE_JSON), "[]").unwrap();
+        fs::create_dir_all(cwd.join(OLD_MODEL_CACHE)).unwrap();
+
+        let moved = migrate_from(&layout, &cwd).unwrap();
+        assert_eq!(moved.len(), 4);
+        assert_eq!(fs::read_to_string(layout.data.join("journal/2026-01-05.md")).unwrap(), "notes");
+        assert!(layout.state.join("session.json").exists() && !layout.config.join("session.json").exists());
+        assert!(layout.config.join("config.toml").exists());
+        assert!(layout.models.is_dir());
+        // Scripture data is copied, leaving the checkout as it was
+        assert!(layout.data.join(SCRIPTURE_JSON).exists() && cwd.join(SCRIPTURE_JSON).exists());
+
+        assert!(migrate_from(&layout, &cwd).unwrap().is_empty());
+    }
+}
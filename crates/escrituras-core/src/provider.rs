@@ -27,6 +27,24 @@ impl Provider {
         vec![Provider::Ollama, Provider::Claude, Provider::OpenAI]
     }
 
+    /// The environment variable holding this provider's API key
+    pub fn key_variable(&self) -> Option<&'static str> {
+        match self {
+            Provider::Ollama => None,
+            Provider::Claude => Some("ANTHROPIC_API_KEY"),
+            Provider::OpenAI => Some("OPENAI_API_KEY"),
+        }
+    }
+
+    /// The model used with this provider until another is picked
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            Provider::Ollama => "gemma3:latest",
+            Provider::Claude => "claude-sonnet-4-20250514",
+            Provider::OpenAI => "gpt-4o",
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Provider::Ollama => "Ollama (Local)",
@@ -0,0 +1,149 @@
+//! Quiz questions drawn from the scriptures
+//!
+//! A quiz asks either which verse some text is, or for a word blanked out of
+//! a verse. Answers are scored from 0.0 to 1.0 like typed memorization
+//! attempts, so they can be recorded in the same history.
+
+use crate::export::STOP_WORDS;
+use crate::scripture::{Scripture, ScriptureRange};
+
+/// Shown in place of the blanked word
+pub const BLANK: &str = "_____";
+
+/// What a quiz question asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuizKind {
+    /// The reference of a verse shown in full
+    Reference,
+    /// A word missing from a verse
+    Blank,
+}
+
+/// A verse with one word blanked out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlankedVerse {
+    pub shown: String,
+    pub answer: String,
+}
+
+/// A small xorshift generator: quizzes only need variety, not quality
+pub struct QuizRng(u64);
+
+impl QuizRng {
+    pub fn new(seed: u64) -> Self {
+        // Xorshift never leaves zero
+        QuizRng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number below `n` (which must not be zero)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Up to `n` different verses in random order
+pub fn pick_verses<'a>(verses: &[&'a Scripture], n: usize, rng: &mut QuizRng) -> Vec<&'a Scripture> {
+    let mut pool = verses.to_vec();
+    let n = n.min(pool.len());
+    // The first n steps of a Fisher-Yates shuffle
+    for i in 0..n {
+        let j = i + rng.below(pool.len() - i);
+        pool.swap(i, j);
+    }
+    pool.truncate(n);
+    pool
+}
+
+/// The verse with one of its longer uncommon words blanked out, or None when
+/// it has no word worth asking for
+pub fn blank_word(text: &str, rng: &mut QuizRng) -> Option<BlankedVerse> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let bare = |word: &str| word.trim_matches(|c: char| !c.is_alphanumeric()).to_string();
+    let candidates: Vec<usize> = (0..words.len())
+        .filter(|&i| {
+            let word = bare(words[i]);
+            word.chars().count() >= 5 && !STOP_WORDS.contains(&word.to_lowercase().as_str())
+        })
+        .collect();
+    let i = *candidates.get(rng.below(candidates.len().max(1)))?;
+
+    let answer = bare(words[i]);
+    let mut shown: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+    shown[i] = words[i].replacen(&answer, BLANK, 1);
+    Some(BlankedVerse { shown: shown.join(" "), answer })
+}
+
+/// Credit for a blank: the word, ignoring case and punctuation
+pub fn blank_score(answer: &str, guess: &str) -> f32 {
+    let normalize = |word: &str| word.trim().trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    if normalize(answer) == normalize(guess) { 1.0 } else { 0.0 }
+}
+
+/// Credit for naming a verse: full for the verse (or a few verses around it),
+/// half for its chapter, a quarter for its book
+pub fn reference_score(verse: &Scripture, guess: &ScriptureRange) -> f32 {
+    if guess.book_title != verse.book_title {
+        0.0
+    } else if guess.chapter_number != verse.chapter_number {
+        0.25
+    } else if (guess.start_verse..=guess.end_verse).contains(&verse.verse_number) && guess.end_verse - guess.start_verse < 3 {
+        1.0
+    } else {
+        0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(chapter: i32, number: i32) -> Scripture {
+        Scripture::test("Alma", chapter, number).text("Faith is not to have a perfect knowledge of things;")
+    }
+
+    fn range(book: &str, chapter: i32, start: i32, end: i32) -> ScriptureRange {
+        ScriptureRange {
+            book_title: book.to_string(),
+            book_short_title: book.to_string(),
+            chapter_number: chapter,
+            start_verse: start,
+            end_verse: end,
+        }
+    }
+
+    #[test]
+    fn test_scores() {
+        let asked = verse(32, 21);
+        assert_eq!(reference_score(&asked, &range("Alma", 32, 21, 21)), 1.0);
+        assert_eq!(reference_score(&asked, &range("Alma", 32, 20, 22)), 1.0);
+        // A whole chapter names the chapter, not the verse
+        assert_eq!(reference_score(&asked, &range("Alma", 32, 1, 43)), 0.5);
+        assert_eq!(reference_score(&asked, &range("Alma", 33, 21, 21)), 0.25);
+        assert_eq!(reference_score(&asked, &range("Ether", 12, 6, 6)), 0.0);
+
+        let blanked = blank_word(&asked.scripture_text, &mut QuizRng::new(7)).unwrap();
+        assert!(["Faith", "perfect", "knowledge", "things"].contains(&blanked.answer.as_str()));
+        assert!(blanked.shown.contains(BLANK) && !blanked.shown.contains(&blanked.answer));
+        assert_eq!(blank_score(&blanked.answer, &format!(" {}. ", blanked.answer.to_uppercase())), 1.0);
+        assert!(blank_word("And it came to pass", &mut QuizRng::new(7)).is_none());
+    }
+
+    #[test]
+    fn test_pick_verses_without_repeats() {
+        let verses: Vec<Scripture> = (1..=20).map(|n| verse(32, n)).collect();
+        let refs: Vec<&Scripture> = verses.iter().collect();
+        let mut picked: Vec<i32> = pick_verses(&refs, 10, &mut QuizRng::new(42)).iter().map(|v| v.verse_number).collect();
+        assert_eq!(picked.len(), 10);
+        picked.sort();
+        picked.dedup();
+        assert_eq!(picked.len(), 10);
+        assert_eq!(pick_verses(&refs, 50, &mut QuizRng::new(1)).len(), 20);
+    }
+}
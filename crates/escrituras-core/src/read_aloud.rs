@@ -0,0 +1,307 @@
+//! Reading scripture aloud
+//!
+//! A passage is read a verse at a time by a `Voice`, a text-to-speech engine.
+//! The built-in voice runs a speech program: the one in the `speech_command`
+//! setting, for a local engine of your choosing, or else the platform's own
+//! (`say` on macOS, `espeak-ng`, `espeak`, or `spd-say` on Linux, and
+//! System.Speech through PowerShell on Windows). Other engines can implement
+//! `Voice` themselves.
+//!
+//! Speaking a verse at a time is what lets `ReadAloud` pause and skip without
+//! the engine's help: pausing stops the verse being spoken, and playing again
+//! starts that verse over.
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+
+use crate::config::Config;
+use crate::error::{EscriturasError, Result};
+use crate::scripture::Scripture;
+
+/// A text-to-speech engine
+pub trait Voice: Send {
+    /// Start speaking `text`, returning without waiting for it to be spoken
+    fn speak(&self, text: &str) -> Result<Box<dyn Utterance>>;
+}
+
+/// Text being spoken
+pub trait Utterance: Send {
+    /// Whether it's been spoken to the end (or couldn't be)
+    fn is_done(&mut self) -> bool;
+    /// Stop speaking
+    fn stop(&mut self);
+}
+
+impl Utterance for Child {
+    fn is_done(&mut self) -> bool {
+        !matches!(self.try_wait(), Ok(None))
+    }
+
+    fn stop(&mut self) {
+        if self.kill().is_ok() {
+            let _ = self.wait();
+        }
+    }
+}
+
+/// A speech program, given the text after its arguments or on stdin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandVoice {
+    program: String,
+    args: Vec<String>,
+    stdin: bool,
+}
+
+impl CommandVoice {
+    /// A command line like "espeak-ng -s 140 -v en-us", split at whitespace;
+    /// None when it's blank
+    pub fn from_command_line(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace().map(str::to_string);
+        let program = words.next()?;
+        Some(Self { program, args: words.collect(), stdin: false })
+    }
+
+    /// The platform's speech program, if it has one installed
+    pub fn system() -> Option<Self> {
+        if cfg!(windows) {
+            let script = "Add-Type -AssemblyName System.Speech; \
+                          (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak([Console]::In.ReadToEnd())";
+            return Some(Self {
+                program: "powershell".to_string(),
+                args: vec!["-NoProfile".to_string(), "-Command".to_string(), script.to_string()],
+                stdin: true,
+            });
+        }
+        ["say", "espeak-ng", "espeak", "spd-say"]
+            .into_iter()
+            .find(|program| on_path(program))
+            .map(|program| {
+                // spd-say hands the text to the speech daemon and returns unless told to wait
+                let args = if program == "spd-say" { vec!["--wait".to_string()] } else { Vec::new() };
+                Self { program: program.to_string(), args, stdin: false }
+            })
+    }
+
+    /// The voice `speech_command` names, or else the platform's
+    pub fn from_config(config: &Config) -> Result<Self> {
+        match config.speech_command.as_deref().and_then(Self::from_command_line) {
+            Some(voice) => Ok(voice),
+            None => Self::system().ok_or_else(|| {
+                EscriturasError::DataNotFound(
+                    "No text-to-speech program found: install espeak-ng, or set speech_command".to_string(),
+                )
+            }),
+        }
+    }
+
+    /// The program run to speak
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+}
+
+impl Voice for CommandVoice {
+    fn speak(&self, text: &str) -> Result<Box<dyn Utterance>> {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args).stdout(Stdio::null()).stderr(Stdio::null());
+        if self.stdin {
+            command.stdin(Stdio::piped());
+        } else {
+            command.arg(text).stdin(Stdio::null());
+        }
+        let mut child =
+            command.spawn().map_err(|e| EscriturasError::io(format!("Couldn't run {}", self.program), e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        Ok(Box::new(child))
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// A passage being read aloud, a verse at a time. Speaking stops when it's dropped.
+pub struct ReadAloud {
+    voice: Box<dyn Voice>,
+    verses: Vec<Scripture>,
+    position: usize,
+    speaking: Option<Box<dyn Utterance>>,
+    paused: bool,
+}
+
+impl ReadAloud {
+    /// Ready to read `verses` from the one at `start`, paused until `play`
+    pub fn new(voice: Box<dyn Voice>, verses: Vec<Scripture>, start: usize) -> Self {
+        Self { voice, verses, position: start, speaking: None, paused: true }
+    }
+
+    pub fn verses(&self) -> &[Scripture] {
+        &self.verses
+    }
+
+    /// Index of the verse being read, or that reading is paused at
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The verse being read, or None once the passage has been read
+    pub fn current(&self) -> Option<&Scripture> {
+        self.verses.get(self.position)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Whether every verse has been read
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.verses.len()
+    }
+
+    /// Read from the start of the current verse
+    pub fn play(&mut self) -> Result<()> {
+        self.paused = false;
+        self.speak_current()
+    }
+
+    /// Stop reading, to go on from this verse
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.stop_speaking();
+    }
+
+    /// Pause when reading, play when paused
+    pub fn toggle(&mut self) -> Result<()> {
+        if self.paused {
+            self.play()
+        } else {
+            self.pause();
+            Ok(())
+        }
+    }
+
+    /// Move `delta` verses ahead (or back), reading from there unless paused
+    pub fn skip(&mut self, delta: isize) -> Result<()> {
+        let last = self.verses.len().saturating_sub(1);
+        self.position = self.position.saturating_add_signed(delta).min(last);
+        if self.paused {
+            self.stop_speaking();
+            Ok(())
+        } else {
+            self.speak_current()
+        }
+    }
+
+    /// Go on to the next verse once the current one has been spoken. Call it
+    /// every so often while reading; returns whether the verse changed.
+    pub fn poll(&mut self) -> Result<bool> {
+        let done = self.speaking.as_mut().is_some_and(|utterance| utterance.is_done());
+        if self.paused || !done {
+            return Ok(false);
+        }
+        self.speaking = None;
+        self.position += 1;
+        self.speak_current()?;
+        Ok(true)
+    }
+
+    fn speak_current(&mut self) -> Result<()> {
+        self.stop_speaking();
+        if let Some(verse) = self.verses.get(self.position) {
+            self.speaking = Some(self.voice.speak(&verse.scripture_text)?);
+        }
+        Ok(())
+    }
+
+    fn stop_speaking(&mut self) {
+        if let Some(mut utterance) = self.speaking.take() {
+            utterance.stop();
+        }
+    }
+}
+
+impl Drop for ReadAloud {
+    fn drop(&mut self) {
+        self.stop_speaking();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Records what it speaks; each utterance is done when `done` is set
+    #[derive(Clone, Default)]
+    struct FakeVoice {
+        spoken: Arc<Mutex<Vec<String>>>,
+        done: Arc<AtomicBool>,
+    }
+
+    struct FakeUtterance(Arc<AtomicBool>);
+
+    impl Utterance for FakeUtterance {
+        fn is_done(&mut self) -> bool {
+            self.0.swap(false, Ordering::SeqCst)
+        }
+        fn stop(&mut self) {}
+    }
+
+    impl Voice for FakeVoice {
+        fn speak(&self, text: &str) -> Result<Box<dyn Utterance>> {
+            self.spoken.lock().unwrap().push(text.to_string());
+            Ok(Box::new(FakeUtterance(self.done.clone())))
+        }
+    }
+
+    fn verses() -> Vec<Scripture> {
+        (1..=3).map(|number| Scripture::test("Alma", 32, number).text(&format!("Verse {}", number))).collect()
+    }
+
+    #[test]
+    fn test_reads_verse_by_verse_with_pause_and_skip() {
+        let voice = FakeVoice::default();
+        let spoken = || voice.spoken.lock().unwrap().clone();
+        let finish_verse = || voice.done.store(true, Ordering::SeqCst);
+        let mut reading = ReadAloud::new(Box::new(voice.clone()), verses(), 0);
+        assert!(spoken().is_empty());
+
+        reading.play().unwrap();
+        assert!(!reading.poll().unwrap());
+        finish_verse();
+        assert!(reading.poll().unwrap());
+        assert_eq!(reading.current().unwrap().verse_title, "Alma 32:2");
+
+        // Paused, the verse being spoken is dropped and starts over on play
+        reading.toggle().unwrap();
+        finish_verse();
+        assert!(!reading.poll().unwrap());
+        reading.toggle().unwrap();
+        assert_eq!(spoken(), ["Verse 1", "Verse 2", "Verse 2"]);
+
+        reading.skip(5).unwrap();
+        reading.skip(-1).unwrap();
+        assert_eq!(reading.position(), 1);
+        reading.skip(1).unwrap();
+        finish_verse();
+        assert!(reading.poll().unwrap());
+        assert!(reading.is_finished());
+        assert_eq!(spoken().last().unwrap(), "Verse 3");
+    }
+
+    #[test]
+    fn test_command_voice_from_a_command_line() {
+        let voice = CommandVoice::from_command_line("  espeak-ng -s 140 ").unwrap();
+        assert_eq!(voice.program(), "espeak-ng");
+        assert_eq!(voice.args, ["-s", "140"]);
+        assert!(CommandVoice::from_command_line(" ").is_none());
+
+        let mut config = Config::new();
+        config.speech_command = Some("say -v Samantha".to_string());
+        assert_eq!(CommandVoice::from_config(&config).unwrap().program(), "say");
+    }
+}
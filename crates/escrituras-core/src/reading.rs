@@ -0,0 +1,134 @@
+//! Reflowed reading layout
+//!
+//! Verse-per-line text is good for study but choppy to read straight through.
+//! This joins a chapter's verses into paragraphs with small superscript verse
+//! numbers and wraps them to a width, for any UI with a reading view.
+
+use crate::scripture::Scripture;
+use unicode_width::UnicodeWidthStr;
+
+/// Verses that usually open a new thought once a paragraph has some length
+const PARAGRAPH_OPENERS: &[&str] = &["And it came to pass", "And now", "Now ", "Behold,", "Verily, verily"];
+
+/// Shortest paragraph before an opener starts a new one
+const MIN_PARAGRAPH_VERSES: usize = 3;
+
+/// A verse number as superscript digits ("¹²")
+pub fn superscript(number: i32) -> String {
+    number
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            other => other,
+        })
+        .collect()
+}
+
+pub fn is_superscript(c: char) -> bool {
+    matches!(c, '⁰' | '¹' | '²' | '³' | '⁴' | '⁵' | '⁶' | '⁷' | '⁸' | '⁹')
+}
+
+/// Group a chapter's verses into paragraphs. A pilcrow (¶) at the start of a
+/// verse always begins one; otherwise a new paragraph starts at a verse with a
+/// narrative opener ("And it came to pass") once the current one is long enough.
+pub fn paragraphs(verses: &[Scripture]) -> Vec<Vec<&Scripture>> {
+    let mut paragraphs: Vec<Vec<&Scripture>> = Vec::new();
+    for verse in verses {
+        let text = verse.scripture_text.trim_start();
+        let breaks = match paragraphs.last() {
+            None => true,
+            Some(current) => {
+                text.starts_with('¶')
+                    || (current.len() >= MIN_PARAGRAPH_VERSES && PARAGRAPH_OPENERS.iter().any(|o| text.starts_with(o)))
+            }
+        };
+        if breaks {
+            paragraphs.push(vec![verse]);
+        } else if let Some(current) = paragraphs.last_mut() {
+            current.push(verse);
+        }
+    }
+    paragraphs
+}
+
+/// The chapter as wrapped lines no wider than `width` columns, with an empty
+/// line between paragraphs
+pub fn reflow(verses: &[Scripture], width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for (i, paragraph) in paragraphs(verses).into_iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        let mut line = String::new();
+        let mut line_len = 0;
+        for verse in paragraph {
+            let text = verse.scripture_text.trim().trim_start_matches('¶').trim_start();
+            let mut words = text.split_whitespace();
+            // Keep the number with the verse's first word
+            let first = format!("{}{}", superscript(verse.verse_number), words.next().unwrap_or(""));
+            for word in std::iter::once(first.as_str()).chain(words) {
+                let word_len = word.width();
+                if line_len > 0 && line_len + 1 + word_len > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_len = 0;
+                }
+                if line_len > 0 {
+                    line.push(' ');
+                    line_len += 1;
+                }
+                line.push_str(word);
+                line_len += word_len;
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(number: i32, text: &str) -> Scripture {
+        Scripture::test("1 Nephi", 3, number).short("1 Ne.").text(text)
+    }
+
+    #[test]
+    fn test_paragraphs_break_at_openers_after_minimum() {
+        let verses = vec![
+            verse(1, "And it came to pass that I returned."),
+            verse(2, "And it came to pass that he spake."),
+            verse(3, "Saying: Behold I have dreamed."),
+            verse(4, "And now, behold thy brothers murmur."),
+            verse(5, "¶ And I said unto him."),
+        ];
+        let numbers: Vec<Vec<i32>> = paragraphs(&verses)
+            .iter()
+            .map(|p| p.iter().map(|v| v.verse_number).collect())
+            .collect();
+        assert_eq!(numbers, [vec![1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn test_reflow_wraps_with_superscript_numbers() {
+        let verses = vec![verse(9, "I will go and do"), verse(10, "the things which the Lord hath commanded")];
+        assert_eq!(
+            reflow(&verses, 22),
+            ["⁹I will go and do", "¹⁰the things which the", "Lord hath commanded"]
+        );
+        assert_eq!(superscript(120), "¹²⁰");
+    }
+}
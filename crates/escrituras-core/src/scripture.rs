@@ -1,13 +1,19 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use anyhow::Result;
+use std::sync::OnceLock;
+use crate::error::Result;
+use crate::timings;
 use rust_stemmers::{Algorithm, Stemmer};
 
+use crate::title::{Interner, Title};
+use crate::word_index::{self, WordIndex};
+
+/// A verse, with the fields of the scripture JSON
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Scripture {
-    pub volume_title: String,
-    pub book_title: String,
-    pub book_short_title: String,
+    pub volume_title: Title,
+    pub book_title: Title,
+    pub book_short_title: Title,
     pub chapter_number: i32,
     pub verse_number: i32,
     pub verse_title: String,
@@ -16,7 +22,7 @@ pub struct Scripture {
 }
 
 /// Represents a scripture reference that may span multiple verses
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScriptureRange {
     pub book_title: String,
     #[allow(dead_code)]
@@ -27,6 +33,17 @@ pub struct ScriptureRange {
 }
 
 impl ScriptureRange {
+    /// The range holding just one verse
+    pub fn of_verse(verse: &Scripture) -> Self {
+        Self {
+            book_title: verse.book_title.to_string(),
+            book_short_title: verse.book_short_title.to_string(),
+            chapter_number: verse.chapter_number,
+            start_verse: verse.verse_number,
+            end_verse: verse.verse_number,
+        }
+    }
+
     pub fn display_title(&self) -> String {
         if self.start_verse == self.end_verse {
             format!("{} {}:{}", self.book_title, self.chapter_number, self.start_verse)
@@ -40,11 +57,14 @@ impl ScriptureRange {
     }
 }
 
+/// The scriptures in canonical order, indexed by volume, book, and chapter
 pub struct ScriptureDb {
     scriptures: Vec<Scripture>,
-    volumes: Vec<String>,
-    books_by_volume: HashMap<String, Vec<String>>,
-    chapters_by_book: HashMap<String, Vec<i32>>,
+    volumes: Vec<Title>,
+    books_by_volume: HashMap<Title, Vec<Title>>,
+    chapters_by_book: HashMap<Title, Vec<i32>>,
+    // Where each stemmed word occurs, built on first search so startup stays fast
+    words: OnceLock<WordIndex>,
 }
 
 impl ScriptureDb {
@@ -54,25 +74,60 @@ impl ScriptureDb {
             volumes: Vec::new(),
             books_by_volume: HashMap::new(),
             chapters_by_book: HashMap::new(),
+            words: OnceLock::new(),
         }
     }
     
+    /// Build a database from already-parsed verses in canonical order
+    pub fn from_verses(scriptures: Vec<Scripture>) -> Self {
+        let mut db = Self::new();
+        db.scriptures = scriptures;
+        db.build_indexes();
+        db
+    }
+
+    /// Build a database from the scripture JSON itself, where there's no file
+    /// to read it from (a browser)
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self::from_verses(timings::time("parse scripture JSON", || serde_json::from_str(json))?))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn load_from_json(&mut self, path: &str) -> Result<()> {
-        let content = tokio::fs::read_to_string(path).await?;
-        self.scriptures = serde_json::from_str(&content)?;
+        let content = {
+            let _stage = timings::stage("read scripture JSON");
+            tokio::fs::read_to_string(path).await?
+        };
+        self.scriptures = timings::time("parse scripture JSON", || serde_json::from_str(&content))?;
         self.build_indexes();
         Ok(())
     }
+
+    /// Add verses after the ones loaded, such as hymn stanzas as a volume of
+    /// their own
+    pub fn append(&mut self, verses: impl IntoIterator<Item = Scripture>) {
+        self.scriptures.extend(verses);
+        self.build_indexes();
+    }
     
     fn build_indexes(&mut self) {
+        let _stage = timings::stage("build indexes");
+        // One copy of each title, shared by its verses and the indexes
+        let mut interner = Interner::default();
+        for scripture in &mut self.scriptures {
+            interner.intern(&mut scripture.volume_title);
+            interner.intern(&mut scripture.book_title);
+            interner.intern(&mut scripture.book_short_title);
+        }
+
         let mut volumes_order = Vec::new();
-        let mut books_by_vol: HashMap<String, Vec<String>> = HashMap::new();
-        let mut chapters_by_bk: HashMap<String, Vec<i32>> = HashMap::new();
+        let mut books_by_vol: HashMap<Title, Vec<Title>> = HashMap::new();
+        let mut chapters_by_bk: HashMap<Title, Vec<i32>> = HashMap::new();
         
         // Track seen items to maintain order while avoiding duplicates
         let mut seen_volumes = HashSet::new();
-        let mut seen_books: HashMap<String, HashSet<String>> = HashMap::new();
-        let mut seen_chapters: HashMap<String, HashSet<i32>> = HashMap::new();
+        let mut seen_books: HashMap<Title, HashSet<Title>> = HashMap::new();
+        let mut seen_chapters: HashMap<Title, HashSet<i32>> = HashMap::new();
         
         // Process in original order to preserve canonical sequence
         for scripture in &self.scriptures {
@@ -126,13 +181,19 @@ impl ScriptureDb {
             chapters.sort();
         }
         self.chapters_by_book = chapters_by_bk;
+        self.words = OnceLock::new();
     }
     
-    pub fn get_volumes(&self) -> &[String] {
+    pub fn get_volumes(&self) -> &[Title] {
         &self.volumes
     }
+
+    /// A volume's title from its name in any case ("book of mormon")
+    pub fn find_volume(&self, name: &str) -> Option<&str> {
+        self.volumes.iter().find(|v| v.eq_ignore_ascii_case(name)).map(Title::as_str)
+    }
     
-    pub fn get_books_for_volume(&self, volume: &str) -> Vec<String> {
+    pub fn get_books_for_volume(&self, volume: &str) -> Vec<Title> {
         self.books_by_volume
             .get(volume)
             .cloned()
@@ -146,6 +207,11 @@ impl ScriptureDb {
             .unwrap_or_default()
     }
     
+    /// Every verse, in canonical order
+    pub fn all_verses(&self) -> &[Scripture] {
+        &self.scriptures
+    }
+
     pub fn get_verses_for_chapter(&self, book: &str, chapter: i32) -> Vec<&Scripture> {
         self.scriptures
             .iter()
@@ -153,6 +219,48 @@ impl ScriptureDb {
             .collect()
     }
 
+    /// The verses of a parsed reference, in order
+    pub fn get_verses_in_range(&self, range: &ScriptureRange) -> Vec<&Scripture> {
+        self.get_verses_for_chapter(&range.book_title, range.chapter_number)
+            .into_iter()
+            .filter(|s| range.contains_verse(s.verse_number))
+            .collect()
+    }
+
+    /// The chapter after (or before) this one, continuing into the next book and volume
+    pub fn adjacent_chapter(&self, book: &str, chapter: i32, forward: bool) -> Option<(String, i32)> {
+        let chapters = self.get_chapters_for_book(book);
+        let i = chapters.iter().position(|&c| c == chapter)?;
+        let same_book = if forward { chapters.get(i + 1) } else { i.checked_sub(1).and_then(|i| chapters.get(i)) };
+        if let Some(&c) = same_book {
+            return Some((book.to_string(), c));
+        }
+
+        let books: Vec<Title> = self.volumes.iter().flat_map(|v| self.get_books_for_volume(v)).collect();
+        let b = books.iter().position(|title| title == book)?;
+        let other = if forward { books.get(b + 1)? } else { books.get(b.checked_sub(1)?)? };
+        let chapters = self.get_chapters_for_book(other);
+        let c = if forward { chapters.first()? } else { chapters.last()? };
+        Some((other.to_string(), *c))
+    }
+
+    /// Whether every verse of a range exists (one cited in an AI answer can
+    /// run past the end of its chapter)
+    pub fn is_valid_range(&self, range: &ScriptureRange) -> bool {
+        range.start_verse <= range.end_verse
+            && self.get_verses_in_range(range).len() == (range.end_verse - range.start_verse + 1) as usize
+    }
+
+    /// The verses of a range with up to `before` and `after` more from its
+    /// chapter on either side
+    pub fn verses_around(&self, range: &ScriptureRange, before: i32, after: i32) -> Vec<&Scripture> {
+        let (start, end) = (range.start_verse - before, range.end_verse + after);
+        self.get_verses_for_chapter(&range.book_title, range.chapter_number)
+            .into_iter()
+            .filter(|s| (start..=end).contains(&s.verse_number))
+            .collect()
+    }
+
     /// Get a scripture by its verse title (e.g., "John 3:16")
     pub fn get_by_title(&self, verse_title: &str) -> Option<&Scripture> {
         self.scriptures.iter().find(|s| s.verse_title == verse_title)
@@ -167,17 +275,23 @@ impl ScriptureDb {
             .collect()
     }
 
+    /// The word index, built the first time it's needed
+    pub fn word_index(&self) -> &WordIndex {
+        self.words.get_or_init(|| timings::time("build word index", || WordIndex::build(&self.scriptures)))
+    }
+
     pub fn search(&self, query: &str, limit: usize) -> Vec<&Scripture> {
+        self.search_where(query, limit, |_| true)
+    }
+
+    /// Keyword search over only the verses `keep` accepts
+    pub fn search_where(&self, query: &str, limit: usize, keep: impl Fn(&Scripture) -> bool) -> Vec<&Scripture> {
         let query_lower = query.to_lowercase();
         let stemmer = Stemmer::create(Algorithm::English);
 
         // Stem each word in the query (strip punctuation first)
-        let stemmed_terms: Vec<String> = query_lower
-            .split_whitespace()
-            .map(|word| {
-                let clean: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
-                stemmer.stem(&clean).to_string()
-            })
+        let stemmed_terms: Vec<String> = word_index::words(&query_lower)
+            .map(|word| stem_word(&stemmer, word))
             .filter(|s| !s.is_empty())
             .collect();
 
@@ -186,9 +300,13 @@ impl ScriptureDb {
             return Vec::new();
         }
 
+        let with_terms = self.word_index().verses_with_all(&stemmed_terms);
+
         self.scriptures
             .iter()
-            .filter(|scripture| {
+            .enumerate()
+            .filter(|(_, scripture)| keep(scripture))
+            .filter(|(i, scripture)| {
                 // Check exact match for verse/book titles (for reference searches)
                 if scripture.verse_title.to_lowercase().contains(&query_lower)
                     || scripture.book_title.to_lowercase().contains(&query_lower)
@@ -196,21 +314,10 @@ impl ScriptureDb {
                     return true;
                 }
 
-                // Stem words in scripture text and check if all query terms match
-                let text_lower = scripture.scripture_text.to_lowercase();
-                let text_stems: Vec<String> = text_lower
-                    .split_whitespace()
-                    .map(|word| {
-                        let clean: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
-                        stemmer.stem(&clean).to_string()
-                    })
-                    .collect();
-
                 // All stemmed query terms must appear in stemmed text
-                stemmed_terms.iter().all(|term| {
-                    text_stems.iter().any(|text_stem| text_stem == term)
-                })
+                with_terms.binary_search(i).is_ok()
             })
+            .map(|(_, scripture)| scripture)
             .take(limit)
             .collect()
     }
@@ -254,8 +361,8 @@ impl ScriptureDb {
                                 .unwrap_or(start_verse);
 
                             let range = ScriptureRange {
-                                book_title: scripture.book_title.clone(),
-                                book_short_title: scripture.book_short_title.clone(),
+                                book_title: scripture.book_title.to_string(),
+                                book_short_title: scripture.book_short_title.to_string(),
                                 chapter_number: chapter,
                                 start_verse,
                                 end_verse,
@@ -279,6 +386,94 @@ impl ScriptureDb {
         references
     }
 
+    /// Parse a typed reference like "Alma 32:21", "1 ne 3:7-9", "dc 76", or "Enos".
+    /// Book names match full titles, short titles ("D&C", "Hel."), or else the first book
+    /// (in canonical order) whose title starts with the typed prefix.
+    /// Chapter-only references span the whole chapter.
+    pub fn parse_reference(&self, input: &str) -> Option<ScriptureRange> {
+        use regex::Regex;
+
+        let re = Regex::new(
+            r"^(?P<book>.*?[A-Za-z&].*?)\s*(?:(?P<chapter>\d+)(?:\s*[:.\s]\s*(?P<verse>\d+)(?:\s*[-–—]\s*(?P<endverse>\d+))?)?)?$",
+        ).ok()?;
+        // "1 Ne. intro" is the book's front matter
+        let input = input.trim();
+        let (input, intro) = match input.rsplit_once(char::is_whitespace) {
+            Some((book, word)) if ["intro", "introduction"].contains(&word.to_lowercase().as_str()) => (book, true),
+            _ => (input, false),
+        };
+        let caps = re.captures(input.trim())?;
+
+        let book_title = self.resolve_book(caps.name("book")?.as_str())?;
+        let chapters = self.get_chapters_for_book(&book_title);
+        let chapter = match caps.name("chapter") {
+            Some(_) if intro => return None,
+            Some(m) => m.as_str().parse::<i32>().ok()?,
+            None if intro => crate::front_matter::FRONT_MATTER_CHAPTER,
+            // The book itself, not its introduction
+            None => *chapters.iter().find(|&&c| c > 0).or(chapters.first())?,
+        };
+        if !chapters.contains(&chapter) {
+            return None;
+        }
+
+        let verses = self.get_verses_for_chapter(&book_title, chapter);
+        let first = verses.first()?;
+        let last_verse = verses.last()?.verse_number;
+
+        let (start_verse, end_verse) = match caps.name("verse") {
+            Some(m) => {
+                let start = m.as_str().parse::<i32>().ok()?;
+                if start < 1 || start > last_verse {
+                    return None;
+                }
+                let end = caps
+                    .name("endverse")
+                    .and_then(|m| m.as_str().parse::<i32>().ok())
+                    .unwrap_or(start)
+                    .clamp(start, last_verse);
+                (start, end)
+            }
+            None => (1, last_verse),
+        };
+
+        Some(ScriptureRange {
+            book_title,
+            book_short_title: first.book_short_title.to_string(),
+            chapter_number: chapter,
+            start_verse,
+            end_verse,
+        })
+    }
+
+    /// Resolve a typed book name to its full title
+    fn resolve_book(&self, name: &str) -> Option<String> {
+        let normalize = |s: &str| -> String {
+            s.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let wanted = normalize(name);
+        if wanted.is_empty() {
+            return None;
+        }
+
+        // (title, short title) for each book in canonical order
+        let mut books: Vec<(&str, &str)> = Vec::new();
+        for scripture in &self.scriptures {
+            if books.last().map(|(title, _)| *title) != Some(scripture.book_title.as_str()) {
+                books.push((&scripture.book_title, &scripture.book_short_title));
+            }
+        }
+
+        books
+            .iter()
+            .find(|(title, short)| normalize(title) == wanted || normalize(short) == wanted)
+            .or_else(|| books.iter().find(|(title, _)| normalize(title).starts_with(&wanted)))
+            .map(|(title, _)| title.to_string())
+    }
+
     fn find_exact_scripture(&self, book_name: &str, chapter: i32, verse: i32) -> Option<Scripture> {
         for scripture in &self.scriptures {
             // Try exact book title match
@@ -345,6 +540,60 @@ impl ScriptureDb {
     }
 }
 
+/// "Section 76" in the Doctrine and Covenants, "Hymn 2" in the hymns,
+/// "Chapter 32" elsewhere
+pub fn chapter_label(book: &str, chapter: i32) -> String {
+    if chapter == crate::front_matter::FRONT_MATTER_CHAPTER {
+        "Introduction".to_string()
+    } else if book == "Doctrine and Covenants" {
+        format!("Section {}", chapter)
+    } else if book == crate::hymns::HYMN_VOLUME {
+        format!("Hymn {}", chapter)
+    } else {
+        format!("Chapter {}", chapter)
+    }
+}
+
+/// Lowercased word with punctuation removed, reduced to its English stem
+pub(crate) fn stem_word(stemmer: &Stemmer, word: &str) -> String {
+    let clean: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
+    stemmer.stem(&clean).to_string()
+}
+
+/// Verses for tests: `Scripture::test("Alma", 32, 21)` is Alma 32:21 in the
+/// Book of Mormon, with no text; `volume`, `short`, and `text` fill in the rest
+#[cfg(test)]
+impl Scripture {
+    pub(crate) fn test(book: &str, chapter: i32, number: i32) -> Self {
+        Scripture {
+            volume_title: "Book of Mormon".into(),
+            book_title: book.into(),
+            book_short_title: book.into(),
+            chapter_number: chapter,
+            verse_number: number,
+            verse_title: format!("{} {}:{}", book, chapter, number),
+            verse_short_title: format!("{} {}:{}", book, chapter, number),
+            scripture_text: String::new(),
+        }
+    }
+
+    pub(crate) fn volume(mut self, volume: &str) -> Self {
+        self.volume_title = volume.into();
+        self
+    }
+
+    pub(crate) fn short(mut self, short: &str) -> Self {
+        self.book_short_title = short.into();
+        self.verse_short_title = format!("{} {}:{}", short, self.chapter_number, self.verse_number);
+        self
+    }
+
+    pub(crate) fn text(mut self, text: &str) -> Self {
+        self.scripture_text = text.to_string();
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,141 +602,26 @@ mod tests {
     fn create_test_db() -> ScriptureDb {
         let scriptures = vec![
             // John (for basic tests)
-            Scripture {
-                volume_title: "New Testament".to_string(),
-                book_title: "John".to_string(),
-                book_short_title: "John".to_string(),
-                chapter_number: 3,
-                verse_number: 16,
-                verse_title: "John 3:16".to_string(),
-                verse_short_title: "John 3:16".to_string(),
-                scripture_text: "For God so loved the world...".to_string(),
-            },
-            Scripture {
-                volume_title: "New Testament".to_string(),
-                book_title: "John".to_string(),
-                book_short_title: "John".to_string(),
-                chapter_number: 3,
-                verse_number: 17,
-                verse_title: "John 3:17".to_string(),
-                verse_short_title: "John 3:17".to_string(),
-                scripture_text: "For God sent not his Son...".to_string(),
-            },
+            Scripture::test("John", 3, 16).volume("New Testament").text("For God so loved the world..."),
+            Scripture::test("John", 3, 17).volume("New Testament").text("For God sent not his Son..."),
             // Numbered Book of Mormon books
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "1 Nephi".to_string(),
-                book_short_title: "1 Ne.".to_string(),
-                chapter_number: 3,
-                verse_number: 7,
-                verse_title: "1 Nephi 3:7".to_string(),
-                verse_short_title: "1 Ne. 3:7".to_string(),
-                scripture_text: "I will go and do...".to_string(),
-            },
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "2 Nephi".to_string(),
-                book_short_title: "2 Ne.".to_string(),
-                chapter_number: 2,
-                verse_number: 25,
-                verse_title: "2 Nephi 2:25".to_string(),
-                verse_short_title: "2 Ne. 2:25".to_string(),
-                scripture_text: "Adam fell that men might be...".to_string(),
-            },
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "3 Nephi".to_string(),
-                book_short_title: "3 Ne.".to_string(),
-                chapter_number: 11,
-                verse_number: 14,
-                verse_title: "3 Nephi 11:14".to_string(),
-                verse_short_title: "3 Ne. 11:14".to_string(),
-                scripture_text: "Arise and come forth unto me...".to_string(),
-            },
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "4 Nephi".to_string(),
-                book_short_title: "4 Ne.".to_string(),
-                chapter_number: 1,
-                verse_number: 1,
-                verse_title: "4 Nephi 1:1".to_string(),
-                verse_short_title: "4 Ne. 1:1".to_string(),
-                scripture_text: "And it came to pass...".to_string(),
-            },
+            Scripture::test("1 Nephi", 3, 7).short("1 Ne.").text("I will go and do..."),
+            Scripture::test("2 Nephi", 2, 25).short("2 Ne.").text("Adam fell that men might be..."),
+            Scripture::test("3 Nephi", 11, 14).short("3 Ne.").text("Arise and come forth unto me..."),
+            Scripture::test("4 Nephi", 1, 1).short("4 Ne.").text("And it came to pass..."),
             // Mosiah (for range tests)
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "Mosiah".to_string(),
-                book_short_title: "Mosiah".to_string(),
-                chapter_number: 4,
-                verse_number: 19,
-                verse_title: "Mosiah 4:19".to_string(),
-                verse_short_title: "Mosiah 4:19".to_string(),
-                scripture_text: "For behold, are we not all beggars?".to_string(),
-            },
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "Mosiah".to_string(),
-                book_short_title: "Mosiah".to_string(),
-                chapter_number: 4,
-                verse_number: 20,
-                verse_title: "Mosiah 4:20".to_string(),
-                verse_short_title: "Mosiah 4:20".to_string(),
-                scripture_text: "And behold, even at this time...".to_string(),
-            },
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "Mosiah".to_string(),
-                book_short_title: "Mosiah".to_string(),
-                chapter_number: 4,
-                verse_number: 21,
-                verse_title: "Mosiah 4:21".to_string(),
-                verse_short_title: "Mosiah 4:21".to_string(),
-                scripture_text: "And now, if God, who has created you...".to_string(),
-            },
+            Scripture::test("Mosiah", 4, 19).text("For behold, are we not all beggars?"),
+            Scripture::test("Mosiah", 4, 20).text("And behold, even at this time..."),
+            Scripture::test("Mosiah", 4, 21).text("And now, if God, who has created you..."),
             // Doctrine and Covenants
-            Scripture {
-                volume_title: "Doctrine and Covenants".to_string(),
-                book_title: "Doctrine and Covenants".to_string(),
-                book_short_title: "D&C".to_string(),
-                chapter_number: 76,
-                verse_number: 22,
-                verse_title: "Doctrine and Covenants 76:22".to_string(),
-                verse_short_title: "D&C 76:22".to_string(),
-                scripture_text: "And now, after the many testimonies...".to_string(),
-            },
-            Scripture {
-                volume_title: "Doctrine and Covenants".to_string(),
-                book_title: "Doctrine and Covenants".to_string(),
-                book_short_title: "D&C".to_string(),
-                chapter_number: 4,
-                verse_number: 2,
-                verse_title: "Doctrine and Covenants 4:2".to_string(),
-                verse_short_title: "D&C 4:2".to_string(),
-                scripture_text: "Therefore, O ye that embark...".to_string(),
-            },
+            Scripture::test("Doctrine and Covenants", 76, 22).volume("Doctrine and Covenants").short("D&C").text("And now, after the many testimonies..."),
+            Scripture::test("Doctrine and Covenants", 4, 2).volume("Doctrine and Covenants").short("D&C").text("Therefore, O ye that embark..."),
             // Numbered NT books
-            Scripture {
-                volume_title: "New Testament".to_string(),
-                book_title: "1 Corinthians".to_string(),
-                book_short_title: "1 Cor.".to_string(),
-                chapter_number: 13,
-                verse_number: 4,
-                verse_title: "1 Corinthians 13:4".to_string(),
-                verse_short_title: "1 Cor. 13:4".to_string(),
-                scripture_text: "Charity suffereth long...".to_string(),
-            },
+            Scripture::test("1 Corinthians", 13, 4).volume("New Testament").short("1 Cor.").text("Charity suffereth long..."),
             // Alma (for additional tests)
-            Scripture {
-                volume_title: "Book of Mormon".to_string(),
-                book_title: "Alma".to_string(),
-                book_short_title: "Alma".to_string(),
-                chapter_number: 32,
-                verse_number: 21,
-                verse_title: "Alma 32:21".to_string(),
-                verse_short_title: "Alma 32:21".to_string(),
-                scripture_text: "And now as I said concerning faith...".to_string(),
-            },
+            Scripture::test("Alma", 32, 21).text("And now as I said concerning faith..."),
+            // Doctrine and Covenants (for short-title parsing)
+            Scripture::test("Doctrine and Covenants", 76, 22).volume("Doctrine and Covenants").short("D&C").text("And now, after the many testimonies..."),
         ];
 
         let mut db = ScriptureDb::new();
@@ -602,6 +736,25 @@ mod tests {
         assert_eq!(refs[0].end_verse, 21);
     }
 
+    #[test]
+    fn test_extracted_ranges_past_the_chapter_are_invalid() {
+        let db = create_test_db();
+        let refs = db.extract_scripture_references("Mosiah 4:19-21; Mosiah 4:19-40; Mosiah 4:21-19");
+        let valid: Vec<bool> = refs.iter().map(|r| db.is_valid_range(r)).collect();
+        assert_eq!(valid, [true, false, false]);
+    }
+
+    #[test]
+    fn test_adjacent_chapter_crosses_books() {
+        let db = create_test_db();
+        assert_eq!(db.adjacent_chapter("1 Nephi", 3, true), Some(("2 Nephi".to_string(), 2)));
+        assert_eq!(db.adjacent_chapter("2 Nephi", 2, false), Some(("1 Nephi".to_string(), 3)));
+        assert_eq!(db.adjacent_chapter("John", 3, false), None);
+        assert_eq!(db.adjacent_chapter("John", 4, true), None);
+        assert_eq!(chapter_label("Doctrine and Covenants", 76), "Section 76");
+        assert_eq!(chapter_label("Doctrine and Covenants", 0), "Introduction");
+    }
+
     #[test]
     fn test_extract_verse_range_en_dash() {
         let db = create_test_db();
@@ -672,6 +825,16 @@ mod tests {
         assert_eq!(refs[0].start_verse, 2);
     }
 
+    #[test]
+    fn test_from_json() {
+        let json = r#"[{"volume_title":"Book of Mormon","book_title":"Enos","book_short_title":"Enos","chapter_number":1,
+            "verse_number":1,"verse_title":"Enos 1:1","verse_short_title":"Enos 1:1","scripture_text":"Behold"}]"#;
+        let db = ScriptureDb::from_json(json).unwrap();
+        assert_eq!(db.get_volumes(), ["Book of Mormon"]);
+        assert_eq!(db.parse_reference("enos").unwrap().display_title(), "Enos 1:1");
+        assert!(ScriptureDb::from_json("not scripture").is_err());
+    }
+
     // Edge cases
 
     #[test]
@@ -814,4 +977,76 @@ mod tests {
         let results = db.search("...", 10);
         assert!(results.is_empty(), "Punctuation-only query should return no results");
     }
-}
\ No newline at end of file
+
+    // Typed reference parsing tests
+
+    #[test]
+    fn test_parse_reference_verse_and_range() {
+        let db = create_test_db();
+
+        let r = db.parse_reference("Alma 32:21").unwrap();
+        assert_eq!(r.book_title, "Alma");
+        assert_eq!((r.chapter_number, r.start_verse, r.end_verse), (32, 21, 21));
+
+        let r = db.parse_reference("john 3:16-17").unwrap();
+        assert_eq!(r.book_title, "John");
+        assert_eq!((r.start_verse, r.end_verse), (16, 17));
+        let titles: Vec<&str> = db.get_verses_in_range(&r).iter().map(|v| v.verse_title.as_str()).collect();
+        assert_eq!(titles, ["John 3:16", "John 3:17"]);
+
+        let r = db.parse_reference("John 3:17").unwrap();
+        let titles: Vec<&str> = db.verses_around(&r, 2, 2).iter().map(|v| v.verse_title.as_str()).collect();
+        assert_eq!(titles, ["John 3:16", "John 3:17"]);
+    }
+
+    #[test]
+    fn test_parse_reference_short_titles_and_prefixes() {
+        let db = create_test_db();
+
+        assert_eq!(db.parse_reference("1 ne 3:7").unwrap().book_title, "1 Nephi");
+        assert_eq!(db.parse_reference("2 Ne. 2:25").unwrap().book_title, "2 Nephi");
+        assert_eq!(db.parse_reference("D&C 76:22").unwrap().book_title, "Doctrine and Covenants");
+        assert_eq!(db.parse_reference("mosi 4:19").unwrap().book_title, "Mosiah");
+    }
+
+    #[test]
+    fn test_parse_reference_chapter_only() {
+        let db = create_test_db();
+
+        let r = db.parse_reference("dc 76").unwrap();
+        assert_eq!(r.book_title, "Doctrine and Covenants");
+        assert_eq!(r.chapter_number, 76);
+        assert_eq!((r.start_verse, r.end_verse), (1, 22));
+    }
+
+    #[test]
+    fn test_parse_reference_invalid() {
+        let db = create_test_db();
+
+        assert!(db.parse_reference("").is_none());
+        assert!(db.parse_reference("Alma 99").is_none());
+        assert!(db.parse_reference("Alma 32:99").is_none());
+        assert!(db.parse_reference("Hezekiah 1:1").is_none());
+    }
+
+    #[test]
+    fn test_verses_share_their_titles() {
+        let db = create_test_db();
+        let verses = db.get_verses_for_chapter("John", 3);
+        assert!(Title::ptr_eq(&verses[0].book_title, &verses[1].book_title));
+        assert!(Title::ptr_eq(&verses[0].volume_title, &db.get_volumes()[0]));
+        let nephi = db.get_by_title("1 Nephi 3:7").unwrap();
+        let books = db.get_books_for_volume("Book of Mormon");
+        assert!(books.iter().any(|book| Title::ptr_eq(book, &nephi.book_title)));
+    }
+
+    #[test]
+    fn test_word_index_waits_for_a_search() {
+        let db = create_test_db();
+        let range = db.parse_reference("John 3:16").unwrap();
+        assert_eq!(db.get_verses_in_range(&range).len(), 1);
+        assert!(db.words.get().is_none());
+        assert_eq!(db.search("loved", 10).len(), 1);
+        assert!(db.words.get().is_some());
+    }
+}
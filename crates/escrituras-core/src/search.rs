@@ -0,0 +1,308 @@
+//! Filtered scripture search
+//!
+//! Merges semantic (embedding) and keyword matches into one list, optionally
+//! restricted to a volume or book. Each hit records which pass found it and,
+//! for semantic hits, the similarity score, so UIs can badge results.
+
+use crate::embeddings::EmbeddingsDb;
+use crate::error::Result;
+use crate::events::{self, Event};
+use crate::scripture::{Scripture, ScriptureDb};
+use crate::title::Title;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Which search passes to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SearchMode {
+    /// Semantic matches first, then keyword matches
+    #[default]
+    Combined,
+    Keyword,
+    Semantic,
+}
+
+impl SearchMode {
+    pub fn all() -> &'static [SearchMode] {
+        &[SearchMode::Combined, SearchMode::Keyword, SearchMode::Semantic]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchMode::Combined => "semantic + keyword",
+            SearchMode::Keyword => "keyword",
+            SearchMode::Semantic => "semantic",
+        }
+    }
+
+    pub fn next(&self) -> SearchMode {
+        let all = Self::all();
+        let i = all.iter().position(|m| m == self).unwrap_or(0);
+        all[(i + 1) % all.len()]
+    }
+}
+
+/// Restrictions applied to a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchFilter {
+    /// Only verses in this volume ("Book of Mormon")
+    pub volume: Option<String>,
+    /// Only verses in this book ("Alma"); takes precedence over `volume`
+    pub book: Option<String>,
+    pub mode: SearchMode,
+    /// Maximum number of hits
+    pub limit: usize,
+}
+
+impl Default for SearchFilter {
+    fn default() -> Self {
+        Self {
+            volume: None,
+            book: None,
+            mode: SearchMode::default(),
+            limit: 50,
+        }
+    }
+}
+
+/// Which pass found a hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchSource {
+    Semantic,
+    Keyword,
+}
+
+/// A verse a search found, and how
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub scripture: Scripture,
+    pub source: MatchSource,
+    /// Cosine similarity for semantic hits
+    pub score: Option<f32>,
+}
+
+/// Search with the given filter; semantic passes are skipped when no embeddings are loaded.
+/// Announced as `Event::SearchPerformed`.
+pub fn search(
+    db: &ScriptureDb,
+    embeddings: Option<&mut EmbeddingsDb>,
+    query: &str,
+    filter: &SearchFilter,
+) -> Vec<SearchHit> {
+    let semantic = semantic_pass(db, embeddings, query, filter);
+    search_with(db, semantic, query, filter)
+}
+
+/// Keyword matches for a query still being typed, which aren't announced as a search
+pub fn preview(db: &ScriptureDb, query: &str, filter: &SearchFilter) -> Vec<SearchHit> {
+    find(db, Vec::new(), query, &SearchFilter { mode: SearchMode::Keyword, ..filter.clone() })
+}
+
+/// A search whose semantic pass was run separately by a `SemanticQuery`,
+/// given its matches. Announced as `Event::SearchPerformed`.
+pub fn search_with(db: &ScriptureDb, semantic: Vec<(String, f32)>, query: &str, filter: &SearchFilter) -> Vec<SearchHit> {
+    let hits = find(db, semantic, query, filter);
+    if !query.trim().is_empty() {
+        events::emit(Event::SearchPerformed { query: query.to_string(), mode: filter.mode, hits: hits.len() });
+    }
+    hits
+}
+
+/// Like `search_with`, for a query still being typed, which isn't announced
+pub fn preview_with(db: &ScriptureDb, semantic: Vec<(String, f32)>, query: &str, filter: &SearchFilter) -> Vec<SearchHit> {
+    find(db, semantic, query, filter)
+}
+
+/// The semantic pass of a search, apart from the scripture database so it can
+/// run on another thread: it's the slow part, embedding the query (loading
+/// the model the first time) and scoring every verse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticQuery {
+    pub query: String,
+    pub filter: SearchFilter,
+    /// Books the search may return, or None for everything
+    books: Option<HashSet<Title>>,
+}
+
+impl SemanticQuery {
+    pub fn new(db: &ScriptureDb, query: &str, filter: &SearchFilter) -> Self {
+        Self { query: query.to_string(), filter: filter.clone(), books: allowed_books(db, filter) }
+    }
+
+    /// Whether the search has a semantic pass at all
+    pub fn is_needed(&self) -> bool {
+        self.filter.mode != SearchMode::Keyword && !self.query.trim().is_empty() && self.filter.limit > 0
+    }
+
+    /// Matching verse titles and their similarity, best first, for `search_with`
+    pub fn run(&self, embeddings: &mut EmbeddingsDb) -> Result<Vec<(String, f32)>> {
+        if !self.is_needed() {
+            return Ok(Vec::new());
+        }
+        let limit = match self.filter.mode {
+            SearchMode::Semantic => self.filter.limit,
+            // Leave most of the list for keyword matches
+            _ => (self.filter.limit * 2 / 5).max(5).min(self.filter.limit),
+        };
+        // Verse titles are "<book> <chapter>:<verse>"
+        let keep = |title: &str| {
+            title.rsplit_once(' ').is_some_and(|(book, _)| self.books.as_ref().is_none_or(|b| b.contains(book)))
+        };
+        embeddings.search_where(&self.query, limit, keep)
+    }
+}
+
+/// Semantic matches, or none when there are no embeddings or the model can't be loaded
+fn semantic_pass(db: &ScriptureDb, embeddings: Option<&mut EmbeddingsDb>, query: &str, filter: &SearchFilter) -> Vec<(String, f32)> {
+    match embeddings {
+        Some(embeddings) => SemanticQuery::new(db, query, filter).run(embeddings).unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+/// Books a search may return, or None for everything
+fn allowed_books(db: &ScriptureDb, filter: &SearchFilter) -> Option<HashSet<Title>> {
+    match (&filter.book, &filter.volume) {
+        (Some(book), _) => Some(HashSet::from([Title::from(book)])),
+        (None, Some(volume)) => Some(db.get_books_for_volume(volume).into_iter().collect()),
+        (None, None) => None,
+    }
+}
+
+/// The semantic matches that are verses, then keyword matches, up to the limit
+fn find(db: &ScriptureDb, semantic: Vec<(String, f32)>, query: &str, filter: &SearchFilter) -> Vec<SearchHit> {
+    if query.trim().is_empty() || filter.limit == 0 {
+        return Vec::new();
+    }
+    let books = allowed_books(db, filter);
+    let allows = |book: &str| books.as_ref().is_none_or(|b| b.contains(book));
+
+    let mut hits: Vec<SearchHit> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    if filter.mode != SearchMode::Keyword {
+        for (verse_title, score) in semantic {
+            if let Some(scripture) = db.get_by_title(&verse_title) {
+                seen.insert(verse_title);
+                hits.push(SearchHit {
+                    scripture: scripture.clone(),
+                    source: MatchSource::Semantic,
+                    score: Some(score),
+                });
+            }
+        }
+    }
+
+    if filter.mode != SearchMode::Semantic {
+        for scripture in db.search_where(query, filter.limit, |s| allows(&s.book_title)) {
+            if hits.len() >= filter.limit {
+                break;
+            }
+            if seen.insert(scripture.verse_title.clone()) {
+                hits.push(SearchHit {
+                    scripture: scripture.clone(),
+                    source: MatchSource::Keyword,
+                    score: None,
+                });
+            }
+        }
+    }
+
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(volume: &str, book: &str, chapter: i32, number: i32, text: &str) -> Scripture {
+        Scripture::test(book, chapter, number).volume(volume).text(text)
+    }
+
+    fn test_db() -> ScriptureDb {
+        ScriptureDb::from_verses(vec![
+            verse("New Testament", "Hebrews", 11, 1, "Now faith is the substance of things hoped for"),
+            verse("Book of Mormon", "Alma", 32, 21, "Faith is not to have a perfect knowledge of things"),
+            verse("Book of Mormon", "Ether", 12, 6, "Faith is things which are hoped for and not seen"),
+        ])
+    }
+
+    #[test]
+    fn test_keyword_search_respects_volume_and_book() {
+        let db = test_db();
+        let filter = SearchFilter {
+            mode: SearchMode::Keyword,
+            ..Default::default()
+        };
+        assert_eq!(search(&db, None, "faith", &filter).len(), 3);
+
+        let by_volume = SearchFilter {
+            volume: Some("Book of Mormon".to_string()),
+            ..filter.clone()
+        };
+        let hits = search(&db, None, "faith", &by_volume);
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| h.source == MatchSource::Keyword && h.score.is_none()));
+
+        let by_book = SearchFilter {
+            book: Some("Ether".to_string()),
+            ..by_volume
+        };
+        let hits = search(&db, None, "faith", &by_book);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].scripture.verse_title, "Ether 12:6");
+    }
+
+    #[test]
+    fn test_limit_and_semantic_only_without_embeddings() {
+        let db = test_db();
+        let limited = SearchFilter {
+            limit: 1,
+            ..Default::default()
+        };
+        assert_eq!(search(&db, None, "faith", &limited).len(), 1);
+
+        let semantic = SearchFilter {
+            mode: SearchMode::Semantic,
+            ..Default::default()
+        };
+        assert!(search(&db, None, "faith", &semantic).is_empty());
+    }
+
+    #[test]
+    fn test_semantic_matches_found_separately() {
+        let db = test_db();
+        let filter = SearchFilter { volume: Some("Book of Mormon".to_string()), ..Default::default() };
+        let query = SemanticQuery::new(&db, "faith", &filter);
+        assert!(query.is_needed());
+        assert!(!SemanticQuery::new(&db, "faith", &SearchFilter { mode: SearchMode::Keyword, ..filter.clone() }).is_needed());
+
+        // Matches that aren't verses are dropped; keyword matches follow without repeats
+        let semantic = vec![("Ether 12:6".to_string(), 0.8), ("Moroni 11:1".to_string(), 0.5)];
+        let hits = preview_with(&db, semantic, "faith", &filter);
+        let found: Vec<(&str, MatchSource)> = hits.iter().map(|h| (h.scripture.verse_title.as_str(), h.source)).collect();
+        assert_eq!(found, [("Ether 12:6", MatchSource::Semantic), ("Alma 32:21", MatchSource::Keyword)]);
+        assert_eq!(hits[0].score, Some(0.8));
+    }
+
+    #[test]
+    fn test_searches_are_announced_but_previews_are_not() {
+        let db = test_db();
+        let heard = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let id = events::subscribe({
+            let heard = heard.clone();
+            move |event| {
+                if let Event::SearchPerformed { query, hits, .. } = event {
+                    if query.starts_with("hoped for") {
+                        heard.lock().unwrap().push((query.clone(), *hits));
+                    }
+                }
+            }
+        });
+
+        assert_eq!(preview(&db, "hoped for", &SearchFilter::default()).len(), 2);
+        search(&db, None, "hoped for things", &SearchFilter::default());
+        events::unsubscribe(id);
+        assert_eq!(*heard.lock().unwrap(), [("hoped for things".to_string(), 2)]);
+    }
+}
@@ -0,0 +1,176 @@
+//! First-run setup
+//!
+//! Finds the scripture data the way every UI loads it (local checkout first,
+//! then the data directory) and installs it when it's missing: either from a
+//! copy the user already has, or from the latest GitHub release, whose archive
+//! carries the same `lds-scriptures-2020.12.08/` and `data/` folders that
+//! `install.sh` unpacks. Downloading needs the `download` feature.
+
+#[cfg(feature = "download")]
+use flate2::read::GzDecoder;
+#[cfg(feature = "download")]
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{EscriturasError, Result};
+use crate::paths;
+
+#[cfg(feature = "download")]
+const REPO: &str = "noahread/escrituras";
+
+/// Scripture text, relative to the checkout or the data directory
+pub const SCRIPTURE_JSON: &str = "lds-scriptures-2020.12.08/json/lds-scriptures-json.txt";
+
+/// Folders searched for data, in order: `data_dir` from the config, the
+/// current directory, then the data directory (see `paths`)
+fn data_roots() -> Vec<PathBuf> {
+    let configured = crate::Config::load().ok().and_then(|c| c.data_dir).map(PathBuf::from);
+    configured.into_iter().chain([PathBuf::new()]).chain(paths::data_dir().ok()).collect()
+}
+
+/// The data directory holding `file`: the configured data_dir's data/, local
+/// data/, then ~/.local/share/escrituras/data/
+pub fn find_data_dir(file: &str) -> Option<PathBuf> {
+    data_roots().into_iter().map(|root| root.join("data")).find(|dir| dir.join(file).exists())
+}
+
+/// The scripture JSON, from the configured data_dir, the current directory,
+/// or the data directory
+pub fn find_scripture_data() -> Option<PathBuf> {
+    data_roots().into_iter().map(|root| root.join(SCRIPTURE_JSON)).find(|p| p.exists())
+}
+
+/// Copy scripture data the user already has into the data directory.
+/// `source` is the JSON file itself or a folder containing it (such as
+/// `lds-scriptures-2020.12.08/`).
+pub fn install_scripture_file(source: &Path) -> Result<PathBuf> {
+    install_scripture_file_to(source, &paths::data_dir()?)
+}
+
+pub fn install_scripture_file_to(source: &Path, data_dir: &Path) -> Result<PathBuf> {
+    let file = if source.is_dir() {
+        ["json/lds-scriptures-json.txt", "lds-scriptures-json.txt", SCRIPTURE_JSON]
+            .iter()
+            .map(|name| source.join(name))
+            .find(|p| p.is_file())
+            .ok_or_else(|| EscriturasError::DataNotFound(format!("No lds-scriptures-json.txt in {}", source.display())))?
+    } else {
+        source.to_path_buf()
+    };
+
+    // Make sure it's really scripture data before copying it
+    let content = fs::read_to_string(&file).map_err(|e| EscriturasError::io(format!("Failed to read {}", file.display()), e))?;
+    let verses: Vec<crate::scripture::Scripture> =
+        serde_json::from_str(&content).map_err(|e| EscriturasError::ParseError(format!("{} isn't scripture data: {}", file.display(), e)))?;
+    if verses.is_empty() {
+        return Err(EscriturasError::ParseError(format!("{} has no verses", file.display())));
+    }
+
+    let dest = data_dir.join(SCRIPTURE_JSON);
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&dest, content)?;
+    Ok(dest)
+}
+
+#[cfg(feature = "download")]
+#[derive(Deserialize)]
+struct Release {
+    assets: Vec<Asset>,
+}
+
+#[cfg(feature = "download")]
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Download the latest release and unpack its scripture data and embeddings
+/// into the data directory. Returns the path of the scripture JSON.
+#[cfg(feature = "download")]
+pub async fn download_release_data() -> Result<PathBuf> {
+    let client = reqwest::Client::builder().user_agent("escrituras").build()?;
+    let release: Release = client
+        .get(format!("https://api.github.com/repos/{}/releases/latest", REPO))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    // Every platform's archive carries the same data; prefer this one's
+    let platform = format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let archives: Vec<&Asset> = release.assets.iter().filter(|a| a.name.ends_with(".tar.gz")).collect();
+    let asset = archives
+        .iter()
+        .find(|a| a.name.contains(&platform))
+        .or_else(|| archives.first())
+        .ok_or_else(|| EscriturasError::Other("The latest release has no data archive".to_string()))?;
+
+    let bytes = client
+        .get(&asset.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    let dir = paths::data_dir()?;
+    tokio::task::spawn_blocking(move || unpack_data(&bytes, &dir))
+        .await
+        .map_err(|e| EscriturasError::Other(format!("Unpacking the data failed: {}", e)))?
+}
+
+/// Extract `lds-scriptures-2020.12.08/` and `data/` from a release archive
+#[cfg(feature = "download")]
+fn unpack_data(archive: &[u8], dir: &Path) -> Result<PathBuf> {
+    let mut archive = tar::Archive::new(GzDecoder::new(archive));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let path = path.strip_prefix(".").unwrap_or(&path).to_path_buf();
+        if !(path.starts_with("lds-scriptures-2020.12.08") || path.starts_with("data")) {
+            continue;
+        }
+        let dest = dir.join(&path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&dest)?;
+    }
+
+    let scriptures = dir.join(SCRIPTURE_JSON);
+    if !scriptures.exists() {
+        return Err(EscriturasError::Other("The release archive didn't contain scripture data".to_string()));
+    }
+    Ok(scriptures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_install_scripture_file_from_folder() {
+        let source = tempdir().unwrap();
+        let config = tempdir().unwrap();
+        fs::create_dir_all(source.path().join("json")).unwrap();
+        fs::write(
+            source.path().join("json/lds-scriptures-json.txt"),
+            r#"[{"volume_title":"Book of Mormon","book_title":"Enos","book_short_title":"Enos","chapter_number":1,
+                "verse_number":1,"verse_title":"Enos 1:1","verse_short_title":"Enos 1:1","scripture_text":"Behold"}]"#,
+        )
+        .unwrap();
+
+        let dest = install_scripture_file_to(source.path(), config.path()).unwrap();
+        assert_eq!(dest, config.path().join(SCRIPTURE_JSON));
+        assert!(dest.exists());
+
+        fs::write(source.path().join("notes.txt"), "not scripture").unwrap();
+        assert!(install_scripture_file_to(&source.path().join("notes.txt"), config.path()).is_err());
+    }
+}
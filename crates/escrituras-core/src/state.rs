@@ -3,7 +3,13 @@
 //! This module contains data structures that are shared between different UIs
 //! (TUI, Tauri desktop app, etc.) and don't depend on any specific UI framework.
 
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::scripture::Scripture;
 
 /// A chat message in the AI conversation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,3 +24,329 @@ pub enum ChatRole {
     User,
     Assistant,
 }
+
+/// Where a study session left off, saved on quit and restored at the next launch
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Open screen ("browse", "search", or "query")
+    pub screen: String,
+    pub book_title: Option<String>,
+    pub chapter_number: Option<i32>,
+    /// Verse number under the cursor
+    pub selected_verse: Option<i32>,
+    /// Top line of the content pane
+    pub line_scroll: usize,
+    pub search_input: String,
+    pub chat_messages: Vec<ChatMessage>,
+}
+
+impl SessionState {
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::get_session_path()?)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let session: SessionState = serde_json::from_str(&content)?;
+        Ok(session)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::get_session_path()?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    fn get_session_path() -> Result<PathBuf> {
+        Ok(crate::paths::state_dir()?.join("session.json"))
+    }
+}
+
+/// Verses saved for study (the Saved Scriptures panel), written whenever the list changes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedScriptures {
+    pub verses: Vec<Scripture>,
+    /// Tags ("faith", "lesson-prep") by verse title
+    #[serde(default)]
+    pub tags: BTreeMap<String, Vec<String>>,
+}
+
+impl SavedScriptures {
+    /// From the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Result<Self> {
+        crate::store::Store::open()?.saved()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let saved: SavedScriptures = serde_json::from_str(&content)?;
+        Ok(saved)
+    }
+
+    /// Write what changed since `loaded` to the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, loaded: &Self) -> Result<()> {
+        crate::store::Store::open()?.save_saved(loaded, self)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Save verses not already saved; returns how many were added
+    pub fn add(&mut self, verses: impl IntoIterator<Item = Scripture>) -> usize {
+        let before = self.verses.len();
+        for verse in verses {
+            if !self.verses.iter().any(|v| v.verse_title == verse.verse_title) {
+                self.verses.push(verse);
+            }
+        }
+        self.verses.len() - before
+    }
+
+    /// Remove a saved verse and its tags; false when it wasn't saved
+    pub fn remove(&mut self, verse_title: &str) -> bool {
+        self.tags.remove(verse_title);
+        let before = self.verses.len();
+        self.verses.retain(|v| v.verse_title != verse_title);
+        self.verses.len() < before
+    }
+
+    /// Replace a verse's tags (none removes them)
+    pub fn set_tags(&mut self, verse_title: &str, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.tags.remove(verse_title);
+        } else {
+            self.tags.insert(verse_title.to_string(), tags);
+        }
+    }
+
+    /// Saved verses carrying a tag, in saved order
+    pub fn with_tag(&self, tag: &str) -> Vec<Scripture> {
+        self.verses
+            .iter()
+            .filter(|v| self.tags.get(&v.verse_title).is_some_and(|tags| tags.iter().any(|t| t == tag)))
+            .cloned()
+            .collect()
+    }
+
+    /// Every tag in use, sorted
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.tags.values().flatten().cloned().collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Parse "faith, #Lesson-Prep" into normalized tags: lowercase, no '#', no duplicates
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split([',', ' ']) {
+            let tag = tag.trim().trim_start_matches('#').to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+}
+
+/// An earlier AI conversation, archived when a new one is started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversation {
+    /// Unix timestamp (seconds) when it was archived
+    pub timestamp: u64,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl Conversation {
+    /// The first question asked, for listing conversations
+    pub fn title(&self) -> &str {
+        self.messages
+            .iter()
+            .find(|m| m.role == ChatRole::User)
+            .map(|m| m.content.lines().next().unwrap_or(""))
+            .unwrap_or("")
+    }
+}
+
+/// Prior AI conversations, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConversationHistory {
+    pub conversations: Vec<Conversation>,
+}
+
+impl ConversationHistory {
+    /// Oldest conversations are dropped beyond this many
+    pub const MAX_CONVERSATIONS: usize = 50;
+
+    /// From the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Result<Self> {
+        crate::store::Store::open()?.conversations()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let history: ConversationHistory = serde_json::from_str(&content)?;
+        Ok(history)
+    }
+
+    /// Write what changed since `loaded` to the study data store
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, loaded: &Self) -> Result<()> {
+        crate::store::Store::open()?.save_conversations(loaded, self)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Archive a finished conversation at the current time (empty ones are skipped)
+    pub fn archive(&mut self, messages: Vec<ChatMessage>) {
+        if messages.is_empty() {
+            return;
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.conversations.push(Conversation { timestamp, messages });
+
+        let excess = self.conversations.len().saturating_sub(Self::MAX_CONVERSATIONS);
+        self.conversations.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let session = SessionState {
+            screen: "query".to_string(),
+            book_title: Some("Alma".to_string()),
+            chapter_number: Some(32),
+            selected_verse: Some(21),
+            line_scroll: 40,
+            chat_messages: vec![ChatMessage {
+                role: ChatRole::User,
+                content: "What is faith?".to_string(),
+            }],
+            ..Default::default()
+        };
+        session.save_to(&path).unwrap();
+
+        let loaded = SessionState::load_from(&path).unwrap();
+        assert_eq!(loaded.screen, "query");
+        assert_eq!(loaded.chapter_number, Some(32));
+        assert_eq!(loaded.selected_verse, Some(21));
+        assert_eq!(loaded.chat_messages.len(), 1);
+
+        // Older files missing fields still load
+        fs::write(&path, r#"{"screen": "browse"}"#).unwrap();
+        let partial = SessionState::load_from(&path).unwrap();
+        assert!(partial.book_title.is_none());
+        assert!(partial.chat_messages.is_empty());
+    }
+
+    #[test]
+    fn test_saved_scriptures_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let saved = SavedScriptures::load_from(&dir.path().join("saved.json")).unwrap();
+        assert!(saved.verses.is_empty());
+
+        // Files written before tags existed still load
+        let path = dir.path().join("old.json");
+        fs::write(&path, r#"{"verses": []}"#).unwrap();
+        assert!(SavedScriptures::load_from(&path).unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn test_tags_are_normalized() {
+        assert_eq!(SavedScriptures::parse_tags("Faith, #lesson-prep faith,,"), ["faith", "lesson-prep"]);
+
+        let mut saved = SavedScriptures::default();
+        saved.tags.insert("Alma 32:21".to_string(), vec!["faith".to_string()]);
+        saved.tags.insert("Ether 12:6".to_string(), vec!["trial".to_string(), "faith".to_string()]);
+        assert_eq!(saved.all_tags(), ["faith", "trial"]);
+    }
+
+    #[test]
+    fn test_add_and_remove_keep_tags_in_step() {
+        let verse = |title: &str| Scripture {
+            volume_title: "Book of Mormon".into(),
+            book_title: "Alma".into(),
+            book_short_title: "Alma".into(),
+            chapter_number: 32,
+            verse_number: 21,
+            verse_title: title.to_string(),
+            verse_short_title: title.to_string(),
+            scripture_text: String::new(),
+        };
+        let mut saved = SavedScriptures::default();
+        assert_eq!(saved.add([verse("Alma 32:21"), verse("Alma 32:22")]), 2);
+        assert_eq!(saved.add([verse("Alma 32:21")]), 0);
+        saved.set_tags("Alma 32:21", vec!["faith".to_string()]);
+        assert_eq!(saved.with_tag("faith").len(), 1);
+
+        assert!(saved.remove("Alma 32:21"));
+        assert!(!saved.remove("Alma 32:21"));
+        assert!(saved.tags.is_empty());
+        assert_eq!(saved.verses.len(), 1);
+    }
+
+    #[test]
+    fn test_archive_skips_empty_and_caps_history() {
+        let mut history = ConversationHistory::default();
+        history.archive(Vec::new());
+        assert!(history.conversations.is_empty());
+
+        for i in 0..ConversationHistory::MAX_CONVERSATIONS + 2 {
+            history.archive(vec![ChatMessage {
+                role: ChatRole::User,
+                content: format!("Question {}", i),
+            }]);
+        }
+        assert_eq!(history.conversations.len(), ConversationHistory::MAX_CONVERSATIONS);
+        assert_eq!(history.conversations[0].title(), "Question 2");
+    }
+}
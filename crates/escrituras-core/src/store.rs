@@ -0,0 +1,487 @@
+//! The study data store
+//!
+//! Saved scriptures (with their tags; the desktop app calls them bookmarks),
+//! AI conversations, memorization attempts, Come, Follow Me progress, and
+//! verse highlights live in one SQLite database, `study.db` in the data
+//! directory, shared by the TUI, the command line, and the desktop app. The types that hold this data load and
+//! save through it (`SavedScriptures::load`, `CfmProgress::save`, ...), so
+//! callers don't deal with the database themselves. Saving takes what was
+//! loaded as well, and writes only what changed since: the TUI may hold its
+//! data for hours while the command line, the desktop app, or a sync change
+//! the same tables, and their changes are kept.
+//!
+//! The schema is a list of numbered migrations, and `PRAGMA user_version`
+//! records how many have run; opening a database runs the rest. A new
+//! database also imports the JSON files older versions wrote next to it, in
+//! the same transaction as its migrations, and they're then renamed with an
+//! `.imported` suffix. Notes on verses are journal entries, which stay
+//! Markdown files (see `journal`) since they're written in an editor.
+
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Transaction, TransactionBehavior};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::come_follow_me::CfmProgress;
+use crate::error::{EscriturasError, Result};
+use crate::memorize::{MemorizeAttempt, MemorizeHistory};
+use crate::scripture::Scripture;
+use crate::state::{Conversation, ConversationHistory, SavedScriptures};
+
+/// Each migration's SQL, in order. Add to the end; never change one that has shipped.
+const MIGRATIONS: &[&str] = &[
+    // 1: what older versions kept in JSON files
+    "CREATE TABLE saved_verses (
+        position INTEGER NOT NULL,
+        verse_title TEXT PRIMARY KEY,
+        verse TEXT NOT NULL
+    );
+    CREATE TABLE verse_tags (
+        verse_title TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (verse_title, tag)
+    );
+    CREATE TABLE conversations (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        messages TEXT NOT NULL
+    );
+    CREATE TABLE memorize_attempts (
+        verse_title TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        accuracy REAL NOT NULL
+    );
+    CREATE INDEX memorize_attempts_verse ON memorize_attempts (verse_title);
+    CREATE TABLE reading_progress (
+        week TEXT NOT NULL,
+        label TEXT NOT NULL,
+        PRIMARY KEY (week, label)
+    );",
+    // 2: highlighted verses
+    "CREATE TABLE highlights (
+        verse_title TEXT PRIMARY KEY,
+        color TEXT NOT NULL,
+        timestamp INTEGER NOT NULL
+    );",
+];
+
+/// JSON files older versions kept in the data directory, imported into a new database
+const SAVED_FILE: &str = "saved_scriptures.json";
+const CONVERSATIONS_FILE: &str = "conversations.json";
+const MEMORIZE_FILE: &str = "memorize_history.json";
+const PROGRESS_FILE: &str = "come_follow_me_progress.json";
+
+/// Highlight colors a verse can be marked with
+pub const HIGHLIGHT_COLORS: &[&str] = &["yellow", "green", "blue", "pink", "orange"];
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub const FILE_NAME: &'static str = "study.db";
+
+    /// `study.db` in the data directory, created on first use
+    pub fn open() -> Result<Self> {
+        Self::open_at(&crate::paths::data_dir()?.join(Self::FILE_NAME))
+    }
+
+    pub fn open_at(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        // The TUI and the desktop app may both have it open
+        conn.busy_timeout(Duration::from_secs(5))?;
+        let mut store = Self { conn };
+        store.migrate(path.parent())?;
+        Ok(store)
+    }
+
+    /// How many migrations the database has had
+    pub fn version(&self) -> Result<usize> {
+        Ok(self.conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+    }
+
+    /// Run the migrations this database hasn't had, and for a new one import
+    /// the JSON files in `import_dir`, all in one transaction: a database is
+    /// never left migrated but not yet imported
+    fn migrate(&mut self, import_dir: Option<&Path>) -> Result<()> {
+        if self.version()? == MIGRATIONS.len() {
+            return Ok(());
+        }
+        // Immediate, so two programs opening a new database don't both import
+        let tx = self.conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let version: usize = tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > MIGRATIONS.len() {
+            return Err(EscriturasError::NewerVersion(format!("study.db is from a newer version of escrituras (schema {})", version)));
+        }
+        for (i, sql) in MIGRATIONS.iter().enumerate().skip(version) {
+            tracing::info!("Upgrading study.db to schema {}", i + 1);
+            tx.execute_batch(sql)?;
+        }
+        tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+        let imported = match import_dir {
+            Some(dir) if version == 0 => import_json(&tx, dir)?,
+            _ => Vec::new(),
+        };
+        tx.commit()?;
+        for path in imported {
+            mark_imported(&path)?;
+        }
+        Ok(())
+    }
+
+    pub fn saved(&self) -> Result<SavedScriptures> {
+        let mut saved = SavedScriptures::default();
+        let mut verses = self.conn.prepare("SELECT verse FROM saved_verses ORDER BY position")?;
+        for verse in verses.query_map([], |row| row.get::<_, String>(0))? {
+            saved.verses.push(serde_json::from_str(&verse?)?);
+        }
+        let mut tags = self.conn.prepare("SELECT verse_title, tag FROM verse_tags ORDER BY verse_title, position")?;
+        for row in tags.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+            let (title, tag) = row?;
+            saved.tags.entry(title).or_default().push(tag);
+        }
+        Ok(saved)
+    }
+
+    /// Write the verses saved, removed, and retagged since `loaded`
+    pub fn save_saved(&mut self, loaded: &SavedScriptures, saved: &SavedScriptures) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        update_saved(&tx, loaded, saved)?;
+        Ok(tx.commit()?)
+    }
+
+    /// Archived conversations, oldest first
+    pub fn conversations(&self) -> Result<ConversationHistory> {
+        let mut statement = self.conn.prepare("SELECT timestamp, messages FROM conversations ORDER BY id")?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+        let mut history = ConversationHistory::default();
+        for row in rows {
+            let (timestamp, messages) = row?;
+            history.conversations.push(Conversation { timestamp: timestamp as u64, messages: serde_json::from_str(&messages)? });
+        }
+        Ok(history)
+    }
+
+    /// Write the conversations archived, continued, and dropped since `loaded`
+    pub fn save_conversations(&mut self, loaded: &ConversationHistory, history: &ConversationHistory) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        update_conversations(&tx, loaded, history)?;
+        Ok(tx.commit()?)
+    }
+
+    pub fn memorize_history(&self) -> Result<MemorizeHistory> {
+        let mut statement = self.conn.prepare("SELECT verse_title, timestamp, accuracy FROM memorize_attempts ORDER BY rowid")?;
+        let rows = statement.query_map([], |row| {
+            let attempt = MemorizeAttempt { timestamp: row.get::<_, i64>(1)? as u64, accuracy: row.get::<_, f64>(2)? as f32 };
+            Ok((row.get::<_, String>(0)?, attempt))
+        })?;
+        let mut history = MemorizeHistory::new();
+        for row in rows {
+            let (title, attempt) = row?;
+            history.verses.entry(title).or_default().push(attempt);
+        }
+        Ok(history)
+    }
+
+    /// Write the attempts recorded and removed since `loaded`
+    pub fn save_memorize_history(&mut self, loaded: &MemorizeHistory, history: &MemorizeHistory) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        update_memorize_history(&tx, loaded, history)?;
+        Ok(tx.commit()?)
+    }
+
+    pub fn cfm_progress(&self) -> Result<CfmProgress> {
+        let mut statement = self.conn.prepare("SELECT week, label FROM reading_progress")?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        let mut progress = CfmProgress::default();
+        for row in rows {
+            let (week, label) = row?;
+            let week = NaiveDate::parse_from_str(&week, "%Y-%m-%d")
+                .map_err(|e| EscriturasError::ParseError(format!("Bad week {:?} in study.db: {}", week, e)))?;
+            progress.weeks.entry(week).or_default().insert(label);
+        }
+        Ok(progress)
+    }
+
+    /// Write the readings marked finished and unfinished since `loaded`
+    pub fn save_cfm_progress(&mut self, loaded: &CfmProgress, progress: &CfmProgress) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        update_cfm_progress(&tx, loaded, progress)?;
+        Ok(tx.commit()?)
+    }
+
+    /// Highlight colors by verse title
+    pub fn highlights(&self) -> Result<BTreeMap<String, String>> {
+        let mut statement = self.conn.prepare("SELECT verse_title, color FROM highlights")?;
+        let rows = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn highlight(&self, verse_title: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT color FROM highlights WHERE verse_title = ?1", [verse_title], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// Highlight a verse in one of `HIGHLIGHT_COLORS`, or with None remove its highlight
+    pub fn set_highlight(&mut self, verse_title: &str, color: Option<&str>) -> Result<()> {
+        match color {
+            Some(color) if !HIGHLIGHT_COLORS.contains(&color) => {
+                return Err(EscriturasError::Other(format!("Unknown highlight color '{}'. Use {}", color, HIGHLIGHT_COLORS.join(", "))));
+            }
+            Some(color) => self.conn.execute(
+                "INSERT OR REPLACE INTO highlights (verse_title, color, timestamp) VALUES (?1, ?2, ?3)",
+                params![verse_title, color, now() as i64],
+            )?,
+            None => self.conn.execute("DELETE FROM highlights WHERE verse_title = ?1", [verse_title])?,
+        };
+        Ok(())
+    }
+}
+
+/// Bring in the JSON files in `dir`, returning the ones to rename once the
+/// transaction is committed
+fn import_json(tx: &Transaction, dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut imported = Vec::new();
+    let path = dir.join(SAVED_FILE);
+    if path.exists() {
+        update_saved(tx, &SavedScriptures::default(), &SavedScriptures::load_from(&path)?)?;
+        imported.push(path);
+    }
+    let path = dir.join(CONVERSATIONS_FILE);
+    if path.exists() {
+        update_conversations(tx, &ConversationHistory::default(), &ConversationHistory::load_from(&path)?)?;
+        imported.push(path);
+    }
+    let path = dir.join(MEMORIZE_FILE);
+    if path.exists() {
+        update_memorize_history(tx, &MemorizeHistory::new(), &MemorizeHistory::load_from(&path)?)?;
+        imported.push(path);
+    }
+    let path = dir.join(PROGRESS_FILE);
+    if path.exists() {
+        update_cfm_progress(tx, &CfmProgress::default(), &CfmProgress::load_from(&path)?)?;
+        imported.push(path);
+    }
+    Ok(imported)
+}
+
+fn update_saved(tx: &Transaction, loaded: &SavedScriptures, saved: &SavedScriptures) -> Result<()> {
+    let before: HashMap<&str, &Scripture> = loaded.verses.iter().map(|v| (v.verse_title.as_str(), v)).collect();
+    let after: HashMap<&str, &Scripture> = saved.verses.iter().map(|v| (v.verse_title.as_str(), v)).collect();
+    for title in before.keys().filter(|title| !after.contains_key(*title)) {
+        tx.execute("DELETE FROM saved_verses WHERE verse_title = ?1", [title])?;
+    }
+    // New verses go after everything saved, here or elsewhere
+    for verse in saved.verses.iter().filter(|v| !before.contains_key(v.verse_title.as_str())) {
+        tx.execute(
+            "INSERT OR IGNORE INTO saved_verses (position, verse_title, verse)
+             VALUES ((SELECT COALESCE(MAX(position), -1) + 1 FROM saved_verses), ?1, ?2)",
+            params![verse.verse_title, serde_json::to_string(verse)?],
+        )?;
+    }
+    let titles: BTreeSet<&String> = loaded.tags.keys().chain(saved.tags.keys()).collect();
+    for title in titles {
+        let tags = saved.tags.get(title).map(Vec::as_slice).unwrap_or_default();
+        if loaded.tags.get(title).map(Vec::as_slice).unwrap_or_default() == tags {
+            continue;
+        }
+        tx.execute("DELETE FROM verse_tags WHERE verse_title = ?1", [title])?;
+        for (i, tag) in tags.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO verse_tags (verse_title, position, tag) VALUES (?1, ?2, ?3)",
+                params![title, i, tag],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Conversations are told apart by when they were archived
+fn update_conversations(tx: &Transaction, loaded: &ConversationHistory, history: &ConversationHistory) -> Result<()> {
+    let before: HashMap<u64, &Conversation> = loaded.conversations.iter().map(|c| (c.timestamp, c)).collect();
+    let after: HashMap<u64, &Conversation> = history.conversations.iter().map(|c| (c.timestamp, c)).collect();
+    for timestamp in before.keys().filter(|t| !after.contains_key(*t)) {
+        tx.execute("DELETE FROM conversations WHERE timestamp = ?1", [*timestamp as i64])?;
+    }
+    for conversation in &history.conversations {
+        let messages = serde_json::to_string(&conversation.messages)?;
+        if let Some(earlier) = before.get(&conversation.timestamp) {
+            if serde_json::to_string(&earlier.messages)? == messages {
+                continue;
+            }
+        }
+        let timestamp = conversation.timestamp as i64;
+        if tx.execute("UPDATE conversations SET messages = ?2 WHERE timestamp = ?1", params![timestamp, messages])? == 0 {
+            tx.execute("INSERT INTO conversations (timestamp, messages) VALUES (?1, ?2)", params![timestamp, messages])?;
+        }
+    }
+    Ok(())
+}
+
+fn update_memorize_history(tx: &Transaction, loaded: &MemorizeHistory, history: &MemorizeHistory) -> Result<()> {
+    // Attempts can repeat, so count them rather than compare sets
+    let mut counts: HashMap<(&str, u64, u32), i64> = HashMap::new();
+    for (title, attempt) in loaded.timeline() {
+        *counts.entry((title, attempt.timestamp, attempt.accuracy.to_bits())).or_default() -= 1;
+    }
+    // Oldest first, so row order is attempt order
+    for (title, attempt) in history.timeline() {
+        let count = counts.entry((title, attempt.timestamp, attempt.accuracy.to_bits())).or_default();
+        *count += 1;
+        if *count > 0 {
+            tx.execute(
+                "INSERT INTO memorize_attempts (verse_title, timestamp, accuracy) VALUES (?1, ?2, ?3)",
+                params![title, attempt.timestamp as i64, attempt.accuracy as f64],
+            )?;
+        }
+    }
+    for ((title, timestamp, accuracy), count) in counts {
+        for _ in 0..-count {
+            tx.execute(
+                "DELETE FROM memorize_attempts WHERE rowid = (SELECT rowid FROM memorize_attempts
+                 WHERE verse_title = ?1 AND timestamp = ?2 AND accuracy = ?3 LIMIT 1)",
+                params![title, timestamp as i64, f32::from_bits(accuracy) as f64],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn update_cfm_progress(tx: &Transaction, loaded: &CfmProgress, progress: &CfmProgress) -> Result<()> {
+    let readings = |progress: &CfmProgress| -> BTreeSet<(String, String)> {
+        progress.weeks.iter().flat_map(|(week, labels)| labels.iter().map(move |label| (week.to_string(), label.clone()))).collect()
+    };
+    let (before, after) = (readings(loaded), readings(progress));
+    for (week, label) in before.difference(&after) {
+        tx.execute("DELETE FROM reading_progress WHERE week = ?1 AND label = ?2", params![week, label])?;
+    }
+    for (week, label) in after.difference(&before) {
+        tx.execute("INSERT OR IGNORE INTO reading_progress (week, label) VALUES (?1, ?2)", params![week, label])?;
+    }
+    Ok(())
+}
+
+fn mark_imported(path: &Path) -> Result<()> {
+    tracing::info!("Imported {} into study.db", path.display());
+    let mut imported = path.as_os_str().to_owned();
+    imported.push(".imported");
+    fs::rename(path, imported)?;
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{ChatMessage, ChatRole};
+
+    fn verse(number: i32) -> Scripture {
+        Scripture::test("Alma", 32, number).text("And now as I said concerning faith")
+    }
+
+    #[test]
+    fn test_new_store_imports_json_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut saved = SavedScriptures::default();
+        saved.add([verse(27), verse(21)]);
+        saved.set_tags("Alma 32:21", vec!["faith".to_string(), "seed".to_string()]);
+        saved.save_to(&dir.path().join(SAVED_FILE)).unwrap();
+        let mut history = MemorizeHistory::new();
+        history.record("Alma 32:21", 0.5);
+        history.record("Alma 32:21", 0.75);
+        history.save_to(&dir.path().join(MEMORIZE_FILE)).unwrap();
+
+        let path = dir.path().join(Store::FILE_NAME);
+        let store = Store::open_at(&path).unwrap();
+        assert_eq!(store.version().unwrap(), MIGRATIONS.len());
+        let loaded = store.saved().unwrap();
+        let titles: Vec<&str> = loaded.verses.iter().map(|v| v.verse_title.as_str()).collect();
+        assert_eq!(titles, ["Alma 32:27", "Alma 32:21"]);
+        assert_eq!(loaded.tags["Alma 32:21"], ["faith", "seed"]);
+        assert_eq!(loaded.verses[1].scripture_text, "And now as I said concerning faith");
+        let stats = store.memorize_history().unwrap().stats("Alma 32:21").unwrap();
+        assert_eq!((stats.attempts, stats.last), (2, 0.75));
+        assert!(dir.path().join("saved_scriptures.json.imported").exists());
+
+        // Reopening finds the data and doesn't import again
+        drop(store);
+        saved.save_to(&dir.path().join(SAVED_FILE)).unwrap();
+        let mut store = Store::open_at(&path).unwrap();
+        store.save_saved(&loaded, &SavedScriptures::default()).unwrap();
+        drop(store);
+        assert!(Store::open_at(&path).unwrap().saved().unwrap().verses.is_empty());
+    }
+
+    #[test]
+    fn test_saving_keeps_what_another_program_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(Store::FILE_NAME);
+        let mut tui = Store::open_at(&path).unwrap();
+        let mut cli = Store::open_at(&path).unwrap();
+        let mut saved = SavedScriptures::default();
+        saved.add([verse(21)]);
+        tui.save_saved(&SavedScriptures::default(), &saved).unwrap();
+
+        // Both load, then each changes something different
+        let tui_loaded = tui.saved().unwrap();
+        let cli_loaded = cli.saved().unwrap();
+        let mut theirs = cli_loaded.clone();
+        theirs.add([verse(27)]);
+        theirs.set_tags("Alma 32:21", vec!["faith".to_string()]);
+        cli.save_saved(&cli_loaded, &theirs).unwrap();
+        let mut ours = tui_loaded.clone();
+        ours.add([verse(28)]);
+        tui.save_saved(&tui_loaded, &ours).unwrap();
+
+        let both = tui.saved().unwrap();
+        let titles: Vec<&str> = both.verses.iter().map(|v| v.verse_title.as_str()).collect();
+        assert_eq!(titles, ["Alma 32:21", "Alma 32:27", "Alma 32:28"]);
+        assert_eq!(both.tags["Alma 32:21"], ["faith"]);
+
+        let mut history = MemorizeHistory::new();
+        history.record("Alma 32:21", 0.5);
+        cli.save_memorize_history(&MemorizeHistory::new(), &history).unwrap();
+        let mut ours = MemorizeHistory::new();
+        ours.record("Alma 32:27", 0.9);
+        tui.save_memorize_history(&MemorizeHistory::new(), &ours).unwrap();
+        assert_eq!(tui.memorize_history().unwrap().timeline().len(), 2);
+        tui.save_memorize_history(&ours, &MemorizeHistory::new()).unwrap();
+        assert_eq!(tui.memorize_history().unwrap().stats("Alma 32:21").unwrap().attempts, 1);
+        assert!(tui.memorize_history().unwrap().stats("Alma 32:27").is_none());
+    }
+
+    #[test]
+    fn test_conversations_progress_and_highlights_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = Store::open_at(&dir.path().join(Store::FILE_NAME)).unwrap();
+
+        let mut history = ConversationHistory::default();
+        history.archive(vec![ChatMessage { role: ChatRole::User, content: "What is faith?".to_string() }]);
+        store.save_conversations(&ConversationHistory::default(), &history).unwrap();
+        assert_eq!(store.conversations().unwrap().conversations[0].title(), "What is faith?");
+
+        let mut progress = CfmProgress::default();
+        let week = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        progress.weeks.entry(week).or_default().insert("Genesis 1".to_string());
+        store.save_cfm_progress(&CfmProgress::default(), &progress).unwrap();
+        assert_eq!(store.cfm_progress().unwrap().weeks, progress.weeks);
+
+        store.set_highlight("Alma 32:21", Some("yellow")).unwrap();
+        store.set_highlight("Alma 32:21", Some("green")).unwrap();
+        assert_eq!(store.highlight("Alma 32:21").unwrap().as_deref(), Some("green"));
+        assert!(store.set_highlight("Alma 32:21", Some("plaid")).is_err());
+        store.set_highlight("Alma 32:21", None).unwrap();
+        assert!(store.highlights().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,594 @@
+//! Syncing study data through a folder
+//!
+//! With `sync_dir` set, the study data is also kept as plain text in that
+//! folder, for Dropbox, Syncthing, or a git repository to carry between
+//! computers: a JSON line per saved verse (`saved.jsonl`), highlight,
+//! conversation, memorization attempt, and finished reading, and the journal
+//! as its Markdown files (`journal/`).
+//!
+//! Each sync is a three-way merge of what's here, what's in the folder, and
+//! what both were after the last sync (kept in the state directory). A record
+//! changed or removed on one side only takes that side's change, so deletions
+//! follow too. A record changed differently on both sides is a conflict: this
+//! computer's version is kept, except that a journal entry edited on both
+//! keeps both, the folder's under the next free time. The copies Dropbox and
+//! Syncthing make of a file changed in two places (`saved.sync-conflict-….jsonl`,
+//! `saved (conflicted copy).jsonl`) are read as part of the folder and then
+//! removed, and git's conflict markers are skipped, so both sides' lines count.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backup::{free_name, Backup};
+use crate::error::{EscriturasError, Result};
+use crate::journal::{Journal, FILE_DATE_FORMAT};
+use crate::scripture::Scripture;
+use crate::state::Conversation;
+use crate::store::{Store, HIGHLIGHT_COLORS};
+
+/// What both sides were after the last sync, in the state directory
+const BASE_FILE: &str = "sync_base.json";
+
+const SAVED: &str = "saved";
+const HIGHLIGHTS: &str = "highlights";
+const CONVERSATIONS: &str = "conversations";
+const ATTEMPTS: &str = "memorize";
+const READINGS: &str = "progress";
+const JOURNAL: &str = "journal";
+
+#[derive(Serialize, Deserialize)]
+struct SavedLine {
+    verse: Scripture,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HighlightLine {
+    verse: String,
+    color: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AttemptLine {
+    verse: String,
+    timestamp: u64,
+    accuracy: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReadingLine {
+    week: NaiveDate,
+    label: String,
+}
+
+/// A kind of line, and what identifies the record it holds
+trait Line: Serialize + DeserializeOwned {
+    /// None for a line that parses but can't be used
+    fn key(&self) -> Option<String>;
+}
+
+impl Line for SavedLine {
+    fn key(&self) -> Option<String> {
+        Some(self.verse.verse_title.clone())
+    }
+}
+
+impl Line for HighlightLine {
+    fn key(&self) -> Option<String> {
+        HIGHLIGHT_COLORS.contains(&self.color.as_str()).then(|| self.verse.clone())
+    }
+}
+
+impl Line for Conversation {
+    fn key(&self) -> Option<String> {
+        Some(format!("{:020}", self.timestamp))
+    }
+}
+
+/// Attempts and readings are only ever added or removed, so the line is its own key
+impl Line for AttemptLine {
+    fn key(&self) -> Option<String> {
+        serde_json::to_string(self).ok()
+    }
+}
+
+impl Line for ReadingLine {
+    fn key(&self) -> Option<String> {
+        serde_json::to_string(self).ok()
+    }
+}
+
+/// The study data as lines of JSON by key, and journal entries by file name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Records {
+    saved: BTreeMap<String, String>,
+    /// Saved verses' keys in saved order
+    #[serde(skip)]
+    order: Vec<String>,
+    highlights: BTreeMap<String, String>,
+    conversations: BTreeMap<String, String>,
+    attempts: BTreeMap<String, String>,
+    readings: BTreeMap<String, String>,
+    journal: BTreeMap<String, String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Base {
+    /// The folder synced with, since another folder shares nothing with this one
+    dir: PathBuf,
+    records: Records,
+}
+
+/// What a sync changed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncSummary {
+    /// Records added, changed, or removed here
+    pub pulled: usize,
+    /// Records added, changed, or removed in the folder
+    pub pushed: usize,
+    /// Records changed differently on both sides, as "highlight Alma 32:21"
+    pub conflicts: Vec<String>,
+    /// Lines in the folder that couldn't be read
+    pub skipped: usize,
+}
+
+impl SyncSummary {
+    /// "2 changes in, 1 out", or "up to date"
+    pub fn describe(&self) -> String {
+        let mut out = match (self.pulled, self.pushed) {
+            (0, 0) => "up to date".to_string(),
+            (pulled, pushed) => format!("{} {} in, {} out", pulled, if pulled == 1 { "change" } else { "changes" }, pushed),
+        };
+        if self.skipped > 0 {
+            out.push_str(&format!(", {} unreadable {} skipped", self.skipped, if self.skipped == 1 { "line" } else { "lines" }));
+        }
+        out
+    }
+}
+
+/// Sync the study data store and journal with `dir`
+pub fn sync(dir: &Path) -> Result<SyncSummary> {
+    let mut store = Store::open()?;
+    let journal = Journal::load()?;
+    sync_with(&mut store, &journal, dir, &crate::paths::state_dir()?.join(BASE_FILE))
+}
+
+fn sync_with(store: &mut Store, journal: &Journal, dir: &Path, base_path: &Path) -> Result<SyncSummary> {
+    fs::create_dir_all(dir).map_err(|e| EscriturasError::io(format!("Could not open sync folder {}", dir.display()), e))?;
+    let local = Records::from_backup(&Backup::collect(store, journal)?)?;
+    let folder = Folder::read(dir)?;
+
+    // An empty folder (a new one, or one that isn't mounted) is a first sync,
+    // rather than everything having been deleted from it
+    let base = match fs::read_to_string(base_path) {
+        Ok(content) if !folder.is_empty() => {
+            let base: Base = serde_json::from_str(&content)?;
+            if base.dir == dir { base.records } else { Records::default() }
+        }
+        _ => Records::default(),
+    };
+
+    let mut conflicts = Vec::new();
+    let mut merged = Records::merge(&base, &local, &folder.records, &mut conflicts);
+    for (name, content) in &folder.journal_copies {
+        if !merged.journal.values().any(|c| c == content) {
+            let name = free_name(name, &merged.journal);
+            merged.journal.insert(name, content.clone());
+        }
+    }
+
+    let summary = SyncSummary {
+        pulled: local.changes(&merged),
+        pushed: folder.records.changes(&merged) + folder.copies.len(),
+        conflicts,
+        skipped: folder.skipped,
+    };
+    if merged != local {
+        merged.apply(store, journal, &local)?;
+    }
+    merged.write(dir, &folder)?;
+
+    let base = Base { dir: dir.to_path_buf(), records: merged };
+    if let Some(parent) = base_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(base_path, serde_json::to_string(&base)?)?;
+    tracing::info!("Synced with {}: {}", dir.display(), summary.describe());
+    Ok(summary)
+}
+
+/// Three-way merge of one kind of record, keyed alike on each side
+fn merge_maps(
+    kind: &str,
+    base: &BTreeMap<String, String>,
+    local: &BTreeMap<String, String>,
+    remote: &BTreeMap<String, String>,
+    conflicts: &mut Vec<String>,
+) -> BTreeMap<String, String> {
+    let keys: BTreeSet<&String> = base.keys().chain(local.keys()).chain(remote.keys()).collect();
+    let mut merged = BTreeMap::new();
+    for key in keys {
+        let (b, l, r) = (base.get(key), local.get(key), remote.get(key));
+        let value = if l == r || b == r {
+            l
+        } else if b == l {
+            r
+        } else {
+            conflicts.push(format!("{} {}", kind, key));
+            l.or(r)
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    merged
+}
+
+impl Records {
+    fn from_backup(backup: &Backup) -> Result<Self> {
+        let mut records = Self::default();
+        for verse in &backup.saved.verses {
+            let tags = backup.saved.tags.get(&verse.verse_title).cloned().unwrap_or_default();
+            insert(&mut records.saved, &SavedLine { verse: verse.clone(), tags })?;
+            records.order.push(verse.verse_title.clone());
+        }
+        for (verse, color) in &backup.highlights {
+            insert(&mut records.highlights, &HighlightLine { verse: verse.clone(), color: color.clone() })?;
+        }
+        for conversation in &backup.conversations.conversations {
+            insert(&mut records.conversations, conversation)?;
+        }
+        for (verse, attempt) in backup.memorize.timeline() {
+            let line = AttemptLine { verse: verse.to_string(), timestamp: attempt.timestamp, accuracy: attempt.accuracy };
+            insert(&mut records.attempts, &line)?;
+        }
+        for (week, labels) in &backup.progress.weeks {
+            for label in labels {
+                insert(&mut records.readings, &ReadingLine { week: *week, label: label.clone() })?;
+            }
+        }
+        records.journal = backup.journal.clone();
+        Ok(records)
+    }
+
+    fn to_backup(&self) -> Result<Backup> {
+        let mut backup = Backup::default();
+        for key in &self.order {
+            let line: SavedLine = serde_json::from_str(&self.saved[key])?;
+            backup.saved.add([line.verse]);
+            backup.saved.set_tags(key, line.tags);
+        }
+        for value in self.highlights.values() {
+            let line: HighlightLine = serde_json::from_str(value)?;
+            backup.highlights.insert(line.verse, line.color);
+        }
+        for value in self.conversations.values() {
+            backup.conversations.conversations.push(serde_json::from_str(value)?);
+        }
+        for value in self.attempts.values() {
+            let line: AttemptLine = serde_json::from_str(value)?;
+            let attempts = backup.memorize.verses.entry(line.verse).or_default();
+            attempts.push(crate::memorize::MemorizeAttempt { timestamp: line.timestamp, accuracy: line.accuracy });
+            attempts.sort_by_key(|a| a.timestamp);
+        }
+        for value in self.readings.values() {
+            let line: ReadingLine = serde_json::from_str(value)?;
+            backup.progress.weeks.entry(line.week).or_default().insert(line.label);
+        }
+        backup.journal = self.journal.clone();
+        Ok(backup)
+    }
+
+    fn merge(base: &Self, local: &Self, remote: &Self, conflicts: &mut Vec<String>) -> Self {
+        let mut journal_conflicts = Vec::new();
+        let mut merged = Self {
+            saved: merge_maps("saved verse", &base.saved, &local.saved, &remote.saved, conflicts),
+            order: Vec::new(),
+            highlights: merge_maps("highlight", &base.highlights, &local.highlights, &remote.highlights, conflicts),
+            conversations: merge_maps("conversation", &base.conversations, &local.conversations, &remote.conversations, conflicts),
+            attempts: merge_maps("attempt", &base.attempts, &local.attempts, &remote.attempts, conflicts),
+            readings: merge_maps("reading", &base.readings, &local.readings, &remote.readings, conflicts),
+            journal: merge_maps("journal", &base.journal, &local.journal, &remote.journal, &mut journal_conflicts),
+        };
+        // Both versions of a journal entry are kept
+        for conflict in journal_conflicts {
+            let name = conflict.trim_start_matches("journal ");
+            if let Some(theirs) = remote.journal.get(name).filter(|c| Some(*c) != merged.journal.get(name)) {
+                let copy = free_name(name, &merged.journal);
+                merged.journal.insert(copy, theirs.clone());
+            }
+            conflicts.push(conflict);
+        }
+        let mut order: Vec<String> = local.order.iter().chain(&remote.order).cloned().collect();
+        order.extend(merged.saved.keys().cloned());
+        for key in order {
+            if merged.saved.contains_key(&key) && !merged.order.contains(&key) {
+                merged.order.push(key);
+            }
+        }
+        merged
+    }
+
+    /// How many records differ from `other`
+    fn changes(&self, other: &Self) -> usize {
+        fn differing(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> usize {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            keys.into_iter().filter(|k| a.get(*k) != b.get(*k)).count()
+        }
+        differing(&self.saved, &other.saved)
+            + differing(&self.highlights, &other.highlights)
+            + differing(&self.conversations, &other.conversations)
+            + differing(&self.attempts, &other.attempts)
+            + differing(&self.readings, &other.readings)
+            + differing(&self.journal, &other.journal)
+    }
+
+    /// Make the store and journal match these records, given what they hold now
+    fn apply(&self, store: &mut Store, journal: &Journal, local: &Self) -> Result<()> {
+        let backup = self.to_backup()?;
+        let loaded = local.to_backup()?;
+        store.save_saved(&loaded.saved, &backup.saved)?;
+        store.save_conversations(&loaded.conversations, &backup.conversations)?;
+        store.save_memorize_history(&loaded.memorize, &backup.memorize)?;
+        store.save_cfm_progress(&loaded.progress, &backup.progress)?;
+        for verse in local.highlights.keys().filter(|v| !backup.highlights.contains_key(*v)) {
+            store.set_highlight(verse, None)?;
+        }
+        for (verse, color) in &backup.highlights {
+            if local.highlights.get(verse) != self.highlights.get(verse) {
+                store.set_highlight(verse, Some(color))?;
+            }
+        }
+        write_journal(&journal.dir, &local.journal, &self.journal)
+    }
+
+    /// Write the records to the folder, leaving files that haven't changed alone
+    fn write(&self, dir: &Path, folder: &Folder) -> Result<()> {
+        let saved: Vec<&String> = self.order.iter().map(|k| &self.saved[k]).collect();
+        write_lines(dir, SAVED, saved)?;
+        write_lines(dir, HIGHLIGHTS, self.highlights.values().collect())?;
+        write_lines(dir, CONVERSATIONS, self.conversations.values().collect())?;
+        write_lines(dir, ATTEMPTS, self.attempts.values().collect())?;
+        write_lines(dir, READINGS, self.readings.values().collect())?;
+        write_journal(&dir.join(JOURNAL), &folder.records.journal, &self.journal)?;
+        for copy in &folder.copies {
+            fs::remove_file(copy)?;
+        }
+        Ok(())
+    }
+}
+
+fn insert<T: Line>(map: &mut BTreeMap<String, String>, line: &T) -> Result<()> {
+    if let Some(key) = line.key() {
+        map.insert(key, serde_json::to_string(line)?);
+    }
+    Ok(())
+}
+
+fn write_lines(dir: &Path, name: &str, lines: Vec<&String>) -> Result<()> {
+    let path = dir.join(format!("{}.jsonl", name));
+    let content: String = lines.into_iter().map(|l| format!("{}\n", l)).collect();
+    if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+        fs::write(&path, content)?;
+    }
+    Ok(())
+}
+
+/// Write entries that are new or changed since `before`, and remove those that are gone
+fn write_journal(dir: &Path, before: &BTreeMap<String, String>, after: &BTreeMap<String, String>) -> Result<()> {
+    for name in before.keys().filter(|n| !after.contains_key(*n)) {
+        let path = dir.join(name);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+    for (name, content) in after {
+        if before.get(name) != Some(content) {
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(name), content)?;
+        }
+    }
+    Ok(())
+}
+
+/// What the sync folder holds
+#[derive(Default)]
+struct Folder {
+    records: Records,
+    /// Conflicted copies, to remove once they've been merged
+    copies: Vec<PathBuf>,
+    /// Conflicted copies of journal entries, by the name of the entry they're a copy of
+    journal_copies: Vec<(String, String)>,
+    skipped: usize,
+}
+
+impl Folder {
+    fn read(dir: &Path) -> Result<Self> {
+        let mut folder = Self::default();
+        let mut order = Vec::new();
+        folder.read_lines::<SavedLine>(dir, SAVED, &mut |r| &mut r.saved, Some(&mut order))?;
+        folder.read_lines::<HighlightLine>(dir, HIGHLIGHTS, &mut |r| &mut r.highlights, None)?;
+        folder.read_lines::<Conversation>(dir, CONVERSATIONS, &mut |r| &mut r.conversations, None)?;
+        folder.read_lines::<AttemptLine>(dir, ATTEMPTS, &mut |r| &mut r.attempts, None)?;
+        folder.read_lines::<ReadingLine>(dir, READINGS, &mut |r| &mut r.readings, None)?;
+        folder.records.order = order;
+
+        let journal = dir.join(JOURNAL);
+        if journal.is_dir() {
+            for entry in fs::read_dir(&journal)? {
+                let path = entry?.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                    continue;
+                };
+                let Some(stem) = name.strip_suffix(".md") else {
+                    continue;
+                };
+                let content = fs::read_to_string(&path)?;
+                let dated = |s: &str| NaiveDateTime::parse_from_str(s, FILE_DATE_FORMAT).is_ok();
+                if dated(stem) {
+                    folder.records.journal.insert(name, content);
+                } else if let Some(original) = stem.get(..17).filter(|s| dated(s)) {
+                    folder.journal_copies.push((format!("{}.md", original), content));
+                    folder.copies.push(path);
+                }
+            }
+        }
+        Ok(folder)
+    }
+
+    /// Lines from `<name>.jsonl` and its conflicted copies, the file itself first
+    fn read_lines<T: Line>(
+        &mut self,
+        dir: &Path,
+        name: &str,
+        map: &mut dyn FnMut(&mut Records) -> &mut BTreeMap<String, String>,
+        mut order: Option<&mut Vec<String>>,
+    ) -> Result<()> {
+        let file = format!("{}.jsonl", name);
+        let mut paths = vec![dir.join(&file)];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(other) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if other != file && other.starts_with(name) && other.ends_with(".jsonl") {
+                self.copies.push(path.clone());
+                paths.push(path);
+            }
+        }
+        for path in paths {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            for line in content.lines().map(str::trim) {
+                if line.is_empty() || ["<<<<<<<", "|||||||", "=======", ">>>>>>>"].iter().any(|m| line.starts_with(m)) {
+                    continue;
+                }
+                let Some(parsed) = serde_json::from_str::<T>(line).ok().and_then(|l| Some((l.key()?, l))) else {
+                    self.skipped += 1;
+                    continue;
+                };
+                let (key, parsed) = parsed;
+                let records = map(&mut self.records);
+                if !records.contains_key(&key) {
+                    records.insert(key.clone(), serde_json::to_string(&parsed)?);
+                    if let Some(order) = order.as_deref_mut() {
+                        order.push(key);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// No study data at all, as in a new folder
+    fn is_empty(&self) -> bool {
+        self.records == Records::default() && self.journal_copies.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::SavedScriptures;
+    use tempfile::tempdir;
+
+    fn verse(number: i32) -> Scripture {
+        Scripture::test("Ether", 12, number).text("And if men come unto me I will show unto them their weakness")
+    }
+
+    /// A computer: its store, journal, and sync base
+    struct Device {
+        store: Store,
+        journal: PathBuf,
+        base: PathBuf,
+    }
+
+    impl Device {
+        fn new(root: &Path, name: &str) -> Self {
+            let dir = root.join(name);
+            Self {
+                store: Store::open_at(&dir.join("study.db")).unwrap(),
+                journal: dir.join("journal"),
+                base: dir.join(BASE_FILE),
+            }
+        }
+
+        fn sync(&mut self, folder: &Path) -> SyncSummary {
+            let journal = Journal::load_from(&self.journal).unwrap();
+            sync_with(&mut self.store, &journal, folder, &self.base).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_changes_and_deletions_follow_between_computers() {
+        let root = tempdir().unwrap();
+        let folder = root.path().join("Dropbox/escrituras");
+        let (mut laptop, mut desktop) = (Device::new(root.path(), "laptop"), Device::new(root.path(), "desktop"));
+
+        let mut saved = SavedScriptures::default();
+        saved.add([verse(27), verse(6)]);
+        laptop.store.save_saved(&SavedScriptures::default(), &saved).unwrap();
+        laptop.store.set_highlight("Ether 12:27", Some("yellow")).unwrap();
+        assert_eq!(laptop.sync(&folder).pushed, 3);
+        assert!(fs::read_to_string(folder.join("saved.jsonl")).unwrap().lines().count() == 2);
+
+        assert_eq!(desktop.sync(&folder).pulled, 3);
+        let titles: Vec<String> = desktop.store.saved().unwrap().verses.into_iter().map(|v| v.verse_title).collect();
+        assert_eq!(titles, ["Ether 12:27", "Ether 12:6"]);
+
+        // Removed on the desktop, recolored on the laptop: both changes arrive
+        let loaded = desktop.store.saved().unwrap();
+        let mut saved = loaded.clone();
+        saved.remove("Ether 12:6");
+        desktop.store.save_saved(&loaded, &saved).unwrap();
+        desktop.sync(&folder);
+        laptop.store.set_highlight("Ether 12:27", Some("green")).unwrap();
+        let summary = laptop.sync(&folder);
+        assert_eq!((summary.pulled, summary.pushed, summary.conflicts.len()), (1, 1, 0));
+        assert_eq!(laptop.store.saved().unwrap().verses.len(), 1);
+        desktop.sync(&folder);
+        assert_eq!(desktop.store.highlight("Ether 12:27").unwrap().as_deref(), Some("green"));
+
+        // Nothing changed, nothing written
+        assert_eq!(desktop.sync(&folder), SyncSummary::default());
+    }
+
+    #[test]
+    fn test_conflicts_keep_this_computers_version_and_both_journal_entries() {
+        let root = tempdir().unwrap();
+        let folder = root.path().join("sync");
+        let (mut laptop, mut desktop) = (Device::new(root.path(), "laptop"), Device::new(root.path(), "desktop"));
+        Journal::load_from(&laptop.journal).unwrap().add_note(&[verse(27)], "Weakness").unwrap();
+        laptop.sync(&folder);
+        desktop.sync(&folder);
+
+        let name = fs::read_dir(folder.join(JOURNAL)).unwrap().next().unwrap().unwrap().file_name();
+        fs::write(laptop.journal.join(&name), "laptop's words").unwrap();
+        fs::write(desktop.journal.join(&name), "desktop's words").unwrap();
+        laptop.store.set_highlight("Ether 12:27", Some("blue")).unwrap();
+        desktop.store.set_highlight("Ether 12:27", Some("pink")).unwrap();
+        laptop.sync(&folder);
+
+        // Syncthing's copy of a file changed in two places is merged and removed
+        fs::write(
+            folder.join("progress.sync-conflict-20261015-101010-ABCDEFG.jsonl"),
+            "{\"week\":\"2026-01-05\",\"label\":\"Genesis 1\"}\nnot json\n",
+        )
+        .unwrap();
+        let summary = desktop.sync(&folder);
+        assert_eq!(summary.conflicts, ["highlight Ether 12:27".to_string(), format!("journal {}", name.to_string_lossy())]);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(desktop.store.highlight("Ether 12:27").unwrap().as_deref(), Some("pink"));
+        let notes: BTreeSet<String> =
+            Journal::load_from(&desktop.journal).unwrap().entries.into_iter().map(|e| e.content).collect();
+        assert_eq!(notes, BTreeSet::from(["desktop's words".to_string(), "laptop's words".to_string()]));
+        assert_eq!(desktop.store.cfm_progress().unwrap().weeks.len(), 1);
+        assert_eq!(fs::read_dir(&folder).unwrap().count(), 6);
+    }
+}
@@ -0,0 +1,268 @@
+//! General Conference talks
+//!
+//! An optional local corpus in the data directory: `talks.json` is an array of
+//! talks (speaker, year, month, title, paragraphs), and `talk_embeddings.npy`, if
+//! present, holds one embedding row per paragraph in corpus order for semantic
+//! search with the same model as the scriptures.
+
+use ndarray::Array2;
+use ndarray_npy::ReadNpyExt;
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::embeddings::cosine_similarity;
+use crate::error::{EscriturasError, Result};
+use crate::scripture::{stem_word, Scripture};
+
+/// `volume_title` of talk paragraphs saved alongside scriptures
+pub const TALK_VOLUME: &str = "General Conference";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Talk {
+    pub speaker: String,
+    pub year: i32,
+    /// "April" or "October"
+    #[serde(default)]
+    pub month: String,
+    pub title: String,
+    pub paragraphs: Vec<String>,
+}
+
+impl Talk {
+    /// "April 2020", or just the year when the month is unknown
+    pub fn session(&self) -> String {
+        if self.month.is_empty() {
+            self.year.to_string()
+        } else {
+            format!("{} {}", self.month, self.year)
+        }
+    }
+
+    /// A paragraph in the shape of a verse, so it can be saved and sent to the AI
+    /// with scriptures. Paragraph numbers are 1-based in the title.
+    pub fn paragraph_as_scripture(&self, index: usize) -> Option<Scripture> {
+        let text = self.paragraphs.get(index)?;
+        let number = index as i32 + 1;
+        Some(Scripture {
+            volume_title: TALK_VOLUME.into(),
+            book_title: self.title.as_str().into(),
+            book_short_title: self.title.as_str().into(),
+            chapter_number: self.year,
+            verse_number: number,
+            verse_title: format!("{}, \"{}\" ({}) ¶{}", self.speaker, self.title, self.session(), number),
+            verse_short_title: format!("{} ¶{}", self.speaker, number),
+            scripture_text: text.clone(),
+        })
+    }
+}
+
+/// A matching paragraph; `score` is the similarity for semantic matches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TalkHit {
+    pub talk: usize,
+    pub paragraph: usize,
+    pub score: Option<f32>,
+}
+
+pub struct TalkDb {
+    talks: Vec<Talk>,
+    embeddings: Option<Array2<f32>>,
+    // Stemmed words of each paragraph, built on first search
+    stems: OnceLock<Vec<Vec<HashSet<String>>>>,
+}
+
+impl TalkDb {
+    pub const FILE_NAME: &'static str = "talks.json";
+    pub const EMBEDDINGS_FILE: &'static str = "talk_embeddings.npy";
+
+    /// Load `talks.json`, plus `talk_embeddings.npy` when it matches the paragraphs
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| EscriturasError::DataNotFound(format!("Failed to open talks {:?}: {}", path, e)))?;
+        let mut db = Self::from_talks(serde_json::from_str(&content)?);
+
+        let embeddings_path = data_dir.join(Self::EMBEDDINGS_FILE);
+        if embeddings_path.exists() {
+            let embeddings = Array2::<f32>::read_npy(File::open(&embeddings_path)?)
+                .map_err(|e| EscriturasError::ParseError(format!("Failed to read .npy file: {}", e)))?;
+            let paragraphs: usize = db.talks.iter().map(|t| t.paragraphs.len()).sum();
+            if embeddings.nrows() != paragraphs {
+                return Err(EscriturasError::ParseError(format!(
+                    "Talk embeddings count ({}) doesn't match paragraph count ({})",
+                    embeddings.nrows(),
+                    paragraphs
+                )));
+            }
+            db.embeddings = Some(embeddings);
+        }
+        Ok(db)
+    }
+
+    pub fn from_talks(talks: Vec<Talk>) -> Self {
+        Self { talks, embeddings: None, stems: OnceLock::new() }
+    }
+
+    pub fn talks(&self) -> &[Talk] {
+        &self.talks
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Talk> {
+        self.talks.get(index)
+    }
+
+    pub fn has_embeddings(&self) -> bool {
+        self.embeddings.is_some()
+    }
+
+    /// Every speaker, alphabetically
+    pub fn speakers(&self) -> Vec<String> {
+        let mut speakers: Vec<String> = self.talks.iter().map(|t| t.speaker.clone()).collect();
+        speakers.sort();
+        speakers.dedup();
+        speakers
+    }
+
+    /// Years a speaker gave talks, most recent first
+    pub fn years_for(&self, speaker: &str) -> Vec<i32> {
+        let mut years: Vec<i32> = self.talks.iter().filter(|t| t.speaker == speaker).map(|t| t.year).collect();
+        years.sort_by(|a, b| b.cmp(a));
+        years.dedup();
+        years
+    }
+
+    /// Indices of a speaker's talks in a year
+    pub fn talks_for(&self, speaker: &str, year: i32) -> Vec<usize> {
+        (0..self.talks.len())
+            .filter(|&i| self.talks[i].speaker == speaker && self.talks[i].year == year)
+            .collect()
+    }
+
+    /// Paragraphs containing every (stemmed) word of the query, or whose talk
+    /// title or speaker contains the query
+    pub fn search(&self, query: &str, limit: usize) -> Vec<TalkHit> {
+        let query_lower = query.to_lowercase();
+        let stemmer = Stemmer::create(Algorithm::English);
+        let terms: Vec<String> = query_lower
+            .split_whitespace()
+            .map(|word| stem_word(&stemmer, word))
+            .filter(|s| !s.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let stems = self.stems.get_or_init(|| {
+            self.talks
+                .iter()
+                .map(|talk| {
+                    talk.paragraphs
+                        .iter()
+                        .map(|p| p.to_lowercase().split_whitespace().map(|w| stem_word(&stemmer, w)).collect())
+                        .collect()
+                })
+                .collect()
+        });
+
+        let mut hits = Vec::new();
+        for (t, talk) in self.talks.iter().enumerate() {
+            let talk_matches = talk.title.to_lowercase().contains(&query_lower)
+                || talk.speaker.to_lowercase().contains(&query_lower);
+            for (p, paragraph_stems) in stems[t].iter().enumerate() {
+                if talk_matches && p > 0 {
+                    break;
+                }
+                if talk_matches || terms.iter().all(|term| paragraph_stems.contains(term)) {
+                    hits.push(TalkHit { talk: t, paragraph: p, score: None });
+                    if hits.len() >= limit {
+                        return hits;
+                    }
+                }
+            }
+        }
+        hits
+    }
+
+    /// Paragraphs most similar to an embedded query (see `EmbeddingsDb::embed_query`)
+    pub fn semantic_search(&self, query_embedding: &[f32], limit: usize) -> Result<Vec<TalkHit>> {
+        let embeddings = self
+            .embeddings
+            .as_ref()
+            .ok_or_else(|| EscriturasError::EmbeddingsMissing(format!("No talk embeddings ({})", Self::EMBEDDINGS_FILE)))?;
+
+        let positions: Vec<(usize, usize)> = self
+            .talks
+            .iter()
+            .enumerate()
+            .flat_map(|(t, talk)| (0..talk.paragraphs.len()).map(move |p| (t, p)))
+            .collect();
+
+        let mut scores: Vec<(usize, f32)> = embeddings
+            .rows()
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| (i, cosine_similarity(&row.to_vec(), query_embedding)))
+            .collect();
+        scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scores.truncate(limit);
+
+        Ok(scores
+            .into_iter()
+            .map(|(i, score)| TalkHit { talk: positions[i].0, paragraph: positions[i].1, score: Some(score) })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn talk(speaker: &str, year: i32, title: &str, paragraphs: &[&str]) -> Talk {
+        Talk {
+            speaker: speaker.to_string(),
+            year,
+            month: "April".to_string(),
+            title: title.to_string(),
+            paragraphs: paragraphs.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn db() -> TalkDb {
+        TalkDb::from_talks(vec![
+            talk("Jeffrey R. Holland", 2013, "Lord, I Believe", &["Be as candid about your doubts.", "Hold fast to what you already know."]),
+            talk("Dieter F. Uchtdorf", 2013, "Come, Join with Us", &["Doubt your doubts before you doubt your faith."]),
+            talk("Jeffrey R. Holland", 2021, "Fear Not", &["Faith is a principle of power."]),
+        ])
+    }
+
+    #[test]
+    fn test_browse_by_speaker_and_year() {
+        let db = db();
+        assert_eq!(db.speakers(), ["Dieter F. Uchtdorf", "Jeffrey R. Holland"]);
+        assert_eq!(db.years_for("Jeffrey R. Holland"), [2021, 2013]);
+        assert_eq!(db.talks_for("Jeffrey R. Holland", 2013), [0]);
+    }
+
+    #[test]
+    fn test_keyword_search_stems_terms() {
+        let db = db();
+        let hits = db.search("doubting", 10);
+        assert_eq!(
+            hits.iter().map(|h| (h.talk, h.paragraph)).collect::<Vec<_>>(),
+            [(0, 0), (1, 0)]
+        );
+        assert!(db.search("", 10).is_empty());
+        assert!(db.semantic_search(&[1.0], 10).is_err());
+    }
+
+    #[test]
+    fn test_paragraph_as_scripture() {
+        let verse = db().talks()[1].paragraph_as_scripture(0).unwrap();
+        assert_eq!(verse.volume_title, TALK_VOLUME);
+        assert_eq!(verse.verse_title, "Dieter F. Uchtdorf, \"Come, Join with Us\" (April 2013) ¶1");
+    }
+}
@@ -0,0 +1,90 @@
+//! Startup timings
+//!
+//! `scriptures --timings` reports where a command's time went: reading and
+//! parsing the scripture JSON, building its indexes, loading embeddings and
+//! the embedding model, building the word index on the first search. Loading
+//! code marks each stage with `stage` (or `time`), which records nothing and
+//! doesn't read the clock unless timings were turned on with `enable`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STAGES: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+
+/// Record stages from now on
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A stage being timed, recorded when dropped
+pub struct Stage {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Stage {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            STAGES.lock().unwrap_or_else(|e| e.into_inner()).push((self.name, start.elapsed()));
+        }
+    }
+}
+
+/// Time a stage until the returned guard is dropped
+pub fn stage(name: &'static str) -> Stage {
+    Stage { name, start: is_enabled().then(Instant::now) }
+}
+
+/// Time a stage of work
+pub fn time<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let _stage = stage(name);
+    f()
+}
+
+/// The stages recorded so far, in the order they finished
+pub fn stages() -> Vec<(&'static str, Duration)> {
+    STAGES.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// The stages as a table, then the `total` they were part of
+pub fn report(total: Duration) -> String {
+    let stages = stages();
+    let width = stages.iter().map(|(name, _)| name.len()).chain([5]).max().unwrap_or(0);
+    let row = |name: &str, elapsed: Duration| format!("  {:<width$}  {:>8.1} ms\n", name, elapsed.as_secs_f64() * 1000.0);
+    let mut out = String::from("Timings:\n");
+    for (name, elapsed) in &stages {
+        out.push_str(&row(name, *elapsed));
+    }
+    out.push_str(&row("total", total));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stages_are_recorded_once_enabled() {
+        // Tests share the recorder, so look only for this test's stages
+        let ours = || stages().into_iter().filter(|(name, _)| name.starts_with("test ")).count();
+        time("test before", || ());
+        assert_eq!(ours(), 0);
+
+        enable();
+        assert_eq!(time("test parse", || 2 + 2), 4);
+        {
+            let _stage = stage("test build");
+        }
+        assert_eq!(ours(), 2);
+        let report = report(Duration::from_millis(1500));
+        assert!(report.contains("  test parse "));
+        let total = report.lines().last().unwrap();
+        assert!(total.trim_start().starts_with("total") && total.ends_with("1500.0 ms"), "{}", report);
+    }
+}
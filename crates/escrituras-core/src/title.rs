@@ -0,0 +1,217 @@
+//! Shared book and volume titles
+//!
+//! Every verse names its volume and book, so the scriptures hold "Book of
+//! Mormon" some 6,600 times and "Alma" 1,975 times, and indexes and search
+//! results copy them again. A `Title` is a reference-counted string: cloning
+//! one shares it, and `ScriptureDb` interns the titles of the verses it loads
+//! so each title is stored once. Two clones compare by pointer before
+//! comparing text. It reads like a `str` (it derefs to one and compares with
+//! `&str` and `String`), and serializes as a plain string.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A book or volume title, shared by everything that names it
+#[derive(Clone, Default)]
+pub struct Title(Arc<str>);
+
+impl Title {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether two titles are the same shared copy
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl Deref for Title {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Title {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Title {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Title {
+    fn from(title: &str) -> Self {
+        Self(Arc::from(title))
+    }
+}
+
+impl From<String> for Title {
+    fn from(title: String) -> Self {
+        Self(Arc::from(title))
+    }
+}
+
+impl From<&String> for Title {
+    fn from(title: &String) -> Self {
+        Self(Arc::from(title.as_str()))
+    }
+}
+
+impl From<Title> for String {
+    fn from(title: Title) -> Self {
+        title.0.to_string()
+    }
+}
+
+impl PartialEq for Title {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.0 == other.0
+    }
+}
+
+impl Eq for Title {}
+
+impl PartialEq<str> for Title {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Title {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Title {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<Title> for str {
+    fn eq(&self, other: &Title) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<Title> for &str {
+    fn eq(&self, other: &Title) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl PartialEq<Title> for String {
+    fn eq(&self, other: &Title) -> bool {
+        **self == *other.0
+    }
+}
+
+// Hashed like the text, so a `HashMap<Title, _>` can be looked up by `&str`
+impl Hash for Title {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for Title {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Title {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Debug for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl Serialize for Title {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Title {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Titles seen so far, to share one copy of each
+#[derive(Debug, Default)]
+pub struct Interner {
+    titles: HashSet<Title>,
+}
+
+impl Interner {
+    /// Replace a title with the copy seen before, or remember it
+    pub fn intern(&mut self, title: &mut Title) {
+        match self.titles.get(title.as_str()) {
+            Some(seen) => *title = seen.clone(),
+            None => {
+                self.titles.insert(title.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_title_reads_like_a_string() {
+        let alma = Title::from("Alma");
+        assert_eq!(alma, "Alma");
+        assert_eq!("Alma".to_string(), alma);
+        assert!(alma.starts_with("Al"));
+        assert_eq!(format!("{} 32", alma), "Alma 32");
+        assert_eq!(serde_json::to_string(&alma).unwrap(), "\"Alma\"");
+        assert_eq!(serde_json::from_str::<Title>("\"Alma\"").unwrap(), alma);
+
+        let chapters: HashMap<Title, i32> = HashMap::from([(alma.clone(), 63)]);
+        assert_eq!(chapters.get("Alma"), Some(&63));
+        let mut books = vec![Title::from("Ether"), alma];
+        books.sort();
+        assert_eq!(books, ["Alma", "Ether"]);
+    }
+
+    #[test]
+    fn test_interned_titles_share_one_copy() {
+        let mut interner = Interner::default();
+        let mut first = Title::from("Book of Mormon");
+        let mut second = Title::from("Book of Mormon".to_string());
+        assert!(!Title::ptr_eq(&first, &second));
+        interner.intern(&mut first);
+        interner.intern(&mut second);
+        assert!(Title::ptr_eq(&first, &second));
+        assert_eq!(first, second);
+    }
+}
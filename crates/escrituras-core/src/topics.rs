@@ -0,0 +1,477 @@
+//! Study topics
+//!
+//! A topic page gathers what the study data has to say about a gospel topic:
+//! the verses whose footnotes point to its Topical Guide entries ("TG Faith"),
+//! the verses closest to it in meaning (or, without embeddings, those that
+//! name it), and conference talks about it. `TopicIndex` builds pages from
+//! whichever of these sources are loaded and caches them in
+//! `~/.cache/escrituras/topics/`, building a page again when its sources change.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::embeddings::EmbeddingsDb;
+use crate::error::Result;
+use crate::footnotes::FootnoteDb;
+use crate::scripture::ScriptureDb;
+use crate::talks::{TalkDb, TalkHit};
+
+/// A curated study topic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Topic {
+    pub name: &'static str,
+    /// Its Topical Guide entries, as footnotes cite them ("Faith" for "TG Faith")
+    pub topical_guide: &'static [&'static str],
+    /// A sentence on what it covers, which is also what related verses are
+    /// found by
+    pub summary: &'static str,
+}
+
+/// The built-in topics, alphabetically
+pub const TOPICS: &[Topic] = &[
+    Topic {
+        name: "Agency",
+        topical_guide: &["Agency", "Accountability"],
+        summary: "The freedom to choose between good and evil, and accountability for those choices",
+    },
+    Topic {
+        name: "Atonement of Jesus Christ",
+        topical_guide: &["Jesus Christ, Atonement through", "Atone", "Redemption"],
+        summary: "Jesus Christ's suffering and sacrifice to redeem mankind from sin and death",
+    },
+    Topic {
+        name: "Baptism",
+        topical_guide: &["Baptism", "Baptism, Essential", "Baptism, Immersion"],
+        summary: "Baptism by immersion for the remission of sins, a covenant to follow Christ",
+    },
+    Topic {
+        name: "Charity",
+        topical_guide: &["Charity", "Love", "Benevolence"],
+        summary: "The pure love of Christ, shown in love and kindness toward others",
+    },
+    Topic {
+        name: "Covenants",
+        topical_guide: &["Covenants", "Abrahamic Covenant", "Vows"],
+        summary: "Sacred promises made between God and His children",
+    },
+    Topic {
+        name: "Faith",
+        topical_guide: &["Faith", "Trust in God", "Believe"],
+        summary: "Faith in the Lord Jesus Christ: belief and trust in Him, hoping for things not seen which are true",
+    },
+    Topic {
+        name: "Family",
+        topical_guide: &["Family", "Family, Children, Responsibilities toward", "Marriage, Continuing Courtship in"],
+        summary: "Husbands, wives, parents, and children, and the eternal nature of families",
+    },
+    Topic {
+        name: "Forgiveness",
+        topical_guide: &["Forgive", "Remission of Sins", "Mercy"],
+        summary: "God's forgiveness of sins, and forgiving one another",
+    },
+    Topic {
+        name: "Gathering of Israel",
+        topical_guide: &["Israel, Gathering of", "Israel, Restoration of", "Israel, Scattering of"],
+        summary: "The scattering of the house of Israel and its gathering in the last days",
+    },
+    Topic {
+        name: "Grace",
+        topical_guide: &["Grace", "God, Gifts of"],
+        summary: "The divine help and strength given through the mercy and love of Jesus Christ",
+    },
+    Topic {
+        name: "Holy Ghost",
+        topical_guide: &["Holy Ghost", "Holy Ghost, Gift of", "Holy Ghost, Mission of", "Holy Ghost, Comforter"],
+        summary: "The Holy Ghost, who testifies of truth, comforts, guides, and sanctifies",
+    },
+    Topic {
+        name: "Hope",
+        topical_guide: &["Hope", "Despair"],
+        summary: "Hope in Christ and the promise of eternal life",
+    },
+    Topic {
+        name: "Humility",
+        topical_guide: &["Humility", "Meek", "Contrite Heart", "Pride"],
+        summary: "Humility and meekness before God, and the danger of pride",
+    },
+    Topic {
+        name: "Obedience",
+        topical_guide: &["Obedience", "Commandments of God", "Walking with God"],
+        summary: "Keeping the commandments of God, and the blessings of obedience",
+    },
+    Topic {
+        name: "Plan of Salvation",
+        topical_guide: &["Salvation, Plan of", "Man, Antemortal Existence of", "Eternal Life"],
+        summary: "God's plan for His children: premortal life, mortality, death, and eternal life",
+    },
+    Topic {
+        name: "Prayer",
+        topical_guide: &["Prayer", "Pray", "Communication"],
+        summary: "Praying to Heavenly Father in the name of Jesus Christ, and receiving answers",
+    },
+    Topic {
+        name: "Priesthood",
+        topical_guide: &["Priesthood", "Priesthood, Authority", "Priesthood, Keys of", "Priesthood, Melchizedek"],
+        summary: "The power and authority of God given to act in His name",
+    },
+    Topic {
+        name: "Repentance",
+        topical_guide: &["Repent", "Forgive", "Remission of Sins"],
+        summary: "Turning from sin to God with a broken heart and a contrite spirit",
+    },
+    Topic {
+        name: "Resurrection",
+        topical_guide: &["Resurrection", "Jesus Christ, Resurrection", "Immortality"],
+        summary: "The resurrection of Jesus Christ and of all mankind, body and spirit reunited",
+    },
+    Topic {
+        name: "Revelation",
+        topical_guide: &["Revelation", "Guidance, Divine", "Inspiration", "Testimony"],
+        summary: "God making His will known through prophets and to each of His children",
+    },
+    Topic {
+        name: "Sabbath",
+        topical_guide: &["Sabbath", "Sacrament"],
+        summary: "Keeping the Sabbath day holy, worship, and the sacrament",
+    },
+    Topic {
+        name: "Scripture Study",
+        topical_guide: &["Scriptures, Study of", "Scriptures, Value of", "Word of God"],
+        summary: "Searching, feasting upon, and pondering the word of God",
+    },
+    Topic {
+        name: "Service",
+        topical_guide: &["Service", "Good Works", "Welfare"],
+        summary: "Serving God by serving others, ministering to those in need",
+    },
+    Topic {
+        name: "Temples",
+        topical_guide: &["Temple", "Temple, House of the Lord", "Ordinances"],
+        summary: "The house of the Lord and the ordinances and covenants made there",
+    },
+];
+
+impl Topic {
+    /// A topic by name in any case, or the first whose name starts with it ("atone")
+    pub fn find(name: &str) -> Option<&'static Topic> {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+        TOPICS
+            .iter()
+            .find(|t| t.name.to_lowercase() == name)
+            .or_else(|| TOPICS.iter().find(|t| t.name.to_lowercase().starts_with(&name)))
+    }
+
+    /// File name of its cached page ("plan-of-salvation.json")
+    fn cache_file_name(&self) -> String {
+        let words: Vec<&str> = self.name.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+        format!("{}.json", words.join("-").to_lowercase())
+    }
+}
+
+/// A talk paragraph about a topic
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TopicTalk {
+    pub speaker: String,
+    pub title: String,
+    /// "April 2020"
+    pub session: String,
+    /// Index of the paragraph
+    pub paragraph: usize,
+    pub text: String,
+}
+
+/// What the study data has on a topic. Verses are by title ("Alma 32:21").
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TopicPage {
+    pub topic: String,
+    /// Verses whose footnotes cite the topic's Topical Guide entries, in
+    /// scripture order
+    pub topical_guide: Vec<String>,
+    /// Other verses about the topic, closest first
+    pub related: Vec<String>,
+    /// Talks about the topic, one paragraph from each
+    pub talks: Vec<TopicTalk>,
+    /// What the page was built from, to tell when it's out of date
+    sources: String,
+}
+
+/// Builds topic pages from the scriptures and whichever study data is loaded
+pub struct TopicIndex<'a> {
+    db: &'a ScriptureDb,
+    footnotes: Option<&'a FootnoteDb>,
+    embeddings: Option<&'a mut EmbeddingsDb>,
+    talks: Option<&'a TalkDb>,
+    cache_dir: Option<PathBuf>,
+    limit: usize,
+}
+
+impl<'a> TopicIndex<'a> {
+    /// Related verses and talks listed per page, by default
+    pub const DEFAULT_LIMIT: usize = 20;
+
+    pub fn new(db: &'a ScriptureDb) -> Self {
+        Self { db, footnotes: None, embeddings: None, talks: None, cache_dir: None, limit: Self::DEFAULT_LIMIT }
+    }
+
+    /// Footnotes to find Topical Guide citations in
+    pub fn with_footnotes(mut self, footnotes: Option<&'a FootnoteDb>) -> Self {
+        self.footnotes = footnotes;
+        self
+    }
+
+    /// Embeddings to find related verses (and talks, when they're embedded
+    /// too) by meaning rather than by name
+    pub fn with_embeddings(mut self, embeddings: Option<&'a mut EmbeddingsDb>) -> Self {
+        self.embeddings = embeddings;
+        self
+    }
+
+    pub fn with_talks(mut self, talks: Option<&'a TalkDb>) -> Self {
+        self.talks = talks;
+        self
+    }
+
+    /// Where `page` caches pages; without one they're built every time
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// `~/.cache/escrituras/topics`
+    pub fn default_cache_dir() -> Result<PathBuf> {
+        Ok(crate::paths::cache_dir()?.join("topics"))
+    }
+
+    /// The cached page for a topic, or a new one (cached for next time) when
+    /// there's none from the same sources
+    pub fn page(&mut self, topic: &Topic) -> Result<TopicPage> {
+        let sources = self.sources();
+        let path = self.cache_dir.as_ref().map(|dir| dir.join(topic.cache_file_name()));
+        if let Some(page) = path.as_deref().and_then(read_cached) {
+            if page.topic == topic.name && page.sources == sources {
+                return Ok(page);
+            }
+        }
+
+        let (page, complete) = self.build_page(topic);
+        // A page missing what the embeddings couldn't find this time isn't kept
+        if let (Some(path), true) = (path, complete) {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, serde_json::to_string_pretty(&page)?)?;
+        }
+        Ok(page)
+    }
+
+    /// A new page for a topic, without the cache
+    pub fn build(&mut self, topic: &Topic) -> TopicPage {
+        self.build_page(topic).0
+    }
+
+    /// The page, and whether every source answered
+    fn build_page(&mut self, topic: &Topic) -> (TopicPage, bool) {
+        let entries: Vec<String> = topic.topical_guide.iter().map(|e| format!("tg {}", e.to_lowercase())).collect();
+        let topical_guide: Vec<String> = match self.footnotes {
+            Some(footnotes) => self
+                .db
+                .all_verses()
+                .iter()
+                .filter(|v| {
+                    footnotes
+                        .for_verse(&v.verse_title)
+                        .iter()
+                        .flat_map(|note| &note.references)
+                        .any(|r| entries.contains(&r.trim().to_lowercase()))
+                })
+                .map(|v| v.verse_title.clone())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut complete = true;
+        let seen: HashSet<&str> = topical_guide.iter().map(String::as_str).collect();
+        let query = format!("{}: {}", topic.name, topic.summary);
+        let related: Vec<String> = match self.embeddings.as_deref_mut() {
+            Some(embeddings) => match embeddings.search(&query, self.limit + seen.len()) {
+                Ok(matches) => matches.into_iter().map(|(title, _)| title).collect(),
+                Err(e) => {
+                    tracing::warn!("Couldn't find verses related to {}: {}", topic.name, e);
+                    complete = false;
+                    Vec::new()
+                }
+            },
+            None => self.db.search(topic.name, self.limit + seen.len()).into_iter().map(|v| v.verse_title.clone()).collect(),
+        };
+        let related = related.into_iter().filter(|t| !seen.contains(t.as_str())).take(self.limit).collect();
+
+        let talks = match self.talks {
+            Some(talks) => {
+                let (hits, answered) = self.find_talks(talks, topic, &query);
+                complete &= answered;
+                topic_talks(talks, hits, self.limit)
+            }
+            None => Vec::new(),
+        };
+
+        let page = TopicPage { topic: topic.name.to_string(), topical_guide, related, talks, sources: self.sources() };
+        (page, complete)
+    }
+
+    /// Talk paragraphs about the topic, by meaning when the talks are
+    /// embedded and otherwise by name; and whether the search answered
+    fn find_talks(&mut self, talks: &TalkDb, topic: &Topic, query: &str) -> (Vec<TalkHit>, bool) {
+        // Several paragraphs of a talk may match; ask for enough to fill the page with different talks
+        let limit = self.limit * 5;
+        match self.embeddings.as_deref_mut() {
+            Some(embeddings) if talks.has_embeddings() => {
+                match embeddings.embed_query(query).and_then(|q| talks.semantic_search(&q, limit)) {
+                    Ok(hits) => (hits, true),
+                    Err(e) => {
+                        tracing::warn!("Couldn't find talks about {}: {}", topic.name, e);
+                        (Vec::new(), false)
+                    }
+                }
+            }
+            _ => (talks.search(topic.name, limit), true),
+        }
+    }
+
+    /// The data a page is built from, in brief
+    fn sources(&self) -> String {
+        let talks = self.talks.map_or(0, |t| t.talks().len());
+        let embedded_talks = self.talks.is_some_and(TalkDb::has_embeddings);
+        format!(
+            "{} verses, {} with footnotes, {} embedded, {} talks{}, limit {}",
+            self.db.all_verses().len(),
+            self.footnotes.map_or(0, FootnoteDb::len),
+            self.embeddings.as_ref().map_or(0, |e| e.verse_count()),
+            talks,
+            if embedded_talks { " embedded" } else { "" },
+            self.limit
+        )
+    }
+}
+
+fn read_cached(path: &Path) -> Option<TopicPage> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// The first paragraph found from each talk, up to `limit` talks
+fn topic_talks(talks: &TalkDb, hits: Vec<TalkHit>, limit: usize) -> Vec<TopicTalk> {
+    let mut seen = HashSet::new();
+    hits.into_iter()
+        .filter(|hit| seen.insert(hit.talk))
+        .filter_map(|hit| {
+            let talk = talks.get(hit.talk)?;
+            Some(TopicTalk {
+                speaker: talk.speaker.clone(),
+                title: talk.title.clone(),
+                session: talk.session(),
+                paragraph: hit.paragraph,
+                text: talk.paragraphs.get(hit.paragraph)?.clone(),
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripture::Scripture;
+    use crate::talks::Talk;
+
+    fn db() -> ScriptureDb {
+        let verse = |book: &str, chapter, number, text: &str| Scripture {
+            volume_title: "Book of Mormon".into(),
+            book_title: book.into(),
+            book_short_title: book.into(),
+            chapter_number: chapter,
+            verse_number: number,
+            verse_title: format!("{} {}:{}", book, chapter, number),
+            verse_short_title: format!("{} {}:{}", book, chapter, number),
+            scripture_text: text.to_string(),
+        };
+        ScriptureDb::from_verses(vec![
+            verse("Alma", 32, 21, "Faith is not to have a perfect knowledge of things"),
+            verse("Alma", 32, 27, "Exercise a particle of faith"),
+            verse("Ether", 12, 6, "Faith is things which are hoped for and not seen"),
+            verse("Moroni", 7, 33, "If ye will have faith in me ye shall have power"),
+        ])
+    }
+
+    fn footnotes() -> FootnoteDb {
+        FootnoteDb::from_json(
+            r#"{
+                "Ether 12:6": [{"marker": "a", "word": "faith", "references": ["TG Faith"]}],
+                "Alma 32:21": [{"marker": "b", "word": "faith", "references": ["Heb. 11:1", "tg faith"]}],
+                "Moroni 7:33": [{"marker": "a", "word": "power", "references": ["TG God, Power of"]}]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn talks() -> TalkDb {
+        let talk = |speaker: &str, title: &str, paragraphs: &[&str]| Talk {
+            speaker: speaker.to_string(),
+            year: 2020,
+            month: "April".to_string(),
+            title: title.to_string(),
+            paragraphs: paragraphs.iter().map(|p| p.to_string()).collect(),
+        };
+        TalkDb::from_talks(vec![
+            talk("Speaker One", "Faith to Act", &["Faith moves us to act.", "And faith again."]),
+            talk("Speaker Two", "Covenants", &["Covenants bind us to God."]),
+        ])
+    }
+
+    #[test]
+    fn test_find_and_build_a_page() {
+        assert_eq!(Topic::find("atone").unwrap().name, "Atonement of Jesus Christ");
+        assert_eq!(Topic::find("PRAYER").unwrap().cache_file_name(), "prayer.json");
+        assert!(Topic::find("").is_none());
+
+        let (db, footnotes, talks) = (db(), footnotes(), talks());
+        let page = TopicIndex::new(&db)
+            .with_footnotes(Some(&footnotes))
+            .with_talks(Some(&talks))
+            .build(Topic::find("faith").unwrap());
+        // Topical Guide citations in scripture order, then other verses naming the topic
+        assert_eq!(page.topical_guide, ["Alma 32:21", "Ether 12:6"]);
+        assert_eq!(page.related, ["Alma 32:27", "Moroni 7:33"]);
+        assert_eq!(page.talks.len(), 1);
+        assert_eq!((page.talks[0].title.as_str(), page.talks[0].paragraph), ("Faith to Act", 0));
+    }
+
+    #[test]
+    fn test_pages_are_cached_until_their_sources_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let (db, footnotes) = (db(), footnotes());
+        let topic = Topic::find("Faith").unwrap();
+        let page = TopicIndex::new(&db).with_footnotes(Some(&footnotes)).with_cache_dir(dir.path()).page(topic).unwrap();
+        assert!(dir.path().join("faith.json").exists());
+
+        // A page from the same sources comes from the cache
+        let mut cached = page.clone();
+        cached.related = vec!["Cached 1:1".to_string()];
+        fs::write(dir.path().join("faith.json"), serde_json::to_string(&cached).unwrap()).unwrap();
+        let mut index = TopicIndex::new(&db).with_footnotes(Some(&footnotes)).with_cache_dir(dir.path());
+        assert_eq!(index.page(topic).unwrap(), cached);
+
+        // Without the footnotes it's built again
+        let page = TopicIndex::new(&db).with_cache_dir(dir.path()).page(topic).unwrap();
+        assert!(page.topical_guide.is_empty());
+        assert_ne!(page.related, cached.related);
+    }
+}
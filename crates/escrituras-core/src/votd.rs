@@ -0,0 +1,100 @@
+//! Verse of the day
+//!
+//! A date picks one verse from a curated list of well-loved passages, so every
+//! UI shows the same verse on the same day without any network service.
+
+use chrono::{Datelike, Local, NaiveDate};
+
+use crate::scripture::{Scripture, ScriptureDb};
+
+/// Verses in rotation, one per day
+const VERSES: &[&str] = &[
+    "John 3:16",
+    "1 Nephi 3:7",
+    "Moroni 10:4",
+    "Proverbs 3:5",
+    "Doctrine and Covenants 4:2",
+    "Mosiah 2:17",
+    "Matthew 11:28",
+    "2 Nephi 2:25",
+    "Joshua 1:9",
+    "Alma 32:21",
+    "Philippians 4:13",
+    "Ether 12:27",
+    "Isaiah 41:10",
+    "Doctrine and Covenants 18:10",
+    "John 14:27",
+    "Helaman 5:12",
+    "Psalm 23:1",
+    "2 Nephi 31:20",
+    "Matthew 5:16",
+    "Moroni 7:47",
+    "James 1:5",
+    "Doctrine and Covenants 6:36",
+    "Romans 8:38",
+    "Alma 37:37",
+    "Isaiah 40:31",
+    "Mosiah 3:19",
+    "John 13:34",
+    "Doctrine and Covenants 88:118",
+    "Micah 6:8",
+    "3 Nephi 12:48",
+    "Proverbs 22:6",
+    "Alma 7:11",
+    "1 Corinthians 13:13",
+    "Doctrine and Covenants 19:23",
+    "Moses 1:39",
+    "2 Nephi 32:3",
+    "Hebrews 11:1",
+    "Alma 36:3",
+    "Psalm 46:10",
+    "Doctrine and Covenants 121:7",
+    "Matthew 6:33",
+    "Mosiah 18:9",
+    "Romans 12:21",
+    "Ether 12:6",
+    "John 15:13",
+    "Doctrine and Covenants 64:33",
+    "Isaiah 1:18",
+    "Alma 34:32",
+    "Galatians 5:22",
+    "3 Nephi 11:11",
+    "Lamentations 3:22",
+    "Doctrine and Covenants 58:27",
+];
+
+/// The verse for a date; `None` only when the database lacks it
+pub fn verse_of_the_day(db: &ScriptureDb, date: NaiveDate) -> Option<Scripture> {
+    let index = date.num_days_from_ce().rem_euclid(VERSES.len() as i32) as usize;
+    let range = db.parse_reference(VERSES[index])?;
+    let title = format!("{} {}:{}", range.book_title, range.chapter_number, range.start_verse);
+    db.get_by_title(&title).cloned()
+}
+
+/// Today's verse, by the local date
+pub fn todays_verse(db: &ScriptureDb) -> Option<Scripture> {
+    verse_of_the_day(db, Local::now().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(book: &str, chapter: i32, number: i32) -> Scripture {
+        Scripture::test(book, chapter, number).volume("Volume")
+    }
+
+    #[test]
+    fn test_same_verse_all_day_and_next_verse_tomorrow() {
+        let db = ScriptureDb::from_verses(vec![verse("John", 3, 16), verse("1 Nephi", 3, 7)]);
+        // Consecutive days walk the list in order
+        let day = (0..VERSES.len() as i64)
+            .map(|n| NaiveDate::from_ymd_opt(2026, 1, 1).unwrap() + chrono::Days::new(n as u64))
+            .find(|d| d.num_days_from_ce().rem_euclid(VERSES.len() as i32) == 0)
+            .unwrap();
+
+        assert_eq!(verse_of_the_day(&db, day).unwrap().verse_title, "John 3:16");
+        assert_eq!(verse_of_the_day(&db, day.succ_opt().unwrap()).unwrap().verse_title, "1 Nephi 3:7");
+        assert!(verse_of_the_day(&db, day + chrono::Days::new(2)).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+//! Where every word of the scriptures occurs
+//!
+//! Words are indexed by their English stem, so "remember" finds "remembered"
+//! and "remembering" too. Keyword search narrows its candidates with the index,
+//! and a concordance lists every occurrence of a word grouped by book.
+
+use crate::scripture::{stem_word, Scripture};
+use crate::title::Title;
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The words of a verse or query: split on whitespace and dashes, so
+/// "faith--faith" is two words
+pub fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '-' | '–' | '—')).filter(|w| !w.is_empty())
+}
+
+/// Verses containing a stem, by index into the database, in canonical order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Posting {
+    pub verse: usize,
+    /// How many times the stem occurs in the verse
+    pub count: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct WordIndex {
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl WordIndex {
+    pub fn build(verses: &[Scripture]) -> Self {
+        let stemmer = Stemmer::create(Algorithm::English);
+        let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+        for (i, verse) in verses.iter().enumerate() {
+            for word in words(&verse.scripture_text.to_lowercase()) {
+                let stem = stem_word(&stemmer, word);
+                if stem.is_empty() {
+                    continue;
+                }
+                let list = postings.entry(stem).or_default();
+                match list.last_mut() {
+                    Some(posting) if posting.verse == i => posting.count += 1,
+                    _ => list.push(Posting { verse: i, count: 1 }),
+                }
+            }
+        }
+        WordIndex { postings }
+    }
+
+    /// Where a stem occurs (nothing for a stem that never does)
+    pub(crate) fn postings(&self, stem: &str) -> &[Posting] {
+        self.postings.get(stem).map_or(&[], Vec::as_slice)
+    }
+
+    /// Indexes of the verses containing every stem, in canonical order
+    pub(crate) fn verses_with_all(&self, stems: &[String]) -> Vec<usize> {
+        let mut lists: Vec<&[Posting]> = stems.iter().map(|s| self.postings(s)).collect();
+        // Walk the rarest word's verses and check the others
+        lists.sort_by_key(|list| list.len());
+        let Some((rarest, rest)) = lists.split_first() else {
+            return Vec::new();
+        };
+        rarest
+            .iter()
+            .map(|p| p.verse)
+            .filter(|&verse| rest.iter().all(|list| list.binary_search_by_key(&verse, |p| p.verse).is_ok()))
+            .collect()
+    }
+}
+
+/// A verse a word occurs in, with the forms it takes there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Occurrence {
+    pub reference: String,
+    pub chapter: i32,
+    pub verse: i32,
+    pub count: u32,
+    /// The words as written that share the stem ("remembered", "Remember")
+    pub forms: Vec<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookOccurrences {
+    pub volume: Title,
+    pub book: Title,
+    pub count: u32,
+    pub verses: Vec<Occurrence>,
+}
+
+/// Every occurrence of a word, grouped by book in canonical order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Concordance {
+    pub word: String,
+    pub count: u32,
+    pub books: Vec<BookOccurrences>,
+}
+
+/// The concordance of `word` among the verses `keep` accepts
+pub fn concordance(index: &WordIndex, verses: &[Scripture], word: &str, keep: impl Fn(&Scripture) -> bool) -> Concordance {
+    let stemmer = Stemmer::create(Algorithm::English);
+    let stem = stem_word(&stemmer, &word.to_lowercase());
+    let mut books: Vec<BookOccurrences> = Vec::new();
+    for posting in index.postings(&stem) {
+        let verse = &verses[posting.verse];
+        if !keep(verse) {
+            continue;
+        }
+        let mut forms: Vec<String> = Vec::new();
+        for w in words(&verse.scripture_text) {
+            let form = w.trim_matches(|c: char| !c.is_alphanumeric());
+            if stem_word(&stemmer, &w.to_lowercase()) == stem && !forms.iter().any(|f| f == form) {
+                forms.push(form.to_string());
+            }
+        }
+        let occurrence = Occurrence {
+            reference: verse.verse_title.clone(),
+            chapter: verse.chapter_number,
+            verse: verse.verse_number,
+            count: posting.count,
+            forms,
+            text: verse.scripture_text.clone(),
+        };
+        match books.last_mut() {
+            Some(book) if book.book == verse.book_title => {
+                book.count += posting.count;
+                book.verses.push(occurrence);
+            }
+            _ => books.push(BookOccurrences {
+                volume: verse.volume_title.clone(),
+                book: verse.book_title.clone(),
+                count: posting.count,
+                verses: vec![occurrence],
+            }),
+        }
+    }
+    Concordance { word: word.to_string(), count: books.iter().map(|b| b.count).sum(), books }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verse(book: &str, chapter: i32, number: i32, text: &str) -> Scripture {
+        Scripture::test(book, chapter, number).text(text)
+    }
+
+    #[test]
+    fn test_concordance_groups_forms_by_book() {
+        let verses = vec![
+            verse("Mosiah", 4, 11, "I would that ye should remember, and always retain in remembrance"),
+            verse("Alma", 36, 2, "Remember the captivity of our fathers; remember, remember"),
+            verse("Alma", 36, 3, "Trust in God--faith--and remembered not"),
+            verse("Alma", 36, 4, "Nothing to see here"),
+        ];
+        let index = WordIndex::build(&verses);
+        assert_eq!(index.verses_with_all(&["rememb".into(), "faith".into()]), [2]);
+        assert!(index.verses_with_all(&["rememb".into(), "unknown".into()]).is_empty());
+
+        let found = concordance(&index, &verses, "Remember", |_| true);
+        assert_eq!(found.count, 5);
+        let books: Vec<(&str, u32)> = found.books.iter().map(|b| (b.book.as_str(), b.count)).collect();
+        assert_eq!(books, [("Mosiah", 1), ("Alma", 4)]);
+        assert_eq!(found.books[1].verses[0].forms, ["Remember", "remember"]);
+        assert_eq!(found.books[1].verses[1].forms, ["remembered"]);
+
+        let alma_only = concordance(&index, &verses, "remember", |v| v.book_title == "Alma");
+        assert_eq!(alma_only.books.len(), 1);
+    }
+}
@@ -0,0 +1,96 @@
+//! The public API as a downstream crate sees it: only what's re-exported or
+//! in a public module, and the JSON the serde types produce. A test failing
+//! here after a change means the change breaks the TUI, the desktop app, or
+//! someone else's tool.
+
+use escrituras_core::error::Result;
+use escrituras_core::search::{self, SearchFilter};
+use escrituras_core::{
+    ChatMessage, ChatRole, CopyFormat, EscriturasError, MatchSource, Scripture, ScriptureDb, ScriptureRange, SearchHit,
+    SearchMode,
+};
+
+const SCRIPTURES: &str = r#"[
+    {"volume_title":"New Testament","book_title":"John","book_short_title":"John","chapter_number":3,
+     "verse_number":16,"verse_title":"John 3:16","verse_short_title":"John 3:16",
+     "scripture_text":"For God so loved the world, that he gave his only begotten Son"},
+    {"volume_title":"New Testament","book_title":"John","book_short_title":"John","chapter_number":3,
+     "verse_number":17,"verse_title":"John 3:17","verse_short_title":"John 3:17",
+     "scripture_text":"For God sent not his Son into the world to condemn the world"},
+    {"volume_title":"Book of Mormon","book_title":"Alma","book_short_title":"Alma","chapter_number":32,
+     "verse_number":21,"verse_title":"Alma 32:21","verse_short_title":"Alma 32:21",
+     "scripture_text":"And now as I said concerning faith--faith is not to have a perfect knowledge of things"}
+]"#;
+
+fn db() -> ScriptureDb {
+    ScriptureDb::from_json(SCRIPTURES).unwrap()
+}
+
+#[test]
+fn test_lookup_references_and_copy() {
+    let db = db();
+    assert_eq!(db.get_volumes(), ["New Testament", "Book of Mormon"]);
+    assert_eq!(db.get_books_for_volume("Book of Mormon"), ["Alma"]);
+    assert_eq!(db.get_chapters_for_book("John"), [3]);
+
+    let range = db.parse_reference("john 3:16-17").unwrap();
+    assert_eq!(range.display_title(), "John 3:16-17");
+    let verses: Vec<Scripture> = db.get_verses_in_range(&range).into_iter().cloned().collect();
+    assert_eq!(verses.len(), 2);
+    assert_eq!(CopyFormat::ReferenceOnly.format(&verses), "John 3:16-17");
+
+    let found = db.extract_scripture_references("Compare: **Alma 32:21**; John 3:16.");
+    let titles: Vec<String> = found.iter().map(ScriptureRange::display_title).collect();
+    assert_eq!(titles, ["Alma 32:21", "John 3:16"]);
+}
+
+#[test]
+fn test_search_with_a_filter() {
+    let db = db();
+    let filter = SearchFilter { volume: Some("New Testament".to_string()), mode: SearchMode::Keyword, ..Default::default() };
+    let hits = search::search(&db, None, "world", &filter);
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|hit| hit.source == MatchSource::Keyword && hit.score.is_none()));
+    assert!(search::search(&db, None, "faith", &filter).is_empty());
+}
+
+#[test]
+fn test_serde_formats_are_stable() {
+    let db = db();
+    let verse = db.get_by_title("Alma 32:21").unwrap().clone();
+    let json = serde_json::to_value(&verse).unwrap();
+    assert_eq!(json["verse_title"], "Alma 32:21");
+    assert_eq!(json["chapter_number"], 32);
+
+    let range = db.parse_reference("John 3:16-17").unwrap();
+    let json = serde_json::to_value(&range).unwrap();
+    assert_eq!(json["book_title"], "John");
+    assert_eq!((json["start_verse"].clone(), json["end_verse"].clone()), (16.into(), 17.into()));
+    assert_eq!(serde_json::from_value::<ScriptureRange>(json).unwrap(), range);
+
+    let message = ChatMessage { role: ChatRole::Assistant, content: "Read Alma 32".to_string() };
+    let json = serde_json::to_string(&message).unwrap();
+    assert_eq!(json, r#"{"role":"Assistant","content":"Read Alma 32"}"#);
+    assert_eq!(serde_json::from_str::<ChatMessage>(&json).unwrap().role, ChatRole::Assistant);
+
+    let hit = SearchHit { scripture: verse, source: MatchSource::Semantic, score: Some(0.5) };
+    let json = serde_json::to_value(&hit).unwrap();
+    assert_eq!((json["source"].clone(), json["score"].clone()), ("Semantic".into(), 0.5.into()));
+    assert_eq!(serde_json::from_value::<SearchHit>(json).unwrap().scripture.verse_title, "Alma 32:21");
+}
+
+#[test]
+fn test_errors_say_what_kind() {
+    let parsed: Result<ScriptureDb> = ScriptureDb::from_json("not scripture");
+    let error = parsed.err().unwrap();
+    assert!(matches!(error, EscriturasError::ParseError(_)));
+
+    let error = EscriturasError::EmbeddingsMissing("No embeddings".to_string());
+    assert_eq!(error.to_string(), "No embeddings");
+    let hint = match &error {
+        EscriturasError::DataNotFound(_) => None,
+        // New kinds can be added, so there's always a catch-all
+        _ => error.hint(),
+    };
+    assert!(hint.unwrap().contains("scriptures embeddings download"));
+}
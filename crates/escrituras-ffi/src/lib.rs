@@ -0,0 +1,222 @@
+//! Escrituras for iOS and Android
+//!
+//! UniFFI bindings over escrituras-core, so a mobile app looks up, searches,
+//! and copies verses with the same engine as the TUI. Everything is
+//! synchronous: the app calls from a background thread and gets its answer
+//! back, with no async runtime to share. Search is keyword only, since
+//! embedding a query needs the embedding model.
+//!
+//! Generate the Swift or Kotlin bindings from the built library:
+//!
+//! ```sh
+//! cargo build -p escrituras-ffi --release
+//! cargo run -p escrituras-ffi --features cli --bin uniffi-bindgen -- generate \
+//!     --library target/release/libescrituras_ffi.so --language swift --out-dir out
+//! ```
+
+use std::sync::Arc;
+
+use escrituras_core::search::{self, MatchSource, SearchFilter, SearchMode};
+use escrituras_core::{CopyFormat, EscriturasError, Scripture, ScriptureDb, ScriptureRange, Title};
+
+uniffi::setup_scaffolding!();
+
+/// What went wrong, for the app to tell apart
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ScripturesError {
+    /// No scripture data at the path, or no verses for a reference
+    #[error("{message}")]
+    NotFound { message: String },
+    /// A file that isn't scripture data, or an unknown copy format
+    #[error("{message}")]
+    Invalid { message: String },
+    #[error("{message}")]
+    Failed { message: String },
+}
+
+impl From<EscriturasError> for ScripturesError {
+    fn from(e: EscriturasError) -> Self {
+        let message = e.to_string();
+        match e {
+            EscriturasError::DataNotFound(_) => Self::NotFound { message },
+            EscriturasError::ParseError(_) | EscriturasError::InvalidSetting(_) => Self::Invalid { message },
+            _ => Self::Failed { message },
+        }
+    }
+}
+
+/// A scripture verse
+#[derive(uniffi::Record)]
+pub struct Verse {
+    pub volume_title: String,
+    pub book_title: String,
+    pub book_short_title: String,
+    pub chapter_number: i32,
+    pub verse_number: i32,
+    pub verse_title: String,
+    pub scripture_text: String,
+}
+
+impl From<&Scripture> for Verse {
+    fn from(s: &Scripture) -> Self {
+        Self {
+            volume_title: s.volume_title.to_string(),
+            book_title: s.book_title.to_string(),
+            book_short_title: s.book_short_title.to_string(),
+            chapter_number: s.chapter_number,
+            verse_number: s.verse_number,
+            verse_title: s.verse_title.clone(),
+            scripture_text: s.scripture_text.clone(),
+        }
+    }
+}
+
+/// A search hit, and how it was found
+#[derive(uniffi::Record)]
+pub struct SearchResult {
+    pub verse: Verse,
+    pub semantic: bool,
+    /// Cosine similarity for semantic hits
+    pub score: Option<f32>,
+}
+
+/// A reference, with the book's full title
+#[derive(uniffi::Record)]
+pub struct Reference {
+    pub book: String,
+    pub chapter: i32,
+    pub start_verse: i32,
+    pub end_verse: i32,
+    /// "Alma 32:21-23"
+    pub title: String,
+}
+
+impl From<&ScriptureRange> for Reference {
+    fn from(range: &ScriptureRange) -> Self {
+        Self {
+            book: range.book_title.clone(),
+            chapter: range.chapter_number,
+            start_verse: range.start_verse,
+            end_verse: range.end_verse,
+            title: range.display_title(),
+        }
+    }
+}
+
+/// The scriptures, loaded once and shared across the app's threads
+#[derive(uniffi::Object)]
+pub struct Scriptures {
+    db: ScriptureDb,
+}
+
+#[uniffi::export]
+impl Scriptures {
+    /// Load `lds-scriptures-json.txt` from the app's bundle or files
+    #[uniffi::constructor]
+    pub fn open(path: String) -> Result<Arc<Self>, ScripturesError> {
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| ScripturesError::NotFound { message: format!("Couldn't read {}: {}", path, e) })?;
+        Self::from_json(json)
+    }
+
+    /// Load the scripture JSON itself
+    #[uniffi::constructor]
+    pub fn from_json(json: String) -> Result<Arc<Self>, ScripturesError> {
+        Ok(Arc::new(Self { db: ScriptureDb::from_json(&json)? }))
+    }
+
+    /// Volumes in canonical order
+    pub fn volumes(&self) -> Vec<String> {
+        self.db.get_volumes().iter().map(Title::to_string).collect()
+    }
+
+    /// Books in a volume, in canonical order
+    pub fn books(&self, volume: String) -> Vec<String> {
+        self.db.get_books_for_volume(&volume).into_iter().map(String::from).collect()
+    }
+
+    /// Chapter numbers in a book
+    pub fn chapters(&self, book: String) -> Vec<i32> {
+        self.db.get_chapters_for_book(&book)
+    }
+
+    pub fn chapter(&self, book: String, chapter: i32) -> Vec<Verse> {
+        self.db.get_verses_for_chapter(&book, chapter).into_iter().map(Verse::from).collect()
+    }
+
+    /// The verses a typed reference names ("Alma 32:21-23", "moro 10:4",
+    /// "dc 76"), or none when it doesn't name any
+    pub fn lookup(&self, reference: String) -> Vec<Verse> {
+        self.db
+            .parse_reference(&reference)
+            .map(|range| self.db.get_verses_in_range(&range).into_iter().map(Verse::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// What a typed reference names, if it names any verses
+    pub fn parse_reference(&self, input: String) -> Option<Reference> {
+        self.db.parse_reference(&input).as_ref().map(Reference::from)
+    }
+
+    /// Every reference in a passage of text
+    pub fn references(&self, text: String) -> Vec<Reference> {
+        self.db.extract_scripture_references(&text).iter().map(Reference::from).collect()
+    }
+
+    /// Keyword search, optionally only in a volume or a book
+    pub fn search(&self, query: String, limit: u32, volume: Option<String>, book: Option<String>) -> Vec<SearchResult> {
+        let filter = SearchFilter { volume, book, mode: SearchMode::Keyword, limit: limit as usize };
+        search::search(&self.db, None, &query, &filter)
+            .iter()
+            .map(|hit| SearchResult {
+                verse: Verse::from(&hit.scripture),
+                semantic: hit.source == MatchSource::Semantic,
+                score: hit.score,
+            })
+            .collect()
+    }
+
+    /// A passage as it's copied everywhere else, in a `copy_format` such as
+    /// "reference", "plain", or "markdown"
+    pub fn copy(&self, reference: String, format: String) -> Result<String, ScripturesError> {
+        let format = CopyFormat::from_name(&format).ok_or_else(|| {
+            let names: Vec<&str> = CopyFormat::all().iter().map(|f| f.name()).collect();
+            ScripturesError::Invalid { message: format!("Unknown format '{}'. Use {}", format, names.join(", ")) }
+        })?;
+        let range = self
+            .db
+            .parse_reference(&reference)
+            .ok_or_else(|| ScripturesError::NotFound { message: format!("No verses found for '{}'", reference) })?;
+        let verses: Vec<Scripture> = self.db.get_verses_in_range(&range).into_iter().cloned().collect();
+        Ok(format.format(&verses))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"[
+        {"volume_title":"Book of Mormon","book_title":"Moroni","book_short_title":"Moro.","chapter_number":10,
+         "verse_number":4,"verse_title":"Moroni 10:4","verse_short_title":"Moro. 10:4","scripture_text":"And when ye shall receive these things"},
+        {"volume_title":"Book of Mormon","book_title":"Moroni","book_short_title":"Moro.","chapter_number":10,
+         "verse_number":5,"verse_title":"Moroni 10:5","verse_short_title":"Moro. 10:5","scripture_text":"And by the power of the Holy Ghost"}
+    ]"#;
+
+    #[test]
+    fn test_lookup_search_and_copy() {
+        let scriptures = Scriptures::from_json(JSON.to_string()).unwrap();
+        assert_eq!(scriptures.lookup("moro 10:4-5".to_string()).len(), 2);
+        assert_eq!(scriptures.parse_reference("moro 10:5".to_string()).unwrap().title, "Moroni 10:5");
+        assert!(scriptures.lookup("Alma 32:21".to_string()).is_empty());
+
+        let hits = scriptures.search("holy ghost".to_string(), 10, None, None);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].verse.verse_title, "Moroni 10:5");
+        assert!(!hits[0].semantic);
+
+        assert_eq!(scriptures.copy("moro 10:4".to_string(), "ref-only".to_string()).unwrap(), "Moroni 10:4");
+        assert!(matches!(scriptures.copy("moro 10:4".to_string(), "bogus".to_string()), Err(ScripturesError::Invalid { .. })));
+        assert!(matches!(Scriptures::from_json("nope".to_string()), Err(ScripturesError::Invalid { .. })));
+    }
+}
@@ -0,0 +1,201 @@
+//! Asking the AI provider chosen in the config
+//!
+//! The prompt is built the way the TUI builds it: the study instructions,
+//! what the user is reading, the verses given as context, the conversation
+//! so far, then the question.
+//!
+//! `chat_with_llm` returns the whole answer. `start_chat` instead streams it
+//! as events, each carrying the chat's id: `chat-token` for each piece of
+//! text, then `chat-done` (also after `cancel_chat`) or `chat-error`, which
+//! says what to do about it where there's something to suggest (`hint`).
+
+use escrituras_core::ai::{self, prompt::{context_block, history_block, question_block, PROMPT_CONTEXT_VERSES}};
+use escrituras_core::{ChatMessage, Config, EscriturasError, Provider, Scripture, ScriptureDb};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::oneshot;
+
+use crate::{AppState, ReferenceResult};
+
+/// An answer and the references it cites
+#[derive(Serialize)]
+pub struct ChatReply {
+    pub response: String,
+    pub references: Vec<ReferenceResult>,
+    pub provider: String,
+    pub model: String,
+}
+
+#[derive(Clone, Serialize)]
+struct ChatToken {
+    id: u64,
+    token: String,
+}
+
+#[derive(Clone, Serialize)]
+struct ChatDone {
+    id: u64,
+    /// All of the answer, or as much as came before it was cancelled
+    response: String,
+    references: Vec<ReferenceResult>,
+    cancelled: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct ChatError {
+    id: u64,
+    message: String,
+    hint: Option<String>,
+}
+
+/// Streamed chats still being answered, each with a way to cancel it
+#[derive(Default)]
+pub struct RunningChats {
+    next_id: AtomicU64,
+    cancels: Mutex<HashMap<u64, oneshot::Sender<()>>>,
+}
+
+/// The provider and model from the config
+pub fn provider_and_model(config: &Config) -> (Provider, String) {
+    let provider = config.current_provider();
+    let model = config.model_for(provider);
+    (provider, model)
+}
+
+/// The prompt for `question` after `history`, with the verses of each
+/// reference in `context` and the chapter the user is `reading`
+pub fn build_prompt(
+    config: &Config,
+    db: &ScriptureDb,
+    question: &str,
+    history: &[ChatMessage],
+    context: &[String],
+    reading: Option<&str>,
+) -> String {
+    let mut instructions = config.study_instructions();
+    if let Some(reading) = reading {
+        instructions.push_str(&format!("The user is currently reading {}.\n\n", reading));
+    }
+    let verses: Vec<Scripture> = context
+        .iter()
+        .filter_map(|r| db.parse_reference(r))
+        .flat_map(|range| db.get_verses_in_range(&range).into_iter().cloned().collect::<Vec<_>>())
+        .take(PROMPT_CONTEXT_VERSES)
+        .collect();
+    [instructions, context_block(&verses), history_block(history), question_block(question)].concat()
+}
+
+/// The references cited in an answer
+fn cited(state: &Mutex<AppState>, response: &str) -> Vec<ReferenceResult> {
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    db.extract_scripture_references(response).iter().map(|r| ReferenceResult::new(db, r)).collect()
+}
+
+/// Ask the configured provider `question`, after the earlier messages in
+/// `history`, about the verses in `context` (references)
+#[tauri::command]
+pub async fn chat_with_llm(
+    state: State<'_, Mutex<AppState>>,
+    question: String,
+    history: Vec<ChatMessage>,
+    context: Vec<String>,
+    reading: Option<String>,
+) -> Result<ChatReply, String> {
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let (provider, model) = provider_and_model(&config);
+    let prompt = {
+        let state = state.lock().unwrap();
+        build_prompt(&config, &state.scripture_db, &question, &history, &context, reading.as_deref())
+    };
+
+    // Nothing listens to the tokens; the whole answer comes back at once
+    let (tokens, _) = tokio::sync::mpsc::unbounded_channel();
+    let response = ai::ask(&config, provider, &model, &prompt, tokens).await.map_err(|e| match hint(&e) {
+        Some(hint) => format!("{}. {}", e, hint),
+        None => e.to_string(),
+    })?;
+
+    let references = cited(&state, &response);
+    Ok(ChatReply { response, references, provider: provider.as_str().to_string(), model })
+}
+
+/// Start answering like `chat_with_llm`, streaming the answer as events.
+/// Returns the chat's id, for matching events and `cancel_chat`.
+#[tauri::command]
+pub fn start_chat(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    chats: State<'_, RunningChats>,
+    question: String,
+    history: Vec<ChatMessage>,
+    context: Vec<String>,
+    reading: Option<String>,
+) -> u64 {
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let (provider, model) = provider_and_model(&config);
+    let prompt = {
+        let state = state.lock().unwrap();
+        build_prompt(&config, &state.scripture_db, &question, &history, &context, reading.as_deref())
+    };
+    let id = chats.next_id.fetch_add(1, Ordering::Relaxed);
+    let (cancel, mut cancelled) = oneshot::channel();
+    chats.cancels.lock().unwrap().insert(id, cancel);
+
+    tauri::async_runtime::spawn(async move {
+        let (tokens, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let answer = ai::ask(&config, provider, &model, &prompt, tokens);
+        tokio::pin!(answer);
+        let mut response = String::new();
+        let emit_token = |response: &mut String, token: String| {
+            response.push_str(&token);
+            let _ = app.emit("chat-token", ChatToken { id, token });
+        };
+        // None when cancelled
+        let result = loop {
+            tokio::select! {
+                biased;
+                Some(token) = rx.recv() => emit_token(&mut response, token),
+                result = &mut answer => break Some(result),
+                _ = &mut cancelled => break None,
+            }
+        };
+        // Text sent just before the answer finished
+        while let Ok(token) = rx.try_recv() {
+            emit_token(&mut response, token);
+        }
+        app.state::<RunningChats>().cancels.lock().unwrap().remove(&id);
+
+        let references = cited(&app.state::<Mutex<AppState>>(), &response);
+        let _ = match result {
+            Some(Err(e)) => {
+                tracing::warn!("Chat {} failed: {}", id, e);
+                app.emit("chat-error", ChatError { id, message: e.to_string(), hint: hint(&e) })
+            }
+            Some(Ok(_)) => app.emit("chat-done", ChatDone { id, response, references, cancelled: false }),
+            None => app.emit("chat-done", ChatDone { id, response, references, cancelled: true }),
+        };
+    });
+    id
+}
+
+/// What to do about a failed chat, pointing to Settings rather than the command line for a key
+fn hint(e: &EscriturasError) -> Option<String> {
+    match e {
+        EscriturasError::ProviderAuth { .. } => Some("Add an API key in Settings".to_string()),
+        _ => e.hint(),
+    }
+}
+
+/// Stop a streamed chat; it ends with `chat-done` marked cancelled. False
+/// when it had already finished.
+#[tauri::command]
+pub fn cancel_chat(chats: State<'_, RunningChats>, id: u64) -> bool {
+    match chats.cancels.lock().unwrap().remove(&id) {
+        Some(cancel) => cancel.send(()).is_ok(),
+        None => false,
+    }
+}
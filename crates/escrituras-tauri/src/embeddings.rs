@@ -0,0 +1,152 @@
+//! Setting up semantic search from the app
+//!
+//! Embeddings can be downloaded from the latest release (with the embedding
+//! model) or generated locally from the loaded scriptures. Either runs in the
+//! background, reporting `embeddings-progress` events and finishing with
+//! `embeddings-ready` or `embeddings-error`; once ready, `semantic_search`
+//! works without restarting the app. Only one runs at a time.
+
+use anyhow::{anyhow, Result};
+use escrituras_core::{cache_embedding_model, embedding_model_cached, paths, setup, EmbeddingsDb};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::startup::{load_embeddings, LoadStatus};
+use crate::AppState;
+
+/// Whether a download or generation is running
+#[derive(Default)]
+pub struct EmbeddingsJob {
+    running: AtomicBool,
+}
+
+#[derive(Serialize)]
+pub struct EmbeddingsStatus {
+    /// The directory holding the embeddings, when they're installed
+    path: Option<String>,
+    /// How many verses the installed embeddings cover
+    verses: Option<usize>,
+    /// Whether the embedding model has been downloaded
+    model: bool,
+    /// Whether semantic search is ready to use
+    loaded: bool,
+    /// Whether a download or generation is running
+    busy: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct EmbeddingsProgress {
+    message: String,
+    /// Verses embedded so far, while generating
+    done: Option<usize>,
+    total: Option<usize>,
+}
+
+impl EmbeddingsProgress {
+    fn step(message: &str) -> Self {
+        Self { message: message.to_string(), done: None, total: None }
+    }
+}
+
+/// Where the embeddings are and whether semantic search can use them
+#[tauri::command]
+pub fn embeddings_status(state: State<'_, Mutex<AppState>>, job: State<'_, EmbeddingsJob>) -> EmbeddingsStatus {
+    let dir = setup::find_data_dir(EmbeddingsDb::FILE_NAME);
+    let loaded = state.lock().unwrap().embeddings_db.is_some();
+    EmbeddingsStatus {
+        verses: dir.as_deref().and_then(|d| EmbeddingsDb::load(d).ok()).map(|e| e.verse_count()),
+        path: dir.map(|d| d.to_string_lossy().into_owned()),
+        model: embedding_model_cached(),
+        loaded,
+        busy: job.running.load(Ordering::SeqCst),
+    }
+}
+
+/// Download the embeddings and the embedding model from the latest release
+/// (as `scriptures embeddings download` does)
+#[tauri::command]
+pub fn download_embeddings(app: AppHandle, job: State<'_, EmbeddingsJob>) -> Result<(), String> {
+    start(&job)?;
+    tauri::async_runtime::spawn(async move {
+        let result = download(&app).await;
+        finish(&app, result);
+    });
+    Ok(())
+}
+
+/// Embed every loaded verse with the local model (as `scriptures embeddings
+/// generate` does), which takes several minutes
+#[tauri::command]
+pub fn generate_embeddings(app: AppHandle, state: State<'_, Mutex<AppState>>, job: State<'_, EmbeddingsJob>) -> Result<(), String> {
+    let verses = state.lock().unwrap().scripture_db.all_verses().to_vec();
+    if verses.is_empty() {
+        return Err("The scriptures haven't loaded yet".to_string());
+    }
+    start(&job)?;
+    tauri::async_runtime::spawn(async move {
+        let progress = app.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let dir = match setup::find_data_dir(EmbeddingsDb::FILE_NAME) {
+                Some(dir) => dir,
+                None => paths::data_dir()?.join("data"),
+            };
+            let _ = progress.emit("embeddings-progress", EmbeddingsProgress::step("Loading the embedding model"));
+            escrituras_core::generate_embeddings(&verses, &dir, |done, total| {
+                let message = format!("Embedding verses: {}/{}", done, total);
+                let _ = progress.emit("embeddings-progress", EmbeddingsProgress { message, done: Some(done), total: Some(total) });
+            })?;
+            load_or_fail()
+        })
+        .await
+        .map_err(|e| anyhow!(e))
+        .and_then(|r| r);
+        finish(&app, result);
+    });
+    Ok(())
+}
+
+fn start(job: &EmbeddingsJob) -> Result<(), String> {
+    if job.running.swap(true, Ordering::SeqCst) {
+        return Err("Embeddings are already being set up".to_string());
+    }
+    Ok(())
+}
+
+async fn download(app: &AppHandle) -> Result<EmbeddingsDb> {
+    let _ = app.emit("embeddings-progress", EmbeddingsProgress::step("Downloading embeddings"));
+    setup::download_release_data().await?;
+    let _ = app.emit("embeddings-progress", EmbeddingsProgress::step("Downloading the embedding model"));
+    tokio::task::spawn_blocking(|| {
+        cache_embedding_model()?;
+        load_or_fail()
+    })
+    .await?
+}
+
+fn load_or_fail() -> Result<EmbeddingsDb> {
+    load_embeddings().ok_or_else(|| anyhow!("The embeddings were written but couldn't be loaded"))
+}
+
+/// Put the embeddings in use and tell the frontend, or report what went wrong
+fn finish(app: &AppHandle, result: Result<EmbeddingsDb>) {
+    match result {
+        Ok(embeddings) => {
+            let verses = embeddings.verse_count();
+            {
+                let state = app.state::<Mutex<AppState>>();
+                let mut state = state.lock().unwrap();
+                state.embeddings_db = Some(embeddings);
+                if let LoadStatus::Ready { embeddings, .. } = &mut state.status {
+                    *embeddings = true;
+                }
+            }
+            let _ = app.emit("embeddings-ready", verses);
+        }
+        Err(e) => {
+            let _ = app.emit("embeddings-error", e.to_string());
+        }
+    }
+    app.state::<EmbeddingsJob>().running.store(false, Ordering::SeqCst);
+}
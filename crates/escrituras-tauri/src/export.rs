@@ -0,0 +1,93 @@
+//! Handouts: a chapter, the saved scriptures, or a chat as Markdown or PDF
+//!
+//! `export_handout` asks where to save through the system's save dialog,
+//! starting in the Downloads folder. For paper the frontend shows the handout
+//! and calls `print_page`.
+
+use escrituras_core::export::{chapter_markdown, chat_markdown, handout_pdf, saved_markdown};
+use escrituras_core::{ChatMessage, SavedScriptures};
+use serde::Deserialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State, WebviewWindow};
+use tauri_plugin_dialog::DialogExt;
+use tokio::sync::oneshot;
+
+use crate::AppState;
+
+/// What to export, as `{"kind": "chapter", "book": "Alma", "chapter": 32}`,
+/// `{"kind": "saved"}`, or `{"kind": "chat", "messages": [...]}`
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Handout {
+    Chapter { book: String, chapter: i32 },
+    Saved,
+    Chat { messages: Vec<ChatMessage> },
+}
+
+/// The file a handout is saved as
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum HandoutFormat {
+    Markdown,
+    Pdf,
+}
+
+/// The handout's Markdown and a name for its file, without the extension
+fn render(state: &Mutex<AppState>, handout: &Handout) -> Result<(String, String), String> {
+    match handout {
+        Handout::Chapter { book, chapter } => {
+            let state = state.lock().unwrap();
+            let markdown = chapter_markdown(&state.scripture_db, book, *chapter)
+                .ok_or_else(|| format!("There is no {} {}", book, chapter))?;
+            Ok((markdown, format!("{} {}", book, chapter)))
+        }
+        Handout::Saved => {
+            let saved = SavedScriptures::load().map_err(|e| format!("Could not read saved scriptures: {}", e))?;
+            Ok((saved_markdown(&saved), "Saved Scriptures".to_string()))
+        }
+        Handout::Chat { messages } => Ok((chat_markdown(messages), "Conversation".to_string())),
+    }
+}
+
+/// The handout as Markdown, for the frontend to show before printing
+#[tauri::command]
+pub fn export_preview(state: State<'_, Mutex<AppState>>, handout: Handout) -> Result<String, String> {
+    render(&state, &handout).map(|(markdown, _)| markdown)
+}
+
+/// Ask where to save the handout, then write it there; where it went, or
+/// None when the dialog was cancelled
+#[tauri::command]
+pub async fn export_handout(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    handout: Handout,
+    format: HandoutFormat,
+) -> Result<Option<String>, String> {
+    let (markdown, name) = render(&state, &handout)?;
+    let (contents, kind, extension) = match format {
+        HandoutFormat::Markdown => (markdown.into_bytes(), "Markdown", "md"),
+        HandoutFormat::Pdf => (handout_pdf(&markdown), "PDF", "pdf"),
+    };
+
+    let mut dialog = app.dialog().file().set_file_name(format!("{}.{}", name, extension)).add_filter(kind, &[extension]);
+    if let Ok(downloads) = app.path().download_dir() {
+        dialog = dialog.set_directory(downloads);
+    }
+    let (chosen, path) = oneshot::channel();
+    dialog.save_file(move |path| {
+        let _ = chosen.send(path);
+    });
+    let Some(path) = path.await.ok().flatten() else {
+        return Ok(None);
+    };
+    let path = path.into_path().map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+/// Open the system print dialog for the window
+#[tauri::command]
+pub fn print_page(window: WebviewWindow) -> Result<(), String> {
+    window.print().map_err(|e| e.to_string())
+}
@@ -0,0 +1,154 @@
+//! Opening `escrituras://` and Gospel Library links
+//!
+//! The app registers itself as the handler for `escrituras://` links: through
+//! `Info.plist` on macOS, and for the current user as it starts on Windows
+//! (under `HKEY_CURRENT_USER\Software\Classes`) and Linux (an
+//! `x-scheme-handler/escrituras` desktop entry), pointing at whichever copy
+//! of the app last ran. A link the app was launched with (the system passes
+//! it as an argument, or on macOS as an `Opened` event) is held until the
+//! scriptures load, then sent to the frontend as a `navigate` event carrying
+//! a `ReferenceResult`. Links pasted into the app go through `open_link`
+//! instead.
+
+use anyhow::Result;
+use escrituras_core::links::{link_to, reference_from_link};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::{AppState, ReferenceResult};
+
+const SCHEME: &str = "escrituras";
+
+/// The link the app was launched with, until the scriptures load
+#[derive(Default)]
+pub struct PendingLink(Mutex<Option<String>>);
+
+impl PendingLink {
+    /// The first argument that looks like a link
+    pub fn from_args() -> Self {
+        let link = std::env::args().skip(1).find(|arg| arg.contains("://"));
+        Self(Mutex::new(link))
+    }
+}
+
+fn resolve(state: &Mutex<AppState>, link: &str) -> Result<ReferenceResult, String> {
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    let range = reference_from_link(db, link).ok_or_else(|| format!("Not a scripture link: {}", link))?;
+    Ok(ReferenceResult::new(db, &range))
+}
+
+/// Navigate to the link the app was launched with, once the scriptures have loaded
+pub fn open_pending(app: &AppHandle) {
+    let Some(link) = app.state::<PendingLink>().0.lock().unwrap().take() else {
+        return;
+    };
+    if let Ok(reference) = resolve(&app.state::<Mutex<AppState>>(), &link) {
+        let _ = app.emit("navigate", reference);
+    }
+}
+
+/// Navigate to a link the system opened the app with, now or once the scriptures load
+#[cfg(target_os = "macos")]
+pub fn open(app: &AppHandle, link: String) {
+    *app.state::<PendingLink>().0.lock().unwrap() = Some(link);
+    if matches!(app.state::<Mutex<AppState>>().lock().unwrap().status, crate::LoadStatus::Ready { .. }) {
+        open_pending(app);
+    }
+}
+
+/// Make this copy of the app the handler for `escrituras://` links
+pub fn register_scheme(app: &AppHandle) -> Result<()> {
+    platform::register(app, &std::env::current_exe()?)
+}
+
+#[cfg(windows)]
+mod platform {
+    use anyhow::Result;
+    use std::path::Path;
+    use tauri::AppHandle;
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    use super::SCHEME;
+
+    pub fn register(_app: &AppHandle, exe: &Path) -> Result<()> {
+        let classes = RegKey::predef(HKEY_CURRENT_USER);
+        let (key, _) = classes.create_subkey(format!("Software\\Classes\\{}", SCHEME))?;
+        key.set_value("", &"URL:Escrituras link")?;
+        key.set_value("URL Protocol", &"")?;
+        let (command, _) = key.create_subkey("shell\\open\\command")?;
+        command.set_value("", &format!("\"{}\" \"%1\"", exe.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use anyhow::{bail, Result};
+    use std::path::Path;
+    use std::process::Command;
+    use tauri::{AppHandle, Manager};
+
+    use super::SCHEME;
+
+    const DESKTOP_FILE: &str = "escrituras-handler.desktop";
+
+    /// A desktop entry argument, quoted as the spec asks
+    fn quote(arg: &str) -> String {
+        let escaped: String = arg
+            .chars()
+            .flat_map(|c| match c {
+                '"' | '`' | '$' | '\\' => vec!['\\', c],
+                c => vec![c],
+            })
+            .collect();
+        format!("\"{}\"", escaped)
+    }
+
+    pub fn register(app: &AppHandle, exe: &Path) -> Result<()> {
+        let applications = app.path().data_dir()?.join("applications");
+        std::fs::create_dir_all(&applications)?;
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName=Escrituras\nExec={} %u\nTerminal=false\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+            quote(&exe.to_string_lossy()),
+            SCHEME
+        );
+        let path = applications.join(DESKTOP_FILE);
+        if std::fs::read_to_string(&path).ok().as_deref() == Some(entry.as_str()) {
+            return Ok(());
+        }
+        std::fs::write(&path, entry)?;
+        let status = Command::new("xdg-mime").args(["default", DESKTOP_FILE, &format!("x-scheme-handler/{}", SCHEME)]).status()?;
+        if !status.success() {
+            bail!("xdg-mime couldn't make Escrituras the handler for {}:// links", SCHEME);
+        }
+        Ok(())
+    }
+}
+
+/// Info.plist declares the scheme, so there's nothing to do as the app starts
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+mod platform {
+    use anyhow::Result;
+    use std::path::Path;
+    use tauri::AppHandle;
+
+    pub fn register(_app: &AppHandle, _exe: &Path) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The reference a link points to
+#[tauri::command]
+pub fn open_link(state: State<'_, Mutex<AppState>>, link: &str) -> Result<ReferenceResult, String> {
+    resolve(&state, link)
+}
+
+/// An `escrituras://` link to a reference, for pasting into notes
+#[tauri::command]
+pub fn link_to_reference(state: State<'_, Mutex<AppState>>, reference: &str) -> Result<String, String> {
+    let state = state.lock().unwrap();
+    let range = state.scripture_db.parse_reference(reference).ok_or_else(|| format!("Not a reference: {}", reference))?;
+    Ok(link_to(&range))
+}
@@ -1,21 +1,37 @@
 //! Escrituras Tauri Desktop Application
 //!
-//! This is a placeholder demonstrating how to integrate the escrituras-core library
-//! with a Tauri desktop application. The actual UI would be built separately
-//! using web technologies (React, Svelte, etc.).
+//! Tauri commands over the escrituras-core library for a desktop frontend
+//! built separately with web technologies (React, Svelte, etc.). Scripture
+//! data loads in the background after startup (see `startup`); until it's
+//! ready, commands answer as if there were no verses.
 
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use escrituras_core::{EmbeddingsDb, Scripture, ScriptureDb};
+mod chat;
+mod embeddings;
+mod export;
+mod links;
+mod plan;
+mod reminders;
+mod reader;
+mod settings;
+mod startup;
+mod study;
+
+use escrituras_core::events::{self, Event};
+use escrituras_core::search::{self, SearchFilter, SearchMode};
+use escrituras_core::{logging, paths, Config, EmbeddingsDb, Scripture, ScriptureDb, ScriptureRange, Title};
 use serde::Serialize;
 use std::sync::Mutex;
-use tauri::State;
+use startup::LoadStatus;
+use tauri::{Emitter, State};
 
 /// Application state shared across Tauri commands
 struct AppState {
     scripture_db: ScriptureDb,
     embeddings_db: Option<EmbeddingsDb>,
+    status: LoadStatus,
 }
 
 /// A scripture verse for JSON serialization to the frontend
@@ -32,7 +48,7 @@ impl From<&Scripture> for ScriptureResult {
     fn from(s: &Scripture) -> Self {
         Self {
             verse_title: s.verse_title.clone(),
-            book_title: s.book_title.clone(),
+            book_title: s.book_title.to_string(),
             chapter_number: s.chapter_number,
             verse_number: s.verse_number,
             scripture_text: s.scripture_text.clone(),
@@ -47,22 +63,54 @@ struct SearchResult {
     score: Option<f32>,
 }
 
+/// A reference found in text, for the frontend to show as a link
+#[derive(Clone, Serialize)]
+struct ReferenceResult {
+    book: String,
+    chapter: i32,
+    start_verse: i32,
+    end_verse: i32,
+    /// "Alma 32:21-23", with the book's full title
+    title: String,
+    /// Whether every verse exists; a range can run past its chapter
+    valid: bool,
+}
+
+impl ReferenceResult {
+    fn new(db: &ScriptureDb, range: &ScriptureRange) -> Self {
+        Self {
+            book: range.book_title.clone(),
+            chapter: range.chapter_number,
+            start_verse: range.start_verse,
+            end_verse: range.end_verse,
+            title: range.display_title(),
+            valid: db.is_valid_range(range),
+        }
+    }
+}
+
 // ============================================================================
 // Tauri Commands
 // ============================================================================
 
+/// Whether the scriptures have loaded, for a frontend that missed the load events
+#[tauri::command]
+fn app_status(state: State<Mutex<AppState>>) -> LoadStatus {
+    state.lock().unwrap().status.clone()
+}
+
 /// Get all available scripture volumes
 #[tauri::command]
 fn get_volumes(state: State<Mutex<AppState>>) -> Vec<String> {
     let state = state.lock().unwrap();
-    state.scripture_db.get_volumes().to_vec()
+    state.scripture_db.get_volumes().iter().map(Title::to_string).collect()
 }
 
 /// Get all books in a volume
 #[tauri::command]
 fn get_books(state: State<Mutex<AppState>>, volume: &str) -> Vec<String> {
     let state = state.lock().unwrap();
-    state.scripture_db.get_books_for_volume(volume)
+    state.scripture_db.get_books_for_volume(volume).into_iter().map(String::from).collect()
 }
 
 /// Get all chapters in a book
@@ -98,12 +146,11 @@ fn lookup_verse(state: State<Mutex<AppState>>, reference: &str) -> Option<Script
 #[tauri::command]
 fn search(state: State<Mutex<AppState>>, query: &str, limit: usize) -> Vec<SearchResult> {
     let state = state.lock().unwrap();
-    state
-        .scripture_db
-        .search(query, limit)
+    let filter = SearchFilter { mode: SearchMode::Keyword, limit, ..Default::default() };
+    search::search(&state.scripture_db, None, query, &filter)
         .iter()
-        .map(|s| SearchResult {
-            verse: ScriptureResult::from(*s),
+        .map(|hit| SearchResult {
+            verse: ScriptureResult::from(&hit.scripture),
             score: None,
         })
         .collect()
@@ -121,13 +168,13 @@ fn semantic_search(
     let embeddings_db = state
         .embeddings_db
         .as_mut()
-        .ok_or_else(|| "Embeddings not available".to_string())?;
+        .ok_or_else(|| "Embeddings not available; set them up with download_embeddings or generate_embeddings".to_string())?;
 
     let results = embeddings_db
         .search(query, limit)
         .map_err(|e| e.to_string())?;
 
-    Ok(results
+    let results: Vec<SearchResult> = results
         .into_iter()
         .filter_map(|(verse_title, score)| {
             state.scripture_db.get_by_title(&verse_title).map(|s| SearchResult {
@@ -135,18 +182,26 @@ fn semantic_search(
                 score: Some(score),
             })
         })
-        .collect())
+        .collect();
+    events::emit(Event::SearchPerformed { query: query.to_string(), mode: SearchMode::Semantic, hits: results.len() });
+    Ok(results)
+}
+
+/// Tell subscribers (see `escrituras_core::events`) that the reader showed a verse
+#[tauri::command]
+fn verse_viewed(verse_title: String) {
+    events::emit(Event::VerseViewed { verse_title });
 }
 
 /// Extract scripture references from text (e.g., AI response)
 #[tauri::command]
-fn extract_references(state: State<Mutex<AppState>>, text: &str) -> Vec<String> {
+fn extract_references(state: State<Mutex<AppState>>, text: &str) -> Vec<ReferenceResult> {
     let state = state.lock().unwrap();
     state
         .scripture_db
         .extract_scripture_references(text)
         .iter()
-        .map(|r| r.display_title())
+        .map(|r| ReferenceResult::new(&state.scripture_db, r))
         .collect()
 }
 
@@ -155,19 +210,54 @@ fn extract_references(state: State<Mutex<AppState>>, text: &str) -> Vec<String>
 // ============================================================================
 
 fn main() {
+    // `--profile <name>` picks a config profile, as in the TUI
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(name) = args.iter().position(|a| a == "--profile").and_then(|i| args.get(i + 1)) {
+        Config::select_profile(name);
+    }
+    let log_level = Config::load().ok().and_then(|c| c.log_level);
+    let _log = logging::init(log_level.as_deref(), args.iter().any(|a| a == "--verbose"));
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .setup(|_app| {
-            // Initialize state - this would normally load data asynchronously
-            // For now, we create empty state. The actual app would load scripture data
-            // during startup or on-demand.
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            // Files older versions kept in the config directory move to where they go now
+            if let Err(e) = paths::migrate() {
+                tracing::warn!("Couldn't move files to their new folders: {}", e);
+            }
+            // Keys the TUI saved in the config file move to the keychain
+            if let Err(e) = Config::migrate_saved_keys() {
+                tracing::warn!("Couldn't move API keys to the keychain: {}", e);
+            }
+            // Start from what other computers have synced
+            if let Err(e) = study::sync_study_data() {
+                tracing::warn!("{}", e);
+            }
+            // Pass study events on to the frontend as `study-event`
+            let handle = app.handle().clone();
+            events::subscribe(move |event| {
+                let _ = handle.emit("study-event", event);
+            });
+            // Links to `escrituras://` open this app
+            if let Err(e) = links::register_scheme(app.handle()) {
+                tracing::warn!("Couldn't register escrituras:// links: {}", e);
+            }
+            startup::spawn(app.handle().clone());
+            reminders::spawn(app.handle().clone());
             Ok(())
         })
+        .manage(chat::RunningChats::default())
+        .manage(embeddings::EmbeddingsJob::default())
+        .manage(links::PendingLink::from_args())
         .manage(Mutex::new(AppState {
             scripture_db: ScriptureDb::new(),
             embeddings_db: None,
+            status: LoadStatus::Loading { message: "Starting".to_string() },
         }))
         .invoke_handler(tauri::generate_handler![
+            app_status,
             get_volumes,
             get_books,
             get_chapters,
@@ -175,8 +265,52 @@ fn main() {
             lookup_verse,
             search,
             semantic_search,
+            embeddings::embeddings_status,
+            embeddings::download_embeddings,
+            embeddings::generate_embeddings,
             extract_references,
+            verse_viewed,
+            reader::next_chapter,
+            reader::prev_chapter,
+            reader::get_chapter_with_heading,
+            reader::get_verse_page,
+            reader::resolve_reference,
+            chat::chat_with_llm,
+            chat::start_chat,
+            chat::cancel_chat,
+            study::list_bookmarks,
+            study::add_bookmark,
+            study::set_bookmark_tags,
+            study::remove_bookmark,
+            study::list_highlights,
+            study::set_highlight,
+            study::sync_study_data,
+            study::list_notes,
+            study::add_note,
+            study::delete_note,
+            links::open_link,
+            links::link_to_reference,
+            export::export_preview,
+            export::export_handout,
+            export::print_page,
+            plan::todays_reading,
+            plan::mark_reading,
+            plan::study_stats,
+            settings::get_settings,
+            settings::update_setting,
+            settings::set_api_key,
+            settings::remove_api_key,
+            settings::test_api_key,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, _event| {
+            // macOS hands over links as events rather than arguments
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                for url in urls {
+                    links::open(_app, url.to_string());
+                }
+            }
+        });
 }
@@ -0,0 +1,134 @@
+//! The Come, Follow Me reading plan and study statistics
+//!
+//! Progress is the same study data store the TUI's Come, Follow Me screen and
+//! `scriptures plan` use, read afresh by each command.
+
+use escrituras_core::{setup, CfmProgress, CfmSchedule, Journal, MemorizeHistory, SavedScriptures, ScriptureDb, VolumeProgress};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::{AppState, ReferenceResult};
+
+#[derive(Serialize)]
+pub struct PlanReading {
+    /// "Genesis 1", or the verses for part of a chapter
+    label: String,
+    reference: ReferenceResult,
+    done: bool,
+}
+
+#[derive(Serialize)]
+pub struct PlanWeek {
+    /// Monday and Sunday, as `2026-01-05`
+    start: String,
+    end: String,
+    title: String,
+    readings: Vec<PlanReading>,
+}
+
+#[derive(Serialize)]
+pub struct StudyStats {
+    /// Weeks of the plan finished in a row, up to this one
+    streak: usize,
+    weeks_finished: usize,
+    /// Weeks of the plan up to and including this one
+    weeks_so_far: usize,
+    volumes: Vec<VolumeProgress>,
+    bookmarks: usize,
+    notes: usize,
+    /// Verses with at least one memorization attempt
+    verses_memorized: usize,
+    memorize_attempts: usize,
+}
+
+/// The schedule and the index of this week in it
+pub fn load_schedule() -> Result<(CfmSchedule, usize), String> {
+    let dir = setup::find_data_dir(CfmSchedule::FILE_NAME)
+        .ok_or_else(|| format!("No reading plan: add {} to the data directory", CfmSchedule::FILE_NAME))?;
+    let schedule = CfmSchedule::load(&dir).map_err(|e| e.to_string())?;
+    let current = schedule.current_week().ok_or_else(|| format!("{} has no weeks", CfmSchedule::FILE_NAME))?;
+    Ok((schedule, current))
+}
+
+fn load_progress() -> Result<CfmProgress, String> {
+    CfmProgress::load().map_err(|e| format!("Could not read reading progress: {}", e))
+}
+
+fn plan_week(db: &ScriptureDb, schedule: &CfmSchedule, current: usize, progress: &CfmProgress) -> PlanWeek {
+    let week = &schedule.weeks()[current];
+    PlanWeek {
+        start: week.start.to_string(),
+        end: week.end().to_string(),
+        title: week.title.clone(),
+        readings: week
+            .readings(db)
+            .into_iter()
+            .map(|r| PlanReading {
+                done: progress.is_done(week, &r.label),
+                reference: ReferenceResult::new(db, &r.range),
+                label: r.label,
+            })
+            .collect(),
+    }
+}
+
+/// This week's reading and which of it is finished
+#[tauri::command]
+pub fn todays_reading(state: State<'_, Mutex<AppState>>) -> Result<PlanWeek, String> {
+    let (schedule, current) = load_schedule()?;
+    let progress = load_progress()?;
+    let state = state.lock().unwrap();
+    Ok(plan_week(&state.scripture_db, &schedule, current, &progress))
+}
+
+/// Mark one of this week's readings (by label) finished or not, returning the week
+#[tauri::command]
+pub fn mark_reading(state: State<'_, Mutex<AppState>>, label: &str, done: bool) -> Result<PlanWeek, String> {
+    let (schedule, current) = load_schedule()?;
+    let mut progress = load_progress()?;
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    let week = &schedule.weeks()[current];
+    if !week.readings(db).iter().any(|r| r.label == label) {
+        return Err(format!("{} isn't in this week's reading", label));
+    }
+    if progress.is_done(week, label) != done {
+        let loaded = progress.clone();
+        progress.toggle(week, label);
+        progress.save(&loaded).map_err(|e| format!("Could not save reading progress: {}", e))?;
+    }
+    Ok(plan_week(db, &schedule, current, &progress))
+}
+
+/// Reading streak and progress, with counts of bookmarks, notes, and
+/// memorization. The reading figures are zero without a plan.
+#[tauri::command]
+pub fn study_stats(state: State<'_, Mutex<AppState>>) -> Result<StudyStats, String> {
+    let progress = load_progress()?;
+    let memorize = MemorizeHistory::load().map_err(|e| format!("Could not read memorization history: {}", e))?;
+    let bookmarks = SavedScriptures::load().map(|s| s.verses.len()).unwrap_or(0);
+    let notes = Journal::load().map(|j| j.entries.len()).unwrap_or(0);
+
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    let (streak, weeks_finished, weeks_so_far, volumes) = match load_schedule() {
+        Ok((schedule, current)) => (
+            progress.streak(&schedule, db, current),
+            schedule.weeks()[..=current].iter().filter(|w| progress.is_finished(w, db)).count(),
+            current + 1,
+            progress.volume_progress(&schedule, db),
+        ),
+        Err(_) => (0, 0, 0, Vec::new()),
+    };
+    Ok(StudyStats {
+        streak,
+        weeks_finished,
+        weeks_so_far,
+        volumes,
+        bookmarks,
+        notes,
+        verses_memorized: memorize.verses.values().filter(|a| !a.is_empty()).count(),
+        memorize_attempts: memorize.verses.values().map(Vec::len).sum(),
+    })
+}
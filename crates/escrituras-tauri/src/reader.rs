@@ -0,0 +1,119 @@
+//! Moving through the scriptures in the reader
+//!
+//! Chapters follow one another across books and volumes in canonical order,
+//! the way the TUI's next/previous chapter keys do. Pages of verses run on
+//! across chapter ends too, so the frontend can keep loading as the user
+//! scrolls.
+
+use escrituras_core::scripture::chapter_label;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::{AppState, ReferenceResult, ScriptureResult};
+
+#[derive(Serialize)]
+pub struct ChapterRef {
+    book: String,
+    chapter: i32,
+}
+
+#[derive(Serialize)]
+pub struct Chapter {
+    volume: String,
+    book: String,
+    chapter: i32,
+    /// "Alma 32"
+    title: String,
+    /// "Chapter 32", or "Section 76" in the Doctrine and Covenants
+    label: String,
+    verses: Vec<ScriptureResult>,
+    previous: Option<ChapterRef>,
+    next: Option<ChapterRef>,
+}
+
+#[derive(Serialize)]
+pub struct VersePage {
+    verses: Vec<ScriptureResult>,
+    /// The verse the page before this one starts at
+    previous: Option<String>,
+    /// The verse the page after this one starts at
+    next: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ResolvedReference {
+    reference: ReferenceResult,
+    verses: Vec<ScriptureResult>,
+}
+
+fn adjacent(state: &Mutex<AppState>, book: &str, chapter: i32, forward: bool) -> Option<ChapterRef> {
+    let state = state.lock().unwrap();
+    state
+        .scripture_db
+        .adjacent_chapter(book, chapter, forward)
+        .map(|(book, chapter)| ChapterRef { book, chapter })
+}
+
+/// The chapter after this one, continuing into the next book; None after the last
+#[tauri::command]
+pub fn next_chapter(state: State<'_, Mutex<AppState>>, book: &str, chapter: i32) -> Option<ChapterRef> {
+    adjacent(&state, book, chapter, true)
+}
+
+/// The chapter before this one, going back into the previous book; None before the first
+#[tauri::command]
+pub fn prev_chapter(state: State<'_, Mutex<AppState>>, book: &str, chapter: i32) -> Option<ChapterRef> {
+    adjacent(&state, book, chapter, false)
+}
+
+/// A chapter's verses with the heading to show above them and the chapters
+/// on either side
+#[tauri::command]
+pub fn get_chapter_with_heading(state: State<'_, Mutex<AppState>>, book: &str, chapter: i32) -> Result<Chapter, String> {
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    let verses = db.get_verses_for_chapter(book, chapter);
+    let first = verses.first().ok_or_else(|| format!("There is no {} {}", book, chapter))?;
+    let to_ref = |(book, chapter)| ChapterRef { book, chapter };
+    Ok(Chapter {
+        volume: first.volume_title.to_string(),
+        book: book.to_string(),
+        chapter,
+        title: format!("{} {}", book, chapter),
+        label: chapter_label(book, chapter),
+        verses: verses.iter().map(|s| ScriptureResult::from(*s)).collect(),
+        previous: db.adjacent_chapter(book, chapter, false).map(to_ref),
+        next: db.adjacent_chapter(book, chapter, true).map(to_ref),
+    })
+}
+
+/// Up to `limit` verses starting at `from` ("Alma 32:21"), running on into
+/// the chapters after it
+#[tauri::command]
+pub fn get_verse_page(state: State<'_, Mutex<AppState>>, from: &str, limit: usize) -> Result<VersePage, String> {
+    let state = state.lock().unwrap();
+    let all = state.scripture_db.all_verses();
+    let start = all.iter().position(|s| s.verse_title == from).ok_or_else(|| format!("No verse {}", from))?;
+    let limit = limit.max(1);
+    let end = start.saturating_add(limit).min(all.len());
+    Ok(VersePage {
+        verses: all[start..end].iter().map(ScriptureResult::from).collect(),
+        previous: (start > 0).then(|| all[start.saturating_sub(limit)].verse_title.clone()),
+        next: all.get(end).map(|s| s.verse_title.clone()),
+    })
+}
+
+/// Turn typed text ("alma 32:21-23", "Moroni 10") into a reference and its
+/// verses, for jumping straight to it
+#[tauri::command]
+pub fn resolve_reference(state: State<'_, Mutex<AppState>>, text: &str) -> Result<ResolvedReference, String> {
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    let range = db.parse_reference(text.trim()).ok_or_else(|| format!("Not a reference: {}", text.trim()))?;
+    let verses: Vec<ScriptureResult> = db.get_verses_in_range(&range).into_iter().map(ScriptureResult::from).collect();
+    if verses.is_empty() {
+        return Err(format!("{} isn't in the scriptures", range.display_title()));
+    }
+    Ok(ResolvedReference { reference: ReferenceResult::new(db, &range), verses })
+}
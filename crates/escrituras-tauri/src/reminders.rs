@@ -0,0 +1,85 @@
+//! The daily study reminder
+//!
+//! With `study_reminder` set ("07:30"), once a day at or after that time the
+//! app reminds you of this week's unread Come, Follow Me reading: a
+//! `study-reminder` event for the frontend, and a system notification through
+//! the notification plugin. Nothing is sent once the week's reading is
+//! finished.
+
+use chrono::{Local, NaiveDate, NaiveTime};
+use escrituras_core::{reading_summary, CfmProgress, Config};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::startup::LoadStatus;
+use crate::{AppState, ReferenceResult};
+
+/// How often the reminder time is checked
+const CHECK_EVERY: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Serialize)]
+struct Reminder {
+    title: String,
+    body: String,
+    /// The first unread reading, to open from the reminder
+    reference: ReferenceResult,
+}
+
+/// Check for the reminder time in the background for as long as the app runs
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut reminded: Option<NaiveDate> = None;
+        let mut tick = tokio::time::interval(CHECK_EVERY);
+        loop {
+            tick.tick().await;
+            let now = Local::now();
+            let Some(time) = reminder_time() else {
+                continue;
+            };
+            if reminded == Some(now.date_naive()) || now.time() < time || !loaded(&app) {
+                continue;
+            }
+            reminded = Some(now.date_naive());
+            if let Some(reminder) = todays_reminder(&app) {
+                notify(&app, &reminder);
+                let _ = app.emit("study-reminder", reminder);
+            }
+        }
+    });
+}
+
+fn reminder_time() -> Option<NaiveTime> {
+    let config = Config::load().ok()?;
+    NaiveTime::parse_from_str(config.study_reminder.as_deref()?, "%H:%M").ok()
+}
+
+fn loaded(app: &AppHandle) -> bool {
+    matches!(app.state::<Mutex<AppState>>().lock().unwrap().status, LoadStatus::Ready { .. })
+}
+
+/// "Today's reading: Alma 5–7", or None without a plan or with nothing left to read
+fn todays_reminder(app: &AppHandle) -> Option<Reminder> {
+    let (schedule, current) = crate::plan::load_schedule().ok()?;
+    let progress = CfmProgress::load().ok()?;
+    let week = &schedule.weeks()[current];
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.lock().unwrap();
+    let db = &state.scripture_db;
+    let unread: Vec<_> = week.readings(db).into_iter().filter(|r| !progress.is_done(week, &r.label)).collect();
+    let first = unread.first()?;
+    Some(Reminder {
+        title: week.title.clone(),
+        body: format!("Today's reading: {}", reading_summary(&unread)),
+        reference: ReferenceResult::new(db, &first.range),
+    })
+}
+
+/// Show the reminder as a system notification
+fn notify(app: &AppHandle, reminder: &Reminder) {
+    if let Err(e) = app.notification().builder().title(&reminder.title).body(&reminder.body).show() {
+        tracing::warn!("Couldn't show the study reminder: {}", e);
+    }
+}
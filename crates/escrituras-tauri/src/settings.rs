@@ -0,0 +1,144 @@
+//! Settings, shared with the TUI through `config.toml`
+//!
+//! API keys set here go to the OS keychain rather than the config file
+//! (unless `plaintext_keys` is on), and are never sent back to the frontend:
+//! settings only show each key masked and say where it was found.
+
+use escrituras_core::config::mask_secret;
+use escrituras_core::{keychain, Config, KeySource, Provider};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct KeyStatus {
+    provider: String,
+    /// None when there's no key
+    source: Option<KeySource>,
+    /// The key's first and last few characters
+    masked: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Settings {
+    /// The config profile in use, from ESCRITURAS_PROFILE
+    profile: Option<String>,
+    provider: String,
+    model: String,
+    ollama_url: String,
+    openai_base_url: Option<String>,
+    claude_base_url: Option<String>,
+    theme: Option<String>,
+    search_limit: usize,
+    semantic_search: bool,
+    /// Time of the daily study reminder ("07:30"), when it's on
+    study_reminder: Option<String>,
+    /// Folder the study data is synced through
+    sync_dir: Option<String>,
+    /// Whether keys can be stored in the keychain on this system
+    keychain: bool,
+    keys: Vec<KeyStatus>,
+}
+
+fn load_config() -> Result<Config, String> {
+    if !Config::exists() {
+        return Ok(Config::new());
+    }
+    Config::load().map_err(|e| format!("Could not read the config: {}", e))
+}
+
+fn provider_named(name: &str) -> Result<Provider, String> {
+    Provider::from_str(name).ok_or_else(|| format!("Unknown provider '{}'. Use ollama, claude, or openai", name))
+}
+
+fn save(config: &Config) -> Result<(), String> {
+    config.save().map_err(|e| format!("Could not save the config: {}", e))
+}
+
+fn settings(config: &Config) -> Settings {
+    let (provider, _) = crate::chat::provider_and_model(config);
+    Settings {
+        profile: config.profile_name().map(str::to_string),
+        provider: provider.as_str().to_string(),
+        model: config.model_for(provider),
+        ollama_url: config.ollama_url().to_string(),
+        openai_base_url: config.openai_base_url.clone(),
+        claude_base_url: config.claude_base_url.clone(),
+        theme: config.theme.clone(),
+        search_limit: config.search_defaults().limit,
+        semantic_search: config.semantic_search.unwrap_or(true),
+        study_reminder: config.study_reminder.clone(),
+        sync_dir: config.sync_dir.clone(),
+        keychain: keychain::available(),
+        keys: [Provider::Claude, Provider::OpenAI]
+            .into_iter()
+            .map(|p| {
+                let found = config.find_api_key(p);
+                KeyStatus {
+                    provider: p.as_str().to_string(),
+                    masked: found.as_ref().map(|(key, _)| mask_secret(key)),
+                    source: found.map(|(_, source)| source),
+                }
+            })
+            .collect(),
+    }
+}
+
+#[tauri::command]
+pub fn get_settings() -> Result<Settings, String> {
+    Ok(settings(&load_config()?))
+}
+
+/// Change a setting by its `scriptures config` name ("provider", "model",
+/// "ollama_url", "study_reminder", ...); an empty or missing value clears it.
+/// Keys have their own commands.
+#[tauri::command]
+pub fn update_setting(key: &str, value: Option<&str>) -> Result<Settings, String> {
+    if Config::is_secret(key) {
+        return Err("Set API keys with set_api_key".to_string());
+    }
+    let mut config = load_config()?;
+    config.set(key, value).map_err(|e| e.to_string())?;
+    save(&config)?;
+    Ok(settings(&config))
+}
+
+/// Store a provider's key in the keychain (moving it out of the config file
+/// if the TUI saved one there), or in the config file where the user has
+/// opted into `plaintext_keys`
+#[tauri::command]
+pub fn set_api_key(provider: &str, key: &str) -> Result<Settings, String> {
+    let provider = provider_named(provider)?;
+    let mut config = load_config()?;
+    config.store_api_key(provider, key).map_err(|e| e.to_string())?;
+    save(&config)?;
+    Ok(settings(&config))
+}
+
+/// Remove a provider's key from the keychain and the config file. An
+/// environment variable still supplies one, which the settings show.
+#[tauri::command]
+pub fn remove_api_key(provider: &str) -> Result<Settings, String> {
+    let provider = provider_named(provider)?;
+    let mut config = load_config()?;
+    config.remove_api_key(provider).map_err(|e| e.to_string())?;
+    save(&config)?;
+    Ok(settings(&config))
+}
+
+/// Check a provider answers with the key it would use (for Ollama, that the
+/// server is running), returning a line to show
+#[tauri::command]
+pub async fn test_api_key(provider: &str) -> Result<String, String> {
+    let provider = provider_named(provider)?;
+    let config = load_config()?;
+    if provider == Provider::Ollama {
+        let models = config.ollama_client().list_models().await.map_err(|e| format!("Ollama isn't answering at {}: {}", config.ollama_url(), e))?;
+        return Ok(format!("Ollama is running with {} models", models.len()));
+    }
+    let key = config.api_key(provider).ok_or_else(|| format!("No {} API key", provider.display_name()))?;
+    let checked = match provider {
+        Provider::Claude => config.claude_client(&key).check_key().await,
+        _ => config.openai_client(&key).check_key().await,
+    };
+    checked.map_err(|e| e.to_string())?;
+    Ok(format!("The {} key works", provider.display_name()))
+}
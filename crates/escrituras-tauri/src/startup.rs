@@ -0,0 +1,99 @@
+//! Loading the scriptures and embeddings once the window is up
+//!
+//! Data loads on a background task that reports each step to the frontend as
+//! a `load-progress` event and finishes with `load-ready` or `load-error`.
+//! The payload of each is a `LoadStatus`, which `app_status` also returns for
+//! a frontend that starts listening late.
+
+use anyhow::{anyhow, Result};
+use escrituras_core::{embedding_model_cached, setup, EmbeddingsDb, ScriptureDb};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::AppState;
+
+/// How far startup loading has got
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum LoadStatus {
+    Loading { message: String },
+    Ready { verses: usize, embeddings: bool },
+    Failed { message: String },
+}
+
+impl LoadStatus {
+    fn loading(message: &str) -> Self {
+        LoadStatus::Loading { message: message.to_string() }
+    }
+
+    fn event(&self) -> &'static str {
+        match self {
+            LoadStatus::Loading { .. } => "load-progress",
+            LoadStatus::Ready { .. } => "load-ready",
+            LoadStatus::Failed { .. } => "load-error",
+        }
+    }
+}
+
+/// Record the status for `app_status` and tell the frontend
+fn report(app: &AppHandle, status: LoadStatus) {
+    app.state::<Mutex<AppState>>().lock().unwrap().status = status.clone();
+    let _ = app.emit(status.event(), status);
+}
+
+/// Start loading in the background
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        match load(&app).await {
+            Ok((scripture_db, embeddings_db)) => {
+                let ready = LoadStatus::Ready { verses: scripture_db.all_verses().len(), embeddings: embeddings_db.is_some() };
+                {
+                    let state = app.state::<Mutex<AppState>>();
+                    let mut state = state.lock().unwrap();
+                    state.scripture_db = scripture_db;
+                    state.embeddings_db = embeddings_db;
+                }
+                report(&app, ready);
+                crate::links::open_pending(&app);
+            }
+            Err(e) => report(&app, LoadStatus::Failed { message: e.to_string() }),
+        }
+    });
+}
+
+/// Find the scripture data (downloading it when it's missing) and load it,
+/// with the embeddings when they're installed
+async fn load(app: &AppHandle) -> Result<(ScriptureDb, Option<EmbeddingsDb>)> {
+    report(app, LoadStatus::loading("Looking for scripture data"));
+    let path = match setup::find_scripture_data() {
+        Some(path) => path,
+        None => {
+            report(app, LoadStatus::loading("Downloading scripture data"));
+            setup::download_release_data()
+                .await
+                .map_err(|e| anyhow!("Scripture data isn't installed and couldn't be downloaded: {}", e))?
+        }
+    };
+
+    report(app, LoadStatus::loading("Loading scriptures"));
+    let mut db = ScriptureDb::new();
+    db.load_from_json(&path.to_string_lossy())
+        .await
+        .map_err(|e| anyhow!("Couldn't read the scripture data at {}: {}", path.display(), e))?;
+
+    report(app, LoadStatus::loading("Loading embeddings"));
+    let embeddings = tokio::task::spawn_blocking(load_embeddings).await?;
+    Ok((db, embeddings))
+}
+
+/// The embeddings, with the model loaded when it's already downloaded.
+/// Without them the app works with keyword search only.
+pub fn load_embeddings() -> Option<EmbeddingsDb> {
+    let dir = setup::find_data_dir(EmbeddingsDb::FILE_NAME)?;
+    let mut embeddings = EmbeddingsDb::load(&dir).ok()?;
+    if embedding_model_cached() {
+        embeddings.ensure_model().ok()?;
+    }
+    Some(embeddings)
+}
@@ -0,0 +1,210 @@
+//! Bookmarks, highlights, and notes, stored where the TUI keeps them
+//!
+//! Bookmarks are the saved scriptures with their tags and highlights are
+//! colors by verse, both in the study data store (`study.db`); notes are
+//! journal entries (`journal/*.md`). So study done in either app shows up in
+//! the other. Each command reads them afresh rather than caching them, since
+//! the TUI may have changed them since.
+//!
+//! With `sync_dir` set, the app syncs with that folder as it starts, and
+//! `sync_study_data` syncs again (say after a round of changes, or on close).
+
+use escrituras_core::sync::{self, SyncSummary};
+use escrituras_core::{Config, Journal, JournalEntry, SavedScriptures, Scripture, Store};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::{AppState, ScriptureResult};
+
+#[derive(Serialize)]
+pub struct Bookmark {
+    verse: ScriptureResult,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SyncResult {
+    /// Records changed here, and in the folder
+    pulled: usize,
+    pushed: usize,
+    /// Records changed differently here and elsewhere, as "highlight Alma 32:21"
+    conflicts: Vec<String>,
+}
+
+impl From<SyncSummary> for SyncResult {
+    fn from(summary: SyncSummary) -> Self {
+        Self { pulled: summary.pulled, pushed: summary.pushed, conflicts: summary.conflicts }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Note {
+    path: String,
+    /// When it was written, as `2026-10-15T14:32:05`
+    created: String,
+    title: String,
+    content: String,
+}
+
+impl From<&JournalEntry> for Note {
+    fn from(entry: &JournalEntry) -> Self {
+        Self {
+            path: entry.path.to_string_lossy().into_owned(),
+            created: entry.created.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            title: entry.title.clone(),
+            content: entry.content.clone(),
+        }
+    }
+}
+
+/// The verses of a reference, or an error naming it
+fn verses_of(state: &Mutex<AppState>, reference: &str) -> Result<Vec<Scripture>, String> {
+    let state = state.lock().unwrap();
+    let range = state.scripture_db.parse_reference(reference).ok_or_else(|| format!("Not a reference: {}", reference))?;
+    Ok(state.scripture_db.get_verses_in_range(&range).into_iter().cloned().collect())
+}
+
+fn load_saved() -> Result<SavedScriptures, String> {
+    SavedScriptures::load().map_err(|e| format!("Could not read saved scriptures: {}", e))
+}
+
+fn store_saved(loaded: &SavedScriptures, saved: &SavedScriptures) -> Result<(), String> {
+    saved.save(loaded).map_err(|e| format!("Could not save scriptures: {}", e))
+}
+
+/// Every bookmarked verse in saved order, with its tags
+#[tauri::command]
+pub fn list_bookmarks() -> Result<Vec<Bookmark>, String> {
+    let saved = load_saved()?;
+    Ok(saved
+        .verses
+        .iter()
+        .map(|v| Bookmark {
+            verse: ScriptureResult::from(v),
+            tags: saved.tags.get(&v.verse_title).cloned().unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Bookmark each verse of a reference, tagging them with `tags` ("faith,
+/// #lesson-prep") when given. Returns how many weren't bookmarked already.
+#[tauri::command]
+pub fn add_bookmark(state: State<'_, Mutex<AppState>>, reference: &str, tags: Option<&str>) -> Result<usize, String> {
+    let verses = verses_of(&state, reference)?;
+    let loaded = load_saved()?;
+    let mut saved = loaded.clone();
+    let titles: Vec<String> = verses.iter().map(|v| v.verse_title.clone()).collect();
+    let added = saved.add(verses);
+    if let Some(tags) = tags {
+        for title in &titles {
+            saved.set_tags(title, SavedScriptures::parse_tags(tags));
+        }
+    }
+    store_saved(&loaded, &saved)?;
+    Ok(added)
+}
+
+/// Replace a bookmarked verse's tags (an empty string removes them) and
+/// return them as stored
+#[tauri::command]
+pub fn set_bookmark_tags(verse_title: &str, tags: &str) -> Result<Vec<String>, String> {
+    let loaded = load_saved()?;
+    let mut saved = loaded.clone();
+    if !saved.verses.iter().any(|v| v.verse_title == verse_title) {
+        return Err(format!("{} isn't bookmarked", verse_title));
+    }
+    let tags = SavedScriptures::parse_tags(tags);
+    saved.set_tags(verse_title, tags.clone());
+    store_saved(&loaded, &saved)?;
+    Ok(tags)
+}
+
+/// Remove a bookmark and its tags; false when the verse wasn't bookmarked
+#[tauri::command]
+pub fn remove_bookmark(verse_title: &str) -> Result<bool, String> {
+    let loaded = load_saved()?;
+    let mut saved = loaded.clone();
+    let removed = saved.remove(verse_title);
+    if removed {
+        store_saved(&loaded, &saved)?;
+    }
+    Ok(removed)
+}
+
+fn open_store() -> Result<Store, String> {
+    Store::open().map_err(|e| format!("Could not open the study data: {}", e))
+}
+
+/// Highlight colors by verse title
+#[tauri::command]
+pub fn list_highlights() -> Result<BTreeMap<String, String>, String> {
+    open_store()?.highlights().map_err(|e| e.to_string())
+}
+
+/// Highlight each verse of a reference in `color` (one of `HIGHLIGHT_COLORS`),
+/// or with no color remove their highlights
+#[tauri::command]
+pub fn set_highlight(state: State<'_, Mutex<AppState>>, reference: &str, color: Option<&str>) -> Result<(), String> {
+    let verses = verses_of(&state, reference)?;
+    let mut store = open_store()?;
+    for verse in &verses {
+        store.set_highlight(&verse.verse_title, color).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Sync with the `sync_dir` folder; None when there isn't one
+#[tauri::command]
+pub fn sync_study_data() -> Result<Option<SyncResult>, String> {
+    let config = Config::load().map_err(|e| format!("Could not read the config: {}", e))?;
+    let Some(dir) = config.sync_dir else {
+        return Ok(None);
+    };
+    let summary = sync::sync(Path::new(&dir)).map_err(|e| format!("Could not sync with {}: {}", dir, e))?;
+    Ok(Some(summary.into()))
+}
+
+/// Journal entries about any verse of a reference, newest first
+#[tauri::command]
+pub fn list_notes(state: State<'_, Mutex<AppState>>, reference: &str) -> Result<Vec<Note>, String> {
+    let verses = verses_of(&state, reference)?;
+    let journal = Journal::load().map_err(|e| format!("Could not read the journal: {}", e))?;
+    let state = state.lock().unwrap();
+    Ok(journal
+        .entries
+        .iter()
+        .filter(|entry| verses.iter().any(|v| entry.is_about(&state.scripture_db, v)))
+        .map(Note::from)
+        .collect())
+}
+
+/// Write a journal entry quoting the verses of a reference, followed by `note`
+#[tauri::command]
+pub fn add_note(state: State<'_, Mutex<AppState>>, reference: &str, note: &str) -> Result<Note, String> {
+    let verses = verses_of(&state, reference)?;
+    let mut journal = Journal::load().map_err(|e| format!("Could not read the journal: {}", e))?;
+    let path = journal.add_note(&verses, note).map_err(|e| format!("Could not write the note: {}", e))?;
+    journal.reload().map_err(|e| e.to_string())?;
+    journal
+        .entries
+        .iter()
+        .find(|e| e.path == path)
+        .map(Note::from)
+        .ok_or_else(|| format!("Could not read back {}", path.display()))
+}
+
+/// Delete a journal entry by its path (only ones in the journal directory)
+#[tauri::command]
+pub fn delete_note(path: &str) -> Result<(), String> {
+    let mut journal = Journal::load().map_err(|e| format!("Could not read the journal: {}", e))?;
+    let index = journal
+        .entries
+        .iter()
+        .position(|e| e.path == Path::new(path))
+        .ok_or_else(|| format!("No journal entry at {}", path))?;
+    journal.delete(index).map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -1,9 +1,20 @@
 use ratatui::layout::Rect;
+use futures_util::FutureExt;
 use ratatui::widgets::ListState;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use crate::keymap::Keymap;
+use crate::theme::{ColorSupport, Theme};
+use crate::toast::{ToastLevel, Toasts};
+use escrituras_core::ai::prompt::{context_block, history_block, question_block, PROMPT_CONTEXT_VERSES};
+use escrituras_core::events::{self, Event};
+use escrituras_core::front_matter::FRONT_MATTER_CHAPTER;
+use escrituras_core::parallels;
+use escrituras_core::setup::find_data_dir;
 use escrituras_core::{
-    ChatMessage, ClaudeClient, Config, EmbeddingsDb, OllamaClient, OpenAIClient, Provider,
-    Scripture, ScriptureDb, ScriptureRange,
+    context_window, estimate_tokens, CfmProgress, CfmReading, CfmSchedule, CfmWeek, ChatMessage, ChatRole, Alignment, ClaudeClient, CommandVoice, Config, ConversationHistory, CopyFormat, Deck, Dictionary, EmbeddingsDb, EscriturasError, Footnote, FootnoteDb, FrontMatter, HymnDb, Interlinear, Journal, Library, MemorizeHistory, OllamaClient, OpenAIClient, Outline, OutlineCache, PaneLayout,
+    Provider, ReadAloud, Scripture, ScriptureDb, ScriptureRange, SavedScriptures, SearchFilter, SearchHit, SearchMode, SemanticQuery, SessionState, StrongsEntry, TaggedWord, Talk, TalkDb, TalkHit, Title, TopicIndex, TopicPage, TOPICS, todays_verse, DECKS, HYMN_VOLUME, LIBRARY_VOLUME,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,6 +23,11 @@ pub enum Screen {
     Search,
     Query,
     Focus,
+    Journal,
+    Talks,
+    ComeFollowMe,
+    Topics,
+    Reading,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -26,6 +42,14 @@ pub enum MemorizeMode {
     #[default]
     Progressive,
     Flashcard,
+    FirstLetter, // Each word reduced to its first letter
+}
+
+impl MemorizeMode {
+    /// Whether the mode supports typing an attempt and scoring it against the text
+    pub fn supports_typing(&self) -> bool {
+        matches!(self, MemorizeMode::Flashcard | MemorizeMode::FirstLetter)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -36,6 +60,155 @@ pub enum FlashcardPhase {
     Revealed, // Shows actual text with diff highlighting
 }
 
+/// A file for the main loop to open in $EDITOR (it owns the terminal)
+#[derive(Debug, Clone)]
+pub struct EditorRequest {
+    pub path: PathBuf,
+    pub template: Option<String>, // Initial text of a new entry, to discard it if left untouched
+}
+
+/// Actions available from the command palette (Ctrl-P)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    GotoReference,
+    Search,
+    AskAi,
+    Browse,
+    ToggleSaved,
+    FocusVerse,
+    VisualSelect,
+    CopyVerse,
+    CopyWithFormat,
+    QuoteCard,
+    SaveVerse,
+    ClearSaved,
+    NewConversation,
+    NewJournalEntry,
+    OpenJournal,
+    OpenTalks,
+    ComeFollowMe,
+    Topics,
+    ReadingMode,
+    ReadAloud,
+    CompactLayout,
+    RelatedVerses,
+    ViewParallel,
+    ChapterOutline,
+    SwitchProvider,
+    SwitchModel,
+    Quit,
+}
+
+impl PaletteAction {
+    pub fn all() -> &'static [PaletteAction] {
+        &[
+            PaletteAction::GotoReference,
+            PaletteAction::Search,
+            PaletteAction::AskAi,
+            PaletteAction::Browse,
+            PaletteAction::ToggleSaved,
+            PaletteAction::FocusVerse,
+            PaletteAction::VisualSelect,
+            PaletteAction::CopyVerse,
+            PaletteAction::CopyWithFormat,
+            PaletteAction::QuoteCard,
+            PaletteAction::SaveVerse,
+            PaletteAction::ClearSaved,
+            PaletteAction::NewConversation,
+            PaletteAction::NewJournalEntry,
+            PaletteAction::OpenJournal,
+            PaletteAction::OpenTalks,
+            PaletteAction::ComeFollowMe,
+            PaletteAction::Topics,
+            PaletteAction::ReadingMode,
+            PaletteAction::ReadAloud,
+            PaletteAction::CompactLayout,
+            PaletteAction::RelatedVerses,
+            PaletteAction::ViewParallel,
+            PaletteAction::ChapterOutline,
+            PaletteAction::SwitchProvider,
+            PaletteAction::SwitchModel,
+            PaletteAction::Quit,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaletteAction::GotoReference => "Go to reference",
+            PaletteAction::Search => "Search scriptures",
+            PaletteAction::AskAi => "Ask AI",
+            PaletteAction::Browse => "Browse scriptures",
+            PaletteAction::ToggleSaved => "Toggle saved scriptures",
+            PaletteAction::FocusVerse => "Focus on selected verse",
+            PaletteAction::VisualSelect => "Select verse range (visual mode)",
+            PaletteAction::CopyVerse => "Copy selected verse",
+            PaletteAction::CopyWithFormat => "Copy selected verse as...",
+            PaletteAction::QuoteCard => "Save quote card image",
+            PaletteAction::SaveVerse => "Save selected verse",
+            PaletteAction::ClearSaved => "Clear all saved scriptures",
+            PaletteAction::NewConversation => "New AI conversation",
+            PaletteAction::NewJournalEntry => "New journal entry",
+            PaletteAction::OpenJournal => "Open journal",
+            PaletteAction::OpenTalks => "Browse conference talks",
+            PaletteAction::ComeFollowMe => "Come, Follow Me this week",
+            PaletteAction::Topics => "Study topics",
+            PaletteAction::ReadingMode => "Reading mode",
+            PaletteAction::ReadAloud => "Read chapter aloud / pause",
+            PaletteAction::CompactLayout => "Toggle compact layout",
+            PaletteAction::RelatedVerses => "Toggle related verses",
+            PaletteAction::ViewParallel => "View parallel passage (Isaiah, Matthew)",
+            PaletteAction::ChapterOutline => "Toggle chapter outline",
+            PaletteAction::SwitchProvider => "Switch AI provider",
+            PaletteAction::SwitchModel => "Switch AI model",
+            PaletteAction::Quit => "Quit",
+        }
+    }
+}
+
+/// Study data read at startup, or none (with a warning in the log) when it can't be read
+fn load_or_default<T: Default>(what: &str, loaded: escrituras_core::error::Result<T>) -> T {
+    loaded.unwrap_or_else(|e| {
+        tracing::warn!("Couldn't read {}: {}", what, e);
+        T::default()
+    })
+}
+
+/// A failed answer as shown in the chat, with what to do about it
+pub fn chat_error(e: &EscriturasError) -> String {
+    let hint = match e {
+        EscriturasError::ProviderAuth { .. } => Some("Press 'P' to set up a key".to_string()),
+        _ => e.hint(),
+    };
+    match hint {
+        Some(hint) => format!("Error: {}\n\n{}.", e, hint),
+        None => format!("Error: {}", e),
+    }
+}
+
+/// Score a fuzzy (subsequence) match of `query` in `text`; higher is better.
+/// Rewards consecutive characters and matches at word starts.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for qc in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let idx = (pos..text.len()).find(|&i| text[i] == qc)?;
+        score += 1;
+        if prev_match == Some(idx.wrapping_sub(1)) {
+            score += 5; // Consecutive run
+        }
+        if idx == 0 || text[idx - 1] == ' ' {
+            score += 3; // Word start
+        }
+        prev_match = Some(idx);
+        pos = idx + 1;
+    }
+
+    Some(score)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
@@ -55,6 +228,8 @@ pub enum FocusPane {
     Content,
     References,
     Input,  // Query input box (AI mode only)
+    Related, // Related-verses panel (Browse only)
+    Outline, // Chapter outline panel (Browse only)
 }
 
 // ChatMessage and ChatRole are re-exported from escrituras_core
@@ -67,6 +242,89 @@ pub enum SearchFocus {
     Input,  // Search input field
 }
 
+/// Webster 1828 definitions for the words of the selected verse
+#[derive(Debug, Clone)]
+pub struct WordLookup {
+    pub reference: String,
+    pub words: Vec<String>, // Words with a definition, in verse order
+    pub state: ListState,
+    pub scroll: u16,        // Definition scroll
+}
+
+/// Strong's numbers and definitions for the words of the selected Bible verse
+#[derive(Debug, Clone)]
+pub struct StrongsLookup {
+    pub reference: String,
+    pub words: Vec<TaggedWord>, // Words with Strong's numbers, in verse order
+    pub state: ListState,
+    pub scroll: u16,           // Definition scroll
+}
+
+/// A Strong's number and its lexicon entry (None if the lexicon lacks it)
+pub type StrongsDefinition<'a> = (&'a str, Option<&'a StrongsEntry>);
+
+/// Reading mode: consecutive chapters reflowed into paragraphs
+#[derive(Debug, Clone, Default)]
+pub struct ReadingView {
+    pub chapters: Vec<(String, i32)>, // Loaded (book, chapter), in order
+    pub lines: Vec<String>,           // Heading and reflowed text of every loaded chapter
+    pub chapter_starts: Vec<usize>,   // Heading line of each chapter in `lines`
+    pub width: usize,                 // Text width `lines` were wrapped to
+    pub scroll: usize,
+    pub visible_height: usize,
+}
+
+/// Depth of the talk browser's list: speakers, then a speaker's years, then their talks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TalkLevel {
+    #[default]
+    Speaker,
+    Year,
+    Talk,
+}
+
+/// Conference talk browser state
+#[derive(Debug, Clone, Default)]
+pub struct TalksView {
+    pub level: TalkLevel,
+    pub speaker: Option<String>,
+    pub year: Option<i32>,
+    pub list_state: ListState,
+    pub open_talk: Option<usize>,   // Talk shown in the reading pane
+    pub paragraph: usize,           // Highlighted paragraph of the open talk
+    pub text_focused: bool,         // Reading pane has focus (vs. the list)
+    pub scroll: u16,
+    pub visible_height: usize,
+    pub search_input: String,
+    pub editing: bool,              // Typing in the search box
+    pub semantic: bool,
+    pub results: Option<Vec<TalkHit>>, // Search results replace the browse list
+}
+
+/// Study topic pages: the topic list, and the page of the topic opened from it
+#[derive(Debug, Clone, Default)]
+pub struct TopicsView {
+    pub list_state: ListState,   // Highlighted topic
+    pub page: Option<TopicPage>, // Page of the opened topic
+    pub page_state: ListState,   // Highlighted row of the page
+    pub page_focused: bool,      // Page has focus (vs. the topic list)
+}
+
+/// A row of a topic page
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopicRow {
+    Heading(String),
+    Verse(String), // Verse title
+    Talk(usize),   // Index into the page's talks
+}
+
+/// Which search filter dropdown is open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterDropdown {
+    Volume,
+    Book,
+}
+
 /// Direction of last scroll movement (for verse positioning in view)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ScrollDirection {
@@ -100,6 +358,114 @@ pub struct FocusState {
     pub flashcard_input: String,
     /// Cursor position in flashcard input
     pub flashcard_input_cursor: usize,
+    /// Verses being studied (just current_verse unless a multi-verse range was selected)
+    pub passage: Vec<Scripture>,
+    /// Temporarily showing the full text in first-letter mode
+    pub first_letter_peek: bool,
+    /// The doctrinal mastery deck being reviewed, which `j`/`k` step through
+    pub deck: Option<DeckReview>,
+}
+
+/// A deck's passages in review order, and the one being studied
+#[derive(Debug, Clone)]
+pub struct DeckReview {
+    pub deck: &'static Deck,
+    pub passages: Vec<Vec<Scripture>>,
+    pub position: usize,
+}
+
+impl FocusState {
+    /// Whether a multi-verse passage is being studied
+    pub fn is_passage(&self) -> bool {
+        self.passage.len() > 1
+    }
+
+    /// Reference for the verse or passage (e.g., "Mosiah 4:19-21")
+    pub fn passage_title(&self) -> String {
+        match (self.passage.first(), self.passage.last()) {
+            (Some(first), Some(last)) if self.is_passage() => format!(
+                "{} {}:{}-{}",
+                first.book_title, first.chapter_number, first.verse_number, last.verse_number
+            ),
+            _ => self.current_verse.verse_title.clone(),
+        }
+    }
+
+    /// Text of the whole passage joined into one string (for flashcard comparison)
+    pub fn passage_text(&self) -> String {
+        self.passage
+            .iter()
+            .map(|v| v.scripture_text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Second chapter shown beside the main one in split view
+#[derive(Debug, Clone)]
+pub struct SplitPane {
+    pub verses: Vec<Scripture>,
+    pub selected: usize, // Index of the verse under the cursor
+    pub scroll: usize,   // Top line of the pane (updated during render)
+    pub alignment: Option<Alignment>, // Verses lined up with the main chapter's, for a parallel chapter
+}
+
+impl SplitPane {
+    pub fn title(&self) -> String {
+        let title = self.verses.first().map(|v| format!("{} {}", v.book_title, v.chapter_number)).unwrap_or_default();
+        if self.alignment.is_some() {
+            format!("{} · parallel", title)
+        } else {
+            title
+        }
+    }
+}
+
+/// The semantic pass of a search, running in the background
+pub struct SearchTask {
+    query: SemanticQuery,
+    announce: bool, // A search asked for with Enter, not a preview while typing
+    handle: tokio::task::JoinHandle<escrituras_core::error::Result<Vec<(String, f32)>>>,
+}
+
+/// Verses listed in the related-verses panel
+const RELATED_VERSES: usize = 10;
+
+/// An AI prompt, in the parts the usage indicator counts separately
+#[derive(Debug, Clone, Default)]
+pub struct QueryPrompt {
+    pub instructions: String, // Study instructions and what the user is reading
+    pub context: String,      // Saved verses and the selected passage
+    pub history: String,
+    pub question: String,
+}
+
+impl QueryPrompt {
+    pub fn text(&self) -> String {
+        [self.instructions.as_str(), &self.context, &self.history, &self.question].concat()
+    }
+}
+
+/// Estimated size of the prompt the next question will send
+#[derive(Debug, Clone, Copy)]
+pub struct PromptUsage {
+    pub instructions: usize,
+    pub context: usize,
+    pub history: usize,
+    pub question: usize,
+    pub window: usize,         // The model's context window
+    pub dropped_verses: usize, // Saved verses beyond PROMPT_CONTEXT_VERSES
+}
+
+impl PromptUsage {
+    pub fn total(&self) -> usize {
+        self.instructions + self.context + self.history + self.question
+    }
+
+    /// The prompt is longer than the model will read
+    pub fn overflows(&self) -> bool {
+        self.total() > self.window
+    }
 }
 
 /// Saved navigation state for returning to previous location
@@ -144,21 +510,34 @@ pub struct App {
 
     // Search state
     pub search_input: String,
-    pub search_results: Vec<Scripture>,
+    pub search_results: Vec<SearchHit>,
     pub search_state: ListState,
     pub search_focus: SearchFocus,
+    pub search_filter: SearchFilter,
+    pub search_typed_at: Option<Instant>, // Last keystroke of a live search not yet run
+    pub search_task: Option<SearchTask>,  // Semantic pass still running; results so far are keyword only
+    pub filter_dropdown: Option<FilterDropdown>,
+    pub filter_dropdown_state: ListState,
 
     // AI Query state (chat history)
     pub query_input: String,
     pub query_cursor: usize, // cursor position in query_input
+    pub query_input_width: usize, // inner width of the input box, for moving between wrapped rows
     pub chat_messages: Vec<ChatMessage>,
     pub query_loading: bool,
     pub query_scroll: u16,
     pub query_chat_height: u16, // Height of chat area for scroll calculations
     pub query_chat_width: u16,  // Width of chat area for wrap calculations
-    pub query_task: Option<tokio::task::JoinHandle<anyhow::Result<String>>>,
+    pub query_task: Option<tokio::task::JoinHandle<escrituras_core::error::Result<String>>>,
+    pub query_tokens: Option<tokio::sync::mpsc::UnboundedReceiver<String>>, // Streamed response chunks
+    pub streaming_response: String, // Partial response received so far
     pub extracted_references: Vec<ScriptureRange>,
     pub references_state: ListState,
+    // Archived conversations; while one is shown, the live thread waits in live_chat
+    pub conversations: ConversationHistory,
+    stored_conversations: ConversationHistory, // As last loaded or saved, so saving writes only our changes
+    pub conversation_index: Option<usize>,
+    pub live_chat: Vec<ChatMessage>,
 
     // Navigation history (for returning after jumping to references)
     pub navigation_stack: Vec<NavigationState>,
@@ -167,27 +546,50 @@ pub struct App {
     pub selected_verse_idx: Option<usize>,
     // Range selection (when jumping from References - highlights multiple verses)
     pub selected_range: Option<ScriptureRange>,
+    // Visual mode anchor (selection runs from this verse index to the cursor)
+    pub visual_anchor: Option<usize>,
+    // Verse block attached to the next AI question (sent from visual mode)
+    pub pending_passage: Vec<Scripture>,
+    // Split view: a second chapter beside the content pane
+    pub split: Option<SplitPane>,
+    pub split_focused: bool,
+    pub goto_into_split: bool, // Goto prompt is choosing the split chapter
 
     // Session context
     pub session_context: Vec<Scripture>,
+    pub saved_tags: BTreeMap<String, Vec<String>>, // Tags by verse title
+    stored_saved: SavedScriptures, // As last loaded or saved, so saving writes only our changes
+    pub tag_filter: Option<String>,      // Only this tag's verses in the panel and AI context
+    pub tag_input: Option<String>,       // Tag prompt for the selected saved verse, while open
+    pub tag_cursor: usize,
     pub context_state: ListState,        // For navigating context list
     pub show_context_panel: bool,        // Toggle between scripture and context view
 
     // Browsed chapters (for AI context, lightweight tracking)
-    pub browsed_chapters: Vec<(String, i32)>,  // (book_title, chapter_number)
+    pub browsed_chapters: Vec<(Title, i32)>,  // (book_title, chapter_number)
+    viewed_verse: Option<String>, // Last verse announced as viewed
+
+    // Transient status messages (bottom-right)
+    pub toasts: Toasts,
 
     // Animation state
     pub animation_frame: u8, // 0-2 for ellipsis animation
+    pub needs_redraw: bool,  // Something on screen changed since the last draw
 
     // Model picker state
     pub show_model_picker: bool,
+    pub regenerate_after_model_pick: bool, // Model picker was opened with 'R'
     pub available_models: Vec<String>,
     pub model_picker_state: ListState,
 
     // Provider state
     pub current_provider: Provider,
+    /// Config profile in use (`--profile` or ESCRITURAS_PROFILE), shown in the header
+    pub profile: Option<String>,
     pub claude_client: Option<ClaudeClient>,
     pub openai_client: Option<OpenAIClient>,
+    /// Opening of every prompt (`prompt_template` in the config)
+    pub study_instructions: String,
     pub show_provider_picker: bool,
     pub provider_picker_state: ListState,
 
@@ -197,6 +599,58 @@ pub struct App {
     pub api_key_input_cursor: usize,
     pub api_key_target_provider: Option<Provider>,
 
+    // Help overlay state
+    pub show_help: bool,
+    pub help_scroll: u16,
+
+    // Active keybindings and colors
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub colors: ColorSupport,
+
+    // Pane sizes (persisted in config)
+    pub layout: PaneLayout,
+
+    // Clipboard format and the copy-format menu
+    pub copy_format: CopyFormat,
+    pub gospel_library_language: String, // Of copied and opened links
+    pub show_copy_menu: bool,
+    pub copy_menu_state: ListState,
+    pub copy_menu_verses: Vec<Scripture>, // Verses the menu will copy
+
+    // Footnotes for the selected verse (when footnotes.json is installed)
+    pub footnotes: Option<FootnoteDb>,
+    pub footnote_state: ListState,
+
+    // Verses most similar to the selected one (from the embeddings), for the related panel
+    pub related: Vec<(String, f32)>,
+    pub related_for: Option<String>, // Verse title `related` was computed for
+    pub related_state: ListState,
+
+    // Outline of the chapter being read, for the outline panel
+    pub outline: Option<Outline>,
+    pub outline_collapsed: HashSet<usize>, // Sections showing only their title
+    pub outline_state: ListState,
+    pub outline_task: Option<tokio::task::JoinHandle<escrituras_core::error::Result<Outline>>>,
+
+    // Webster 1828 word lookup (dictionary loaded on first use)
+    pub dictionary: Option<Dictionary>,
+    pub word_lookup: Option<WordLookup>,
+    // Interlinear Strong's lookup (data loaded on first use)
+    pub interlinear: Option<Interlinear>,
+    pub strongs_lookup: Option<StrongsLookup>,
+
+    // Command palette state
+    pub show_command_palette: bool,
+    pub palette_input: String,
+    pub palette_state: ListState,
+
+    // Go-to-reference prompt state
+    pub show_goto_input: bool,
+    pub goto_input: String,
+    pub goto_cursor: usize,
+    pub goto_error: Option<String>,
+
     // Panel areas for mouse hit-testing (updated during render)
     pub nav_area: Option<Rect>,
     pub content_area: Option<Rect>,
@@ -204,6 +658,40 @@ pub struct App {
 
     // Focus mode state
     pub focus_state: Option<FocusState>,
+    pub memorize_history: MemorizeHistory,  // Typed attempt scores per verse
+
+    // Study journal
+    pub journal: Journal,
+    pub journal_state: ListState,
+    pub journal_scroll: u16,
+    pub journal_visible_height: usize,
+    pub editor_request: Option<EditorRequest>,
+
+    // Verse-of-the-day splash shown at launch until a key is pressed
+    pub votd: Option<Scripture>,
+
+    // Hymns (when hymns.json is installed), browsed as a volume of the scriptures
+    pub hymns: Option<HymnDb>,
+
+    // Books imported from EPUB, browsed as a volume too
+    pub library: Library,
+
+    // The chapter being read aloud, playing or paused
+    pub read_aloud: Option<ReadAloud>,
+
+    // Conference talks (when talks.json is installed)
+    pub talks: Option<TalkDb>,
+    pub talks_view: TalksView,
+    pub topics_view: TopicsView,
+
+    // Reading mode
+    pub reading: ReadingView,
+
+    // Come, Follow Me schedule (when come_follow_me.json is installed)
+    pub cfm: Option<CfmSchedule>,
+    pub cfm_progress: CfmProgress,
+    pub cfm_week: usize, // Week shown on the Come, Follow Me screen
+    pub cfm_state: ListState,
 
     // Data
     pub scripture_db: ScriptureDb,
@@ -212,8 +700,8 @@ pub struct App {
     pub selected_model: String,
 
     // Cached navigation data
-    pub cached_volumes: Vec<String>,
-    pub cached_books: Vec<String>,
+    pub cached_volumes: Vec<Title>,
+    pub cached_books: Vec<Title>,
     pub cached_chapters: Vec<i32>,
     pub cached_verses: Vec<Scripture>,
 }
@@ -222,68 +710,96 @@ impl App {
     pub async fn new() -> anyhow::Result<Self> {
         let mut scripture_db = ScriptureDb::new();
 
-        // Try local path first, then config directory
-        let local_path = "lds-scriptures-2020.12.08/json/lds-scriptures-json.txt";
-        let config_path = dirs::config_dir()
-            .map(|p| p.join("escrituras/lds-scriptures-2020.12.08/json/lds-scriptures-json.txt"));
+        // Try local path first, then the data directory
+        let scripture_path = escrituras_core::setup::find_scripture_data()
+            .ok_or_else(|| anyhow::anyhow!("Scripture data not found. Run `scriptures --setup` to install it"))?;
+        scripture_db.load_from_json(&scripture_path.to_string_lossy()).await?;
 
-        if std::path::Path::new(local_path).exists() {
-            scripture_db.load_from_json(local_path).await?;
-        } else if let Some(ref cfg_path) = config_path {
-            if cfg_path.exists() {
-                scripture_db.load_from_json(cfg_path.to_str().unwrap()).await?;
-            } else {
-                anyhow::bail!("Scripture data not found. Run install.sh or place data in lds-scriptures-2020.12.08/");
+        // Title pages, introductions, and witnesses go before their books as chapter 0
+        if let Some(dir) = find_data_dir(FrontMatter::FILE_NAME) {
+            match FrontMatter::load(&dir) {
+                Ok(front_matter) => front_matter.insert_into(&mut scripture_db),
+                Err(e) => tracing::warn!("Couldn't load front matter from {}: {}", dir.display(), e),
             }
-        } else {
-            anyhow::bail!("Scripture data not found. Run install.sh or place data in lds-scriptures-2020.12.08/");
         }
 
-        let ollama = OllamaClient::new("http://localhost:11434");
+        // Hymn stanzas join the scriptures as a last volume, and their
+        // cross-references join the footnotes
+        let mut footnotes = find_data_dir(FootnoteDb::FILE_NAME).and_then(|dir| FootnoteDb::load(&dir).ok());
+        let hymns = find_data_dir(HymnDb::FILE_NAME).and_then(|dir| {
+            HymnDb::load(&dir).map_err(|e| tracing::warn!("Couldn't load hymns from {}: {}", dir.display(), e)).ok()
+        });
+        if let Some(hymns) = &hymns {
+            scripture_db.append(hymns.as_scriptures());
+            footnotes.get_or_insert_with(FootnoteDb::default).extend(hymns.footnotes(&scripture_db));
+        }
+
+        // Books imported into the library follow as a volume of their own
+        let library = find_data_dir(Library::DIR_NAME)
+            .map(|dir| {
+                Library::load(&dir).unwrap_or_else(|e| {
+                    tracing::warn!("Couldn't load the library from {}: {}", dir.display(), e);
+                    Library::default()
+                })
+            })
+            .unwrap_or_default();
+        scripture_db.append(library.as_scriptures());
 
         // Load config
-        let config = Config::load().unwrap_or_else(|_| Config::new());
+        let config = Config::load().unwrap_or_else(|e| {
+            tracing::warn!("Couldn't read settings: {}", e);
+            Config::new()
+        });
+        let ollama = config.ollama_client();
+        let colors = ColorSupport::detect();
 
         // Load provider from config
-        let current_provider = config.provider
-            .as_ref()
-            .and_then(|p| Provider::from_str(p))
-            .unwrap_or(Provider::Ollama);
+        let current_provider = config.current_provider();
 
         // Initialize API clients - check env vars first, then config
-        let claude_key = std::env::var("ANTHROPIC_API_KEY").ok()
-            .or_else(|| config.claude_api_key.clone());
-        let claude_client = claude_key.as_ref().map(|k| ClaudeClient::new(k));
+        let claude_client = config.api_key(Provider::Claude).map(|k| config.claude_client(&k));
+        let openai_client = config.api_key(Provider::OpenAI).map(|k| config.openai_client(&k));
+        let study_instructions = config.study_instructions();
+
+        let copy_format = config.copy_format
+            .as_deref()
+            .and_then(CopyFormat::from_name)
+            .unwrap_or_default();
 
-        let openai_key = std::env::var("OPENAI_API_KEY").ok()
-            .or_else(|| config.openai_api_key.clone());
-        let openai_client = openai_key.as_ref().map(|k| OpenAIClient::new(k));
+        // Load the provider's model from config
+        let selected_model = config.model_for(current_provider);
 
-        // Load default model from config
-        let selected_model = config.default_model
-            .unwrap_or_else(|| "gemma3:latest".to_string());
+        let keymap = match &config.keybindings {
+            Some(path) => Keymap::load_from(Path::new(path)),
+            None => Keymap::load(),
+        };
+        let theme = Theme::load(config.theme.as_deref());
 
         // Load embeddings if available (for semantic search)
-        // Try local data/ directory first, then ~/.config/escrituras/data/
-        let embeddings_db = {
-            let local_path = std::path::Path::new("data");
-            let config_path = dirs::config_dir()
-                .map(|p| p.join("escrituras/data"));
-
-            if local_path.join("scripture_embeddings.npy").exists() {
-                EmbeddingsDb::load(local_path).ok()
-            } else if let Some(ref cfg_path) = config_path {
-                if cfg_path.join("scripture_embeddings.npy").exists() {
-                    EmbeddingsDb::load(cfg_path).ok()
-                } else {
-                    None
-                }
-            } else {
-                None
+        let mut embeddings_db = find_data_dir(EmbeddingsDb::FILE_NAME).and_then(|dir| {
+            EmbeddingsDb::load(&dir).map_err(|e| tracing::warn!("Couldn't load embeddings from {}: {}", dir.display(), e)).ok()
+        });
+        if let Some(embeddings) = embeddings_db.as_mut() {
+            if let Err(e) = library.add_embeddings(embeddings) {
+                tracing::warn!("Couldn't add the library's embeddings: {}", e);
             }
-        };
+        }
 
-        let cached_volumes: Vec<String> = scripture_db.get_volumes().to_vec();
+        let memorize_history = load_or_default("memorization history", MemorizeHistory::load());
+        let journal = load_or_default("the journal", Journal::load());
+        let mut journal_state = ListState::default();
+        if !journal.entries.is_empty() {
+            journal_state.select(Some(0));
+        }
+        let stored_saved = load_or_default("saved scriptures", SavedScriptures::load());
+        let (session_context, saved_tags) = (stored_saved.verses.clone(), stored_saved.tags.clone());
+        let stored_conversations = load_or_default("conversations", ConversationHistory::load());
+        let mut context_state = ListState::default();
+        if !session_context.is_empty() {
+            context_state.select(Some(0));
+        }
+
+        let cached_volumes: Vec<Title> = scripture_db.get_volumes().to_vec();
 
         let mut volume_state = ListState::default();
         volume_state.select(Some(0));
@@ -317,37 +833,66 @@ impl App {
             search_results: Vec::new(),
             search_state: ListState::default(),
             search_focus: SearchFocus::default(),
+            search_filter: config.search_defaults(),
+            search_typed_at: None,
+            search_task: None,
+            filter_dropdown: None,
+            filter_dropdown_state: ListState::default(),
 
             query_input: String::new(),
             query_cursor: 0,
+            query_input_width: 40,
             chat_messages: Vec::new(),
             query_loading: false,
             query_scroll: 0,
             query_chat_height: 0,
             query_chat_width: 0,
             query_task: None,
+            query_tokens: None,
+            streaming_response: String::new(),
             extracted_references: Vec::new(),
             references_state: ListState::default(),
+            conversations: stored_conversations.clone(),
+            stored_conversations,
+            conversation_index: None,
+            live_chat: Vec::new(),
 
             navigation_stack: Vec::new(),
             selected_verse_idx: None,
             selected_range: None,
-
-            session_context: Vec::new(),
-            context_state: ListState::default(),
+            visual_anchor: None,
+            split: None,
+            split_focused: false,
+            goto_into_split: false,
+            pending_passage: Vec::new(),
+
+            session_context,
+            saved_tags,
+            stored_saved,
+            tag_filter: None,
+            tag_input: None,
+            tag_cursor: 0,
+            context_state,
             show_context_panel: false,
 
             browsed_chapters: Vec::new(),
+            viewed_verse: None,
+
+            toasts: Toasts::default(),
 
             animation_frame: 0,
+            needs_redraw: true,
 
             show_model_picker: false,
+            regenerate_after_model_pick: false,
             available_models: Vec::new(),
             model_picker_state: ListState::default(),
 
             current_provider,
+            profile: config.profile_name().map(str::to_string),
             claude_client,
             openai_client,
+            study_instructions,
             show_provider_picker: false,
             provider_picker_state: ListState::default(),
 
@@ -356,11 +901,66 @@ impl App {
             api_key_input_cursor: 0,
             api_key_target_provider: None,
 
+            show_help: false,
+            help_scroll: 0,
+
+            keymap: keymap.unwrap_or_default(),
+            theme: theme.unwrap_or_default().for_terminal(colors),
+            colors,
+
+            layout: config.layout.unwrap_or_default(),
+
+            copy_format,
+            gospel_library_language: config.gospel_library_language().to_string(),
+            show_copy_menu: false,
+            copy_menu_state: ListState::default(),
+            footnotes,
+            footnote_state: ListState::default(),
+            related: Vec::new(),
+            related_for: None,
+            related_state: ListState::default(),
+            outline: None,
+            outline_collapsed: HashSet::new(),
+            outline_state: ListState::default(),
+            outline_task: None,
+            dictionary: None,
+            word_lookup: None,
+            interlinear: None,
+            strongs_lookup: None,
+            copy_menu_verses: Vec::new(),
+
+            show_command_palette: false,
+            palette_input: String::new(),
+            palette_state: ListState::default(),
+
+            show_goto_input: false,
+            goto_input: String::new(),
+            goto_cursor: 0,
+            goto_error: None,
+
             nav_area: None,
             content_area: None,
             refs_area: None,
 
             focus_state: None,
+            memorize_history,
+            journal,
+            journal_state,
+            journal_scroll: 0,
+            journal_visible_height: 10,
+            editor_request: None,
+            votd: if config.verse_of_the_day.unwrap_or(true) { todays_verse(&scripture_db) } else { None },
+            hymns,
+            library,
+            read_aloud: None,
+            talks: find_data_dir(TalkDb::FILE_NAME).and_then(|dir| TalkDb::load(&dir).ok()),
+            talks_view: TalksView::default(),
+            topics_view: TopicsView::default(),
+            reading: ReadingView::default(),
+            cfm: find_data_dir(CfmSchedule::FILE_NAME).and_then(|dir| CfmSchedule::load(&dir).ok()),
+            cfm_progress: load_or_default("reading progress", CfmProgress::load()),
+            cfm_week: 0,
+            cfm_state: ListState::default(),
 
             scripture_db,
             embeddings_db,
@@ -375,11 +975,11 @@ impl App {
     }
 
     // Navigation helpers
-    pub fn selected_volume(&self) -> Option<&String> {
+    pub fn selected_volume(&self) -> Option<&Title> {
         self.volume_state.selected().and_then(|i| self.cached_volumes.get(i))
     }
 
-    pub fn selected_book(&self) -> Option<&String> {
+    pub fn selected_book(&self) -> Option<&Title> {
         self.book_state.selected().and_then(|i| self.cached_books.get(i))
     }
 
@@ -618,9 +1218,10 @@ impl App {
         self.selected_verse_idx = Some(0);
 
         // Track browsed chapter
-        let book_owned = book.to_string();
-        if !self.browsed_chapters.iter().any(|(b, c)| b == &book_owned && *c == chapter) {
-            self.browsed_chapters.push((book_owned, chapter));
+        if let Some(first) = self.cached_verses.first() {
+            if !self.browsed_chapters.iter().any(|(b, c)| *b == first.book_title && *c == chapter) {
+                self.browsed_chapters.push((first.book_title.clone(), chapter));
+            }
         }
 
         true
@@ -664,46 +1265,169 @@ impl App {
 
     // Search - combines semantic (if available) and keyword results
     pub fn perform_search(&mut self) {
+        self.search_typed_at = None;
+        self.cancel_search();
         if self.search_input.is_empty() {
             return;
         }
+        self.start_semantic_search(true);
+    }
+
+    /// Once typing pauses, show the keyword matches and start looking for
+    /// matches by meaning
+    pub fn poll_live_search(&mut self) {
+        const DEBOUNCE: Duration = Duration::from_millis(200);
+        if self.search_typed_at.is_none_or(|t| t.elapsed() < DEBOUNCE) {
+            return;
+        }
+        self.search_typed_at = None;
+        self.cancel_search();
 
-        let query = &self.search_input.clone();
-        let limit = 50;
-        let semantic_limit = 20; // Show up to 20 semantic results first
-        let mut combined_results: Vec<Scripture> = Vec::new();
-        let mut seen_titles: HashSet<String> = HashSet::new();
+        let hits = escrituras_core::search::preview(&self.scripture_db, &self.search_input, &self.search_filter);
+        self.show_search_results(hits);
+        self.start_semantic_search(false);
+    }
 
-        // Try semantic search if embeddings are available (uses local ONNX model)
-        if let Some(embeddings) = &mut self.embeddings_db {
-            // Search embeddings for semantically similar verses (embeds query locally)
-            if let Ok(semantic_matches) = embeddings.search(query, semantic_limit) {
-                // Convert to Scripture objects
-                for (verse_title, _score) in semantic_matches {
-                    if let Some(scripture) = self.scripture_db.get_by_title(&verse_title) {
-                        seen_titles.insert(verse_title);
-                        combined_results.push(scripture.clone());
-                    }
+    /// Run the search's semantic pass in the background, replacing any still
+    /// running. Without one (keyword mode, no embeddings), a search asked for
+    /// is finished at once.
+    fn start_semantic_search(&mut self, announce: bool) {
+        let query = SemanticQuery::new(&self.scripture_db, &self.search_input, &self.search_filter);
+        let Some(mut embeddings) = self.embeddings_db.clone().filter(|_| query.is_needed()) else {
+            if announce {
+                let hits = escrituras_core::search::search_with(&self.scripture_db, Vec::new(), &query.query, &query.filter);
+                self.show_search_results(hits);
+            }
+            return;
+        };
+        let handle = tokio::task::spawn_blocking({
+            let query = query.clone();
+            move || query.run(&mut embeddings)
+        });
+        self.search_task = Some(SearchTask { query, announce, handle });
+    }
+
+    /// Stop waiting for a search's semantic pass. One that hasn't started
+    /// won't; one already running finishes, but its results are dropped.
+    pub fn cancel_search(&mut self) {
+        if let Some(task) = self.search_task.take() {
+            task.handle.abort();
+        }
+    }
+
+    /// Show a semantic pass's matches with the keyword matches once it's
+    /// done, if the search is still the one being typed
+    pub fn poll_search_task(&mut self) {
+        if !self.search_task.as_ref().is_some_and(|task| task.handle.is_finished()) {
+            return;
+        }
+        let Some(SearchTask { query, announce, handle }) = self.search_task.take() else {
+            return;
+        };
+        if query.query != self.search_input || query.filter != self.search_filter {
+            return;
+        }
+        let semantic = match handle.now_or_never() {
+            Some(Ok(Ok(matches))) => matches,
+            Some(Ok(Err(e))) => {
+                if announce {
+                    self.toasts.push(ToastLevel::Warning, format!("Semantic search failed, showing keyword matches: {}", e));
                 }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        };
+        let hits = if announce {
+            escrituras_core::search::search_with(&self.scripture_db, semantic, &query.query, &query.filter)
+        } else {
+            escrituras_core::search::preview_with(&self.scripture_db, semantic, &query.query, &query.filter)
+        };
+        self.show_search_results(hits);
+    }
+
+    fn show_search_results(&mut self, hits: Vec<SearchHit>) {
+        self.search_results = hits;
+        self.search_state.select(if self.search_results.is_empty() { None } else { Some(0) });
+        self.needs_redraw = true;
+    }
+
+    /// Open the volume or book dropdown, highlighting the current choice
+    pub fn open_filter_dropdown(&mut self, dropdown: FilterDropdown) {
+        let current = match dropdown {
+            FilterDropdown::Volume => self.search_filter.volume.clone(),
+            FilterDropdown::Book => self.search_filter.book.clone(),
+        };
+        self.filter_dropdown = Some(dropdown);
+        let position = current.and_then(|c| self.filter_options().iter().position(|o| *o == c));
+        self.filter_dropdown_state.select(Some(position.unwrap_or(0)));
+    }
+
+    /// Choices in the open dropdown; the first ("All ...") clears the filter
+    pub fn filter_options(&self) -> Vec<String> {
+        match self.filter_dropdown {
+            Some(FilterDropdown::Volume) => std::iter::once("All volumes".to_string())
+                .chain(self.scripture_db.get_volumes().iter().map(Title::to_string))
+                .collect(),
+            Some(FilterDropdown::Book) => {
+                let volumes = match &self.search_filter.volume {
+                    Some(volume) => vec![Title::from(volume)],
+                    None => self.scripture_db.get_volumes().to_vec(),
+                };
+                std::iter::once("All books".to_string())
+                    .chain(volumes.iter().flat_map(|v| self.scripture_db.get_books_for_volume(v)).map(String::from))
+                    .collect()
             }
+            None => Vec::new(),
         }
+    }
+
+    pub fn filter_dropdown_nav_down(&mut self) {
+        let len = self.filter_options().len();
+        let i = self.filter_dropdown_state.selected().unwrap_or(0);
+        self.filter_dropdown_state.select(Some((i + 1).min(len.saturating_sub(1))));
+    }
+
+    pub fn filter_dropdown_nav_up(&mut self) {
+        let i = self.filter_dropdown_state.selected().unwrap_or(0);
+        self.filter_dropdown_state.select(Some(i.saturating_sub(1)));
+    }
 
-        // Add keyword search results (deduped)
-        let keyword_results = self.scripture_db.search(query, limit);
-        for scripture in keyword_results {
-            if !seen_titles.contains(&scripture.verse_title) {
-                seen_titles.insert(scripture.verse_title.clone());
-                combined_results.push(scripture.clone());
-                if combined_results.len() >= limit {
-                    break;
+    /// Apply the highlighted dropdown choice and rerun the search
+    pub fn select_filter_option(&mut self) {
+        let Some(i) = self.filter_dropdown_state.selected() else {
+            return;
+        };
+        let choice = self.filter_options().get(i).cloned().filter(|_| i > 0);
+        match self.filter_dropdown.take() {
+            Some(FilterDropdown::Volume) => {
+                // A book outside the new volume no longer applies
+                if let (Some(volume), Some(book)) = (&choice, &self.search_filter.book) {
+                    if !self.scripture_db.get_books_for_volume(volume).iter().any(|b| b == book) {
+                        self.search_filter.book = None;
+                    }
                 }
+                self.search_filter.volume = choice;
             }
+            Some(FilterDropdown::Book) => self.search_filter.book = choice,
+            None => return,
         }
+        self.perform_search();
+    }
 
-        self.search_results = combined_results;
-        if !self.search_results.is_empty() {
-            self.search_state.select(Some(0));
+    /// Switch between semantic + keyword, keyword only, and semantic only
+    pub fn cycle_search_mode(&mut self) {
+        self.search_filter.mode = self.search_filter.mode.next();
+        if self.search_filter.mode != SearchMode::Keyword && self.embeddings_db.is_none() {
+            self.toasts.push(ToastLevel::Warning, "Semantic search unavailable: embeddings not installed");
         }
+        self.perform_search();
+    }
+
+    /// Raise or lower the result limit in steps of 10
+    pub fn change_search_limit(&mut self, delta: i32) {
+        let limit = (self.search_filter.limit as i32 + delta * 10).clamp(10, 200);
+        self.search_filter.limit = limit as usize;
+        self.perform_search();
     }
 
     pub fn search_nav_down(&mut self) {
@@ -725,13 +1449,16 @@ impl App {
     pub fn current_nav_title(&self) -> String {
         match self.nav_level {
             NavLevel::Volume => "Volumes".to_string(),
-            NavLevel::Book => self.selected_volume().cloned().unwrap_or_default(),
+            NavLevel::Book => self.selected_volume().map(Title::to_string).unwrap_or_default(),
             NavLevel::Chapter => {
                 // For single-book volumes, show "Select a section" for D&C
                 if let Some(book) = self.selected_book() {
                     if book == "Doctrine and Covenants" {
                         return "Select a section".to_string();
                     }
+                    if book == HYMN_VOLUME {
+                        return "Select a hymn".to_string();
+                    }
                 }
                 "Select a chapter".to_string()
             }
@@ -744,18 +1471,29 @@ impl App {
         books.len() == 1 && books.first().map(|b| b == volume).unwrap_or(false)
     }
 
-    /// Get the label for a chapter (returns "Section X" for D&C, "Chapter X" for others)
+    /// Get the label for a chapter (returns "Section X" for D&C, the number and
+    /// title for a hymn or library book, "Chapter X" for others)
     pub fn get_chapter_label(&self, chapter: i32) -> String {
-        if let Some(book) = self.selected_book() {
-            if book == "Doctrine and Covenants" {
-                return format!("Section {}", chapter);
+        let book = self.selected_book().map_or("", Title::as_str);
+        if book == HYMN_VOLUME {
+            if let Some(hymn) = self.hymns.as_ref().and_then(|hymns| hymns.get(chapter)) {
+                return hymn.label();
+            }
+        }
+        if self.selected_volume().is_some_and(|v| v == LIBRARY_VOLUME) {
+            let title = self.library.get(book).and_then(|b| b.chapters.get(usize::try_from(chapter - 1).ok()?));
+            if let Some(title) = title.map(|c| c.title.as_str()).filter(|t| !t.is_empty()) {
+                return format!("{} {}", chapter, title);
             }
         }
-        format!("Chapter {}", chapter)
+        escrituras_core::scripture::chapter_label(book, chapter)
     }
 
     pub fn content_title(&self) -> String {
         if let (Some(book), Some(chapter)) = (self.selected_book(), self.selected_chapter()) {
+            if chapter == FRONT_MATTER_CHAPTER {
+                return format!("{} Introduction", book);
+            }
             format!("{} {}", book, chapter)
         } else {
             "Select a chapter".to_string()
@@ -863,92 +1601,476 @@ impl App {
         }
     }
 
-    /// Jump to a single scripture (for search results)
-    pub fn jump_to_scripture(&mut self, scripture: &Scripture) {
-        // Clear any range selection
-        self.selected_range = None;
-
-        // Create a single-verse range and use the range function
-        let range = ScriptureRange {
-            book_title: scripture.book_title.clone(),
-            book_short_title: scripture.book_short_title.clone(),
-            chapter_number: scripture.chapter_number,
-            start_verse: scripture.verse_number,
-            end_verse: scripture.verse_number,
+    /// Snapshot of where the user is, for restoring at the next launch
+    pub fn session_state(&self) -> SessionState {
+        let screen = match self.screen {
+            Screen::Search => "search",
+            Screen::Query => "query",
+            // Focus mode and the journal are transient; resume on the chapter
+            Screen::Browse | Screen::Focus | Screen::Journal | Screen::Talks | Screen::ComeFollowMe | Screen::Topics | Screen::Reading => "browse",
         };
-        self.jump_to_scripture_range(&range);
+        let verse = self.get_selected_verse().or_else(|| self.cached_verses.first());
+
+        SessionState {
+            screen: screen.to_string(),
+            book_title: verse.map(|v| v.book_title.to_string()),
+            chapter_number: verse.map(|v| v.chapter_number),
+            selected_verse: self.get_selected_verse().map(|v| v.verse_number),
+            line_scroll: self.line_scroll,
+            search_input: self.search_input.clone(),
+            chat_messages: match self.conversation_index {
+                Some(_) => self.live_chat.clone(),
+                None => self.chat_messages.clone(),
+            },
+        }
     }
 
-    /// Navigate references list
-    pub fn references_nav_down(&mut self) {
-        let len = self.extracted_references.len();
-        if len > 0 {
-            let i = self.references_state.selected().unwrap_or(0);
-            self.references_state.select(Some((i + 1).min(len - 1)));
-            Self::adjust_list_offset(&mut self.references_state, self.refs_visible_height);
+    /// Reopen the chapter, screen, search, and chat from a previous session
+    pub fn restore_session(&mut self, session: SessionState) {
+        if let (Some(book_title), Some(chapter_number)) = (&session.book_title, session.chapter_number) {
+            if let Some(first) = self.scripture_db.get_verses_for_chapter(book_title, chapter_number).first() {
+                let verse_number = session.selected_verse.unwrap_or(first.verse_number);
+                let range = ScriptureRange {
+                    book_title: first.book_title.to_string(),
+                    book_short_title: first.book_short_title.to_string(),
+                    chapter_number,
+                    start_verse: verse_number,
+                    end_verse: verse_number,
+                };
+                self.jump_to_scripture_range(&range);
+                self.selected_range = None;
+                if session.selected_verse.is_none() {
+                    self.selected_verse_idx = None;
+                }
+                self.line_scroll = session.line_scroll;
+                self.verse_line_offset = 0;
+                self.focus = FocusPane::Content;
+            }
+        }
+
+        self.chat_messages = session.chat_messages;
+
+        self.search_input = session.search_input;
+        if !self.search_input.is_empty() {
+            self.perform_search();
+        }
+
+        self.screen = match session.screen.as_str() {
+            "search" => Screen::Search,
+            "query" => Screen::Query,
+            _ => Screen::Browse,
+        };
+        if self.screen == Screen::Search {
+            self.search_focus = SearchFocus::Results;
         }
     }
 
-    pub fn references_nav_up(&mut self) {
-        let i = self.references_state.selected().unwrap_or(0);
-        self.references_state.select(Some(i.saturating_sub(1)));
-        Self::adjust_list_offset(&mut self.references_state, self.refs_visible_height);
+    /// Open the copy-format menu for the given verses, highlighting the current format
+    pub fn open_copy_menu(&mut self, verses: Vec<Scripture>) {
+        let current = CopyFormat::all().iter().position(|f| *f == self.copy_format);
+        self.copy_menu_state.select(current.or(Some(0)));
+        self.copy_menu_verses = verses;
+        self.show_copy_menu = true;
     }
 
-    // Verse selection methods
+    pub fn copy_menu_nav_down(&mut self) {
+        let i = self.copy_menu_state.selected().unwrap_or(0);
+        self.copy_menu_state.select(Some((i + 1).min(CopyFormat::all().len() - 1)));
+    }
 
-    /// Ensure a verse is selected when focusing on content pane
-    /// Selects the topmost visible verse if nothing is selected or if selection is out of bounds
-    pub fn ensure_verse_selected(&mut self) {
-        if self.cached_verses.is_empty() {
-            self.selected_verse_idx = None;
-            return;
-        }
+    pub fn copy_menu_nav_up(&mut self) {
+        let i = self.copy_menu_state.selected().unwrap_or(0);
+        self.copy_menu_state.select(Some(i.saturating_sub(1)));
+    }
 
-        let max_idx = self.cached_verses.len() - 1;
+    pub fn selected_copy_format(&self) -> CopyFormat {
+        self.copy_menu_state
+            .selected()
+            .and_then(|i| CopyFormat::all().get(i).copied())
+            .unwrap_or_default()
+    }
 
-        // Check if selection is missing or out of bounds
-        let needs_reset = self.selected_verse_idx
-            .map(|idx| idx > max_idx)
-            .unwrap_or(true);
+    /// Open the command palette with an empty filter
+    pub fn open_command_palette(&mut self) {
+        self.show_command_palette = true;
+        self.palette_input.clear();
+        self.palette_state.select(Some(0));
+    }
 
-        if needs_reset {
-            // Select the first verse if selection is invalid
-            self.selected_verse_idx = Some(0);
-            self.line_scroll = 0;
-            self.verse_line_offset = 0;
-        }
+    /// Palette actions matching the current filter, best match first
+    pub fn palette_matches(&self) -> Vec<PaletteAction> {
+        let mut scored: Vec<(i32, PaletteAction)> = PaletteAction::all()
+            .iter()
+            .filter_map(|action| fuzzy_score(&self.palette_input, action.label()).map(|s| (s, *action)))
+            .collect();
+        // Stable sort keeps the default order for equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, action)| action).collect()
     }
 
-    pub fn select_next_verse(&mut self) {
-        let len = self.cached_verses.len();
-        if len == 0 {
-            return;
+    pub fn palette_nav_down(&mut self) {
+        let len = self.palette_matches().len();
+        if len > 0 {
+            let i = self.palette_state.selected().unwrap_or(0);
+            self.palette_state.select(Some((i + 1).min(len - 1)));
         }
+    }
 
-        let current = self.selected_verse_idx.unwrap_or(0);
+    pub fn palette_nav_up(&mut self) {
+        let i = self.palette_state.selected().unwrap_or(0);
+        self.palette_state.select(Some(i.saturating_sub(1)));
+    }
 
-        // Set direction for scroll positioning (lock to bottom when going down)
-        self.last_scroll_direction = ScrollDirection::Down;
+    /// Open the provider picker with the current provider selected
+    pub fn open_provider_picker(&mut self) {
+        let current_idx = Provider::all()
+            .iter()
+            .position(|p| *p == self.current_provider)
+            .unwrap_or(0);
+        self.provider_picker_state.select(Some(current_idx));
+        self.show_provider_picker = true;
+    }
 
-        // If not at the last verse of the chapter, just move to next verse
-        if current < len - 1 {
-            self.selected_verse_idx = Some(current + 1);
-            self.verse_line_offset = 0; // Reset sub-verse offset for new verse
-            return;
+    /// Fetch models for the current provider and open the model picker
+    pub async fn open_model_picker(&mut self) {
+        let models = match self.current_provider {
+            Provider::Ollama => {
+                self.ollama.list_models().await.unwrap_or_default()
+            }
+            Provider::Claude => ClaudeClient::list_models(),
+            Provider::OpenAI => OpenAIClient::list_models(),
+        };
+        self.available_models = models;
+        if !self.available_models.is_empty() {
+            // Select current model if in list, otherwise first
+            let current_idx = self.available_models
+                .iter()
+                .position(|m| m == &self.selected_model)
+                .unwrap_or(0);
+            self.model_picker_state.select(Some(current_idx));
+            self.show_model_picker = true;
         }
+    }
 
-        // At the last verse - try to navigate to next chapter
-        if self.navigate_to_next_chapter() {
-            self.selected_verse_idx = Some(0);
-            self.verse_line_offset = 0;
-            self.line_scroll = 0;
-        }
-        // If at volume boundary, stay at last verse (do nothing)
+    /// Open the go-to-reference prompt
+    pub fn open_goto_input(&mut self) {
+        self.goto_into_split = false;
+        self.show_goto_input = true;
+        self.goto_input.clear();
+        self.goto_cursor = 0;
+        self.goto_error = None;
     }
 
-    pub fn select_prev_verse(&mut self) {
-        if self.cached_verses.is_empty() {
+    /// Jump to the reference typed in the goto prompt (e.g., "Alma 32:21" or "dc 76").
+    /// Leaves the prompt open with an error if the reference can't be parsed.
+    pub fn submit_goto_input(&mut self) {
+        let Some(range) = self.scripture_db.parse_reference(&self.goto_input) else {
+            self.goto_error = Some(format!("Unknown reference: {}", self.goto_input.trim()));
+            return;
+        };
+
+        if self.goto_into_split {
+            self.open_split(&range);
+            self.show_goto_input = false;
+            self.goto_error = None;
+            return;
+        }
+
+        if self.screen == Screen::Query {
+            // Stay in AI mode so 'b' can return to the previous passage
+            self.push_navigation_state();
+        } else {
+            self.screen = Screen::Browse;
+        }
+        self.jump_to_scripture_range(&range);
+
+        // Whole-chapter references just open the chapter without a highlight
+        let chapter_len = self.cached_verses.last().map(|v| v.verse_number).unwrap_or(0);
+        if range.start_verse == 1 && range.end_verse == chapter_len {
+            self.selected_range = None;
+        }
+
+        self.focus = FocusPane::Content;
+        self.show_goto_input = false;
+        self.goto_error = None;
+    }
+
+    /// Widen (positive) or narrow (negative) the left pane of the current screen
+    pub fn resize_pane(&mut self, delta: i16) {
+        match self.screen {
+            Screen::Query => {
+                let split = self.layout.query_split as i16 + delta * 5;
+                self.layout.query_split = split.clamp(20, 80) as u16;
+            }
+            Screen::Reading => {
+                let width = self.layout.reading_width as i16 + delta * 4;
+                self.layout.reading_width = width.clamp(40, 200) as u16;
+            }
+            _ => {
+                let width = self.layout.nav_width as i16 + delta * 2;
+                self.layout.nav_width = width.clamp(16, 80) as u16;
+                self.layout.nav_hidden = false;
+            }
+        }
+        self.save_layout();
+    }
+
+    /// Collapse or restore the Browse navigation pane
+    fn save_layout(&mut self) {
+        if let Err(e) = Config::save_layout(self.layout) {
+            self.toasts.error(format!("Could not save layout: {}", e));
+        }
+    }
+
+    pub fn toggle_nav_pane(&mut self) {
+        self.layout.nav_hidden = !self.layout.nav_hidden;
+        if self.layout.nav_hidden && self.focus == FocusPane::Navigation {
+            self.ensure_verse_selected();
+            self.focus = FocusPane::Content;
+        }
+        self.save_layout();
+    }
+
+    /// Switch between the roomy layout and the compact one for small windows
+    pub fn toggle_compact(&mut self) {
+        self.layout.compact = !self.layout.compact;
+        self.toasts.info(if self.layout.compact { "Compact layout" } else { "Standard layout" });
+        self.save_layout();
+    }
+
+    /// Open split view (asking for the second chapter), or close it if already open
+    pub fn toggle_split(&mut self) {
+        if self.split.is_some() {
+            self.close_split();
+        } else {
+            self.open_goto_input();
+            self.goto_into_split = true;
+        }
+    }
+
+    /// Show a chapter beside the content pane, with the cursor on the range's first verse
+    pub fn open_split(&mut self, range: &ScriptureRange) {
+        let verses: Vec<Scripture> = self
+            .scripture_db
+            .get_verses_for_chapter(&range.book_title, range.chapter_number)
+            .into_iter()
+            .cloned()
+            .collect();
+        let selected = verses
+            .iter()
+            .position(|v| v.verse_number == range.start_verse)
+            .unwrap_or(0);
+
+        self.split = Some(SplitPane { verses, selected, scroll: 0, alignment: None });
+        self.split_focused = true;
+        self.screen = Screen::Browse;
+        self.focus = FocusPane::Content;
+        self.show_context_panel = false;
+        self.ensure_verse_selected();
+    }
+
+    /// Open the chapter paralleling this one (Isaiah beside 2 Nephi) in split
+    /// view, its verses lined up with this chapter's. Again, the next parallel
+    /// chapter, if there are more, then close it.
+    pub fn view_parallel(&mut self) {
+        let Some(first) = self.cached_verses.first() else {
+            return;
+        };
+        let alignments = parallels::alignments(&first.book_title, first.chapter_number);
+        if alignments.is_empty() {
+            self.toasts.info(format!("{} has no parallel passage", self.content_title()));
+            return;
+        }
+        let shown = self.split.as_ref().and_then(|s| s.alignment.as_ref()).and_then(|a| alignments.iter().position(|b| b == a));
+        let next = match shown {
+            Some(i) if i + 1 == alignments.len() => {
+                self.close_split();
+                return;
+            }
+            Some(i) => i + 1,
+            // First the one quoting the selected verse
+            None => {
+                let verse = self.get_selected_verse().map_or(0, |v| v.verse_number);
+                alignments.iter().position(|a| a.counterpart(verse).is_some()).unwrap_or(0)
+            }
+        };
+        let alignment = alignments[next].clone();
+        let verses: Vec<Scripture> = self
+            .scripture_db
+            .get_verses_for_chapter(&alignment.other_book, alignment.other_chapter)
+            .into_iter()
+            .cloned()
+            .collect();
+        if verses.is_empty() {
+            self.toasts.push(ToastLevel::Warning, format!("{} isn't in the scripture data", alignment.other_title()));
+            return;
+        }
+
+        self.split = Some(SplitPane { verses, selected: 0, scroll: 0, alignment: Some(alignment) });
+        self.split_focused = false;
+        self.screen = Screen::Browse;
+        self.focus = FocusPane::Content;
+        self.show_context_panel = false;
+        self.ensure_verse_selected();
+        self.align_split();
+    }
+
+    /// Keep the cursors of parallel chapters on matching verses, the chapter
+    /// with the cursor leading. A parallel no longer beside its chapter is
+    /// shown as an ordinary split.
+    pub fn align_split(&mut self) {
+        let main = self.cached_verses.first().map(|v| (v.book_title.as_str(), v.chapter_number));
+        let selected = self.get_selected_verse().map(|v| v.verse_number);
+        let Some(split) = &mut self.split else {
+            return;
+        };
+        let Some(alignment) = &split.alignment else {
+            return;
+        };
+        if main != Some((alignment.book.as_str(), alignment.chapter)) {
+            split.alignment = None;
+            return;
+        }
+        if self.split_focused {
+            let other = split.verses.get(split.selected).map(|v| v.verse_number);
+            let verse = other.and_then(|o| alignment.counterpart_of_other(o));
+            if let Some(idx) = verse.and_then(|verse| self.cached_verses.iter().position(|v| v.verse_number == verse)) {
+                self.selected_verse_idx = Some(idx);
+            }
+        } else if let Some(other) = selected.and_then(|verse| alignment.counterpart(verse)) {
+            if let Some(idx) = split.verses.iter().position(|v| v.verse_number == other) {
+                split.selected = idx;
+            }
+        }
+    }
+
+    pub fn close_split(&mut self) {
+        self.split = None;
+        self.split_focused = false;
+    }
+
+    /// Move the cursor between the two chapters in split view
+    pub fn swap_split_focus(&mut self) {
+        if self.split.is_some() {
+            self.split_focused = !self.split_focused;
+            self.focus = FocusPane::Content;
+        }
+    }
+
+    pub fn split_down(&mut self) {
+        if let Some(split) = &mut self.split {
+            split.selected = (split.selected + 1).min(split.verses.len().saturating_sub(1));
+        }
+    }
+
+    pub fn split_up(&mut self) {
+        if let Some(split) = &mut self.split {
+            split.selected = split.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn split_top(&mut self) {
+        if let Some(split) = &mut self.split {
+            split.selected = 0;
+        }
+    }
+
+    pub fn split_bottom(&mut self) {
+        if let Some(split) = &mut self.split {
+            split.selected = split.verses.len().saturating_sub(1);
+        }
+    }
+
+    pub fn get_split_verse(&self) -> Option<&Scripture> {
+        self.split.as_ref().and_then(|s| s.verses.get(s.selected))
+    }
+
+    /// Jump to a single scripture (for search results)
+    pub fn jump_to_scripture(&mut self, scripture: &Scripture) {
+        // Clear any range selection
+        self.selected_range = None;
+
+        // Create a single-verse range and use the range function
+        let range = ScriptureRange {
+            book_title: scripture.book_title.to_string(),
+            book_short_title: scripture.book_short_title.to_string(),
+            chapter_number: scripture.chapter_number,
+            start_verse: scripture.verse_number,
+            end_verse: scripture.verse_number,
+        };
+        self.jump_to_scripture_range(&range);
+    }
+
+    /// Navigate references list
+    pub fn references_nav_down(&mut self) {
+        let len = self.extracted_references.len();
+        if len > 0 {
+            let i = self.references_state.selected().unwrap_or(0);
+            self.references_state.select(Some((i + 1).min(len - 1)));
+            Self::adjust_list_offset(&mut self.references_state, self.refs_visible_height);
+        }
+    }
+
+    pub fn references_nav_up(&mut self) {
+        let i = self.references_state.selected().unwrap_or(0);
+        self.references_state.select(Some(i.saturating_sub(1)));
+        Self::adjust_list_offset(&mut self.references_state, self.refs_visible_height);
+    }
+
+    // Verse selection methods
+
+    /// Ensure a verse is selected when focusing on content pane
+    /// Selects the topmost visible verse if nothing is selected or if selection is out of bounds
+    pub fn ensure_verse_selected(&mut self) {
+        if self.cached_verses.is_empty() {
+            self.selected_verse_idx = None;
+            return;
+        }
+
+        let max_idx = self.cached_verses.len() - 1;
+
+        // Check if selection is missing or out of bounds
+        let needs_reset = self.selected_verse_idx
+            .map(|idx| idx > max_idx)
+            .unwrap_or(true);
+
+        if needs_reset {
+            // Select the first verse if selection is invalid
+            self.selected_verse_idx = Some(0);
+            self.line_scroll = 0;
+            self.verse_line_offset = 0;
+        }
+    }
+
+    pub fn select_next_verse(&mut self) {
+        let len = self.cached_verses.len();
+        if len == 0 {
+            return;
+        }
+
+        let current = self.selected_verse_idx.unwrap_or(0);
+
+        // Set direction for scroll positioning (lock to bottom when going down)
+        self.last_scroll_direction = ScrollDirection::Down;
+
+        // If not at the last verse of the chapter, just move to next verse
+        if current < len - 1 {
+            self.selected_verse_idx = Some(current + 1);
+            self.verse_line_offset = 0; // Reset sub-verse offset for new verse
+            return;
+        }
+
+        // At the last verse - try to navigate to next chapter
+        if self.navigate_to_next_chapter() {
+            self.selected_verse_idx = Some(0);
+            self.verse_line_offset = 0;
+            self.line_scroll = 0;
+        }
+        // If at volume boundary, stay at last verse (do nothing)
+    }
+
+    pub fn select_prev_verse(&mut self) {
+        if self.cached_verses.is_empty() {
             return;
         }
 
@@ -1154,9 +2276,245 @@ impl App {
     pub fn tick_animation(&mut self) {
         if self.query_loading {
             self.animation_frame = (self.animation_frame + 1) % 3;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Pause or resume reading aloud, or start reading the chapter on screen
+    /// from the selected verse
+    pub fn toggle_read_aloud(&mut self) {
+        if let Some(reading) = self.read_aloud.as_mut().filter(|r| !r.is_finished()) {
+            if let Err(e) = reading.toggle() {
+                self.toasts.error(format!("Couldn't read aloud: {}", e));
+            }
+            return;
+        }
+        if self.cached_verses.is_empty() {
+            return;
+        }
+        let config = Config::load().unwrap_or_else(|_| Config::new());
+        let voice = match CommandVoice::from_config(&config) {
+            Ok(voice) => voice,
+            Err(e) => {
+                self.toasts.error(e.to_string());
+                return;
+            }
+        };
+        let start = self.selected_verse_idx.unwrap_or(0);
+        let mut reading = ReadAloud::new(Box::new(voice), self.cached_verses.clone(), start);
+        match reading.play() {
+            Ok(()) => self.read_aloud = Some(reading),
+            Err(e) => self.toasts.error(format!("Couldn't read aloud: {}", e)),
+        }
+    }
+
+    /// Stop reading aloud
+    pub fn stop_read_aloud(&mut self) {
+        if self.read_aloud.take().is_some() {
+            self.toasts.info("Stopped reading");
+        }
+    }
+
+    /// Read from `delta` verses ahead of (or behind) the one being read
+    pub fn skip_read_aloud(&mut self, delta: isize) {
+        let Some(reading) = self.read_aloud.as_mut() else {
+            return;
+        };
+        match reading.skip(delta) {
+            Ok(()) => self.follow_read_aloud(),
+            Err(e) => self.toasts.error(format!("Couldn't read aloud: {}", e)),
+        }
+    }
+
+    /// Go on to the next verse when one has been read (called by Tick event)
+    pub fn tick_read_aloud(&mut self) {
+        let Some(reading) = self.read_aloud.as_mut() else {
+            return;
+        };
+        match reading.poll() {
+            Ok(false) => return,
+            Ok(true) if reading.is_finished() => self.read_aloud = None,
+            Ok(true) => self.follow_read_aloud(),
+            Err(e) => {
+                self.read_aloud = None;
+                self.toasts.error(format!("Couldn't read aloud: {}", e));
+            }
+        }
+        self.needs_redraw = true;
+    }
+
+    /// Select the verse being read, while its chapter is on screen
+    fn follow_read_aloud(&mut self) {
+        let Some(verse) = self.read_aloud.as_ref().and_then(ReadAloud::current) else {
+            return;
+        };
+        let same_chapter = self.cached_verses.first().is_some_and(|first| {
+            first.book_title == verse.book_title && first.chapter_number == verse.chapter_number
+        });
+        if same_chapter && self.screen == Screen::Browse {
+            let index = self.cached_verses.iter().position(|v| v.verse_number == verse.verse_number);
+            if index.is_some() {
+                self.last_scroll_direction =
+                    if index > self.selected_verse_idx { ScrollDirection::Down } else { ScrollDirection::Up };
+                self.selected_verse_idx = index;
+            }
+        }
+    }
+
+    /// "▶ Alma 32:21" while reading aloud, "⏸ Alma 32:21" when paused
+    pub fn read_aloud_status(&self) -> Option<String> {
+        let reading = self.read_aloud.as_ref()?;
+        let verse = reading.current()?;
+        Some(format!("{} {}", if reading.is_paused() { "⏸" } else { "▶" }, verse.verse_title))
+    }
+
+    /// Append any streamed tokens to the partial response, refreshing references as citations appear
+    pub fn poll_query_stream(&mut self) {
+        let Some(rx) = self.query_tokens.as_mut() else {
+            return;
+        };
+
+        let mut received = false;
+        while let Ok(token) = rx.try_recv() {
+            self.streaming_response.push_str(&token);
+            received = true;
+        }
+
+        if received {
+            let refs = self.scripture_db.extract_scripture_references(&self.streaming_response);
+            if refs.len() != self.extracted_references.len() {
+                self.extracted_references = refs;
+                if self.references_state.selected().is_none() && !self.extracted_references.is_empty() {
+                    self.references_state.select(Some(0));
+                }
+            }
+            self.scroll_query_to_bottom();
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Stop an in-flight AI query, keeping whatever text has streamed in so far
+    pub fn cancel_query(&mut self) {
+        if let Some(task) = self.query_task.take() {
+            task.abort();
+        }
+        self.poll_query_stream();
+        self.query_tokens = None;
+
+        let partial = std::mem::take(&mut self.streaming_response);
+        let content = if partial.trim().is_empty() {
+            "(cancelled)".to_string()
+        } else {
+            format!("{}\n\n(cancelled)", partial.trim_end())
+        };
+        self.chat_messages.push(ChatMessage {
+            role: ChatRole::Assistant,
+            content,
+        });
+        self.query_loading = false;
+    }
+
+    /// Archive the current thread and start an empty one
+    pub fn new_conversation(&mut self) {
+        if self.query_task.is_some() {
+            self.cancel_query();
+        }
+        self.show_conversation(None);
+        let messages = std::mem::take(&mut self.chat_messages);
+        if !messages.is_empty() {
+            self.conversations.archive(messages);
+            self.save_conversation_history();
+            self.toasts.info("Started a new conversation");
+        }
+        self.reset_chat_view();
+    }
+
+    /// Drop the last question and its answer from the thread
+    pub fn delete_last_exchange(&mut self) {
+        if self.query_task.is_some() {
+            return;
+        }
+        while let Some(msg) = self.chat_messages.pop() {
+            if msg.role == ChatRole::User {
+                break;
+            }
+        }
+        if self.conversation_index.is_some() {
+            self.store_conversation();
+            self.save_conversation_history();
+        }
+        self.extracted_references.clear();
+        self.references_state.select(None);
+        self.scroll_query_to_bottom();
+    }
+
+    /// Show the next older archived conversation
+    pub fn prev_conversation(&mut self) {
+        let target = match self.conversation_index {
+            None => self.conversations.conversations.len().checked_sub(1),
+            Some(i) => Some(i.saturating_sub(1)),
+        };
+        if target.is_some() {
+            self.show_conversation(target);
+        }
+    }
+
+    /// Show the next newer archived conversation, ending at the live thread
+    pub fn next_conversation(&mut self) {
+        if let Some(i) = self.conversation_index {
+            let target = Some(i + 1).filter(|&j| j < self.conversations.conversations.len());
+            self.show_conversation(target);
+        }
+    }
+
+    /// Switch the chat pane to an archived conversation, or back to the live thread with None
+    fn show_conversation(&mut self, target: Option<usize>) {
+        if target == self.conversation_index || self.query_task.is_some() {
+            return;
+        }
+        match self.conversation_index {
+            Some(_) => {
+                self.store_conversation();
+                self.save_conversation_history();
+            }
+            None => self.live_chat = std::mem::take(&mut self.chat_messages),
+        }
+        self.chat_messages = match target {
+            Some(i) => self.conversations.conversations[i].messages.clone(),
+            None => std::mem::take(&mut self.live_chat),
+        };
+        self.conversation_index = target;
+        self.reset_chat_view();
+    }
+
+    /// Write the shown archived conversation back (it may have been continued)
+    fn store_conversation(&mut self) {
+        if let Some(conversation) = self
+            .conversation_index
+            .and_then(|i| self.conversations.conversations.get_mut(i))
+        {
+            conversation.messages = self.chat_messages.clone();
+        }
+    }
+
+    fn save_conversation_history(&mut self) {
+        match self.conversations.save(&self.stored_conversations) {
+            Ok(()) => self.stored_conversations = self.conversations.clone(),
+            Err(e) => self.toasts.error(format!("Could not save conversations: {}", e)),
         }
     }
 
+    /// Save the conversation history, returning to the live thread first
+    pub fn save_conversations(&mut self) {
+        self.show_conversation(None);
+    }
+
+    fn reset_chat_view(&mut self) {
+        self.query_scroll = 0;
+        self.extracted_references.clear();
+        self.references_state.select(None);
+    }
+
     /// Scroll chat to bottom so "Thinking..." is visible
     pub fn scroll_query_to_bottom(&mut self) {
         // Use actual chat width for wrap calculation, default to 50 if not set
@@ -1183,8 +2541,17 @@ impl App {
             total_lines += 1; // Blank line after message
         }
 
-        // Add lines for "Thinking..." indicator
-        total_lines += 2; // "AI:" + "Thinking..."
+        if self.streaming_response.is_empty() {
+            // Add lines for "Thinking..." indicator
+            total_lines += 2; // "AI:" + "Thinking..."
+        } else {
+            // Partial streamed response
+            total_lines += 1; // "AI:"
+            for line in self.streaming_response.lines() {
+                let char_count = line.chars().count();
+                total_lines += ((char_count / wrap_width) + 1) as u16;
+            }
+        }
 
         let visible_height = if self.query_chat_height > 0 {
             self.query_chat_height
@@ -1199,7 +2566,7 @@ impl App {
 
     // Context panel navigation methods
     pub fn context_nav_down(&mut self) {
-        let len = self.session_context.len();
+        let len = self.visible_context().len();
         if len > 0 {
             let i = self.context_state.selected().unwrap_or(0);
             self.context_state.select(Some((i + 1).min(len - 1)));
@@ -1213,25 +2580,287 @@ impl App {
         Self::adjust_list_offset(&mut self.context_state, self.context_visible_height);
     }
 
-    pub fn remove_selected_context(&mut self) {
-        if let Some(i) = self.context_state.selected() {
-            if i < self.session_context.len() {
-                self.session_context.remove(i);
-                // Adjust selection
-                if self.session_context.is_empty() {
-                    self.context_state.select(None);
-                } else if i >= self.session_context.len() {
-                    self.context_state.select(Some(self.session_context.len() - 1));
-                }
-            }
-        }
+    /// Indices into session_context of the saved verses passing the tag filter
+    pub fn visible_context(&self) -> Vec<usize> {
+        (0..self.session_context.len())
+            .filter(|&i| match &self.tag_filter {
+                Some(tag) => self.verse_tags(&self.session_context[i].verse_title).contains(tag),
+                None => true,
+            })
+            .collect()
     }
 
-    pub fn get_selected_verse(&self) -> Option<&Scripture> {
-        self.selected_verse_idx.and_then(|idx| self.cached_verses.get(idx))
+    /// Saved verses sent to the AI: only the filtered tag's when a filter is set
+    pub fn context_for_ai(&self) -> Vec<Scripture> {
+        self.visible_context().into_iter().map(|i| self.session_context[i].clone()).collect()
     }
 
-    // Model picker methods
+    /// What the user is viewing, to tell the AI
+    fn current_reading(&self) -> Option<String> {
+        if self.show_context_panel || self.cached_verses.is_empty() {
+            return None;
+        }
+        if let Some(range) = &self.selected_range {
+            // User is viewing a specific reference range
+            Some(range.display_title())
+        } else {
+            // User is viewing a chapter
+            self.cached_verses
+                .first()
+                .map(|first_verse| format!("{} {}", first_verse.book_title, first_verse.chapter_number))
+        }
+    }
+
+    /// The prompt for `question` after the earlier messages in `history`, with
+    /// the saved verses, browsed chapters, current reading, and selected passage
+    pub fn query_prompt(&self, history: &[ChatMessage], question: &str) -> QueryPrompt {
+        let mut prompt = QueryPrompt::default();
+
+        let instructions = &mut prompt.instructions;
+        instructions.push_str(&self.study_instructions);
+
+        // Include what the user is currently reading
+        if let Some(reading) = self.current_reading() {
+            instructions.push_str(&format!("The user is currently reading {}.\n\n", reading));
+        }
+
+        // Include recently browsed chapters (lightweight context)
+        if !self.browsed_chapters.is_empty() {
+            instructions.push_str("Recently viewed chapters: ");
+            let chapters: Vec<String> = self.browsed_chapters.iter()
+                .take(10)  // Limit to last 10 chapters
+                .map(|(book, ch)| format!("{} {}", book, ch))
+                .collect();
+            instructions.push_str(&chapters.join(", "));
+            instructions.push_str("\n\n");
+        }
+
+        let context = self.context_for_ai();
+        prompt.context = context_block(&context[..context.len().min(PROMPT_CONTEXT_VERSES)]);
+
+        // Include the passage the user selected to ask about, as one block
+        let passage = &self.pending_passage;
+        if let (Some(first), Some(last)) = (passage.first(), passage.last()) {
+            prompt.context.push_str(&format!(
+                "The question is about this passage ({} {}:{}-{}):\n",
+                first.book_title, first.chapter_number, first.verse_number, last.verse_number
+            ));
+            for verse in passage {
+                prompt.context.push_str(&format!("{} {}\n", verse.verse_number, verse.scripture_text));
+            }
+            prompt.context.push('\n');
+        }
+
+        prompt.history = history_block(history);
+        prompt.question = question_block(question);
+
+        prompt
+    }
+
+    /// Size of the prompt the question being typed would send
+    pub fn prompt_usage(&self) -> PromptUsage {
+        let prompt = self.query_prompt(&self.chat_messages, &self.query_input);
+        PromptUsage {
+            instructions: estimate_tokens(&prompt.instructions),
+            context: estimate_tokens(&prompt.context),
+            history: estimate_tokens(&prompt.history),
+            question: estimate_tokens(&prompt.question),
+            window: context_window(self.current_provider, &self.selected_model),
+            dropped_verses: self.visible_context().len().saturating_sub(PROMPT_CONTEXT_VERSES),
+        }
+    }
+
+    pub fn verse_tags(&self, verse_title: &str) -> &[String] {
+        self.saved_tags.get(verse_title).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Index into session_context of the highlighted saved verse
+    fn selected_context_index(&self) -> Option<usize> {
+        self.context_state.selected().and_then(|i| self.visible_context().get(i).copied())
+    }
+
+    /// Step the tag filter through every tag in use, then back to all verses
+    pub fn cycle_tag_filter(&mut self) {
+        let tags = self.saved_scriptures().all_tags();
+        let next = match &self.tag_filter {
+            None => tags.first(),
+            Some(current) => tags.iter().skip_while(|t| *t != current).nth(1),
+        };
+        self.tag_filter = next.cloned();
+        self.context_state.select(if self.visible_context().is_empty() { None } else { Some(0) });
+        match &self.tag_filter {
+            Some(tag) => self.toasts.info(format!("Showing #{}", tag)),
+            None if tags.is_empty() => self.toasts.info("No tags yet: press t on a saved verse"),
+            None => self.toasts.info("Showing all saved scriptures"),
+        }
+    }
+
+    /// Open the tag prompt for the highlighted saved verse, prefilled with its tags
+    pub fn open_tag_input(&mut self) {
+        let Some(i) = self.selected_context_index() else {
+            return;
+        };
+        let input = self.verse_tags(&self.session_context[i].verse_title).join(", ");
+        self.tag_cursor = input.chars().count();
+        self.tag_input = Some(input);
+    }
+
+    /// Replace the highlighted verse's tags with the ones typed in the prompt
+    pub fn submit_tag_input(&mut self) {
+        let (Some(input), Some(i)) = (self.tag_input.take(), self.selected_context_index()) else {
+            return;
+        };
+        let title = self.session_context[i].verse_title.clone();
+        let tags = SavedScriptures::parse_tags(&input);
+        if tags.is_empty() {
+            self.saved_tags.remove(&title);
+            self.toasts.info(format!("Removed tags from {}", title));
+        } else {
+            let list = tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ");
+            self.toasts.success(format!("Tagged {} {}", title, list));
+            self.saved_tags.insert(title, tags);
+        }
+        // Keep the selection valid if the verse no longer matches the filter
+        let len = self.visible_context().len();
+        if self.context_state.selected().is_some_and(|s| s >= len) {
+            self.context_state.select(len.checked_sub(1));
+        }
+        self.persist_saved_scriptures();
+    }
+
+    /// Add verses to saved scriptures (skipping any already saved) and persist the list
+    pub fn save_to_context(&mut self, verses: Vec<Scripture>) {
+        let before = self.session_context.len();
+        for verse in verses {
+            if !self.session_context.iter().any(|v| v.verse_title == verse.verse_title) {
+                self.session_context.push(verse);
+            }
+        }
+        if self.context_state.selected().is_none() && !self.session_context.is_empty() {
+            self.context_state.select(Some(0));
+        }
+        match self.session_context.len() - before {
+            0 => self.toasts.info("Already saved"),
+            1 => self.toasts.success(format!("Saved {}", self.session_context[before].verse_title)),
+            n => self.toasts.success(format!("Saved {} verses", n)),
+        }
+        self.persist_saved_scriptures();
+    }
+
+    pub fn remove_selected_context(&mut self) {
+        if let Some(i) = self.selected_context_index() {
+            let removed = self.session_context.remove(i);
+            self.saved_tags.remove(&removed.verse_title);
+            // Adjust selection
+            let len = self.visible_context().len();
+            if len == 0 {
+                self.context_state.select(None);
+            } else if self.context_state.selected().is_some_and(|s| s >= len) {
+                self.context_state.select(Some(len - 1));
+            }
+            self.toasts.info(format!("Removed {}", removed.verse_title));
+            self.persist_saved_scriptures();
+        }
+    }
+
+    /// Remove every saved scripture
+    pub fn clear_saved_scriptures(&mut self) {
+        let count = self.session_context.len();
+        self.session_context.clear();
+        self.saved_tags.clear();
+        self.tag_filter = None;
+        self.context_state.select(None);
+        self.toasts.info(format!("Cleared {} saved scripture{}", count, if count == 1 { "" } else { "s" }));
+        self.persist_saved_scriptures();
+    }
+
+    fn saved_scriptures(&self) -> SavedScriptures {
+        SavedScriptures {
+            verses: self.session_context.clone(),
+            tags: self.saved_tags.clone(),
+        }
+    }
+
+    fn persist_saved_scriptures(&mut self) {
+        let saved = self.saved_scriptures();
+        match saved.save(&self.stored_saved) {
+            Ok(()) => self.stored_saved = saved,
+            Err(e) => self.toasts.error(format!("Could not save scriptures: {}", e)),
+        }
+    }
+
+    pub fn get_selected_verse(&self) -> Option<&Scripture> {
+        self.selected_verse_idx.and_then(|idx| self.cached_verses.get(idx))
+    }
+
+    /// Announce the verse being read when it changes (see `escrituras_core::events`)
+    pub fn announce_viewed_verse(&mut self) {
+        let verse = match self.screen {
+            Screen::Browse | Screen::Focus => self.get_selected_verse().map(|v| v.verse_title.clone()),
+            _ => None,
+        };
+        if verse != self.viewed_verse {
+            if let Some(verse_title) = verse.clone() {
+                events::emit(Event::VerseViewed { verse_title });
+            }
+        }
+        self.viewed_verse = verse;
+    }
+
+    // Visual mode methods
+    pub fn start_visual(&mut self) {
+        self.ensure_verse_selected();
+        self.visual_anchor = self.selected_verse_idx;
+    }
+
+    pub fn cancel_visual(&mut self) {
+        self.visual_anchor = None;
+    }
+
+    /// Inclusive (start, end) verse indices of the visual selection
+    pub fn visual_bounds(&self) -> Option<(usize, usize)> {
+        let anchor = self.visual_anchor?;
+        let cursor = self.selected_verse_idx.unwrap_or(anchor);
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Verses in the visual selection, in chapter order
+    pub fn get_visual_verses(&self) -> &[Scripture] {
+        match self.visual_bounds() {
+            Some((start, end)) if end < self.cached_verses.len() => &self.cached_verses[start..=end],
+            _ => &[],
+        }
+    }
+
+    /// Extend the visual selection, staying within the current chapter
+    pub fn visual_down(&mut self) {
+        if let Some(idx) = self.selected_verse_idx {
+            if idx + 1 < self.cached_verses.len() {
+                self.selected_verse_idx = Some(idx + 1);
+                self.verse_line_offset = 0;
+                self.last_scroll_direction = ScrollDirection::Down;
+            }
+        }
+    }
+
+    pub fn visual_up(&mut self) {
+        if let Some(idx) = self.selected_verse_idx {
+            if idx > 0 {
+                self.selected_verse_idx = Some(idx - 1);
+                self.verse_line_offset = 0;
+                self.last_scroll_direction = ScrollDirection::Up;
+            }
+        }
+    }
+
+    /// Save every verse in the visual selection to saved scriptures
+    pub fn save_visual_selection(&mut self) {
+        let verses = self.get_visual_verses().to_vec();
+        self.save_to_context(verses);
+        self.visual_anchor = None;
+    }
+
+    // Model picker methods
     pub fn model_picker_nav_down(&mut self) {
         let len = self.available_models.len();
         if len > 0 {
@@ -1251,7 +2880,10 @@ impl App {
                 self.selected_model = model.clone();
                 self.show_model_picker = false;
                 // Save to config
-                let _ = Config::save_default_model(&self.selected_model);
+                match Config::save_model(self.current_provider, &self.selected_model) {
+                    Ok(()) => self.toasts.success(format!("Model set to {}", self.selected_model)),
+                    Err(e) => self.toasts.error(format!("Could not save config: {}", e)),
+                }
             }
         }
     }
@@ -1323,13 +2955,31 @@ impl App {
             Screen::Browse => self.get_selected_verse().cloned(),
             Screen::Search => {
                 self.search_state.selected()
-                    .and_then(|i| self.search_results.get(i).cloned())
+                    .and_then(|i| self.search_results.get(i).map(|h| h.scripture.clone()))
             }
             Screen::Query => self.get_selected_verse().cloned(),
-            Screen::Focus => None, // Already in focus mode
+            Screen::Focus | Screen::Journal | Screen::Talks | Screen::ComeFollowMe | Screen::Topics | Screen::Reading => None, // Already in focus mode / no verse
         };
 
         if let Some(verse) = verse {
+            // Study the whole selected range as a passage when the verse is part of one
+            let passage: Vec<Scripture> = match (&self.selected_range, previous_screen) {
+                (Some(range), Screen::Browse | Screen::Query)
+                    if range.start_verse != range.end_verse
+                        && range.book_title == verse.book_title
+                        && range.chapter_number == verse.chapter_number
+                        && range.contains_verse(verse.verse_number) =>
+                {
+                    self.cached_verses
+                        .iter()
+                        .filter(|v| range.contains_verse(v.verse_number))
+                        .cloned()
+                        .collect()
+                }
+                _ => vec![verse.clone()],
+            };
+            let verse = passage.first().cloned().unwrap_or(verse);
+
             // Save navigation state for return
             self.push_navigation_state();
 
@@ -1356,6 +3006,9 @@ impl App {
                 flashcard_phase: FlashcardPhase::Hidden,
                 flashcard_input: String::new(),
                 flashcard_input_cursor: 0,
+                passage,
+                first_letter_peek: false,
+                deck: None,
             });
 
             self.screen = Screen::Focus;
@@ -1370,16 +3023,21 @@ impl App {
         self.pop_navigation_state();
     }
 
-    /// Navigate to next verse in Focus Mode
+    /// Navigate to next verse in Focus Mode (steps past the whole passage when studying a range)
     pub fn focus_next_verse(&mut self) {
+        if self.focus_step_deck(true) {
+            return;
+        }
         if let Some(ref mut state) = self.focus_state {
-            // Use saturating_sub to avoid underflow when volume_verses is empty
-            if state.current_index < state.volume_verses.len().saturating_sub(1) {
-                state.current_index += 1;
+            let next_index = state.current_index + state.passage.len().max(1);
+            if next_index < state.volume_verses.len() {
+                state.current_index = next_index;
                 state.current_verse = state.volume_verses[state.current_index].clone();
+                state.passage = vec![state.current_verse.clone()];
                 // Reset memorization state when navigating
                 state.memorize_level = 0;
                 state.memorize_revealed = false;
+                state.first_letter_peek = false;
                 state.flashcard_phase = FlashcardPhase::Hidden;
                 state.flashcard_input.clear();
                 state.flashcard_input_cursor = 0;
@@ -1390,13 +3048,18 @@ impl App {
 
     /// Navigate to previous verse in Focus Mode
     pub fn focus_prev_verse(&mut self) {
+        if self.focus_step_deck(false) {
+            return;
+        }
         if let Some(ref mut state) = self.focus_state {
             if state.current_index > 0 {
                 state.current_index -= 1;
                 state.current_verse = state.volume_verses[state.current_index].clone();
+                state.passage = vec![state.current_verse.clone()];
                 // Reset memorization state when navigating
                 state.memorize_level = 0;
                 state.memorize_revealed = false;
+                state.first_letter_peek = false;
                 state.flashcard_phase = FlashcardPhase::Hidden;
                 state.flashcard_input.clear();
                 state.flashcard_input_cursor = 0;
@@ -1405,6 +3068,88 @@ impl App {
         }
     }
 
+    /// Move to the next or previous passage of the deck under review.
+    /// Returns false when no deck is being reviewed.
+    fn focus_step_deck(&mut self, forward: bool) -> bool {
+        let Some(state) = &mut self.focus_state else {
+            return false;
+        };
+        let Some(review) = &mut state.deck else {
+            return false;
+        };
+        let position = if forward {
+            (review.position + 1).min(review.passages.len().saturating_sub(1))
+        } else {
+            review.position.saturating_sub(1)
+        };
+        if position != review.position {
+            review.position = position;
+            let passage = review.passages[position].clone();
+            self.focus_show_passage(passage);
+        }
+        true
+    }
+
+    /// Review the next built-in doctrinal mastery deck, its due passages
+    /// first; after the last deck, go back to reading the volume
+    pub fn focus_next_deck(&mut self) {
+        let Some(state) = &self.focus_state else {
+            return;
+        };
+        let next = match &state.deck {
+            None => DECKS.first(),
+            Some(review) => DECKS.iter().position(|d| d == review.deck).and_then(|i| DECKS.get(i + 1)),
+        };
+
+        let Some(deck) = next else {
+            // Carry on through the volume from the passage being studied
+            let verse = state.current_verse.clone();
+            let volume_verses = self.scripture_db.get_all_verses_for_volume(&verse.volume_title);
+            let current_index = volume_verses.iter().position(|v| v.verse_title == verse.verse_title).unwrap_or(0);
+            if let Some(state) = &mut self.focus_state {
+                state.deck = None;
+                state.volume_verses = volume_verses;
+                state.current_index = current_index;
+            }
+            self.toasts.info("Back to reading the volume");
+            return;
+        };
+
+        let passages = deck.review_order(&self.scripture_db, &self.memorize_history);
+        let Some(first) = passages.first().cloned() else {
+            self.toasts.push(ToastLevel::Warning, format!("No {} passages in the scripture data", deck.name));
+            return;
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let due = deck.due(&self.scripture_db, &self.memorize_history, now);
+        self.toasts.info(format!("{} doctrinal mastery: {} passages, {} due", deck.name, passages.len(), due));
+
+        if let Some(state) = &mut self.focus_state {
+            state.deck = Some(DeckReview { deck, passages, position: 0 });
+            state.sub_mode = FocusSubMode::Memorize;
+        }
+        self.focus_show_passage(first);
+    }
+
+    /// Study a passage in Focus Mode from the start of memorizing it
+    fn focus_show_passage(&mut self, passage: Vec<Scripture>) {
+        if let Some(state) = &mut self.focus_state {
+            if let Some(first) = passage.first() {
+                state.current_verse = first.clone();
+            }
+            state.passage = passage;
+            state.memorize_level = 0;
+            state.memorize_revealed = false;
+            state.first_letter_peek = false;
+            state.flashcard_phase = FlashcardPhase::Hidden;
+            state.flashcard_input.clear();
+            state.flashcard_input_cursor = 0;
+        }
+    }
+
     /// Toggle memorization mode on/off
     pub fn focus_toggle_memorize(&mut self) {
         if let Some(ref mut state) = self.focus_state {
@@ -1418,15 +3163,17 @@ impl App {
         }
     }
 
-    /// Cycle memorization mode (Progressive <-> Flashcard)
+    /// Cycle memorization mode (Progressive -> Flashcard -> First Letter)
     pub fn focus_cycle_memorize_mode(&mut self) {
         if let Some(ref mut state) = self.focus_state {
             state.memorize_mode = match state.memorize_mode {
                 MemorizeMode::Progressive => MemorizeMode::Flashcard,
-                MemorizeMode::Flashcard => MemorizeMode::Progressive,
+                MemorizeMode::Flashcard => MemorizeMode::FirstLetter,
+                MemorizeMode::FirstLetter => MemorizeMode::Progressive,
             };
             state.memorize_level = 0;
             state.memorize_revealed = false;
+            state.first_letter_peek = false;
             state.flashcard_phase = FlashcardPhase::Hidden;
             state.flashcard_input.clear();
             state.flashcard_input_cursor = 0;
@@ -1457,6 +3204,13 @@ impl App {
         }
     }
 
+    /// Toggle peeking at the full text in first-letter mode
+    pub fn focus_toggle_peek(&mut self) {
+        if let Some(ref mut state) = self.focus_state {
+            state.first_letter_peek = !state.first_letter_peek;
+        }
+    }
+
     /// Reset flashcard to hidden state
     pub fn focus_reset_flashcard(&mut self) {
         if let Some(ref mut state) = self.focus_state {
@@ -1471,16 +3225,28 @@ impl App {
     pub fn focus_start_typing(&mut self) {
         if let Some(ref mut state) = self.focus_state {
             state.flashcard_phase = FlashcardPhase::Typing;
+            state.first_letter_peek = false;
             state.flashcard_input.clear();
             state.flashcard_input_cursor = 0;
         }
     }
 
-    /// Submit flashcard typing and reveal with diff
+    /// Submit flashcard typing and reveal with diff, recording each verse's score
     pub fn focus_submit_typing(&mut self) {
         if let Some(ref mut state) = self.focus_state {
             state.flashcard_phase = FlashcardPhase::Revealed;
             state.memorize_revealed = true;
+
+            if !state.flashcard_input.trim().is_empty() {
+                let scores = escrituras_core::memorize::verse_accuracies(&state.passage, &state.flashcard_input);
+                let loaded = self.memorize_history.clone();
+                for (verse, accuracy) in state.passage.iter().zip(scores) {
+                    self.memorize_history.record(&verse.verse_title, accuracy);
+                }
+                if let Err(e) = self.memorize_history.save(&loaded) {
+                    self.toasts.error(format!("Could not save memorization history: {}", e));
+                }
+            }
         }
     }
 
@@ -1493,8 +3259,1055 @@ impl App {
         }
     }
 
-    /// Get the current verse in focus mode (for copy/save operations)
-    pub fn get_focus_verse(&self) -> Option<&Scripture> {
-        self.focus_state.as_ref().map(|s| &s.current_verse)
+    /// Get the verses being studied in focus mode (for copy/save operations)
+    pub fn get_focus_passage(&self) -> &[Scripture] {
+        self.focus_state.as_ref().map(|s| s.passage.as_slice()).unwrap_or(&[])
+    }
+
+    /// "Alma 32" for the chapter on screen, if any
+    pub fn current_chapter_title(&self) -> Option<String> {
+        let verse = match self.screen {
+            Screen::Focus => self.get_focus_passage().first(),
+            Screen::Journal | Screen::Talks | Screen::ComeFollowMe | Screen::Topics => None,
+            Screen::Reading => {
+                return self.reading_current_chapter().map(|(book, chapter)| format!("{} {}", book, chapter));
+            }
+            _ => self.cached_verses.first(),
+        }?;
+        Some(format!("{} {}", verse.book_title, verse.chapter_number))
+    }
+
+    /// Create a dated journal entry for the current chapter and verses, and open it in $EDITOR
+    pub fn new_journal_entry(&mut self, verses: &[Scripture]) {
+        let chapter = self.current_chapter_title();
+        match self.journal.create_entry(chapter.as_deref(), verses) {
+            Ok((path, template)) => {
+                self.editor_request = Some(EditorRequest { path, template: Some(template) });
+            }
+            Err(e) => self.toasts.error(format!("Couldn't create journal entry: {}", e)),
+        }
+    }
+
+    pub fn edit_selected_journal_entry(&mut self) {
+        if let Some(entry) = self.journal_state.selected().and_then(|i| self.journal.entries.get(i)) {
+            self.editor_request = Some(EditorRequest { path: entry.path.clone(), template: None });
+        }
+    }
+
+    /// Called by the main loop once the editor exits: show the saved entry in the Journal screen
+    pub fn finish_editing(&mut self, request: EditorRequest, result: anyhow::Result<()>) {
+        if let Err(e) = result {
+            self.toasts.error(format!("Editor failed: {}", e));
+        }
+        let discarded = request
+            .template
+            .as_ref()
+            .is_some_and(|t| Journal::discard_if_unchanged(&request.path, t).unwrap_or(false));
+        if let Err(e) = self.journal.reload() {
+            self.toasts.error(format!("Couldn't read journal: {}", e));
+        }
+        if discarded {
+            self.toasts.info("Empty journal entry discarded");
+            return;
+        }
+
+        let name = request.path.file_name();
+        let index = self.journal.entries.iter().position(|e| e.path.file_name() == name);
+        if let (Some(_), Some(entry)) = (&request.template, index.and_then(|i| self.journal.entries.get(i))) {
+            let references = entry.references().into_iter().map(str::to_string).collect();
+            events::emit(Event::NoteAdded { path: entry.path.clone(), references });
+        }
+        self.journal_state.select(index.or(Some(0)).filter(|_| !self.journal.entries.is_empty()));
+        self.journal_scroll = 0;
+        self.screen = Screen::Journal;
+        self.input_mode = InputMode::Normal;
+        self.toasts.success("Journal entry saved");
+    }
+
+    pub fn open_journal(&mut self) {
+        if let Err(e) = self.journal.reload() {
+            self.toasts.error(format!("Couldn't read journal: {}", e));
+        }
+        if self.journal_state.selected().is_none_or(|i| i >= self.journal.entries.len()) {
+            self.journal_state.select(if self.journal.entries.is_empty() { None } else { Some(0) });
+        }
+        self.journal_scroll = 0;
+        self.screen = Screen::Journal;
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn journal_select(&mut self, index: usize) {
+        if self.journal.entries.is_empty() {
+            return;
+        }
+        self.journal_state.select(Some(index.min(self.journal.entries.len() - 1)));
+        Self::adjust_list_offset(&mut self.journal_state, self.journal_visible_height);
+        self.journal_scroll = 0;
+    }
+
+    pub fn journal_nav_down(&mut self) {
+        self.journal_select(self.journal_state.selected().map_or(0, |i| i + 1));
+    }
+
+    pub fn journal_nav_up(&mut self) {
+        self.journal_select(self.journal_state.selected().unwrap_or(0).saturating_sub(1));
+    }
+
+    pub fn delete_selected_journal_entry(&mut self) {
+        let Some(i) = self.journal_state.selected() else {
+            return;
+        };
+        match self.journal.delete(i) {
+            Ok(entry) => {
+                self.toasts.info(format!("Deleted journal entry \"{}\"", entry.title));
+                let len = self.journal.entries.len();
+                self.journal_state.select(if len == 0 { None } else { Some(i.min(len - 1)) });
+                self.journal_scroll = 0;
+            }
+            Err(e) => self.toasts.error(format!("Couldn't delete journal entry: {}", e)),
+        }
+    }
+
+    /// Open the word lookup for these verses, loading the dictionary the first time
+    pub fn open_word_lookup(&mut self, verses: &[Scripture]) {
+        let Some(first) = verses.first() else {
+            return;
+        };
+        if self.dictionary.is_none() {
+            match Self::load_dictionary() {
+                Ok(dictionary) => self.dictionary = Some(dictionary),
+                Err(e) => {
+                    self.toasts.push(ToastLevel::Warning, format!("Webster 1828 dictionary not installed: {}", e));
+                    return;
+                }
+            }
+        }
+        let Some(dictionary) = &self.dictionary else {
+            return;
+        };
+
+        let mut words: Vec<String> = Vec::new();
+        let text = verses.iter().map(|v| v.scripture_text.as_str()).collect::<Vec<_>>().join(" ");
+        for word in text.split(|c: char| !c.is_alphabetic() && c != '\'') {
+            let word = word.trim_matches('\'');
+            if word.chars().count() > 1
+                && !words.iter().any(|w| w.eq_ignore_ascii_case(word))
+                && dictionary.lookup(word).is_some()
+            {
+                words.push(word.to_string());
+            }
+        }
+        if words.is_empty() {
+            self.toasts.info("No words in this verse are in the dictionary");
+            return;
+        }
+
+        let reference = match verses {
+            [verse] => verse.verse_title.clone(),
+            [.., last] => format!("{}-{}", first.verse_title, last.verse_number),
+            [] => String::new(),
+        };
+        let mut state = ListState::default();
+        state.select(Some(0));
+        self.word_lookup = Some(WordLookup { reference, words, state, scroll: 0 });
+    }
+
+    fn load_dictionary() -> escrituras_core::error::Result<Dictionary> {
+        let dir = find_data_dir(Dictionary::FILE_NAME)
+            .ok_or_else(|| EscriturasError::DataNotFound(format!("add {} to data/ or ~/.local/share/escrituras/data/", Dictionary::FILE_NAME)))?;
+        Dictionary::load(&dir)
+    }
+
+    pub fn word_lookup_nav(&mut self, down: bool) {
+        if let Some(lookup) = &mut self.word_lookup {
+            let i = lookup.state.selected().unwrap_or(0);
+            let i = if down { (i + 1).min(lookup.words.len() - 1) } else { i.saturating_sub(1) };
+            lookup.state.select(Some(i));
+            lookup.scroll = 0;
+        }
+    }
+
+    /// Headword and definitions for the highlighted word
+    pub fn word_lookup_definition(&self) -> Option<(&str, &[String])> {
+        let lookup = self.word_lookup.as_ref()?;
+        let word = lookup.words.get(lookup.state.selected()?)?;
+        self.dictionary.as_ref()?.lookup(word)
+    }
+
+    /// Open the interlinear lookup for these verses, loading the Strong's data the first time
+    pub fn open_strongs_lookup(&mut self, verses: &[Scripture]) {
+        if verses.is_empty() {
+            return;
+        }
+        if self.interlinear.is_none() {
+            match Self::load_interlinear() {
+                Ok(interlinear) => self.interlinear = Some(interlinear),
+                Err(e) => {
+                    self.toasts.push(ToastLevel::Warning, format!("Strong's data not installed: {}", e));
+                    return;
+                }
+            }
+        }
+        let Some(interlinear) = &self.interlinear else {
+            return;
+        };
+
+        let tagged: Vec<(&Scripture, &[TaggedWord])> =
+            verses.iter().filter_map(|v| Some((v, interlinear.words(&v.verse_title)?))).collect();
+        let words: Vec<TaggedWord> =
+            tagged.iter().flat_map(|(_, words)| words.iter()).filter(|w| !w.strongs.is_empty()).cloned().collect();
+        let reference = match tagged.as_slice() {
+            _ if words.is_empty() => {
+                self.toasts.info("No Strong's data for this verse (the Bible only)");
+                return;
+            }
+            [(verse, _)] => verse.verse_title.clone(),
+            [(first, _), .., (last, _)] => format!("{}-{}", first.verse_title, last.verse_number),
+            [] => return,
+        };
+        let mut state = ListState::default();
+        state.select(Some(0));
+        self.strongs_lookup = Some(StrongsLookup { reference, words, state, scroll: 0 });
+    }
+
+    fn load_interlinear() -> escrituras_core::error::Result<Interlinear> {
+        let dir = find_data_dir(Interlinear::VERSES_FILE_NAME).ok_or_else(|| {
+            EscriturasError::DataNotFound(format!("{} not found in data/", Interlinear::VERSES_FILE_NAME))
+        })?;
+        Interlinear::load(&dir)
+    }
+
+    pub fn strongs_lookup_nav(&mut self, down: bool) {
+        if let Some(lookup) = &mut self.strongs_lookup {
+            let i = lookup.state.selected().unwrap_or(0);
+            let i = if down { (i + 1).min(lookup.words.len() - 1) } else { i.saturating_sub(1) };
+            lookup.state.select(Some(i));
+            lookup.scroll = 0;
+        }
+    }
+
+    /// The highlighted word and the lexicon entry of each of its Strong's
+    /// numbers (None for a number missing from the lexicon)
+    pub fn strongs_lookup_entries(&self) -> Option<(&TaggedWord, Vec<StrongsDefinition<'_>>)> {
+        let lookup = self.strongs_lookup.as_ref()?;
+        let word = lookup.words.get(lookup.state.selected()?)?;
+        let interlinear = self.interlinear.as_ref()?;
+        Some((word, word.strongs.iter().map(|n| (n.as_str(), interlinear.lookup(n))).collect()))
+    }
+
+    /// Whether the footnote pane is shown under the chapter text
+    pub fn footnote_pane_visible(&self) -> bool {
+        self.footnotes.is_some() && !self.layout.footnotes_hidden && !self.show_context_panel
+    }
+
+    /// Footnotes of the selected verse, one (footnote, reference) row per cross-reference
+    pub fn footnote_rows(&self) -> Vec<(&Footnote, &str)> {
+        let (Some(db), Some(verse)) = (&self.footnotes, self.get_selected_verse()) else {
+            return Vec::new();
+        };
+        db.for_verse(&verse.verse_title)
+            .iter()
+            .flat_map(|note| note.references.iter().map(move |r| (note, r.as_str())))
+            .collect()
+    }
+
+    pub fn toggle_footnotes(&mut self) {
+        if self.footnotes.is_none() {
+            self.toasts.push(
+                ToastLevel::Warning,
+                format!("No footnote data: add {} to the data directory", FootnoteDb::FILE_NAME),
+            );
+            return;
+        }
+        self.layout.footnotes_hidden = !self.layout.footnotes_hidden;
+        if self.layout.footnotes_hidden && self.focus == FocusPane::References {
+            self.focus = FocusPane::Content;
+        }
+        self.save_layout();
+    }
+
+    pub fn footnote_nav_down(&mut self) {
+        let len = self.footnote_rows().len();
+        if len > 0 {
+            let i = self.footnote_state.selected().unwrap_or(0);
+            self.footnote_state.select(Some((i + 1).min(len - 1)));
+        }
+    }
+
+    pub fn footnote_nav_up(&mut self) {
+        let i = self.footnote_state.selected().unwrap_or(0);
+        self.footnote_state.select(Some(i.saturating_sub(1)));
+    }
+
+    /// Follow the highlighted cross-reference, remembering where we were
+    pub fn jump_to_footnote(&mut self) {
+        let reference = self
+            .footnote_state
+            .selected()
+            .and_then(|i| self.footnote_rows().get(i).map(|(_, r)| r.to_string()));
+        let Some(reference) = reference else {
+            return;
+        };
+        match self.scripture_db.parse_reference(&reference) {
+            Some(range) => {
+                self.push_navigation_state();
+                self.jump_to_scripture_range(&range);
+                self.focus = FocusPane::Content;
+                self.footnote_state.select(Some(0));
+            }
+            None => self.toasts.info(format!("{} is a study aid, not a verse", reference)),
+        }
+    }
+
+    /// Whether the related-verses panel is shown beside the chapter text
+    pub fn related_pane_visible(&self) -> bool {
+        self.embeddings_db.is_some() && self.layout.related_visible && !self.show_context_panel
+    }
+
+    /// Recompute the related verses when the selected verse has changed
+    pub fn refresh_related(&mut self) {
+        let title = self.get_selected_verse().map(|v| v.verse_title.clone());
+        if title == self.related_for {
+            return;
+        }
+        self.related = match (&self.embeddings_db, &title) {
+            (Some(db), Some(title)) => db.similar_to(title, RELATED_VERSES),
+            _ => Vec::new(),
+        };
+        self.related_for = title;
+        self.related_state.select(if self.related.is_empty() { None } else { Some(0) });
+    }
+
+    pub fn toggle_related(&mut self) {
+        if self.embeddings_db.is_none() {
+            self.toasts.push(
+                ToastLevel::Warning,
+                "No verse embeddings: add scripture_embeddings.npy to the data directory",
+            );
+            return;
+        }
+        self.layout.related_visible = !self.layout.related_visible;
+        if !self.layout.related_visible && self.focus == FocusPane::Related {
+            self.focus = FocusPane::Content;
+        }
+        self.save_layout();
+    }
+
+    pub fn related_nav_down(&mut self) {
+        let len = self.related.len();
+        if len > 0 {
+            let i = self.related_state.selected().unwrap_or(0);
+            self.related_state.select(Some((i + 1).min(len - 1)));
+        }
+    }
+
+    pub fn related_nav_up(&mut self) {
+        let i = self.related_state.selected().unwrap_or(0);
+        self.related_state.select(Some(i.saturating_sub(1)));
+    }
+
+    /// Open the highlighted related verse, remembering where we were
+    pub fn jump_to_related(&mut self) {
+        let Some((title, _)) = self.related_state.selected().and_then(|i| self.related.get(i)).cloned() else {
+            return;
+        };
+        if let Some(range) = self.scripture_db.parse_reference(&title) {
+            self.push_navigation_state();
+            self.jump_to_scripture_range(&range);
+            self.focus = FocusPane::Content;
+        }
+    }
+
+    /// Whether the chapter outline is shown beside the chapter text
+    pub fn outline_pane_visible(&self) -> bool {
+        self.layout.outline_visible && !self.show_context_panel && !self.cached_verses.is_empty()
+    }
+
+    /// Outline the chapter when it has changed: the AI's outline if one is
+    /// cached, otherwise one from the wording of the verses
+    pub fn refresh_outline(&mut self) {
+        let (Some(first), Some(last)) = (self.cached_verses.first(), self.cached_verses.last()) else {
+            self.outline = None;
+            return;
+        };
+        let current = self.outline.as_ref().is_some_and(|o| {
+            o.book == first.book_title && o.chapter == first.chapter_number && o.last_verse == last.verse_number
+        });
+        if current {
+            return;
+        }
+        let cached = OutlineCache::default_dir()
+            .ok()
+            .and_then(|dir| OutlineCache::new(dir).get(&first.book_title, first.chapter_number, last.verse_number));
+        self.outline = Some(cached.unwrap_or_else(|| Outline::heuristic(&self.cached_verses)));
+        self.outline_collapsed.clear();
+        self.outline_state.select(Some(0));
+    }
+
+    pub fn toggle_outline(&mut self) {
+        self.layout.outline_visible = !self.layout.outline_visible;
+        if !self.layout.outline_visible && self.focus == FocusPane::Outline {
+            self.focus = FocusPane::Content;
+        }
+        self.save_layout();
+    }
+
+    /// The outline's rows as (section, child) indexes, leaving out the
+    /// children of collapsed sections
+    pub fn outline_rows(&self) -> Vec<(usize, Option<usize>)> {
+        let Some(outline) = &self.outline else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        for (i, section) in outline.sections.iter().enumerate() {
+            rows.push((i, None));
+            if !self.outline_collapsed.contains(&i) {
+                rows.extend((0..section.children.len()).map(|c| (i, Some(c))));
+            }
+        }
+        rows
+    }
+
+    pub fn outline_nav_down(&mut self) {
+        let len = self.outline_rows().len();
+        if len > 0 {
+            let i = self.outline_state.selected().unwrap_or(0);
+            self.outline_state.select(Some((i + 1).min(len - 1)));
+        }
+    }
+
+    pub fn outline_nav_up(&mut self) {
+        let i = self.outline_state.selected().unwrap_or(0);
+        self.outline_state.select(Some(i.saturating_sub(1)));
+    }
+
+    /// Expand the highlighted section, or go to its first verse
+    pub fn outline_select(&mut self) {
+        let rows = self.outline_rows();
+        let Some(&(section, child)) = self.outline_state.selected().and_then(|i| rows.get(i)) else {
+            return;
+        };
+        if child.is_none() && self.outline_collapsed.remove(&section) {
+            return;
+        }
+        let Some(outline) = &self.outline else {
+            return;
+        };
+        let target = &outline.sections[section];
+        let start = child.map_or(target.start_verse, |c| target.children[c].start_verse);
+        if let Some(idx) = self.cached_verses.iter().position(|v| v.verse_number == start) {
+            self.selected_verse_idx = Some(idx);
+            self.verse_line_offset = 0;
+            self.focus = FocusPane::Content;
+        }
+    }
+
+    /// Collapse the highlighted section (from one of its parts, go up to it);
+    /// false when there's nothing to collapse
+    pub fn outline_collapse(&mut self) -> bool {
+        let rows = self.outline_rows();
+        let Some(&(section, child)) = self.outline_state.selected().and_then(|i| rows.get(i)) else {
+            return false;
+        };
+        if child.is_some() {
+            self.outline_state.select(rows.iter().position(|&row| row == (section, None)));
+            return true;
+        }
+        let has_children = self.outline.as_ref().is_some_and(|o| !o.sections[section].children.is_empty());
+        has_children && self.outline_collapsed.insert(section)
+    }
+
+    /// Ask the AI provider to outline the chapter, in the background
+    pub fn generate_outline(&mut self) {
+        if self.outline_task.is_some() || self.cached_verses.is_empty() {
+            return;
+        }
+        let verses = self.cached_verses.clone();
+        let prompt = escrituras_core::outline::prompt(&verses, &Outline::heuristic(&verses));
+        let model = self.selected_model.clone();
+        let ollama = self.ollama.clone();
+        let task = match self.current_provider {
+            Provider::Ollama => tokio::spawn(async move {
+                let answer = ollama.query(&model, &prompt).await?;
+                Outline::from_ai(&verses, &answer, &model)
+            }),
+            Provider::Claude => {
+                let Some(client) = self.claude_client.clone() else {
+                    self.toasts.push(ToastLevel::Warning, "Claude API key not configured. Press 'P' to set up.");
+                    return;
+                };
+                tokio::spawn(async move {
+                    let answer = client.query(&model, &prompt).await?;
+                    Outline::from_ai(&verses, &answer, &model)
+                })
+            }
+            Provider::OpenAI => {
+                let Some(client) = self.openai_client.clone() else {
+                    self.toasts.push(ToastLevel::Warning, "OpenAI API key not configured. Press 'P' to set up.");
+                    return;
+                };
+                tokio::spawn(async move {
+                    let answer = client.query(&model, &prompt).await?;
+                    Outline::from_ai(&verses, &answer, &model)
+                })
+            }
+        };
+        self.outline_task = Some(task);
+        self.toasts.info(format!("Outlining {} with {}...", self.content_title(), self.selected_model));
+    }
+
+    /// Show and cache the AI's outline once it's ready
+    pub fn finish_outline(&mut self, result: escrituras_core::error::Result<Outline>) {
+        let outline = match result {
+            Ok(outline) => outline,
+            Err(e) => {
+                self.toasts.error(format!("Couldn't outline the chapter: {}", e));
+                return;
+            }
+        };
+        let saved = OutlineCache::default_dir().and_then(|dir| OutlineCache::new(dir).put(&outline));
+        if let Err(e) = saved {
+            tracing::warn!("Failed to cache outline: {}", e);
+        }
+        self.toasts.success(format!("Outlined {} {}", outline.book, outline.chapter));
+        // Shown only if it's still the chapter being read
+        let current = self.cached_verses.first().is_some_and(|v| v.book_title == outline.book && v.chapter_number == outline.chapter);
+        if current {
+            self.outline = Some(outline);
+            self.outline_collapsed.clear();
+            self.outline_state.select(Some(0));
+        }
+    }
+
+    pub fn open_talks(&mut self) {
+        let Some(db) = &self.talks else {
+            self.toasts.push(
+                ToastLevel::Warning,
+                format!("No conference talks: add {} to the data directory", TalkDb::FILE_NAME),
+            );
+            return;
+        };
+        let view = &mut self.talks_view;
+        if view.open_talk.is_none() && !db.talks().is_empty() {
+            view.list_state.select(Some(view.list_state.selected().unwrap_or(0)));
+        }
+        self.screen = Screen::Talks;
+    }
+
+    /// Labels of the talk browser's list: search hits, or the current browse level
+    pub fn talk_list_items(&self) -> Vec<String> {
+        let Some(db) = &self.talks else {
+            return Vec::new();
+        };
+        let view = &self.talks_view;
+        if let Some(results) = &view.results {
+            return results
+                .iter()
+                .filter_map(|hit| {
+                    let talk = db.get(hit.talk)?;
+                    Some(format!("{} ({}) ¶{}", talk.title, talk.speaker, hit.paragraph + 1))
+                })
+                .collect();
+        }
+        match (view.level, &view.speaker, view.year) {
+            (TalkLevel::Year, Some(speaker), _) => db.years_for(speaker).iter().map(|y| y.to_string()).collect(),
+            (TalkLevel::Talk, Some(speaker), Some(year)) => db
+                .talks_for(speaker, year)
+                .into_iter()
+                .map(|i| format!("{} ({})", db.talks()[i].title, db.talks()[i].session()))
+                .collect(),
+            _ => db.speakers(),
+        }
+    }
+
+    pub fn talks_list_select(&mut self, index: usize) {
+        let len = self.talk_list_items().len();
+        let view = &mut self.talks_view;
+        view.list_state.select(if len == 0 { None } else { Some(index.min(len - 1)) });
+        Self::adjust_list_offset(&mut view.list_state, view.visible_height);
+    }
+
+    pub fn talks_nav_down(&mut self) {
+        if self.talks_view.text_focused {
+            self.talk_paragraph_select(self.talks_view.paragraph + 1);
+        } else {
+            self.talks_list_select(self.talks_view.list_state.selected().map_or(0, |i| i + 1));
+        }
+    }
+
+    pub fn talks_nav_up(&mut self) {
+        if self.talks_view.text_focused {
+            self.talk_paragraph_select(self.talks_view.paragraph.saturating_sub(1));
+        } else {
+            self.talks_list_select(self.talks_view.list_state.selected().unwrap_or(0).saturating_sub(1));
+        }
+    }
+
+    pub fn talk_paragraph_select(&mut self, index: usize) {
+        let count = self.open_talk().map_or(0, |t| t.paragraphs.len());
+        if count > 0 {
+            self.talks_view.paragraph = index.min(count - 1);
+        }
+    }
+
+    /// The talk in the reading pane
+    pub fn open_talk(&self) -> Option<&Talk> {
+        self.talks.as_ref()?.get(self.talks_view.open_talk?)
+    }
+
+    /// Drill into the highlighted speaker or year, or open the highlighted talk or hit
+    pub fn talks_select(&mut self) {
+        let Some(db) = &self.talks else {
+            return;
+        };
+        let view = &mut self.talks_view;
+        if view.text_focused {
+            return;
+        }
+        let Some(i) = view.list_state.selected() else {
+            return;
+        };
+        if let Some(results) = &view.results {
+            if let Some(hit) = results.get(i) {
+                view.open_talk = Some(hit.talk);
+                view.paragraph = hit.paragraph;
+                view.text_focused = true;
+            }
+            return;
+        }
+        match (view.level, view.speaker.clone(), view.year) {
+            (TalkLevel::Speaker, _, _) => {
+                view.speaker = db.speakers().get(i).cloned();
+                view.level = TalkLevel::Year;
+            }
+            (TalkLevel::Year, Some(speaker), _) => {
+                view.year = db.years_for(&speaker).get(i).copied();
+                view.level = TalkLevel::Talk;
+            }
+            (TalkLevel::Talk, Some(speaker), Some(year)) => {
+                if let Some(&talk) = db.talks_for(&speaker, year).get(i) {
+                    view.open_talk = Some(talk);
+                    view.paragraph = 0;
+                    view.text_focused = true;
+                }
+                return;
+            }
+            _ => return,
+        }
+        view.list_state.select(Some(0));
+    }
+
+    /// Step back out of the reading pane, the search results, or one browse level
+    pub fn talks_back(&mut self) {
+        let view = &mut self.talks_view;
+        if view.text_focused {
+            view.text_focused = false;
+            return;
+        }
+        if view.results.take().is_some() {
+            view.list_state.select(Some(0));
+            return;
+        }
+        let (level, position) = match view.level {
+            TalkLevel::Speaker => return,
+            TalkLevel::Year => {
+                let speaker = view.speaker.take();
+                let speakers = self.talks.as_ref().map(|db| db.speakers()).unwrap_or_default();
+                (TalkLevel::Speaker, speakers.iter().position(|s| Some(s) == speaker.as_ref()))
+            }
+            TalkLevel::Talk => {
+                let year = view.year.take();
+                let years = match (&self.talks, &view.speaker) {
+                    (Some(db), Some(speaker)) => db.years_for(speaker),
+                    _ => Vec::new(),
+                };
+                (TalkLevel::Year, years.iter().position(|y| Some(*y) == year))
+            }
+        };
+        view.level = level;
+        view.list_state.select(Some(position.unwrap_or(0)));
+        Self::adjust_list_offset(&mut view.list_state, view.visible_height);
+    }
+
+    /// Search talks by keyword, or by meaning when semantic mode is on
+    pub fn perform_talk_search(&mut self) {
+        let Some(db) = &self.talks else {
+            return;
+        };
+        let query = self.talks_view.search_input.trim().to_string();
+        if query.is_empty() {
+            self.talks_view.results = None;
+            return;
+        }
+        let limit = self.search_filter.limit;
+        let hits = if self.talks_view.semantic {
+            let embedded = match self.embeddings_db.as_mut() {
+                Some(embeddings) => embeddings.embed_query(&query),
+                None => Err(EscriturasError::EmbeddingsMissing("Embeddings aren't installed".to_string())),
+            };
+            embedded.and_then(|q| db.semantic_search(&q, limit))
+        } else {
+            Ok(db.search(&query, limit))
+        };
+        match hits {
+            Ok(hits) => {
+                if hits.is_empty() {
+                    self.toasts.info(format!("No talks match \"{}\"", query));
+                }
+                let view = &mut self.talks_view;
+                view.list_state.select(if hits.is_empty() { None } else { Some(0) });
+                view.results = Some(hits);
+                view.text_focused = false;
+            }
+            Err(e) => self.toasts.error(format!("Talk search failed: {}", e)),
+        }
+    }
+
+    /// Switch talk search between keyword and semantic
+    pub fn toggle_talk_search_mode(&mut self) {
+        let semantic = !self.talks_view.semantic;
+        if semantic && !self.talks.as_ref().is_some_and(|db| db.has_embeddings()) {
+            self.toasts.push(
+                ToastLevel::Warning,
+                format!("Semantic talk search unavailable: add {} to the data directory", TalkDb::EMBEDDINGS_FILE),
+            );
+            return;
+        }
+        self.talks_view.semantic = semantic;
+        if self.talks_view.results.is_some() {
+            self.perform_talk_search();
+        }
+    }
+
+    /// Save the highlighted paragraph (or a search hit's paragraph) to the saved scriptures
+    pub fn save_talk_paragraph(&mut self) {
+        let view = &self.talks_view;
+        let target = if view.text_focused {
+            view.open_talk.map(|t| (t, view.paragraph))
+        } else {
+            match (&view.results, view.list_state.selected()) {
+                (Some(results), Some(i)) => results.get(i).map(|hit| (hit.talk, hit.paragraph)),
+                _ => view.open_talk.map(|t| (t, view.paragraph)),
+            }
+        };
+        let paragraph = target.and_then(|(t, p)| self.talks.as_ref()?.get(t)?.paragraph_as_scripture(p));
+        if let Some(paragraph) = paragraph {
+            self.save_to_context(vec![paragraph]);
+        }
+    }
+
+    /// Open the Come, Follow Me screen on this week's reading
+    pub fn open_come_follow_me(&mut self) {
+        let Some(week) = self.cfm.as_ref().and_then(|s| s.current_week()) else {
+            self.toasts.push(
+                ToastLevel::Warning,
+                format!("No Come, Follow Me schedule: add {} to the data directory", CfmSchedule::FILE_NAME),
+            );
+            return;
+        };
+        self.cfm_week = week;
+        self.cfm_state.select(Some(0));
+        self.screen = Screen::ComeFollowMe;
+    }
+
+    /// The week shown on the Come, Follow Me screen
+    pub fn cfm_current_week(&self) -> Option<&CfmWeek> {
+        self.cfm.as_ref()?.weeks().get(self.cfm_week)
+    }
+
+    pub fn cfm_readings(&self) -> Vec<CfmReading> {
+        self.cfm_current_week().map(|w| w.readings(&self.scripture_db)).unwrap_or_default()
+    }
+
+    /// Show the previous (-1) or next (1) week
+    pub fn cfm_change_week(&mut self, delta: isize) {
+        let weeks = self.cfm.as_ref().map_or(0, |s| s.weeks().len());
+        let week = self.cfm_week.saturating_add_signed(delta);
+        if week < weeks {
+            self.cfm_week = week;
+            self.cfm_state.select(Some(0));
+        }
+    }
+
+    pub fn cfm_select(&mut self, index: usize) {
+        let len = self.cfm_readings().len();
+        if len > 0 {
+            self.cfm_state.select(Some(index.min(len - 1)));
+        }
+    }
+
+    /// Read one of the week's chapters in Browse (Back returns to where we were)
+    pub fn cfm_jump(&mut self, index: usize) {
+        let Some(reading) = self.cfm_readings().into_iter().nth(index) else {
+            return;
+        };
+        self.cfm_state.select(Some(index));
+        self.push_navigation_state();
+        self.jump_to_scripture_range(&reading.range);
+        if !reading.label.contains(':') {
+            self.clear_selected_range();
+        }
+        self.screen = Screen::Browse;
+        self.focus = FocusPane::Content;
+    }
+
+    /// Mark the highlighted reading finished (or not)
+    pub fn cfm_toggle_done(&mut self) {
+        let (Some(week), Some(i)) = (self.cfm_current_week().cloned(), self.cfm_state.selected()) else {
+            return;
+        };
+        let Some(reading) = self.cfm_readings().into_iter().nth(i) else {
+            return;
+        };
+        let loaded = self.cfm_progress.clone();
+        let done = self.cfm_progress.toggle(&week, &reading.label);
+        if let Err(e) = self.cfm_progress.save(&loaded) {
+            self.toasts.error(format!("Couldn't save Come, Follow Me progress: {}", e));
+        }
+        let readings = self.cfm_readings();
+        if done && readings.iter().all(|r| self.cfm_progress.is_done(&week, &r.label)) {
+            self.toasts.success(format!("Finished this week's reading: {}", week.title));
+        }
+    }
+
+    pub fn open_topics(&mut self) {
+        let view = &mut self.topics_view;
+        if view.list_state.selected().is_none() {
+            view.list_state.select(Some(0));
+        }
+        self.screen = Screen::Topics;
+    }
+
+    /// Build (or read from the cache) the page of the highlighted topic and move to it
+    pub fn topics_open_page(&mut self) {
+        let Some(topic) = self.topics_view.list_state.selected().and_then(|i| TOPICS.get(i)) else {
+            return;
+        };
+        let mut index = TopicIndex::new(&self.scripture_db)
+            .with_footnotes(self.footnotes.as_ref())
+            .with_embeddings(self.embeddings_db.as_mut())
+            .with_talks(self.talks.as_ref());
+        if let Ok(dir) = TopicIndex::default_cache_dir() {
+            index = index.with_cache_dir(dir);
+        }
+        let page = match index.page(topic) {
+            Ok(page) => page,
+            Err(e) => {
+                self.toasts.error(format!("Couldn't cache the {} page: {}", topic.name, e));
+                index.build(topic)
+            }
+        };
+        let view = &mut self.topics_view;
+        view.page = Some(page);
+        view.page_focused = true;
+        view.page_state.select(None);
+        self.topics_page_select(0, 1);
+    }
+
+    /// The open page's rows: a heading over each section that has anything in it
+    pub fn topic_rows(&self) -> Vec<TopicRow> {
+        let Some(page) = &self.topics_view.page else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        let sections = [
+            ("Topical Guide", page.topical_guide.iter().cloned().map(TopicRow::Verse).collect::<Vec<_>>()),
+            ("Related verses", page.related.iter().cloned().map(TopicRow::Verse).collect()),
+            ("Conference talks", (0..page.talks.len()).map(TopicRow::Talk).collect()),
+        ];
+        for (heading, section) in sections {
+            if !section.is_empty() {
+                rows.push(TopicRow::Heading(format!("{} ({})", heading, section.len())));
+                rows.extend(section);
+            }
+        }
+        rows
+    }
+
+    /// Highlight the page row at `index`, or the nearest one past it in
+    /// `direction` (1 or -1) that isn't a heading
+    pub fn topics_page_select(&mut self, index: usize, direction: isize) {
+        let rows = self.topic_rows();
+        let mut i = index.min(rows.len().saturating_sub(1));
+        while matches!(rows.get(i), Some(TopicRow::Heading(_))) {
+            match i.checked_add_signed(direction) {
+                Some(next) if next < rows.len() => i = next,
+                // Nothing past the heading that way; stay where we were
+                _ => return,
+            }
+        }
+        if i < rows.len() {
+            self.topics_view.page_state.select(Some(i));
+        }
+    }
+
+    /// Move `delta` rows through the topic list or the open page
+    pub fn topics_nav(&mut self, delta: isize) {
+        let view = &mut self.topics_view;
+        if view.page_focused {
+            let i = view.page_state.selected().unwrap_or(0).saturating_add_signed(delta);
+            self.topics_page_select(i, delta.signum());
+        } else {
+            let i = view.list_state.selected().unwrap_or(0).saturating_add_signed(delta);
+            view.list_state.select(Some(i.min(TOPICS.len() - 1)));
+        }
+    }
+
+    /// Open the highlighted topic, or go to the highlighted verse or talk of its page
+    pub fn topics_select(&mut self) {
+        if !self.topics_view.page_focused {
+            self.topics_open_page();
+            return;
+        }
+        let row = self.topics_view.page_state.selected().and_then(|i| self.topic_rows().into_iter().nth(i));
+        match row {
+            Some(TopicRow::Verse(title)) => {
+                let Some(verse) = self.scripture_db.get_by_title(&title) else {
+                    return;
+                };
+                let range = ScriptureRange::of_verse(verse);
+                self.push_navigation_state();
+                self.jump_to_scripture_range(&range);
+                self.screen = Screen::Browse;
+                self.focus = FocusPane::Content;
+            }
+            Some(TopicRow::Talk(i)) => {
+                let Some(talk) = self.topics_view.page.as_ref().and_then(|p| p.talks.get(i)).cloned() else {
+                    return;
+                };
+                let found = self.talks.as_ref().and_then(|db| {
+                    db.talks().iter().position(|t| t.title == talk.title && t.speaker == talk.speaker)
+                });
+                let Some(index) = found else {
+                    self.toasts.info(format!("\"{}\" isn't in the loaded talks", talk.title));
+                    return;
+                };
+                let view = &mut self.talks_view;
+                view.open_talk = Some(index);
+                view.paragraph = talk.paragraph;
+                view.text_focused = true;
+                self.screen = Screen::Talks;
+            }
+            _ => {}
+        }
+    }
+
+    /// AI request to build a lesson from the shown week's reading
+    pub fn cfm_lesson_prompt(&self) -> Option<String> {
+        let week = self.cfm_current_week()?;
+        Some(format!(
+            "Help me prepare a lesson from this week's Come, Follow Me reading, \"{}\" ({}). \
+             Suggest an outline with the key doctrines and principles, the verses to read together, \
+             discussion questions, and a way to invite people to apply what they learn.",
+            week.title,
+            week.reading.join("; ")
+        ))
+    }
+
+    /// Read the current chapter reflowed into paragraphs
+    pub fn enter_reading_mode(&mut self) {
+        let Some(verse) = self.cached_verses.first() else {
+            self.toasts.info("Open a chapter to read");
+            return;
+        };
+        self.reading = ReadingView {
+            chapters: vec![(verse.book_title.to_string(), verse.chapter_number)],
+            ..ReadingView::default()
+        };
+        self.screen = Screen::Reading;
+    }
+
+    /// Leave reading mode on the chapter at the top of the screen
+    pub fn exit_reading_mode(&mut self) {
+        if let Some((book, chapter)) = self.reading_current_chapter().cloned() {
+            if let Some(range) = self.scripture_db.parse_reference(&format!("{} {}", book, chapter)) {
+                self.jump_to_scripture_range(&range);
+                self.clear_selected_range();
+            }
+        }
+        self.screen = Screen::Browse;
+    }
+
+    /// The chapter whose text is at the top of the screen
+    pub fn reading_current_chapter(&self) -> Option<&(String, i32)> {
+        let view = &self.reading;
+        let i = view.chapter_starts.iter().rposition(|&start| start <= view.scroll).unwrap_or(0);
+        view.chapters.get(i)
+    }
+
+    /// Rewrap the loaded chapters to `width` if it changed, keeping the top chapter in view
+    pub fn reading_relayout(&mut self, width: usize) {
+        if width == self.reading.width && !self.reading.lines.is_empty() {
+            self.reading_fill();
+            return;
+        }
+        let top = self.reading.chapter_starts.iter().rposition(|&start| start <= self.reading.scroll).unwrap_or(0);
+        let chapters = self.reading.chapters.clone();
+        let view = &mut self.reading;
+        view.width = width;
+        view.lines.clear();
+        view.chapter_starts.clear();
+        for (book, chapter) in &chapters {
+            let lines = Self::reading_chapter_lines(&self.scripture_db, book, *chapter, width);
+            view.chapter_starts.push(view.lines.len());
+            view.lines.extend(lines);
+        }
+        view.scroll = view.chapter_starts.get(top).copied().unwrap_or(0);
+        self.reading_fill();
+    }
+
+    pub fn reading_scroll(&mut self, delta: isize) {
+        let view = &mut self.reading;
+        view.scroll = view.scroll.saturating_add_signed(delta).min(view.lines.len().saturating_sub(1));
+        self.reading_fill();
+    }
+
+    /// Load neighbouring chapters so there's always a screen of text above and below
+    fn reading_fill(&mut self) {
+        let width = self.reading.width;
+        let height = self.reading.visible_height.max(1);
+        if width == 0 {
+            return;
+        }
+
+        while self.reading.lines.len() < self.reading.scroll + height * 2 {
+            let Some((book, chapter)) = self.reading.chapters.last().cloned() else {
+                break;
+            };
+            let Some(next) = self.scripture_db.adjacent_chapter(&book, chapter, true) else {
+                break;
+            };
+            let lines = Self::reading_chapter_lines(&self.scripture_db, &next.0, next.1, width);
+            let view = &mut self.reading;
+            view.chapter_starts.push(view.lines.len());
+            view.lines.extend(lines);
+            view.chapters.push(next);
+        }
+
+        while self.reading.scroll < height {
+            let Some((book, chapter)) = self.reading.chapters.first().cloned() else {
+                break;
+            };
+            let Some(previous) = self.scripture_db.adjacent_chapter(&book, chapter, false) else {
+                break;
+            };
+            let lines = Self::reading_chapter_lines(&self.scripture_db, &previous.0, previous.1, width);
+            let added = lines.len();
+            let view = &mut self.reading;
+            view.lines.splice(0..0, lines);
+            view.chapter_starts.iter_mut().for_each(|start| *start += added);
+            view.chapter_starts.insert(0, 0);
+            view.chapters.insert(0, previous);
+            view.scroll += added;
+        }
+    }
+
+    /// Heading, blank line, reflowed text, and a gap before the next chapter
+    fn reading_chapter_lines(db: &ScriptureDb, book: &str, chapter: i32, width: usize) -> Vec<String> {
+        let verses: Vec<Scripture> = db.get_verses_for_chapter(book, chapter).into_iter().cloned().collect();
+        let mut lines = vec![format!("{} {}", book, chapter), String::new()];
+        lines.extend(escrituras_core::reading::reflow(&verses, width));
+        lines.extend([String::new(), String::new()]);
+        lines
     }
 }
@@ -0,0 +1,1847 @@
+//! Subcommands that print to stdout and exit instead of starting the TUI,
+//! for scripts and shell aliases (`scriptures lookup "1 Nephi 3:7"`)
+
+use anyhow::{anyhow, Result};
+use crossterm::style::{Color, Stylize};
+use escrituras_core::ai::{self, prompt::{context_block, history_block, question_block}};
+use escrituras_core::card::{ReferencePlacement, Rgb};
+use escrituras_core::compare::{diff_words, pair_verses, WordChange};
+use escrituras_core::config::mask_secret;
+use escrituras_core::daemon::{self, ContextHit, DaemonRequest, DaemonResponse, DaemonState, DaemonStatus, SearchRequest};
+use escrituras_core::export::{anki_cards, chat_markdown, vault_files, AnkiNoteType};
+use escrituras_core::quiz::{blank_score, blank_word, pick_verses, reference_score, QuizKind, QuizRng};
+use escrituras_core::{
+    download_embedding_model, embedding_model_cached, embedding_model_dir, generate_embeddings, library, logging, mcp, paths, setup, sync,
+    Backup, Card, CardFormat, CardStyle, CfmProgress, CommandVoice, CfmReading, CfmSchedule, ChatMessage, ChatRole, ClaudeClient, Config, ConversationHistory, CopyFormat, EmbeddingsDb, EscriturasError, FootnoteDb, FrontMatter, HymnDb, Interlinear, Journal, JournalEntry, Library, LibraryBook,
+    KeySource, MatchSource, MemorizeHistory, OpenAIClient, Provider, ReadAloud, SavedScriptures, Scripture, ScriptureDb, SearchFilter,
+    SearchMode, Store, TalkDb, TokenSender, Topic, TopicIndex, TOPICS,
+};
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
+
+use crate::commands::{self, Args};
+use crate::lineedit::LineEditor;
+use crate::theme::ColorSupport;
+
+/// Default port of `mcp --transport http`
+const MCP_HTTP_PORT: u16 = 8765;
+
+/// `e`, followed by what to do about it when it's an error core has a suggestion for
+pub fn with_hint(e: anyhow::Error) -> anyhow::Error {
+    match e.downcast_ref::<EscriturasError>().and_then(EscriturasError::hint) {
+        Some(hint) => anyhow!("{}\n{}", e, hint),
+        None => e,
+    }
+}
+
+pub async fn run(command: &str, args: &[String]) -> Result<()> {
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        print!("{}", commands::help(Some(command)));
+        return Ok(());
+    }
+    let Some(spec) = commands::find(command) else {
+        return Err(anyhow!("Unknown command: {}", command));
+    };
+    let args = Args::parse(spec, args)?;
+    match command {
+        "lookup" => lookup(&args).await,
+        "search" => search(&args).await,
+        "concordance" => concordance(&args).await,
+        "query" => query(&args).await,
+        "mcp" => mcp(&args).await,
+        "config" => config(&args.rest),
+        "embeddings" => embeddings(&args).await,
+        "batch" => batch(&args).await,
+        "plan" => plan(&args).await,
+        "note" => note(&args).await,
+        "library" => library(&args),
+        "export-anki" => export_anki(&args).await,
+        "export-vault" => export_vault(&args.rest).await,
+        "backup" => backup(&args.rest),
+        "sync" => sync_now(&args.rest),
+        "logs" => logs(&args),
+        "compare" => compare(&args).await,
+        "read-aloud" => read_aloud(&args.rest).await,
+        "card" => card(&args).await,
+        "topic" => topic(&args.rest).await,
+        "chat" => chat(&args).await,
+        "quiz" => quiz(&args).await,
+        "daemon" => run_daemon(&args).await,
+        "completions" => completions(&args.rest),
+        "man" => man(&args),
+        "help" => {
+            print!("{}", commands::help(args.rest.first().map(String::as_str)));
+            Ok(())
+        }
+        _ => Err(anyhow!("Unknown command: {}", command)),
+    }
+}
+
+/// `--format md|plain|ref-only|...` (any copy format name)
+fn format_option(args: &Args) -> Result<Option<CopyFormat>> {
+    args.value("format")
+        .map(|name| {
+            CopyFormat::from_name(&name).ok_or_else(|| {
+                let names: Vec<&str> = CopyFormat::all().iter().map(|f| f.name()).collect();
+                anyhow!("Unknown format {:?} (expected md, {})", name, names.join(", "))
+            })
+        })
+        .transpose()
+}
+
+/// Passages in a format: one per line for bare references, else a blank line between
+fn print_passages(format: CopyFormat, passages: &[Vec<Scripture>]) {
+    let separator = if format == CopyFormat::ReferenceOnly { "\n" } else { "\n\n" };
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let language = config.gospel_library_language();
+    let text: Vec<String> = passages.iter().map(|verses| format.format_in(verses, language)).collect();
+    println!("{}", text.join(separator));
+}
+
+async fn load_scriptures() -> Result<ScriptureDb> {
+    load_scriptures_from(None).await
+}
+
+/// Scripture data from `path` (the JSON file, or a folder holding it as installed),
+/// or else from where the app finds it, with the books' front matter if installed
+async fn load_scriptures_from(path: Option<&Path>) -> Result<ScriptureDb> {
+    let mut db = load_verses_from(path).await?;
+    if let Some(dir) = setup::find_data_dir(FrontMatter::FILE_NAME) {
+        match FrontMatter::load(&dir) {
+            Ok(front_matter) => front_matter.insert_into(&mut db),
+            Err(e) => eprintln!("Couldn't load front matter from {}: {}", dir.display(), e),
+        }
+    }
+    Ok(db)
+}
+
+/// Only the verses of the scripture data, which are what's embedded
+async fn load_verses_from(path: Option<&Path>) -> Result<ScriptureDb> {
+    let path = match path {
+        Some(path) if path.is_dir() => path.join(setup::SCRIPTURE_JSON),
+        Some(path) => path.to_path_buf(),
+        None => setup::find_scripture_data()
+            .ok_or_else(|| anyhow!("Scripture data not found. Run `scriptures --setup` to install it"))?,
+    };
+    if !path.is_file() {
+        return Err(anyhow!("No scripture data at {}", path.display()));
+    }
+    let mut db = ScriptureDb::new();
+    db.load_from_json(&path.to_string_lossy()).await?;
+    Ok(db)
+}
+
+/// `lookup <reference>[; <reference>...] [--format <format>]`: each reference's
+/// verses, by default numbered under its title. The arguments are joined, so
+/// quoting is optional.
+async fn lookup(args: &Args) -> Result<()> {
+    let format = format_option(args)?.unwrap_or(CopyFormat::Reference);
+    let input = args.rest.join(" ");
+    let references: Vec<&str> = input.split(';').map(str::trim).filter(|r| !r.is_empty()).collect();
+    if references.is_empty() {
+        return Err(anyhow!(commands::usage("lookup")));
+    }
+
+    // Every reference is checked before any prints, so a typo doesn't leave partial output
+    let references: Vec<String> = references.iter().map(|r| r.to_string()).collect();
+    let passages = match ask_daemon(DaemonRequest::Lookup { references: references.clone() }).await? {
+        Some(DaemonResponse::Passages { passages }) => passages,
+        Some(_) => return Err(anyhow!("Unexpected answer from the daemon")),
+        None => daemon::lookup_passages(&load_scriptures().await?, &references)?,
+    };
+    print_passages(format, &passages);
+    Ok(())
+}
+
+/// `search <query> [--semantic | --hybrid] [--volume <volume>] [--book <book>]
+/// [--limit <n>] [-C <n>] [--format <format>]`: matching verses, one per line,
+/// or as passages in a format. Keyword matching by default; `--semantic` ranks
+/// by meaning and `--hybrid` puts semantic matches before keyword ones. `-C`
+/// (`--context`) adds the verses around each match, as `grep -C` does.
+async fn search(args: &Args) -> Result<()> {
+    let format = format_option(args)?;
+    let context = match args.value("context") {
+        Some(n) => n.parse::<i32>().ok().filter(|n| *n >= 0).ok_or_else(|| anyhow!("-C needs a number, not {:?}", n))?,
+        None => 0,
+    };
+    let mode = match (args.has("semantic"), args.has("hybrid")) {
+        (true, true) => return Err(anyhow!("Use --semantic or --hybrid, not both")),
+        (true, false) => SearchMode::Semantic,
+        (false, true) => SearchMode::Combined,
+        (false, false) => SearchMode::Keyword,
+    };
+    let volume = args.value("volume");
+    let book = args.value("book");
+    let limit = match args.value("limit") {
+        Some(n) => n.parse().map_err(|_| anyhow!("--limit needs a number, not {:?}", n))?,
+        None => 20,
+    };
+    let query = args.rest.join(" ");
+    if query.trim().is_empty() {
+        return Err(anyhow!(commands::usage("search")));
+    }
+
+    let request = SearchRequest { query, volume, book, mode, limit, context };
+    let hits = match ask_daemon(DaemonRequest::Search(request.clone())).await? {
+        Some(DaemonResponse::Hits { hits }) => hits,
+        Some(_) => return Err(anyhow!("Unexpected answer from the daemon")),
+        None => {
+            let db = load_scriptures().await?;
+            let mut embeddings = match mode {
+                SearchMode::Keyword => None,
+                _ => Some(load_embeddings()?),
+            };
+            daemon::search_with_context(&db, embeddings.as_mut(), &request)?
+        }
+    };
+
+    if let Some(format) = format {
+        let passages: Vec<Vec<Scripture>> = hits.into_iter().map(|h| h.passage).collect();
+        print_passages(format, &passages);
+        return Ok(());
+    }
+    // Semantic hits lead with their similarity, keyword hits in a hybrid search with "kw"
+    let styled = std::io::stdout().is_terminal() && ColorSupport::detect() != ColorSupport::None;
+    for (i, ContextHit { hit, passage }) in hits.iter().enumerate() {
+        let score = match (mode, hit.source, hit.score) {
+            (SearchMode::Keyword, _, _) => String::new(),
+            (_, MatchSource::Semantic, Some(score)) => format!("{:.2}  ", score),
+            _ => "  kw  ".to_string(),
+        };
+        if context > 0 && i > 0 {
+            println!("--");
+        }
+        for verse in passage {
+            let is_hit = verse.verse_title == hit.scripture.verse_title;
+            // With context, matches are marked as get_context marks them
+            let marker = match (context > 0, is_hit) {
+                (false, _) => "",
+                (true, true) => "> ",
+                (true, false) => "  ",
+            };
+            let score = if is_hit { score.clone() } else { " ".repeat(score.len()) };
+            let title = verse.verse_title.as_str();
+            match (styled, is_hit) {
+                (false, _) => println!("{}{}{}  {}", marker, score, title, verse.scripture_text),
+                (true, true) => println!("{}{}{}  {}", marker, score.dim(), title.bold(), verse.scripture_text),
+                (true, false) => println!("{}{}{}  {}", marker, score, title.dim(), verse.scripture_text.as_str().dim()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `concordance <word> [--volume <volume>] [--book <book>] [--json]`: every
+/// verse the word (or another form of it) occurs in, grouped by book
+async fn concordance(args: &Args) -> Result<()> {
+    let json = args.has("json");
+    let volume = args.value("volume");
+    let book = args.value("book");
+    let [word] = args.rest.as_slice() else {
+        return Err(anyhow!(commands::usage("concordance")));
+    };
+
+    let request = DaemonRequest::Concordance { word: word.clone(), volume: volume.clone(), book: book.clone() };
+    let found = match ask_daemon(request).await? {
+        Some(DaemonResponse::Concordance(found)) => found,
+        Some(_) => return Err(anyhow!("Unexpected answer from the daemon")),
+        None => daemon::concordance(&load_scriptures().await?, word, volume.as_deref(), book.as_deref())?,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&found)?);
+        return Ok(());
+    }
+    let styled = std::io::stdout().is_terminal() && ColorSupport::detect() != ColorSupport::None;
+    for (i, book) in found.books.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let header = format!("{} ({})", book.book, book.count);
+        if styled {
+            println!("{}", header.bold());
+        } else {
+            println!("{}", header);
+        }
+        for verse in &book.verses {
+            let text = highlight_forms(&verse.text, &verse.forms, styled);
+            if styled {
+                println!("  {}  {}", verse.reference.as_str().dim(), text);
+            } else {
+                println!("  {}  {}", verse.reference, text);
+            }
+        }
+    }
+    let verses: usize = found.books.iter().map(|b| b.verses.len()).sum();
+    match found.count {
+        0 => println!("No occurrences of \"{}\"", found.word),
+        n => println!(
+            "\n\"{}\": {} {} in {} {} of {} {}",
+            found.word,
+            n,
+            if n == 1 { "time" } else { "times" },
+            verses,
+            if verses == 1 { "verse" } else { "verses" },
+            found.books.len(),
+            if found.books.len() == 1 { "book" } else { "books" },
+        ),
+    }
+    Ok(())
+}
+
+/// The text with each of `forms` in bold (when styled)
+fn highlight_forms(text: &str, forms: &[String], styled: bool) -> String {
+    if !styled {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if forms.iter().any(|f| f == word.trim_matches('\'')) {
+            out.push_str(&word.as_str().bold().yellow().to_string());
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '\'' {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+/// How a run of words in a comparison is shown
+#[derive(Clone, Copy, PartialEq)]
+enum WordKind {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Consecutive words of the same kind joined into runs
+fn word_runs<'a>(changes: impl IntoIterator<Item = &'a WordChange>) -> Vec<(WordKind, String)> {
+    let mut runs: Vec<(WordKind, String)> = Vec::new();
+    for change in changes {
+        let (kind, word) = match change {
+            WordChange::Same(w) => (WordKind::Same, w),
+            WordChange::Removed(w) => (WordKind::Removed, w),
+            WordChange::Added(w) => (WordKind::Added, w),
+        };
+        match runs.last_mut() {
+            Some((last, text)) if *last == kind => {
+                text.push(' ');
+                text.push_str(word);
+            }
+            _ => runs.push((kind, word.clone())),
+        }
+    }
+    runs
+}
+
+/// A run as printed: colored when `styled`, else marked like `wdiff` ([-gone-] {+new+})
+fn show_run(kind: WordKind, text: &str, styled: bool) -> String {
+    match (kind, styled) {
+        (WordKind::Same, _) => text.to_string(),
+        (WordKind::Removed, true) => text.red().to_string(),
+        (WordKind::Added, true) => text.green().to_string(),
+        (WordKind::Removed, false) => format!("[-{}-]", text),
+        (WordKind::Added, false) => format!("{{+{}+}}", text),
+    }
+}
+
+/// Runs wrapped into lines of at most `width` columns, each printed and
+/// padded to `width`. Long runs are split at their words.
+fn wrap_runs(runs: &[(WordKind, String)], width: usize, styled: bool) -> Vec<String> {
+    // Words carry their run's kind so a split run keeps its marking on each line
+    let words: Vec<(WordKind, &str)> = runs.iter().flat_map(|(kind, text)| text.split(' ').map(|w| (*kind, w))).collect();
+    let mut lines: Vec<Vec<(WordKind, &str)>> = vec![Vec::new()];
+    let mut used = 0;
+    for (kind, word) in words {
+        let line = lines.last_mut().unwrap();
+        let w = show_run(kind, word, false).width();
+        if !line.is_empty() && used + 1 + w > width {
+            lines.push(vec![(kind, word)]);
+            used = w;
+        } else {
+            used += w + usize::from(!line.is_empty());
+            line.push((kind, word));
+        }
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut runs: Vec<(WordKind, String)> = Vec::new();
+            for (kind, word) in line {
+                match runs.last_mut() {
+                    Some((last, text)) if *last == kind => {
+                        text.push(' ');
+                        text.push_str(word);
+                    }
+                    _ => runs.push((kind, word.to_string())),
+                }
+            }
+            let plain: Vec<String> = runs.iter().map(|(k, t)| show_run(*k, t, false)).collect();
+            let shown: Vec<String> = runs.iter().map(|(k, t)| show_run(*k, t, styled)).collect();
+            let pad = width.saturating_sub(plain.join(" ").width());
+            format!("{}{}", shown.join(" "), " ".repeat(pad))
+        })
+        .collect()
+}
+
+/// `compare <reference> <reference> [--unified]`: two parallel passages
+/// (`"Isaiah 2" "2 Nephi 12"`) verse by verse, side by side with the words that
+/// differ highlighted, or with `--unified` only the verses that differ, as a
+/// word diff
+/// `read-aloud <reference>`: speak a chapter or passage a verse at a time
+/// (see `escrituras_core::read_aloud`), printing each verse as it's read
+async fn read_aloud(args: &[String]) -> Result<()> {
+    let reference = args.join(" ");
+    if reference.trim().is_empty() {
+        return Err(anyhow!(commands::usage("read-aloud")));
+    }
+    let db = load_scriptures().await?;
+    let range = db.parse_reference(&reference).ok_or_else(|| anyhow!("Not a reference: {}", reference))?;
+    let verses: Vec<Scripture> = db.get_verses_in_range(&range).into_iter().cloned().collect();
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let voice = CommandVoice::from_config(&config)?;
+
+    let mut reading = ReadAloud::new(Box::new(voice), verses, 0);
+    reading.play()?;
+    while let Some(verse) = reading.current() {
+        println!("{:>3}  {}", verse.verse_number, verse.scripture_text);
+        while !reading.poll()? {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+    Ok(())
+}
+
+/// `card <reference> [--out <file>] [--background <color>] [--font <font>]
+/// [--reference above|below] [--size <WxH>]`: a quote card of the passage,
+/// styled by the card settings and then the options
+async fn card(args: &Args) -> Result<()> {
+    let out = args.value("out").map(PathBuf::from);
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let mut style = CardStyle::from_config(&config)?;
+    if let Some(background) = args.value("background") {
+        style.background = Rgb::parse(&background).ok_or_else(|| anyhow!("Not a color: {} (use one like #1f2a44)", background))?;
+    }
+    if let Some(font) = args.value("font") {
+        style.font = font;
+    }
+    if let Some(placement) = args.value("reference") {
+        style.reference = ReferencePlacement::from_name(&placement).ok_or_else(|| anyhow!("--reference is above or below"))?;
+    }
+    if let Some(size) = args.value("size") {
+        style = style.with_size(&size)?;
+    }
+    let reference = args.rest.join(" ");
+    if reference.trim().is_empty() {
+        return Err(anyhow!(commands::usage("card")));
+    }
+
+    let db = load_scriptures().await?;
+    let range = db.parse_reference(&reference).ok_or_else(|| anyhow!("Not a reference: {}", reference))?;
+    let verses: Vec<Scripture> = db.get_verses_in_range(&range).into_iter().cloned().collect();
+    let card = Card::of_verses(&verses).ok_or_else(|| anyhow!("No verses in {}", reference))?;
+    let out = out.unwrap_or_else(|| PathBuf::from(card.file_name(CardFormat::Png)));
+    card.save(&out, &style)?;
+    eprintln!("✓ Wrote {}", out.display());
+    Ok(())
+}
+
+/// `topic [<topic>]`: the page `TopicIndex` builds for a study topic, from
+/// whatever footnotes, embeddings, and talks are installed; or, with no topic,
+/// the topics there are
+async fn topic(args: &[String]) -> Result<()> {
+    let name = args.join(" ");
+    if name.trim().is_empty() {
+        for topic in TOPICS {
+            println!("{:<28}{}", topic.name, topic.summary);
+        }
+        return Ok(());
+    }
+    let topic = Topic::find(&name).ok_or_else(|| anyhow!("No topic {:?}; `scriptures topic` lists them", name))?;
+
+    let db = load_scriptures().await?;
+    let footnotes = setup::find_data_dir(FootnoteDb::FILE_NAME).and_then(|d| FootnoteDb::load(&d).ok());
+    let mut embeddings = setup::find_data_dir(EmbeddingsDb::FILE_NAME).and_then(|d| EmbeddingsDb::load(&d).ok());
+    let talks = setup::find_data_dir(TalkDb::FILE_NAME).and_then(|d| TalkDb::load(&d).ok());
+    let page = TopicIndex::new(&db)
+        .with_footnotes(footnotes.as_ref())
+        .with_embeddings(embeddings.as_mut())
+        .with_talks(talks.as_ref())
+        .with_cache_dir(TopicIndex::default_cache_dir()?)
+        .page(topic)?;
+
+    println!("{}\n{}", topic.name, topic.summary);
+    for (heading, verses) in [("Topical Guide", &page.topical_guide), ("Related verses", &page.related)] {
+        if verses.is_empty() {
+            continue;
+        }
+        println!("\n{} ({})", heading, verses.len());
+        for verse in verses.iter().filter_map(|title| db.get_by_title(title)) {
+            println!("  {}  {}", verse.verse_title, verse.scripture_text);
+        }
+    }
+    if !page.talks.is_empty() {
+        println!("\nConference talks ({})", page.talks.len());
+        for talk in &page.talks {
+            println!("  \"{}\" by {} ({}) ¶{}", talk.title, talk.speaker, talk.session, talk.paragraph + 1);
+        }
+    }
+    Ok(())
+}
+
+async fn compare(args: &Args) -> Result<()> {
+    let unified = args.has("unified");
+    let [first, second] = args.rest.as_slice() else {
+        return Err(anyhow!(commands::usage("compare")));
+    };
+    let db = load_scriptures().await?;
+    let passage = |reference: &str| {
+        let range = db.parse_reference(reference).ok_or_else(|| anyhow!("Not a reference: {}", reference))?;
+        Ok::<_, anyhow::Error>((range.display_title(), db.get_verses_in_range(&range)))
+    };
+    let ((first_title, first), (second_title, second)) = (passage(first)?, passage(second)?);
+
+    let styled = std::io::stdout().is_terminal() && ColorSupport::detect() != ColorSupport::None;
+    let bold = |text: &str| if styled { text.bold().to_string() } else { text.to_string() };
+    let width = match crossterm::terminal::size() {
+        Ok((columns, _)) if std::io::stdout().is_terminal() => columns as usize,
+        _ => 100,
+    };
+    let column = (width.saturating_sub(3) / 2).max(20);
+
+    let pairs = pair_verses(&first, &second);
+    let mut differing = 0;
+    if unified {
+        println!("--- {}\n+++ {}", first_title, second_title);
+    }
+    for (left, right) in &pairs {
+        let text = |verse: Option<&Scripture>| verse.map_or(String::new(), |v| v.scripture_text.clone());
+        let changes = diff_words(&text(*left), &text(*right));
+        let differs = changes.iter().any(|c| !matches!(c, WordChange::Same(_)));
+        differing += usize::from(differs);
+        let title = |verse: &Option<&Scripture>| verse.map_or("—".to_string(), |v| v.verse_title.clone());
+
+        if unified {
+            if differs {
+                println!("\n{}", bold(&format!("@@ {} | {} @@", title(left), title(right))));
+                let runs: Vec<String> = word_runs(&changes).iter().map(|(k, t)| show_run(*k, t, styled)).collect();
+                println!("{}", runs.join(" "));
+            }
+            continue;
+        }
+        let left_words = changes.iter().filter(|c| !matches!(c, WordChange::Added(_)));
+        let right_words = changes.iter().filter(|c| !matches!(c, WordChange::Removed(_)));
+        let left_lines = wrap_runs(&word_runs(left_words), column, styled);
+        let right_lines = wrap_runs(&word_runs(right_words), column, styled);
+        let header = |t: String| format!("{}{}", bold(&t), " ".repeat(column.saturating_sub(t.width())));
+        println!("{} │ {}", header(title(left)), bold(&title(right)));
+        for i in 0..left_lines.len().max(right_lines.len()) {
+            let blank = " ".repeat(column);
+            let (l, r) = (left_lines.get(i).unwrap_or(&blank), right_lines.get(i).unwrap_or(&blank));
+            println!("{} │ {}", l, r.trim_end());
+        }
+        println!("{} │", " ".repeat(column));
+    }
+    let summary = format!("{} of {} verses differ", differing, pairs.len());
+    println!("{}{}", if unified { "\n" } else { "" }, if styled { summary.dim().to_string() } else { summary });
+    Ok(())
+}
+
+/// `quiz [--volume <volume>] [--book <book>] [--n <count>] [--mode reference|blank]`:
+/// random verses to name or complete, scored and recorded with the typed
+/// memorization attempts. Questions mix both kinds unless `--mode` picks one.
+async fn quiz(args: &Args) -> Result<()> {
+    let volume = args.value("volume");
+    let book = args.value("book");
+    let count = match args.value("n") {
+        Some(n) => n.parse::<usize>().ok().filter(|n| *n > 0).ok_or_else(|| anyhow!("--n needs a number, not {:?}", n))?,
+        None => 10,
+    };
+    let kinds = match args.value("mode").as_deref() {
+        None | Some("mixed") => vec![QuizKind::Reference, QuizKind::Blank],
+        Some("reference") => vec![QuizKind::Reference],
+        Some("blank") => vec![QuizKind::Blank],
+        Some(other) => return Err(anyhow!("Unknown mode {:?} (expected reference, blank, or mixed)", other)),
+    };
+    if !args.rest.is_empty() {
+        return Err(anyhow!(commands::usage("quiz")));
+    }
+
+    let db = load_scriptures().await?;
+    let volume = volume.map(|v| resolve_volume(&db, &v)).transpose()?;
+    let book = book
+        .map(|b| db.parse_reference(&b).map(|r| r.book_title).ok_or_else(|| anyhow!("No such book: {}", b)))
+        .transpose()?;
+    let pool: Vec<&Scripture> = db
+        .all_verses()
+        .iter()
+        .filter(|v| volume.as_ref().is_none_or(|volume| v.volume_title == *volume))
+        .filter(|v| book.as_ref().is_none_or(|book| v.book_title == *book))
+        .collect();
+    if pool.is_empty() {
+        return Err(anyhow!("No verses to quiz on"));
+    }
+
+    let seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(1, |d| d.as_nanos() as u64);
+    let mut rng = QuizRng::new(seed);
+    let verses = pick_verses(&pool, count, &mut rng);
+    let mut history = MemorizeHistory::load().unwrap_or_default();
+    let loaded = history.clone();
+
+    let styled = interactive() && ColorSupport::detect() != ColorSupport::None;
+    let paint = |text: String, color: Color| if styled { text.with(color).to_string() } else { text };
+    let prompt = if styled { "› ".cyan().bold().to_string() } else { "› ".to_string() };
+    let mut editor = LineEditor::new(Vec::new(), prompt, 2);
+    let mut total = 0.0;
+    let mut asked = 0;
+
+    for (i, verse) in verses.iter().enumerate() {
+        let kind = kinds[rng.below(kinds.len())];
+        // Verses without a word worth blanking are asked by reference
+        let blanked = match kind {
+            QuizKind::Blank => blank_word(&verse.scripture_text, &mut rng),
+            QuizKind::Reference => None,
+        };
+        let heading = match &blanked {
+            Some(_) => format!("{}/{} Fill in the blank ({})", i + 1, verses.len(), verse.verse_title),
+            None => format!("{}/{} Which verse is this?", i + 1, verses.len()),
+        };
+        println!("\n{}", if styled { heading.bold().to_string() } else { heading });
+        println!("  {}", blanked.as_ref().map_or(verse.scripture_text.as_str(), |b| b.shown.as_str()));
+
+        let score = loop {
+            let Some(guess) = editor.read_line()? else {
+                break None;
+            };
+            let guess = guess.trim();
+            match &blanked {
+                _ if guess.is_empty() => break Some(0.0),
+                Some(blanked) => break Some(blank_score(&blanked.answer, guess)),
+                None => match db.parse_reference(guess) {
+                    Some(range) => break Some(reference_score(verse, &range)),
+                    None => println!("{}", paint(format!("{:?} isn't a reference; try again, or Enter to skip", guess), Color::Yellow)),
+                },
+            }
+        };
+        // Ctrl-D ends the quiz early
+        let Some(score) = score else {
+            break;
+        };
+
+        let answer = blanked.as_ref().map_or(verse.verse_title.as_str(), |b| b.answer.as_str());
+        let verdict = match score {
+            s if s >= 1.0 => paint(format!("✓ {}", answer), Color::Green),
+            s if s > 0.0 => paint(
+                format!("≈ {} ({})", answer, if s >= 0.5 { "right chapter" } else { "right book" }),
+                Color::Yellow,
+            ),
+            _ => paint(format!("✗ {}", answer), Color::Red),
+        };
+        println!("{}", verdict);
+        history.record(&verse.verse_title, score);
+        total += score;
+        asked += 1;
+    }
+
+    if asked == 0 {
+        return Ok(());
+    }
+    history.save(&loaded)?;
+    let percent = (total / asked as f32 * 100.0).round();
+    println!("\nScore: {} of {} ({}%)", (total * 100.0).round() / 100.0, asked, percent);
+    Ok(())
+}
+
+/// A volume by name, ignoring case ("book of mormon")
+fn resolve_volume(db: &ScriptureDb, name: &str) -> Result<String> {
+    db.find_volume(name)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("No such volume: {} (expected one of: {})", name, db.get_volumes().join(", ")))
+}
+
+/// Scripture embeddings with the model loaded, or an error saying what to download
+fn load_embeddings() -> Result<EmbeddingsDb> {
+    let dir = setup::find_data_dir(EmbeddingsDb::FILE_NAME).ok_or_else(|| {
+        anyhow!(
+            "Semantic search needs {} and {} in data/ or ~/.local/share/escrituras/data/. \
+             Run `scriptures embeddings download` to fetch them",
+            EmbeddingsDb::FILE_NAME,
+            EmbeddingsDb::METADATA_FILE
+        )
+    })?;
+    let mut embeddings = EmbeddingsDb::load(&dir)?;
+    embeddings.ensure_model().map_err(|e| {
+        anyhow!("{}. Run `scriptures embeddings download` to fetch the embedding model", e)
+    })?;
+    Ok(embeddings)
+}
+
+/// `plan [today]|start [--format <format>]|done [<n> | <chapter>]|status`: this
+/// week's Come, Follow Me reading from the shell, with the same progress as
+/// the TUI's Come, Follow Me screen
+async fn plan(args: &Args) -> Result<()> {
+    let format = format_option(args)?.unwrap_or(CopyFormat::Reference);
+    let dir = setup::find_data_dir(CfmSchedule::FILE_NAME).ok_or_else(|| {
+        anyhow!("No reading plan: add {} to data/ or ~/.local/share/escrituras/data/", CfmSchedule::FILE_NAME)
+    })?;
+    let schedule = CfmSchedule::load(&dir)?;
+    let current = schedule.current_week().ok_or_else(|| anyhow!("{} has no weeks", CfmSchedule::FILE_NAME))?;
+    let week = &schedule.weeks()[current];
+    let db = load_scriptures().await?;
+    let readings = week.readings(&db);
+    let mut progress = CfmProgress::load()?;
+    let loaded = progress.clone();
+    let next_unread = |progress: &CfmProgress| readings.iter().position(|r| !progress.is_done(week, &r.label));
+
+    match args.rest.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["today"] => {
+            println!("{} – {}: {}", week.start.format("%B %-d"), week.end().format("%B %-d"), week.title);
+            for reading in &readings {
+                let mark = if progress.is_done(week, &reading.label) { "[x]" } else { "[ ]" };
+                println!("  {} {}", mark, reading.label);
+            }
+        }
+        ["start"] => match next_unread(&progress) {
+            Some(i) => {
+                let verses: Vec<Scripture> = db.get_verses_in_range(&readings[i].range).into_iter().cloned().collect();
+                print_passages(format, &[verses]);
+            }
+            None => println!("This week's reading is finished"),
+        },
+        ["done", which @ ..] => {
+            let which = which.join(" ");
+            let i = if which.is_empty() {
+                next_unread(&progress).ok_or_else(|| anyhow!("This week's reading is already finished"))?
+            } else {
+                find_reading(&readings, &which)?
+            };
+            progress.mark_done(week, &readings[i].label);
+            progress.save(&loaded)?;
+            let read = readings.iter().filter(|r| progress.is_done(week, &r.label)).count();
+            println!("✓ {} ({} of {} this week)", readings[i].label, read, readings.len());
+        }
+        ["status"] => {
+            let read = readings.iter().filter(|r| progress.is_done(week, &r.label)).count();
+            println!("This week: {} of {} chapters read ({})", read, readings.len(), week.title);
+            let weeks_finished = schedule.weeks()[..=current].iter().filter(|w| progress.is_finished(w, &db)).count();
+            println!("Weeks finished: {} of {} so far", weeks_finished, current + 1);
+            let streak = progress.streak(&schedule, &db, current);
+            println!("Streak: {} week{}", streak, if streak == 1 { "" } else { "s" });
+            if let Some(i) = next_unread(&progress) {
+                println!("Next: {}", readings[i].label);
+            }
+        }
+        _ => return Err(anyhow!(commands::usage("plan"))),
+    }
+    Ok(())
+}
+
+/// A week's reading by its number in the list (from 1) or its label, ignoring case
+fn find_reading(readings: &[CfmReading], which: &str) -> Result<usize> {
+    if let Ok(n) = which.parse::<usize>() {
+        return (1..=readings.len())
+            .contains(&n)
+            .then(|| n - 1)
+            .ok_or_else(|| anyhow!("This week has {} readings", readings.len()));
+    }
+    readings
+        .iter()
+        .position(|r| r.label.eq_ignore_ascii_case(which))
+        .ok_or_else(|| {
+            let labels: Vec<&str> = readings.iter().map(|r| r.label.as_str()).collect();
+            anyhow!("{:?} isn't in this week's reading ({})", which, labels.join(", "))
+        })
+}
+
+/// `note add <reference> [text]|list [--book <book>]|export [--book <book>] [--out <file>]`:
+/// quick notes on verses, kept as journal entries so they're on the Journal screen too
+/// `library import <file.epub> [--embed] | list | embed <title> | remove <title>`:
+/// the books studied beside the scriptures (see `escrituras_core::library`)
+fn library(args: &Args) -> Result<()> {
+    let embed_after = args.has("embed");
+    let dir = match setup::find_data_dir(Library::DIR_NAME) {
+        Some(dir) => dir,
+        None => paths::data_dir()?.join("data"),
+    };
+    let usage = commands::usage("library");
+    let title = args.rest.get(1..).map(|rest| rest.join(" ")).unwrap_or_default();
+
+    match args.rest.first().map(String::as_str) {
+        Some("import") if !title.is_empty() => {
+            let book = library::import_epub(Path::new(&title), &dir)?;
+            let paragraphs = book.paragraph_count();
+            eprintln!("✓ Imported {} ({} chapters, {} paragraphs)", book.title, book.chapters.len(), paragraphs);
+            if embed_after {
+                embed_book(&book, &dir)?;
+            }
+        }
+        Some("list") => {
+            let library = Library::load(&dir)?;
+            for book in library.books() {
+                let embedded = if library.is_embedded(&book.title) { "  (embedded)" } else { "" };
+                let author = if book.author.is_empty() { String::new() } else { format!(", {}", book.author) };
+                println!("{}{}: {} chapters{}", book.title, author, book.chapters.len(), embedded);
+            }
+        }
+        Some("embed") if !title.is_empty() => {
+            let library = Library::load(&dir)?;
+            let book = find_library_book(&library, &title)?;
+            embed_book(book, &dir)?;
+        }
+        Some("remove") if !title.is_empty() => {
+            if !library::remove(&dir, &title)? {
+                return Err(anyhow!("No book {:?} in the library", title));
+            }
+            eprintln!("✓ Removed {}", title);
+        }
+        _ => return Err(anyhow!("{}", usage)),
+    }
+    Ok(())
+}
+
+/// A library book by title (in any case) or id
+fn find_library_book<'a>(library: &'a Library, title: &str) -> Result<&'a LibraryBook> {
+    let wanted = title.to_lowercase();
+    library
+        .books()
+        .iter()
+        .find(|b| b.title.to_lowercase() == wanted || b.id() == wanted)
+        .ok_or_else(|| anyhow!("No book {:?} in the library", title))
+}
+
+/// Embed a library book's paragraphs, showing progress on a terminal
+fn embed_book(book: &LibraryBook, dir: &Path) -> Result<()> {
+    let show_progress = std::io::stderr().is_terminal();
+    library::embed(book, dir, |done, total| {
+        if show_progress {
+            eprint!("\rEmbedding paragraphs: {}/{}", done, total);
+        }
+    })?;
+    if show_progress {
+        eprintln!();
+    }
+    eprintln!("✓ Embedded {} for semantic search", book.title);
+    Ok(())
+}
+
+async fn note(args: &Args) -> Result<()> {
+    let book = args.value("book");
+    let out = args.value("out").map(PathBuf::from);
+    let journal = Journal::load()?;
+    let usage = commands::usage("note");
+
+    // Entries about the --book, oldest first
+    let entries = async {
+        let book = match &book {
+            Some(b) => {
+                let db = load_scriptures().await?;
+                Some(db.parse_reference(b).map(|r| r.book_title).ok_or_else(|| anyhow!("No such book: {}", b))?)
+            }
+            None => None,
+        };
+        let about_book = |e: &&JournalEntry| book.as_ref().is_none_or(|b| e.is_about_book(b));
+        Ok::<_, anyhow::Error>(journal.entries.iter().rev().filter(about_book).collect::<Vec<_>>())
+    };
+
+    match args.rest.first().map(String::as_str) {
+        Some("add") => {
+            let reference = args.rest.get(1).ok_or_else(|| anyhow!("{}", usage))?;
+            let mut text = args.rest[2..].join(" ");
+            // No text on the command line: read it from a pipe
+            if text.trim().is_empty() && !std::io::stdin().is_terminal() {
+                std::io::stdin().read_to_string(&mut text)?;
+            }
+            if text.trim().is_empty() {
+                return Err(anyhow!("{}", usage));
+            }
+            let db = load_scriptures().await?;
+            let range = db.parse_reference(reference).ok_or_else(|| anyhow!("Unknown reference: {}", reference))?;
+            let verses: Vec<Scripture> = db.get_verses_in_range(&range).into_iter().cloned().collect();
+            let path = journal.add_note(&verses, &text)?;
+            eprintln!("✓ Noted {} in {}", range.display_title(), path.display());
+        }
+        Some("list") => {
+            for entry in entries.await? {
+                println!("{}  {}", entry.created.format("%Y-%m-%d %H:%M"), entry.title);
+            }
+        }
+        Some("export") => {
+            let text: Vec<&str> = entries.await?.iter().map(|e| e.content.trim_end()).collect();
+            let text = format!("{}\n", text.join("\n\n---\n\n"));
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, text)?;
+                    eprintln!("✓ Wrote notes to {}", path.display());
+                }
+                None => print!("{}", text),
+            }
+        }
+        _ => return Err(anyhow!("{}", usage)),
+    }
+    Ok(())
+}
+
+/// `export-anki [--tag <tag>] [--memorized] [--cloze] [--out <file>]`: saved
+/// scriptures (or those with a tag, or the verses practiced in memorize mode)
+/// as cards to import into Anki
+async fn export_anki(args: &Args) -> Result<()> {
+    let tag = args.value("tag");
+    let out = args.value("out").map(PathBuf::from);
+    let memorized = args.has("memorized");
+    let note_type = if args.has("cloze") { AnkiNoteType::Cloze } else { AnkiNoteType::Basic };
+    if out.as_ref().is_some_and(|p| p.extension().is_some_and(|e| e == "apkg")) {
+        return Err(anyhow!("Cards are written as text for File → Import; use a .txt or .tsv file rather than .apkg"));
+    }
+    if let Some(arg) = args.rest.first() {
+        return Err(anyhow!("Unexpected argument for export-anki: {}", arg));
+    }
+
+    let saved = SavedScriptures::load()?;
+    let verses: Vec<Scripture> = if memorized {
+        let db = load_scriptures().await?;
+        let history = MemorizeHistory::load()?;
+        let mut titles: Vec<&String> = history.verses.keys().collect();
+        titles.sort();
+        titles.into_iter().filter_map(|t| db.get_by_title(t)).cloned().collect()
+    } else {
+        match &tag {
+            Some(tag) => saved.with_tag(&tag.trim_start_matches('#').to_lowercase()),
+            None => saved.verses.clone(),
+        }
+    };
+    if verses.is_empty() {
+        return Err(anyhow!(match (memorized, &tag) {
+            (true, _) => "No verses practiced in memorize mode yet".to_string(),
+            (false, Some(tag)) => format!("No saved scriptures tagged #{}", tag.trim_start_matches('#')),
+            (false, None) => "No saved scriptures to export".to_string(),
+        }));
+    }
+
+    let cards: Vec<(Scripture, Vec<String>)> = verses
+        .into_iter()
+        .map(|v| {
+            let tags = saved.tags.get(&v.verse_title).cloned().unwrap_or_default();
+            (v, tags)
+        })
+        .collect();
+    let text = anki_cards(&cards, note_type);
+    match out {
+        Some(path) => {
+            std::fs::write(&path, text)?;
+            eprintln!("✓ Wrote {} cards to {}. Import it in Anki with File → Import", cards.len(), path.display());
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+/// `export-vault <dir>`: the scriptures as an Obsidian vault (or a folder in
+/// one), a linked Markdown note per chapter
+async fn export_vault(args: &[String]) -> Result<()> {
+    let [dir] = args else {
+        return Err(anyhow!(commands::usage("export-vault")));
+    };
+    let dir = PathBuf::from(dir);
+    let db = load_scriptures().await?;
+    let footnotes = setup::find_data_dir(FootnoteDb::FILE_NAME).and_then(|d| FootnoteDb::load(&d).ok());
+
+    let files = vault_files(&db, footnotes.as_ref());
+    for file in &files {
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &file.content).map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+    }
+    eprintln!("✓ Wrote {} notes to {}", files.len(), dir.display());
+    if footnotes.is_none() {
+        eprintln!("No {} was found, so the notes have no cross-references", FootnoteDb::FILE_NAME);
+    }
+    Ok(())
+}
+
+/// Verses re-embedded by `embeddings verify` to check the stored vectors
+const VERIFY_SAMPLES: usize = 50;
+
+/// `embeddings [status]|download|generate [--out <dir>]|verify`: report, fetch,
+/// or build the files semantic search and related verses need
+/// `backup export [<file>]`: write the saved scriptures, highlights,
+/// conversations, progress, and journal to one JSON file
+/// (`escrituras-backup-<date>.json` by default). `backup import <file>`:
+/// merge one in, keeping what's here
+fn backup(args: &[String]) -> Result<()> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["export"] | ["export", _] => {
+            let path = match args.get(1) {
+                Some(file) => PathBuf::from(file),
+                None => PathBuf::from(Backup::file_name()),
+            };
+            let backup = Backup::collect(&Store::open()?, &Journal::load()?)?;
+            backup.write(&path).map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+            eprintln!("✓ Backed up your study data to {}", path.display());
+            Ok(())
+        }
+        ["import", file] => {
+            let backup = Backup::read(Path::new(file))?;
+            let summary = backup.import(&mut Store::open()?, &Journal::load()?)?;
+            eprintln!("✓ Imported {}: {}", file, summary.describe());
+            Ok(())
+        }
+        _ => Err(anyhow!(commands::usage("backup"))),
+    }
+}
+
+/// `sync [<folder>]`: sync with a folder, or the `sync_dir` one, now and say what changed
+fn sync_now(args: &[String]) -> Result<()> {
+    let dir = match args {
+        [] => Config::load()?
+            .sync_dir
+            .map(PathBuf::from)
+            .ok_or_else(|| anyhow!("No sync folder. Choose one with `scriptures config set sync_dir <folder>`"))?,
+        [dir] => PathBuf::from(dir),
+        _ => return Err(anyhow!(commands::usage("sync"))),
+    };
+    let summary = sync::sync(&dir)?;
+    for conflict in &summary.conflicts {
+        eprintln!("Conflict: {}", conflict);
+    }
+    eprintln!("✓ Synced with {}: {}", dir.display(), summary.describe());
+    Ok(())
+}
+
+/// `logs [path] [--n <lines>]`: the last lines of the newest log file, or
+/// the folder logs are kept in
+fn logs(args: &Args) -> Result<()> {
+    let count = match args.value("n") {
+        Some(n) => n.parse::<usize>().ok().filter(|n| *n > 0).ok_or_else(|| anyhow!("--n needs a number, not {:?}", n))?,
+        None => 40,
+    };
+    match args.rest.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["path"] => println!("{}", logging::log_dir()?.display()),
+        [] => {
+            let Some(path) = logging::latest_log()? else {
+                eprintln!("Nothing has been logged yet ({})", logging::log_dir()?.display());
+                return Ok(());
+            };
+            let content = std::fs::read_to_string(&path).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+            let lines: Vec<&str> = content.lines().collect();
+            eprintln!("{}:", path.display());
+            for line in &lines[lines.len().saturating_sub(count)..] {
+                println!("{}", line);
+            }
+        }
+        _ => return Err(anyhow!(commands::usage("logs"))),
+    }
+    Ok(())
+}
+
+async fn embeddings(args: &Args) -> Result<()> {
+    let out = args.value("out").map(PathBuf::from);
+    match args.rest.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["status"] => {
+            embeddings_status();
+            Ok(())
+        }
+        ["download"] => {
+            eprintln!("Downloading scripture data and embeddings from the latest release...");
+            let scriptures = setup::download_release_data().await?;
+            eprintln!("✓ Installed {}", scriptures.display());
+            Ok(download_embedding_model()?)
+        }
+        ["generate"] => {
+            let db = load_verses_from(None).await?;
+            let dir = match out {
+                Some(dir) => dir,
+                None => match setup::find_data_dir(EmbeddingsDb::FILE_NAME) {
+                    Some(dir) => dir,
+                    None => paths::data_dir()?.join("data"),
+                },
+            };
+            let verses = db.all_verses();
+            let show_progress = std::io::stderr().is_terminal();
+            generate_embeddings(verses, &dir, |done, total| {
+                if show_progress {
+                    eprint!("\rEmbedding verses: {}/{}", done, total);
+                }
+            })?;
+            if show_progress {
+                eprintln!();
+            }
+            eprintln!("✓ Wrote {} embeddings to {}", verses.len(), dir.display());
+            Ok(())
+        }
+        ["verify"] => verify_embeddings().await,
+        _ => Err(anyhow!(commands::usage("embeddings"))),
+    }
+}
+
+/// Where the scripture data, embeddings, and model are, or that they're missing
+fn embeddings_status() {
+    let row = |label: &str, value: String| println!("{:<16}{}", label, value);
+    row(
+        "Scripture data",
+        setup::find_scripture_data()
+            .map_or_else(|| "not found (run `scriptures --setup`)".to_string(), |p| p.display().to_string()),
+    );
+    match setup::find_data_dir(EmbeddingsDb::FILE_NAME) {
+        Some(dir) => {
+            let summary = match EmbeddingsDb::load(&dir) {
+                Ok(embeddings) => format!("{} verses × {} dimensions", embeddings.verse_count(), embeddings.dimension()),
+                Err(e) => format!("unreadable: {}", e),
+            };
+            row("Embeddings", format!("{} ({})", dir.join(EmbeddingsDb::FILE_NAME).display(), summary));
+            row("Metadata", dir.join(EmbeddingsDb::METADATA_FILE).display().to_string());
+        }
+        None => row("Embeddings", "not found (run `scriptures embeddings download`)".to_string()),
+    }
+    let model = if embedding_model_cached() { "" } else { " (not downloaded)" };
+    row("Model", format!("{}{}", embedding_model_dir().display(), model));
+}
+
+/// Check the embeddings cover exactly the scripture data and, when the model
+/// is downloaded, that a sample of them still matches their verses
+async fn verify_embeddings() -> Result<()> {
+    let db = load_verses_from(None).await?;
+    let dir = setup::find_data_dir(EmbeddingsDb::FILE_NAME)
+        .ok_or_else(|| anyhow!("No embeddings found. Run `scriptures embeddings download` or `generate`"))?;
+    let mut embeddings = EmbeddingsDb::load(&dir)?;
+
+    let mut problems = embeddings.check_index(db.all_verses());
+    if embedding_model_cached() {
+        problems.extend(embeddings.check_sample(db.all_verses(), VERIFY_SAMPLES)?);
+    } else {
+        eprintln!("The embedding model isn't downloaded, so stored vectors weren't re-checked");
+    }
+
+    if problems.is_empty() {
+        println!("✓ {} embeddings match the scripture data", embeddings.verse_count());
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("✗ {}", problem);
+    }
+    Err(anyhow!("The embeddings don't match the scripture data. Run `scriptures embeddings generate` to rebuild them"))
+}
+
+/// The running daemon's answer to a request, or None when there's no daemon
+/// (or it can't be reached) and the command should load the data itself.
+/// An error it answers with, like a bad reference, is returned as one.
+async fn ask_daemon(request: DaemonRequest) -> Result<Option<DaemonResponse>> {
+    match daemon::request(&request).await {
+        Ok(Some(response)) => Ok(response.ok().map(Some)?),
+        _ => Ok(None),
+    }
+}
+
+/// `daemon [start | stop | status] [--no-embeddings]`: keep the scriptures,
+/// embeddings, and model loaded so `lookup` and `search` answer without
+/// loading them. `start` (the default) runs until stopped.
+async fn run_daemon(args: &Args) -> Result<()> {
+    let no_embeddings = args.has("no-embeddings");
+    let path = daemon::socket_path()?;
+    match args.rest.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["start"] => {
+            let db = load_scriptures().await?;
+            let embeddings = if no_embeddings {
+                None
+            } else {
+                load_embeddings().map_err(|e| eprintln!("Searching by keyword only: {}", e)).ok()
+            };
+            // Build the word index now so the first search doesn't wait on it
+            db.word_index();
+            let started = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            let semantic = if embeddings.is_some() { ", with semantic search" } else { "" };
+            eprintln!("Serving {} verses{} at {} (Ctrl-C or `scriptures daemon stop` to stop)", db.all_verses().len(), semantic, path.display());
+            Ok(daemon::serve(DaemonState { db, embeddings, started }, &path).await?)
+        }
+        ["stop"] => match daemon::request(&DaemonRequest::Stop).await? {
+            Some(_) => {
+                println!("✓ Stopped the daemon");
+                Ok(())
+            }
+            None => Err(anyhow!("No daemon is running")),
+        },
+        ["status"] => match daemon::request(&DaemonRequest::Status).await? {
+            Some(DaemonResponse::Status(DaemonStatus { pid, started, verses, embeddings })) => {
+                let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+                let minutes = now.saturating_sub(started) / 60;
+                let uptime = if minutes < 60 { format!("{}m", minutes) } else { format!("{}h {}m", minutes / 60, minutes % 60) };
+                println!("Running (pid {}, up {})", pid, uptime);
+                println!("  {} verses, {}", verses, if embeddings { "semantic search ready" } else { "keyword search only" });
+                println!("  {}", path.display());
+                Ok(())
+            }
+            Some(_) => Err(anyhow!("Unexpected answer from the daemon")),
+            None => {
+                println!("Not running (start it with `scriptures daemon`)");
+                Ok(())
+            }
+        },
+        _ => Err(anyhow!(commands::usage("daemon"))),
+    }
+}
+
+/// `completions bash|zsh|fish`: a completion script for the shell
+fn completions(args: &[String]) -> Result<()> {
+    let script = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["bash"] => commands::bash(),
+        ["zsh"] => commands::zsh(),
+        ["fish"] => commands::fish(),
+        _ => return Err(anyhow!(commands::usage("completions"))),
+    };
+    print!("{}", script);
+    Ok(())
+}
+
+/// `man [--out <file>]`: the man page, to read with `man -l -` or install
+fn man(args: &Args) -> Result<()> {
+    let out = args.value("out").map(PathBuf::from);
+    if !args.rest.is_empty() {
+        return Err(anyhow!(commands::usage("man")));
+    }
+    match out {
+        Some(path) => {
+            std::fs::write(&path, commands::man_page()).map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+            eprintln!("✓ Wrote {}", path.display());
+        }
+        None => print!("{}", commands::man_page()),
+    }
+    Ok(())
+}
+
+/// `mcp [--transport stdio|http] [--port <port>] [--data <path>] [--embeddings <dir>]
+/// [--no-embeddings]`: serve the scriptures to AI assistants over the Model
+/// Context Protocol. Semantic search uses the embeddings when they're found.
+async fn mcp(args: &Args) -> Result<()> {
+    let transport = args.value("transport").unwrap_or_else(|| "stdio".to_string());
+    let port = args.value("port").unwrap_or_else(|| MCP_HTTP_PORT.to_string());
+    let data = args.value("data").map(PathBuf::from);
+    let embeddings_dir = args.value("embeddings").map(PathBuf::from);
+    let no_embeddings = args.has("no-embeddings");
+    if let Some(arg) = args.rest.first() {
+        return Err(anyhow!("Unexpected argument for mcp: {}", arg));
+    }
+
+    let db = load_scriptures_from(data.as_deref()).await?;
+    let embeddings = match (no_embeddings, embeddings_dir) {
+        (true, _) => None,
+        // Asked for explicitly, so a bad path is an error rather than silently keyword-only
+        (false, Some(dir)) => Some(EmbeddingsDb::load(&dir)?),
+        (false, None) => setup::find_data_dir(EmbeddingsDb::FILE_NAME).and_then(|dir| EmbeddingsDb::load(&dir).ok()),
+    };
+    let hymns = setup::find_data_dir(HymnDb::FILE_NAME).and_then(|dir| HymnDb::load(&dir).ok());
+    let interlinear =
+        setup::find_data_dir(Interlinear::VERSES_FILE_NAME).and_then(|dir| Interlinear::load(&dir).ok());
+
+    match transport.as_str() {
+        "stdio" => {
+            mcp::run_mcp_server(db, hymns, interlinear, embeddings);
+            Ok(())
+        }
+        "http" => {
+            let addr = format!("127.0.0.1:{}", port);
+            eprintln!("Serving MCP at http://{}/mcp", addr);
+            Ok(mcp::run_mcp_http_server(db, hymns, interlinear, embeddings, &addr).await?)
+        }
+        other => Err(anyhow!("Unknown transport {:?} (expected stdio or http)", other)),
+    }
+}
+
+/// `config list|path [<name>]|profiles|get <key>|set <key> <value>|unset <key>`: read and
+/// change settings without editing config.toml by hand. API keys go to the
+/// keychain (see `Config::store_api_key`) and print masked, with where they're
+/// kept. With `--profile`, provider, model, URL, and prompt settings are read
+/// from and saved to that profile.
+fn config(args: &[String]) -> Result<()> {
+    let usage = commands::usage("config");
+    let mut config = Config::load()?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [] | ["list"] => {
+            for key in Config::KEYS {
+                match shown_setting(&config, key)? {
+                    Some(value) => println!("{} = {}", key, value),
+                    None => println!("{} (not set)", key),
+                }
+            }
+        }
+        ["path"] => {
+            for (name, path) in locations()? {
+                println!("{:<11} {}", name, path.display());
+            }
+        }
+        ["path", name] => {
+            let (_, path) = locations()?
+                .into_iter()
+                .find(|(n, _)| n == name)
+                .ok_or_else(|| anyhow!("No location '{}'. Use config, data, state, cache, scriptures, or embeddings", name))?;
+            println!("{}", path.display());
+        }
+        ["profiles"] => {
+            if config.profiles.is_empty() {
+                println!("No profiles. Make one with `scriptures --profile <name> config set provider <provider>`");
+            }
+            for (name, profile) in &config.profiles {
+                let active = if config.profile_name() == Some(name.as_str()) { "*" } else { " " };
+                let provider = profile.provider.as_deref().unwrap_or("-");
+                println!("{} {}  {} {}", active, name, provider, profile.model.as_deref().unwrap_or(""));
+            }
+        }
+        ["get", key] => {
+            let value = shown_setting(&config, key)?.ok_or_else(|| anyhow!("{} is not set", key))?;
+            println!("{}", value);
+        }
+        ["set", key, value @ ..] if !value.is_empty() => {
+            match key_provider(key) {
+                Some(provider) => {
+                    let source = config.store_api_key(provider, &value.join(" "))?;
+                    if source == KeySource::Keychain {
+                        eprintln!("✓ Stored the {} key in the keychain", provider.display_name());
+                    }
+                }
+                None => config.set(key, Some(&value.join(" ")))?,
+            }
+            config.save()?;
+            if let Some(variable) = config.overridden_by(key) {
+                eprintln!("Note: {} is set, and overrides this", variable);
+            }
+        }
+        ["unset", key] => {
+            match key_provider(key) {
+                Some(provider) => config.remove_api_key(provider)?,
+                None => config.set(key, None)?,
+            }
+            config.save()?;
+        }
+        _ => return Err(anyhow!("{}", usage)),
+    }
+    Ok(())
+}
+
+/// Where files are kept, by the name `config path <name>` takes. Scripture
+/// data and embeddings are where they were found, or else where setup puts them.
+fn locations() -> Result<Vec<(&'static str, PathBuf)>> {
+    let data = paths::data_dir()?;
+    Ok(vec![
+        ("config", Config::path()?),
+        ("data", data.clone()),
+        ("state", paths::state_dir()?),
+        ("cache", paths::cache_dir()?),
+        ("logs", logging::log_dir()?),
+        ("study", data.join(Store::FILE_NAME)),
+        ("scriptures", setup::find_scripture_data().unwrap_or_else(|| data.join(setup::SCRIPTURE_JSON))),
+        ("embeddings", setup::find_data_dir(EmbeddingsDb::FILE_NAME).unwrap_or_else(|| data.join("data"))),
+    ])
+}
+
+/// A setting as printed, with API keys masked and found wherever they're
+/// stored, followed by where that is, and others followed by the variable
+/// they came from when one overrides them
+fn shown_setting(config: &Config, key: &str) -> Result<Option<String>> {
+    let Some(provider) = key_provider(key) else {
+        let value = config.get(key)?;
+        return Ok(match config.overridden_by(key) {
+            Some(variable) => value.map(|v| format!("{} ({})", v, variable)),
+            None => value,
+        });
+    };
+    Ok(config.find_api_key(provider).map(|(value, source)| {
+        let from = match source {
+            KeySource::Environment => provider.key_variable().unwrap_or_default(),
+            KeySource::Keychain => "keychain",
+            KeySource::ConfigFile => "config.toml",
+        };
+        format!("{} ({})", mask_secret(&value), from)
+    }))
+}
+
+/// The provider an API key setting ("claude_api_key") is for
+fn key_provider(key: &str) -> Option<Provider> {
+    key.strip_suffix("_api_key").and_then(Provider::from_str)
+}
+
+/// Whether someone is watching: stdout and stderr are terminals. Otherwise
+/// output is plain and nothing is asked interactively.
+fn interactive() -> bool {
+    std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// `query [question] [--context <tag or reference>] [--no-stream]`: ask the
+/// configured AI provider and print the answer as it's generated (or all at
+/// once with `--no-stream`). With no question argument it's read from stdin,
+/// so outlines and notes can be piped in.
+async fn query(args: &Args) -> Result<()> {
+    let context_spec = args.value("context");
+    let stream = !args.has("no-stream");
+    let question = match args.rest.join(" ").trim() {
+        "" => read_question()?,
+        question => question.to_string(),
+    };
+
+    let context = match &context_spec {
+        Some(spec) => query_context(&load_scriptures().await?, &SavedScriptures::load()?, spec)?,
+        None => Vec::new(),
+    };
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let prompt = [config.study_instructions(), context_block(&context), question_block(&question)].concat();
+    let provider = config.current_provider();
+    let model = config.model_for(provider);
+    print_answer(&config, provider, &model, &prompt, stream).await?;
+    Ok(())
+}
+
+/// Ask a provider and print the answer, as it's generated when `stream` is set,
+/// with a status line on stderr until it starts
+async fn print_answer(config: &Config, provider: Provider, model: &str, prompt: &str, stream: bool) -> Result<String> {
+    // A status line while waiting, cleared before the answer
+    let mut status_shown = interactive();
+    if status_shown {
+        let status = format!("Asking {} ({})…", provider.display_name(), model);
+        eprint!("{}", if ColorSupport::detect() == ColorSupport::None { status } else { status.dim().to_string() });
+        std::io::stderr().flush()?;
+    }
+    let mut clear_status = || {
+        if std::mem::take(&mut status_shown) {
+            eprint!("\r\x1b[K");
+        }
+    };
+
+    let (tokens, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let print_tokens = async {
+        let mut printed = false;
+        while let Some(token) = rx.recv().await {
+            if stream {
+                clear_status();
+                print!("{}", token);
+                std::io::stdout().flush()?;
+                printed = true;
+            }
+        }
+        Ok::<_, std::io::Error>(printed)
+    };
+    let (answer, printed) = tokio::join!(ask(config, provider, model, prompt, tokens), print_tokens);
+    clear_status();
+    let answer = answer?;
+
+    if printed? {
+        if !answer.ends_with('\n') {
+            println!();
+        }
+    } else {
+        println!("{}", answer.trim_end());
+    }
+    Ok(answer)
+}
+
+/// The question from stdin: all of it when piped, else one line typed at a prompt
+fn read_question() -> Result<String> {
+    let usage = commands::usage("query");
+    let mut question = String::new();
+    if std::io::stdin().is_terminal() {
+        if !interactive() {
+            return Err(anyhow!("{}", usage));
+        }
+        eprint!("Question: ");
+        std::io::stderr().flush()?;
+        std::io::stdin().read_line(&mut question)?;
+    } else {
+        std::io::stdin().read_to_string(&mut question)?;
+    }
+    match question.trim() {
+        "" => Err(anyhow!("{}", usage)),
+        question => Ok(question.to_string()),
+    }
+}
+
+/// Saved scriptures with the tag `spec`, or else the verses of the reference `spec`
+fn query_context(db: &ScriptureDb, saved: &SavedScriptures, spec: &str) -> Result<Vec<Scripture>> {
+    let tag = spec.trim_start_matches('#').to_lowercase();
+    let tagged = saved.with_tag(&tag);
+    if !tagged.is_empty() {
+        return Ok(tagged);
+    }
+    let range = db
+        .parse_reference(spec)
+        .ok_or_else(|| anyhow!("{:?} is neither a tag on saved scriptures nor a reference", spec))?;
+    Ok(db.get_verses_in_range(&range).into_iter().cloned().collect())
+}
+
+/// Slash commands of `chat`, with what they do, for /help
+const CHAT_COMMANDS: &[(&str, &str)] = &[
+    ("/context [tag | reference | clear]", "show or set the verses the answers draw on"),
+    ("/model [provider] [model]", "show or switch the model (for this chat only)"),
+    ("/save [file.md]", "save the conversation to the journal, or to a file"),
+    ("/new", "start a new conversation"),
+    ("/help", "list these commands"),
+    ("/quit", "leave (or Ctrl-D)"),
+];
+
+/// `chat [--context <tag or reference>] [--continue]`: a conversation at a
+/// prompt, for terminals where the full interface doesn't fit. Answers stream
+/// as they're generated; Up recalls earlier questions. The conversation is
+/// kept with the app's (`[` and `]` in AI mode), and `--continue` picks up
+/// the most recent one.
+async fn chat(args: &Args) -> Result<()> {
+    let context_spec = args.value("context");
+    let resume = args.has("continue");
+    if !args.rest.is_empty() {
+        return Err(anyhow!(commands::usage("chat")));
+    }
+
+    let db = load_scriptures().await?;
+    let saved = SavedScriptures::load().unwrap_or_default();
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let mut provider = config.current_provider();
+    let mut model = config.model_for(provider);
+    let mut context = match &context_spec {
+        Some(spec) => query_context(&db, &saved, spec)?,
+        None => Vec::new(),
+    };
+
+    let mut history = ConversationHistory::load().unwrap_or_default();
+    let mut loaded = history.clone();
+    let mut messages = if resume { history.conversations.pop().map(|c| c.messages).unwrap_or_default() } else { Vec::new() };
+    let asked: Vec<String> = history
+        .conversations
+        .iter()
+        .flat_map(|c| &c.messages)
+        .chain(&messages)
+        .filter(|m| m.role == ChatRole::User)
+        .map(|m| m.content.clone())
+        .collect();
+
+    let styled = interactive() && ColorSupport::detect() != ColorSupport::None;
+    let dim = |text: String| if styled { text.dim().to_string() } else { text };
+    let prompt = if styled { "› ".cyan().bold().to_string() } else { "› ".to_string() };
+    let mut editor = LineEditor::new(asked, prompt, 2);
+
+    println!("{}", dim(format!("Chatting with {} ({}). /help for commands, Ctrl-D to leave.", provider.display_name(), model)));
+    if !context.is_empty() {
+        println!("{}", dim(format!("Context: {}", chat_context_summary(&context))));
+    }
+    if !messages.is_empty() {
+        println!("{}", dim(format!("Continuing \"{}\" ({} messages)", truncate_title(&messages), messages.len())));
+    }
+
+    while let Some(line) = editor.read_line()? {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history(line);
+
+        if let Some(command) = line.strip_prefix('/') {
+            let (name, arg) = command.split_once(' ').map_or((command, ""), |(n, a)| (n, a.trim()));
+            let result: Result<String> = match name {
+                "help" | "?" => Ok(CHAT_COMMANDS.iter().map(|(c, what)| format!("{:<36} {}", c, what)).collect::<Vec<_>>().join("\n")),
+                "quit" | "exit" | "q" => break,
+                "new" => {
+                    history.archive(std::mem::take(&mut messages));
+                    history.save(&loaded)?;
+                    loaded = history.clone();
+                    Ok("Started a new conversation".to_string())
+                }
+                "context" => match arg {
+                    "" if context.is_empty() => Ok("No context verses (/context <tag or reference> to add some)".to_string()),
+                    "" => Ok(format!("Context: {}", chat_context_summary(&context))),
+                    "clear" | "none" => {
+                        context.clear();
+                        Ok("Context cleared".to_string())
+                    }
+                    spec => query_context(&db, &saved, spec).map(|verses| {
+                        context = verses;
+                        format!("Context: {}", chat_context_summary(&context))
+                    }),
+                },
+                "model" => match arg.split_whitespace().collect::<Vec<_>>()[..] {
+                    [] => {
+                        let mut shown = format!("{} ({})", provider.display_name(), model);
+                        let others: Vec<String> = available_models(&config, provider).await.into_iter().filter(|m| *m != model).collect();
+                        if !others.is_empty() {
+                            shown.push_str(&format!("\nAlso available: {}", others.join(", ")));
+                        }
+                        Ok(shown)
+                    }
+                    [name] if Provider::from_str(name).is_none() => {
+                        model = name.to_string();
+                        Ok(format!("Now using {} ({})", provider.display_name(), model))
+                    }
+                    [name] | [name, _] => match Provider::from_str(name) {
+                        None => Err(anyhow!("Unknown provider {:?} (ollama, claude, or openai)", name)),
+                        Some(next) => {
+                            let chosen = match arg.split_whitespace().nth(1) {
+                                Some(chosen) => Some(chosen.to_string()),
+                                None => available_models(&config, next).await.into_iter().next(),
+                            };
+                            match chosen {
+                                Some(chosen) => {
+                                    (provider, model) = (next, chosen);
+                                    Ok(format!("Now using {} ({})", provider.display_name(), model))
+                                }
+                                None => Err(anyhow!("No {} models found; name one: /model {} <model>", next.display_name(), name)),
+                            }
+                        }
+                    },
+                    _ => Err(anyhow!("Usage: /model [provider] [model]")),
+                },
+                "save" if messages.is_empty() => Ok("Nothing to save yet".to_string()),
+                "save" => save_chat(&messages, &context, arg),
+                _ => Err(anyhow!("Unknown command /{} (/help lists them)", name)),
+            };
+            match result {
+                Ok(text) => println!("{}", dim(text)),
+                Err(e) => eprintln!("{}", if styled { e.to_string().red().to_string() } else { e.to_string() }),
+            }
+            continue;
+        }
+
+        let prompt = [config.study_instructions(), context_block(&context), history_block(&messages), question_block(line)].concat();
+        // Ctrl-C stops an answer without leaving the chat
+        let answer = tokio::select! {
+            answer = print_answer(&config, provider, &model, &prompt, true) => answer,
+            _ = tokio::signal::ctrl_c() => Err(anyhow!("Stopped")),
+        };
+        match answer {
+            Ok(answer) => {
+                messages.push(ChatMessage { role: ChatRole::User, content: line.to_string() });
+                messages.push(ChatMessage { role: ChatRole::Assistant, content: answer });
+            }
+            Err(e) => {
+                let e = with_hint(e).to_string();
+                eprintln!("\n{}", if styled { e.red().to_string() } else { e });
+            }
+        }
+        println!();
+    }
+
+    if !messages.is_empty() {
+        history.archive(messages);
+        history.save(&loaded)?;
+    }
+    Ok(())
+}
+
+/// Models a provider offers: those installed for Ollama, else the known list
+async fn available_models(config: &Config, provider: Provider) -> Vec<String> {
+    match provider {
+        Provider::Ollama => config.ollama_client().list_models().await.unwrap_or_default(),
+        Provider::Claude => ClaudeClient::list_models(),
+        Provider::OpenAI => OpenAIClient::list_models(),
+    }
+}
+
+/// The first question of a conversation, cut to fit a status line
+fn truncate_title(messages: &[ChatMessage]) -> String {
+    let title = messages.iter().find(|m| m.role == ChatRole::User).map_or("", |m| m.content.lines().next().unwrap_or(""));
+    match title.char_indices().nth(50) {
+        Some((i, _)) => format!("{}…", &title[..i]),
+        None => title.to_string(),
+    }
+}
+
+/// How many context verses there are and the first few references
+fn chat_context_summary(verses: &[Scripture]) -> String {
+    let titles: Vec<&str> = verses.iter().take(3).map(|v| v.verse_title.as_str()).collect();
+    let more = if verses.len() > titles.len() { ", …" } else { "" };
+    format!("{} verse{} ({}{})", verses.len(), if verses.len() == 1 { "" } else { "s" }, titles.join(", "), more)
+}
+
+/// `/save`: the conversation as a journal entry quoting the context verses,
+/// or as Markdown in `file`
+fn save_chat(messages: &[ChatMessage], context: &[Scripture], file: &str) -> Result<String> {
+    if !file.is_empty() {
+        std::fs::write(file, chat_markdown(messages)).map_err(|e| anyhow!("Failed to write {}: {}", file, e))?;
+        return Ok(format!("✓ Saved to {}", file));
+    }
+    let (path, template) = Journal::load()?.create_entry(None, context)?;
+    std::fs::write(&path, format!("{}\n\n{}", template.trim_end(), chat_markdown(messages)))?;
+    Ok(format!("✓ Saved to the journal ({})", path.display()))
+}
+
+/// Verses found for each batch question and added to its prompt
+const BATCH_RETRIEVED_VERSES: usize = 8;
+
+/// One question from a batch file, with the tags or references (separated by
+/// `;`) to answer from
+#[derive(Debug, PartialEq)]
+struct BatchQuestion {
+    question: String,
+    context: Option<String>,
+}
+
+/// Questions from a batch file: one per line (blank lines and `#` comments
+/// skipped), or as YAML, a list of `question:`/`context:` items
+fn parse_batch(text: &str, yaml: bool) -> Result<Vec<BatchQuestion>> {
+    let lines = text.lines().map(str::trim_end).filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'));
+    if !yaml {
+        return Ok(lines.map(|l| BatchQuestion { question: l.trim().to_string(), context: None }).collect());
+    }
+
+    let unquote = |value: &str| {
+        let value = value.trim();
+        let quoted = value.len() >= 2
+            && ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\'')));
+        if quoted { value[1..value.len() - 1].to_string() } else { value.to_string() }
+    };
+    let mut items: Vec<(Option<String>, Option<String>)> = Vec::new();
+    for line in lines {
+        let entry = match line.trim_start().strip_prefix("- ") {
+            Some(entry) => {
+                items.push((None, None));
+                entry
+            }
+            None => line.trim_start(),
+        };
+        let item = items.last_mut().ok_or_else(|| anyhow!("Expected a list item (\"- question: ...\"), found {:?}", line))?;
+        match entry.split_once(':').map(|(key, value)| (key.trim(), value)) {
+            Some(("question", value)) => item.0 = Some(unquote(value)),
+            Some(("context", value)) => {
+                let value = value.trim();
+                item.1 = Some(match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                    Some(list) => list.split(',').map(unquote).collect::<Vec<_>>().join("; "),
+                    None => unquote(value),
+                });
+            }
+            // A bare "- What is faith?" item
+            _ if line.trim_start().starts_with("- ") => item.0 = Some(unquote(entry)),
+            _ => return Err(anyhow!("Unknown line in batch file: {:?} (expected question: or context:)", line)),
+        }
+    }
+    items
+        .into_iter()
+        .map(|(question, context)| {
+            let question = question.ok_or_else(|| anyhow!("A batch item has context but no question"))?;
+            Ok(BatchQuestion { question, context })
+        })
+        .collect()
+}
+
+/// `batch <file> [--out <file>]`: answer every question in a file, each with
+/// the verses most related to it (plus any context it names), and write the
+/// answers as Markdown with the verses they drew on
+async fn batch(args: &Args) -> Result<()> {
+    let out = args.value("out").map(PathBuf::from);
+    let [file] = args.rest.as_slice() else {
+        return Err(anyhow!(commands::usage("batch")));
+    };
+    let text = if file == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(file).map_err(|e| anyhow!("Failed to read {}: {}", file, e))?
+    };
+    let yaml = file.ends_with(".yaml") || file.ends_with(".yml");
+    let questions = parse_batch(&text, yaml)?;
+    if questions.is_empty() {
+        return Err(anyhow!("No questions in {}", file));
+    }
+
+    let db = load_scriptures().await?;
+    let saved = SavedScriptures::load().unwrap_or_default();
+    // Related verses come from semantic search when it's available, else keywords
+    let mut embeddings = load_embeddings().ok();
+    let mode = if embeddings.is_some() { SearchMode::Combined } else { SearchMode::Keyword };
+    let filter = SearchFilter { mode, limit: BATCH_RETRIEVED_VERSES, ..SearchFilter::default() };
+
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    let provider = config.current_provider();
+    let model = config.model_for(provider);
+
+    let mut writer: Box<dyn Write> = match &out {
+        Some(path) => Box::new(std::fs::File::create(path).map_err(|e| anyhow!("Failed to create {}: {}", path.display(), e))?),
+        None => Box::new(std::io::stdout()),
+    };
+    let show_progress = std::io::stderr().is_terminal();
+    if show_progress && mode == SearchMode::Keyword {
+        eprintln!("No semantic search available, so related verses are found by keyword");
+    }
+
+    for (i, item) in questions.iter().enumerate() {
+        if show_progress {
+            eprintln!("[{}/{}] {}", i + 1, questions.len(), item.question);
+        }
+        let mut verses = Vec::new();
+        for spec in item.context.iter().flat_map(|c| c.split(';')).map(str::trim).filter(|s| !s.is_empty()) {
+            verses.extend(query_context(&db, &saved, spec)?);
+        }
+        for hit in escrituras_core::search::search(&db, embeddings.as_mut(), &item.question, &filter) {
+            if !verses.iter().any(|v| v.verse_title == hit.scripture.verse_title) {
+                verses.push(hit.scripture);
+            }
+        }
+
+        let prompt = [config.study_instructions(), context_block(&verses), question_block(&item.question)].concat();
+        let (tokens, _) = tokio::sync::mpsc::unbounded_channel();
+        let answer = ask(&config, provider, &model, &prompt, tokens).await?;
+
+        writeln!(writer, "## {}\n", item.question)?;
+        if let Some(context) = &item.context {
+            writeln!(writer, "*Context: {}*\n", context)?;
+        }
+        writeln!(writer, "{}\n", answer.trim())?;
+        if !verses.is_empty() {
+            let cited: Vec<&str> = verses.iter().map(|v| v.verse_title.as_str()).collect();
+            writeln!(writer, "**Verses consulted:** {}\n", cited.join(", "))?;
+        }
+        // Keep what's answered so far if a later question fails
+        writer.flush()?;
+    }
+    if let Some(path) = out {
+        eprintln!("✓ Wrote {} answers to {}", questions.len(), path.display());
+    }
+    Ok(())
+}
+
+/// Stream an answer from a provider, sending the text to `tokens` as it arrives
+async fn ask(config: &Config, provider: Provider, model: &str, prompt: &str, tokens: TokenSender) -> Result<String> {
+    Ok(ai::ask(config, provider, model, prompt, tokens).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch_text_and_yaml() {
+        let text = "# Lesson 12\nWhat is faith?\n\n  Why plant the seed?\n";
+        let questions = parse_batch(text, false).unwrap();
+        assert_eq!(questions.iter().map(|q| q.question.as_str()).collect::<Vec<_>>(), ["What is faith?", "Why plant the seed?"]);
+
+        let yaml = "- question: \"How does faith grow?\"\n  context: Alma 32:28-43\n- What is hope?\n- question: Why serve?\n  context: [Mosiah 2:17, service]\n";
+        assert_eq!(
+            parse_batch(yaml, true).unwrap(),
+            [
+                BatchQuestion { question: "How does faith grow?".into(), context: Some("Alma 32:28-43".into()) },
+                BatchQuestion { question: "What is hope?".into(), context: None },
+                BatchQuestion { question: "Why serve?".into(), context: Some("Mosiah 2:17; service".into()) },
+            ]
+        );
+        assert!(parse_batch("- context: Alma 32\n", true).is_err());
+        assert!(parse_batch("question: orphan\n", true).is_err());
+    }
+
+    #[test]
+    fn test_chat_markdown_and_title() {
+        let message = |role, content: &str| ChatMessage { role, content: content.to_string() };
+        let messages = [
+            message(ChatRole::User, "What does Alma compare faith to?\n"),
+            message(ChatRole::Assistant, "A seed (Alma 32:28).\n"),
+        ];
+        assert_eq!(chat_markdown(&messages), "### What does Alma compare faith to?\n\nA seed (Alma 32:28).\n\n");
+        assert_eq!(truncate_title(&messages), "What does Alma compare faith to?");
+        assert_eq!(truncate_title(&[message(ChatRole::User, &"a".repeat(60))]), format!("{}…", "a".repeat(50)));
+    }
+}
@@ -0,0 +1,727 @@
+//! The subcommands and their options, described once so parsing, usage
+//! errors, `--help`, shell completions, and the man page all agree
+//!
+//! `Args::parse` splits a command's arguments by its entry here, so an
+//! option that isn't in the table is refused rather than taken as text, and
+//! `cli` asking for one that isn't panics.
+
+use anyhow::{anyhow, Result};
+
+/// What an option or a command's first argument takes, for completing it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// Anything, shown as `<placeholder>`
+    Text(&'static str),
+    /// One of these words
+    Choice(&'static [&'static str]),
+    File,
+    Dir,
+    /// A subcommand name (for `help`)
+    Command,
+}
+
+/// A `--name` option
+#[derive(Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    /// A one-letter spelling, as in `-C 2` or `-C2`
+    pub short: Option<char>,
+    pub value: Option<Value>,
+    pub about: &'static str,
+}
+
+/// A subcommand of `scriptures`
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    /// Arguments after the name, as in usage errors
+    pub synopsis: &'static str,
+    pub about: &'static str,
+    /// What the first argument is, when it can be completed
+    pub first: Option<Value>,
+    /// Whether it writes study data, and so needs older files moved, old
+    /// settings upgraded, and the sync folder synced before and after it
+    pub changes_study_data: bool,
+    pub flags: &'static [Flag],
+}
+
+/// Options of the app itself, used without a subcommand
+pub const GLOBAL_FLAGS: &[Flag] = &[
+    Flag { name: "setup", short: None, value: None, about: "Run the setup wizard before starting" },
+    Flag { name: "profile", short: None, value: Some(Value::Text("name")), about: "Use a config profile (or set ESCRITURAS_PROFILE)" },
+    Flag { name: "verbose", short: None, value: None, about: "Log debug messages, and show them on stderr" },
+    Flag { name: "timings", short: None, value: None, about: "Report how long loading the data took, on stderr" },
+    Flag { name: "mcp", short: None, value: None, about: "Same as `scriptures mcp`" },
+    Flag { name: "download-model", short: None, value: None, about: "Download the semantic search model and exit" },
+    Flag { name: "help", short: None, value: None, about: "List the subcommands" },
+];
+
+const FORMATS: Value = Value::Choice(&["reference", "plain", "md", "attributed", "footnote", "ref-only"]);
+const FORMAT: Flag = Flag { name: "format", short: None, value: Some(FORMATS), about: "Print passages in a copy format" };
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "lookup",
+        synopsis: "<reference>[; <reference>...] [--format <format>]",
+        about: "Print verses by reference",
+        first: None,
+        changes_study_data: false,
+        flags: &[FORMAT],
+    },
+    Command {
+        name: "search",
+        synopsis: "<query> [--semantic | --hybrid] [--volume <volume>] [--book <book>] [--limit <n>] [-C <n>] [--format <format>]",
+        about: "Print verses matching a search",
+        first: None,
+        changes_study_data: false,
+        flags: &[
+            Flag { name: "semantic", short: None, value: None, about: "Rank verses by meaning" },
+            Flag { name: "hybrid", short: None, value: None, about: "Meaning matches first, then keyword matches" },
+            Flag { name: "volume", short: None, value: Some(Value::Text("volume")), about: "Only search one volume" },
+            Flag { name: "book", short: None, value: Some(Value::Text("book")), about: "Only search one book" },
+            Flag { name: "limit", short: None, value: Some(Value::Text("n")), about: "How many verses to print (20)" },
+            Flag { name: "context", short: Some('C'), value: Some(Value::Text("n")), about: "Verses to show around each match" },
+            FORMAT,
+        ],
+    },
+    Command {
+        name: "concordance",
+        synopsis: "<word> [--volume <volume>] [--book <book>] [--json]",
+        about: "Print every verse a word occurs in, by book",
+        first: None,
+        changes_study_data: false,
+        flags: &[
+            Flag { name: "volume", short: None, value: Some(Value::Text("volume")), about: "Only one volume" },
+            Flag { name: "book", short: None, value: Some(Value::Text("book")), about: "Only one book" },
+            Flag { name: "json", short: None, value: None, about: "Print the occurrences as JSON" },
+        ],
+    },
+    Command {
+        name: "read-aloud",
+        synopsis: "<reference>",
+        about: "Read a chapter or passage aloud with text-to-speech",
+        first: None,
+        changes_study_data: false,
+        flags: &[],
+    },
+    Command {
+        name: "card",
+        synopsis: "<reference> [--out <card.png | card.svg>] [--background <color>] [--font <font>] [--reference above|below] [--size <WxH>]",
+        about: "Make a PNG or SVG quote card of a verse or passage to share",
+        first: None,
+        changes_study_data: false,
+        flags: &[
+            Flag { name: "out", short: None, value: Some(Value::File), about: "Where to write the card; .svg for SVG (<reference>.png)" },
+            Flag { name: "background", short: None, value: Some(Value::Text("color")), about: "Background color, like #1f2a44" },
+            Flag { name: "font", short: None, value: Some(Value::Text("font")), about: "Font family of an SVG card" },
+            Flag { name: "reference", short: None, value: Some(Value::Choice(&["above", "below"])), about: "Where the reference goes" },
+            Flag { name: "size", short: None, value: Some(Value::Text("WxH")), about: "Card size in pixels (1200x630)" },
+        ],
+    },
+    Command {
+        name: "topic",
+        synopsis: "[<topic>]",
+        about: "A study topic's Topical Guide verses, related verses, and talks, or the list of topics",
+        first: None,
+        changes_study_data: false,
+        flags: &[],
+    },
+    Command {
+        name: "compare",
+        synopsis: "<reference> <reference> [--unified]",
+        about: "Compare parallel passages word by word",
+        first: None,
+        changes_study_data: false,
+        flags: &[Flag { name: "unified", short: None, value: None, about: "Only the verses that differ, as one line each" }],
+    },
+    Command {
+        name: "quiz",
+        synopsis: "[--volume <volume>] [--book <book>] [--n <count>] [--mode reference|blank]",
+        about: "Name or complete random verses",
+        first: None,
+        changes_study_data: true,
+        flags: &[
+            Flag { name: "volume", short: None, value: Some(Value::Text("volume")), about: "Only ask about one volume" },
+            Flag { name: "book", short: None, value: Some(Value::Text("book")), about: "Only ask about one book" },
+            Flag { name: "n", short: None, value: Some(Value::Text("count")), about: "How many questions (10)" },
+            Flag { name: "mode", short: None, value: Some(Value::Choice(&["reference", "blank", "mixed"])), about: "Which kind of question" },
+        ],
+    },
+    Command {
+        name: "query",
+        synopsis: "<question> [--context <tag or reference>] [--no-stream]",
+        about: "Ask the AI a question",
+        first: None,
+        changes_study_data: false,
+        flags: &[
+            Flag { name: "context", short: None, value: Some(Value::Text("tag or reference")), about: "Answer from tagged verses or a passage" },
+            Flag { name: "no-stream", short: None, value: None, about: "Print the answer when it's finished" },
+        ],
+    },
+    Command {
+        name: "chat",
+        synopsis: "[--context <tag or reference>] [--continue]",
+        about: "Talk with the AI at a prompt",
+        first: None,
+        changes_study_data: true,
+        flags: &[
+            Flag { name: "context", short: None, value: Some(Value::Text("tag or reference")), about: "Answer from tagged verses or a passage" },
+            Flag { name: "continue", short: None, value: None, about: "Pick up the last conversation" },
+        ],
+    },
+    Command {
+        name: "batch",
+        synopsis: "<questions.txt | questions.yaml | -> [--out <answers.md>]",
+        about: "Answer a file of questions",
+        first: Some(Value::File),
+        changes_study_data: false,
+        flags: &[Flag { name: "out", short: None, value: Some(Value::File), about: "Write the answers to a file" }],
+    },
+    Command {
+        name: "plan",
+        synopsis: "[today | start | done [<n> | <chapter>] | status]",
+        about: "Follow the Come, Follow Me reading",
+        first: Some(Value::Choice(&["today", "start", "done", "status"])),
+        changes_study_data: true,
+        flags: &[FORMAT],
+    },
+    Command {
+        name: "note",
+        synopsis: "add <reference> [text] | list [--book <book>] | export [--book <book>] [--out <file>]",
+        about: "Write and list notes on verses",
+        first: Some(Value::Choice(&["add", "list", "export"])),
+        changes_study_data: true,
+        flags: &[
+            Flag { name: "book", short: None, value: Some(Value::Text("book")), about: "Only notes on one book" },
+            Flag { name: "out", short: None, value: Some(Value::File), about: "Write the export to a file" },
+        ],
+    },
+    Command {
+        name: "library",
+        synopsis: "import <file.epub> [--embed] | list | embed <title> | remove <title>",
+        about: "Import EPUB books to study beside the scriptures",
+        first: Some(Value::Choice(&["import", "list", "embed", "remove"])),
+        changes_study_data: false,
+        flags: &[Flag { name: "embed", short: None, value: None, about: "Embed the book for semantic search after importing" }],
+    },
+    Command {
+        name: "export-anki",
+        synopsis: "[--tag <tag>] [--memorized] [--cloze] [--out <file>]",
+        about: "Export saved scriptures as Anki cards",
+        first: None,
+        changes_study_data: false,
+        flags: &[
+            Flag { name: "tag", short: None, value: Some(Value::Text("tag")), about: "Only saved verses with a tag" },
+            Flag { name: "memorized", short: None, value: None, about: "The verses practiced in memorize mode" },
+            Flag { name: "cloze", short: None, value: None, about: "Cloze cards instead of Basic" },
+            Flag { name: "out", short: None, value: Some(Value::File), about: "Write the cards to a file" },
+        ],
+    },
+    Command {
+        name: "export-vault",
+        synopsis: "<dir>",
+        about: "Export the scriptures as an Obsidian vault",
+        first: Some(Value::Dir),
+        changes_study_data: false,
+        flags: &[],
+    },
+    Command {
+        name: "backup",
+        synopsis: "export [<file>] | import <file>",
+        about: "Save your study data to a file, or merge one in",
+        first: Some(Value::Choice(&["export", "import"])),
+        changes_study_data: true,
+        flags: &[],
+    },
+    Command {
+        name: "sync",
+        synopsis: "[<folder>]",
+        about: "Sync your study data with a folder (sync_dir by default)",
+        first: Some(Value::Dir),
+        changes_study_data: true,
+        flags: &[],
+    },
+    Command {
+        name: "logs",
+        synopsis: "[path] [--n <lines>]",
+        about: "Show the end of the log, or where logs are kept",
+        first: Some(Value::Choice(&["path"])),
+        changes_study_data: false,
+        flags: &[Flag { name: "n", short: None, value: Some(Value::Text("lines")), about: "How many lines to show (40)" }],
+    },
+    Command {
+        name: "config",
+        synopsis: "list | path [<name>] | profiles | get <key> | set <key> <value> | unset <key>",
+        about: "Show and change settings",
+        first: Some(Value::Choice(&["list", "path", "profiles", "get", "set", "unset"])),
+        changes_study_data: false,
+        flags: &[],
+    },
+    Command {
+        name: "embeddings",
+        synopsis: "[status | download | generate [--out <dir>] | verify]",
+        about: "Manage the semantic search files",
+        first: Some(Value::Choice(&["status", "download", "generate", "verify"])),
+        changes_study_data: false,
+        flags: &[Flag { name: "out", short: None, value: Some(Value::Dir), about: "Where generate writes the embeddings" }],
+    },
+    Command {
+        name: "mcp",
+        synopsis: "[--transport stdio|http] [--port <port>] [--data <path>] [--embeddings <dir>] [--no-embeddings]",
+        about: "Serve the scriptures over the Model Context Protocol",
+        first: None,
+        changes_study_data: false,
+        flags: &[
+            Flag { name: "transport", short: None, value: Some(Value::Choice(&["stdio", "http"])), about: "How clients connect (stdio)" },
+            Flag { name: "port", short: None, value: Some(Value::Text("port")), about: "Port for the http transport (8765)" },
+            Flag { name: "data", short: None, value: Some(Value::File), about: "Scripture JSON file or its folder" },
+            Flag { name: "embeddings", short: None, value: Some(Value::Dir), about: "Folder holding the embeddings" },
+            Flag { name: "no-embeddings", short: None, value: None, about: "Keyword search only" },
+        ],
+    },
+    Command {
+        name: "daemon",
+        synopsis: "[start | stop | status] [--no-embeddings]",
+        about: "Keep the scriptures loaded for fast lookups and searches",
+        first: Some(Value::Choice(&["start", "stop", "status"])),
+        changes_study_data: false,
+        flags: &[Flag { name: "no-embeddings", short: None, value: None, about: "Keyword search only" }],
+    },
+    Command {
+        name: "completions",
+        synopsis: "bash | zsh | fish",
+        about: "Print a shell completion script",
+        first: Some(Value::Choice(&["bash", "zsh", "fish"])),
+        changes_study_data: false,
+        flags: &[],
+    },
+    Command {
+        name: "man",
+        synopsis: "[--out <file>]",
+        about: "Print the man page",
+        first: None,
+        changes_study_data: false,
+        flags: &[Flag { name: "out", short: None, value: Some(Value::File), about: "Write the page to a file" }],
+    },
+    Command {
+        name: "help",
+        synopsis: "[<command>]",
+        about: "Show how to use a command",
+        first: Some(Value::Command),
+        changes_study_data: false,
+        flags: &[],
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/// `Usage: scriptures <name> <synopsis>`, for errors
+pub fn usage(name: &str) -> String {
+    match find(name) {
+        Some(command) => format!("Usage: scriptures {} {}", command.name, command.synopsis),
+        None => format!("Usage: scriptures {}", name),
+    }
+}
+
+/// A command's arguments, split by its table entry into options and the rest
+#[derive(Debug)]
+pub struct Args {
+    flags: &'static [Flag],
+    /// The arguments that aren't options, in order
+    pub rest: Vec<String>,
+    given: Vec<(&'static str, Option<String>)>,
+}
+
+impl Args {
+    /// Split `args` by the command's flags: `--name value`, `--name=value`,
+    /// `-C value`, or `-Cvalue`, and after `--` only arguments
+    pub fn parse(command: &'static Command, args: &[String]) -> Result<Args> {
+        let mut parsed = Args { flags: command.flags, rest: Vec::new(), given: Vec::new() };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                parsed.rest.extend(args.by_ref().cloned());
+                break;
+            }
+            let (flag, attached) = if let Some(long) = arg.strip_prefix("--") {
+                let (name, attached) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (long, None),
+                };
+                let flag = command.flags.iter().find(|f| f.name == name);
+                (flag.ok_or_else(|| anyhow!("Unknown option --{}\n{}", name, usage(command.name)))?, attached)
+            } else {
+                let mut chars = arg.chars();
+                let short = (chars.next() == Some('-')).then(|| chars.next()).flatten();
+                match command.flags.iter().find(|f| short.is_some() && f.short == short) {
+                    Some(flag) => (flag, Some(chars.as_str()).filter(|value| !value.is_empty())),
+                    None => {
+                        parsed.rest.push(arg.clone());
+                        continue;
+                    }
+                }
+            };
+            let value = match (flag.value, attached) {
+                (Some(_), Some(value)) => Some(value.to_string()),
+                (Some(_), None) => Some(args.next().cloned().ok_or_else(|| anyhow!("--{} needs a value", flag.name))?),
+                (None, None) => None,
+                (None, Some(_)) => return Err(anyhow!("--{} doesn't take a value", flag.name)),
+            };
+            parsed.given.push((flag.name, value));
+        }
+        Ok(parsed)
+    }
+
+    fn flag(&self, name: &str) -> &'static Flag {
+        let flags = self.flags;
+        flags.iter().find(|f| f.name == name).unwrap_or_else(|| panic!("--{} isn't in the command's table entry", name))
+    }
+
+    /// The value given for `--name`, the last one when it's given twice
+    pub fn value(&self, name: &str) -> Option<String> {
+        assert!(self.flag(name).value.is_some(), "--{} is a switch", name);
+        self.given.iter().rev().find(|(given, _)| *given == name).and_then(|(_, value)| value.clone())
+    }
+
+    /// Whether the switch `--name` was given
+    pub fn has(&self, name: &str) -> bool {
+        assert!(self.flag(name).value.is_none(), "--{} takes a value", name);
+        self.given.iter().any(|(given, _)| *given == name)
+    }
+}
+
+/// `--name <value>` as shown in help, after `-C, ` when it has a short spelling
+fn flag_text(flag: &Flag) -> String {
+    let long = match flag.value {
+        Some(Value::Text(placeholder)) => format!("--{} <{}>", flag.name, placeholder),
+        Some(Value::Choice(words)) => format!("--{} {}", flag.name, words.join("|")),
+        Some(Value::File) => format!("--{} <file>", flag.name),
+        Some(Value::Dir) => format!("--{} <dir>", flag.name),
+        Some(Value::Command) => format!("--{} <command>", flag.name),
+        None => format!("--{}", flag.name),
+    };
+    match flag.short {
+        Some(short) => format!("-{}, {}", short, long),
+        None => long,
+    }
+}
+
+fn flag_table(flags: &[Flag]) -> String {
+    let width = flags.iter().map(|f| flag_text(f).len()).max().unwrap_or(0);
+    flags.iter().map(|f| format!("  {:<width$}  {}\n", flag_text(f), f.about, width = width)).collect()
+}
+
+/// Help for a command, or with None the list of commands
+pub fn help(name: Option<&str>) -> String {
+    match name.and_then(find) {
+        Some(command) => {
+            let mut out = format!("{}\n\n{}\n", usage(command.name), command.about);
+            if !command.flags.is_empty() {
+                out.push_str(&format!("\nOptions:\n{}", flag_table(command.flags)));
+            }
+            out
+        }
+        None => {
+            let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+            let mut out = String::from("Usage: scriptures [<command>] [<args>]\n\nWith no command, opens the app.\n\nCommands:\n");
+            for command in COMMANDS {
+                out.push_str(&format!("  {:<width$}  {}\n", command.name, command.about, width = width));
+            }
+            out.push_str(&format!("\nOptions:\n{}", flag_table(GLOBAL_FLAGS)));
+            out.push_str("\n`scriptures help <command>` (or `<command> --help`) shows a command's options.\n");
+            out
+        }
+    }
+}
+
+fn names() -> Vec<&'static str> {
+    COMMANDS.iter().map(|c| c.name).collect()
+}
+
+/// Completion script for bash
+pub fn bash() -> String {
+    let globals: Vec<String> = GLOBAL_FLAGS.iter().map(|f| format!("--{}", f.name)).collect();
+    let complete = |value: Value| match value {
+        Value::Choice(words) => format!("COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", words.join(" ")),
+        Value::Command => format!("COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", names().join(" ")),
+        Value::File => "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string(),
+        Value::Dir => "COMPREPLY=($(compgen -d -- \"$cur\"))".to_string(),
+        Value::Text(_) => "COMPREPLY=()".to_string(),
+    };
+
+    let mut values = String::new();
+    let mut flags = String::new();
+    let mut firsts = String::new();
+    for command in COMMANDS {
+        for flag in command.flags {
+            if let Some(value) = flag.value {
+                values.push_str(&format!("        {}:--{}) {}; return ;;\n", command.name, flag.name, complete(value)));
+            }
+        }
+        let names: Vec<String> = command
+            .flags
+            .iter()
+            .flat_map(|f| f.short.map(|c| format!("-{}", c)).into_iter().chain([format!("--{}", f.name)]))
+            .chain(["--help".to_string()])
+            .collect();
+        flags.push_str(&format!("        {}) flags=\"{}\" ;;\n", command.name, names.join(" ")));
+        if let Some(value) = command.first {
+            firsts.push_str(&format!("            {}) {} ;;\n", command.name, complete(value)));
+        }
+    }
+
+    format!(
+        r#"# bash completion for scriptures
+# Install: scriptures completions bash > ~/.local/share/bash-completion/completions/scriptures
+
+_scriptures() {{
+    local cur prev cmd flags
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "{commands} {globals}" -- "$cur"))
+        return
+    fi
+    cmd="${{COMP_WORDS[1]}}"
+    case "$cmd:$prev" in
+{values}    esac
+    case "$cmd" in
+{flags}    esac
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "$flags" -- "$cur"))
+    elif [ "$COMP_CWORD" -eq 2 ]; then
+        case "$cmd" in
+{firsts}        esac
+    fi
+}}
+complete -F _scriptures scriptures
+"#,
+        commands = names().join(" "),
+        globals = globals.join(" "),
+    )
+}
+
+/// Text safe inside a zsh `_arguments` description or `_describe` entry
+fn zsh_quote(text: &str) -> String {
+    text.replace('\'', "'\\''").replace(':', "\\:").replace('[', "(").replace(']', ")")
+}
+
+/// Completion script for zsh
+pub fn zsh() -> String {
+    let action = |value: Value| match value {
+        Value::Choice(words) => format!("({})", words.join(" ")),
+        Value::Command => format!("({})", names().join(" ")),
+        Value::File => "_files".to_string(),
+        Value::Dir => "_files -/".to_string(),
+        Value::Text(_) => " ".to_string(),
+    };
+    let described: Vec<String> = COMMANDS.iter().map(|c| format!("        '{}:{}'", c.name, zsh_quote(c.about))).collect();
+
+    let mut cases = String::new();
+    for command in COMMANDS {
+        let mut specs: Vec<String> = command
+            .flags
+            .iter()
+            .flat_map(|f| {
+                // A long option's value follows `=`, a short one's may follow it directly
+                let spellings = f.short.map(|c| format!("-{}+", c)).into_iter().chain([format!("--{}=", f.name)]);
+                spellings.map(move |spelling| match f.value {
+                    Some(value) => {
+                        let placeholder = match value {
+                            Value::Text(p) => p,
+                            _ => f.name,
+                        };
+                        format!("'{}[{}]:{}:{}'", spelling, zsh_quote(f.about), zsh_quote(placeholder), action(value))
+                    }
+                    None => format!("'{}[{}]'", spelling.trim_end_matches(['=', '+']), zsh_quote(f.about)),
+                })
+            })
+            .collect();
+        specs.push("'--help[Show how to use the command]'".to_string());
+        if let Some(value) = command.first {
+            specs.push(format!("'1:argument:{}'", action(value)));
+        }
+        specs.push("'*::argument: '".to_string());
+        cases.push_str(&format!("        {})\n            _arguments \\\n                {} ;;\n", command.name, specs.join(" \\\n                ")));
+    }
+    let globals: Vec<String> = GLOBAL_FLAGS.iter().map(|f| format!("'--{}[{}]'", f.name, zsh_quote(f.about))).collect();
+
+    format!(
+        r#"#compdef scriptures
+# zsh completion for scriptures
+# Install: scriptures completions zsh > "${{fpath[1]}}/_scriptures"
+
+_scriptures() {{
+    local -a commands
+    commands=(
+{described}
+    )
+    if (( CURRENT == 2 )); then
+        _describe 'command' commands
+        _arguments {globals}
+        return
+    fi
+    words=("${{words[@]:1}}")
+    (( CURRENT-- ))
+    case "$words[1]" in
+{cases}    esac
+}}
+
+_scriptures "$@"
+"#,
+        described = described.join("\n"),
+        globals = globals.join(" "),
+    )
+}
+
+/// Completion script for fish
+pub fn fish() -> String {
+    let quote = |text: &str| format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"));
+    let mut out = String::from(
+        "# fish completion for scriptures\n# Install: scriptures completions fish > ~/.config/fish/completions/scriptures.fish\n\n\
+         complete -c scriptures -f\n",
+    );
+    for flag in GLOBAL_FLAGS {
+        out.push_str(&format!("complete -c scriptures -n __fish_use_subcommand -l {} -d {}\n", flag.name, quote(flag.about)));
+    }
+    for command in COMMANDS {
+        out.push_str(&format!("complete -c scriptures -n __fish_use_subcommand -a {} -d {}\n", command.name, quote(command.about)));
+    }
+    // Candidates for a value: -a words, -F files. An option's value is
+    // required (-r), and unless it's a file, files aren't offered (-x).
+    let candidates = |value: Value| match value {
+        Value::Choice(words) => format!("-a {}", quote(&words.join(" "))),
+        Value::Command => format!("-a {}", quote(&names().join(" "))),
+        Value::File => "-F".to_string(),
+        Value::Dir => "-a '(__fish_complete_directories)'".to_string(),
+        Value::Text(_) => String::new(),
+    };
+    for command in COMMANDS {
+        let when = format!("-n '__fish_seen_subcommand_from {}'", command.name);
+        out.push('\n');
+        if let Some(value) = command.first {
+            out.push_str(&format!("complete -c scriptures {} {}\n", when, candidates(value)));
+        }
+        for flag in command.flags {
+            let value = match flag.value {
+                Some(Value::File) => " -r -F".to_string(),
+                Some(Value::Text(_)) => " -x".to_string(),
+                Some(value) => format!(" -x {}", candidates(value)),
+                None => String::new(),
+            };
+            let short = flag.short.map(|c| format!(" -s {}", c)).unwrap_or_default();
+            out.push_str(&format!("complete -c scriptures {}{} -l {}{} -d {}\n", when, short, flag.name, value, quote(flag.about)));
+        }
+    }
+    out
+}
+
+/// Text for roff, with hyphens kept as hyphens and lines that would read as
+/// requests escaped
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// The man page, in roff (`scriptures man | man -l -`)
+pub fn man_page() -> String {
+    let mut out = format!(
+        ".TH SCRIPTURES 1 \"\" \"scriptures {}\" \"User Commands\"\n\
+         .SH NAME\nscriptures \\- study the scriptures in the terminal, with AI insights\n\
+         .SH SYNOPSIS\n.B scriptures\n[\\fB\\-\\-setup\\fR]\n.br\n.B scriptures\n\\fIcommand\\fR [\\fIargs\\fR]\n\
+         .SH DESCRIPTION\n\
+         With no command, \\fBscriptures\\fR opens a terminal interface for browsing, searching, \
+         and studying the standard works, and for asking an AI provider (Ollama, Claude, or OpenAI) \
+         about them. The commands below print to standard output and exit, for scripts and shell aliases.\n\
+         .SH OPTIONS\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for flag in GLOBAL_FLAGS {
+        out.push_str(&format!(".TP\n.B {}\n{}\n", roff(&flag_text(flag)), roff(flag.about)));
+    }
+    out.push_str(".SH COMMANDS\n");
+    for command in COMMANDS {
+        out.push_str(&format!(".SS \"{} {}\"\n{}.\n", roff(command.name), roff(command.synopsis), roff(command.about)));
+        for flag in command.flags {
+            out.push_str(&format!(".TP\n.B {}\n{}\n", roff(&flag_text(flag)), roff(flag.about)));
+        }
+    }
+    out.push_str(
+        ".SH FILES\n\
+         .TP\n.I ~/.config/escrituras/config.toml\nSettings (see \\fBscriptures config\\fR)\n\
+         .TP\n.I ~/.config/escrituras/keybindings.toml\nKey bindings of the interface\n\
+         .TP\n.I ~/.local/share/escrituras/\nScripture data, embeddings, and your journal (see \\fBscriptures config path\\fR)\n\
+         .TP\n.I ~/.local/share/escrituras/study.db\nSaved verses, highlights, conversations, and memorization and reading progress\n\
+         .TP\n.I ~/.local/share/escrituras/logs/\nA log file a day, the last week kept (see \\fBscriptures logs\\fR)\n\
+         .SH ENVIRONMENT\n\
+         .TP\n.B ANTHROPIC_API_KEY\nAPI key for Claude, used before the one in the config\n\
+         .TP\n.B OPENAI_API_KEY\nAPI key for OpenAI, used before the one in the config\n\
+         .TP\n.B ESCRITURAS_PROFILE\nConfig profile to use, as with \\fB\\-\\-profile\\fR\n\
+         .TP\n.BI ESCRITURAS_ SETTING\nAny setting, over the profile and the config: \\fBESCRITURAS_PROVIDER\\fR, \\fBESCRITURAS_MODEL\\fR, \\fBESCRITURAS_OLLAMA_URL\\fR, and so on\n\
+         .TP\n.B ESCRITURAS_PROMPT_FILE\nFile to read \\fBprompt_template\\fR from\n\
+         .TP\n.B EDITOR\nEditor for journal entries\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_cover_every_command() {
+        let (bash, zsh, fish, man) = (bash(), zsh(), fish(), man_page());
+        for command in COMMANDS {
+            assert!(bash.contains(&format!("        {}) flags=", command.name)), "bash: {}", command.name);
+            assert!(zsh.contains(&format!("        {})\n", command.name)), "zsh: {}", command.name);
+            assert!(fish.contains(&format!("-a {} -d", command.name)), "fish: {}", command.name);
+            assert!(man.contains(&format!(".SS \"{} ", roff(command.name))), "man: {}", command.name);
+        }
+        assert!(bash.contains("search:--format) COMPREPLY=($(compgen -W \"reference plain md attributed footnote ref-only\""));
+        assert_eq!(usage("compare"), "Usage: scriptures compare <reference> <reference> [--unified]");
+        assert!(help(Some("quiz")).contains("  --mode reference|blank|mixed  Which kind of question\n"));
+        assert!(help(Some("search")).contains("  -C, --context <n>"));
+        assert!(zsh.contains("'-C+[Verses to show around each match]:n: '"));
+        assert!(fish.contains("-s C -l context -x"));
+    }
+
+    fn parse(command: &str, args: &[&str]) -> Result<Args> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        Args::parse(find(command).unwrap(), &args)
+    }
+
+    #[test]
+    fn test_args_are_parsed_by_the_table() {
+        let args = parse("search", &["faith", "--limit", "5", "-C2", "--semantic", "--book=Alma", "hope"]).unwrap();
+        assert_eq!(args.rest, ["faith", "hope"]);
+        assert_eq!(args.value("limit").as_deref(), Some("5"));
+        assert_eq!(args.value("context").as_deref(), Some("2"));
+        assert_eq!(args.value("book").as_deref(), Some("Alma"));
+        assert!(args.has("semantic") && !args.has("hybrid"));
+        assert_eq!(args.value("volume"), None);
+        assert_eq!(parse("search", &["-C", "3", "x"]).unwrap().value("context").as_deref(), Some("3"));
+
+        // Everything after `--` is an argument, and `-` alone is one
+        assert_eq!(parse("batch", &["--", "--out"]).unwrap().rest, ["--out"]);
+        assert_eq!(parse("batch", &["-"]).unwrap().rest, ["-"]);
+
+        let error = parse("lookup", &["John 3:16", "--fromat", "md"]).unwrap_err().to_string();
+        assert!(error.starts_with("Unknown option --fromat\nUsage: scriptures lookup"), "{}", error);
+        assert_eq!(parse("search", &["faith", "--limit"]).unwrap_err().to_string(), "--limit needs a value");
+        assert_eq!(parse("compare", &["--unified=yes"]).unwrap_err().to_string(), "--unified doesn't take a value");
+        // Short spellings belong to one command
+        assert_eq!(parse("quiz", &["-C2"]).unwrap().rest, ["-C2"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "--limit isn't in the command's table entry")]
+    fn test_options_outside_the_table_panic() {
+        parse("lookup", &[]).unwrap().value("limit");
+    }
+}
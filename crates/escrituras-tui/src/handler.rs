@@ -1,9 +1,12 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::layout::Rect;
-use crate::app::{App, FlashcardPhase, FocusPane, FocusSubMode, InputMode, MemorizeMode, Screen, ScrollDirection, SearchFocus};
+use std::time::Instant;
+use crate::keymap::{Action, KeyContext};
+use crate::app::{App, FilterDropdown, FlashcardPhase, FocusPane, FocusSubMode, InputMode, MemorizeMode, PaletteAction, Screen, ScrollDirection, SearchFocus};
+use crate::textarea;
 use crate::tui::AppEvent;
-use escrituras_core::{ChatMessage, ChatRole, ClaudeClient, Config, OpenAIClient, Provider, Scripture};
+use escrituras_core::{card, links, Card, CardFormat, CardStyle, ChatMessage, ChatRole, Config, KeySource, Provider, Scripture, ScriptureRange};
 
 /// Convert a character index to a byte index for UTF-8 safe string operations
 fn char_to_byte_index(s: &str, char_idx: usize) -> usize {
@@ -14,12 +17,19 @@ fn char_to_byte_index(s: &str, char_idx: usize) -> usize {
 }
 
 pub async fn handle_event(app: &mut App, event: AppEvent) -> Result<()> {
+    // Input and resizes redraw; ticks only when they move something, and the
+    // mouse moving over the screen never does
+    if !matches!(event, AppEvent::Tick | AppEvent::Mouse(MouseEvent { kind: MouseEventKind::Moved, .. })) {
+        app.needs_redraw = true;
+    }
     match event {
         AppEvent::Key(key) => handle_key(app, key).await?,
         AppEvent::Mouse(mouse) => handle_mouse(app, mouse),
         AppEvent::Resize(_, _) => {}
+        AppEvent::Paste(text) => handle_paste(app, &text).await?,
         AppEvent::Tick => {
             app.tick_animation();
+            app.tick_read_aloud();
         }
     }
     Ok(())
@@ -28,7 +38,18 @@ pub async fn handle_event(app: &mut App, event: AppEvent) -> Result<()> {
 async fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
     // Global keys that work in any mode
     if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-        app.should_quit = true;
+        // Ctrl-C stops a generating answer before it quits the app
+        if app.query_task.is_some() {
+            app.cancel_query();
+        } else {
+            app.should_quit = true;
+        }
+        return Ok(());
+    }
+
+    // The verse-of-the-day splash takes the first key
+    if app.votd.is_some() {
+        handle_votd(app, key);
         return Ok(());
     }
 
@@ -40,41 +61,149 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Bracketed paste: a multi-line question goes into the AI input as-is; any
+/// other input gets the text typed in, one line
+async fn handle_paste(app: &mut App, text: &str) -> Result<()> {
+    let text = textarea::normalize_newlines(text);
+    if app.screen == Screen::Query && app.input_mode == InputMode::Editing && app.votd.is_none() {
+        let byte_pos = char_to_byte_index(&app.query_input, app.query_cursor);
+        app.query_input.insert_str(byte_pos, &text);
+        app.query_cursor += text.chars().count();
+        return Ok(());
+    }
+    for c in text.trim_end_matches('\n').chars() {
+        let c = if c == '\n' { ' ' } else { c };
+        handle_key(app, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)).await?;
+    }
+    Ok(())
+}
+
 async fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    // Go-to-reference prompt takes all keys while open
+    if app.show_goto_input {
+        handle_goto_input(app, key);
+        return Ok(());
+    }
+    // Tag prompt for a saved scripture
+    if app.tag_input.is_some() {
+        handle_tag_input(app, key);
+        return Ok(());
+    }
+
+    // Command palette
+    if app.show_command_palette {
+        handle_command_palette(app, key).await;
+        return Ok(());
+    }
+    // Search filter dropdown
+    if app.filter_dropdown.is_some() {
+        handle_filter_dropdown(app, key);
+        return Ok(());
+    }
+    // Copy-format menu
+    if app.show_copy_menu {
+        handle_copy_menu(app, key);
+        return Ok(());
+    }
+    // Word lookup popup
+    if app.word_lookup.is_some() {
+        handle_word_lookup(app, key);
+        return Ok(());
+    }
+    // Interlinear Strong's popup
+    if app.strongs_lookup.is_some() {
+        handle_strongs_lookup(app, key);
+        return Ok(());
+    }
+    // Help overlay
+    if app.show_help {
+        handle_help(app, key);
+        return Ok(());
+    }
+
+    // Global keys (skipped while a picker or typed attempt is capturing input)
+    let picker_open = app.show_api_key_input || app.show_provider_picker || app.show_model_picker;
+    let typing_attempt = app.focus_state
+        .as_ref()
+        .is_some_and(|s| s.flashcard_phase == FlashcardPhase::Typing);
+    if !picker_open && !typing_attempt {
+        match app.keymap.action(KeyContext::Global, &key) {
+            Some(Action::Palette) => {
+                app.open_command_palette();
+                return Ok(());
+            }
+            Some(Action::Help) => {
+                app.show_help = true;
+                app.help_scroll = 0;
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
     match app.screen {
         Screen::Browse => handle_browse_normal(app, key).await?,
         Screen::Search => handle_search_normal(app, key).await,
         Screen::Query => handle_query_normal(app, key).await?,
         Screen::Focus => handle_focus_normal(app, key),
+        Screen::Journal => handle_journal_normal(app, key),
+        Screen::Talks => handle_talks_normal(app, key),
+        Screen::ComeFollowMe => handle_come_follow_me_normal(app, key),
+        Screen::Topics => handle_topics_normal(app, key),
+        Screen::Reading => handle_reading_normal(app, key),
     }
     Ok(())
 }
 
 async fn handle_browse_normal(app: &mut App, key: KeyEvent) -> Result<()> {
-    match key.code {
-        // Quit
-        KeyCode::Char('q') => app.should_quit = true,
+    if app.visual_anchor.is_some() {
+        handle_visual(app, key);
+        return Ok(());
+    }
+
+    let Some(action) = app.keymap.action(KeyContext::Browse, &key) else {
+        return Ok(());
+    };
+
+    if app.split_focused && handle_split_pane(app, action) {
+        return Ok(());
+    }
+
+    match action {
+        Action::Quit => app.should_quit = true,
 
         // Navigation
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::Down => {
             if app.focus == FocusPane::Navigation {
                 app.nav_down();
+            } else if app.focus == FocusPane::References {
+                app.footnote_nav_down();
+            } else if app.focus == FocusPane::Related {
+                app.related_nav_down();
+            } else if app.focus == FocusPane::Outline {
+                app.outline_nav_down();
             } else if app.show_context_panel {
                 app.context_nav_down();
             } else {
                 app.select_next_verse();
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             if app.focus == FocusPane::Navigation {
                 app.nav_up();
+            } else if app.focus == FocusPane::References {
+                app.footnote_nav_up();
+            } else if app.focus == FocusPane::Related {
+                app.related_nav_up();
+            } else if app.focus == FocusPane::Outline {
+                app.outline_nav_up();
             } else if app.show_context_panel {
                 app.context_nav_up();
             } else {
                 app.select_prev_verse();
             }
         }
-        KeyCode::Char('g') => {
+        Action::Top => {
             if app.focus == FocusPane::Navigation {
                 app.nav_first();
             } else {
@@ -84,7 +213,7 @@ async fn handle_browse_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.last_scroll_direction = ScrollDirection::Up;
             }
         }
-        KeyCode::Char('G') => {
+        Action::Bottom => {
             if app.focus == FocusPane::Navigation {
                 app.nav_last();
             } else {
@@ -96,16 +225,35 @@ async fn handle_browse_normal(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
 
-        // Enter/Select
-        KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+        // Enter/Select (in the footnote or related pane, open that verse; in
+        // the outline, expand a section or go to it)
+        Action::Select => {
             if app.focus == FocusPane::Navigation {
                 app.nav_enter();
+            } else if app.focus == FocusPane::References {
+                app.jump_to_footnote();
+            } else if app.focus == FocusPane::Related {
+                app.jump_to_related();
+            } else if app.focus == FocusPane::Outline {
+                app.outline_select();
             }
         }
 
-        // Back
-        KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => {
-            if app.focus == FocusPane::Content {
+        // Back (after following a footnote, back to the verse it came from)
+        Action::Back => {
+            if app.focus == FocusPane::Outline {
+                if !app.outline_collapse() {
+                    app.focus = FocusPane::Content;
+                }
+            } else if matches!(app.focus, FocusPane::References | FocusPane::Related) {
+                app.focus = FocusPane::Content;
+            } else if app.focus == FocusPane::Content && app.pop_navigation_state() {
+                app.clear_selected_range();
+            } else if app.focus == FocusPane::Content {
+                // Going back to navigation brings a collapsed pane back
+                if app.layout.nav_hidden {
+                    app.toggle_nav_pane();
+                }
                 app.focus = FocusPane::Navigation;
             } else {
                 app.nav_back();
@@ -113,55 +261,101 @@ async fn handle_browse_normal(app: &mut App, key: KeyEvent) -> Result<()> {
         }
 
         // Tab to switch focus (Browse only has Nav and Content)
-        KeyCode::Tab => {
+        Action::CycleFocus => {
             app.focus = match app.focus {
                 FocusPane::Navigation => {
                     // Select topmost visible verse when entering content pane
                     app.ensure_verse_selected();
                     FocusPane::Content
                 }
-                FocusPane::Content | FocusPane::References | FocusPane::Input => FocusPane::Navigation,
+                // With a split open, Tab visits the second chapter before returning to navigation
+                FocusPane::Content if app.split.is_some() && !app.split_focused => {
+                    app.split_focused = true;
+                    FocusPane::Content
+                }
+                // Then the footnote pane, when the verse has footnotes
+                FocusPane::Content if app.footnote_pane_visible() && !app.footnote_rows().is_empty() => {
+                    app.split_focused = false;
+                    if app.footnote_state.selected().is_none() {
+                        app.footnote_state.select(Some(0));
+                    }
+                    FocusPane::References
+                }
+                // Then the related verses
+                FocusPane::Content | FocusPane::References if app.related_pane_visible() && !app.related.is_empty() => {
+                    app.split_focused = false;
+                    FocusPane::Related
+                }
+                // Then the outline
+                FocusPane::Content | FocusPane::References | FocusPane::Related if app.outline_pane_visible() => {
+                    app.split_focused = false;
+                    FocusPane::Outline
+                }
+                // Navigation is skipped while collapsed
+                FocusPane::Content | FocusPane::References | FocusPane::Related | FocusPane::Outline | FocusPane::Input
+                    if app.layout.nav_hidden =>
+                {
+                    app.split_focused = false;
+                    FocusPane::Content
+                }
+                FocusPane::Content | FocusPane::References | FocusPane::Related | FocusPane::Outline | FocusPane::Input => {
+                    app.split_focused = false;
+                    FocusPane::Navigation
+                }
             };
         }
+        Action::Split => app.toggle_split(),
+        Action::ViewParallel => app.view_parallel(),
+        Action::SwapPane => app.swap_split_focus(),
+
+        // Pane layout
+        Action::GrowPane => app.resize_pane(1),
+        Action::ShrinkPane => app.resize_pane(-1),
+        Action::ToggleNav => app.toggle_nav_pane(),
+        Action::ToggleCompact => app.toggle_compact(),
+        Action::ToggleRelated => app.toggle_related(),
+        Action::ToggleOutline => app.toggle_outline(),
+        Action::ToggleFootnotes => app.toggle_footnotes(),
+        Action::ReadingMode => app.enter_reading_mode(),
+
+        // Reading aloud
+        Action::ReadAloud => app.toggle_read_aloud(),
+        Action::StopReading => app.stop_read_aloud(),
+        Action::ReadNext => app.skip_read_aloud(1),
+        Action::ReadPrevious => app.skip_read_aloud(-1),
 
         // Half-page scroll
-        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.scroll_half_page_down();
-        }
-        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.scroll_half_page_up();
-        }
+        Action::HalfPageDown => app.scroll_half_page_down(),
+        Action::HalfPageUp => app.scroll_half_page_up(),
 
         // Verse actions (only when Content is focused)
-        KeyCode::Char('c') => {
-            if app.focus == FocusPane::Content {
-                if let Some(verse) = app.get_selected_verse() {
-                    let text = format!("{}\n{}", verse.verse_title, verse.scripture_text);
-                    copy_to_clipboard(&text);
-                }
-            }
-        }
-        KeyCode::Char('x') => {
+        Action::Copy => copy_selection(app),
+        Action::CopyMenu => open_copy_menu(app),
+        Action::OpenInBrowser => open_in_browser(app),
+        Action::QuoteCard => save_quote_card(app),
+        Action::Quote => quote_into_input(app),
+        Action::Save => {
             if app.focus == FocusPane::Content && !app.show_context_panel {
                 if let Some(verse) = app.get_selected_verse().cloned() {
-                    if !app.session_context.iter().any(|v| v.verse_title == verse.verse_title) {
-                        app.session_context.push(verse);
-                    }
+                    app.save_to_context(vec![verse]);
                 }
             }
         }
         // Toggle saved scriptures panel
-        KeyCode::Char('X') => {
+        Action::ToggleSaved => {
             app.show_context_panel = !app.show_context_panel;
             if app.show_context_panel && app.context_state.selected().is_none() && !app.session_context.is_empty() {
                 app.context_state.select(Some(0));
             }
         }
         // Remove from saved scriptures when panel is shown
-        KeyCode::Char('d') if app.focus == FocusPane::Content && app.show_context_panel => {
+        Action::Remove if app.focus == FocusPane::Content && app.show_context_panel => {
             app.remove_selected_context();
         }
-        KeyCode::Char('s') => {
+        Action::ClearSaved if app.show_context_panel => app.clear_saved_scriptures(),
+        Action::Tag if app.show_context_panel => app.open_tag_input(),
+        Action::FilterTag if app.show_context_panel => app.cycle_tag_filter(),
+        Action::SearchVerse => {
             if app.focus == FocusPane::Content {
                 if let Some(verse) = app.get_selected_verse() {
                     app.search_input = verse.verse_title.clone();
@@ -171,18 +365,33 @@ async fn handle_browse_normal(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
         // Enter focus mode
-        KeyCode::Char('f') => {
+        Action::FocusMode => {
             if app.focus == FocusPane::Content && app.get_selected_verse().is_some() {
                 app.enter_focus_mode();
             }
         }
+        // Start visual (multi-verse) selection
+        Action::Visual if app.focus == FocusPane::Content && !app.show_context_panel => {
+            app.start_visual();
+        }
+
+        // Go to reference
+        Action::Goto => app.open_goto_input(),
+        Action::Journal => start_journal_entry(app),
+        Action::Lookup => open_word_lookup(app),
+        Action::Interlinear => open_strongs_lookup(app),
+        Action::Talks => app.open_talks(),
+        Action::ComeFollowMe => app.open_come_follow_me(),
+        Action::Topics => app.open_topics(),
 
         // Screen switching
-        KeyCode::Char('/') => {
+        Action::Search => {
             app.screen = Screen::Search;
             app.input_mode = InputMode::Editing;
         }
-        KeyCode::Char('a') => {
+        // From the outline, ask the AI to outline the chapter instead
+        Action::AskAi if app.focus == FocusPane::Outline => app.generate_outline(),
+        Action::AskAi => {
             app.screen = Screen::Query;
             app.input_mode = InputMode::Editing;
         }
@@ -193,17 +402,22 @@ async fn handle_browse_normal(app: &mut App, key: KeyEvent) -> Result<()> {
 }
 
 async fn handle_search_normal(app: &mut App, key: KeyEvent) {
-    match key.code {
+    let Some(action) = app.keymap.action(KeyContext::Search, &key) else {
+        return;
+    };
+
+    match action {
         // Back to browse
-        KeyCode::Esc => {
+        Action::Exit => {
             app.screen = Screen::Browse;
             app.search_input.clear();
             app.search_results.clear();
+            app.cancel_search();
             app.search_focus = SearchFocus::Results;
         }
 
         // Tab cycles focus: Results -> Preview -> Input -> Results
-        KeyCode::Tab => {
+        Action::CycleFocus => {
             app.search_focus = match app.search_focus {
                 SearchFocus::Results => {
                     // When entering saved scriptures panel, select first item
@@ -228,14 +442,14 @@ async fn handle_search_normal(app: &mut App, key: KeyEvent) {
         }
 
         // Navigation - depends on focus and panel
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::Down => {
             if app.search_focus == SearchFocus::Preview && app.show_context_panel {
                 app.context_nav_down();
             } else {
                 app.search_nav_down();
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             if app.search_focus == SearchFocus::Preview && app.show_context_panel {
                 app.context_nav_up();
             } else {
@@ -244,7 +458,7 @@ async fn handle_search_normal(app: &mut App, key: KeyEvent) {
         }
 
         // Toggle saved scriptures panel
-        KeyCode::Char('X') => {
+        Action::ToggleSaved => {
             app.show_context_panel = !app.show_context_panel;
             if app.show_context_panel && app.context_state.selected().is_none()
                 && !app.session_context.is_empty()
@@ -254,39 +468,34 @@ async fn handle_search_normal(app: &mut App, key: KeyEvent) {
         }
 
         // Save scripture (when Preview focused, not showing saved panel)
-        KeyCode::Char('x') => {
+        Action::Save => {
             if app.search_focus == SearchFocus::Preview && !app.show_context_panel {
                 if let Some(i) = app.search_state.selected() {
-                    if let Some(scripture) = app.search_results.get(i).cloned() {
-                        if !app.session_context.iter().any(|v| v.verse_title == scripture.verse_title) {
-                            app.session_context.push(scripture);
-                        }
+                    if let Some(scripture) = app.search_results.get(i).map(|h| h.scripture.clone()) {
+                        app.save_to_context(vec![scripture]);
                     }
                 }
             }
         }
 
         // Remove from saved (when Saved panel focused)
-        KeyCode::Char('d') => {
+        Action::Remove => {
             if app.search_focus == SearchFocus::Preview && app.show_context_panel {
                 app.remove_selected_context();
             }
         }
+        Action::ClearSaved if app.show_context_panel => app.clear_saved_scriptures(),
+        Action::Tag if app.show_context_panel => app.open_tag_input(),
+        Action::FilterTag if app.show_context_panel => app.cycle_tag_filter(),
 
         // Copy scripture (when Preview focused)
-        KeyCode::Char('c') => {
-            if app.search_focus == SearchFocus::Preview && !app.show_context_panel {
-                if let Some(i) = app.search_state.selected() {
-                    if let Some(scripture) = app.search_results.get(i) {
-                        let text = format!("{}\n{}", scripture.verse_title, scripture.scripture_text);
-                        copy_to_clipboard(&text);
-                    }
-                }
-            }
-        }
+        Action::Copy => copy_selection(app),
+        Action::CopyMenu => open_copy_menu(app),
+        Action::OpenInBrowser => open_in_browser(app),
+        Action::QuoteCard => save_quote_card(app),
 
         // Enter focus mode (when Preview focused)
-        KeyCode::Char('f') => {
+        Action::FocusMode => {
             if app.search_focus == SearchFocus::Preview && !app.show_context_panel {
                 if app.search_state.selected().is_some() {
                     app.enter_focus_mode();
@@ -295,15 +504,22 @@ async fn handle_search_normal(app: &mut App, key: KeyEvent) {
         }
 
         // Edit search
-        KeyCode::Char('i') | KeyCode::Char('/') => {
+        Action::EditInput => {
             app.input_mode = InputMode::Editing;
         }
 
+        // Filter bar
+        Action::FilterVolume => app.open_filter_dropdown(FilterDropdown::Volume),
+        Action::FilterBook => app.open_filter_dropdown(FilterDropdown::Book),
+        Action::CycleSearchMode => app.cycle_search_mode(),
+        Action::MoreResults => app.change_search_limit(1),
+        Action::FewerResults => app.change_search_limit(-1),
+
         // View selected result (go to that chapter)
-        KeyCode::Enter => {
+        Action::Select => {
             if app.search_focus == SearchFocus::Results {
                 if let Some(i) = app.search_state.selected() {
-                    if let Some(scripture) = app.search_results.get(i).cloned() {
+                    if let Some(scripture) = app.search_results.get(i).map(|h| h.scripture.clone()) {
                         app.jump_to_scripture(&scripture);
                         app.screen = Screen::Browse;
                         app.focus = FocusPane::Content;
@@ -329,25 +545,27 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                 if !app.api_key_input.is_empty() {
                     if let Some(provider) = app.api_key_target_provider {
                         let mut config = Config::load().unwrap_or_else(|_| Config::new());
+                        let stored = config.store_api_key(provider, &app.api_key_input);
                         match provider {
-                            Provider::Claude => {
-                                config.claude_api_key = Some(app.api_key_input.clone());
-                                app.claude_client = Some(ClaudeClient::new(&app.api_key_input));
-                            }
-                            Provider::OpenAI => {
-                                config.openai_api_key = Some(app.api_key_input.clone());
-                                app.openai_client = Some(OpenAIClient::new(&app.api_key_input));
-                            }
+                            Provider::Claude => app.claude_client = Some(config.claude_client(&app.api_key_input)),
+                            Provider::OpenAI => app.openai_client = Some(config.openai_client(&app.api_key_input)),
                             Provider::Ollama => {}
                         }
+                        // Use the provider's saved model, or its first
+                        let model = config.saved_model(provider).or_else(|| app.get_models_for_provider(provider).into_iter().next());
                         config.provider = Some(provider.as_str().to_string());
-                        // Set default model for the new provider
-                        let models = app.get_models_for_provider(provider);
-                        if let Some(model) = models.first() {
-                            app.selected_model = model.clone();
-                            config.default_model = Some(model.clone());
+                        if let Some(model) = model {
+                            config.set_model(provider, &model);
+                            app.selected_model = model;
+                        }
+                        match (stored, config.save()) {
+                            (Err(e), _) => app.toasts.error(format!("Key not saved: {}", e)),
+                            (_, Err(e)) => app.toasts.error(format!("Could not save config: {}", e)),
+                            (Ok(KeySource::Keychain), Ok(())) => {
+                                app.toasts.success(format!("{} API key saved to the keychain", provider.display_name()))
+                            }
+                            (Ok(_), Ok(())) => app.toasts.success(format!("{} API key saved to config", provider.display_name())),
                         }
-                        let _ = config.save();
                         app.current_provider = provider;
                     }
                 }
@@ -406,10 +624,14 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                             app.current_provider = provider;
                             // Save provider to config
                             let mut config = Config::load().unwrap_or_else(|_| Config::new());
+                            let saved_model = config.saved_model(provider);
                             config.provider = Some(provider.as_str().to_string());
-                            let _ = config.save();
-                            // Set model for the new provider
+                            if let Err(e) = config.save() {
+                                app.toasts.error(format!("Could not save config: {}", e));
+                            }
+                            // Set model for the new provider, the one last used with it if any
                             let new_model = match provider {
+                                _ if saved_model.is_some() => saved_model,
                                 Provider::Ollama => {
                                     // Fetch Ollama models
                                     app.ollama.list_models().await.ok()
@@ -422,8 +644,11 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                             if let Some(model) = new_model {
                                 app.selected_model = model.clone();
                                 // Save auto-selected model to config
-                                let _ = Config::save_default_model(&model);
+                                if let Err(e) = Config::save_model(provider, &model) {
+                                    tracing::warn!("Couldn't save the model: {}", e);
+                                }
                             }
+                            app.toasts.info(format!("Switched to {}: {}", provider.display_name(), app.selected_model));
                         }
                         app.show_provider_picker = false;
                     }
@@ -439,6 +664,7 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
         match key.code {
             KeyCode::Esc => {
                 app.show_model_picker = false;
+                app.regenerate_after_model_pick = false;
             }
             KeyCode::Char('j') | KeyCode::Down => {
                 app.model_picker_nav_down();
@@ -448,15 +674,32 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
             }
             KeyCode::Enter => {
                 app.select_model();
+                if std::mem::take(&mut app.regenerate_after_model_pick) {
+                    regenerate_answer(app);
+                }
             }
             _ => {}
         }
         return Ok(());
     }
 
-    match key.code {
+    if app.visual_anchor.is_some() {
+        handle_visual(app, key);
+        return Ok(());
+    }
+
+    let Some(action) = app.keymap.action(KeyContext::Query, &key) else {
+        return Ok(());
+    };
+
+    match action {
+        // Cancel a generating answer
+        Action::Exit if app.query_task.is_some() => {
+            app.cancel_query();
+        }
+
         // Back to browse (or exit input, or pop navigation stack)
-        KeyCode::Esc => {
+        Action::Exit => {
             if app.focus == FocusPane::Input {
                 // Exit input mode, return to chat
                 app.input_mode = InputMode::Normal;
@@ -468,14 +711,14 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
         }
 
         // Go back in navigation stack
-        KeyCode::Char('b') | KeyCode::Backspace => {
+        Action::Back => {
             if app.focus != FocusPane::Input {
                 app.pop_navigation_state();
             }
         }
 
         // Tab cycles: Navigation -> Input -> Content -> References -> Navigation
-        KeyCode::Tab => {
+        Action::CycleFocus => {
             app.focus = match app.focus {
                 FocusPane::Navigation => FocusPane::Input,
                 FocusPane::Input => {
@@ -498,7 +741,7 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                         FocusPane::Navigation
                     }
                 }
-                FocusPane::References => FocusPane::Navigation,
+                FocusPane::References | FocusPane::Related | FocusPane::Outline => FocusPane::Navigation,
             };
 
             // Auto-enter editing mode when focusing input
@@ -510,7 +753,7 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
         }
 
         // Toggle context panel view
-        KeyCode::Char('X') => {
+        Action::ToggleSaved => {
             app.show_context_panel = !app.show_context_panel;
             // When entering context view, select first item if any
             if app.show_context_panel && app.context_state.selected().is_none() && !app.session_context.is_empty() {
@@ -519,7 +762,7 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
         }
 
         // Enter to jump to selected reference (when References focused)
-        KeyCode::Enter => {
+        Action::Select => {
             if app.focus == FocusPane::References {
                 if let Some(idx) = app.references_state.selected() {
                     if let Some(range) = app.extracted_references.get(idx).cloned() {
@@ -533,7 +776,7 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
         }
 
         // Scroll/navigate based on focus
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::Down => {
             match app.focus {
                 FocusPane::Navigation => app.query_scroll = app.query_scroll.saturating_add(1),
                 FocusPane::Content => {
@@ -545,9 +788,10 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                 }
                 FocusPane::References => app.references_nav_down(),
                 FocusPane::Input => {} // Handled by editing mode
+                FocusPane::Related | FocusPane::Outline => {} // Browse only
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             match app.focus {
                 FocusPane::Navigation => app.query_scroll = app.query_scroll.saturating_sub(1),
                 FocusPane::Content => {
@@ -559,30 +803,34 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                 }
                 FocusPane::References => app.references_nav_up(),
                 FocusPane::Input => {} // Handled by editing mode
+                FocusPane::Related | FocusPane::Outline => {} // Browse only
             }
         }
 
-        // Half-page scroll for content (must be before plain 'd' to match first)
-        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        // Half-page scroll for content
+        Action::HalfPageDown => {
             if app.focus == FocusPane::Content {
                 app.scroll_half_page_down();
             }
         }
-        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        Action::HalfPageUp => {
             if app.focus == FocusPane::Content {
                 app.scroll_half_page_up();
             }
         }
 
         // Remove from context when context panel is focused
-        KeyCode::Char('d') => {
+        Action::Remove => {
             if app.focus == FocusPane::Content && app.show_context_panel {
                 app.remove_selected_context();
             }
         }
+        Action::ClearSaved if app.show_context_panel => app.clear_saved_scriptures(),
+        Action::Tag if app.show_context_panel => app.open_tag_input(),
+        Action::FilterTag if app.show_context_panel => app.cycle_tag_filter(),
 
         // Jump to top/bottom of content
-        KeyCode::Char('g') => {
+        Action::Top => {
             if app.focus == FocusPane::Content {
                 app.selected_verse_idx = Some(0);
                 app.line_scroll = 0;
@@ -592,7 +840,7 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.query_scroll = 0;
             }
         }
-        KeyCode::Char('G') => {
+        Action::Bottom => {
             if app.focus == FocusPane::Content {
                 let last = app.cached_verses.len().saturating_sub(1);
                 app.selected_verse_idx = Some(last);
@@ -601,63 +849,61 @@ async fn handle_query_normal(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
 
-        // Verse actions (only when Content is focused)
-        KeyCode::Char('c') => {
-            if app.focus == FocusPane::Content {
-                if let Some(verse) = app.get_selected_verse() {
-                    let text = format!("{}\n{}", verse.verse_title, verse.scripture_text);
-                    copy_to_clipboard(&text);
-                }
-            }
+        // Conversation management
+        Action::NewConversation => app.new_conversation(),
+        Action::DeleteExchange => app.delete_last_exchange(),
+        Action::PrevConversation => app.prev_conversation(),
+        Action::NextConversation => app.next_conversation(),
+        Action::Regenerate => regenerate_answer(app),
+        Action::RegenerateWithModel if app.query_task.is_none() && !app.chat_messages.is_empty() => {
+            app.open_model_picker().await;
+            app.regenerate_after_model_pick = app.show_model_picker;
         }
-        KeyCode::Char('x') => {
+        Action::EditQuestion => edit_last_question(app),
+
+        // Verse actions (only when Content is focused)
+        Action::Copy => copy_selection(app),
+        Action::CopyMenu => open_copy_menu(app),
+        Action::OpenInBrowser => open_in_browser(app),
+        Action::QuoteCard => save_quote_card(app),
+        Action::Quote => quote_into_input(app),
+        Action::Save => {
             if app.focus == FocusPane::Content {
                 if let Some(verse) = app.get_selected_verse().cloned() {
-                    if !app.session_context.iter().any(|v| v.verse_title == verse.verse_title) {
-                        app.session_context.push(verse);
-                    }
+                    app.save_to_context(vec![verse]);
                 }
             }
         }
 
         // Enter focus mode (when Content is focused)
-        KeyCode::Char('f') => {
+        Action::FocusMode => {
             if app.focus == FocusPane::Content && app.get_selected_verse().is_some() {
                 app.enter_focus_mode();
             }
         }
+        // Start visual (multi-verse) selection
+        Action::Visual if app.focus == FocusPane::Content && !app.show_context_panel => {
+            app.start_visual();
+        }
+
+        // Go to reference
+        Action::Goto => app.open_goto_input(),
+        Action::Journal => start_journal_entry(app),
+        Action::Lookup => open_word_lookup(app),
+        Action::Interlinear => open_strongs_lookup(app),
+        Action::Talks => app.open_talks(),
+        Action::ComeFollowMe => app.open_come_follow_me(),
+        Action::Topics => app.open_topics(),
 
         // Open model picker
-        KeyCode::Char('M') => {
-            // Fetch available models based on current provider
-            let models = match app.current_provider {
-                Provider::Ollama => {
-                    app.ollama.list_models().await.unwrap_or_default()
-                }
-                Provider::Claude => ClaudeClient::list_models(),
-                Provider::OpenAI => OpenAIClient::list_models(),
-            };
-            app.available_models = models;
-            if !app.available_models.is_empty() {
-                // Select current model if in list, otherwise first
-                let current_idx = app.available_models
-                    .iter()
-                    .position(|m| m == &app.selected_model)
-                    .unwrap_or(0);
-                app.model_picker_state.select(Some(current_idx));
-                app.show_model_picker = true;
-            }
-        }
+        Action::ModelPicker => app.open_model_picker().await,
 
         // Open provider picker
-        KeyCode::Char('P') => {
-            let current_idx = Provider::all()
-                .iter()
-                .position(|p| *p == app.current_provider)
-                .unwrap_or(0);
-            app.provider_picker_state.select(Some(current_idx));
-            app.show_provider_picker = true;
-        }
+        Action::ProviderPicker => app.open_provider_picker(),
+
+        // Pane layout
+        Action::GrowPane => app.resize_pane(1),
+        Action::ShrinkPane => app.resize_pane(-1),
 
         _ => {}
     }
@@ -676,13 +922,21 @@ fn handle_focus_normal(app: &mut App, key: KeyEvent) {
         .map(|s| s.memorize_mode == MemorizeMode::Flashcard)
         .unwrap_or(false);
 
+    let is_first_letter = app.focus_state
+        .as_ref()
+        .map(|s| s.memorize_mode == MemorizeMode::FirstLetter)
+        .unwrap_or(false);
+
+    // Flashcard and first-letter modes share the typing/scoring flow
+    let supports_typing = app.focus_state.as_ref().is_some_and(|s| s.memorize_mode.supports_typing());
+
     let flashcard_phase = app.focus_state
         .as_ref()
         .map(|s| s.flashcard_phase)
         .unwrap_or(FlashcardPhase::Hidden);
 
     // Handle flashcard typing mode input
-    if in_memorize && is_flashcard && flashcard_phase == FlashcardPhase::Typing {
+    if in_memorize && supports_typing && flashcard_phase == FlashcardPhase::Typing {
         match key.code {
             KeyCode::Esc => {
                 app.focus_cancel_typing();
@@ -722,68 +976,79 @@ fn handle_focus_normal(app: &mut App, key: KeyEvent) {
         return;
     }
 
-    match key.code {
+    // Memorization keys take precedence while memorizing
+    let action = if in_memorize {
+        app.keymap.action(KeyContext::Memorize, &key)
+    } else {
+        None
+    };
+    let Some(action) = action.or_else(|| app.keymap.action(KeyContext::Focus, &key)) else {
+        return;
+    };
+
+    match action {
         // Exit Focus Mode
-        KeyCode::Esc | KeyCode::Char('q') => {
+        Action::Exit => {
             app.exit_focus_mode();
         }
 
         // Navigation (next/previous verse)
-        KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('n') => {
+        Action::Down => {
             app.focus_next_verse();
         }
-        KeyCode::Char('k') | KeyCode::Up | KeyCode::Char('p') => {
+        Action::Up => {
             app.focus_prev_verse();
         }
 
-        // Copy scripture
-        KeyCode::Char('c') => {
-            if let Some(verse) = app.get_focus_verse() {
-                let text = format!("{}\n{}", verse.verse_title, verse.scripture_text);
-                copy_to_clipboard(&text);
-            }
-        }
+        // Copy scripture (whole passage when studying a range)
+        Action::Copy => copy_selection(app),
+        Action::CopyMenu => open_copy_menu(app),
+        Action::OpenInBrowser => open_in_browser(app),
+        Action::QuoteCard => save_quote_card(app),
 
         // Save to context
-        KeyCode::Char('x') => {
-            if let Some(verse) = app.get_focus_verse().cloned() {
-                if !app.session_context.iter().any(|v| v.verse_title == verse.verse_title) {
-                    app.session_context.push(verse);
-                }
-            }
+        Action::Save => {
+            let passage = app.get_focus_passage().to_vec();
+            app.save_to_context(passage);
         }
+        Action::Journal => start_journal_entry(app),
+        Action::Lookup => open_word_lookup(app),
+        Action::Interlinear => open_strongs_lookup(app),
 
         // Toggle memorization mode
-        KeyCode::Char('m') => {
+        Action::ToggleMemorize => {
             app.focus_toggle_memorize();
         }
+        Action::Deck => app.focus_next_deck(),
 
         // Memorization-specific keys
-        KeyCode::Char('M') if in_memorize => {
-            // Cycle memorization type (Progressive/Flashcard)
+        Action::CycleMemorizeMode => {
+            // Cycle memorization type (Progressive/Flashcard/First Letter)
             app.focus_cycle_memorize_mode();
         }
-        KeyCode::Char('+') | KeyCode::Char('=') if in_memorize => {
-            // Increase difficulty
+        Action::Harder => {
             app.focus_increase_difficulty();
         }
-        KeyCode::Char('-') if in_memorize => {
-            // Decrease difficulty
+        Action::Easier => {
             app.focus_decrease_difficulty();
         }
 
-        // Flashcard-specific keys
-        KeyCode::Char('t') if in_memorize && is_flashcard && flashcard_phase == FlashcardPhase::Hidden => {
+        // Flashcard-specific keys (also used by first-letter mode)
+        Action::StartTyping if supports_typing && flashcard_phase == FlashcardPhase::Hidden => {
             // Start typing mode
             app.focus_start_typing();
         }
-        KeyCode::Char('r') if in_memorize && is_flashcard && flashcard_phase == FlashcardPhase::Revealed => {
+        Action::ResetCard if supports_typing && flashcard_phase == FlashcardPhase::Revealed => {
             // Reset flashcard to hidden
             app.focus_reset_flashcard();
         }
-        KeyCode::Enter | KeyCode::Char(' ') if in_memorize => {
-            // Reveal answer (flashcard) or advance difficulty (progressive)
-            if is_flashcard {
+        Action::Reveal => {
+            // Reveal answer (flashcard), peek at full text (first letter), or advance difficulty (progressive)
+            if is_first_letter {
+                if flashcard_phase == FlashcardPhase::Hidden {
+                    app.focus_toggle_peek();
+                }
+            } else if is_flashcard {
                 match flashcard_phase {
                     FlashcardPhase::Hidden => {
                         app.focus_reveal_flashcard();
@@ -818,6 +1083,7 @@ async fn handle_search_editing(app: &mut App, key: KeyEvent) {
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
             app.search_focus = SearchFocus::Results;  // Return focus to results
+            app.search_typed_at = None; // Drop a pending live search
         }
         KeyCode::Enter => {
             app.perform_search();
@@ -831,9 +1097,11 @@ async fn handle_search_editing(app: &mut App, key: KeyEvent) {
         }
         KeyCode::Backspace => {
             app.search_input.pop();
+            app.search_typed_at = Some(Instant::now());
         }
         KeyCode::Char(c) => {
             app.search_input.push(c);
+            app.search_typed_at = Some(Instant::now());
         }
         _ => {}
     }
@@ -845,105 +1113,46 @@ async fn handle_query_editing(app: &mut App, key: KeyEvent) -> Result<()> {
             app.input_mode = InputMode::Normal;
         }
         KeyCode::Enter => {
-            if !app.query_input.is_empty() && app.query_task.is_none() {
+            // Shift-Enter where the terminal reports it, Alt-Enter everywhere else
+            if key.modifiers.intersects(KeyModifiers::SHIFT | KeyModifiers::ALT) {
+                let byte_pos = char_to_byte_index(&app.query_input, app.query_cursor);
+                app.query_input.insert(byte_pos, '\n');
+                app.query_cursor += 1;
+            } else if !app.query_input.trim().is_empty() && app.query_task.is_none() {
                 // Add user message to chat history
-                let user_message = app.query_input.clone();
+                let user_message = std::mem::take(&mut app.query_input);
                 app.chat_messages.push(ChatMessage {
                     role: ChatRole::User,
                     content: user_message,
                 });
-
-                // Determine what the user is currently viewing
-                let current_reading = if !app.show_context_panel && !app.cached_verses.is_empty() {
-                    if let Some(range) = &app.selected_range {
-                        // User is viewing a specific reference range
-                        Some(range.display_title())
-                    } else if let Some(first_verse) = app.cached_verses.first() {
-                        // User is viewing a chapter
-                        Some(format!("{} {}", first_verse.book_title, first_verse.chapter_number))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                // Build prompt with chat history, session context, browsed chapters, and current reading
-                let prompt = build_query_prompt(
-                    &app.chat_messages,
-                    &app.session_context,
-                    &app.browsed_chapters,
-                    current_reading.as_deref(),
-                );
-
-                app.query_input.clear();
                 app.query_cursor = 0;
-                app.query_loading = true;
                 app.input_mode = InputMode::Normal;
-
-                // Scroll to bottom so "Thinking..." is visible
-                app.scroll_query_to_bottom();
-
-                // Spawn background task to query the AI provider
-                let model = app.selected_model.clone();
-                let provider = app.current_provider;
-
-                match provider {
-                    Provider::Ollama => {
-                        let ollama = app.ollama.clone();
-                        app.query_task = Some(tokio::spawn(async move {
-                            ollama.query(&model, &prompt).await
-                        }));
-                    }
-                    Provider::Claude => {
-                        if let Some(client) = app.claude_client.clone() {
-                            app.query_task = Some(tokio::spawn(async move {
-                                client.query(&model, &prompt).await
-                            }));
-                        } else {
-                            app.query_loading = false;
-                            app.chat_messages.push(ChatMessage {
-                                role: ChatRole::Assistant,
-                                content: "Error: Claude API key not configured. Press 'P' to set up.".to_string(),
-                            });
-                        }
-                    }
-                    Provider::OpenAI => {
-                        if let Some(client) = app.openai_client.clone() {
-                            app.query_task = Some(tokio::spawn(async move {
-                                client.query(&model, &prompt).await
-                            }));
-                        } else {
-                            app.query_loading = false;
-                            app.chat_messages.push(ChatMessage {
-                                role: ChatRole::Assistant,
-                                content: "Error: OpenAI API key not configured. Press 'P' to set up.".to_string(),
-                            });
-                        }
-                    }
-                }
+                send_query(app);
             }
         }
+        // Edit and move by whole graphemes so accents typed as combining marks go with their letter
         KeyCode::Backspace => {
-            if app.query_cursor > 0 {
-                app.query_cursor -= 1;
-                let byte_pos = char_to_byte_index(&app.query_input, app.query_cursor);
-                app.query_input.remove(byte_pos);
-            }
+            let start = textarea::prev_grapheme(&app.query_input, app.query_cursor);
+            let range = char_to_byte_index(&app.query_input, start)..char_to_byte_index(&app.query_input, app.query_cursor);
+            app.query_input.replace_range(range, "");
+            app.query_cursor = start;
         }
         KeyCode::Delete => {
-            let char_count = app.query_input.chars().count();
-            if app.query_cursor < char_count {
-                let byte_pos = char_to_byte_index(&app.query_input, app.query_cursor);
-                app.query_input.remove(byte_pos);
-            }
+            let end = textarea::next_grapheme(&app.query_input, app.query_cursor);
+            let range = char_to_byte_index(&app.query_input, app.query_cursor)..char_to_byte_index(&app.query_input, end);
+            app.query_input.replace_range(range, "");
         }
         KeyCode::Left => {
-            app.query_cursor = app.query_cursor.saturating_sub(1);
+            app.query_cursor = textarea::prev_grapheme(&app.query_input, app.query_cursor);
         }
         KeyCode::Right => {
-            let char_count = app.query_input.chars().count();
-            app.query_cursor = (app.query_cursor + 1).min(char_count);
+            app.query_cursor = textarea::next_grapheme(&app.query_input, app.query_cursor);
+        }
+        KeyCode::Up => {
+            app.query_cursor = textarea::move_vertical(&app.query_input, app.query_cursor, app.query_input_width, -1);
+        }
+        KeyCode::Down => {
+            app.query_cursor = textarea::move_vertical(&app.query_input, app.query_cursor, app.query_input_width, 1);
         }
         KeyCode::Home => {
             app.query_cursor = 0;
@@ -961,63 +1170,572 @@ async fn handle_query_editing(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
-fn build_query_prompt(
-    chat_history: &[ChatMessage],
-    context: &[Scripture],
-    browsed_chapters: &[(String, i32)],
-    current_reading: Option<&str>,
-) -> String {
-    let mut prompt = String::new();
+/// Ask the AI about the last question in the chat, streaming the answer in the background
+fn send_query(app: &mut App) {
+    // Build prompt with chat history, session context, browsed chapters, and current reading
+    let prompt = match app.chat_messages.split_last() {
+        Some((question, history)) => app.query_prompt(history, &question.content).text(),
+        None => return,
+    };
+    app.pending_passage.clear();
+
+    app.query_loading = true;
+
+    // Scroll to bottom so "Thinking..." is visible
+    app.scroll_query_to_bottom();
+
+    // Spawn background task to stream the answer from the AI provider
+    let model = app.selected_model.clone();
+    let provider = app.current_provider;
+    let (tokens, rx) = tokio::sync::mpsc::unbounded_channel();
+    app.query_tokens = Some(rx);
+    app.streaming_response.clear();
+    app.extracted_references.clear();
+    app.references_state.select(None);
+
+    match provider {
+        Provider::Ollama => {
+            let ollama = app.ollama.clone();
+            app.query_task = Some(tokio::spawn(async move {
+                ollama.query_stream(&model, &prompt, tokens).await
+            }));
+        }
+        Provider::Claude => {
+            if let Some(client) = app.claude_client.clone() {
+                app.query_task = Some(tokio::spawn(async move {
+                    client.query_stream(&model, &prompt, tokens).await
+                }));
+            } else {
+                app.query_loading = false;
+                app.chat_messages.push(ChatMessage {
+                    role: ChatRole::Assistant,
+                    content: "Error: Claude API key not configured. Press 'P' to set up.".to_string(),
+                });
+            }
+        }
+        Provider::OpenAI => {
+            if let Some(client) = app.openai_client.clone() {
+                app.query_task = Some(tokio::spawn(async move {
+                    client.query_stream(&model, &prompt, tokens).await
+                }));
+            } else {
+                app.query_loading = false;
+                app.chat_messages.push(ChatMessage {
+                    role: ChatRole::Assistant,
+                    content: "Error: OpenAI API key not configured. Press 'P' to set up.".to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Drop the last answer and ask the same question again
+fn regenerate_answer(app: &mut App) {
+    if app.query_task.is_some() {
+        return;
+    }
+    while app.chat_messages.last().is_some_and(|m| m.role == ChatRole::Assistant) {
+        app.chat_messages.pop();
+    }
+    if app.chat_messages.last().is_some() {
+        send_query(app);
+    }
+}
+
+/// Take the last question (and its answer) off the chat and put it back in the input
+fn edit_last_question(app: &mut App) {
+    if app.query_task.is_some() {
+        return;
+    }
+    let Some(idx) = app.chat_messages.iter().rposition(|m| m.role == ChatRole::User) else {
+        return;
+    };
+    let question = app.chat_messages.drain(idx..).next().map(|m| m.content).unwrap_or_default();
+    app.query_cursor = question.chars().count();
+    app.query_input = question;
+    app.extracted_references.clear();
+    app.references_state.select(None);
+    app.focus = FocusPane::Input;
+    app.input_mode = InputMode::Editing;
+}
+
+/// Volume/book dropdown in the search filter bar
+fn handle_filter_dropdown(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.filter_dropdown = None,
+        KeyCode::Char('j') | KeyCode::Down => app.filter_dropdown_nav_down(),
+        KeyCode::Char('k') | KeyCode::Up => app.filter_dropdown_nav_up(),
+        KeyCode::Enter => app.select_filter_option(),
+        _ => {}
+    }
+}
+
+/// Enter reads the verse's chapter, x saves it, anything else just dismisses the splash
+fn handle_votd(app: &mut App, key: KeyEvent) {
+    let Some(verse) = app.votd.take() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Enter => {
+            app.push_navigation_state();
+            app.jump_to_scripture(&verse);
+            app.screen = Screen::Browse;
+            app.focus = FocusPane::Content;
+            app.input_mode = InputMode::Normal;
+        }
+        KeyCode::Char('x') => app.save_to_context(vec![verse]),
+        _ => {}
+    }
+}
+
+fn handle_word_lookup(app: &mut App, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.word_lookup = None,
+        KeyCode::Char('d') if ctrl => {
+            if let Some(lookup) = &mut app.word_lookup {
+                lookup.scroll = lookup.scroll.saturating_add(10);
+            }
+        }
+        KeyCode::Char('u') if ctrl => {
+            if let Some(lookup) = &mut app.word_lookup {
+                lookup.scroll = lookup.scroll.saturating_sub(10);
+            }
+        }
+        KeyCode::Char('j') | KeyCode::Down => app.word_lookup_nav(true),
+        KeyCode::Char('k') | KeyCode::Up => app.word_lookup_nav(false),
+        _ => {}
+    }
+}
+
+fn handle_strongs_lookup(app: &mut App, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.strongs_lookup = None,
+        KeyCode::Char('d') if ctrl => {
+            if let Some(lookup) = &mut app.strongs_lookup {
+                lookup.scroll = lookup.scroll.saturating_add(10);
+            }
+        }
+        KeyCode::Char('u') if ctrl => {
+            if let Some(lookup) = &mut app.strongs_lookup {
+                lookup.scroll = lookup.scroll.saturating_sub(10);
+            }
+        }
+        KeyCode::Char('j') | KeyCode::Down => app.strongs_lookup_nav(true),
+        KeyCode::Char('k') | KeyCode::Up => app.strongs_lookup_nav(false),
+        _ => {}
+    }
+}
+
+/// Keys acting on the second chapter in split view; returns false for keys the main pane handles
+fn handle_split_pane(app: &mut App, action: Action) -> bool {
+    match action {
+        Action::Down => app.split_down(),
+        Action::Up => app.split_up(),
+        Action::Top => app.split_top(),
+        Action::Bottom => app.split_bottom(),
+        Action::Save => {
+            if let Some(verse) = app.get_split_verse().cloned() {
+                app.save_to_context(vec![verse]);
+            }
+        }
+        // Focus mode, visual selection, and verse search follow the main chapter
+        Action::FocusMode | Action::Visual | Action::SearchVerse => {}
+        Action::Back => app.split_focused = false,
+        _ => return false,
+    }
+    true
+}
+
+/// Keys while the help overlay is open
+fn handle_help(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => app.show_help = false,
+        KeyCode::Char('j') | KeyCode::Down => app.help_scroll = app.help_scroll.saturating_add(1),
+        KeyCode::Char('k') | KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.help_scroll = app.help_scroll.saturating_add(10);
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.help_scroll = app.help_scroll.saturating_sub(10);
+        }
+        KeyCode::PageDown => app.help_scroll = app.help_scroll.saturating_add(10),
+        KeyCode::PageUp => app.help_scroll = app.help_scroll.saturating_sub(10),
+        KeyCode::Char('g') | KeyCode::Home => app.help_scroll = 0,
+        _ => {}
+    }
+}
+
+/// Keys while the command palette is open
+async fn handle_command_palette(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.show_command_palette = false,
+        KeyCode::Down => app.palette_nav_down(),
+        KeyCode::Up => app.palette_nav_up(),
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => app.palette_nav_down(),
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => app.palette_nav_up(),
+        KeyCode::Enter => {
+            let selected = app.palette_state.selected().unwrap_or(0);
+            let action = app.palette_matches().get(selected).copied();
+            app.show_command_palette = false;
+            if let Some(action) = action {
+                run_palette_action(app, action).await;
+            }
+        }
+        KeyCode::Backspace => {
+            app.palette_input.pop();
+            app.palette_state.select(Some(0));
+        }
+        KeyCode::Char(c) => {
+            app.palette_input.push(c);
+            app.palette_state.select(Some(0));
+        }
+        _ => {}
+    }
+}
 
-    prompt.push_str("You are helping with LDS (Latter-day Saint) scripture study. ");
-    prompt.push_str("When answering, prioritize the Book of Mormon, Doctrine and Covenants, ");
-    prompt.push_str("and Pearl of Great Price alongside the Bible. Include specific verse citations.\n\n");
+/// Execute a command palette action
+async fn run_palette_action(app: &mut App, action: PaletteAction) {
+    match action {
+        PaletteAction::GotoReference => app.open_goto_input(),
+        PaletteAction::Search => {
+            app.screen = Screen::Search;
+            app.input_mode = InputMode::Editing;
+        }
+        PaletteAction::AskAi => {
+            app.screen = Screen::Query;
+            app.focus = FocusPane::Input;
+            app.input_mode = InputMode::Editing;
+        }
+        PaletteAction::Browse => {
+            app.screen = Screen::Browse;
+            app.focus = FocusPane::Navigation;
+        }
+        PaletteAction::ToggleSaved => {
+            app.show_context_panel = !app.show_context_panel;
+            if app.show_context_panel && app.context_state.selected().is_none() && !app.session_context.is_empty() {
+                app.context_state.select(Some(0));
+            }
+        }
+        PaletteAction::FocusVerse => {
+            app.ensure_verse_selected();
+            if app.get_selected_verse().is_some() {
+                app.enter_focus_mode();
+            }
+        }
+        PaletteAction::VisualSelect => {
+            if matches!(app.screen, Screen::Browse | Screen::Query) && !app.cached_verses.is_empty() {
+                app.focus = FocusPane::Content;
+                app.show_context_panel = false;
+                app.start_visual();
+            }
+        }
+        PaletteAction::CopyVerse => {
+            if let Some(verse) = app.get_selected_verse().cloned() {
+                copy_verses(app, &[verse]);
+            }
+        }
+        PaletteAction::CopyWithFormat => {
+            if let Some(verse) = app.get_selected_verse().cloned() {
+                app.open_copy_menu(vec![verse]);
+            }
+        }
+        PaletteAction::QuoteCard => save_quote_card(app),
+        PaletteAction::ClearSaved => app.clear_saved_scriptures(),
+        PaletteAction::NewJournalEntry => start_journal_entry(app),
+        PaletteAction::OpenJournal => app.open_journal(),
+        PaletteAction::OpenTalks => app.open_talks(),
+        PaletteAction::ComeFollowMe => app.open_come_follow_me(),
+        PaletteAction::Topics => app.open_topics(),
+        PaletteAction::ReadingMode => app.enter_reading_mode(),
+        PaletteAction::ReadAloud => app.toggle_read_aloud(),
+        PaletteAction::CompactLayout => app.toggle_compact(),
+        PaletteAction::RelatedVerses => app.toggle_related(),
+        PaletteAction::ViewParallel => app.view_parallel(),
+        PaletteAction::ChapterOutline => app.toggle_outline(),
+        PaletteAction::NewConversation => {
+            app.new_conversation();
+            app.screen = Screen::Query;
+            app.focus = FocusPane::Input;
+            app.input_mode = InputMode::Editing;
+        }
+        PaletteAction::SaveVerse => {
+            if let Some(verse) = app.get_selected_verse().cloned() {
+                app.save_to_context(vec![verse]);
+            }
+        }
+        // Pickers live on the AI screen
+        PaletteAction::SwitchProvider => {
+            app.screen = Screen::Query;
+            app.open_provider_picker();
+        }
+        PaletteAction::SwitchModel => {
+            app.screen = Screen::Query;
+            app.open_model_picker().await;
+        }
+        PaletteAction::Quit => app.should_quit = true,
+    }
+}
+
+/// Keys while the go-to-reference prompt is open
+fn handle_goto_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.show_goto_input = false;
+            app.goto_error = None;
+        }
+        KeyCode::Enter => app.submit_goto_input(),
+        KeyCode::Backspace if app.goto_cursor > 0 => {
+            app.goto_cursor -= 1;
+            let byte_pos = char_to_byte_index(&app.goto_input, app.goto_cursor);
+            app.goto_input.remove(byte_pos);
+            app.goto_error = None;
+        }
+        KeyCode::Left => {
+            app.goto_cursor = app.goto_cursor.saturating_sub(1);
+        }
+        KeyCode::Right => {
+            let char_count = app.goto_input.chars().count();
+            app.goto_cursor = (app.goto_cursor + 1).min(char_count);
+        }
+        KeyCode::Char(c) => {
+            let byte_pos = char_to_byte_index(&app.goto_input, app.goto_cursor);
+            app.goto_input.insert(byte_pos, c);
+            app.goto_cursor += 1;
+            app.goto_error = None;
+        }
+        _ => {}
+    }
+}
+
+/// Keys while the tag prompt for a saved scripture is open
+fn handle_tag_input(app: &mut App, key: KeyEvent) {
+    let Some(input) = app.tag_input.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.tag_input = None,
+        KeyCode::Enter => app.submit_tag_input(),
+        KeyCode::Backspace if app.tag_cursor > 0 => {
+            app.tag_cursor -= 1;
+            input.remove(char_to_byte_index(input, app.tag_cursor));
+        }
+        KeyCode::Left => app.tag_cursor = app.tag_cursor.saturating_sub(1),
+        KeyCode::Right => app.tag_cursor = (app.tag_cursor + 1).min(input.chars().count()),
+        KeyCode::Char(c) => {
+            input.insert(char_to_byte_index(input, app.tag_cursor), c);
+            app.tag_cursor += 1;
+        }
+        _ => {}
+    }
+}
 
-    // Include what the user is currently reading
-    if let Some(reading) = current_reading {
-        prompt.push_str(&format!("The user is currently reading {}.\n\n", reading));
+/// Keys on the Journal screen
+fn handle_journal_normal(app: &mut App, key: KeyEvent) {
+    let Some(action) = app.keymap.action(KeyContext::Journal, &key) else {
+        return;
+    };
+
+    match action {
+        Action::Exit => app.screen = Screen::Browse,
+        Action::Down => app.journal_nav_down(),
+        Action::Up => app.journal_nav_up(),
+        Action::Top => app.journal_select(0),
+        Action::Bottom => app.journal_select(usize::MAX),
+        Action::HalfPageDown => app.journal_scroll = app.journal_scroll.saturating_add(10),
+        Action::HalfPageUp => app.journal_scroll = app.journal_scroll.saturating_sub(10),
+        Action::Select => app.edit_selected_journal_entry(),
+        Action::Journal => app.new_journal_entry(&[]),
+        Action::Remove => app.delete_selected_journal_entry(),
+        _ => {}
     }
+}
 
-    // Include recently browsed chapters (lightweight context)
-    if !browsed_chapters.is_empty() {
-        prompt.push_str("Recently viewed chapters: ");
-        let chapters: Vec<String> = browsed_chapters.iter()
-            .take(10)  // Limit to last 10 chapters
-            .map(|(book, ch)| format!("{} {}", book, ch))
-            .collect();
-        prompt.push_str(&chapters.join(", "));
-        prompt.push_str("\n\n");
+/// Keys on the conference talks screen
+fn handle_talks_normal(app: &mut App, key: KeyEvent) {
+    if app.talks_view.editing {
+        handle_talk_search_input(app, key);
+        return;
     }
+    let Some(action) = app.keymap.action(KeyContext::Talks, &key) else {
+        return;
+    };
+
+    match action {
+        Action::Exit => app.screen = Screen::Browse,
+        Action::Down => app.talks_nav_down(),
+        Action::Up => app.talks_nav_up(),
+        Action::Top if app.talks_view.text_focused => app.talk_paragraph_select(0),
+        Action::Top => app.talks_list_select(0),
+        Action::Bottom if app.talks_view.text_focused => app.talk_paragraph_select(usize::MAX),
+        Action::Bottom => app.talks_list_select(usize::MAX),
+        Action::Select => app.talks_select(),
+        Action::Back => app.talks_back(),
+        Action::CycleFocus => {
+            let view = &mut app.talks_view;
+            view.text_focused = !view.text_focused && view.open_talk.is_some();
+        }
+        Action::EditInput => app.talks_view.editing = true,
+        Action::CycleSearchMode => app.toggle_talk_search_mode(),
+        Action::Save => app.save_talk_paragraph(),
+        _ => {}
+    }
+}
 
-    if !context.is_empty() {
-        prompt.push_str("Scripture Context:\n");
-        for verse in context.iter().take(20) {
-            prompt.push_str(&format!("{}: {}\n", verse.verse_title, verse.scripture_text));
+/// Typing in the talk search box; Enter searches, Esc stops editing
+fn handle_talk_search_input(app: &mut App, key: KeyEvent) {
+    let view = &mut app.talks_view;
+    match key.code {
+        KeyCode::Esc => view.editing = false,
+        KeyCode::Enter => {
+            view.editing = false;
+            app.perform_talk_search();
         }
-        prompt.push('\n');
+        KeyCode::Backspace => {
+            view.search_input.pop();
+        }
+        KeyCode::Char(c) => view.search_input.push(c),
+        _ => {}
+    }
+}
+
+/// Keys in reading mode
+fn handle_reading_normal(app: &mut App, key: KeyEvent) {
+    let Some(action) = app.keymap.action(KeyContext::Reading, &key) else {
+        return;
+    };
+    let half_page = (app.reading.visible_height / 2).max(1) as isize;
+
+    match action {
+        Action::Exit => app.exit_reading_mode(),
+        Action::Down => app.reading_scroll(1),
+        Action::Up => app.reading_scroll(-1),
+        Action::HalfPageDown => app.reading_scroll(half_page),
+        Action::HalfPageUp => app.reading_scroll(-half_page),
+        Action::GrowPane => app.resize_pane(1),
+        Action::ShrinkPane => app.resize_pane(-1),
+        _ => {}
+    }
+}
+
+/// Keys on the Come, Follow Me screen; digits 1-9 jump straight to a chapter
+fn handle_come_follow_me_normal(app: &mut App, key: KeyEvent) {
+    if let KeyCode::Char(c @ '1'..='9') = key.code {
+        app.cfm_jump(c as usize - '1' as usize);
+        return;
     }
+    let Some(action) = app.keymap.action(KeyContext::ComeFollowMe, &key) else {
+        return;
+    };
 
-    // Include chat history for context
-    if chat_history.len() > 1 {
-        prompt.push_str("Conversation so far:\n");
-        for msg in chat_history.iter().take(chat_history.len().saturating_sub(1)) {
-            match msg.role {
-                ChatRole::User => prompt.push_str(&format!("User: {}\n", msg.content)),
-                ChatRole::Assistant => prompt.push_str(&format!("Assistant: {}\n", msg.content)),
+    match action {
+        Action::Exit => app.screen = Screen::Browse,
+        Action::Down => app.cfm_select(app.cfm_state.selected().map_or(0, |i| i + 1)),
+        Action::Up => app.cfm_select(app.cfm_state.selected().unwrap_or(0).saturating_sub(1)),
+        Action::Select => {
+            if let Some(i) = app.cfm_state.selected() {
+                app.cfm_jump(i);
             }
         }
-        prompt.push('\n');
+        Action::MarkRead => app.cfm_toggle_done(),
+        Action::PrevWeek => app.cfm_change_week(-1),
+        Action::NextWeek => app.cfm_change_week(1),
+        Action::AskAi => prepare_lesson(app),
+        _ => {}
     }
+}
 
-    // Add the current question
-    if let Some(last_msg) = chat_history.last() {
-        prompt.push_str("Current question: ");
-        prompt.push_str(&last_msg.content);
+fn handle_topics_normal(app: &mut App, key: KeyEvent) {
+    let Some(action) = app.keymap.action(KeyContext::Topics, &key) else {
+        return;
+    };
+
+    match action {
+        Action::Exit => app.screen = Screen::Browse,
+        Action::Down => app.topics_nav(1),
+        Action::Up => app.topics_nav(-1),
+        Action::HalfPageDown => app.topics_nav(10),
+        Action::HalfPageUp => app.topics_nav(-10),
+        Action::Select => app.topics_select(),
+        Action::Back => app.topics_view.page_focused = false,
+        _ => {}
     }
+}
 
-    prompt.push_str("\n\nPlease provide specific scripture references in your answer.");
+/// Ask the AI for a lesson plan on the Come, Follow Me week being shown
+fn prepare_lesson(app: &mut App) {
+    if app.query_task.is_some() {
+        app.toasts.info("Wait for the current answer to finish");
+        return;
+    }
+    let Some(prompt) = app.cfm_lesson_prompt() else {
+        return;
+    };
+    app.chat_messages.push(ChatMessage {
+        role: ChatRole::User,
+        content: prompt,
+    });
+    app.screen = Screen::Query;
+    app.focus = FocusPane::Input;
+    app.input_mode = InputMode::Normal;
+    send_query(app);
+}
 
-    prompt
+/// Keys while a visual selection is active in the content pane
+fn handle_visual(app: &mut App, key: KeyEvent) {
+    let Some(action) = app.keymap.action(KeyContext::Visual, &key) else {
+        return;
+    };
+
+    match action {
+        Action::Exit => app.cancel_visual(),
+        Action::Down => app.visual_down(),
+        Action::Up => app.visual_up(),
+        Action::Top => {
+            app.selected_verse_idx = Some(0);
+            app.last_scroll_direction = ScrollDirection::Up;
+        }
+        Action::Bottom => {
+            app.selected_verse_idx = Some(app.cached_verses.len().saturating_sub(1));
+            app.last_scroll_direction = ScrollDirection::Down;
+        }
+        // Copy the selection as one block
+        Action::Copy => {
+            let verses = app.get_visual_verses().to_vec();
+            copy_verses(app, &verses);
+            app.cancel_visual();
+        }
+        Action::CopyMenu => {
+            open_copy_menu(app);
+            app.cancel_visual();
+        }
+        Action::OpenInBrowser => {
+            open_in_browser(app);
+            app.cancel_visual();
+        }
+        Action::QuoteCard => {
+            save_quote_card(app);
+            app.cancel_visual();
+        }
+        // Save every selected verse and end the selection
+        Action::Save => app.save_visual_selection(),
+        Action::Quote => quote_into_input(app),
+        Action::Journal => {
+            start_journal_entry(app);
+            app.cancel_visual();
+        }
+        // Attach the selection to the next AI question
+        Action::AskAi => {
+            app.pending_passage = app.get_visual_verses().to_vec();
+            app.cancel_visual();
+            app.screen = Screen::Query;
+            app.focus = FocusPane::Input;
+            app.input_mode = InputMode::Editing;
+            app.query_cursor = app.query_input.chars().count();
+        }
+        _ => {}
+    }
 }
 
 /// Check if a point is within a rectangle
@@ -1064,6 +1782,11 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent) {
                     // Navigate to next verse on scroll down
                     app.focus_next_verse();
                 }
+                Screen::Journal => app.journal_scroll = app.journal_scroll.saturating_add(3),
+                Screen::Talks => app.talks_nav_down(),
+                Screen::ComeFollowMe => app.cfm_select(app.cfm_state.selected().map_or(0, |i| i + 1)),
+                Screen::Topics => app.topics_nav(1),
+                Screen::Reading => app.reading_scroll(3),
             }
         }
         MouseEventKind::ScrollUp => {
@@ -1095,24 +1818,224 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent) {
                     // Navigate to previous verse on scroll up
                     app.focus_prev_verse();
                 }
+                Screen::Journal => app.journal_scroll = app.journal_scroll.saturating_sub(3),
+                Screen::Talks => app.talks_nav_up(),
+                Screen::ComeFollowMe => app.cfm_select(app.cfm_state.selected().unwrap_or(0).saturating_sub(1)),
+                Screen::Topics => app.topics_nav(-1),
+                Screen::Reading => app.reading_scroll(-3),
             }
         }
         _ => {}
     }
 }
 
-fn copy_to_clipboard(text: &str) {
+/// Verses the copy keys act on in the current screen and focus
+fn copyable_verses(app: &App) -> Vec<Scripture> {
+    if app.visual_anchor.is_some() {
+        return app.get_visual_verses().to_vec();
+    }
+    match app.screen {
+        Screen::Focus => app.get_focus_passage().to_vec(),
+        Screen::Search if app.search_focus == SearchFocus::Preview && !app.show_context_panel => app
+            .search_state
+            .selected()
+            .and_then(|i| app.search_results.get(i))
+            .map(|h| h.scripture.clone())
+            .into_iter()
+            .collect(),
+        Screen::Browse if app.split_focused => app.get_split_verse().cloned().into_iter().collect(),
+        Screen::Browse | Screen::Query if app.focus == FocusPane::Content => {
+            app.get_selected_verse().cloned().into_iter().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Look up the words of the verses the copy keys would copy
+fn open_word_lookup(app: &mut App) {
+    let verses = copyable_verses(app);
+    app.open_word_lookup(&verses);
+}
+
+/// Show the Hebrew and Greek behind the verses the copy keys would copy
+fn open_strongs_lookup(app: &mut App) {
+    let verses = copyable_verses(app);
+    app.open_strongs_lookup(&verses);
+}
+
+/// Open a new journal entry in $EDITOR quoting the verses the copy keys would copy
+fn start_journal_entry(app: &mut App) {
+    let verses = copyable_verses(app);
+    app.new_journal_entry(&verses);
+}
+
+/// Insert the selected verse or visual range into the AI input as a quotation
+fn quote_into_input(app: &mut App) {
+    let verses = copyable_verses(app);
+    if verses.is_empty() {
+        return;
+    }
+    let reference = passage_reference(&verses);
+    let text = verses.iter().map(|v| v.scripture_text.as_str()).collect::<Vec<_>>().join(" ");
+
+    let mut quote = format!("\"{}\" ({}) ", text, reference);
+    let before = app.query_input.chars().nth(app.query_cursor.wrapping_sub(1));
+    if before.is_some_and(|c| !c.is_whitespace()) {
+        quote.insert(0, ' ');
+    }
+    let byte_pos = char_to_byte_index(&app.query_input, app.query_cursor);
+    app.query_input.insert_str(byte_pos, &quote);
+    app.query_cursor += quote.chars().count();
+
+    app.cancel_visual();
+    app.screen = Screen::Query;
+    app.focus = FocusPane::Input;
+    app.input_mode = InputMode::Editing;
+}
+
+/// "Alma 32:21" for one verse, "Alma 32:21-23" for a passage
+fn passage_reference(verses: &[Scripture]) -> String {
+    match verses {
+        [] => String::new(),
+        [verse] => verse.verse_title.clone(),
+        [first, .., last] => format!(
+            "{} {}:{}-{}",
+            first.book_title, first.chapter_number, first.verse_number, last.verse_number
+        ),
+    }
+}
+
+fn copy_selection(app: &mut App) {
+    let verses = copyable_verses(app);
+    copy_verses(app, &verses);
+}
+
+/// Copy verses in the configured format
+fn copy_verses(app: &mut App, verses: &[Scripture]) {
+    if verses.is_empty() {
+        return;
+    }
+    match copy_to_clipboard(&app.copy_format.format_in(verses, &app.gospel_library_language)) {
+        Ok(()) => app.toasts.success(format!("Copied {}", passage_reference(verses))),
+        Err(e) => app.toasts.error(format!("Copy failed: {}", e)),
+    }
+}
+
+/// Open the verses the copy keys would copy in the Gospel Library
+fn open_in_browser(app: &mut App) {
+    let verses = copyable_verses(app);
+    let (Some(first), Some(last)) = (verses.first(), verses.last()) else {
+        return;
+    };
+    let range = ScriptureRange { end_verse: last.verse_number, ..ScriptureRange::of_verse(first) };
+    let Some(url) = links::gospel_library_url(&app.scripture_db, &range, &app.gospel_library_language) else {
+        app.toasts.info(format!("{} isn't in the Gospel Library", passage_reference(&verses)));
+        return;
+    };
+    match open_url(&url) {
+        Ok(()) => app.toasts.success(format!("Opened {}", passage_reference(&verses))),
+        Err(e) => app.toasts.error(format!("Couldn't open browser: {}", e)),
+    }
+}
+
+/// Save the verses the copy keys would copy as a PNG quote card in the
+/// pictures folder, styled by the card settings
+fn save_quote_card(app: &mut App) {
+    let verses = copyable_verses(app);
+    let Some(card) = Card::of_verses(&verses) else {
+        return;
+    };
+    let Some(dir) = card::default_dir() else {
+        app.toasts.error("Couldn't find a folder to save the card in");
+        return;
+    };
+    let path = dir.join(card.file_name(CardFormat::Png));
+    let config = Config::load().unwrap_or_else(|_| Config::new());
+    match CardStyle::from_config(&config).and_then(|style| card.save(&path, &style)) {
+        Ok(()) => app.toasts.success(format!("Saved card to {}", path.display())),
+        Err(e) => app.toasts.error(format!("Couldn't save card: {}", e)),
+    }
+}
+
+fn open_copy_menu(app: &mut App) {
+    let verses = copyable_verses(app);
+    if !verses.is_empty() {
+        app.open_copy_menu(verses);
+    }
+}
+
+/// Keys while the copy-format menu is open
+fn handle_copy_menu(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.show_copy_menu = false,
+        KeyCode::Char('j') | KeyCode::Down => app.copy_menu_nav_down(),
+        KeyCode::Char('k') | KeyCode::Up => app.copy_menu_nav_up(),
+        // Copy in the highlighted format and remember it as the default
+        KeyCode::Enter => {
+            let format = app.selected_copy_format();
+            app.copy_format = format;
+            if let Err(e) = Config::save_copy_format(format.name()) {
+                app.toasts.error(format!("Could not save config: {}", e));
+            }
+            let verses = std::mem::take(&mut app.copy_menu_verses);
+            copy_verses(app, &verses);
+            app.show_copy_menu = false;
+        }
+        _ => {}
+    }
+}
+
+/// Open a URL in the default browser
+fn open_url(url: &str) -> Result<()> {
+    use std::process::{Command, Stdio};
+
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // Not `cmd /C start`: cmd would split the URL at its `&`s
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(url).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()?;
+    Ok(())
+}
+
+/// Pipe text to the first clipboard tool available (macOS, Wayland, X11, Windows/WSL)
+fn copy_to_clipboard(text: &str) -> Result<()> {
     use std::process::{Command, Stdio};
     use std::io::Write;
 
-    if let Ok(mut child) = Command::new("pbcopy")
-        .stdin(Stdio::piped())
-        .spawn()
-    {
+    const TOOLS: &[(&str, &[&str])] = &[
+        ("pbcopy", &[]),
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+        ("clip.exe", &[]),
+    ];
+
+    for (program, args) in TOOLS {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
         if let Some(mut stdin) = child.stdin.take() {
-            let _ = stdin.write_all(text.as_bytes());
+            stdin.write_all(text.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if status.success() {
+            return Ok(());
         }
+        anyhow::bail!("{} exited with {}", program, status);
     }
+    anyhow::bail!("no clipboard tool found (install pbcopy, wl-copy, xclip, or xsel)")
 }
 
 #[cfg(test)]
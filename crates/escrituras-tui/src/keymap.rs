@@ -0,0 +1,819 @@
+//! Keybinding table
+//!
+//! Every remappable normal-mode key is declared once in `DEFAULT_BINDINGS`.
+//! The handler resolves key presses to `Action`s through the `Keymap`, and the
+//! help overlay and footer hints are rendered from the same table, so the three
+//! can't drift apart.
+//!
+//! Users can override any binding in `keybindings.toml` next to the config:
+//!
+//! ```toml
+//! [browse]
+//! save = "s"
+//! search_verse = ["S", "Ctrl-f"]
+//! ```
+
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a binding applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,   // Any screen in normal mode
+    Browse,
+    Search,
+    Query,
+    Visual,   // Visual (multi-verse) selection in the content pane
+    Focus,
+    Memorize, // Focus mode with memorization on
+    Journal,
+    Talks,
+    ComeFollowMe,
+    Topics,
+    Reading,
+}
+
+impl KeyContext {
+    /// Contexts in the order they appear in the help overlay
+    pub fn all() -> &'static [KeyContext] {
+        &[
+            KeyContext::Global,
+            KeyContext::Browse,
+            KeyContext::Search,
+            KeyContext::Query,
+            KeyContext::Visual,
+            KeyContext::Focus,
+            KeyContext::Memorize,
+            KeyContext::Journal,
+            KeyContext::Talks,
+            KeyContext::ComeFollowMe,
+            KeyContext::Topics,
+            KeyContext::Reading,
+        ]
+    }
+
+    /// Table name in keybindings.toml
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "global",
+            KeyContext::Browse => "browse",
+            KeyContext::Search => "search",
+            KeyContext::Query => "query",
+            KeyContext::Visual => "visual",
+            KeyContext::Focus => "focus",
+            KeyContext::Memorize => "memorize",
+            KeyContext::Journal => "journal",
+            KeyContext::Talks => "talks",
+            KeyContext::ComeFollowMe => "come_follow_me",
+            KeyContext::Topics => "topics",
+            KeyContext::Reading => "reading",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "Global",
+            KeyContext::Browse => "Browse",
+            KeyContext::Search => "Search",
+            KeyContext::Query => "AI Mode",
+            KeyContext::Visual => "Visual Selection",
+            KeyContext::Focus => "Focus Mode",
+            KeyContext::Memorize => "Memorization",
+            KeyContext::Journal => "Journal",
+            KeyContext::Talks => "Conference Talks",
+            KeyContext::ComeFollowMe => "Come, Follow Me",
+            KeyContext::Topics => "Study Topics",
+            KeyContext::Reading => "Reading Mode",
+        }
+    }
+}
+
+/// Named actions the handler dispatches on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Exit,
+    Down,
+    Up,
+    Top,
+    Bottom,
+    HalfPageDown,
+    HalfPageUp,
+    Select,
+    Back,
+    CycleFocus,
+    Copy,
+    CopyMenu,
+    OpenInBrowser,
+    QuoteCard,
+    Quote,
+    Save,
+    ToggleSaved,
+    Remove,
+    ClearSaved,
+    Tag,
+    FilterTag,
+    Journal,
+    Talks,
+    ComeFollowMe,
+    Topics,
+    MarkRead,
+    PrevWeek,
+    NextWeek,
+    Lookup,
+    Interlinear,
+    ToggleFootnotes,
+    SearchVerse,
+    FocusMode,
+    ReadingMode,
+    Visual,
+    Split,
+    ViewParallel,
+    SwapPane,
+    GrowPane,
+    ShrinkPane,
+    ToggleNav,
+    ToggleCompact,
+    ToggleRelated,
+    ToggleOutline,
+    Goto,
+    Search,
+    AskAi,
+    EditInput,
+    FilterVolume,
+    FilterBook,
+    CycleSearchMode,
+    MoreResults,
+    FewerResults,
+    NewConversation,
+    DeleteExchange,
+    PrevConversation,
+    NextConversation,
+    Regenerate,
+    RegenerateWithModel,
+    EditQuestion,
+    ModelPicker,
+    ProviderPicker,
+    Palette,
+    Help,
+    ToggleMemorize,
+    Deck,
+    ReadAloud,
+    StopReading,
+    ReadNext,
+    ReadPrevious,
+    CycleMemorizeMode,
+    Harder,
+    Easier,
+    StartTyping,
+    ResetCard,
+    Reveal,
+}
+
+impl Action {
+    const ALL: &'static [Action] = &[
+        Action::Quit,
+        Action::Exit,
+        Action::Down,
+        Action::Up,
+        Action::Top,
+        Action::Bottom,
+        Action::HalfPageDown,
+        Action::HalfPageUp,
+        Action::Select,
+        Action::Back,
+        Action::CycleFocus,
+        Action::Copy,
+        Action::CopyMenu,
+        Action::OpenInBrowser,
+        Action::QuoteCard,
+        Action::Quote,
+        Action::Save,
+        Action::ToggleSaved,
+        Action::Remove,
+        Action::ClearSaved,
+        Action::Tag,
+        Action::FilterTag,
+        Action::Journal,
+        Action::Talks,
+        Action::ComeFollowMe,
+        Action::Topics,
+        Action::MarkRead,
+        Action::PrevWeek,
+        Action::NextWeek,
+        Action::Lookup,
+        Action::Interlinear,
+        Action::ToggleFootnotes,
+        Action::SearchVerse,
+        Action::FocusMode,
+        Action::ReadingMode,
+        Action::Visual,
+        Action::Split,
+        Action::ViewParallel,
+        Action::SwapPane,
+        Action::GrowPane,
+        Action::ShrinkPane,
+        Action::ToggleNav,
+        Action::ToggleCompact,
+        Action::ToggleRelated,
+        Action::ToggleOutline,
+        Action::Goto,
+        Action::Search,
+        Action::AskAi,
+        Action::EditInput,
+        Action::FilterVolume,
+        Action::FilterBook,
+        Action::CycleSearchMode,
+        Action::MoreResults,
+        Action::FewerResults,
+        Action::NewConversation,
+        Action::DeleteExchange,
+        Action::PrevConversation,
+        Action::NextConversation,
+        Action::Regenerate,
+        Action::RegenerateWithModel,
+        Action::EditQuestion,
+        Action::ModelPicker,
+        Action::ProviderPicker,
+        Action::Palette,
+        Action::Help,
+        Action::ToggleMemorize,
+        Action::Deck,
+        Action::ReadAloud,
+        Action::StopReading,
+        Action::ReadNext,
+        Action::ReadPrevious,
+        Action::CycleMemorizeMode,
+        Action::Harder,
+        Action::Easier,
+        Action::StartTyping,
+        Action::ResetCard,
+        Action::Reveal,
+    ];
+
+    /// Key name in keybindings.toml
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Exit => "exit",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::HalfPageDown => "half_page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::Select => "select",
+            Action::Back => "back",
+            Action::CycleFocus => "cycle_focus",
+            Action::Copy => "copy",
+            Action::CopyMenu => "copy_menu",
+            Action::OpenInBrowser => "open_in_browser",
+            Action::QuoteCard => "quote_card",
+            Action::Quote => "quote",
+            Action::Save => "save",
+            Action::ToggleSaved => "toggle_saved",
+            Action::Remove => "remove",
+            Action::ClearSaved => "clear_saved",
+            Action::Tag => "tag",
+            Action::FilterTag => "filter_tag",
+            Action::Journal => "journal",
+            Action::Talks => "talks",
+            Action::ComeFollowMe => "come_follow_me",
+            Action::Topics => "topics",
+            Action::MarkRead => "mark_read",
+            Action::PrevWeek => "prev_week",
+            Action::NextWeek => "next_week",
+            Action::Lookup => "lookup",
+            Action::Interlinear => "interlinear",
+            Action::ToggleFootnotes => "toggle_footnotes",
+            Action::SearchVerse => "search_verse",
+            Action::FocusMode => "focus_mode",
+            Action::ReadingMode => "reading_mode",
+            Action::Visual => "visual",
+            Action::Split => "split",
+            Action::ViewParallel => "view_parallel",
+            Action::SwapPane => "swap_pane",
+            Action::GrowPane => "grow_pane",
+            Action::ShrinkPane => "shrink_pane",
+            Action::ToggleNav => "toggle_nav",
+            Action::ToggleCompact => "toggle_compact",
+            Action::ToggleRelated => "toggle_related",
+            Action::ToggleOutline => "toggle_outline",
+            Action::Goto => "goto",
+            Action::Search => "search",
+            Action::AskAi => "ask_ai",
+            Action::EditInput => "edit_input",
+            Action::FilterVolume => "filter_volume",
+            Action::FilterBook => "filter_book",
+            Action::CycleSearchMode => "cycle_search_mode",
+            Action::MoreResults => "more_results",
+            Action::FewerResults => "fewer_results",
+            Action::NewConversation => "new_conversation",
+            Action::DeleteExchange => "delete_exchange",
+            Action::PrevConversation => "prev_conversation",
+            Action::NextConversation => "next_conversation",
+            Action::Regenerate => "regenerate",
+            Action::RegenerateWithModel => "regenerate_with_model",
+            Action::EditQuestion => "edit_question",
+            Action::ModelPicker => "model_picker",
+            Action::ProviderPicker => "provider_picker",
+            Action::Palette => "palette",
+            Action::Help => "help",
+            Action::ToggleMemorize => "toggle_memorize",
+            Action::Deck => "deck",
+            Action::ReadAloud => "read_aloud",
+            Action::StopReading => "stop_reading",
+            Action::ReadNext => "read_next",
+            Action::ReadPrevious => "read_previous",
+            Action::CycleMemorizeMode => "cycle_memorize_mode",
+            Action::Harder => "harder",
+            Action::Easier => "easier",
+            Action::StartTyping => "start_typing",
+            Action::ResetCard => "reset_card",
+            Action::Reveal => "reveal",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Self::ALL.iter().copied().find(|a| a.name() == name)
+    }
+}
+
+/// A single key press (code plus Ctrl/Alt; Shift is implied by the character)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    /// Parse a key like "j", "G", "Enter", "Space", "Ctrl-d", or "Alt-x"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        loop {
+            if let Some(r) = rest.strip_prefix("Ctrl-").or_else(|| rest.strip_prefix("ctrl-")) {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("Alt-").or_else(|| rest.strip_prefix("alt-")) {
+                modifiers |= KeyModifiers::ALT;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+
+        let code = match rest {
+            "Enter" => KeyCode::Enter,
+            "Esc" => KeyCode::Esc,
+            "Tab" => KeyCode::Tab,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Space" => KeyCode::Char(' '),
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => {
+                        let n = rest.strip_prefix('F')?.parse::<u8>().ok()?;
+                        KeyCode::F(n)
+                    }
+                }
+            }
+        };
+
+        Some(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let mods = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        key.code == self.code && mods == self.modifiers
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// A binding of one or more keys to an action in a context
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub context: KeyContext,
+    pub action: Action,
+    pub keys: Vec<KeyPress>,
+    pub description: &'static str,
+}
+
+/// (context, action, keys, description) for every remappable key
+const DEFAULT_BINDINGS: &[(KeyContext, Action, &[&str], &str)] = &[
+    (KeyContext::Global, Action::Palette, &["Ctrl-p"], "Command palette"),
+    (KeyContext::Global, Action::Help, &["?"], "Toggle this help"),
+
+    (KeyContext::Browse, Action::Down, &["j", "Down"], "Move down"),
+    (KeyContext::Browse, Action::Up, &["k", "Up"], "Move up"),
+    (KeyContext::Browse, Action::Top, &["g"], "Jump to first item"),
+    (KeyContext::Browse, Action::Bottom, &["G"], "Jump to last item"),
+    (KeyContext::Browse, Action::HalfPageDown, &["Ctrl-d"], "Half page down"),
+    (KeyContext::Browse, Action::HalfPageUp, &["Ctrl-u"], "Half page up"),
+    (KeyContext::Browse, Action::Select, &["Enter", "l", "Right"], "Select / expand"),
+    (KeyContext::Browse, Action::Back, &["h", "Left", "Backspace"], "Go back"),
+    (KeyContext::Browse, Action::CycleFocus, &["Tab"], "Switch panel"),
+    (KeyContext::Browse, Action::Copy, &["c"], "Copy verse"),
+    (KeyContext::Browse, Action::CopyMenu, &["C"], "Copy as... (choose format)"),
+    (KeyContext::Browse, Action::OpenInBrowser, &["O"], "Open in Gospel Library (browser)"),
+    (KeyContext::Browse, Action::QuoteCard, &["E"], "Save a quote card image"),
+    (KeyContext::Browse, Action::Quote, &["Q"], "Quote verse into AI question"),
+    (KeyContext::Browse, Action::Save, &["x"], "Save verse"),
+    (KeyContext::Browse, Action::ToggleSaved, &["X"], "Show saved scriptures"),
+    (KeyContext::Browse, Action::Remove, &["d"], "Remove saved scripture"),
+    (KeyContext::Browse, Action::ClearSaved, &["D"], "Clear all saved scriptures"),
+    (KeyContext::Browse, Action::Tag, &["t"], "Tag saved scripture"),
+    (KeyContext::Browse, Action::FilterTag, &["T"], "Filter saved scriptures by tag"),
+    (KeyContext::Browse, Action::SearchVerse, &["s"], "Search for verse"),
+    (KeyContext::Browse, Action::FocusMode, &["f"], "Focus mode"),
+    (KeyContext::Browse, Action::ReadingMode, &["R"], "Reading mode (flowing paragraphs)"),
+    (KeyContext::Browse, Action::Visual, &["v"], "Select verse range"),
+    (KeyContext::Browse, Action::Split, &["S"], "Split view: open a second chapter / close"),
+    (KeyContext::Browse, Action::ViewParallel, &["V"], "View parallel passage beside the chapter (Isaiah, Matthew)"),
+    (KeyContext::Browse, Action::SwapPane, &["Ctrl-w"], "Switch between split chapters"),
+    (KeyContext::Browse, Action::GrowPane, &["Ctrl-l", "+", "="], "Widen navigation pane"),
+    (KeyContext::Browse, Action::ShrinkPane, &["Ctrl-h", "-"], "Narrow navigation pane"),
+    (KeyContext::Browse, Action::ToggleNav, &["N"], "Hide/show navigation pane"),
+    (KeyContext::Browse, Action::ToggleFootnotes, &["F"], "Hide/show footnote pane"),
+    (KeyContext::Browse, Action::ToggleCompact, &["z"], "Compact layout for small windows"),
+    (KeyContext::Browse, Action::ToggleRelated, &["r"], "Hide/show related verses"),
+    (KeyContext::Browse, Action::ToggleOutline, &["L"], "Hide/show chapter outline"),
+    (KeyContext::Browse, Action::Goto, &[":", "o"], "Go to reference"),
+    (KeyContext::Browse, Action::Search, &["/"], "Search"),
+    (KeyContext::Browse, Action::AskAi, &["a"], "Ask AI"),
+    (KeyContext::Browse, Action::Journal, &["J"], "New journal entry for this chapter"),
+    (KeyContext::Browse, Action::Lookup, &["w"], "Look up a word (Webster 1828)"),
+    (KeyContext::Browse, Action::Interlinear, &["i"], "Hebrew/Greek words (Strong's)"),
+    (KeyContext::Browse, Action::ReadAloud, &["p"], "Read chapter aloud / pause / resume"),
+    (KeyContext::Browse, Action::ReadNext, &[">"], "Read aloud from the next verse"),
+    (KeyContext::Browse, Action::ReadPrevious, &["<"], "Read aloud from the previous verse"),
+    (KeyContext::Browse, Action::StopReading, &["P"], "Stop reading aloud"),
+    (KeyContext::Browse, Action::Talks, &["Ctrl-t"], "Browse conference talks"),
+    (KeyContext::Browse, Action::ComeFollowMe, &["W"], "This week's Come, Follow Me reading"),
+    (KeyContext::Browse, Action::Topics, &["I"], "Study topics (Topical Guide, related verses, talks)"),
+    (KeyContext::Browse, Action::Quit, &["q"], "Quit"),
+
+    (KeyContext::Search, Action::Down, &["j", "Down"], "Move down"),
+    (KeyContext::Search, Action::Up, &["k", "Up"], "Move up"),
+    (KeyContext::Search, Action::Select, &["Enter"], "Open result in Browse"),
+    (KeyContext::Search, Action::CycleFocus, &["Tab"], "Switch panel"),
+    (KeyContext::Search, Action::EditInput, &["i", "/"], "Edit search"),
+    (KeyContext::Search, Action::FilterVolume, &["V"], "Filter by volume"),
+    (KeyContext::Search, Action::FilterBook, &["B"], "Filter by book"),
+    (KeyContext::Search, Action::CycleSearchMode, &["m"], "Semantic / keyword / both"),
+    (KeyContext::Search, Action::MoreResults, &["+", "="], "Show more results"),
+    (KeyContext::Search, Action::FewerResults, &["-"], "Show fewer results"),
+    (KeyContext::Search, Action::Copy, &["c"], "Copy verse"),
+    (KeyContext::Search, Action::CopyMenu, &["C"], "Copy as... (choose format)"),
+    (KeyContext::Search, Action::OpenInBrowser, &["O"], "Open in Gospel Library (browser)"),
+    (KeyContext::Search, Action::QuoteCard, &["E"], "Save a quote card image"),
+    (KeyContext::Search, Action::Save, &["x"], "Save verse"),
+    (KeyContext::Search, Action::ToggleSaved, &["X"], "Show saved scriptures"),
+    (KeyContext::Search, Action::Remove, &["d"], "Remove saved scripture"),
+    (KeyContext::Search, Action::ClearSaved, &["D"], "Clear all saved scriptures"),
+    (KeyContext::Search, Action::Tag, &["t"], "Tag saved scripture"),
+    (KeyContext::Search, Action::FilterTag, &["T"], "Filter saved scriptures by tag"),
+    (KeyContext::Search, Action::FocusMode, &["f"], "Focus mode"),
+    (KeyContext::Search, Action::Exit, &["Esc"], "Back to Browse"),
+
+    (KeyContext::Query, Action::Down, &["j", "Down"], "Scroll / move down"),
+    (KeyContext::Query, Action::Up, &["k", "Up"], "Scroll / move up"),
+    (KeyContext::Query, Action::Top, &["g"], "Jump to top"),
+    (KeyContext::Query, Action::Bottom, &["G"], "Jump to last verse"),
+    (KeyContext::Query, Action::HalfPageDown, &["Ctrl-d"], "Half page down"),
+    (KeyContext::Query, Action::HalfPageUp, &["Ctrl-u"], "Half page up"),
+    (KeyContext::Query, Action::Select, &["Enter"], "Jump to reference"),
+    (KeyContext::Query, Action::Back, &["b", "Backspace"], "Back to previous passage"),
+    (KeyContext::Query, Action::CycleFocus, &["Tab"], "Switch panel"),
+    (KeyContext::Query, Action::Copy, &["c"], "Copy verse"),
+    (KeyContext::Query, Action::CopyMenu, &["C"], "Copy as... (choose format)"),
+    (KeyContext::Query, Action::OpenInBrowser, &["O"], "Open in Gospel Library (browser)"),
+    (KeyContext::Query, Action::QuoteCard, &["E"], "Save a quote card image"),
+    (KeyContext::Query, Action::Quote, &["Q"], "Quote verse into AI question"),
+    (KeyContext::Query, Action::Save, &["x"], "Save verse"),
+    (KeyContext::Query, Action::ToggleSaved, &["X"], "Show saved scriptures"),
+    (KeyContext::Query, Action::Remove, &["d"], "Remove saved scripture"),
+    (KeyContext::Query, Action::ClearSaved, &["D"], "Clear all saved scriptures"),
+    (KeyContext::Query, Action::Tag, &["t"], "Tag saved scripture"),
+    (KeyContext::Query, Action::FilterTag, &["T"], "Filter saved scriptures by tag"),
+    (KeyContext::Query, Action::FocusMode, &["f"], "Focus mode"),
+    (KeyContext::Query, Action::Visual, &["v"], "Select verse range"),
+    (KeyContext::Query, Action::Goto, &[":", "o"], "Go to reference"),
+    (KeyContext::Query, Action::Journal, &["J"], "New journal entry for this chapter"),
+    (KeyContext::Query, Action::Lookup, &["w"], "Look up a word (Webster 1828)"),
+    (KeyContext::Query, Action::Interlinear, &["i"], "Hebrew/Greek words (Strong's)"),
+    (KeyContext::Query, Action::Talks, &["Ctrl-t"], "Browse conference talks"),
+    (KeyContext::Query, Action::ComeFollowMe, &["W"], "This week's Come, Follow Me reading"),
+    (KeyContext::Query, Action::Topics, &["I"], "Study topics (Topical Guide, related verses, talks)"),
+    (KeyContext::Query, Action::NewConversation, &["n"], "New conversation"),
+    (KeyContext::Query, Action::DeleteExchange, &["u"], "Delete last question and answer"),
+    (KeyContext::Query, Action::PrevConversation, &["["], "Previous (older) conversation"),
+    (KeyContext::Query, Action::NextConversation, &["]"], "Next (newer) conversation"),
+    (KeyContext::Query, Action::Regenerate, &["r"], "Regenerate last answer"),
+    (KeyContext::Query, Action::RegenerateWithModel, &["R"], "Regenerate with a different model"),
+    (KeyContext::Query, Action::EditQuestion, &["e"], "Edit and resend last question"),
+    (KeyContext::Query, Action::ModelPicker, &["M"], "Change AI model"),
+    (KeyContext::Query, Action::ProviderPicker, &["P"], "Change AI provider"),
+    (KeyContext::Query, Action::GrowPane, &["Ctrl-l", "+", "="], "Widen chat pane"),
+    (KeyContext::Query, Action::ShrinkPane, &["Ctrl-h", "-"], "Narrow chat pane"),
+    (KeyContext::Query, Action::Exit, &["Esc"], "Stop answer / back"),
+
+    (KeyContext::Visual, Action::Down, &["j", "Down"], "Extend selection down"),
+    (KeyContext::Visual, Action::Up, &["k", "Up"], "Extend selection up"),
+    (KeyContext::Visual, Action::Top, &["g"], "Extend to first verse"),
+    (KeyContext::Visual, Action::Bottom, &["G"], "Extend to last verse"),
+    (KeyContext::Visual, Action::Copy, &["c", "y"], "Copy selection"),
+    (KeyContext::Visual, Action::CopyMenu, &["C"], "Copy as... (choose format)"),
+    (KeyContext::Visual, Action::OpenInBrowser, &["O"], "Open selection in Gospel Library"),
+    (KeyContext::Visual, Action::QuoteCard, &["E"], "Save selection as a quote card image"),
+    (KeyContext::Visual, Action::Quote, &["Q"], "Quote selection into AI question"),
+    (KeyContext::Visual, Action::Save, &["x", "v"], "Save selection"),
+    (KeyContext::Visual, Action::AskAi, &["a"], "Ask AI about selection"),
+    (KeyContext::Visual, Action::Journal, &["J"], "New journal entry quoting selection"),
+    (KeyContext::Visual, Action::Exit, &["Esc"], "Cancel selection"),
+
+    (KeyContext::Focus, Action::Down, &["j", "Down", "n"], "Next verse"),
+    (KeyContext::Focus, Action::Up, &["k", "Up", "p"], "Previous verse"),
+    (KeyContext::Focus, Action::Copy, &["c"], "Copy verse or passage"),
+    (KeyContext::Focus, Action::CopyMenu, &["C"], "Copy as... (choose format)"),
+    (KeyContext::Focus, Action::OpenInBrowser, &["O"], "Open in Gospel Library (browser)"),
+    (KeyContext::Focus, Action::QuoteCard, &["E"], "Save a quote card image"),
+    (KeyContext::Focus, Action::Save, &["x"], "Save verse or passage"),
+    (KeyContext::Focus, Action::ToggleMemorize, &["m"], "Toggle memorization"),
+    (KeyContext::Focus, Action::Deck, &["D"], "Review a doctrinal mastery deck (next deck)"),
+    (KeyContext::Focus, Action::Journal, &["J"], "New journal entry quoting passage"),
+    (KeyContext::Focus, Action::Lookup, &["w"], "Look up a word (Webster 1828)"),
+    (KeyContext::Focus, Action::Interlinear, &["i"], "Hebrew/Greek words (Strong's)"),
+    (KeyContext::Focus, Action::Exit, &["Esc", "q"], "Exit focus mode"),
+
+    (KeyContext::Memorize, Action::CycleMemorizeMode, &["M"], "Cycle memorization mode"),
+    (KeyContext::Memorize, Action::Harder, &["+", "="], "Increase difficulty"),
+    (KeyContext::Memorize, Action::Easier, &["-"], "Decrease difficulty"),
+    (KeyContext::Memorize, Action::Reveal, &["Space", "Enter"], "Reveal / peek / next level"),
+    (KeyContext::Memorize, Action::StartTyping, &["t"], "Type your attempt"),
+    (KeyContext::Memorize, Action::ResetCard, &["r"], "Reset and try again"),
+
+    (KeyContext::Journal, Action::Down, &["j", "Down"], "Next entry"),
+    (KeyContext::Journal, Action::Up, &["k", "Up"], "Previous entry"),
+    (KeyContext::Journal, Action::Top, &["g"], "Newest entry"),
+    (KeyContext::Journal, Action::Bottom, &["G"], "Oldest entry"),
+    (KeyContext::Journal, Action::HalfPageDown, &["Ctrl-d"], "Scroll entry down"),
+    (KeyContext::Journal, Action::HalfPageUp, &["Ctrl-u"], "Scroll entry up"),
+    (KeyContext::Journal, Action::Select, &["Enter", "e"], "Edit entry in $EDITOR"),
+    (KeyContext::Journal, Action::Journal, &["J"], "New journal entry"),
+    (KeyContext::Journal, Action::Remove, &["d"], "Delete entry"),
+    (KeyContext::Journal, Action::Exit, &["Esc", "q"], "Back to Browse"),
+
+    (KeyContext::Talks, Action::Down, &["j", "Down"], "Move down"),
+    (KeyContext::Talks, Action::Up, &["k", "Up"], "Move up"),
+    (KeyContext::Talks, Action::Top, &["g"], "Jump to first item"),
+    (KeyContext::Talks, Action::Bottom, &["G"], "Jump to last item"),
+    (KeyContext::Talks, Action::Select, &["Enter", "l", "Right"], "Open speaker / year / talk"),
+    (KeyContext::Talks, Action::Back, &["h", "Left", "Backspace"], "Go back"),
+    (KeyContext::Talks, Action::CycleFocus, &["Tab"], "Switch between list and talk"),
+    (KeyContext::Talks, Action::EditInput, &["/", "i"], "Search talks"),
+    (KeyContext::Talks, Action::CycleSearchMode, &["m"], "Keyword / semantic search"),
+    (KeyContext::Talks, Action::Save, &["x"], "Save paragraph"),
+    (KeyContext::Talks, Action::Exit, &["Esc", "q"], "Back to Browse"),
+
+    (KeyContext::ComeFollowMe, Action::Down, &["j", "Down"], "Next chapter"),
+    (KeyContext::ComeFollowMe, Action::Up, &["k", "Up"], "Previous chapter"),
+    (KeyContext::ComeFollowMe, Action::Select, &["Enter", "l"], "Read chapter"),
+    (KeyContext::ComeFollowMe, Action::MarkRead, &["Space", "x"], "Mark chapter read / unread"),
+    (KeyContext::ComeFollowMe, Action::PrevWeek, &["[", "h"], "Previous week"),
+    (KeyContext::ComeFollowMe, Action::NextWeek, &["]"], "Next week"),
+    (KeyContext::ComeFollowMe, Action::AskAi, &["a"], "Prepare a lesson with AI"),
+    (KeyContext::ComeFollowMe, Action::Exit, &["Esc", "q"], "Back to Browse"),
+
+    (KeyContext::Topics, Action::Down, &["j", "Down"], "Move down"),
+    (KeyContext::Topics, Action::Up, &["k", "Up"], "Move up"),
+    (KeyContext::Topics, Action::HalfPageDown, &["Ctrl-d"], "Half page down"),
+    (KeyContext::Topics, Action::HalfPageUp, &["Ctrl-u"], "Half page up"),
+    (KeyContext::Topics, Action::Select, &["Enter", "l", "Right"], "Open topic / go to verse or talk"),
+    (KeyContext::Topics, Action::Back, &["h", "Left", "Backspace"], "Back to the topic list"),
+    (KeyContext::Topics, Action::Exit, &["Esc", "q"], "Back to Browse"),
+
+    (KeyContext::Reading, Action::Down, &["j", "Down"], "Scroll down"),
+    (KeyContext::Reading, Action::Up, &["k", "Up"], "Scroll up"),
+    (KeyContext::Reading, Action::HalfPageDown, &["Ctrl-d", "Space"], "Half page down"),
+    (KeyContext::Reading, Action::HalfPageUp, &["Ctrl-u"], "Half page up"),
+    (KeyContext::Reading, Action::GrowPane, &["+", "="], "Wider lines"),
+    (KeyContext::Reading, Action::ShrinkPane, &["-"], "Narrower lines"),
+    (KeyContext::Reading, Action::Exit, &["Esc", "q"], "Back to Browse at this chapter"),
+];
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .map(|(context, action, keys, description)| Binding {
+                context: *context,
+                action: *action,
+                keys: keys.iter().filter_map(|k| KeyPress::parse(k)).collect(),
+                description,
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Default bindings with any overrides from keybindings.toml applied
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::get_keymap_path()?)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let mut keymap = Self::default();
+        if path.exists() {
+            keymap.apply_overrides(&fs::read_to_string(path)?)?;
+        }
+        Ok(keymap)
+    }
+
+    /// Replace the keys of each action named in the TOML, leaving the rest as default
+    fn apply_overrides(&mut self, content: &str) -> Result<()> {
+        let table: toml::Table = content.parse()?;
+
+        for (context_name, actions) in &table {
+            let context = KeyContext::all()
+                .iter()
+                .copied()
+                .find(|c| c.name() == context_name)
+                .ok_or_else(|| anyhow!("Unknown keybinding section [{}]", context_name))?;
+            let actions = actions
+                .as_table()
+                .ok_or_else(|| anyhow!("[{}] must be a table of action = keys", context_name))?;
+
+            for (action_name, value) in actions {
+                let action = Action::from_name(action_name)
+                    .ok_or_else(|| anyhow!("Unknown action '{}' in [{}]", action_name, context_name))?;
+                let specs: Vec<&str> = match value {
+                    toml::Value::String(spec) => vec![spec.as_str()],
+                    toml::Value::Array(specs) => specs.iter().filter_map(|v| v.as_str()).collect(),
+                    _ => return Err(anyhow!("Keys for {}.{} must be a string or list", context_name, action_name)),
+                };
+                let keys = specs
+                    .iter()
+                    .map(|spec| {
+                        KeyPress::parse(spec)
+                            .ok_or_else(|| anyhow!("Invalid key '{}' for {}.{}", spec, context_name, action_name))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                match self.bindings.iter_mut().find(|b| b.context == context && b.action == action) {
+                    Some(binding) => binding.keys = keys,
+                    None => {
+                        return Err(anyhow!("{} has no '{}' action", context.title(), action_name));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_keymap_path() -> Result<PathBuf> {
+        Ok(escrituras_core::paths::config_dir()?.join("keybindings.toml"))
+    }
+
+    /// Footer label for an action's first key, padded like the other hints (e.g. " x ")
+    pub fn hint(&self, context: KeyContext, action: Action) -> String {
+        format!(" {} ", self.first_key(context, action))
+    }
+
+    /// Footer label for a down/up pair (e.g. " j/k ")
+    pub fn nav_hint(&self, context: KeyContext) -> String {
+        self.pair_hint(context, Action::Down, Action::Up)
+    }
+
+    /// Footer label for two related actions (e.g. " +/- ")
+    pub fn pair_hint(&self, context: KeyContext, first: Action, second: Action) -> String {
+        format!(" {}/{} ", self.first_key(context, first), self.first_key(context, second))
+    }
+
+    fn first_key(&self, context: KeyContext, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|b| b.context == context && b.action == action)
+            .and_then(|b| b.keys.first())
+            .map(|k| k.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// Resolve a key press to an action in the given context
+    pub fn action(&self, context: KeyContext, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .filter(|b| b.context == context)
+            .find(|b| b.keys.iter().any(|k| k.matches(key)))
+            .map(|b| b.action)
+    }
+
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(KeyPress::parse("j").unwrap().code, KeyCode::Char('j'));
+        assert_eq!(KeyPress::parse("Space").unwrap().code, KeyCode::Char(' '));
+        assert_eq!(KeyPress::parse("F5").unwrap().code, KeyCode::F(5));
+
+        let ctrl_d = KeyPress::parse("Ctrl-d").unwrap();
+        assert_eq!(ctrl_d.code, KeyCode::Char('d'));
+        assert_eq!(ctrl_d.modifiers, KeyModifiers::CONTROL);
+
+        assert!(KeyPress::parse("Nope").is_none());
+    }
+
+    #[test]
+    fn test_default_bindings_all_parse() {
+        for (_, _, keys, description) in DEFAULT_BINDINGS {
+            for key in *keys {
+                assert!(KeyPress::parse(key).is_some(), "{} ({})", key, description);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modifiers_distinguish_actions() {
+        let keymap = Keymap::default();
+        let d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        let shift_g = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+
+        assert_eq!(keymap.action(KeyContext::Browse, &d), Some(Action::Remove));
+        assert_eq!(keymap.action(KeyContext::Browse, &ctrl_d), Some(Action::HalfPageDown));
+        assert_eq!(keymap.action(KeyContext::Browse, &shift_g), Some(Action::Bottom));
+    }
+
+    #[test]
+    fn test_overrides_replace_keys() {
+        let mut keymap = Keymap::default();
+        keymap
+            .apply_overrides("[browse]\nsave = \"s\"\nsearch_verse = [\"S\", \"Ctrl-f\"]\n")
+            .unwrap();
+
+        let s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE);
+        let x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        let ctrl_f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(KeyContext::Browse, &s), Some(Action::Save));
+        assert_eq!(keymap.action(KeyContext::Browse, &x), None);
+        assert_eq!(keymap.action(KeyContext::Browse, &ctrl_f), Some(Action::SearchVerse));
+        assert_eq!(keymap.hint(KeyContext::Browse, Action::Save), " s ");
+
+        // Other contexts keep their defaults
+        assert_eq!(keymap.action(KeyContext::Search, &x), Some(Action::Save));
+    }
+
+    #[test]
+    fn test_overrides_reject_unknown_names() {
+        assert!(Keymap::default().apply_overrides("[nope]\nsave = \"s\"").is_err());
+        assert!(Keymap::default().apply_overrides("[browse]\nfly = \"s\"").is_err());
+        assert!(Keymap::default().apply_overrides("[browse]\nsave = \"Hyper-s\"").is_err());
+        assert!(Keymap::default().apply_overrides("[visual]\nquit = \"q\"").is_err());
+    }
+}
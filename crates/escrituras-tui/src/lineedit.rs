@@ -0,0 +1,203 @@
+//! A one-line prompt with editing and history, for `scriptures chat`
+//!
+//! The terminal is in raw mode only while a line is typed, so streamed
+//! answers print normally in between. Lines that wrap past the terminal width
+//! are redrawn from their first row. When stdin isn't a terminal, lines are
+//! read as they come.
+
+use anyhow::Result;
+use crossterm::cursor::{MoveToColumn, MoveUp};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::{execute, queue};
+use std::io::{IsTerminal, Write};
+
+use crate::textarea::{next_grapheme, prev_grapheme, width_before};
+
+/// Leaves raw mode however reading a line ends
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub struct LineEditor {
+    /// Earlier lines, oldest first, for Up and Down
+    history: Vec<String>,
+    /// Drawn before the text, with its width in columns
+    prompt: String,
+    prompt_width: usize,
+    text: String,
+    /// Char index into `text`
+    cursor: usize,
+    /// Row of the cursor below the prompt's first row, as last drawn
+    cursor_row: usize,
+}
+
+impl LineEditor {
+    /// An editor whose Up key recalls `history` (oldest first). `prompt` may be
+    /// styled; `prompt_width` is the columns it takes.
+    pub fn new(history: Vec<String>, prompt: String, prompt_width: usize) -> Self {
+        LineEditor { history, prompt, prompt_width, text: String::new(), cursor: 0, cursor_row: 0 }
+    }
+
+    /// Remember a line for Up, skipping repeats of the last one
+    pub fn add_history(&mut self, line: &str) {
+        if !line.is_empty() && self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+        }
+    }
+
+    /// The next line typed, or None at Ctrl-D on an empty line (or the end of input).
+    /// Ctrl-C abandons the line and returns it empty.
+    pub fn read_line(&mut self) -> Result<Option<String>> {
+        let mut stdout = std::io::stdout();
+        if !std::io::stdin().is_terminal() {
+            let mut line = String::new();
+            if std::io::stdin().read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            return Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()));
+        }
+
+        self.text.clear();
+        self.cursor = 0;
+        self.cursor_row = 0;
+        // Index into history being shown, and the line typed before going up to it
+        let mut recalled: Option<usize> = None;
+        let mut draft = String::new();
+
+        let _raw = RawMode::enable()?;
+        self.redraw(&mut stdout)?;
+        loop {
+            let Event::Key(KeyEvent { code, modifiers, kind, .. }) = event::read()? else {
+                continue;
+            };
+            if kind != KeyEventKind::Press {
+                continue;
+            }
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            let len = self.text.chars().count();
+            match code {
+                KeyCode::Enter => {
+                    self.cursor = len;
+                    self.redraw(&mut stdout)?;
+                    execute!(stdout, Print("\r\n"))?;
+                    return Ok(Some(self.text.clone()));
+                }
+                KeyCode::Char('c') if ctrl => {
+                    self.cursor = len;
+                    self.redraw(&mut stdout)?;
+                    execute!(stdout, Print("^C\r\n"))?;
+                    return Ok(Some(String::new()));
+                }
+                KeyCode::Char('d') if ctrl && self.text.is_empty() => {
+                    execute!(stdout, Print("\r\n"))?;
+                    return Ok(None);
+                }
+                KeyCode::Char('d') if ctrl => self.delete_forward(),
+                KeyCode::Char('a') if ctrl => self.cursor = 0,
+                KeyCode::Char('e') if ctrl => self.cursor = len,
+                KeyCode::Char('b') if ctrl => self.cursor = prev_grapheme(&self.text, self.cursor),
+                KeyCode::Char('f') if ctrl => self.cursor = next_grapheme(&self.text, self.cursor),
+                KeyCode::Char('u') if ctrl => {
+                    self.text = self.text.chars().skip(self.cursor).collect();
+                    self.cursor = 0;
+                }
+                KeyCode::Char('k') if ctrl => self.text = self.text.chars().take(self.cursor).collect(),
+                KeyCode::Char('w') if ctrl => {
+                    let before: Vec<char> = self.text.chars().take(self.cursor).collect();
+                    let mut start = before.len();
+                    while start > 0 && before[start - 1].is_whitespace() {
+                        start -= 1;
+                    }
+                    while start > 0 && !before[start - 1].is_whitespace() {
+                        start -= 1;
+                    }
+                    let rest: String = self.text.chars().skip(self.cursor).collect();
+                    self.text = before[..start].iter().collect::<String>() + &rest;
+                    self.cursor = start;
+                }
+                KeyCode::Char(c) if !ctrl => {
+                    let byte = self.text.char_indices().nth(self.cursor).map_or(self.text.len(), |(i, _)| i);
+                    self.text.insert(byte, c);
+                    self.cursor += 1;
+                }
+                KeyCode::Backspace if self.cursor > 0 => {
+                    let start = prev_grapheme(&self.text, self.cursor);
+                    self.text = self.text.chars().take(start).chain(self.text.chars().skip(self.cursor)).collect();
+                    self.cursor = start;
+                }
+                KeyCode::Delete => self.delete_forward(),
+                KeyCode::Left => self.cursor = prev_grapheme(&self.text, self.cursor),
+                KeyCode::Right => self.cursor = next_grapheme(&self.text, self.cursor),
+                KeyCode::Home => self.cursor = 0,
+                KeyCode::End => self.cursor = len,
+                KeyCode::Up if recalled != Some(0) && !self.history.is_empty() => {
+                    let i = match recalled {
+                        Some(i) => i - 1,
+                        None => {
+                            draft = std::mem::take(&mut self.text);
+                            self.history.len() - 1
+                        }
+                    };
+                    recalled = Some(i);
+                    self.text = self.history[i].clone();
+                    self.cursor = self.text.chars().count();
+                }
+                KeyCode::Down if recalled.is_some() => {
+                    let next = recalled.map(|i| i + 1).filter(|&i| i < self.history.len());
+                    self.text = match next {
+                        Some(i) => self.history[i].clone(),
+                        None => std::mem::take(&mut draft),
+                    };
+                    recalled = next;
+                    self.cursor = self.text.chars().count();
+                }
+                _ => continue,
+            }
+            self.redraw(&mut stdout)?;
+        }
+    }
+
+    fn delete_forward(&mut self) {
+        let end = next_grapheme(&self.text, self.cursor);
+        self.text = self.text.chars().take(self.cursor).chain(self.text.chars().skip(end)).collect();
+    }
+
+    /// Draw the prompt and text over the last drawing and place the cursor
+    fn redraw(&mut self, out: &mut impl Write) -> Result<()> {
+        // Some terminals (and ptys) report no size
+        let columns = terminal::size().ok().map(|(c, _)| c as usize).filter(|&c| c > 0).unwrap_or(80);
+        if self.cursor_row > 0 {
+            queue!(out, MoveUp(self.cursor_row as u16))?;
+        }
+        queue!(out, MoveToColumn(0), Clear(ClearType::FromCursorDown), Print(&self.prompt), Print(&self.text))?;
+
+        let end = self.prompt_width + width_before(&self.text, usize::MAX);
+        // A line that exactly fills its last row leaves the cursor waiting to
+        // wrap; move it to the next row so the arithmetic below holds
+        if end > 0 && end.is_multiple_of(columns) {
+            queue!(out, Print("\r\n"))?;
+        }
+        let at = self.prompt_width + width_before(&self.text, self.cursor);
+        let rows_up = end / columns - at / columns;
+        if rows_up > 0 {
+            queue!(out, MoveUp(rows_up as u16))?;
+        }
+        queue!(out, MoveToColumn((at % columns) as u16))?;
+        out.flush()?;
+        self.cursor_row = at / columns;
+        Ok(())
+    }
+}
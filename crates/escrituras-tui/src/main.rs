@@ -1,80 +1,185 @@
 mod app;
+mod cli;
+mod commands;
 mod handler;
+mod keymap;
+mod lineedit;
+mod onboarding;
+mod textarea;
+mod theme;
+mod toast;
 mod tui;
 mod ui;
 
-use anyhow::Result;
-use escrituras_core::{download_embedding_model, mcp, ChatMessage, ChatRole, EmbeddingsDb, ScriptureDb};
-use std::time::Duration;
+use anyhow::{anyhow, Result};
+use escrituras_core::{download_embedding_model, logging, paths, setup, sync, timings, ChatMessage, ChatRole, Config, SessionState};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
 
-    // Check for MCP server mode
+    // `--profile <name>`, `--verbose`, and `--timings` may come anywhere, and apply to every command
+    if let Some(name) = take_profile(&mut args)? {
+        Config::select_profile(&name);
+    }
+    let verbose = take_switch(&mut args, "--verbose");
+    let _timings = take_switch(&mut args, "--timings").then(TimingsReport::start);
+
+    // Fail early on a bad ESCRITURAS_* variable rather than fall back to defaults
+    let config = Config::load().map_err(|e| anyhow!("Could not read settings: {}", e))?;
+    let _log = logging::init(config.log_level.as_deref(), verbose);
+    tracing::debug!(args = ?&args[1..], "starting");
+
+    // Only the TUI and commands that write study data get it ready first;
+    // lookups, searches, and the like leave files and the sync folder be
+    let command = args.get(1).and_then(|a| commands::find(a));
+    let writes_study_data = match command {
+        Some(command) => command.changes_study_data,
+        None => !args.iter().any(|a| ["--help", "-h", "--mcp", "--download-model"].contains(&a.as_str())),
+    };
+
+    // Files older versions kept in the config directory move to where they go now
+    if writes_study_data {
+        match paths::migrate() {
+            Ok(moved) if !moved.is_empty() => {
+                eprintln!("Moved files to their new folders:");
+                for (from, to) in moved {
+                    tracing::info!("moved {} to {}", from.display(), to.display());
+                    eprintln!("  {} → {}", from.display(), to.display());
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Couldn't move files to their new folders: {}", e),
+        }
+    }
+    if let Some(name) = config.profile_name() {
+        // A new profile is made by setting something in it; anything else needs one that exists
+        if !config.profiles.contains_key(name) && args.get(1).map(String::as_str) != Some("config") {
+            let names: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+            return Err(anyhow!(
+                "No profile '{}' (profiles: {}). Create it with `scriptures --profile {} config set provider <provider>`",
+                name,
+                if names.is_empty() { "none".to_string() } else { names.join(", ") },
+                name
+            ));
+        }
+    }
+
+    // Keys saved in the config file by older versions move to the keychain
+    if writes_study_data {
+        if let Err(e) = Config::migrate_saved_keys() {
+            tracing::warn!("Couldn't move API keys to the keychain: {}", e);
+        }
+    }
+
+    // Study data is synced before and after whatever writes it, so it starts
+    // from the other computers' changes and leaves its own for them
+    let sync_dir = config.sync_dir.as_ref().map(PathBuf::from);
+    let sync_dir = sync_dir.as_deref().filter(|_| writes_study_data && command.is_none_or(|c| c.name != "sync"));
+    sync_study_data(sync_dir);
+
+    // Subcommands print their output and exit without starting the TUI
+    if let Some(command) = command {
+        let result = cli::run(command.name, &args[2..]).await.map_err(cli::with_hint);
+        sync_study_data(sync_dir);
+        return result;
+    }
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        print!("{}", commands::help(None));
+        return Ok(());
+    }
+
+    // MCP server mode (older spelling of `scriptures mcp`)
     if args.iter().any(|a| a == "--mcp") {
-        return run_mcp_server().await;
+        return cli::run("mcp", &[]).await;
     }
 
     // Check for model download mode (used by install.sh)
     if args.iter().any(|a| a == "--download-model") {
-        return download_embedding_model();
+        return Ok(download_embedding_model()?);
     }
 
-    // Run TUI mode
-    run_tui().await
+    // Run TUI mode, with the setup wizard first if asked for
+    let result = run_tui(args.iter().any(|a| a == "--setup")).await;
+    sync_study_data(sync_dir);
+    result
 }
 
-async fn run_mcp_server() -> Result<()> {
-    // Load scripture database - try local path first, then config directory
-    let mut scripture_db = ScriptureDb::new();
-    let local_scripture_path = "lds-scriptures-2020.12.08/json/lds-scriptures-json.txt";
-    let config_scripture_path = dirs::config_dir()
-        .map(|p| p.join("escrituras/lds-scriptures-2020.12.08/json/lds-scriptures-json.txt"));
-
-    if std::path::Path::new(local_scripture_path).exists() {
-        scripture_db.load_from_json(local_scripture_path).await?;
-    } else if let Some(ref cfg_path) = config_scripture_path {
-        if cfg_path.exists() {
-            scripture_db.load_from_json(cfg_path.to_str().unwrap()).await?;
-        } else {
-            anyhow::bail!("Scripture data not found. Run install.sh or place data in lds-scriptures-2020.12.08/");
-        }
-    } else {
-        anyhow::bail!("Scripture data not found. Run install.sh or place data in lds-scriptures-2020.12.08/");
-    }
-
-    // Load embeddings if available (for semantic search)
-    // Try local data/ directory first, then ~/.config/escrituras/data/
-    let embeddings_db = {
-        let local_path = std::path::Path::new("data");
-        let config_path = dirs::config_dir()
-            .map(|p| p.join("escrituras/data"));
-
-        if local_path.join("scripture_embeddings.npy").exists() {
-            EmbeddingsDb::load(local_path).ok()
-        } else if let Some(ref cfg_path) = config_path {
-            if cfg_path.join("scripture_embeddings.npy").exists() {
-                EmbeddingsDb::load(cfg_path).ok()
-            } else {
-                None
+/// Sync with the folder in `sync_dir`, mentioning only what needs attention
+fn sync_study_data(dir: Option<&Path>) {
+    let Some(dir) = dir else {
+        return;
+    };
+    match sync::sync(dir) {
+        Ok(summary) => {
+            for conflict in &summary.conflicts {
+                tracing::warn!("Sync conflict: {}", conflict);
             }
-        } else {
-            None
         }
-    };
+        Err(e) => tracing::warn!("Couldn't sync with {}: {}", dir.display(), e),
+    }
+}
 
-    mcp::run_mcp_server(scripture_db, embeddings_db);
-    Ok(())
+/// Remove a flag like `--verbose` from the arguments, saying whether it was there
+fn take_switch(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != flag);
+    args.len() < before
+}
+
+/// With `--timings`, what loading took, printed to stderr as the program
+/// finishes (after the app gives back the terminal)
+struct TimingsReport {
+    started: Instant,
 }
 
-async fn run_tui() -> Result<()> {
+impl TimingsReport {
+    fn start() -> Self {
+        timings::enable();
+        Self { started: Instant::now() }
+    }
+}
+
+impl Drop for TimingsReport {
+    fn drop(&mut self) {
+        eprint!("{}", timings::report(self.started.elapsed()));
+    }
+}
+
+/// Remove `--profile <name>` (or `--profile=<name>`) from the arguments
+fn take_profile(args: &mut Vec<String>) -> Result<Option<String>> {
+    let Some(i) = args.iter().position(|a| a == "--profile" || a.starts_with("--profile=")) else {
+        return Ok(None);
+    };
+    let flag = args.remove(i);
+    match flag.strip_prefix("--profile=") {
+        Some(name) => Ok(Some(name.to_string())),
+        None if i < args.len() => Ok(Some(args.remove(i))),
+        None => Err(anyhow!("--profile needs a profile name")),
+    }
+}
+
+async fn run_tui(force_setup: bool) -> Result<()> {
     // Install panic hook to restore terminal on crash
     tui::install_panic_hook();
 
     // Initialize terminal
     let mut terminal = tui::init()?;
 
+    // Walk a first-time user through setup instead of failing on missing data
+    if force_setup || !Config::exists() || setup::find_scripture_data().is_none() {
+        match onboarding::run(&mut terminal).await {
+            Ok(true) => {}
+            Ok(false) => return tui::restore(),
+            Err(e) => {
+                tui::restore()?;
+                return Err(e);
+            }
+        }
+    }
+
     // Create app state - restore terminal on failure
     let mut app = match app::App::new().await {
         Ok(app) => app,
@@ -84,20 +189,41 @@ async fn run_tui() -> Result<()> {
         }
     };
 
+    // Pick up where the last session left off
+    if let Ok(session) = SessionState::load() {
+        app.restore_session(session);
+    }
+
     // Create event handler
     let mut events = tui::EventHandler::new();
 
     // Main loop
     loop {
-        // Draw UI
-        terminal.draw(|frame| {
-            ui::render(&mut app, frame);
-        })?;
+        // Draw UI, when something has changed since the last time
+        if app.needs_redraw {
+            app.needs_redraw = false;
+            terminal.draw(|frame| {
+                ui::render(&mut app, frame);
+            })?;
+        }
 
-        // Check if AI query task completed
+        // Run a live search once typing pauses, add a search's matches by
+        // meaning once they're found, announce the verse being read, and let
+        // old toasts fade
+        app.poll_live_search();
+        app.poll_search_task();
+        app.announce_viewed_verse();
+        if app.toasts.expire() {
+            app.needs_redraw = true;
+        }
+
+        // Render any streamed tokens, then check if AI query task completed
+        app.poll_query_stream();
         if let Some(task) = &app.query_task {
             if task.is_finished() {
                 let task = app.query_task.take().unwrap();
+                app.query_tokens = None;
+                app.streaming_response.clear();
                 match task.await {
                     Ok(Ok(response)) => {
                         // Extract scripture references from the response
@@ -116,7 +242,7 @@ async fn run_tui() -> Result<()> {
                         app.extracted_references.clear();
                         app.chat_messages.push(ChatMessage {
                             role: ChatRole::Assistant,
-                            content: format!("Error: {}", e),
+                            content: app::chat_error(&e),
                         });
                     }
                     Err(e) => {
@@ -128,9 +254,20 @@ async fn run_tui() -> Result<()> {
                     }
                 }
                 app.query_loading = false;
+                app.needs_redraw = true;
             }
         }
 
+        // Show the AI's chapter outline once it's ready
+        if app.outline_task.as_ref().is_some_and(|task| task.is_finished()) {
+            let task = app.outline_task.take().unwrap();
+            match task.await {
+                Ok(result) => app.finish_outline(result),
+                Err(e) => app.toasts.error(format!("Task error: {}", e)),
+            }
+            app.needs_redraw = true;
+        }
+
         // Handle events with timeout so we can poll task completion
         // Use select to either get an event or timeout after 100ms
         tokio::select! {
@@ -144,12 +281,27 @@ async fn run_tui() -> Result<()> {
             }
         }
 
+        // Hand the terminal to $EDITOR for a journal entry, then pick up where we were
+        if let Some(request) = app.editor_request.take() {
+            drop(events);
+            let result = tui::run_editor(&mut terminal, &request.path);
+            events = tui::EventHandler::new();
+            app.finish_editing(request, result);
+            app.needs_redraw = true;
+        }
+
         // Check if we should quit
         if app.should_quit {
             break;
         }
     }
 
+    // Remember where we were for next launch
+    app.save_conversations();
+    if let Err(e) = app.session_state().save() {
+        tracing::warn!("Couldn't save the session: {}", e);
+    }
+
     // Restore terminal
     tui::restore()?;
 
@@ -0,0 +1,453 @@
+//! First-run setup wizard
+//!
+//! Runs before the app loads when there's no config file yet, the scripture
+//! data can't be found, or `--setup` is passed. It installs the data, picks an
+//! AI provider, stores its API key, and optionally fetches the semantic search
+//! model, so a fresh install doesn't fail at startup or on the first question.
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use escrituras_core::{cache_embedding_model, setup, Config, Provider};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::theme::{self, ColorSupport, Theme};
+use crate::tui::{AppEvent, EventHandler, Tui};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Welcome,
+    Data,
+    Provider,
+    ApiKey,
+    Embeddings,
+    Done,
+}
+
+impl Step {
+    const ALL: [Step; 6] = [Step::Welcome, Step::Data, Step::Provider, Step::ApiKey, Step::Embeddings, Step::Done];
+
+    fn title(&self) -> &'static str {
+        match self {
+            Step::Welcome => "Welcome",
+            Step::Data => "Scripture data",
+            Step::Provider => "AI provider",
+            Step::ApiKey => "API key",
+            Step::Embeddings => "Semantic search",
+            Step::Done => "Done",
+        }
+    }
+}
+
+/// A download running in the background; yields a message for the status line
+type Download = JoinHandle<Result<String>>;
+
+struct Wizard {
+    step: Step,
+    theme: Theme,
+    colors: ColorSupport,
+    data_path: Option<PathBuf>,
+    path_input: Option<String>, // Typing the location of an existing copy
+    providers: Vec<Provider>,
+    provider_state: ListState,
+    key_input: String,
+    download: Option<Download>,
+    status: Option<(bool, String)>, // (is error, message)
+    model_cached: bool,
+    quit: bool,
+    finished: bool,
+}
+
+impl Wizard {
+    fn new() -> Self {
+        let config = Config::load().unwrap_or_else(|_| Config::new());
+        let colors = ColorSupport::detect();
+        let providers = Provider::all();
+        let current = config.current_provider();
+        let mut provider_state = ListState::default();
+        provider_state.select(providers.iter().position(|p| *p == current));
+
+        Self {
+            step: Step::Welcome,
+            theme: Theme::load(config.theme.as_deref()).unwrap_or_default().for_terminal(colors),
+            colors,
+            data_path: setup::find_scripture_data(),
+            path_input: None,
+            providers,
+            provider_state,
+            key_input: String::new(),
+            download: None,
+            status: None,
+            model_cached: false,
+            quit: false,
+            finished: false,
+        }
+    }
+
+    fn provider(&self) -> Provider {
+        self.provider_state
+            .selected()
+            .and_then(|i| self.providers.get(i).copied())
+            .unwrap_or(Provider::Ollama)
+    }
+
+    /// Environment variable that already supplies the provider's key, if set
+    fn env_key(provider: Provider) -> Option<&'static str> {
+        let var = match provider {
+            Provider::Claude => "ANTHROPIC_API_KEY",
+            Provider::OpenAI => "OPENAI_API_KEY",
+            Provider::Ollama => return None,
+        };
+        std::env::var(var).is_ok().then_some(var)
+    }
+
+    fn go(&mut self, step: Step) {
+        self.step = step;
+        self.status = None;
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.quit = true;
+            return;
+        }
+        // Downloads can't be interrupted except by quitting
+        if self.download.is_some() {
+            return;
+        }
+        if let Some(input) = &mut self.path_input {
+            match key.code {
+                KeyCode::Esc => self.path_input = None,
+                KeyCode::Enter => self.install_from_path(),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        match (self.step, key.code) {
+            (Step::Welcome, KeyCode::Enter) => self.go(Step::Data),
+            (Step::Welcome, KeyCode::Esc | KeyCode::Char('q')) => self.quit = true,
+
+            (Step::Data, KeyCode::Enter) if self.data_path.is_some() => self.go(Step::Provider),
+            (Step::Data, KeyCode::Char('d')) => self.start_data_download(),
+            (Step::Data, KeyCode::Char('p')) => {
+                self.path_input = Some(String::new());
+                self.status = None;
+            }
+            (Step::Data, KeyCode::Esc) => self.go(Step::Welcome),
+
+            (Step::Provider, KeyCode::Char('j') | KeyCode::Down) => {
+                let i = self.provider_state.selected().map_or(0, |i| (i + 1).min(self.providers.len() - 1));
+                self.provider_state.select(Some(i));
+            }
+            (Step::Provider, KeyCode::Char('k') | KeyCode::Up) => {
+                let i = self.provider_state.selected().unwrap_or(0).saturating_sub(1);
+                self.provider_state.select(Some(i));
+            }
+            (Step::Provider, KeyCode::Enter) => {
+                let provider = self.provider();
+                if provider == Provider::Ollama || Self::env_key(provider).is_some() {
+                    self.go(Step::Embeddings);
+                } else {
+                    self.key_input.clear();
+                    self.go(Step::ApiKey);
+                }
+            }
+            (Step::Provider, KeyCode::Esc) => self.go(Step::Data),
+
+            (Step::ApiKey, KeyCode::Enter) if !self.key_input.trim().is_empty() => self.go(Step::Embeddings),
+            (Step::ApiKey, KeyCode::Esc) => {
+                // Skip for now; the key can be entered later from the AI screen
+                self.key_input.clear();
+                self.go(Step::Embeddings);
+            }
+            (Step::ApiKey, KeyCode::Backspace) => {
+                self.key_input.pop();
+            }
+            (Step::ApiKey, KeyCode::Char(c)) => self.key_input.push(c),
+
+            (Step::Embeddings, KeyCode::Char('y')) if !self.model_cached => self.start_model_download(),
+            (Step::Embeddings, KeyCode::Char('n') | KeyCode::Enter) => self.go(Step::Done),
+            (Step::Embeddings, KeyCode::Esc) => self.go(Step::Provider),
+
+            (Step::Done, KeyCode::Enter) => self.finish(),
+            (Step::Done, KeyCode::Esc) => self.go(Step::Embeddings),
+            _ => {}
+        }
+    }
+
+    /// Pasted paths and keys are a single line; drop a trailing newline
+    fn paste(&mut self, text: &str) {
+        if self.download.is_some() {
+            return;
+        }
+        let text = text.trim();
+        if let Some(input) = &mut self.path_input {
+            input.push_str(text);
+        } else if self.step == Step::ApiKey {
+            self.key_input.push_str(text);
+        }
+    }
+
+    fn install_from_path(&mut self) {
+        let Some(input) = self.path_input.take() else {
+            return;
+        };
+        let path = match input.trim().strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|h| h.join(rest)).unwrap_or_else(|| PathBuf::from(input.trim())),
+            None => PathBuf::from(input.trim()),
+        };
+        match setup::install_scripture_file(&path) {
+            Ok(dest) => {
+                self.status = Some((false, format!("Copied scripture data to {}", dest.display())));
+                self.data_path = Some(dest);
+            }
+            Err(e) => self.status = Some((true, e.to_string())),
+        }
+    }
+
+    fn start_data_download(&mut self) {
+        self.status = Some((false, "Downloading scripture data from the latest release...".to_string()));
+        self.download = Some(tokio::spawn(async {
+            let path = setup::download_release_data().await?;
+            Ok(path.display().to_string())
+        }));
+    }
+
+    fn start_model_download(&mut self) {
+        self.status = Some((false, "Downloading the embedding model (about 130 MB)...".to_string()));
+        self.download = Some(tokio::task::spawn_blocking(|| {
+            cache_embedding_model()?;
+            Ok(String::new())
+        }));
+    }
+
+    /// Pick up a finished download
+    async fn poll_download(&mut self) {
+        if !self.download.as_ref().is_some_and(|t| t.is_finished()) {
+            return;
+        }
+        let Some(task) = self.download.take() else {
+            return;
+        };
+        let result = task.await.map_err(anyhow::Error::from).and_then(|r| r);
+        match (self.step, result) {
+            (Step::Data, Ok(path)) => {
+                self.status = Some((false, format!("Installed scripture data to {}", path)));
+                self.data_path = Some(PathBuf::from(path));
+            }
+            (Step::Embeddings, Ok(_)) => {
+                self.model_cached = true;
+                self.status = Some((false, "Embedding model downloaded".to_string()));
+            }
+            (_, Ok(_)) => {}
+            (_, Err(e)) => self.status = Some((true, format!("Download failed: {}", e))),
+        }
+    }
+
+    /// Write the chosen provider to the config file and store the key
+    fn finish(&mut self) {
+        let mut config = Config::load().unwrap_or_else(|_| Config::new());
+        let provider = self.provider();
+        config.provider = Some(provider.as_str().to_string());
+        let key = self.key_input.trim().to_string();
+        if !key.is_empty() && provider != Provider::Ollama {
+            if let Err(e) = config.store_api_key(provider, &key) {
+                self.status = Some((true, format!("Couldn't save the key: {}", e)));
+                return;
+            }
+        }
+        match config.save() {
+            Ok(()) => self.finished = true,
+            Err(e) => self.status = Some((true, format!("Couldn't save config: {}", e))),
+        }
+    }
+}
+
+/// Run the wizard; returns false if the user quit instead of finishing
+pub async fn run(terminal: &mut Tui) -> Result<bool> {
+    let mut wizard = Wizard::new();
+    let mut events = EventHandler::new();
+
+    loop {
+        terminal.draw(|frame| {
+            render(&mut wizard, frame);
+            if wizard.colors == ColorSupport::None {
+                theme::strip_colors(frame.buffer_mut(), wizard.theme.muted);
+            }
+        })?;
+        wizard.poll_download().await;
+
+        tokio::select! {
+            event = events.next() => {
+                match event {
+                    Some(AppEvent::Key(key)) => wizard.handle_key(key),
+                    Some(AppEvent::Paste(text)) => wizard.paste(&text),
+                    _ => {}
+                }
+            }
+            _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+        }
+
+        if wizard.quit {
+            if let Some(task) = wizard.download.take() {
+                task.abort();
+            }
+            return Ok(false);
+        }
+        if wizard.finished {
+            return Ok(true);
+        }
+    }
+}
+
+fn render(wizard: &mut Wizard, frame: &mut Frame) {
+    let theme = wizard.theme;
+    let area = frame.area();
+    let width = 72.min(area.width.saturating_sub(4));
+    let height = 22.min(area.height.saturating_sub(2));
+    let area = Rect::new((area.width - width) / 2, (area.height - height) / 2, width, height);
+
+    let position = Step::ALL.iter().position(|s| *s == wizard.step).unwrap_or(0);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Escrituras setup · {} ({}/{}) ", wizard.step.title(), position + 1, Step::ALL.len()))
+        .title_bottom(Line::from(" Ctrl-c quit ").right_aligned());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [body_area, status_area] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(2),
+    ])
+    .areas(inner);
+
+    let muted = Style::default().fg(theme.muted);
+    let key = Style::default().fg(theme.key_fg).bg(theme.key_bg);
+    let text = |s: &str| Line::from(s.to_string());
+    let choice = |k: &str, label: &str| Line::from(vec![Span::styled(format!(" {} ", k), key), Span::raw(format!(" {}", label))]);
+
+    let lines: Vec<Line> = match wizard.step {
+        Step::Welcome => vec![
+            Line::from(Span::styled("Welcome to Escrituras", Style::default().fg(theme.heading).bold())),
+            Line::default(),
+            text("Let's get you set up: the scripture text, an AI provider for questions,"),
+            text("and (optionally) the model for searching by meaning."),
+            Line::default(),
+            choice("Enter", "Begin"),
+            choice("Esc", "Quit"),
+        ],
+        Step::Data => {
+            let mut lines = match &wizard.data_path {
+                Some(path) => vec![
+                    text("Scripture data found:"),
+                    Line::from(Span::styled(path.display().to_string(), Style::default().fg(theme.secondary))),
+                    Line::default(),
+                    choice("Enter", "Continue"),
+                ],
+                None => vec![
+                    text("The scripture text isn't installed yet."),
+                    Line::default(),
+                    choice("d", "Download it from the latest release (with search embeddings)"),
+                    choice("p", "Use a copy you already have (lds-scriptures-json.txt)"),
+                ],
+            };
+            if let Some(input) = &wizard.path_input {
+                lines.extend([
+                    Line::default(),
+                    text("Path to lds-scriptures-json.txt or its folder (Enter to copy, Esc to cancel):"),
+                    Line::from(Span::styled(format!("> {}", input), Style::default().fg(theme.accent))),
+                ]);
+            }
+            lines
+        }
+        Step::Provider => {
+            let [intro_area, list_area] = Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ])
+            .areas(body_area);
+            frame.render_widget(
+                Paragraph::new(vec![text("Which AI should answer your questions?"), Line::from(Span::styled("j/k choose · Enter select", muted))]),
+                intro_area,
+            );
+            let items: Vec<ListItem> = wizard
+                .providers
+                .iter()
+                .map(|p| {
+                    let note = match p {
+                        Provider::Ollama => "runs locally, free; needs Ollama installed",
+                        Provider::Claude => "Anthropic API key",
+                        Provider::OpenAI => "OpenAI API key",
+                    };
+                    ListItem::new(Line::from(vec![Span::raw(p.display_name()), Span::styled(format!("  {}", note), muted)]))
+                })
+                .collect();
+            let list = List::new(items)
+                .highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg).add_modifier(Modifier::BOLD))
+                .highlight_symbol("> ");
+            frame.render_stateful_widget(list, list_area, &mut wizard.provider_state);
+            Vec::new()
+        }
+        Step::ApiKey => vec![
+            text(&format!("Paste your {} API key:", wizard.provider().display_name())),
+            Line::default(),
+            Line::from(Span::styled(format!("> {}", "*".repeat(wizard.key_input.chars().count())), Style::default().fg(theme.accent))),
+            Line::default(),
+            Line::from(Span::styled("Saved to ~/.config/escrituras/config.toml.", muted)),
+            choice("Enter", "Save"),
+            choice("Esc", "Skip (add it later from the provider picker, P)"),
+        ],
+        Step::Embeddings => {
+            let mut lines = vec![
+                text("Semantic search finds verses by meaning (\"hope in hard times\"),"),
+                text("not just matching words. It needs a small local model (about 130 MB)."),
+                Line::default(),
+            ];
+            if let Some(var) = Wizard::env_key(wizard.provider()) {
+                lines.insert(0, Line::from(Span::styled(format!("Using {} from your environment.", var), muted)));
+                lines.insert(1, Line::default());
+            }
+            if wizard.model_cached {
+                lines.push(choice("Enter", "Continue"));
+            } else {
+                lines.push(choice("y", "Download it now"));
+                lines.push(choice("n", "Skip (it downloads on first semantic search)"));
+            }
+            lines
+        }
+        Step::Done => vec![
+            Line::from(Span::styled("You're all set.", Style::default().fg(theme.heading).bold())),
+            Line::default(),
+            text(&format!("AI provider: {}", wizard.provider().display_name())),
+            Line::default(),
+            text("Press ? anytime for the keys, or Ctrl-p for the command palette."),
+            Line::default(),
+            choice("Enter", "Start reading"),
+        ],
+    };
+    if !lines.is_empty() {
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), body_area);
+    }
+
+    if let Some((is_error, message)) = &wizard.status {
+        let color = if *is_error { theme.error } else if wizard.download.is_some() { theme.warning } else { theme.success };
+        frame.render_widget(
+            Paragraph::new(message.as_str()).style(Style::default().fg(color)).wrap(Wrap { trim: true }),
+            status_area,
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Layout for the multi-line AI question box
+//!
+//! The text is a plain `String` with a char-index cursor (as in the other
+//! inputs); these helpers hard-wrap it into rows of the box's width so the box
+//! can grow, place the cursor, and move it between rows. Widths are terminal
+//! columns, so wide characters take two and combining marks stay with their
+//! base character.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+/// Rows shown before the box scrolls instead of growing
+pub const MAX_ROWS: usize = 6;
+
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Columns taken by the first `chars` characters of `text`, for placing a cursor
+pub fn width_before(text: &str, chars: usize) -> usize {
+    text.chars().take(chars).map(char_width).sum()
+}
+
+/// (first char index, length in chars) of each row. Newlines start a new row,
+/// and a line that exactly fills its last row gets an empty row after it for the cursor.
+fn rows(text: &str, width: usize) -> Vec<(usize, usize)> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut start = 0;
+    for line in text.split('\n') {
+        let (mut row_start, mut row_len, mut row_width) = (start, 0, 0);
+        for c in line.chars() {
+            let w = char_width(c);
+            if w > 0 && row_len > 0 && row_width + w > width {
+                rows.push((row_start, row_len));
+                (row_start, row_len, row_width) = (row_start + row_len, 0, 0);
+            }
+            row_len += 1;
+            row_width += w;
+        }
+        rows.push((row_start, row_len));
+        if row_width >= width {
+            rows.push((row_start + row_len, 0));
+        }
+        start = row_start + row_len + 1;
+    }
+    rows
+}
+
+/// The text of each row
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    rows(text, width)
+        .into_iter()
+        .map(|(start, len)| chars[start..start + len].iter().collect())
+        .collect()
+}
+
+/// Row and column of the cursor
+pub fn cursor_position(text: &str, cursor: usize, width: usize) -> (usize, usize) {
+    let rows = rows(text, width);
+    let row = rows.iter().rposition(|(start, _)| *start <= cursor).unwrap_or(0);
+    let col = text.chars().skip(rows[row].0).take(cursor - rows[row].0).map(char_width).sum();
+    (row, col)
+}
+
+/// The cursor moved `delta` rows, keeping its column where the row is long
+/// enough; unchanged at the first or last row
+pub fn move_vertical(text: &str, cursor: usize, width: usize, delta: isize) -> usize {
+    let (row, col) = cursor_position(text, cursor, width);
+    let rows = rows(text, width);
+    let Some(&(start, len)) = row.checked_add_signed(delta).and_then(|r| rows.get(r)) else {
+        return cursor;
+    };
+    let mut index = start;
+    let mut used = 0;
+    for c in text.chars().skip(start).take(len) {
+        let w = char_width(c);
+        if w > 0 && used + w > col {
+            break;
+        }
+        used += w;
+        index += 1;
+    }
+    index
+}
+
+/// Char index of the start of the grapheme before `cursor`, for Left and Backspace
+pub fn prev_grapheme(text: &str, cursor: usize) -> usize {
+    let byte = char_to_byte(text, cursor);
+    let start = text[..byte].grapheme_indices(true).next_back().map_or(0, |(i, _)| i);
+    text[..start].chars().count()
+}
+
+/// Char index of the end of the grapheme after `cursor`, for Right and Delete
+pub fn next_grapheme(text: &str, cursor: usize) -> usize {
+    let byte = char_to_byte(text, cursor);
+    let len = text[byte..].graphemes(true).next().map_or(0, str::len);
+    text[..byte + len].chars().count()
+}
+
+fn char_to_byte(text: &str, chars: usize) -> usize {
+    text.char_indices().nth(chars).map_or(text.len(), |(i, _)| i)
+}
+
+/// Pasted text with Windows and old Mac line endings turned into `\n`
+pub fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_breaks_on_width_and_newlines() {
+        assert_eq!(wrap("abcdefg\nhi", 3), ["abc", "def", "g", "hi"]);
+        assert_eq!(wrap("abc", 3), ["abc", ""]);
+        assert_eq!(wrap("", 3), [""]);
+        assert_eq!(cursor_position("abcdefg\nhi", 3, 3), (1, 0));
+        assert_eq!(cursor_position("abcdefg\nhi", 7, 3), (2, 1));
+        assert_eq!(cursor_position("abcdefg\nhi", 10, 3), (3, 2));
+    }
+
+    #[test]
+    fn test_move_vertical_keeps_column() {
+        let text = "abcdefg\nhi";
+        assert_eq!(move_vertical(text, 5, 3, -1), 2);
+        assert_eq!(move_vertical(text, 5, 3, 1), 7);
+        assert_eq!(move_vertical(text, 7, 3, 1), 9);
+        assert_eq!(move_vertical(text, 1, 3, -1), 1);
+        assert_eq!(normalize_newlines("a\r\nb\rc"), "a\nb\nc");
+    }
+
+    #[test]
+    fn test_wide_and_combining_characters() {
+        // Each of these takes two columns
+        assert_eq!(wrap("日本語", 4), ["日本", "語"]);
+        assert_eq!(cursor_position("日本語", 1, 4), (0, 2));
+        // "é" as e + combining acute is one column and one grapheme
+        let text = "cafe\u{301} y";
+        assert_eq!(wrap(text, 4), ["cafe\u{301}", " y"]);
+        assert_eq!(width_before(text, 5), 4);
+        assert_eq!(prev_grapheme(text, 5), 3);
+        assert_eq!(next_grapheme(text, 3), 5);
+    }
+}
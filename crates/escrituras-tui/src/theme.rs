@@ -0,0 +1,461 @@
+//! Color themes
+//!
+//! ui.rs never names a color directly; it asks the active `Theme` for a role
+//! (accent, muted, selection, ...). Built-in themes cover dark, light, and
+//! solarized terminals plus a high-contrast option, and users can define their
+//! own palettes in `theme.toml` next to the config:
+//!
+//! ```toml
+//! theme = "mine"
+//!
+//! [palettes.mine]
+//! base = "solarized"
+//! accent = "#268bd2"
+//! heading = "yellow"
+//! ```
+//!
+//! Whatever the theme, colors are reduced to what the terminal can show (see
+//! `ColorSupport`), and `NO_COLOR` turns them off in favor of reverse video.
+
+use anyhow::{anyhow, Result};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Colors for each UI role
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub text: Color,         // Body text on bars and popups
+    pub accent: Color,       // Focused borders, popup borders, typed input
+    pub muted: Color,        // Unfocused borders, placeholders, hints
+    pub heading: Color,      // Verse numbers, titles, editing borders
+    pub selection_fg: Color, // Selected row / cursor verse
+    pub selection_bg: Color,
+    pub range_fg: Color,     // Text drawn on accent backgrounds
+    pub range_bg: Color,     // Background of verses in the selected range
+    pub secondary: Color,    // References panel, visual mode
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub bar_bg: Color,       // Header/footer bar background
+    pub key_fg: Color,       // Footer key hints
+    pub key_bg: Color,
+}
+
+/// Colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// `NO_COLOR` is set or the terminal is dumb: attributes only
+    None,
+    /// The 16 ANSI colors
+    Basic,
+    /// The xterm 256-color palette
+    Indexed,
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// From `NO_COLOR`, `COLORTERM`, and `TERM`
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self::from_env(var("NO_COLOR").as_deref(), var("COLORTERM").as_deref(), var("TERM").as_deref())
+    }
+
+    fn from_env(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        // https://no-color.org: set and not empty
+        if no_color.is_some_and(|v| !v.is_empty()) {
+            return ColorSupport::None;
+        }
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorSupport::TrueColor;
+        }
+        match term {
+            Some("dumb") => ColorSupport::None,
+            Some(t) if t.ends_with("-direct") => ColorSupport::TrueColor,
+            Some(t) if t.contains("256") => ColorSupport::Indexed,
+            Some(_) => ColorSupport::Basic,
+            // Windows consoles don't set TERM, and have had true color since Windows 10
+            None if cfg!(windows) => ColorSupport::TrueColor,
+            None => ColorSupport::Basic,
+        }
+    }
+}
+
+/// The 16 ANSI colors in index order, with xterm's default values
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 color cube in the 256-color palette
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// RGB value of a 256-color palette entry
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            (CUBE[(i / 36) as usize], CUBE[(i / 6 % 6) as usize], CUBE[(i % 6) as usize])
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Closest 256-color palette entry, from the color cube or the gray ramp
+fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    let level = |c: u8| (0..6).min_by_key(|&i| (CUBE[i] as i32 - c as i32).abs()).unwrap_or(0) as u8;
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+    if distance(indexed_rgb(gray), rgb) < distance(indexed_rgb(cube), rgb) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn nearest_ansi(rgb: (u8, u8, u8)) -> Color {
+    ANSI.iter().min_by_key(|(_, value)| distance(*value, rgb)).map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// `color` as the closest one the terminal can show
+fn reduce(color: Color, colors: ColorSupport) -> Color {
+    match (colors, color) {
+        (ColorSupport::Indexed, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed((r, g, b))),
+        (ColorSupport::Basic, Color::Rgb(r, g, b)) => nearest_ansi((r, g, b)),
+        (ColorSupport::Basic, Color::Indexed(i)) if i < 16 => ANSI[i as usize].0,
+        (ColorSupport::Basic, Color::Indexed(i)) => nearest_ansi(indexed_rgb(i)),
+        _ => color,
+    }
+}
+
+/// For `ColorSupport::None`: take the colors out of a drawn frame. Anything
+/// drawn on a background (selections, key hints) is shown in reverse video
+/// instead, and muted text is dimmed.
+pub fn strip_colors(buffer: &mut Buffer, muted: Color) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        if cell.fg == muted {
+            cell.modifier.insert(Modifier::DIM);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+        cell.underline_color = Color::Reset;
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::terminal()
+    }
+}
+
+impl Theme {
+    /// Names accepted by `theme = "..."` and `base = "..."`
+    pub const BUILT_IN: &'static [&'static str] = &["default", "dark", "light", "solarized", "high-contrast"];
+
+    pub fn built_in(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Self::terminal()),
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Follows the terminal's own foreground/background, so it reads on light and dark backgrounds
+    pub fn terminal() -> Self {
+        Self {
+            text: Color::Reset,
+            accent: Color::Cyan,
+            muted: Color::DarkGray,
+            heading: Color::Magenta,
+            selection_fg: Color::White,
+            selection_bg: Color::Blue,
+            range_fg: Color::Black,
+            range_bg: Color::Cyan,
+            secondary: Color::Blue,
+            success: Color::Green,
+            warning: Color::Magenta,
+            error: Color::Red,
+            bar_bg: Color::Reset,
+            key_fg: Color::White,
+            key_bg: Color::DarkGray,
+        }
+    }
+
+    /// The original palette, tuned for dark backgrounds
+    pub fn dark() -> Self {
+        Self {
+            text: Color::White,
+            accent: Color::Cyan,
+            muted: Color::DarkGray,
+            heading: Color::Yellow,
+            selection_fg: Color::White,
+            selection_bg: Color::Blue,
+            range_fg: Color::Black,
+            range_bg: Color::DarkGray,
+            secondary: Color::Magenta,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            bar_bg: Color::Black,
+            key_fg: Color::White,
+            key_bg: Color::DarkGray,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            accent: Color::Blue,
+            muted: Color::Indexed(243),
+            heading: Color::Indexed(130),
+            selection_fg: Color::White,
+            selection_bg: Color::Blue,
+            range_fg: Color::White,
+            range_bg: Color::Indexed(253),
+            secondary: Color::Magenta,
+            success: Color::Indexed(28),
+            warning: Color::Indexed(130),
+            error: Color::Red,
+            bar_bg: Color::Indexed(254),
+            key_fg: Color::Black,
+            key_bg: Color::Indexed(250),
+        }
+    }
+
+    pub fn solarized() -> Self {
+        Self {
+            text: Color::Rgb(147, 161, 161),
+            accent: Color::Rgb(42, 161, 152),
+            muted: Color::Rgb(88, 110, 117),
+            heading: Color::Rgb(181, 137, 0),
+            selection_fg: Color::Rgb(253, 246, 227),
+            selection_bg: Color::Rgb(38, 139, 210),
+            range_fg: Color::Rgb(0, 43, 54),
+            range_bg: Color::Rgb(7, 54, 66),
+            secondary: Color::Rgb(211, 54, 130),
+            success: Color::Rgb(133, 153, 0),
+            warning: Color::Rgb(203, 75, 22),
+            error: Color::Rgb(220, 50, 47),
+            bar_bg: Color::Rgb(0, 43, 54),
+            key_fg: Color::Rgb(147, 161, 161),
+            key_bg: Color::Rgb(7, 54, 66),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            text: Color::White,
+            accent: Color::LightCyan,
+            muted: Color::Gray,
+            heading: Color::LightYellow,
+            selection_fg: Color::Black,
+            selection_bg: Color::LightYellow,
+            range_fg: Color::Black,
+            range_bg: Color::Blue,
+            secondary: Color::LightMagenta,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            bar_bg: Color::Black,
+            key_fg: Color::Black,
+            key_bg: Color::White,
+        }
+    }
+
+    /// The theme with each color swapped for the closest the terminal can show.
+    /// Without color, the default theme stands in, since `strip_colors` keys off
+    /// its muted color and bar backgrounds that match the terminal's.
+    pub fn for_terminal(self, colors: ColorSupport) -> Self {
+        if colors == ColorSupport::None {
+            return Self::terminal();
+        }
+        let mut theme = self;
+        for slot in [
+            &mut theme.text,
+            &mut theme.accent,
+            &mut theme.muted,
+            &mut theme.heading,
+            &mut theme.selection_fg,
+            &mut theme.selection_bg,
+            &mut theme.range_fg,
+            &mut theme.range_bg,
+            &mut theme.secondary,
+            &mut theme.success,
+            &mut theme.warning,
+            &mut theme.error,
+            &mut theme.bar_bg,
+            &mut theme.key_fg,
+            &mut theme.key_bg,
+        ] {
+            *slot = reduce(*slot, colors);
+        }
+        theme
+    }
+
+    /// The theme named by `name` (the config's `theme`), else the one selected
+    /// in theme.toml, or the default if there is neither
+    pub fn load(name: Option<&str>) -> Result<Self> {
+        Self::load_from(&Self::get_theme_path()?, name)
+    }
+
+    pub fn load_from(path: &Path, name: Option<&str>) -> Result<Self> {
+        let content = if path.exists() { fs::read_to_string(path)? } else { String::new() };
+        Self::parse(&content, name)
+    }
+
+    /// Palettes are always read from theme.toml, so `name` can pick one of them
+    fn parse(content: &str, name: Option<&str>) -> Result<Self> {
+        let table: toml::Table = content.parse()?;
+        let name = match (name, table.get("theme")) {
+            (Some(name), _) => name,
+            (None, Some(value)) => value.as_str().ok_or_else(|| anyhow!("theme must be a string"))?,
+            (None, None) => "default",
+        };
+
+        if let Some(theme) = Self::built_in(name) {
+            return Ok(theme);
+        }
+
+        let palette = table
+            .get("palettes")
+            .and_then(|p| p.get(name))
+            .and_then(|p| p.as_table())
+            .ok_or_else(|| anyhow!("Unknown theme '{}' (built-in: {})", name, Self::BUILT_IN.join(", ")))?;
+
+        let base = match palette.get("base") {
+            Some(value) => value.as_str().ok_or_else(|| anyhow!("base must be a string"))?,
+            None => "default",
+        };
+        let mut theme = Self::built_in(base)
+            .ok_or_else(|| anyhow!("Palette '{}' has unknown base '{}'", name, base))?;
+
+        for (role, value) in palette.iter().filter(|(role, _)| role.as_str() != "base") {
+            let spec = value
+                .as_str()
+                .ok_or_else(|| anyhow!("{}.{} must be a color string", name, role))?;
+            let color = spec
+                .parse::<Color>()
+                .map_err(|_| anyhow!("Invalid color '{}' for {}.{}", spec, name, role))?;
+            theme.set(role, color)?;
+        }
+        Ok(theme)
+    }
+
+    fn set(&mut self, role: &str, color: Color) -> Result<()> {
+        let slot = match role {
+            "text" => &mut self.text,
+            "accent" => &mut self.accent,
+            "muted" => &mut self.muted,
+            "heading" => &mut self.heading,
+            "selection_fg" => &mut self.selection_fg,
+            "selection_bg" => &mut self.selection_bg,
+            "range_fg" => &mut self.range_fg,
+            "range_bg" => &mut self.range_bg,
+            "secondary" => &mut self.secondary,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "bar_bg" => &mut self.bar_bg,
+            "key_fg" => &mut self.key_fg,
+            "key_bg" => &mut self.key_bg,
+            _ => return Err(anyhow!("Unknown theme color '{}'", role)),
+        };
+        *slot = color;
+        Ok(())
+    }
+
+    fn get_theme_path() -> Result<PathBuf> {
+        Ok(escrituras_core::paths::config_dir()?.join("theme.toml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_theme_by_name() {
+        assert_eq!(Theme::parse("theme = \"light\"", None).unwrap(), Theme::light());
+        assert_eq!(Theme::parse("", None).unwrap(), Theme::default());
+        for name in Theme::BUILT_IN {
+            assert!(Theme::built_in(name).is_some(), "{}", name);
+        }
+        // The config's theme wins over theme.toml's, and can name its palettes
+        assert_eq!(Theme::parse("theme = \"light\"", Some("dark")).unwrap(), Theme::dark());
+        let mine = Theme::parse("[palettes.mine]\nbase = \"light\"\n", Some("mine")).unwrap();
+        assert_eq!(mine, Theme::light());
+    }
+
+    #[test]
+    fn test_user_palette_overrides_base() {
+        let theme = Theme::parse(
+            "theme = \"mine\"\n[palettes.mine]\nbase = \"dark\"\naccent = \"#268bd2\"\nheading = \"light green\"\n",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(theme.accent, Color::Rgb(0x26, 0x8b, 0xd2));
+        assert_eq!(theme.heading, Color::LightGreen);
+        assert_eq!(theme.muted, Theme::dark().muted);
+    }
+
+    #[test]
+    fn test_color_support_from_env() {
+        assert_eq!(ColorSupport::from_env(Some("1"), Some("truecolor"), Some("xterm-256color")), ColorSupport::None);
+        assert_eq!(ColorSupport::from_env(Some(""), None, Some("xterm-256color")), ColorSupport::Indexed);
+        assert_eq!(ColorSupport::from_env(None, Some("24bit"), Some("xterm")), ColorSupport::TrueColor);
+        assert_eq!(ColorSupport::from_env(None, None, Some("linux")), ColorSupport::Basic);
+        assert_eq!(ColorSupport::from_env(None, None, Some("dumb")), ColorSupport::None);
+    }
+
+    #[test]
+    fn test_colors_reduced_for_terminal() {
+        let indexed = Theme::solarized().for_terminal(ColorSupport::Indexed);
+        assert_eq!(indexed.selection_bg, Color::Indexed(32)); // (38, 139, 210) -> (0, 135, 215)
+        assert_eq!(indexed.bar_bg, Color::Indexed(234)); // (0, 43, 54) -> gray 28
+
+        let basic = Theme::light().for_terminal(ColorSupport::Basic);
+        assert_eq!(basic.muted, Color::DarkGray);
+        assert_eq!(basic.bar_bg, Color::Gray);
+        assert_eq!(Theme::solarized().for_terminal(ColorSupport::Basic).error, Color::Red);
+        assert_eq!(Theme::dark().for_terminal(ColorSupport::None), Theme::terminal());
+    }
+
+    #[test]
+    fn test_invalid_theme_is_error() {
+        assert!(Theme::parse("theme = \"nope\"", None).is_err());
+        assert!(Theme::parse("theme = \"x\"\n[palettes.x]\naccent = \"not a color\"", None).is_err());
+        assert!(Theme::parse("theme = \"x\"\n[palettes.x]\nsparkle = \"red\"", None).is_err());
+    }
+}
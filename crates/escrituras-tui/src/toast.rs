@@ -0,0 +1,119 @@
+//! Transient status messages
+//!
+//! Actions report what happened ("Copied", "Saved to context", a failed config
+//! write) by pushing a toast. ui.rs stacks the live toasts in the bottom-right
+//! corner, and the main loop drops them once their level's timeout passes.
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    /// How long a toast stays up; problems linger so they can be read
+    pub fn timeout(&self) -> Duration {
+        match self {
+            ToastLevel::Info | ToastLevel::Success => Duration::from_secs(3),
+            ToastLevel::Warning | ToastLevel::Error => Duration::from_secs(6),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+/// Live toasts, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    items: Vec<Toast>,
+}
+
+impl Toasts {
+    /// At most this many are shown; older ones are dropped early
+    const MAX_VISIBLE: usize = 4;
+
+    pub fn push(&mut self, level: ToastLevel, message: impl Into<String>) {
+        let message = message.into();
+        // Repeating the same action refreshes its toast instead of stacking copies
+        self.items.retain(|t| t.message != message);
+        self.items.push(Toast {
+            level,
+            message,
+            shown_at: Instant::now(),
+        });
+        let excess = self.items.len().saturating_sub(Self::MAX_VISIBLE);
+        self.items.drain(..excess);
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Info, message);
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Success, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(ToastLevel::Error, message);
+    }
+
+    /// Drop toasts whose timeout has passed
+    /// Drop toasts shown for long enough; true if any were
+    pub fn expire(&mut self) -> bool {
+        self.expire_at(Instant::now())
+    }
+
+    fn expire_at(&mut self, now: Instant) -> bool {
+        let count = self.items.len();
+        self.items
+            .retain(|t| now.duration_since(t.shown_at) < t.level.timeout());
+        self.items.len() != count
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.items.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_by_level() {
+        let mut toasts = Toasts::default();
+        toasts.success("Copied");
+        toasts.error("Could not save config");
+
+        assert!(!toasts.expire_at(Instant::now()));
+        assert!(toasts.expire_at(Instant::now() + Duration::from_secs(4)));
+        let left: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(left, ["Could not save config"]);
+
+        toasts.expire_at(Instant::now() + Duration::from_secs(7));
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn test_repeated_toast_is_refreshed_not_stacked() {
+        let mut toasts = Toasts::default();
+        toasts.success("Copied");
+        toasts.info("Saved");
+        toasts.success("Copied");
+        let messages: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, ["Saved", "Copied"]);
+    }
+}
@@ -1,13 +1,21 @@
 use std::io::{self, Stderr};
-use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use anyhow::{anyhow, Result};
 use crossterm::{
-    event::{self, Event, KeyEvent, KeyEventKind, MouseEvent},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyEvent,
+        KeyEventKind, KeyboardEnhancementFlags, MouseEvent, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use futures_util::StreamExt;
+use std::path::Path;
+use std::process::Command;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use escrituras_core::logging;
 
 pub type Tui = Terminal<CrosstermBackend<Stderr>>;
 
@@ -17,12 +25,14 @@ pub enum AppEvent {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Resize(u16, u16),
+    Paste(String),
     Tick,
 }
 
 pub struct EventHandler {
     rx: mpsc::UnboundedReceiver<AppEvent>,
     _tx: mpsc::UnboundedSender<AppEvent>,
+    tasks: Vec<JoinHandle<()>>,
 }
 
 impl EventHandler {
@@ -32,7 +42,7 @@ impl EventHandler {
 
         // Spawn event reader task
         let tx_events = tx.clone();
-        tokio::spawn(async move {
+        let reader_task = tokio::spawn(async move {
             let mut reader = event::EventStream::new();
             loop {
                 if let Some(Ok(evt)) = reader.next().await {
@@ -47,6 +57,7 @@ impl EventHandler {
                         }
                         Event::Mouse(mouse) => Some(AppEvent::Mouse(mouse)),
                         Event::Resize(w, h) => Some(AppEvent::Resize(w, h)),
+                        Event::Paste(text) => Some(AppEvent::Paste(text)),
                         _ => None,
                     };
 
@@ -61,7 +72,7 @@ impl EventHandler {
 
         // Spawn tick timer for animations (300ms interval)
         let tx_tick = tx.clone();
-        tokio::spawn(async move {
+        let tick_task = tokio::spawn(async move {
             let mut interval = tokio::time::interval(std::time::Duration::from_millis(300));
             loop {
                 interval.tick().await;
@@ -71,7 +82,7 @@ impl EventHandler {
             }
         });
 
-        Self { rx, _tx }
+        Self { rx, _tx, tasks: vec![reader_task, tick_task] }
     }
 
     pub async fn next(&mut self) -> Option<AppEvent> {
@@ -79,12 +90,20 @@ impl EventHandler {
     }
 }
 
-pub fn init() -> Result<Tui> {
-    enable_raw_mode()?;
-    execute!(io::stderr(), EnterAlternateScreen)?;
+// Stop reading the terminal so a child process (e.g. $EDITOR) gets every key
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+// Whether the terminal was asked to report modified keys like Shift-Enter
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
 
-    // Enable mouse capture
-    execute!(io::stderr(), crossterm::event::EnableMouseCapture)?;
+pub fn init() -> Result<Tui> {
+    enter()?;
 
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
@@ -92,10 +111,53 @@ pub fn init() -> Result<Tui> {
     Ok(terminal)
 }
 
+/// Raw mode, the alternate screen, mouse capture, and bracketed paste (so a
+/// pasted multi-line question arrives as one event rather than as keystrokes)
+fn enter() -> Result<()> {
+    // Log messages would be drawn over the screen
+    logging::set_terminal_in_use(true);
+    enable_raw_mode()?;
+    execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+
+    // Terminals using the kitty keyboard protocol can tell Shift-Enter from Enter
+    if matches!(supports_keyboard_enhancement(), Ok(true)) {
+        execute!(io::stderr(), PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES))?;
+        KEYBOARD_ENHANCED.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
 pub fn restore() -> Result<()> {
-    execute!(io::stderr(), crossterm::event::DisableMouseCapture)?;
+    if KEYBOARD_ENHANCED.swap(false, Ordering::Relaxed) {
+        execute!(io::stderr(), PopKeyboardEnhancementFlags)?;
+    }
+    execute!(io::stderr(), DisableBracketedPaste, DisableMouseCapture)?;
     execute!(io::stderr(), LeaveAlternateScreen)?;
     disable_raw_mode()?;
+    logging::set_terminal_in_use(false);
+    Ok(())
+}
+
+/// Leave the TUI, edit `path` in $VISUAL / $EDITOR (vi if unset), then take the terminal back
+///
+/// Drop the `EventHandler` first so it isn't competing with the editor for input.
+pub fn run_editor(terminal: &mut Tui, path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad".to_string() } else { "vi".to_string() });
+    // Allow editors with arguments, e.g. EDITOR="code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| anyhow!("$EDITOR is empty"))?;
+
+    restore()?;
+    let status = Command::new(program).args(parts).arg(path).status();
+    enter()?;
+    terminal.clear()?;
+
+    let status = status.map_err(|e| anyhow!("Couldn't run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(anyhow!("{} exited with {}", program, status));
+    }
     Ok(())
 }
 
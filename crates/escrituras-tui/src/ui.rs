@@ -1,12 +1,19 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
-use crate::app::{App, FlashcardPhase, FocusPane, FocusSubMode, InputMode, MemorizeMode, NavLevel, Screen, SearchFocus};
-use escrituras_core::{Provider, Scripture};
+use crate::keymap::{Action, KeyContext};
+use crate::textarea;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::theme::{self, ColorSupport, Theme};
+use crate::toast::ToastLevel;
+use crate::app::{App, FilterDropdown, FlashcardPhase, FocusPane, FocusSubMode, InputMode, MemorizeMode, NavLevel, Screen, SearchFocus, TalkLevel, TopicRow};
+use escrituras_core::memorize::{compute_word_diff, passage_verse_accuracies, word_accuracy, DiffResult, WordStatus};
+use escrituras_core::outline::{OutlineSource, SectionKind};
+use escrituras_core::{CopyFormat, MatchSource, Provider, Scripture, Title, Topic, TOPICS};
 
 /// Ensure the selected item in a list is visible by adjusting the ListState offset.
 /// This clamps the offset to a valid range where the selected item is always visible.
@@ -31,7 +38,8 @@ fn ensure_selected_visible(state: &mut ListState, visible_height: usize) {
 }
 
 /// Wrap text to fit within a given width, returning multiple lines
-/// Uses word boundaries for wrapping (doesn't break mid-word)
+/// Uses word boundaries for wrapping (doesn't break mid-word) and measures
+/// terminal columns, so wide characters count double and combining marks not at all
 fn wrap_text_to_width(text: &str, width: usize) -> Vec<String> {
     if width == 0 {
         return vec![text.to_string()];
@@ -42,7 +50,7 @@ fn wrap_text_to_width(text: &str, width: usize) -> Vec<String> {
     let mut current_len = 0;
 
     for word in text.split_whitespace() {
-        let word_len = word.chars().count();
+        let word_len = word.width();
 
         if current_len == 0 {
             // First word on line
@@ -73,6 +81,16 @@ fn wrap_text_to_width(text: &str, width: usize) -> Vec<String> {
     lines
 }
 
+/// Border for the main panes: all around, or just the title bar in compact layout
+fn pane_block(compact: bool) -> Block<'static> {
+    Block::default().borders(if compact { Borders::TOP } else { Borders::ALL })
+}
+
+/// `text` padded with spaces to `width` columns, for highlighting a full line
+fn pad_to_width(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
+
 /// Pre-calculated layout information for a single verse
 struct VerseLayout {
     verse_idx: usize,       // Index into cached_verses
@@ -87,15 +105,16 @@ struct ChapterLayout {
     total_lines: usize,     // Total lines in the chapter
 }
 
-/// Calculate the line-based layout for all verses in a chapter
-fn calculate_chapter_layout(verses: &[Scripture], width: usize) -> ChapterLayout {
+/// Calculate the line-based layout for all verses in a chapter; `compact`
+/// leaves out the blank line between verses
+fn calculate_chapter_layout(verses: &[Scripture], width: usize, compact: bool) -> ChapterLayout {
     let mut layouts = Vec::with_capacity(verses.len());
     let mut current_line = 0;
 
     for (idx, verse) in verses.iter().enumerate() {
-        // Calculate the verse number prefix width (e.g., "12  " = 4 chars)
+        // Calculate the verse number prefix width (e.g., "12  " = 4 columns)
         let num_prefix = format!("{}  ", verse.verse_number);
-        let prefix_len = num_prefix.chars().count();
+        let prefix_len = num_prefix.width();
 
         // First line has less available width due to verse number prefix
         let first_line_width = width.saturating_sub(prefix_len);
@@ -112,12 +131,11 @@ fn calculate_chapter_layout(verses: &[Scripture], width: usize) -> ChapterLayout
 
                 // If there's remaining text after the first line, wrap it at full width
                 if first_line_wrapped.len() > 1 {
-                    // Reconstruct remaining text and rewrap at full width
-                    let first_line_chars: usize = first_line_wrapped[0].chars().count();
-                    let remaining: String = text.chars().skip(first_line_chars).collect();
-                    let remaining = remaining.trim_start();
+                    // Rewrap the words after the first line at full width
+                    let first_line_words = first_line_wrapped[0].split_whitespace().count();
+                    let remaining: Vec<&str> = text.split_whitespace().skip(first_line_words).collect();
                     if !remaining.is_empty() {
-                        let rest_wrapped = wrap_text_to_width(remaining, width);
+                        let rest_wrapped = wrap_text_to_width(&remaining.join(" "), width);
                         wrapped.extend(rest_wrapped);
                     }
                 }
@@ -131,7 +149,7 @@ fn calculate_chapter_layout(verses: &[Scripture], width: usize) -> ChapterLayout
             wrapped.push(String::new());
         }
 
-        let line_count = wrapped.len() + 1; // +1 for blank line after verse
+        let line_count = wrapped.len() + usize::from(!compact); // +1 for blank line after verse
 
         layouts.push(VerseLayout {
             verse_idx: idx,
@@ -270,147 +288,330 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         Screen::Search => render_search_screen(app, frame, body_area),
         Screen::Query => render_query_screen(app, frame, body_area),
         Screen::Focus => render_focus_screen(app, frame, body_area),
+        Screen::Journal => render_journal_screen(app, frame, body_area),
+        Screen::Talks => render_talks_screen(app, frame, body_area),
+        Screen::ComeFollowMe => render_come_follow_me_screen(app, frame, body_area),
+        Screen::Topics => render_topics_screen(app, frame, body_area),
+        Screen::Reading => render_reading_screen(app, frame, body_area),
     }
 
     render_footer(app, frame, footer_area);
+    render_toasts(app, frame, body_area);
 
     // Render popups (in order of priority)
-    if app.show_api_key_input {
+    if app.votd.is_some() {
+        render_votd(app, frame, area);
+    } else if app.show_api_key_input {
         render_api_key_input(app, frame, area);
     } else if app.show_provider_picker {
         render_provider_picker(app, frame, area);
     } else if app.show_model_picker {
         render_model_picker(app, frame, area);
+    } else if app.show_goto_input {
+        render_goto_input(app, frame, area);
+    } else if app.tag_input.is_some() {
+        render_tag_input(app, frame, area);
+    } else if app.show_command_palette {
+        render_command_palette(app, frame, area);
+    } else if app.filter_dropdown.is_some() {
+        render_filter_dropdown(app, frame, area);
+    } else if app.show_copy_menu {
+        render_copy_menu(app, frame, area);
+    } else if app.word_lookup.is_some() {
+        render_word_lookup(app, frame, area);
+    } else if app.strongs_lookup.is_some() {
+        render_strongs_lookup(app, frame, area);
+    } else if app.show_help {
+        render_help(app, frame, area);
+    }
+
+    if app.colors == ColorSupport::None {
+        theme::strip_colors(frame.buffer_mut(), app.theme.muted);
+    }
+}
+
+/// Stack live toasts in the bottom-right corner of the body, newest at the bottom
+fn render_toasts(app: &App, frame: &mut Frame, area: Rect) {
+    use ratatui::widgets::Clear;
+    if app.toasts.is_empty() {
+        return;
+    }
+    let theme = app.theme;
+
+    let mut bottom = area.y + area.height;
+    for toast in app.toasts.iter().collect::<Vec<_>>().into_iter().rev() {
+        let width = (toast.message.width() as u16 + 4).min(area.width);
+        if bottom < area.y + 3 || width < 5 {
+            break;
+        }
+        let toast_area = Rect::new(area.x + area.width - width, bottom - 3, width, 3);
+        bottom -= 3;
+
+        let color = match toast.level {
+            ToastLevel::Info => theme.accent,
+            ToastLevel::Success => theme.success,
+            ToastLevel::Warning => theme.warning,
+            ToastLevel::Error => theme.error,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color));
+        let text = Paragraph::new(toast.message.as_str())
+            .style(Style::default().fg(theme.text))
+            .block(block);
+
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(text, toast_area);
     }
 }
 
 fn render_header(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
     let context_count = app.session_context_count();
     let context_indicator = if context_count > 0 {
         format!(" [{} saved]", context_count)
     } else {
         String::new()
     };
+    let profile_indicator = match &app.profile {
+        Some(name) => format!(" [profile: {}]", name),
+        None => String::new(),
+    };
 
     let title = Line::from(vec![
-        Span::styled(" Stick of Joseph, Stick of Judah ", Style::default().fg(Color::Cyan).bold()),
-        Span::styled(context_indicator, Style::default().fg(Color::DarkGray)),
+        Span::styled(" Stick of Joseph, Stick of Judah ", Style::default().fg(theme.accent).bold()),
+        Span::styled(profile_indicator, Style::default().fg(theme.heading)),
+        Span::styled(context_indicator, Style::default().fg(theme.muted)),
         Span::raw(" "),
         Span::styled(
             format!("v{}", env!("CARGO_PKG_VERSION")),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ]);
 
-    let header = Paragraph::new(title).style(Style::default().bg(Color::DarkGray));
+    let header = Paragraph::new(title).style(Style::default().bg(theme.key_bg));
     frame.render_widget(header, area);
 }
 
 fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let in_visual = app.visual_anchor.is_some();
+
     let mode_style = match app.input_mode {
-        InputMode::Normal => Style::default().bg(Color::Blue).fg(Color::White),
-        InputMode::Editing => Style::default().bg(Color::Yellow).fg(Color::Black),
+        InputMode::Normal if in_visual => Style::default().bg(theme.secondary).fg(theme.selection_fg),
+        InputMode::Normal => Style::default().bg(theme.selection_bg).fg(theme.selection_fg),
+        InputMode::Editing => Style::default().bg(theme.heading).fg(theme.range_fg),
     };
 
     let mode_text = match app.screen {
+        _ if in_visual => " VISUAL ",
         Screen::Browse => " BROWSE ",
         Screen::Search => " SEARCH ",
         Screen::Query => " AI ",
         Screen::Focus => " FOCUS ",
+        Screen::Journal => " JOURNAL ",
+        Screen::Talks => " TALKS ",
+        Screen::ComeFollowMe => " COME FOLLOW ME ",
+        Screen::Topics => " TOPICS ",
+        Screen::Reading => " READING ",
     };
 
-    // Key style: dark background with bright text for visibility on both light/dark terminals
-    let key_style = Style::default().bg(Color::DarkGray).fg(Color::White);
-    let label_style = Style::default().bg(Color::Black).fg(Color::White);
+    let key_style = Style::default().bg(theme.key_bg).fg(theme.key_fg);
+    let label_style = Style::default().bg(theme.bar_bg).fg(theme.text);
+
+    // Remappable keys are labelled from the active keymap
+    let keys = &app.keymap;
 
     let hints = match (app.screen, app.input_mode) {
+        _ if app.show_help => vec![
+            Span::styled(" j/k ", key_style),
+            Span::styled(" scroll ", label_style),
+            Span::styled(" Esc/? ", key_style),
+            Span::styled(" close ", label_style),
+        ],
+        _ if app.show_copy_menu => vec![
+            Span::styled(" j/k ", key_style),
+            Span::styled(" format ", label_style),
+            Span::styled(" Enter ", key_style),
+            Span::styled(" copy ", label_style),
+            Span::styled(" Esc ", key_style),
+            Span::styled(" cancel ", label_style),
+        ],
+        _ if app.show_command_palette => vec![
+            Span::styled(" ↑/↓ ", key_style),
+            Span::styled(" select ", label_style),
+            Span::styled(" Enter ", key_style),
+            Span::styled(" run ", label_style),
+            Span::styled(" Esc ", key_style),
+            Span::styled(" cancel ", label_style),
+        ],
+        _ if app.show_goto_input => vec![
+            Span::styled(" Enter ", key_style),
+            Span::styled(" go ", label_style),
+            Span::styled(" Esc ", key_style),
+            Span::styled(" cancel ", label_style),
+        ],
+        _ if in_visual => vec![
+            Span::styled(keys.nav_hint(KeyContext::Visual), key_style),
+            Span::styled(" extend ", label_style),
+            Span::styled(keys.hint(KeyContext::Visual, Action::Copy), key_style),
+            Span::styled(" copy ", label_style),
+            Span::styled(keys.hint(KeyContext::Visual, Action::Save), key_style),
+            Span::styled(" save ", label_style),
+            Span::styled(keys.hint(KeyContext::Visual, Action::AskAi), key_style),
+            Span::styled(" ask AI ", label_style),
+            Span::styled(keys.hint(KeyContext::Visual, Action::Exit), key_style),
+            Span::styled(" cancel ", label_style),
+        ],
         (Screen::Browse, InputMode::Normal) => {
             let mut hints = if app.focus == FocusPane::Content {
                 if app.show_context_panel {
                     // Saved scriptures panel is showing
                     vec![
-                        Span::styled(" j/k ", key_style),
+                        Span::styled(keys.nav_hint(KeyContext::Browse), key_style),
                         Span::styled(" nav ", label_style),
-                        Span::styled(" d ", key_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::Remove), key_style),
                         Span::styled(" remove ", label_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::ClearSaved), key_style),
+                        Span::styled(" clear all ", label_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::Tag), key_style),
+                        Span::styled(" tag ", label_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::FilterTag), key_style),
+                        Span::styled(" filter ", label_style),
                     ]
                 } else {
                     // Normal scripture content
                     vec![
-                        Span::styled(" j/k ", key_style),
+                        Span::styled(keys.nav_hint(KeyContext::Browse), key_style),
                         Span::styled(" verse ", label_style),
-                        Span::styled(" c ", key_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::Copy), key_style),
                         Span::styled(" copy ", label_style),
-                        Span::styled(" x ", key_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::Save), key_style),
                         Span::styled(" save ", label_style),
-                        Span::styled(" f ", key_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::FocusMode), key_style),
                         Span::styled(" focus ", label_style),
-                        Span::styled(" s ", key_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::Visual), key_style),
+                        Span::styled(" select ", label_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::SearchVerse), key_style),
                         Span::styled(" search ", label_style),
+                        Span::styled(keys.hint(KeyContext::Browse, Action::Split), key_style),
+                        Span::styled(if app.split.is_some() { " unsplit " } else { " split " }, label_style),
                     ]
                 }
+            } else if app.focus == FocusPane::References {
+                // Footnote pane
+                vec![
+                    Span::styled(keys.nav_hint(KeyContext::Browse), key_style),
+                    Span::styled(" footnote ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Select), key_style),
+                    Span::styled(" jump ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Back), key_style),
+                    Span::styled(" verse ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::ToggleFootnotes), key_style),
+                    Span::styled(" hide ", label_style),
+                ]
+            } else if app.focus == FocusPane::Related {
+                vec![
+                    Span::styled(keys.nav_hint(KeyContext::Browse), key_style),
+                    Span::styled(" related ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Select), key_style),
+                    Span::styled(" open ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Back), key_style),
+                    Span::styled(" verse ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::ToggleRelated), key_style),
+                    Span::styled(" hide ", label_style),
+                ]
+            } else if app.focus == FocusPane::Outline {
+                vec![
+                    Span::styled(keys.nav_hint(KeyContext::Browse), key_style),
+                    Span::styled(" section ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Select), key_style),
+                    Span::styled(" expand/go ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Back), key_style),
+                    Span::styled(" collapse ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::AskAi), key_style),
+                    Span::styled(" AI outline ", label_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::ToggleOutline), key_style),
+                    Span::styled(" hide ", label_style),
+                ]
             } else {
                 vec![
-                    Span::styled(" j/k ", key_style),
+                    Span::styled(keys.nav_hint(KeyContext::Browse), key_style),
                     Span::styled(" nav ", label_style),
-                    Span::styled(" Enter ", key_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Select), key_style),
                     Span::styled(" select ", label_style),
-                    Span::styled(" h ", key_style),
+                    Span::styled(keys.hint(KeyContext::Browse, Action::Back), key_style),
                     Span::styled(" back ", label_style),
                 ]
             };
+            if app.split.is_some() && app.focus == FocusPane::Content {
+                hints.extend(vec![
+                    Span::styled(keys.hint(KeyContext::Browse, Action::SwapPane), key_style),
+                    Span::styled(" swap ", label_style),
+                ]);
+            }
             // Common hints for Browse mode
             hints.extend(vec![
-                Span::styled(" Tab ", key_style),
+                Span::styled(keys.hint(KeyContext::Browse, Action::CycleFocus), key_style),
                 Span::styled(" focus ", label_style),
-                Span::styled(" X ", key_style),
+                Span::styled(keys.hint(KeyContext::Browse, Action::ToggleSaved), key_style),
                 Span::styled(if app.show_context_panel { " scripture " } else { " saved " }, label_style),
-                Span::styled(" / ", key_style),
+                Span::styled(keys.hint(KeyContext::Browse, Action::Search), key_style),
                 Span::styled(" search ", label_style),
-                Span::styled(" a ", key_style),
+                Span::styled(keys.hint(KeyContext::Browse, Action::Goto), key_style),
+                Span::styled(" goto ", label_style),
+                Span::styled(keys.hint(KeyContext::Browse, Action::AskAi), key_style),
                 Span::styled(" AI ", label_style),
-                Span::styled(" q ", key_style),
+                Span::styled(keys.hint(KeyContext::Global, Action::Help), key_style),
+                Span::styled(" help ", label_style),
+                Span::styled(keys.hint(KeyContext::Browse, Action::Quit), key_style),
                 Span::styled(" quit ", label_style),
             ]);
             hints
         },
         (Screen::Search, InputMode::Normal) => {
             let mut hints = vec![
-                Span::styled(" j/k ", key_style),
+                Span::styled(keys.nav_hint(KeyContext::Search), key_style),
                 Span::styled(" nav ", label_style),
             ];
 
             if app.search_focus == SearchFocus::Results {
                 hints.extend(vec![
-                    Span::styled(" Enter ", key_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::Select), key_style),
                     Span::styled(" view ", label_style),
                 ]);
             } else if app.show_context_panel {
                 // Preview focused, showing saved scriptures
                 hints.extend(vec![
-                    Span::styled(" d ", key_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::Remove), key_style),
                     Span::styled(" remove ", label_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::ClearSaved), key_style),
+                    Span::styled(" clear all ", label_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::Tag), key_style),
+                    Span::styled(" tag ", label_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::FilterTag), key_style),
+                    Span::styled(" filter ", label_style),
                 ]);
             } else {
                 // Preview focused, showing preview
                 hints.extend(vec![
-                    Span::styled(" x ", key_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::Save), key_style),
                     Span::styled(" save ", label_style),
-                    Span::styled(" c ", key_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::Copy), key_style),
                     Span::styled(" copy ", label_style),
-                    Span::styled(" f ", key_style),
+                    Span::styled(keys.hint(KeyContext::Search, Action::FocusMode), key_style),
                     Span::styled(" focus ", label_style),
                 ]);
             }
 
             hints.extend(vec![
-                Span::styled(" Tab ", key_style),
+                Span::styled(keys.hint(KeyContext::Search, Action::CycleFocus), key_style),
                 Span::styled(" focus ", label_style),
-                Span::styled(" X ", key_style),
+                Span::styled(keys.hint(KeyContext::Search, Action::ToggleSaved), key_style),
                 Span::styled(if app.show_context_panel { " scripture " } else { " saved " }, label_style),
-                Span::styled(" i ", key_style),
+                Span::styled(keys.hint(KeyContext::Search, Action::EditInput), key_style),
                 Span::styled(" edit ", label_style),
-                Span::styled(" Esc ", key_style),
+                Span::styled(keys.hint(KeyContext::Search, Action::Exit), key_style),
                 Span::styled(" browse ", label_style),
             ]);
             hints
@@ -423,7 +624,7 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
         ],
         (Screen::Query, InputMode::Normal) => {
             let mut hints = vec![
-                Span::styled(" Tab ", key_style),
+                Span::styled(keys.hint(KeyContext::Query, Action::CycleFocus), key_style),
                 Span::styled(" focus ", label_style),
             ];
 
@@ -431,81 +632,118 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
             match app.focus {
                 FocusPane::Navigation => {
                     hints.extend(vec![
-                        Span::styled(" j/k ", key_style),
+                        Span::styled(keys.nav_hint(KeyContext::Query), key_style),
                         Span::styled(" scroll ", label_style),
                     ]);
                 }
                 FocusPane::Content => {
                     hints.extend(vec![
-                        Span::styled(" j/k ", key_style),
+                        Span::styled(keys.nav_hint(KeyContext::Query), key_style),
                         Span::styled(" nav ", label_style),
                     ]);
                     if app.show_context_panel {
                         hints.extend(vec![
-                            Span::styled(" d ", key_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::Remove), key_style),
                             Span::styled(" remove ", label_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::ClearSaved), key_style),
+                            Span::styled(" clear all ", label_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::Tag), key_style),
+                            Span::styled(" tag ", label_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::FilterTag), key_style),
+                            Span::styled(" filter ", label_style),
                         ]);
                     } else {
                         hints.extend(vec![
-                            Span::styled(" c ", key_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::Copy), key_style),
                             Span::styled(" copy ", label_style),
-                            Span::styled(" x ", key_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::Save), key_style),
                             Span::styled(" save ", label_style),
-                            Span::styled(" f ", key_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::FocusMode), key_style),
                             Span::styled(" focus ", label_style),
+                            Span::styled(keys.hint(KeyContext::Query, Action::Visual), key_style),
+                            Span::styled(" select ", label_style),
                         ]);
                     }
                 }
                 FocusPane::References => {
                     hints.extend(vec![
-                        Span::styled(" j/k ", key_style),
+                        Span::styled(keys.nav_hint(KeyContext::Query), key_style),
                         Span::styled(" nav ", label_style),
-                        Span::styled(" Enter ", key_style),
+                        Span::styled(keys.hint(KeyContext::Query, Action::Select), key_style),
                         Span::styled(" jump ", label_style),
                     ]);
                 }
                 FocusPane::Input => {} // Handled by Editing mode
+                FocusPane::Related | FocusPane::Outline => {} // Browse only
             }
 
             // Saved scriptures toggle hint
             hints.extend(vec![
-                Span::styled(" X ", key_style),
+                Span::styled(keys.hint(KeyContext::Query, Action::ToggleSaved), key_style),
                 Span::styled(if app.show_context_panel { " scripture " } else { " saved " }, label_style),
             ]);
+            // Conversation hints
+            if app.query_task.is_none() && !app.chat_messages.is_empty() {
+                hints.extend(vec![
+                    Span::styled(keys.hint(KeyContext::Query, Action::Regenerate), key_style),
+                    Span::styled(" regenerate ", label_style),
+                    Span::styled(keys.hint(KeyContext::Query, Action::EditQuestion), key_style),
+                    Span::styled(" edit ", label_style),
+                ]);
+            }
+            hints.extend(vec![
+                Span::styled(keys.hint(KeyContext::Query, Action::NewConversation), key_style),
+                Span::styled(" new chat ", label_style),
+            ]);
+            if !app.conversations.conversations.is_empty() {
+                hints.extend(vec![
+                    Span::styled(keys.pair_hint(KeyContext::Query, Action::PrevConversation, Action::NextConversation), key_style),
+                    Span::styled(" history ", label_style),
+                ]);
+            }
             // Provider and model picker hints
             hints.extend(vec![
-                Span::styled(" P ", key_style),
+                Span::styled(keys.hint(KeyContext::Query, Action::ProviderPicker), key_style),
                 Span::styled(" provider ", label_style),
-                Span::styled(" M ", key_style),
+                Span::styled(keys.hint(KeyContext::Query, Action::ModelPicker), key_style),
                 Span::styled(" model ", label_style),
             ]);
             if !app.navigation_stack.is_empty() {
                 hints.extend(vec![
-                    Span::styled(" b ", key_style),
+                    Span::styled(keys.hint(KeyContext::Query, Action::Back), key_style),
                     Span::styled(" back ", label_style),
                 ]);
             }
-            hints.extend(vec![
-                Span::styled(" Esc ", key_style),
-                Span::styled(" browse ", label_style),
-            ]);
+            if app.query_task.is_some() {
+                hints.extend(vec![
+                    Span::styled(keys.hint(KeyContext::Query, Action::Exit), key_style),
+                    Span::styled(" stop ", label_style),
+                ]);
+            } else {
+                hints.extend(vec![
+                    Span::styled(keys.hint(KeyContext::Query, Action::Exit), key_style),
+                    Span::styled(" browse ", label_style),
+                ]);
+            }
             hints
         },
         (Screen::Query, InputMode::Editing) => vec![
             Span::styled(" Enter ", key_style),
             Span::styled(" send ", label_style),
+            Span::styled(" Shift-Enter ", key_style),
+            Span::styled(" new line ", label_style),
             Span::styled(" Esc ", key_style),
             Span::styled(" stop typing ", label_style),
         ],
         (Screen::Focus, InputMode::Normal) => {
             let mut hints = vec![
-                Span::styled(" j/k ", key_style),
+                Span::styled(keys.nav_hint(KeyContext::Focus), key_style),
                 Span::styled(" verse ", label_style),
-                Span::styled(" c ", key_style),
+                Span::styled(keys.hint(KeyContext::Focus, Action::Copy), key_style),
                 Span::styled(" copy ", label_style),
-                Span::styled(" x ", key_style),
+                Span::styled(keys.hint(KeyContext::Focus, Action::Save), key_style),
                 Span::styled(" save ", label_style),
-                Span::styled(" m ", key_style),
+                Span::styled(keys.hint(KeyContext::Focus, Action::ToggleMemorize), key_style),
                 Span::styled(" memorize ", label_style),
             ];
 
@@ -515,9 +753,9 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
                     match state.memorize_mode {
                         MemorizeMode::Progressive => {
                             hints.extend(vec![
-                                Span::styled(" +/- ", key_style),
+                                Span::styled(keys.pair_hint(KeyContext::Memorize, Action::Harder, Action::Easier), key_style),
                                 Span::styled(" difficulty ", label_style),
-                                Span::styled(" M ", key_style),
+                                Span::styled(keys.hint(KeyContext::Memorize, Action::CycleMemorizeMode), key_style),
                                 Span::styled(" mode ", label_style),
                             ]);
                         }
@@ -525,11 +763,43 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
                             match state.flashcard_phase {
                                 FlashcardPhase::Hidden => {
                                     hints.extend(vec![
-                                        Span::styled(" Space ", key_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::Reveal), key_style),
                                         Span::styled(" reveal ", label_style),
-                                        Span::styled(" t ", key_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::StartTyping), key_style),
+                                        Span::styled(" type ", label_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::CycleMemorizeMode), key_style),
+                                        Span::styled(" mode ", label_style),
+                                    ]);
+                                }
+                                FlashcardPhase::Typing => {
+                                    hints.clear();
+                                    hints.extend(vec![
+                                        Span::styled(" Enter ", key_style),
+                                        Span::styled(" submit ", label_style),
+                                        Span::styled(" Esc ", key_style),
+                                        Span::styled(" cancel ", label_style),
+                                    ]);
+                                }
+                                FlashcardPhase::Revealed => {
+                                    hints.extend(vec![
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::ResetCard), key_style),
+                                        Span::styled(" reset ", label_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::CycleMemorizeMode), key_style),
+                                        Span::styled(" mode ", label_style),
+                                    ]);
+                                }
+                            }
+                        }
+                        MemorizeMode::FirstLetter => {
+                            match state.flashcard_phase {
+                                FlashcardPhase::Hidden => {
+                                    let peek_label = if state.first_letter_peek { " hide " } else { " peek " };
+                                    hints.extend(vec![
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::Reveal), key_style),
+                                        Span::styled(peek_label, label_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::StartTyping), key_style),
                                         Span::styled(" type ", label_style),
-                                        Span::styled(" M ", key_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::CycleMemorizeMode), key_style),
                                         Span::styled(" mode ", label_style),
                                     ]);
                                 }
@@ -544,9 +814,9 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
                                 }
                                 FlashcardPhase::Revealed => {
                                     hints.extend(vec![
-                                        Span::styled(" r ", key_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::ResetCard), key_style),
                                         Span::styled(" reset ", label_style),
-                                        Span::styled(" M ", key_style),
+                                        Span::styled(keys.hint(KeyContext::Memorize, Action::CycleMemorizeMode), key_style),
                                         Span::styled(" mode ", label_style),
                                     ]);
                                 }
@@ -563,12 +833,86 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
                 .unwrap_or(false);
             if !in_typing {
                 hints.extend(vec![
-                    Span::styled(" Esc ", key_style),
+                    Span::styled(keys.hint(KeyContext::Focus, Action::Exit), key_style),
                     Span::styled(" exit ", label_style),
                 ]);
             }
             hints
         },
+        (Screen::Journal, InputMode::Normal) => vec![
+            Span::styled(keys.nav_hint(KeyContext::Journal), key_style),
+            Span::styled(" entry ", label_style),
+            Span::styled(keys.pair_hint(KeyContext::Journal, Action::HalfPageDown, Action::HalfPageUp), key_style),
+            Span::styled(" scroll ", label_style),
+            Span::styled(keys.hint(KeyContext::Journal, Action::Select), key_style),
+            Span::styled(" edit ", label_style),
+            Span::styled(keys.hint(KeyContext::Journal, Action::Journal), key_style),
+            Span::styled(" new ", label_style),
+            Span::styled(keys.hint(KeyContext::Journal, Action::Remove), key_style),
+            Span::styled(" delete ", label_style),
+            Span::styled(keys.hint(KeyContext::Journal, Action::Exit), key_style),
+            Span::styled(" back ", label_style),
+        ],
+        (Screen::Reading, InputMode::Normal) => vec![
+            Span::styled(
+                format!("{} ", app.current_chapter_title().unwrap_or_default()),
+                Style::default().bg(theme.bar_bg).fg(theme.heading),
+            ),
+            Span::styled(keys.nav_hint(KeyContext::Reading), key_style),
+            Span::styled(" scroll ", label_style),
+            Span::styled(keys.pair_hint(KeyContext::Reading, Action::HalfPageDown, Action::HalfPageUp), key_style),
+            Span::styled(" page ", label_style),
+            Span::styled(keys.pair_hint(KeyContext::Reading, Action::GrowPane, Action::ShrinkPane), key_style),
+            Span::styled(" width ", label_style),
+            Span::styled(keys.hint(KeyContext::Reading, Action::Exit), key_style),
+            Span::styled(" back ", label_style),
+        ],
+        (Screen::ComeFollowMe, InputMode::Normal) => vec![
+            Span::styled(keys.nav_hint(KeyContext::ComeFollowMe), key_style),
+            Span::styled(" move ", label_style),
+            Span::styled(" 1-9 ", key_style),
+            Span::styled(" ", label_style),
+            Span::styled(keys.hint(KeyContext::ComeFollowMe, Action::Select), key_style),
+            Span::styled(" read ", label_style),
+            Span::styled(keys.hint(KeyContext::ComeFollowMe, Action::MarkRead), key_style),
+            Span::styled(" done ", label_style),
+            Span::styled(keys.pair_hint(KeyContext::ComeFollowMe, Action::PrevWeek, Action::NextWeek), key_style),
+            Span::styled(" week ", label_style),
+            Span::styled(keys.hint(KeyContext::ComeFollowMe, Action::AskAi), key_style),
+            Span::styled(" lesson ", label_style),
+            Span::styled(keys.hint(KeyContext::ComeFollowMe, Action::Exit), key_style),
+            Span::styled(" back ", label_style),
+        ],
+        (Screen::Topics, InputMode::Normal) => vec![
+            Span::styled(keys.nav_hint(KeyContext::Topics), key_style),
+            Span::styled(" move ", label_style),
+            Span::styled(keys.pair_hint(KeyContext::Topics, Action::Select, Action::Back), key_style),
+            Span::styled(" open/back ", label_style),
+            Span::styled(keys.hint(KeyContext::Topics, Action::Exit), key_style),
+            Span::styled(" back ", label_style),
+        ],
+        (Screen::Talks, InputMode::Normal) if app.talks_view.editing => vec![
+            Span::styled(" Enter ", key_style),
+            Span::styled(" search ", label_style),
+            Span::styled(" Esc ", key_style),
+            Span::styled(" stop typing ", label_style),
+        ],
+        (Screen::Talks, InputMode::Normal) => vec![
+            Span::styled(keys.nav_hint(KeyContext::Talks), key_style),
+            Span::styled(" move ", label_style),
+            Span::styled(keys.pair_hint(KeyContext::Talks, Action::Select, Action::Back), key_style),
+            Span::styled(" open/back ", label_style),
+            Span::styled(keys.hint(KeyContext::Talks, Action::CycleFocus), key_style),
+            Span::styled(" list/talk ", label_style),
+            Span::styled(keys.hint(KeyContext::Talks, Action::EditInput), key_style),
+            Span::styled(" search ", label_style),
+            Span::styled(keys.hint(KeyContext::Talks, Action::CycleSearchMode), key_style),
+            Span::styled(" mode ", label_style),
+            Span::styled(keys.hint(KeyContext::Talks, Action::Save), key_style),
+            Span::styled(" save ", label_style),
+            Span::styled(keys.hint(KeyContext::Talks, Action::Exit), key_style),
+            Span::styled(" back ", label_style),
+        ],
         _ => vec![],
     };
 
@@ -582,14 +926,15 @@ fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
         .collect::<Vec<_>>(),
     );
 
-    let footer = Paragraph::new(footer_content).style(Style::default().bg(Color::Black));
+    let footer = Paragraph::new(footer_content).style(Style::default().bg(theme.bar_bg));
     frame.render_widget(footer, area);
 }
 
 fn render_browse_screen(app: &mut App, frame: &mut Frame, area: Rect) {
-    // Split into navigation (left) and content (right)
+    // Split into navigation (left) and content (right); navigation can be collapsed
+    let nav_width = if app.layout.nav_hidden { 0 } else { app.layout.nav_width };
     let [nav_area, content_area] = Layout::horizontal([
-        Constraint::Length(30),
+        Constraint::Length(nav_width),
         Constraint::Min(0),
     ])
     .areas(area);
@@ -599,34 +944,357 @@ fn render_browse_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     app.content_area = Some(content_area);
     app.refs_area = None;
 
-    render_navigation(app, frame, nav_area);
+    if !app.layout.nav_hidden {
+        render_navigation(app, frame, nav_area);
+    }
 
-    // Show saved scriptures panel or scripture content
+    // Show saved scriptures panel or scripture content (beside a second chapter in split view)
     if app.show_context_panel {
         render_context_panel(app, frame, content_area);
+        return;
+    }
+
+    // A parallel chapter's cursor follows the main one's (or leads it), before either is drawn
+    app.align_split();
+
+    // The chapter's outline in a column before it
+    let content_area = if app.outline_pane_visible() {
+        let [outline_area, text_area] = Layout::horizontal([
+            Constraint::Length(OUTLINE_PANE_WIDTH),
+            Constraint::Min(0),
+        ])
+        .areas(content_area);
+        app.content_area = Some(text_area);
+        render_outline_pane(app, frame, outline_area);
+        text_area
+    } else {
+        content_area
+    };
+
+    // Verses similar to the selected one in a column beside the chapter
+    let content_area = if app.related_pane_visible() {
+        let [text_area, related_area] = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(RELATED_PANE_WIDTH),
+        ])
+        .areas(content_area);
+        app.content_area = Some(text_area);
+        render_related_pane(app, frame, related_area);
+        text_area
+    } else {
+        content_area
+    };
+
+    // Footnotes of the selected verse in a pane under the chapter
+    let content_area = if app.footnote_pane_visible() {
+        let rows = app.footnote_rows().len().clamp(1, 6) as u16;
+        let borders = if app.layout.compact { 1 } else { 2 };
+        let [text_area, footnote_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(rows + borders),
+        ])
+        .areas(content_area);
+        app.content_area = Some(text_area);
+        render_footnote_pane(app, frame, footnote_area);
+        text_area
+    } else {
+        content_area
+    };
+
+    if app.split.is_some() {
+        let [main_area, split_area] = Layout::horizontal([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .areas(content_area);
+        app.content_area = Some(main_area);
+        render_content(app, frame, main_area);
+        render_split_pane(app, frame, split_area);
     } else {
         render_content(app, frame, content_area);
     }
 }
 
+/// One row per cross-reference: marker, footnoted word, reference
+fn render_footnote_pane(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let focused = app.focus == FocusPane::References;
+    let border_color = if focused { theme.accent } else { theme.muted };
+    let title = match app.get_selected_verse() {
+        Some(verse) => format!(" Footnotes · {} ", verse.verse_title),
+        None => " Footnotes ".to_string(),
+    };
+    let block = pane_block(app.layout.compact)
+        .border_style(Style::default().fg(border_color))
+        .title(title);
+    let visible_rows = block.inner(area).height as usize;
+
+    let rows = app.footnote_rows();
+    if rows.is_empty() {
+        let placeholder = Paragraph::new("No footnotes for this verse")
+            .style(Style::default().fg(theme.muted))
+            .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let mut previous_marker = None;
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|(note, reference)| {
+            // Marker and word only on a footnote's first reference
+            let first = previous_marker != Some(&note.marker);
+            previous_marker = Some(&note.marker);
+            let (marker, word) = if first {
+                (format!("{:>2} ", note.marker), format!("{} ", pad_to_width(&note.word, 16)))
+            } else {
+                ("   ".to_string(), format!("{:<16} ", ""))
+            };
+            let is_verse = app.scripture_db.parse_reference(reference).is_some();
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.heading).bold()),
+                Span::styled(word, Style::default().fg(theme.muted)),
+                Span::styled(
+                    reference.to_string(),
+                    Style::default().fg(if is_verse { theme.accent } else { theme.text }),
+                ),
+            ]))
+        })
+        .collect();
+    let len = items.len();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(if focused {
+            Style::default().bg(theme.selection_bg).fg(theme.selection_fg)
+        } else {
+            Style::default()
+        });
+
+    // The selected verse may have fewer footnotes than the last one
+    if app.footnote_state.selected().is_some_and(|i| i >= len) {
+        app.footnote_state.select(Some(0));
+    }
+    ensure_selected_visible(&mut app.footnote_state, visible_rows);
+    frame.render_stateful_widget(list, area, &mut app.footnote_state);
+}
+
+/// Width of the related-verses column, borders included (fits "Doctrine and Covenants 138:60" and a score)
+const RELATED_PANE_WIDTH: u16 = 36;
+
+/// The verses most similar to the selected one, with their similarity
+fn render_related_pane(app: &mut App, frame: &mut Frame, area: Rect) {
+    app.refresh_related();
+    let theme = app.theme;
+    let focused = app.focus == FocusPane::Related;
+    let border_color = if focused { theme.accent } else { theme.muted };
+    let block = pane_block(app.layout.compact)
+        .border_style(Style::default().fg(border_color))
+        .title(" Related ");
+    let inner_width = block.inner(area).width as usize;
+    let visible_rows = block.inner(area).height as usize;
+
+    if app.related.is_empty() {
+        let message = if app.related_for.is_some() {
+            "No related verses"
+        } else {
+            "Select a verse to see related verses"
+        };
+        let placeholder = Paragraph::new(message)
+            .style(Style::default().fg(theme.muted))
+            .wrap(Wrap { trim: true })
+            .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    // Similarity as a percentage on the right, the reference in what's left
+    let items: Vec<ListItem> = app
+        .related
+        .iter()
+        .map(|(title, score)| {
+            let score = format!(" {:>3.0}%", score * 100.0);
+            let title_width = inner_width.saturating_sub(score.len());
+            ListItem::new(Line::from(vec![
+                Span::styled(pad_to_width(title, title_width), Style::default().fg(theme.accent)),
+                Span::styled(score, Style::default().fg(theme.muted)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(if focused {
+            Style::default().bg(theme.selection_bg).fg(theme.selection_fg)
+        } else {
+            Style::default()
+        });
+
+    ensure_selected_visible(&mut app.related_state, visible_rows);
+    frame.render_stateful_widget(list, area, &mut app.related_state);
+}
+
+/// Width of the outline column, borders included
+const OUTLINE_PANE_WIDTH: u16 = 34;
+
+/// The chapter's sections, collapsible to their titles, with the one being
+/// read marked
+fn render_outline_pane(app: &mut App, frame: &mut Frame, area: Rect) {
+    app.refresh_outline();
+    let theme = app.theme;
+    let focused = app.focus == FocusPane::Outline;
+    let border_color = if focused { theme.accent } else { theme.muted };
+    let title = match app.outline.as_ref().map(|o| &o.source) {
+        _ if app.outline_task.is_some() => " Outline (outlining...) ",
+        Some(OutlineSource::Ai { .. }) => " Outline (AI) ",
+        _ => " Outline ",
+    };
+    let block = pane_block(app.layout.compact).border_style(Style::default().fg(border_color)).title(title);
+    let inner_width = block.inner(area).width as usize;
+    let visible_rows = block.inner(area).height as usize;
+
+    let rows = app.outline_rows();
+    let Some(outline) = &app.outline else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let reading = app.get_selected_verse().and_then(|v| outline.section_of(v.verse_number));
+
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|&(i, child)| {
+            let section = &outline.sections[i];
+            let (marker, part) = match child {
+                Some(c) => ("   ", &section.children[c]),
+                None if section.children.is_empty() => ("  ", section),
+                None if app.outline_collapsed.contains(&i) => ("▸ ", section),
+                None => ("▾ ", section),
+            };
+            let here = reading.is_some_and(|(s, c)| s == i && (child.is_none() || c == child));
+            let range = format!(" {}", part.range_label());
+            let title_width = inner_width.saturating_sub(marker.width() + range.width());
+            let mut title = part.title.clone();
+            if title.width() > title_width {
+                while title.width() + 1 > title_width && title.pop().is_some() {}
+                title.push('…');
+            }
+            let color = match part.kind {
+                _ if here => theme.heading,
+                SectionKind::Narrative => theme.text,
+                SectionKind::Doctrine => theme.secondary,
+            };
+            let mut style = Style::default().fg(color);
+            if here {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.muted)),
+                Span::styled(pad_to_width(&title, title_width), style),
+                Span::styled(range, Style::default().fg(theme.muted)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(if focused {
+            Style::default().bg(theme.selection_bg).fg(theme.selection_fg)
+        } else {
+            Style::default()
+        });
+
+    ensure_selected_visible(&mut app.outline_state, visible_rows);
+    frame.render_stateful_widget(list, area, &mut app.outline_state);
+}
+
+fn render_split_pane(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let focused = app.split_focused && app.focus == FocusPane::Content;
+    let compact = app.layout.compact;
+    let Some(split) = &mut app.split else {
+        return;
+    };
+    let border_color = if focused { theme.accent } else { theme.muted };
+
+    let block = pane_block(compact)
+        .border_style(Style::default().fg(border_color))
+        .title(format!(" {} ", split.title()));
+
+    let inner_area = block.inner(area);
+    let inner_width = inner_area.width as usize;
+    let layout = calculate_chapter_layout(&split.verses, inner_width, compact);
+
+    // Independent lazy scrolling for this pane
+    split.scroll = calculate_scroll_for_verse(
+        &layout,
+        split.selected,
+        inner_area.height as usize,
+        split.scroll,
+        crate::app::ScrollDirection::Down,
+        0,
+    );
+
+    let mut lines: Vec<Line> = Vec::new();
+    for verse_layout in &layout.verses {
+        let verse = &split.verses[verse_layout.verse_idx];
+        let is_cursor = focused && verse_layout.verse_idx == split.selected;
+        // A parallel chapter marks the verse matching the main chapter's
+        let is_parallel = !focused && split.alignment.is_some() && verse_layout.verse_idx == split.selected;
+
+        for (line_idx, wrapped_line) in verse_layout.wrapped_lines.iter().enumerate() {
+            let text = if line_idx == 0 {
+                format!("{}  {}", verse.verse_number, wrapped_line)
+            } else {
+                wrapped_line.clone()
+            };
+            if is_cursor {
+                let padded = pad_to_width(&text, inner_width);
+                lines.push(Line::styled(
+                    padded,
+                    Style::default().fg(theme.selection_fg).bg(theme.selection_bg),
+                ));
+            } else if is_parallel {
+                lines.push(Line::styled(pad_to_width(&text, inner_width), Style::default().fg(theme.range_fg).bg(theme.range_bg)));
+            } else if line_idx == 0 {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}  ", verse.verse_number), Style::default().fg(theme.heading).bold()),
+                    Span::raw(wrapped_line.clone()),
+                ]));
+            } else {
+                lines.push(Line::raw(text));
+            }
+        }
+        if !compact {
+            lines.push(Line::default());
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((split.scroll as u16, 0));
+    frame.render_widget(paragraph, area);
+}
+
 fn render_navigation(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
     let nav_focused = app.focus == FocusPane::Navigation;
-    let border_color = if nav_focused { Color::Cyan } else { Color::DarkGray };
+    let border_color = if nav_focused { theme.accent } else { theme.muted };
 
     // Calculate visible height (subtract borders)
-    let visible_height = area.height.saturating_sub(2) as usize;
+    let inner = pane_block(app.layout.compact).inner(area);
+    let visible_height = inner.height as usize;
     app.nav_visible_height = visible_height;
 
     // Get items, scroll reference, and selection for current nav level
     let (items, total, scroll, selected): (Vec<String>, usize, &mut usize, usize) = match app.nav_level {
         NavLevel::Volume => (
-            app.cached_volumes.clone(),
+            app.cached_volumes.iter().map(Title::to_string).collect(),
             app.cached_volumes.len(),
             &mut app.volume_scroll,
             app.volume_state.selected().unwrap_or(0),
         ),
         NavLevel::Book => (
-            app.cached_books.clone(),
+            app.cached_books.iter().map(Title::to_string).collect(),
             app.cached_books.len(),
             &mut app.book_scroll,
             app.book_state.selected().unwrap_or(0),
@@ -663,13 +1331,12 @@ fn render_navigation(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let title = format!(" {} ", app.current_nav_title());
 
-    let block = Block::default()
-        .borders(Borders::ALL)
+    let block = pane_block(app.layout.compact)
         .border_style(Style::default().fg(border_color))
         .title(title);
 
     // Calculate inner width for full-width highlighting (subtract borders)
-    let inner_width = area.width.saturating_sub(2) as usize;
+    let inner_width = inner.width as usize;
 
     // Build lines as plain text - no List widget, just a Paragraph
     // This gives us complete control and avoids any widget-specific behavior
@@ -682,13 +1349,13 @@ fn render_navigation(app: &mut App, frame: &mut Frame, area: Rect) {
                 let actual_index = scroll_val + i;
                 let text = format!("> {} ", v);
                 // Pad to full width so background color fills the line
-                let padded = format!("{:<width$}", text, width = inner_width);
+                let padded = pad_to_width(&text, inner_width);
                 if actual_index == selected {
                     Line::styled(
                         padded,
                         Style::default()
-                            .bg(Color::Blue)
-                            .fg(Color::White)
+                            .bg(theme.selection_bg)
+                            .fg(theme.selection_fg)
                             .add_modifier(Modifier::BOLD)
                     )
                 } else {
@@ -705,16 +1372,27 @@ fn render_navigation(app: &mut App, frame: &mut Frame, area: Rect) {
 }
 
 fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
-    let content_focused = app.focus == FocusPane::Content;
-    let border_color = if content_focused { Color::Cyan } else { Color::DarkGray };
+    let theme = app.theme;
+    let split_focused = app.screen == Screen::Browse && app.split_focused;
+    let content_focused = app.focus == FocusPane::Content && !split_focused;
+    let border_color = if content_focused { theme.accent } else { theme.muted };
 
     let title = app.content_title();
-    let block = Block::default()
-        .borders(Borders::ALL)
+    let mut block = pane_block(app.layout.compact)
         .border_style(Style::default().fg(border_color))
         .title(format!(" {} ", title));
+    if let Some(position) = verse_position(app) {
+        block = block.title(Line::styled(position, Style::default().fg(theme.muted)).right_aligned());
+    }
+    if let Some(status) = app.read_aloud_status() {
+        block = block.title_bottom(Line::styled(format!(" {} ", status), Style::default().fg(theme.accent)));
+    }
 
-    let inner_area = block.inner(area);
+    // The scrollbar sits on the right border, or takes the last column when compact
+    let mut inner_area = block.inner(area);
+    if app.layout.compact {
+        inner_area.width = inner_area.width.saturating_sub(1);
+    }
     app.content_height = inner_area.height;
     app.content_width = inner_area.width as usize;
     let view_height = inner_area.height as usize;
@@ -722,14 +1400,14 @@ fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
 
     if app.cached_verses.is_empty() {
         let placeholder = Paragraph::new("Select a chapter to view verses")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(block);
         frame.render_widget(placeholder, area);
         return;
     }
 
     // Calculate layout for all verses (pre-wrap all text)
-    let layout = calculate_chapter_layout(&app.cached_verses, inner_width);
+    let layout = calculate_chapter_layout(&app.cached_verses, inner_width, app.layout.compact);
     app.total_content_lines = layout.total_lines as u16;
 
     // Determine selected verse
@@ -767,9 +1445,11 @@ fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
             break;
         }
 
-        let is_cursor = app.selected_verse_idx == Some(verse_layout.verse_idx)
-            && app.focus == FocusPane::Content;
-        let is_in_range = app.selected_range.as_ref().is_some_and(|range| {
+        let is_cursor = app.selected_verse_idx == Some(verse_layout.verse_idx) && content_focused;
+        let is_in_visual = app.visual_bounds().is_some_and(|(start, end)| {
+            (start..=end).contains(&verse_layout.verse_idx)
+        });
+        let is_in_range = is_in_visual || app.selected_range.as_ref().is_some_and(|range| {
             range.book_title == verse.book_title
                 && range.chapter_number == verse.chapter_number
                 && range.contains_verse(verse.verse_number)
@@ -777,17 +1457,17 @@ fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
 
         // Determine styles
         let verse_num_style = if is_cursor {
-            Style::default().fg(Color::White).bg(Color::Blue).bold()
+            Style::default().fg(theme.selection_fg).bg(theme.selection_bg).bold()
         } else if is_in_range {
-            Style::default().fg(Color::Black).bg(Color::Cyan).bold()
+            Style::default().fg(theme.range_fg).bg(theme.accent).bold()
         } else {
-            Style::default().fg(Color::Yellow).bold()
+            Style::default().fg(theme.heading).bold()
         };
 
         let verse_text_style = if is_cursor {
-            Style::default().fg(Color::White).bg(Color::Blue)
+            Style::default().fg(theme.selection_fg).bg(theme.selection_bg)
         } else if is_in_range {
-            Style::default().bg(Color::DarkGray)
+            Style::default().bg(theme.range_bg)
         } else {
             Style::default()
         };
@@ -804,10 +1484,10 @@ fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
                     if is_cursor {
                         // Full line with verse number, padded for highlight
                         let full_line = format!("{}{}", num_prefix, wrapped_line);
-                        let padded = format!("{:<width$}", full_line, width = inner_width);
+                        let padded = pad_to_width(&full_line, inner_width);
                         lines.push(Line::styled(padded, verse_text_style));
                     } else {
-                        // Verse number in yellow, text in default
+                        // Verse number highlighted, text in default
                         lines.push(Line::from(vec![
                             Span::styled(num_prefix, verse_num_style),
                             Span::styled(wrapped_line.clone(), verse_text_style),
@@ -816,7 +1496,7 @@ fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
                 } else {
                     // Continuation lines
                     if is_cursor {
-                        let padded = format!("{:<width$}", wrapped_line, width = inner_width);
+                        let padded = pad_to_width(wrapped_line, inner_width);
                         lines.push(Line::styled(padded, verse_text_style));
                     } else {
                         lines.push(Line::styled(wrapped_line.clone(), verse_text_style));
@@ -827,20 +1507,73 @@ fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
 
         // Add blank line after verse (if visible)
         let blank_line_pos = verse_layout.start_line + verse_layout.wrapped_lines.len();
-        if blank_line_pos >= scroll_start && blank_line_pos < scroll_end {
+        if !app.layout.compact && blank_line_pos >= scroll_start && blank_line_pos < scroll_end {
             lines.push(Line::default());
         }
     }
 
     // Render without Paragraph's internal wrapping (we did it manually)
-    let paragraph = Paragraph::new(lines).block(block);
-    frame.render_widget(paragraph, area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(lines), inner_area);
+
+    let scrollbar = Rect::new(area.right().saturating_sub(1), inner_area.y, 1, inner_area.height);
+    render_verse_minimap(frame, scrollbar, &layout, app.line_scroll, selected_idx, &theme);
+}
+
+/// "v. 21/63 · 33%" for the verse under the cursor
+fn verse_position(app: &App) -> Option<String> {
+    let total = app.cached_verses.len();
+    let selected = app.selected_verse_idx?;
+    (total > 0).then(|| format!(" v. {}/{} · {}% ", selected + 1, total, (selected + 1) * 100 / total))
+}
+
+/// A scrollbar drawn as a map of the chapter: each row stands for a slice of its
+/// lines, with a tick where a verse starts, the visible part as the thumb, and a
+/// dot at the selected verse. Nothing is drawn when the chapter fits.
+fn render_verse_minimap(
+    frame: &mut Frame,
+    area: Rect,
+    layout: &ChapterLayout,
+    scroll: usize,
+    selected_idx: usize,
+    theme: &Theme,
+) {
+    let rows = area.height as usize;
+    let total = layout.total_lines;
+    if rows == 0 || total <= rows {
+        return;
+    }
+
+    let selected_line = layout.verses.get(selected_idx).map(|v| v.start_line);
+    let buffer = frame.buffer_mut();
+    for row in 0..rows {
+        // Chapter lines this row stands for
+        let start = row * total / rows;
+        let end = ((row + 1) * total / rows).max(start + 1);
+        let visible = start < scroll + rows && end > scroll;
+        let verse_starts = layout.verses.iter().any(|v| (start..end).contains(&v.start_line));
+
+        let (symbol, color) = if selected_line.is_some_and(|line| (start..end).contains(&line)) {
+            ("●", theme.heading)
+        } else if visible {
+            ("┃", theme.accent)
+        } else if verse_starts {
+            ("╴", theme.muted)
+        } else {
+            ("│", theme.muted)
+        };
+        let cell = &mut buffer[(area.x, area.y + row as u16)];
+        cell.set_symbol(symbol);
+        cell.set_fg(color);
+    }
 }
 
 fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
-    // Layout: search input at top, results below split into list and preview
-    let [input_area, results_area] = Layout::vertical([
+    let theme = app.theme;
+    // Layout: search input at top, filter bar, results below split into list and preview
+    let [input_area, filter_area, results_area] = Layout::vertical([
         Constraint::Length(3),
+        Constraint::Length(1),
         Constraint::Min(0),
     ])
     .areas(area);
@@ -850,15 +1583,15 @@ fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(
             if app.input_mode == InputMode::Editing {
-                Color::Yellow
+                theme.heading
             } else {
-                Color::DarkGray
+                theme.muted
             },
         ))
         .title(" Search ");
 
     let input = Paragraph::new(app.search_input.as_str())
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(theme.accent))
         .block(input_block);
 
     frame.render_widget(input, input_area);
@@ -866,11 +1599,13 @@ fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     // Show cursor when editing
     if app.input_mode == InputMode::Editing {
         frame.set_cursor_position((
-            input_area.x + app.search_input.len() as u16 + 1,
+            input_area.x + app.search_input.width() as u16 + 1,
             input_area.y + 1,
         ));
     }
 
+    render_search_filter_bar(app, frame, filter_area);
+
     // Results: list on left, preview/saved on right
     let [list_area, preview_area] = Layout::horizontal([
         Constraint::Percentage(40),
@@ -880,25 +1615,36 @@ fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
 
     // Results list - highlight when focused
     let results_focused = app.search_focus == SearchFocus::Results;
-    let results_border_color = if results_focused { Color::Cyan } else { Color::DarkGray };
+    let results_border_color = if results_focused { theme.accent } else { theme.muted };
 
     let results_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(results_border_color))
-        .title(format!(" Results ({}) ", app.search_results.len()));
+        .title(if app.search_task.is_some() {
+            format!(" Results ({}, keyword · searching by meaning...) ", app.search_results.len())
+        } else {
+            format!(" Results ({}) ", app.search_results.len())
+        });
 
     let items: Vec<ListItem> = app
         .search_results
         .iter()
-        .map(|s| ListItem::new(format!(" {} ", s.verse_title)))
+        .map(|hit| {
+            // Badge: similarity for semantic matches, "kw" for keyword matches
+            let badge = match hit.score {
+                Some(score) => Span::styled(format!(" {:.2}", score), Style::default().fg(theme.secondary)),
+                None => Span::styled("   kw", Style::default().fg(theme.muted)),
+            };
+            ListItem::new(Line::from(vec![badge, Span::raw(format!(" {} ", hit.scripture.verse_title))]))
+        })
         .collect();
 
     let list = List::new(items)
         .block(results_block)
         .highlight_style(
             Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
@@ -916,7 +1662,7 @@ fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     } else {
         // Preview panel - highlight when focused
         let preview_focused = app.search_focus == SearchFocus::Preview;
-        let preview_border_color = if preview_focused { Color::Cyan } else { Color::DarkGray };
+        let preview_border_color = if preview_focused { theme.accent } else { theme.muted };
 
         let preview_block = Block::default()
             .borders(Borders::ALL)
@@ -924,14 +1670,20 @@ fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
             .title(" Preview ");
 
         let preview_text = if let Some(i) = app.search_state.selected() {
-            if let Some(scripture) = app.search_results.get(i) {
+            if let Some(hit) = app.search_results.get(i) {
+                let source = match (hit.source, hit.score) {
+                    (MatchSource::Semantic, Some(score)) => format!("Semantic match · similarity {:.2}", score),
+                    (MatchSource::Semantic, None) => "Semantic match".to_string(),
+                    (MatchSource::Keyword, _) => "Keyword match".to_string(),
+                };
                 Text::from(vec![
                     Line::from(Span::styled(
-                        &scripture.verse_title,
-                        Style::default().fg(Color::Yellow).bold(),
+                        &hit.scripture.verse_title,
+                        Style::default().fg(theme.heading).bold(),
                     )),
+                    Line::from(Span::styled(source, Style::default().fg(theme.muted))),
                     Line::default(),
-                    Line::from(&scripture.scripture_text[..]),
+                    Line::from(&hit.scripture.scripture_text[..]),
                 ])
             } else {
                 Text::from("Select a result to preview")
@@ -948,36 +1700,499 @@ fn render_search_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     }
 }
 
-fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
-    use escrituras_core::ChatRole;
-
-    // Split layout: AI panel on left, scripture content on right (like browse)
-    let [ai_area, content_area] = Layout::horizontal([
-        Constraint::Percentage(50),
-        Constraint::Percentage(50),
+/// Journal entries (newest first) beside the selected entry's text
+fn render_journal_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let [list_area, entry_area] = Layout::horizontal([
+        Constraint::Percentage(35),
+        Constraint::Percentage(65),
     ])
     .areas(area);
 
-    // Calculate references panel height (if we have references)
-    let refs_height = if app.extracted_references.is_empty() {
-        0
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Journal ({}) ", app.journal.entries.len()));
+
+    if app.journal.entries.is_empty() {
+        let placeholder = Paragraph::new(format!(
+            "No journal entries yet.\nPress '{}' while reading to start one.",
+            app.keymap.hint(KeyContext::Browse, Action::Journal).trim()
+        ))
+        .style(Style::default().fg(theme.muted))
+        .block(list_block)
+        .wrap(Wrap { trim: true });
+        frame.render_widget(placeholder, list_area);
+        frame.render_widget(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted)), entry_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .journal
+        .entries
+        .iter()
+        .map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::styled(entry.created.format("%Y-%m-%d ").to_string(), Style::default().fg(theme.muted)),
+                Span::raw(entry.title.clone()),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(list_block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    let visible_height = list_area.height.saturating_sub(2) as usize;
+    app.journal_visible_height = visible_height;
+    ensure_selected_visible(&mut app.journal_state, visible_height);
+    frame.render_stateful_widget(list, list_area, &mut app.journal_state);
+
+    let Some(entry) = app.journal_state.selected().and_then(|i| app.journal.entries.get(i)) else {
+        return;
+    };
+    let entry_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.muted))
+        .title(format!(" {} ", entry.created.format("%B %-d, %Y %-I:%M %p")));
+
+    // Light Markdown styling: headings and quotes stand out from the prose
+    let lines: Vec<Line> = entry
+        .content
+        .lines()
+        .map(|line| {
+            if line.starts_with('#') {
+                Line::from(Span::styled(line.trim_start_matches('#').trim().to_string(), Style::default().fg(theme.heading).bold()))
+            } else if line.starts_with('>') {
+                Line::from(Span::styled(line.replace("**", ""), Style::default().fg(theme.secondary)))
+            } else {
+                Line::from(line.to_string())
+            }
+        })
+        .collect();
+
+    let text = Paragraph::new(lines)
+        .block(entry_block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.journal_scroll, 0));
+    frame.render_widget(text, entry_area);
+}
+
+fn render_talks_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let [left_area, talk_area] = Layout::horizontal([
+        Constraint::Percentage(40),
+        Constraint::Percentage(60),
+    ])
+    .areas(area);
+    let [input_area, list_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .areas(left_area);
+
+    let Some(db) = &app.talks else {
+        let placeholder = Paragraph::new("No conference talks installed.\nAdd talks.json to the data directory.")
+            .style(Style::default().fg(theme.muted))
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.muted)))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(placeholder, area);
+        return;
+    };
+
+    // Search box
+    let view = &app.talks_view;
+    let mode = if view.semantic { "semantic" } else { "keyword" };
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if view.editing { theme.heading } else { theme.muted }))
+        .title(format!(" Search talks ({}) ", mode));
+    let input = Paragraph::new(view.search_input.as_str())
+        .style(Style::default().fg(theme.accent))
+        .block(input_block);
+    frame.render_widget(input, input_area);
+    if view.editing {
+        frame.set_cursor_position((
+            input_area.x + view.search_input.width() as u16 + 1,
+            input_area.y + 1,
+        ));
+    }
+
+    // Speakers / years / talks, or search results
+    let list_title = match (&view.results, view.level, &view.speaker, view.year) {
+        (Some(results), ..) => format!(" Results ({}, {}) ", results.len(), mode),
+        (None, TalkLevel::Year, Some(speaker), _) => format!(" {} ", speaker),
+        (None, TalkLevel::Talk, Some(speaker), Some(year)) => format!(" {} · {} ", speaker, year),
+        _ => format!(" Speakers ({}) ", db.speakers().len()),
+    };
+    let list_focused = !view.text_focused && !view.editing;
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if list_focused { theme.accent } else { theme.muted }))
+        .title(list_title);
+    let items: Vec<ListItem> = app.talk_list_items().into_iter().map(ListItem::new).collect();
+    let list = List::new(items)
+        .block(list_block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    let visible_height = list_area.height.saturating_sub(2) as usize;
+    app.talks_view.visible_height = visible_height;
+    ensure_selected_visible(&mut app.talks_view.list_state, visible_height);
+    frame.render_stateful_widget(list, list_area, &mut app.talks_view.list_state);
+
+    // Open talk, one numbered paragraph per block
+    let view = &app.talks_view;
+    let talk_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if view.text_focused { theme.accent } else { theme.muted }));
+    let Some(talk) = app.open_talk() else {
+        let hint = Paragraph::new("Choose a speaker, year, and talk, or search.")
+            .style(Style::default().fg(theme.muted))
+            .block(talk_block);
+        frame.render_widget(hint, talk_area);
+        return;
+    };
+    let talk_block = talk_block.title(format!(" {} — {}, {} ", talk.title, talk.speaker, talk.session()));
+
+    let width = talk_area.width.saturating_sub(2) as usize;
+    let height = talk_area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_span = (0, 0);
+    for (i, paragraph) in talk.paragraphs.iter().enumerate() {
+        let selected = i == view.paragraph;
+        let style = if selected && view.text_focused {
+            Style::default().bg(theme.selection_bg).fg(theme.selection_fg)
+        } else if selected {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let start = lines.len();
+        let number = format!("{:>3} ", i + 1);
+        for (j, line) in wrap_text_to_width(paragraph, width.saturating_sub(number.len())).into_iter().enumerate() {
+            let gutter = if j == 0 { number.clone() } else { " ".repeat(number.len()) };
+            lines.push(Line::from(vec![
+                Span::styled(gutter, Style::default().fg(theme.muted)),
+                Span::styled(line, style),
+            ]));
+        }
+        if selected {
+            selected_span = (start, lines.len());
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Keep the highlighted paragraph on screen
+    let mut scroll = view.scroll as usize;
+    if selected_span.0 < scroll {
+        scroll = selected_span.0;
+    } else if selected_span.1 > scroll + height {
+        scroll = selected_span.1.saturating_sub(height).min(selected_span.0);
+    }
+    app.talks_view.scroll = scroll as u16;
+
+    let text = Paragraph::new(lines).block(talk_block).scroll((scroll as u16, 0));
+    frame.render_widget(text, talk_area);
+}
+
+/// Chapters as flowing paragraphs in a centered column, with no panes or borders
+fn render_reading_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let width = app.layout.reading_width.min(area.width.saturating_sub(4)).max(1);
+    let column = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + 1,
+        width,
+        height: area.height.saturating_sub(1),
+    };
+    app.reading.visible_height = column.height as usize;
+    app.reading_relayout(width as usize);
+
+    let view = &app.reading;
+    let lines: Vec<Line> = view
+        .lines
+        .iter()
+        .enumerate()
+        .skip(view.scroll)
+        .take(column.height as usize)
+        .map(|(i, line)| {
+            if view.chapter_starts.contains(&i) {
+                return Line::from(Span::styled(line.clone(), Style::default().fg(theme.heading).bold())).centered();
+            }
+            // Verse numbers are the runs of superscript digits
+            let mut spans = Vec::new();
+            let mut run = String::new();
+            let mut run_is_number = false;
+            for c in line.chars() {
+                let is_number = escrituras_core::reading::is_superscript(c);
+                if is_number != run_is_number && !run.is_empty() {
+                    spans.push(reading_span(std::mem::take(&mut run), run_is_number, &theme));
+                }
+                run_is_number = is_number;
+                run.push(c);
+            }
+            if !run.is_empty() {
+                spans.push(reading_span(run, run_is_number, &theme));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), column);
+}
+
+fn reading_span(text: String, verse_number: bool, theme: &Theme) -> Span<'static> {
+    if verse_number {
+        Span::styled(text, Style::default().fg(theme.muted))
+    } else {
+        Span::styled(text, Style::default().fg(theme.text))
+    }
+}
+
+fn render_come_follow_me_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let Some(week) = app.cfm_current_week().cloned() else {
+        return;
+    };
+    let readings = app.cfm_readings();
+    let done = readings.iter().filter(|r| app.cfm_progress.is_done(&week, &r.label)).count();
+    let weeks = app.cfm.as_ref().map_or(0, |s| s.weeks().len());
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Come, Follow Me · {} ", week.title));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [summary_area, list_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+
+    let summary = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(" {} – {}", week.start.format("%B %-d"), week.end().format("%B %-d, %Y")),
+                Style::default().fg(theme.heading).bold(),
+            ),
+            Span::styled(format!("  ·  week {} of {}", app.cfm_week + 1, weeks), Style::default().fg(theme.muted)),
+        ]),
+        Line::from(Span::styled(format!(" {}", week.reading.join("; ")), Style::default().fg(theme.secondary))),
+        Line::from(Span::styled(
+            format!(" {} of {} chapters read", done, readings.len()),
+            Style::default().fg(if done == readings.len() && done > 0 { theme.accent } else { theme.muted }),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(summary), summary_area);
+
+    if readings.is_empty() {
+        let placeholder = Paragraph::new(" None of this week's references were found in the scriptures.")
+            .style(Style::default().fg(theme.muted));
+        frame.render_widget(placeholder, list_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = readings
+        .iter()
+        .enumerate()
+        .map(|(i, reading)| {
+            let is_done = app.cfm_progress.is_done(&week, &reading.label);
+            let number = if i < 9 { format!("{} ", i + 1) } else { "  ".to_string() };
+            ListItem::new(Line::from(vec![
+                Span::styled(number, Style::default().fg(theme.muted)),
+                Span::styled(
+                    if is_done { "[x] " } else { "[ ] " },
+                    Style::default().fg(if is_done { theme.accent } else { theme.muted }),
+                ),
+                Span::raw(reading.label.clone()),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::TOP).border_style(Style::default().fg(theme.muted)))
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+    ensure_selected_visible(&mut app.cfm_state, list_area.height.saturating_sub(1) as usize);
+    frame.render_stateful_widget(list, list_area, &mut app.cfm_state);
+}
+
+fn render_topics_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let [list_area, page_area] = Layout::horizontal([
+        Constraint::Length(30),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+    let highlight = Style::default()
+        .bg(theme.selection_bg)
+        .fg(theme.selection_fg)
+        .add_modifier(Modifier::BOLD);
+    let page_focused = app.topics_view.page_focused && app.topics_view.page.is_some();
+
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if page_focused { theme.muted } else { theme.accent }))
+        .title(format!(" Topics ({}) ", TOPICS.len()));
+    let items: Vec<ListItem> = TOPICS.iter().map(|t| ListItem::new(t.name)).collect();
+    let list = List::new(items).block(list_block).highlight_style(highlight).highlight_symbol("> ");
+    ensure_selected_visible(&mut app.topics_view.list_state, list_area.height.saturating_sub(2) as usize);
+    frame.render_stateful_widget(list, list_area, &mut app.topics_view.list_state);
+
+    let page_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if page_focused { theme.accent } else { theme.muted }));
+    let Some(page) = &app.topics_view.page else {
+        let placeholder = Paragraph::new(" Choose a topic to see its Topical Guide verses, related verses, and talks.")
+            .style(Style::default().fg(theme.muted))
+            .block(page_block)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(placeholder, page_area);
+        return;
+    };
+    let summary = Topic::find(&page.topic).map_or("", |t| t.summary);
+    let page_block = page_block.title(format!(" {} ", page.topic));
+    let inner = page_block.inner(page_area);
+    frame.render_widget(page_block, page_area);
+    let [summary_area, rows_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(format!(" {}", summary), Style::default().fg(theme.secondary).italic()))),
+        summary_area,
+    );
+
+    let rows = app.topic_rows();
+    if rows.is_empty() {
+        let placeholder = Paragraph::new(" Nothing found: install footnotes, embeddings, or talks to fill topic pages.")
+            .style(Style::default().fg(theme.muted))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(placeholder, rows_area);
+        return;
+    }
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|row| match row {
+            TopicRow::Heading(heading) => {
+                ListItem::new(Line::from(Span::styled(heading.clone(), Style::default().fg(theme.heading).bold())))
+            }
+            TopicRow::Verse(title) => {
+                let text = app.scripture_db.get_by_title(title).map_or("", |v| v.scripture_text.as_str());
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{}  ", title), Style::default().fg(theme.accent)),
+                    Span::raw(text.to_string()),
+                ]))
+            }
+            TopicRow::Talk(i) => {
+                let talk = &page.talks[*i];
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{} ", talk.title), Style::default().fg(theme.accent)),
+                    Span::styled(format!("({}, {})  ", talk.speaker, talk.session), Style::default().fg(theme.muted)),
+                    Span::raw(talk.text.clone()),
+                ]))
+            }
+        })
+        .collect();
+    let list = List::new(items).highlight_style(highlight).highlight_symbol("> ");
+    ensure_selected_visible(&mut app.topics_view.page_state, rows_area.height as usize);
+    frame.render_stateful_widget(list, rows_area, &mut app.topics_view.page_state);
+}
+
+/// One-line summary of the active search filters with the keys that change them
+fn render_search_filter_bar(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let keys = &app.keymap;
+    let filter = &app.search_filter;
+    let label = Style::default().fg(theme.muted);
+    let value = Style::default().fg(theme.accent);
+    let key = Style::default().fg(theme.key_fg).bg(theme.key_bg);
+
+    let line = Line::from(vec![
+        Span::styled(" Volume: ", label),
+        Span::styled(format!("{} ▾ ", filter.volume.as_deref().unwrap_or("All")), value),
+        Span::styled(keys.hint(KeyContext::Search, Action::FilterVolume), key),
+        Span::styled("  Book: ", label),
+        Span::styled(format!("{} ▾ ", filter.book.as_deref().unwrap_or("All")), value),
+        Span::styled(keys.hint(KeyContext::Search, Action::FilterBook), key),
+        Span::styled("  Mode: ", label),
+        Span::styled(format!("{} ", filter.mode.label()), value),
+        Span::styled(keys.hint(KeyContext::Search, Action::CycleSearchMode), key),
+        Span::styled("  Limit: ", label),
+        Span::styled(format!("{} ", filter.limit), value),
+        Span::styled(keys.pair_hint(KeyContext::Search, Action::MoreResults, Action::FewerResults), key),
+    ]);
+
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Message count, plus which archived conversation is shown, for the chat border
+fn chat_history_title(app: &App) -> Line<'static> {
+    let count = app.chat_messages.len();
+    let mut title = format!(" {} message{} ", count, if count == 1 { "" } else { "s" });
+    if let Some(i) = app.conversation_index {
+        title.push_str(&format!("· conversation {}/{} ", i + 1, app.conversations.conversations.len()));
+    }
+    Line::from(title).right_aligned()
+}
+
+fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    use escrituras_core::ChatRole;
+
+    // Split layout: AI panel on left, scripture content on right (like browse)
+    let [ai_area, content_area] = Layout::horizontal([
+        Constraint::Percentage(app.layout.query_split),
+        Constraint::Percentage(100 - app.layout.query_split),
+    ])
+    .areas(area);
+
+    // Calculate references panel height (if we have references)
+    let refs_height = if app.extracted_references.is_empty() {
+        0
     } else {
         (app.extracted_references.len().min(5) + 2) as u16 // +2 for borders
     };
 
+    // The input grows with its wrapped rows, up to a limit
+    let input_width = ai_area.width.saturating_sub(2) as usize;
+    app.query_input_width = input_width.max(1);
+    let input_rows = textarea::wrap(&app.query_input, input_width);
+    let input_height = (input_rows.len().min(textarea::MAX_ROWS) + 2) as u16;
+
     // AI panel: chat history on top, references (if any), input at bottom
     let ai_layout = if refs_height > 0 {
         Layout::vertical([
             Constraint::Min(0),
             Constraint::Length(refs_height),
-            Constraint::Length(3),
+            Constraint::Length(input_height),
         ])
         .split(ai_area)
     } else {
         Layout::vertical([
             Constraint::Min(0),
             Constraint::Length(0),
-            Constraint::Length(3),
+            Constraint::Length(input_height),
         ])
         .split(ai_area)
     };
@@ -996,7 +2211,7 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
 
     // Determine focus colors
     let ai_focused = app.focus == FocusPane::Navigation;
-    let ai_border_color = if ai_focused { Color::Cyan } else { Color::DarkGray };
+    let ai_border_color = if ai_focused { theme.accent } else { theme.muted };
 
     // Chat history area - show provider and model
     let provider_name = match app.current_provider {
@@ -1007,12 +2222,13 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     let chat_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(ai_border_color))
-        .title(format!(" {}: {} ", provider_name, app.selected_model));
+        .title(format!(" {}: {} ", provider_name, app.selected_model))
+        .title(chat_history_title(app));
 
     let chat_text = if app.chat_messages.is_empty() && !app.query_loading {
         Text::from(Span::styled(
             "Ask a question about the scriptures...",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ))
     } else {
         let mut lines: Vec<Line> = Vec::new();
@@ -1022,15 +2238,15 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
                 ChatRole::User => {
                     lines.push(Line::from(Span::styled(
                         "You:",
-                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
                     )));
-                    lines.push(Line::from(msg.content.as_str()));
+                    lines.extend(msg.content.lines().map(Line::from));
                     lines.push(Line::default());
                 }
                 ChatRole::Assistant => {
                     lines.push(Line::from(Span::styled(
                         "AI:",
-                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                        Style::default().fg(theme.heading).add_modifier(Modifier::BOLD),
                     )));
                     // Split response into lines and parse markdown
                     for line in msg.content.lines() {
@@ -1044,14 +2260,21 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
         if app.query_loading {
             lines.push(Line::from(Span::styled(
                 "AI:",
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-            )));
-            // Animated ellipsis: cycles through ".", "..", "..."
-            let dots = ".".repeat((app.animation_frame as usize) + 1);
-            lines.push(Line::from(Span::styled(
-                format!("Thinking{}", dots),
-                Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+                Style::default().fg(theme.heading).add_modifier(Modifier::BOLD),
             )));
+            if app.streaming_response.is_empty() {
+                // Animated ellipsis: cycles through ".", "..", "..."
+                let dots = ".".repeat((app.animation_frame as usize) + 1);
+                lines.push(Line::from(Span::styled(
+                    format!("Thinking{}", dots),
+                    Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+                )));
+            } else {
+                // Partial answer rendered as it streams in
+                for line in app.streaming_response.lines() {
+                    lines.push(parse_markdown_line(line));
+                }
+            }
         }
 
         Text::from(lines)
@@ -1067,7 +2290,7 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     // Render references panel if we have any
     if !app.extracted_references.is_empty() && refs_area.height > 0 {
         let refs_focused = app.focus == FocusPane::References;
-        let refs_border_color = if refs_focused { Color::Cyan } else { Color::Magenta };
+        let refs_border_color = if refs_focused { theme.accent } else { theme.secondary };
 
         let refs_block = Block::default()
             .borders(Borders::ALL)
@@ -1087,8 +2310,8 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
             .block(refs_block)
             .highlight_style(
                 Style::default()
-                    .bg(Color::Magenta)
-                    .fg(Color::White)
+                    .bg(theme.secondary)
+                    .fg(theme.selection_fg)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol("> ");
@@ -1104,117 +2327,640 @@ fn render_query_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     // Query input at the bottom - highlight when focused or editing
     let input_focused = app.focus == FocusPane::Input;
     let input_border_color = if input_focused || app.input_mode == InputMode::Editing {
-        Color::Yellow
+        theme.heading
     } else {
-        Color::DarkGray
+        theme.muted
     };
 
+    let input_title = match (app.pending_passage.first(), app.pending_passage.last()) {
+        (Some(first), Some(last)) => format!(
+            " Ask about {} {}:{}-{} ",
+            first.book_title, first.chapter_number, first.verse_number, last.verse_number
+        ),
+        _ => " Ask (Tab to focus) ".to_string(),
+    };
     let input_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(input_border_color))
-        .title(" Ask (Tab to focus) ");
+        .title(input_title)
+        .title_bottom(prompt_usage_line(app).right_aligned());
+
+    // Scroll the rows to keep the cursor visible once the box stops growing
+    let (cursor_row, cursor_col) = textarea::cursor_position(&app.query_input, app.query_cursor, input_width);
+    let visible_rows = input_area.height.saturating_sub(2) as usize;
+    let scroll_offset = (cursor_row + 1).saturating_sub(visible_rows);
+    let visible_text: Vec<Line> = input_rows
+        .into_iter()
+        .skip(scroll_offset)
+        .take(visible_rows)
+        .map(Line::from)
+        .collect();
+
+    // Accent text to match the "You:" label
+    let input = Paragraph::new(visible_text)
+        .style(Style::default().fg(theme.accent))
+        .block(input_block);
 
-    // Calculate visible portion of input with horizontal scrolling
-    // Inner width = total width - 2 (for borders)
-    let inner_width = input_area.width.saturating_sub(2) as usize;
-    let cursor_pos = app.query_cursor;
+    frame.render_widget(input, input_area);
 
-    // Calculate scroll offset to keep cursor visible
-    let scroll_offset = if inner_width == 0 {
-        0
-    } else if cursor_pos >= inner_width {
-        cursor_pos - inner_width + 1
+    // Show cursor when editing
+    if app.input_mode == InputMode::Editing {
+        frame.set_cursor_position((
+            input_area.x + cursor_col as u16 + 1,
+            input_area.y + (cursor_row - scroll_offset) as u16 + 1,
+        ));
+    }
+
+    // Right side: Show scripture content or context panel
+    if app.show_context_panel {
+        render_context_panel(app, frame, content_area);
     } else {
-        0
+        render_content(app, frame, content_area);
+    }
+}
+
+/// Estimated prompt size against the model's context window, broken down
+/// while typing, and warning when verses or the prompt's start will be cut
+fn prompt_usage_line(app: &App) -> Line<'static> {
+    let theme = app.theme;
+    let usage = app.prompt_usage();
+    let total = usage.total();
+    let color = if usage.overflows() {
+        theme.error
+    } else if usage.dropped_verses > 0 || total * 5 > usage.window * 4 {
+        theme.warning
+    } else {
+        theme.muted
     };
 
-    // Get the visible slice of the input
-    let visible_text: String = app.query_input
-        .chars()
-        .skip(scroll_offset)
-        .take(inner_width)
+    let mut text = String::from(" ");
+    if app.input_mode == InputMode::Editing {
+        text.push_str(&format!(
+            "prompt {} · verses {} · chat {} · question {} = ",
+            format_tokens(usage.instructions),
+            format_tokens(usage.context),
+            format_tokens(usage.history),
+            format_tokens(usage.question),
+        ));
+    }
+    text.push_str(&format!("~{} / {} tokens ", format_tokens(total), format_tokens(usage.window)));
+    if usage.overflows() {
+        text.push_str("· start will be cut off ");
+    } else if usage.dropped_verses > 0 {
+        text.push_str(&format!("· {} saved verses left out ", usage.dropped_verses));
+    }
+    Line::from(Span::styled(text, Style::default().fg(color)))
+}
+
+/// 950, 1.2k, 128k
+fn format_tokens(tokens: usize) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=9_999 => format!("{:.1}k", tokens as f64 / 1000.0),
+        _ => format!("{}k", tokens / 1000),
+    }
+}
+
+fn render_context_panel(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let content_focused = app.focus == FocusPane::Content;
+    let border_color = if content_focused { theme.accent } else { theme.muted };
+
+    let block = pane_block(app.layout.compact)
+        .border_style(Style::default().fg(border_color))
+        .title(context_panel_title(app));
+
+    let visible = app.visible_context();
+    if visible.is_empty() {
+        let message = match &app.tag_filter {
+            Some(tag) => format!("No saved scriptures tagged #{}.\nPress 'T' to change the filter.", tag),
+            None => "No saved scriptures.\nPress 'x' on a verse to save it.".to_string(),
+        };
+        let placeholder = Paragraph::new(message)
+            .style(Style::default().fg(theme.muted))
+            .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|&i| {
+            let v = &app.session_context[i];
+            let preview: String = v.scripture_text.chars().take(60).collect();
+            let mut title = vec![Span::styled(
+                v.verse_title.clone(),
+                Style::default().fg(theme.heading).bold(),
+            )];
+            for tag in app.verse_tags(&v.verse_title) {
+                title.push(Span::styled(format!(" #{}", tag), Style::default().fg(theme.secondary)));
+            }
+            ListItem::new(vec![Line::from(title), Line::from(format!("{}...", preview))])
+        })
         .collect();
 
-    // Use cyan text to match the "You:" style - visible in both light and dark terminals
-    let input = Paragraph::new(visible_text)
-        .style(Style::default().fg(Color::Cyan))
-        .block(input_block);
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    // Each item is 2 lines, so calculate visible items accordingly
+    let visible_items = (pane_block(app.layout.compact).inner(area).height / 2) as usize;
+    app.context_visible_height = visible_items;
+    ensure_selected_visible(&mut app.context_state, visible_items);
+
+    frame.render_stateful_widget(list, area, &mut app.context_state);
+}
+
+/// " Saved Scriptures (10) ", or " Saved Scriptures (3 of 10 · #faith) " when filtered by tag
+fn context_panel_title(app: &App) -> String {
+    match &app.tag_filter {
+        Some(tag) => format!(
+            " Saved Scriptures ({} of {} · #{}) ",
+            app.visible_context().len(),
+            app.session_context.len(),
+            tag
+        ),
+        None => format!(" Saved Scriptures ({}) ", app.session_context.len()),
+    }
+}
+
+fn render_model_picker(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    use ratatui::widgets::Clear;
+
+    // Calculate popup size and position (centered)
+    let popup_width = 40.min(area.width.saturating_sub(4));
+    let popup_height = (app.available_models.len() as u16 + 2).min(area.height.saturating_sub(4));
+
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Clear the area behind the popup
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(if app.regenerate_after_model_pick {
+            " Regenerate With Model (Enter to select, Esc to cancel) "
+        } else {
+            " Select Model (Enter to select, Esc to cancel) "
+        });
+
+    let items: Vec<ListItem> = app
+        .available_models
+        .iter()
+        .map(|model| {
+            let style = if model == &app.selected_model {
+                Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!(" {} ", model)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, popup_area, &mut app.model_picker_state);
+}
+
+fn render_provider_picker(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    use ratatui::widgets::Clear;
+
+    let providers = Provider::all();
+
+    // Calculate popup size and position (centered)
+    let popup_width = 45.min(area.width.saturating_sub(4));
+    let popup_height = (providers.len() as u16 + 2).min(area.height.saturating_sub(4));
+
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Clear the area behind the popup
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Select Provider ");
+
+    let items: Vec<ListItem> = providers
+        .iter()
+        .map(|provider| {
+            let key_source = app.get_key_source(*provider);
+            let is_current = *provider == app.current_provider;
 
+            let status = match key_source {
+                Some("env") => "(env var)",
+                Some("config") => "(configured)",
+                Some("local") => "(local)",
+                _ => "(needs key)",
+            };
+            let prefix = if is_current { "* " } else { "  " };
+
+            let style = if is_current {
+                Style::default().fg(theme.success).add_modifier(Modifier::BOLD)
+            } else if key_source.is_some() {
+                Style::default()
+            } else {
+                Style::default().fg(theme.muted)
+            };
+
+            ListItem::new(format!("{}{} {}", prefix, provider.display_name(), status)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, popup_area, &mut app.provider_picker_state);
+}
+
+fn render_api_key_input(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    use ratatui::widgets::Clear;
+
+    let provider_name = app.api_key_target_provider
+        .map(|p| p.display_name())
+        .unwrap_or("Provider");
+
+    // Calculate popup size and position (centered)
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = 7;
+
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Clear the area behind the popup
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.heading))
+        .title(format!(" Enter API Key for {} ", provider_name));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    // Instructions
+    let instructions = Paragraph::new("Paste your API key below. Press Enter to save, Esc to cancel.")
+        .style(Style::default().fg(theme.muted));
+
+    let instructions_area = Rect::new(inner.x, inner.y, inner.width, 1);
+    frame.render_widget(instructions, instructions_area);
+
+    // Input field
+    let input_area = Rect::new(inner.x, inner.y + 2, inner.width, 1);
+
+    // Mask the key with asterisks for security (show last 4 chars)
+    let display_text = if app.api_key_input.is_empty() {
+        String::new()
+    } else if app.api_key_input.len() <= 4 {
+        "*".repeat(app.api_key_input.len())
+    } else {
+        let masked_len = app.api_key_input.len() - 4;
+        let last_four: String = app.api_key_input.chars().skip(masked_len).collect();
+        format!("{}...{}", "*".repeat(masked_len.min(20)), last_four)
+    };
+
+    let input = Paragraph::new(display_text)
+        .style(Style::default().fg(theme.accent));
+
+    frame.render_widget(input, input_area);
+
+    // Show cursor
+    let cursor_x = app.api_key_input_cursor.min(input_area.width as usize) as u16;
+    frame.set_cursor_position((input_area.x + cursor_x, input_area.y));
+
+    // Status line
+    let char_count = format!("{} characters", app.api_key_input.len());
+    let status = Paragraph::new(char_count)
+        .style(Style::default().fg(theme.muted));
+
+    let status_area = Rect::new(inner.x, inner.y + 4, inner.width, 1);
+    frame.render_widget(status, status_area);
+}
+
+fn render_command_palette(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    use ratatui::widgets::Clear;
+
+    let matches = app.palette_matches();
+
+    // Calculate popup size and position (top-center, like an editor palette)
+    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_height = (matches.len() as u16 + 4).min(area.height.saturating_sub(2)).max(5);
+
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = area.height / 6;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Clear the area behind the popup
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Commands ");
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let [input_area, _spacer, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.muted)),
+        Span::styled(app.palette_input.as_str(), Style::default().fg(theme.accent)),
+    ]));
     frame.render_widget(input, input_area);
+    let cursor_x = (app.palette_input.width() + 2).min(input_area.width as usize) as u16;
+    frame.set_cursor_position((input_area.x + cursor_x, input_area.y));
+
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new(Span::styled(" No matching commands", Style::default().fg(theme.muted)))]
+    } else {
+        matches.iter().map(|action| ListItem::new(format!(" {}", action.label()))).collect()
+    };
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    frame.render_stateful_widget(list, list_area, &mut app.palette_state);
+}
+
+fn render_filter_dropdown(app: &mut App, frame: &mut Frame, area: Rect) {
+    use ratatui::widgets::Clear;
+    let theme = app.theme;
+
+    let options = app.filter_options();
+    let title = match app.filter_dropdown {
+        Some(FilterDropdown::Book) => " Filter by Book ",
+        _ => " Filter by Volume ",
+    };
+
+    // Calculate popup size and position (centered)
+    let popup_width = 40.min(area.width.saturating_sub(4));
+    let popup_height = (options.len() as u16 + 2).min(area.height.saturating_sub(4));
+
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Clear the area behind the popup
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(title);
+
+    let items: Vec<ListItem> = options
+        .iter()
+        .map(|option| ListItem::new(format!(" {} ", option)))
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, popup_area, &mut app.filter_dropdown_state);
+}
+
+/// Words of the verse on the left, the highlighted word's 1828 definition on the right
+/// Verse-of-the-day splash shown at launch
+fn render_votd(app: &App, frame: &mut Frame, area: Rect) {
+    use ratatui::widgets::Clear;
+    let theme = app.theme;
+    let Some(verse) = &app.votd else {
+        return;
+    };
+
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let text_width = popup_width.saturating_sub(4) as usize;
+    let text_lines = wrap_text_to_width(&verse.scripture_text, text_width);
+    let popup_height = (text_lines.len() as u16 + 6).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        (area.width.saturating_sub(popup_width)) / 2,
+        (area.height.saturating_sub(popup_height)) / 2,
+        popup_width,
+        popup_height,
+    );
+    frame.render_widget(Clear, popup_area);
+
+    let mut lines = vec![Line::default()];
+    lines.extend(text_lines.into_iter().map(|l| Line::from(Span::styled(l, Style::default().fg(theme.text))).centered()));
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(format!("— {}", verse.verse_title), Style::default().fg(theme.secondary))).centered());
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Verse of the Day ")
+        .title_bottom(Line::from(" Enter read chapter · x save · any key to continue ").centered());
+    frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_word_lookup(app: &mut App, frame: &mut Frame, area: Rect) {
+    use ratatui::widgets::Clear;
+    let theme = app.theme;
+
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let popup_height = 24.min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let definition = app.word_lookup_definition().map(|(headword, defs)| {
+        let mut lines = vec![
+            Line::from(Span::styled(headword.to_uppercase(), Style::default().fg(theme.heading).bold())),
+            Line::default(),
+        ];
+        for (i, def) in defs.iter().enumerate() {
+            if defs.len() > 1 {
+                lines.push(Line::from(Span::styled(format!("{}.", i + 1), Style::default().fg(theme.muted))));
+            }
+            lines.push(Line::from(def.clone()));
+            lines.push(Line::default());
+        }
+        lines
+    });
+    let Some(lookup) = &mut app.word_lookup else {
+        return;
+    };
+
+    let [words_area, definition_area] = Layout::horizontal([
+        Constraint::Length(20),
+        Constraint::Min(0),
+    ])
+    .areas(popup_area);
 
-    // Show cursor when editing
-    if app.input_mode == InputMode::Editing {
-        let cursor_x = (cursor_pos - scroll_offset) as u16;
-        frame.set_cursor_position((
-            input_area.x + cursor_x + 1,
-            input_area.y + 1,
-        ));
-    }
+    let words_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" {} ", lookup.reference));
+    let items: Vec<ListItem> = lookup.words.iter().map(|w| ListItem::new(w.as_str())).collect();
+    let list = List::new(items)
+        .block(words_block)
+        .highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+    ensure_selected_visible(&mut lookup.state, words_area.height.saturating_sub(2) as usize);
+    frame.render_stateful_widget(list, words_area, &mut lookup.state);
 
-    // Right side: Show scripture content or context panel
-    if app.show_context_panel {
-        render_context_panel(app, frame, content_area);
-    } else {
-        render_content(app, frame, content_area);
-    }
+    let definition_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Webster 1828 ")
+        .title_bottom(Line::from(" j/k word · Ctrl-d/u scroll · Esc close ").right_aligned());
+    let paragraph = Paragraph::new(definition.unwrap_or_default())
+        .block(definition_block)
+        .wrap(Wrap { trim: true })
+        .scroll((lookup.scroll, 0));
+    frame.render_widget(paragraph, definition_area);
 }
 
-fn render_context_panel(app: &mut App, frame: &mut Frame, area: Rect) {
-    let content_focused = app.focus == FocusPane::Content;
-    let border_color = if content_focused { Color::Cyan } else { Color::DarkGray };
+fn render_strongs_lookup(app: &mut App, frame: &mut Frame, area: Rect) {
+    use ratatui::widgets::Clear;
+    let theme = app.theme;
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(border_color))
-        .title(format!(" Saved Scriptures ({}) ", app.session_context.len()));
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let popup_height = 24.min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
 
-    if app.session_context.is_empty() {
-        let placeholder = Paragraph::new("No saved scriptures.\nPress 'x' on a verse to save it.")
-            .style(Style::default().fg(Color::DarkGray))
-            .block(block);
-        frame.render_widget(placeholder, area);
+    frame.render_widget(Clear, popup_area);
+
+    let definition = app.strongs_lookup_entries().map(|(word, entries)| {
+        let mut lines = vec![
+            Line::from(Span::styled(word.text.clone(), Style::default().fg(theme.heading).bold())),
+            Line::default(),
+        ];
+        for (number, entry) in entries {
+            let mut heading = vec![Span::styled(number.to_string(), Style::default().fg(theme.accent).bold())];
+            if let Some(entry) = entry {
+                heading.push(Span::raw(format!("  {}", entry.lemma)));
+                if !entry.transliteration.is_empty() {
+                    heading.push(Span::styled(format!("  {}", entry.transliteration), Style::default().fg(theme.muted).italic()));
+                }
+            }
+            lines.push(Line::from(heading));
+            match entry {
+                Some(entry) => {
+                    if !entry.definition.is_empty() {
+                        lines.push(Line::from(entry.definition.clone()));
+                    }
+                    if !entry.kjv_usage.is_empty() {
+                        lines.push(Line::from(Span::styled(format!("KJV: {}", entry.kjv_usage), Style::default().fg(theme.muted))));
+                    }
+                }
+                None => lines.push(Line::from(Span::styled("Not in the lexicon", Style::default().fg(theme.muted)))),
+            }
+            lines.push(Line::default());
+        }
+        lines
+    });
+    let Some(lookup) = &mut app.strongs_lookup else {
         return;
-    }
+    };
 
-    let items: Vec<ListItem> = app
-        .session_context
-        .iter()
-        .map(|v| {
-            let preview: String = v.scripture_text.chars().take(60).collect();
-            ListItem::new(vec![
-                Line::from(Span::styled(
-                    v.verse_title.clone(),
-                    Style::default().fg(Color::Yellow).bold(),
-                )),
-                Line::from(format!("{}...", preview)),
-            ])
-        })
-        .collect();
+    let [words_area, definition_area] = Layout::horizontal([
+        Constraint::Length(24),
+        Constraint::Min(0),
+    ])
+    .areas(popup_area);
 
+    let words_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" {} ", lookup.reference));
+    let items: Vec<ListItem> = lookup.words.iter().map(|w| ListItem::new(w.text.as_str())).collect();
     let list = List::new(items)
-        .block(block)
+        .block(words_block)
         .highlight_style(
             Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
+    ensure_selected_visible(&mut lookup.state, words_area.height.saturating_sub(2) as usize);
+    frame.render_stateful_widget(list, words_area, &mut lookup.state);
 
-    // Each item is 2 lines, so calculate visible items accordingly
-    let visible_items = (area.height.saturating_sub(2) / 2) as usize;
-    app.context_visible_height = visible_items;
-    ensure_selected_visible(&mut app.context_state, visible_items);
-
-    frame.render_stateful_widget(list, area, &mut app.context_state);
+    let definition_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Strong's ")
+        .title_bottom(Line::from(" j/k word · Ctrl-d/u scroll · Esc close ").right_aligned());
+    let paragraph = Paragraph::new(definition.unwrap_or_default())
+        .block(definition_block)
+        .wrap(Wrap { trim: true })
+        .scroll((lookup.scroll, 0));
+    frame.render_widget(paragraph, definition_area);
 }
 
-fn render_model_picker(app: &mut App, frame: &mut Frame, area: Rect) {
+fn render_copy_menu(app: &mut App, frame: &mut Frame, area: Rect) {
     use ratatui::widgets::Clear;
+    let theme = app.theme;
 
-    // Calculate popup size and position (centered)
-    let popup_width = 40.min(area.width.saturating_sub(4));
-    let popup_height = (app.available_models.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let formats = CopyFormat::all();
+
+    // Calculate popup size and position (centered, list plus a preview)
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = (formats.len() as u16 + 10).min(area.height.saturating_sub(4));
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -1226,43 +2972,54 @@ fn render_model_picker(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(" Select Model (Enter to select, Esc to cancel) ");
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Copy As ");
 
-    let items: Vec<ListItem> = app
-        .available_models
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let [list_area, _spacer, preview_area] = Layout::vertical([
+        Constraint::Length(formats.len() as u16),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+
+    let items: Vec<ListItem> = formats
         .iter()
-        .map(|model| {
-            let style = if model == &app.selected_model {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-            ListItem::new(format!(" {} ", model)).style(style)
+        .map(|format| {
+            let marker = if *format == app.copy_format { " (current)" } else { "" };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!(" {}", format.label())),
+                Span::styled(marker, Style::default().fg(theme.success)),
+            ]))
         })
         .collect();
 
     let list = List::new(items)
-        .block(block)
         .highlight_style(
             Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg)
                 .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("> ");
+        );
 
-    frame.render_stateful_widget(list, popup_area, &mut app.model_picker_state);
+    frame.render_stateful_widget(list, list_area, &mut app.copy_menu_state);
+
+    // Preview of the highlighted format
+    let preview = Paragraph::new(app.selected_copy_format().format_in(&app.copy_menu_verses, &app.gospel_library_language))
+        .style(Style::default().fg(theme.muted))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, preview_area);
 }
 
-fn render_provider_picker(app: &mut App, frame: &mut Frame, area: Rect) {
+fn render_help(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
     use ratatui::widgets::Clear;
 
-    let providers = Provider::all();
-
-    // Calculate popup size and position (centered)
-    let popup_width = 45.min(area.width.saturating_sub(4));
-    let popup_height = (providers.len() as u16 + 2).min(area.height.saturating_sub(4));
+    // Calculate popup size and position (centered, most of the screen)
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = area.height.saturating_sub(4);
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -1274,58 +3031,57 @@ fn render_provider_picker(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .title(" Select Provider ");
+        .border_style(Style::default().fg(theme.accent))
+        .title(" Help ");
 
-    let items: Vec<ListItem> = providers
-        .iter()
-        .map(|provider| {
-            let key_source = app.get_key_source(*provider);
-            let is_current = *provider == app.current_provider;
+    let inner = block.inner(popup_area);
 
-            let status = match key_source {
-                Some("env") => "(env var)",
-                Some("config") => "(configured)",
-                Some("local") => "(local)",
-                _ => "(needs key)",
-            };
-            let prefix = if is_current { "* " } else { "  " };
+    let section_style = Style::default().fg(theme.accent).add_modifier(Modifier::BOLD);
+    let key_style = Style::default().fg(theme.heading);
+    let desc_style = Style::default().fg(theme.text);
 
-            let style = if is_current {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            } else if key_source.is_some() {
-                Style::default()
-            } else {
-                Style::default().fg(Color::DarkGray)
-            };
+    // One section per context, generated from the keymap table
+    let mut lines: Vec<Line> = Vec::new();
+    for context in KeyContext::all() {
+        let bindings: Vec<_> = app.keymap.bindings().iter().filter(|b| b.context == *context).collect();
+        if bindings.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(context.title(), section_style)));
+        for binding in bindings {
+            let keys = binding.keys.iter().map(|k| k.to_string()).collect::<Vec<_>>().join("/");
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<18}", keys), key_style),
+                Span::styled(binding.description, desc_style),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Ctrl-C quits, or stops an answer while it is streaming",
+        Style::default().fg(theme.muted),
+    )));
 
-            ListItem::new(format!("{}{} {}", prefix, provider.display_name(), status)).style(style)
-        })
-        .collect();
+    // Clamp scroll so the last line stays on screen
+    let max_scroll = (lines.len() as u16).saturating_sub(inner.height);
+    app.help_scroll = app.help_scroll.min(max_scroll);
 
-    let list = List::new(items)
+    let help = Paragraph::new(lines)
         .block(block)
-        .highlight_style(
-            Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("> ");
-
-    frame.render_stateful_widget(list, popup_area, &mut app.provider_picker_state);
+        .scroll((app.help_scroll, 0));
+    frame.render_widget(help, popup_area);
 }
 
-fn render_api_key_input(app: &App, frame: &mut Frame, area: Rect) {
+fn render_goto_input(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
     use ratatui::widgets::Clear;
 
-    let provider_name = app.api_key_target_provider
-        .map(|p| p.display_name())
-        .unwrap_or("Provider");
-
     // Calculate popup size and position (centered)
-    let popup_width = 60.min(area.width.saturating_sub(4));
-    let popup_height = 7;
+    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_height = 5;
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -1337,52 +3093,79 @@ fn render_api_key_input(app: &App, frame: &mut Frame, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .title(format!(" Enter API Key for {} ", provider_name));
+        .border_style(Style::default().fg(theme.accent))
+        .title(if app.goto_into_split { " Open in Split View " } else { " Go to Reference " });
 
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
 
-    // Instructions
-    let instructions = Paragraph::new("Paste your API key below. Press Enter to save, Esc to cancel.")
-        .style(Style::default().fg(Color::DarkGray));
+    // Input field
+    let input_area = Rect::new(inner.x, inner.y, inner.width, 1);
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(":", Style::default().fg(theme.muted)),
+        Span::styled(app.goto_input.as_str(), Style::default().fg(theme.accent)),
+    ]));
+    frame.render_widget(input, input_area);
 
-    let instructions_area = Rect::new(inner.x, inner.y, inner.width, 1);
-    frame.render_widget(instructions, instructions_area);
+    let cursor_x = (textarea::width_before(&app.goto_input, app.goto_cursor) + 1).min(input_area.width as usize) as u16;
+    frame.set_cursor_position((input_area.x + cursor_x, input_area.y));
 
-    // Input field
-    let input_area = Rect::new(inner.x, inner.y + 2, inner.width, 1);
+    // Hint or error line
+    let status = match &app.goto_error {
+        Some(error) => Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+        None => Paragraph::new("e.g. Alma 32:21, 1 Ne 3:7-9, dc 76")
+            .style(Style::default().fg(theme.muted)),
+    };
+    let status_area = Rect::new(inner.x, inner.y + 2, inner.width, 1);
+    frame.render_widget(status, status_area);
+}
 
-    // Mask the key with asterisks for security (show last 4 chars)
-    let display_text = if app.api_key_input.is_empty() {
-        String::new()
-    } else if app.api_key_input.len() <= 4 {
-        "*".repeat(app.api_key_input.len())
-    } else {
-        let masked_len = app.api_key_input.len() - 4;
-        let last_four: String = app.api_key_input.chars().skip(masked_len).collect();
-        format!("{}...{}", "*".repeat(masked_len.min(20)), last_four)
+fn render_tag_input(app: &App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    use ratatui::widgets::Clear;
+    let Some(input) = &app.tag_input else {
+        return;
     };
+    let verse = app
+        .context_state
+        .selected()
+        .and_then(|i| app.visible_context().get(i).copied())
+        .map(|i| app.session_context[i].verse_title.as_str())
+        .unwrap_or("");
+
+    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
 
-    let input = Paragraph::new(display_text)
-        .style(Style::default().fg(Color::Cyan));
+    frame.render_widget(Clear, popup_area);
 
-    frame.render_widget(input, input_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" Tags for {} ", verse));
 
-    // Show cursor
-    let cursor_x = app.api_key_input_cursor.min(input_area.width as usize) as u16;
-    frame.set_cursor_position((input_area.x + cursor_x, input_area.y));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
 
-    // Status line
-    let char_count = format!("{} characters", app.api_key_input.len());
-    let status = Paragraph::new(char_count)
-        .style(Style::default().fg(Color::DarkGray));
+    let input_area = Rect::new(inner.x, inner.y, inner.width, 1);
+    let line = Paragraph::new(Line::from(vec![
+        Span::styled("#", Style::default().fg(theme.muted)),
+        Span::styled(input.as_str(), Style::default().fg(theme.accent)),
+    ]));
+    frame.render_widget(line, input_area);
 
-    let status_area = Rect::new(inner.x, inner.y + 4, inner.width, 1);
-    frame.render_widget(status, status_area);
+    let cursor_x = (textarea::width_before(input, app.tag_cursor) + 1).min(input_area.width as usize) as u16;
+    frame.set_cursor_position((input_area.x + cursor_x, input_area.y));
+
+    let hint = Paragraph::new("e.g. faith, prayer · empty to clear")
+        .style(Style::default().fg(theme.muted));
+    frame.render_widget(hint, Rect::new(inner.x, inner.y + 2, inner.width, 1));
 }
 
 fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
     let Some(state) = &app.focus_state else {
         return;
     };
@@ -1408,14 +3191,51 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     .areas(centered_area);
 
     // Render title (verse reference)
-    let title_block = Block::default()
+    let mut title_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.accent));
+
+    // Which deck passage this is, when reviewing a deck
+    if let Some(review) = &state.deck {
+        title_block = title_block.title(
+            Line::from(Span::styled(
+                format!(" {} {}/{} ", review.deck.name, review.position + 1, review.passages.len()),
+                Style::default().fg(theme.muted),
+            ))
+            .centered(),
+        );
+    }
+
+    // Memorization progress for the current verse, from past typed attempts
+    if state.sub_mode == FocusSubMode::Memorize && !state.is_passage() {
+        if let Some(stats) = app.memorize_history.stats(&state.current_verse.verse_title) {
+            let attempts_label = if stats.attempts == 1 { "attempt" } else { "attempts" };
+            let mut spans = vec![
+                Span::styled(format!(" best {:.0}%", stats.best * 100.0), accuracy_style(&theme, stats.best)),
+                Span::styled(", ", Style::default().fg(theme.muted)),
+                Span::styled(format!("last {:.0}%", stats.last * 100.0), accuracy_style(&theme, stats.last)),
+                Span::styled(
+                    format!(", {} {} ", stats.attempts, attempts_label),
+                    Style::default().fg(theme.muted),
+                ),
+            ];
+            // Progress over time: one bar per recent attempt, oldest first
+            if stats.attempts > 1 {
+                let attempts = &app.memorize_history.verses[&state.current_verse.verse_title];
+                let recent = &attempts[attempts.len().saturating_sub(PROGRESS_ATTEMPTS)..];
+                spans.extend(recent.iter().map(|attempt| {
+                    Span::styled(accuracy_bar(attempt.accuracy).to_string(), accuracy_style(&theme, attempt.accuracy))
+                }));
+                spans.push(Span::raw(" "));
+            }
+            title_block = title_block.title_bottom(Line::from(spans).centered());
+        }
+    }
 
     let title_text = Paragraph::new(Line::from(vec![
         Span::styled(
-            state.current_verse.verse_title.clone(),
-            Style::default().fg(Color::Yellow).bold(),
+            state.passage_title(),
+            Style::default().fg(theme.heading).bold(),
         ),
     ]))
     .block(title_block)
@@ -1433,17 +3253,23 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
                 FlashcardPhase::Typing => " Flashcard (type your attempt) ".to_string(),
                 FlashcardPhase::Revealed => " Flashcard (revealed) ".to_string(),
             },
+            MemorizeMode::FirstLetter => match state.flashcard_phase {
+                FlashcardPhase::Hidden if state.first_letter_peek => " First Letters (peeking) ".to_string(),
+                FlashcardPhase::Hidden => " First Letters ".to_string(),
+                FlashcardPhase::Typing => " First Letters (type your attempt) ".to_string(),
+                FlashcardPhase::Revealed => " First Letters (revealed) ".to_string(),
+            },
         },
     };
 
     let content_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.muted))
         .title(content_title);
 
     // Handle special rendering for flashcard typing and revealed phases
     if state.sub_mode == FocusSubMode::Memorize
-        && state.memorize_mode == MemorizeMode::Flashcard
+        && state.memorize_mode.supports_typing()
     {
         match state.flashcard_phase {
             FlashcardPhase::Typing => {
@@ -1451,19 +3277,44 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
                 let inner = content_block.inner(content_area);
                 frame.render_widget(content_block, content_area);
 
+                // First-letter mode keeps its hint visible above the input
+                let hint_height = if state.memorize_mode == MemorizeMode::FirstLetter {
+                    let width = inner.width.max(1) as usize;
+                    render_memorize_text(state)
+                        .lines()
+                        .map(|line| (line.width() / width) as u16 + 1)
+                        .sum::<u16>()
+                        + 1
+                } else {
+                    0
+                };
+
                 // Layout for input
-                let [prompt_area, input_area] = Layout::vertical([
+                let [hint_area, prompt_area, input_area] = Layout::vertical([
+                    Constraint::Length(hint_height),
                     Constraint::Length(2),
                     Constraint::Min(3),
                 ])
                 .areas(inner);
 
-                let prompt = Paragraph::new("Type the scripture from memory:")
-                    .style(Style::default().fg(Color::DarkGray));
+                if hint_height > 0 {
+                    let hint = Paragraph::new(render_memorize_text(state))
+                        .style(Style::default().fg(theme.muted))
+                        .wrap(Wrap { trim: true });
+                    frame.render_widget(hint, hint_area);
+                }
+
+                let prompt_text = if state.is_passage() {
+                    "Type the passage from memory:"
+                } else {
+                    "Type the scripture from memory:"
+                };
+                let prompt = Paragraph::new(prompt_text)
+                    .style(Style::default().fg(theme.muted));
                 frame.render_widget(prompt, prompt_area);
 
                 let input = Paragraph::new(state.flashcard_input.as_str())
-                    .style(Style::default().fg(Color::Cyan))
+                    .style(Style::default().fg(theme.accent))
                     .wrap(Wrap { trim: true });
                 frame.render_widget(input, input_area);
 
@@ -1479,7 +3330,7 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
                     let mut char_count = 0usize;
 
                     for word in state.flashcard_input.split_whitespace() {
-                        let word_len = word.chars().count();
+                        let word_len = word.width();
 
                         // Check if we need to wrap before this word
                         if col > 0 && col + 1 + word_len > input_width {
@@ -1497,12 +3348,12 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
                         }
 
                         // Process each character in the word
-                        for _ in word.chars() {
+                        for c in word.chars() {
                             if char_count == cursor_char_pos {
                                 break;
                             }
                             char_count += 1;
-                            col += 1;
+                            col += c.width().unwrap_or(0);
                         }
 
                         if char_count >= cursor_char_pos {
@@ -1527,42 +3378,64 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
                 frame.render_widget(content_block, content_area);
 
                 // Layout for diff display
-                let [user_label_area, user_area, _spacer, actual_label_area, actual_area] = Layout::vertical([
+                let [user_label_area, user_area, _spacer, actual_label_area, actual_area, _spacer2, score_area] = Layout::vertical([
                     Constraint::Length(1),
                     Constraint::Min(2),
                     Constraint::Length(1),
                     Constraint::Length(1),
                     Constraint::Min(2),
+                    Constraint::Length(1),
+                    Constraint::Length(2),
                 ])
                 .areas(inner);
 
                 // User's attempt label
                 let user_label = Paragraph::new("Your attempt:")
-                    .style(Style::default().fg(Color::DarkGray));
+                    .style(Style::default().fg(theme.muted));
                 frame.render_widget(user_label, user_label_area);
 
                 // Compute diff and render user's attempt with highlighting
                 let diff_result = compute_word_diff(
-                    &state.current_verse.scripture_text,
+                    &state.passage_text(),
                     &state.flashcard_input,
                 );
 
-                let user_spans = render_diff_user_attempt(&diff_result);
+                let user_spans = render_diff_user_attempt(&theme, &diff_result);
                 let user_text = Paragraph::new(Line::from(user_spans))
                     .wrap(Wrap { trim: true });
                 frame.render_widget(user_text, user_area);
 
                 // Actual text label
                 let actual_label = Paragraph::new("Actual scripture:")
-                    .style(Style::default().fg(Color::DarkGray));
+                    .style(Style::default().fg(theme.muted));
                 frame.render_widget(actual_label, actual_label_area);
 
                 // Render actual text with missing words highlighted
-                let actual_spans = render_diff_actual_text(&diff_result);
+                let actual_spans = render_diff_actual_text(&theme, &diff_result);
                 let actual_text = Paragraph::new(Line::from(actual_spans))
                     .wrap(Wrap { trim: true });
                 frame.render_widget(actual_text, actual_area);
 
+                // Accuracy score, with a per-verse breakdown for passages
+                let overall = word_accuracy(&diff_result.original_words);
+                let mut score_spans = vec![Span::styled(
+                    format!("Accuracy: {:.0}%", overall * 100.0),
+                    accuracy_style(&theme, overall).bold(),
+                )];
+                if state.is_passage() {
+                    let scores = passage_verse_accuracies(&state.passage, &diff_result);
+                    for (verse, accuracy) in state.passage.iter().zip(scores) {
+                        score_spans.push(Span::raw("  "));
+                        score_spans.push(Span::styled(
+                            format!("v{} {:.0}%", verse.verse_number, accuracy * 100.0),
+                            accuracy_style(&theme, accuracy),
+                        ));
+                    }
+                }
+                let score = Paragraph::new(Line::from(score_spans))
+                    .wrap(Wrap { trim: true });
+                frame.render_widget(score, score_area);
+
                 return;
             }
             _ => {
@@ -1574,7 +3447,11 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     // Default content rendering with vertical centering and horizontal padding
     let content_text = match state.sub_mode {
         FocusSubMode::Reading => {
-            state.current_verse.scripture_text.clone()
+            if state.is_passage() {
+                format_passage(state, |text| text.to_string())
+            } else {
+                state.current_verse.scripture_text.clone()
+            }
         }
         FocusSubMode::Memorize => {
             render_memorize_text(state)
@@ -1596,17 +3473,21 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     );
 
     // Estimate text height for vertical centering
-    // Count wrapped lines based on padded width
+    // Count wrapped lines based on padded width (passages span several lines)
     let text_lines: usize = content_text
-        .split_whitespace()
-        .fold((0usize, 0usize), |(lines, line_len), word| {
-            let word_len = word.chars().count() + 1; // +1 for space
-            if line_len + word_len > padded_width as usize {
-                (lines + 1, word_len)
-            } else {
-                (lines, line_len + word_len)
-            }
-        }).0 + 1; // +1 for the final line
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .fold((0usize, 0usize), |(lines, line_len), word| {
+                    let word_len = word.width() + 1; // +1 for space
+                    if line_len + word_len > padded_width as usize {
+                        (lines + 1, word_len)
+                    } else {
+                        (lines, line_len + word_len)
+                    }
+                }).0 + 1 // +1 for the final line
+        })
+        .sum();
 
     let text_height = text_lines as u16;
     let available_height = padded_area.height;
@@ -1627,125 +3508,38 @@ fn render_focus_screen(app: &mut App, frame: &mut Frame, area: Rect) {
     frame.render_widget(content, centered_text_area);
 }
 
-/// Represents the diff result between original and user text
-#[derive(Debug)]
-struct DiffResult {
-    /// Words from original with their status
-    original_words: Vec<(String, WordStatus)>,
-    /// Words from user with their status
-    user_words: Vec<(String, WordStatus)>,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum WordStatus {
-    Correct,  // Word matches
-    Missing,  // Word in original but not in user
-    Wrong,    // Word in user but not matching original
-}
-
-/// Normalize a word for comparison (lowercase, strip punctuation)
-fn normalize_word(word: &str) -> String {
-    word.chars()
-        .filter(|c| c.is_alphabetic())
-        .collect::<String>()
-        .to_lowercase()
-}
-
-/// Compute LCS (Longest Common Subsequence) for word alignment
-fn compute_lcs(original: &[String], user: &[String]) -> Vec<(usize, usize)> {
-    let m = original.len();
-    let n = user.len();
-
-    // Build LCS table
-    let mut dp = vec![vec![0usize; n + 1]; m + 1];
-    for i in 1..=m {
-        for j in 1..=n {
-            if original[i - 1] == user[j - 1] {
-                dp[i][j] = dp[i - 1][j - 1] + 1;
-            } else {
-                dp[i][j] = dp[i - 1][j].max(dp[i][j - 1]);
-            }
-        }
-    }
-
-    // Backtrack to find matching pairs
-    let mut matches = Vec::new();
-    let mut i = m;
-    let mut j = n;
-    while i > 0 && j > 0 {
-        if original[i - 1] == user[j - 1] {
-            matches.push((i - 1, j - 1));
-            i -= 1;
-            j -= 1;
-        } else if dp[i - 1][j] > dp[i][j - 1] {
-            i -= 1;
-        } else {
-            j -= 1;
-        }
+/// Success color for strong recall, warning for partial, error for weak
+fn accuracy_style(theme: &Theme, accuracy: f32) -> Style {
+    if accuracy >= 0.9 {
+        Style::default().fg(theme.success)
+    } else if accuracy >= 0.6 {
+        Style::default().fg(theme.warning)
+    } else {
+        Style::default().fg(theme.error)
     }
-
-    matches.reverse();
-    matches
 }
 
-/// Compute word-by-word diff using LCS
-fn compute_word_diff(original: &str, user: &str) -> DiffResult {
-    // Split into words, preserving original forms
-    let orig_words: Vec<&str> = original.split_whitespace().collect();
-    let user_words: Vec<&str> = user.split_whitespace().collect();
-
-    // Normalize for comparison
-    let orig_normalized: Vec<String> = orig_words.iter().map(|w| normalize_word(w)).collect();
-    let user_normalized: Vec<String> = user_words.iter().map(|w| normalize_word(w)).collect();
-
-    // Find LCS matches
-    let matches = compute_lcs(&orig_normalized, &user_normalized);
-    let match_set_orig: std::collections::HashSet<usize> = matches.iter().map(|(o, _)| *o).collect();
-    let match_set_user: std::collections::HashSet<usize> = matches.iter().map(|(_, u)| *u).collect();
+/// How many of a verse's latest attempts its progress bars show
+const PROGRESS_ATTEMPTS: usize = 12;
 
-    // Build result for original words
-    let original_result: Vec<(String, WordStatus)> = orig_words
-        .iter()
-        .enumerate()
-        .map(|(i, w)| {
-            if match_set_orig.contains(&i) {
-                (w.to_string(), WordStatus::Correct)
-            } else {
-                (w.to_string(), WordStatus::Missing)
-            }
-        })
-        .collect();
-
-    // Build result for user words
-    let user_result: Vec<(String, WordStatus)> = user_words
-        .iter()
-        .enumerate()
-        .map(|(i, w)| {
-            if match_set_user.contains(&i) {
-                (w.to_string(), WordStatus::Correct)
-            } else {
-                (w.to_string(), WordStatus::Wrong)
-            }
-        })
-        .collect();
-
-    DiffResult {
-        original_words: original_result,
-        user_words: user_result,
-    }
+/// A bar as tall as the accuracy, for charting attempts over time
+fn accuracy_bar(accuracy: f32) -> char {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let level = (accuracy.clamp(0.0, 1.0) * (BARS.len() - 1) as f32).round() as usize;
+    BARS[level]
 }
 
 /// Render user's attempt with diff highlighting
-fn render_diff_user_attempt(diff: &DiffResult) -> Vec<Span<'static>> {
+fn render_diff_user_attempt(theme: &Theme, diff: &DiffResult) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
     for (i, (word, status)) in diff.user_words.iter().enumerate() {
         if i > 0 {
             spans.push(Span::raw(" "));
         }
         let style = match status {
-            WordStatus::Correct => Style::default().fg(Color::Green),
-            WordStatus::Wrong => Style::default().fg(Color::Red),
-            WordStatus::Missing => Style::default().fg(Color::Yellow), // Shouldn't happen for user
+            WordStatus::Correct => Style::default().fg(theme.success),
+            WordStatus::Wrong => Style::default().fg(theme.error),
+            WordStatus::Missing => Style::default().fg(theme.warning), // Shouldn't happen for user
         };
         spans.push(Span::styled(word.clone(), style));
     }
@@ -1753,27 +3547,41 @@ fn render_diff_user_attempt(diff: &DiffResult) -> Vec<Span<'static>> {
 }
 
 /// Render actual text with missing words highlighted
-fn render_diff_actual_text(diff: &DiffResult) -> Vec<Span<'static>> {
+fn render_diff_actual_text(theme: &Theme, diff: &DiffResult) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
     for (i, (word, status)) in diff.original_words.iter().enumerate() {
         if i > 0 {
             spans.push(Span::raw(" "));
         }
         let style = match status {
-            WordStatus::Correct => Style::default().fg(Color::Green),
-            WordStatus::Missing => Style::default().fg(Color::Yellow),
-            WordStatus::Wrong => Style::default().fg(Color::Red), // Shouldn't happen for original
+            WordStatus::Correct => Style::default().fg(theme.success),
+            WordStatus::Missing => Style::default().fg(theme.warning),
+            WordStatus::Wrong => Style::default().fg(theme.error), // Shouldn't happen for original
         };
         spans.push(Span::styled(word.clone(), style));
     }
     spans
 }
 
+/// Format each verse of a passage on its own numbered paragraph
+fn format_passage(state: &crate::app::FocusState, transform: impl Fn(&str) -> String) -> String {
+    state
+        .passage
+        .iter()
+        .map(|v| format!("{}  {}", v.verse_number, transform(&v.scripture_text)))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 /// Render text for memorization mode (simple version for Progressive mode)
 fn render_memorize_text(state: &crate::app::FocusState) -> String {
     match state.memorize_mode {
         MemorizeMode::Progressive => {
-            apply_progressive_hiding(&state.current_verse.scripture_text, state.memorize_level)
+            if state.is_passage() {
+                format_passage(state, |text| apply_progressive_hiding(text, state.memorize_level))
+            } else {
+                apply_progressive_hiding(&state.current_verse.scripture_text, state.memorize_level)
+            }
         }
         MemorizeMode::Flashcard => {
             match state.flashcard_phase {
@@ -1786,13 +3594,53 @@ fn render_memorize_text(state: &crate::app::FocusState) -> String {
                 }
                 FlashcardPhase::Revealed => {
                     // This is handled specially in render_focus_screen for diff display
-                    state.current_verse.scripture_text.clone()
+                    if state.is_passage() {
+                        format_passage(state, |text| text.to_string())
+                    } else {
+                        state.current_verse.scripture_text.clone()
+                    }
+                }
+            }
+        }
+        MemorizeMode::FirstLetter => {
+            let show_full = state.first_letter_peek || state.flashcard_phase == FlashcardPhase::Revealed;
+            let transform = |text: &str| {
+                if show_full {
+                    text.to_string()
+                } else {
+                    apply_first_letters(text)
                 }
+            };
+            if state.is_passage() {
+                format_passage(state, transform)
+            } else {
+                transform(&state.current_verse.scripture_text)
             }
         }
     }
 }
 
+/// Reduce each word to its first letter, keeping punctuation visible
+/// e.g. "And it came to pass," -> "A i c t p,"
+fn apply_first_letters(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let mut seen_letter = false;
+            word.chars()
+                .filter(|c| {
+                    if !c.is_alphabetic() {
+                        return true;
+                    }
+                    let keep = !seen_letter;
+                    seen_letter = true;
+                    keep
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Apply progressive word hiding based on difficulty level
 /// Uses deterministic hash so words hidden at level N stay hidden at level N+1
 /// Level 0: Full text
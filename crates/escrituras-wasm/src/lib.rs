@@ -0,0 +1,85 @@
+//! Escrituras for the browser
+//!
+//! wasm-bindgen wrappers over escrituras-core, so a web reader parses
+//! references, searches, and copies verses exactly as the TUI does. The page
+//! fetches `lds-scriptures-json.txt` itself and hands its text to
+//! `Scriptures`; nothing here touches files, the study data store, or the
+//! network. Search is keyword only, since embedding a query needs the
+//! embedding model. Build with `wasm-pack build crates/escrituras-wasm --target web`.
+
+use escrituras_core::search::{self, SearchFilter, SearchMode};
+use escrituras_core::{CopyFormat, ScriptureDb, Title};
+use wasm_bindgen::prelude::*;
+
+/// The scriptures, loaded once and queried from JavaScript
+#[wasm_bindgen]
+pub struct Scriptures {
+    db: ScriptureDb,
+}
+
+#[wasm_bindgen]
+impl Scriptures {
+    /// Load the scripture JSON (the text of `lds-scriptures-json.txt`)
+    #[wasm_bindgen(constructor)]
+    pub fn new(json: &str) -> Result<Scriptures, JsError> {
+        Ok(Self { db: ScriptureDb::from_json(json)? })
+    }
+
+    /// Volumes in canonical order
+    pub fn volumes(&self) -> Vec<String> {
+        self.db.get_volumes().iter().map(Title::to_string).collect()
+    }
+
+    /// Books in a volume, in canonical order
+    pub fn books(&self, volume: &str) -> Vec<String> {
+        self.db.get_books_for_volume(volume).into_iter().map(String::from).collect()
+    }
+
+    /// Chapter numbers in a book
+    pub fn chapters(&self, book: &str) -> Vec<i32> {
+        self.db.get_chapters_for_book(book)
+    }
+
+    /// The verses of a chapter, as objects with the scripture JSON's fields
+    pub fn chapter(&self, book: &str, chapter: i32) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.db.get_verses_for_chapter(book, chapter))?)
+    }
+
+    /// The verses a typed reference names ("Alma 32:21-23", "moro 10:4",
+    /// "dc 76"), or an empty list when it doesn't name any
+    pub fn lookup(&self, reference: &str) -> Result<JsValue, JsError> {
+        let verses = self.db.parse_reference(reference).map(|range| self.db.get_verses_in_range(&range)).unwrap_or_default();
+        Ok(serde_wasm_bindgen::to_value(&verses)?)
+    }
+
+    /// A typed reference written out in full ("moro 10:4" is "Moroni 10:4"),
+    /// or undefined when it doesn't name any verses
+    #[wasm_bindgen(js_name = parseReference)]
+    pub fn parse_reference(&self, input: &str) -> Option<String> {
+        self.db.parse_reference(input).map(|range| range.display_title())
+    }
+
+    /// Every reference in a passage of text, written out in full
+    pub fn references(&self, text: &str) -> Vec<String> {
+        self.db.extract_scripture_references(text).iter().map(|range| range.display_title()).collect()
+    }
+
+    /// Keyword search, optionally only in a volume or a book. Hits are
+    /// objects with the verse (`scripture`), `source`, and `score`.
+    pub fn search(&self, query: &str, limit: usize, volume: Option<String>, book: Option<String>) -> Result<JsValue, JsError> {
+        let filter = SearchFilter { volume, book, mode: SearchMode::Keyword, limit };
+        Ok(serde_wasm_bindgen::to_value(&search::search(&self.db, None, query, &filter))?)
+    }
+
+    /// A passage as it's copied everywhere else, in a `copy_format` such as
+    /// "reference", "plain", or "markdown"
+    pub fn copy(&self, reference: &str, format: &str) -> Result<String, JsError> {
+        let format = CopyFormat::from_name(format).ok_or_else(|| {
+            let names: Vec<&str> = CopyFormat::all().iter().map(|f| f.name()).collect();
+            JsError::new(&format!("Unknown format '{}'. Use {}", format, names.join(", ")))
+        })?;
+        let range = self.db.parse_reference(reference).ok_or_else(|| JsError::new(&format!("No verses found for '{}'", reference)))?;
+        let verses: Vec<_> = self.db.get_verses_in_range(&range).into_iter().cloned().collect();
+        Ok(format.format(&verses))
+    }
+}
@@ -15,13 +15,13 @@ case "$OS" in
   *) echo "Unsupported OS: $OS"; exit 1 ;;
 esac
 
-# Set config directory to match Rust dirs::config_dir()
+# Set the data directory to match Rust dirs::data_dir()
 if [ "$OS" = "macos" ]; then
-  CONFIG_DIR="${HOME}/Library/Application Support/escrituras"
+  SHARE_DIR="${HOME}/Library/Application Support/escrituras"
 else
-  CONFIG_DIR="${XDG_CONFIG_HOME:-$HOME/.config}/escrituras"
+  SHARE_DIR="${XDG_DATA_HOME:-$HOME/.local/share}/escrituras"
 fi
-DATA_DIR="$CONFIG_DIR/data"
+DATA_DIR="$SHARE_DIR/data"
 
 case "$ARCH" in
   x86_64) ARCH="x86_64" ;;
@@ -60,10 +60,10 @@ chmod +x "$INSTALL_DIR/$BINARY"
 
 # Install scripture data
 if [ -d "$TEMP_DIR/lds-scriptures-2020.12.08" ]; then
-  echo "Installing scripture data to $CONFIG_DIR..."
-  mkdir -p "$CONFIG_DIR"
-  rm -rf "$CONFIG_DIR/lds-scriptures-2020.12.08"
-  mv "$TEMP_DIR/lds-scriptures-2020.12.08" "$CONFIG_DIR/"
+  echo "Installing scripture data to $SHARE_DIR..."
+  mkdir -p "$SHARE_DIR"
+  rm -rf "$SHARE_DIR/lds-scriptures-2020.12.08"
+  mv "$TEMP_DIR/lds-scriptures-2020.12.08" "$SHARE_DIR/"
 fi
 
 # Install embeddings for semantic search
@@ -91,7 +91,7 @@ if ! echo ":$PATH:" | grep -qF ":$INSTALL_DIR:"; then
   echo "  export PATH=\"\$PATH:$INSTALL_DIR\""
   echo ""
 fi
-echo "Run '$BINARY' to start, or '$BINARY --mcp' for MCP server mode."
+echo "Run '$BINARY' to start, or '$BINARY mcp' for MCP server mode."
 
 # Offer to install Claude Code skills
 echo ""